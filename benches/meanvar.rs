@@ -1,5 +1,5 @@
 use diol::prelude::*;
-use faer::{prelude::*, ComplexField};
+use faer::{prelude::*, ComplexField, Parallelism};
 
 fn args() -> Vec<List![usize, usize]> {
     (5..12).map(|i| 1 << i).map(|n| list![n, n]).collect()
@@ -14,6 +14,7 @@ fn col_mean_propagate<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![us
             out.as_mut(),
             a.as_ref(),
             faer::stats::NanHandling::Propagate,
+            Parallelism::None,
         );
     })
 }
@@ -23,7 +24,12 @@ fn col_mean_ignore<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![usize
     let mut out = Col::zeros(m);
 
     bencher.bench(|| {
-        faer::stats::col_mean(out.as_mut(), a.as_ref(), faer::stats::NanHandling::Ignore);
+        faer::stats::col_mean(
+            out.as_mut(),
+            a.as_ref(),
+            faer::stats::NanHandling::Ignore,
+            Parallelism::None,
+        );
     })
 }
 
@@ -36,6 +42,7 @@ fn row_mean_propagate<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![us
             out.as_mut(),
             a.as_ref(),
             faer::stats::NanHandling::Propagate,
+            Parallelism::None,
         );
     })
 }
@@ -45,7 +52,12 @@ fn row_mean_ignore<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![usize
     let mut out = Row::zeros(n);
 
     bencher.bench(|| {
-        faer::stats::row_mean(out.as_mut(), a.as_ref(), faer::stats::NanHandling::Ignore);
+        faer::stats::row_mean(
+            out.as_mut(),
+            a.as_ref(),
+            faer::stats::NanHandling::Ignore,
+            Parallelism::None,
+        );
     })
 }
 
@@ -60,6 +72,7 @@ fn col_varm_propagate<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![us
             a.as_ref(),
             mean.as_ref(),
             faer::stats::NanHandling::Propagate,
+            Parallelism::None,
         );
     })
 }
@@ -75,6 +88,7 @@ fn row_varm_propagate<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![us
             a.as_ref(),
             mean.as_ref(),
             faer::stats::NanHandling::Propagate,
+            Parallelism::None,
         );
     })
 }
@@ -90,6 +104,7 @@ fn col_varm_ignore<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![usize
             a.as_ref(),
             mean.as_ref(),
             faer::stats::NanHandling::Ignore,
+            Parallelism::None,
         );
     })
 }
@@ -105,6 +120,7 @@ fn row_varm_ignore<E: ComplexField>(bencher: Bencher, unlist![m, n]: List![usize
             a.as_ref(),
             mean.as_ref(),
             faer::stats::NanHandling::Ignore,
+            Parallelism::None,
         );
     })
 }
@@ -0,0 +1,265 @@
+use crate::{
+    col::{Col, ColMut, ColRef},
+    mat::{Mat, MatMut, MatRef},
+    row::{Row, RowMut, RowRef},
+    sparse::{SparseColMat, SparseColMatRef, SparseRowMat, SparseRowMatRef},
+    utils::slice::SliceGroup,
+    ComplexField, Index, RealField,
+};
+use ::approx::{AbsDiffEq, RelativeEq, UlpsEq};
+use reborrow::*;
+
+#[inline]
+fn abs_diff_eq<E: ComplexField>(a: E, b: E, epsilon: E::Real) -> bool {
+    a.faer_sub(b).faer_abs() <= epsilon
+}
+
+// same convention as the implementations of the `approx` crate for floating point values
+#[inline]
+fn relative_eq<E: ComplexField>(a: E, b: E, epsilon: E::Real, max_relative: E::Real) -> bool {
+    // handles the infinite values
+    if a == b {
+        return true;
+    }
+    let diff = a.faer_sub(b).faer_abs();
+    if diff <= epsilon {
+        return true;
+    }
+    let abs_a = a.faer_abs();
+    let abs_b = b.faer_abs();
+    let largest = if abs_a > abs_b { abs_a } else { abs_b };
+    diff <= largest.faer_mul(max_relative)
+}
+
+fn dense_all<E: ComplexField>(
+    lhs: MatRef<'_, E>,
+    rhs: MatRef<'_, E>,
+    mut eq: impl FnMut(E, E) -> bool,
+) -> bool {
+    if (lhs.nrows(), lhs.ncols()) != (rhs.nrows(), rhs.ncols()) {
+        return false;
+    }
+    for j in 0..lhs.ncols() {
+        for i in 0..lhs.nrows() {
+            if !eq(lhs.read(i, j), rhs.read(i, j)) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+// adds the entries of the `j`-th column of `mat` to `work`, and records the rows that were
+// touched for the first time
+fn scatter<I: Index, E: ComplexField>(
+    mat: SparseColMatRef<'_, I, E>,
+    j: usize,
+    mut work: ColMut<'_, E>,
+    touched: &mut [bool],
+    rows: &mut alloc::vec::Vec<usize>,
+) {
+    let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+    for (k, i) in mat.row_indices_of_col(j).enumerate() {
+        work.write(i, work.read(i).faer_add(values.read(k)));
+        if !touched[i] {
+            touched[i] = true;
+            rows.push(i);
+        }
+    }
+}
+
+// compares the matrices entry by entry, where the entries that are not stored are zero and the
+// duplicate entries are summed, so that matrices with different sparsity patterns can be compared
+fn sparse_all<I: Index, E: ComplexField>(
+    lhs: SparseColMatRef<'_, I, E>,
+    rhs: SparseColMatRef<'_, I, E>,
+    mut eq: impl FnMut(E, E) -> bool,
+) -> bool {
+    if (lhs.nrows(), lhs.ncols()) != (rhs.nrows(), rhs.ncols()) {
+        return false;
+    }
+    let m = lhs.nrows();
+    let mut lhs_work = Col::<E>::zeros(m);
+    let mut rhs_work = Col::<E>::zeros(m);
+    let mut touched = alloc::vec![false; m];
+    let mut rows = alloc::vec::Vec::new();
+
+    for j in 0..lhs.ncols() {
+        scatter(lhs, j, lhs_work.as_mut(), &mut touched, &mut rows);
+        scatter(rhs, j, rhs_work.as_mut(), &mut touched, &mut rows);
+
+        let mut all = true;
+        for &i in &rows {
+            all = all && eq(lhs_work.read(i), rhs_work.read(i));
+            lhs_work.write(i, E::faer_zero());
+            rhs_work.write(i, E::faer_zero());
+            touched[i] = false;
+        }
+        rows.clear();
+        if !all {
+            return false;
+        }
+    }
+    true
+}
+
+macro_rules! impl_approx {
+    ($ty: ty, $all: ident, |$this: ident| $view: expr) => {
+        impl<E: ComplexField> AbsDiffEq for $ty {
+            type Epsilon = E::Real;
+
+            #[inline]
+            fn default_epsilon() -> Self::Epsilon {
+                E::Real::faer_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let view = |$this: &Self| $view;
+                $all(view(self), view(other), |a, b| abs_diff_eq(a, b, epsilon))
+            }
+        }
+
+        impl<E: ComplexField> RelativeEq for $ty {
+            #[inline]
+            fn default_max_relative() -> Self::Epsilon {
+                E::Real::faer_epsilon()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                let view = |$this: &Self| $view;
+                $all(view(self), view(other), |a, b| {
+                    relative_eq(a, b, epsilon, max_relative)
+                })
+            }
+        }
+
+        impl<E: ComplexField + UlpsEq<Epsilon = E::Real>> UlpsEq for $ty {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                E::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                let view = |$this: &Self| $view;
+                $all(view(self), view(other), |a, b| {
+                    UlpsEq::ulps_eq(&a, &b, epsilon, max_ulps)
+                })
+            }
+        }
+    };
+}
+
+impl_approx!(MatRef<'_, E>, dense_all, |this| *this);
+impl_approx!(MatMut<'_, E>, dense_all, |this| this.rb());
+impl_approx!(Mat<E>, dense_all, |this| this.as_ref());
+impl_approx!(ColRef<'_, E>, dense_all, |this| this.as_2d());
+impl_approx!(ColMut<'_, E>, dense_all, |this| this.rb().as_2d());
+impl_approx!(Col<E>, dense_all, |this| this.as_2d());
+impl_approx!(RowRef<'_, E>, dense_all, |this| this.as_2d());
+impl_approx!(RowMut<'_, E>, dense_all, |this| this.rb().as_2d());
+impl_approx!(Row<E>, dense_all, |this| this.as_2d());
+
+macro_rules! impl_approx_sparse {
+    ($ty: ty, |$this: ident| $view: expr) => {
+        impl<I: Index, E: ComplexField> AbsDiffEq for $ty {
+            type Epsilon = E::Real;
+
+            #[inline]
+            fn default_epsilon() -> Self::Epsilon {
+                E::Real::faer_epsilon()
+            }
+
+            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                let view = |$this: &Self| $view;
+                sparse_all(view(self), view(other), |a, b| abs_diff_eq(a, b, epsilon))
+            }
+        }
+
+        impl<I: Index, E: ComplexField> RelativeEq for $ty {
+            #[inline]
+            fn default_max_relative() -> Self::Epsilon {
+                E::Real::faer_epsilon()
+            }
+
+            fn relative_eq(
+                &self,
+                other: &Self,
+                epsilon: Self::Epsilon,
+                max_relative: Self::Epsilon,
+            ) -> bool {
+                let view = |$this: &Self| $view;
+                sparse_all(view(self), view(other), |a, b| {
+                    relative_eq(a, b, epsilon, max_relative)
+                })
+            }
+        }
+
+        impl<I: Index, E: ComplexField + UlpsEq<Epsilon = E::Real>> UlpsEq for $ty {
+            #[inline]
+            fn default_max_ulps() -> u32 {
+                E::default_max_ulps()
+            }
+
+            fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                let view = |$this: &Self| $view;
+                sparse_all(view(self), view(other), |a, b| {
+                    UlpsEq::ulps_eq(&a, &b, epsilon, max_ulps)
+                })
+            }
+        }
+    };
+}
+
+impl_approx_sparse!(SparseColMatRef<'_, I, E>, |this| *this);
+impl_approx_sparse!(SparseColMat<I, E>, |this| this.as_ref());
+impl_approx_sparse!(SparseRowMatRef<'_, I, E>, |this| this.transpose());
+impl_approx_sparse!(SparseRowMat<I, E>, |this| this.as_ref().transpose());
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64};
+    use ::approx::{assert_relative_eq, assert_ulps_eq};
+
+    #[test]
+    fn test_approx() {
+        let a = Mat::from_fn(4, 3, |i, j| (i + 2 * j) as f64 + 0.1);
+        let mut b = a.clone();
+        b.write(2, 1, b.read(2, 1) * (1.0 + f64::EPSILON));
+        assert!(a != b);
+        assert_relative_eq!(a, b);
+        assert_ulps_eq!(a, b);
+        assert_relative_eq!(a.col(1), b.col(1));
+        assert_relative_eq!(a.row(2), b.row(2));
+        assert!(a.abs_diff_eq(&b, 0.0) == false);
+        assert!(a.abs_diff_eq(&Mat::zeros(4, 2), 1e10) == false);
+
+        let c = Mat::from_fn(2, 2, |i, j| c64::new(i as f64, j as f64));
+        let d = Mat::from_fn(2, 2, |i, j| c64::new(i as f64 + 1e-12, j as f64));
+        assert!(c.abs_diff_eq(&d, 1e-10));
+        assert!(!c.abs_diff_eq(&d, 1e-14));
+
+        // the entries that are not stored are zero, and duplicate entries are summed
+        let s = SparseColMat::<usize, f64>::try_new_from_triplets(
+            3,
+            2,
+            &[(0, 0, 1.0), (2, 1, 2.0), (1, 1, 0.0)],
+        )
+        .unwrap();
+        let t = SparseColMat::<usize, f64>::try_new_from_triplets(
+            3,
+            2,
+            &[(0, 0, 0.5), (0, 0, 0.5), (2, 1, 2.0)],
+        )
+        .unwrap();
+        assert_relative_eq!(s, t);
+        assert_relative_eq!(s.to_row_major().unwrap(), t.to_row_major().unwrap());
+        let u = SparseColMat::<usize, f64>::try_new_from_triplets(3, 2, &[(0, 0, 1.0)]).unwrap();
+        assert!(!s.relative_eq(&u, 1e-10, 1e-10));
+    }
+}
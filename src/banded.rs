@@ -0,0 +1,812 @@
+//! Banded matrices, stored in the LAPACK band layout.
+//!
+//! A matrix with lower bandwidth $k_l$ and upper bandwidth $k_u$ only has nonzero entries
+//! $a_{ij}$ for $j - k_u \leq i \leq j + k_l$. It is stored in a dense matrix with
+//! $k_l + k_u + 1$ rows and the same number of columns, where the entry $a_{ij}$ is stored at
+//! row $k_u + i - j$ of column $j$, so that the diagonals of the matrix are the rows of the
+//! storage.
+//!
+//! Such matrices arise from finite difference discretizations, spline fitting, or autoregressive
+//! models. The factorizations in this module take $\mathcal{O}(n k^2)$ operations instead of the
+//! $\mathcal{O}(n^3)$ of a dense factorization, and the solves take $\mathcal{O}(n k)$ operations
+//! per right-hand side, where $k$ is the bandwidth:
+//! - [`BandedCholesky`] for Hermitian positive definite matrices, similar to LAPACK's `xPBTRF`,
+//! - [`BandedLu`] with partial pivoting for general square matrices, similar to LAPACK's
+//!   `xGBTRF`. The pivoting increases the upper bandwidth of the $U$ factor to $k_l + k_u$.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{banded::BandedMat, mat, prelude::*};
+//!
+//! // second order finite difference matrix
+//! let n = 5;
+//! let a = BandedMat::<f64>::from_fn(n, n, 1, 1, |i, j| if i == j { 2.0 } else { -1.0 });
+//! let b = Mat::<f64>::from_fn(n, 1, |_, _| 1.0);
+//!
+//! let x = a.cholesky().unwrap().solve(&b);
+//! assert!((&a.to_dense() * &x - &b).norm_max() < 1e-12);
+//!
+//! let x = a.lu().solve(&b);
+//! assert!((&a.to_dense() * &x - &b).norm_max() < 1e-12);
+//! ```
+
+use crate::{
+    assert,
+    linalg::cholesky::llt::CholeskyError,
+    linop::{BiLinOp, LinOp},
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj, Mat, MatMut, MatRef, Parallelism,
+};
+use alloc::vec::Vec;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Banded matrix, stored in the LAPACK band layout.
+#[derive(Clone, Debug)]
+pub struct BandedMat<E: ComplexField> {
+    nrows: usize,
+    ncols: usize,
+    kl: usize,
+    ku: usize,
+    data: Mat<E>,
+}
+
+/// Cholesky decomposition of a Hermitian positive definite banded matrix.
+///
+/// The factorization is such that $A = LL^H$, where $L$ is a lower triangular banded matrix with
+/// the same lower bandwidth as $A$.
+#[derive(Clone, Debug)]
+pub struct BandedCholesky<E: ComplexField> {
+    // l_ij is stored at (i - j, j)
+    factor: Mat<E>,
+}
+
+/// LU decomposition with partial pivoting of a square banded matrix.
+///
+/// The factorization is such that $A = P_0 L_0 P_1 L_1 \dots P_{n-1} L_{n-1} U$, where $P_j$ is
+/// the transposition of the rows $j$ and $p_j$, $L_j$ is a unit lower triangular elimination
+/// matrix acting on column $j$, and $U$ is an upper triangular banded matrix with upper bandwidth
+/// $k_l + k_u$.
+#[derive(Clone, Debug)]
+pub struct BandedLu<E: ComplexField> {
+    kl: usize,
+    ku: usize,
+    // the entry (i, j) of the factors is stored at (kl + ku + i - j, j)
+    factors: Mat<E>,
+    pivots: Vec<usize>,
+}
+
+impl<E: ComplexField> BandedMat<E> {
+    /// Returns a new matrix with dimensions `(nrows, ncols)`, lower bandwidth `kl` and upper
+    /// bandwidth `ku`, filled with zeros.
+    #[inline]
+    pub fn zeros(nrows: usize, ncols: usize, kl: usize, ku: usize) -> Self {
+        Self {
+            nrows,
+            ncols,
+            kl,
+            ku,
+            data: Mat::zeros(kl + ku + 1, ncols),
+        }
+    }
+
+    /// Returns a new matrix with dimensions `(nrows, ncols)`, lower bandwidth `kl` and upper
+    /// bandwidth `ku`, where the entries inside the band are computed by calling `f(i, j)`.
+    pub fn from_fn(
+        nrows: usize,
+        ncols: usize,
+        kl: usize,
+        ku: usize,
+        f: impl FnMut(usize, usize) -> E,
+    ) -> Self {
+        let mut f = f;
+        let mut this = Self::zeros(nrows, ncols, kl, ku);
+        for j in 0..ncols {
+            for i in j.saturating_sub(ku)..Ord::min(nrows, j + kl + 1) {
+                this.data.write(ku + i - j, j, f(i, j));
+            }
+        }
+        this
+    }
+
+    /// Returns a new matrix with lower bandwidth `kl` and upper bandwidth `ku`, containing the
+    /// entries of `mat` inside the band. The entries outside the band are ignored.
+    pub fn from_dense(mat: MatRef<'_, E>, kl: usize, ku: usize) -> Self {
+        Self::from_fn(mat.nrows(), mat.ncols(), kl, ku, |i, j| mat.read(i, j))
+    }
+
+    /// Returns a new matrix, given its storage in the LAPACK band layout.
+    ///
+    /// # Panics
+    /// Panics if `data` doesn't have `kl + ku + 1` rows.
+    #[track_caller]
+    pub fn from_band_storage(nrows: usize, kl: usize, ku: usize, data: Mat<E>) -> Self {
+        assert!(data.nrows() == kl + ku + 1);
+        Self {
+            nrows,
+            ncols: data.ncols(),
+            kl,
+            ku,
+            data,
+        }
+    }
+
+    /// Returns the number of rows of the matrix.
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns of the matrix.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the lower bandwidth of the matrix.
+    #[inline]
+    pub fn lower_bandwidth(&self) -> usize {
+        self.kl
+    }
+
+    /// Returns the upper bandwidth of the matrix.
+    #[inline]
+    pub fn upper_bandwidth(&self) -> usize {
+        self.ku
+    }
+
+    /// Returns a view over the storage of the matrix in the LAPACK band layout.
+    ///
+    /// The storage entries that don't correspond to an entry of the matrix are unspecified.
+    #[inline]
+    pub fn as_band_storage(&self) -> MatRef<'_, E> {
+        self.data.as_ref()
+    }
+
+    /// Returns a mutable view over the storage of the matrix in the LAPACK band layout.
+    #[inline]
+    pub fn as_band_storage_mut(&mut self) -> MatMut<'_, E> {
+        self.data.as_mut()
+    }
+
+    #[inline]
+    fn in_band(&self, i: usize, j: usize) -> bool {
+        i + self.ku >= j && i <= j + self.kl
+    }
+
+    /// Returns the entry at the given indices, which is zero outside the band.
+    ///
+    /// # Panics
+    /// Panics if the indices are out of bounds.
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        assert!(all(i < self.nrows, j < self.ncols));
+        if self.in_band(i, j) {
+            self.data.read(self.ku + i - j, j)
+        } else {
+            E::faer_zero()
+        }
+    }
+
+    /// Writes `value` to the entry at the given indices.
+    ///
+    /// # Panics
+    /// Panics if the indices are out of bounds, or outside the band.
+    #[track_caller]
+    pub fn write(&mut self, i: usize, j: usize, value: E) {
+        assert!(all(i < self.nrows, j < self.ncols, self.in_band(i, j)));
+        self.data.write(self.ku + i - j, j, value);
+    }
+
+    /// Returns the matrix as a dense matrix.
+    pub fn to_dense(&self) -> Mat<E> {
+        let mut mat = Mat::zeros(self.nrows, self.ncols);
+        for j in 0..self.ncols {
+            for i in j.saturating_sub(self.ku)..Ord::min(self.nrows, j + self.kl + 1) {
+                mat.write(i, j, self.data.read(self.ku + i - j, j));
+            }
+        }
+        mat
+    }
+
+    /// Computes `out = self * rhs`, or `out = conj(self) * rhs`.
+    fn matmul_with_conj(&self, out: MatMut<'_, E>, rhs: MatRef<'_, E>, conj: Conj) {
+        let mut out = out;
+        assert!(all(
+            out.nrows() == self.nrows,
+            rhs.nrows() == self.ncols,
+            out.ncols() == rhs.ncols(),
+        ));
+        out.fill_zero();
+        for k in 0..rhs.ncols() {
+            for j in 0..self.ncols {
+                let b = rhs.read(j, k);
+                for i in j.saturating_sub(self.ku)..Ord::min(self.nrows, j + self.kl + 1) {
+                    let mut a = self.data.read(self.ku + i - j, j);
+                    if conj == Conj::Yes {
+                        a = a.faer_conj();
+                    }
+                    out.write(i, k, out.read(i, k).faer_add(a.faer_mul(b)));
+                }
+            }
+        }
+    }
+
+    /// Computes `out = transpose(self) * rhs`, or `out = adjoint(self) * rhs`.
+    fn transpose_matmul_with_conj(&self, out: MatMut<'_, E>, rhs: MatRef<'_, E>, conj: Conj) {
+        let mut out = out;
+        assert!(all(
+            out.nrows() == self.ncols,
+            rhs.nrows() == self.nrows,
+            out.ncols() == rhs.ncols(),
+        ));
+        for k in 0..rhs.ncols() {
+            for j in 0..self.ncols {
+                let mut acc = E::faer_zero();
+                for i in j.saturating_sub(self.ku)..Ord::min(self.nrows, j + self.kl + 1) {
+                    let mut a = self.data.read(self.ku + i - j, j);
+                    if conj == Conj::Yes {
+                        a = a.faer_conj();
+                    }
+                    acc = acc.faer_add(a.faer_mul(rhs.read(i, k)));
+                }
+                out.write(j, k, acc);
+            }
+        }
+    }
+
+    /// Returns the Cholesky decomposition of `self`, which must be Hermitian positive definite.
+    ///
+    /// Only the diagonal and the lower band are accessed.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    #[track_caller]
+    pub fn cholesky(&self) -> Result<BandedCholesky<E>, CholeskyError> {
+        BandedCholesky::try_new(self)
+    }
+
+    /// Returns the LU decomposition of `self` with partial pivoting.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    #[track_caller]
+    pub fn lu(&self) -> BandedLu<E> {
+        BandedLu::new(self)
+    }
+}
+
+impl<E: ComplexField> BandedCholesky<E> {
+    /// Returns the Cholesky decomposition of `mat`, which must be Hermitian positive definite.
+    ///
+    /// Only the diagonal and the lower band of `mat` are accessed.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn try_new(mat: &BandedMat<E>) -> Result<Self, CholeskyError> {
+        let n = mat.nrows();
+        assert!(mat.ncols() == n);
+        let kd = mat.kl;
+
+        let mut l = Mat::<E>::zeros(kd + 1, n);
+        for j in 0..n {
+            for i in j..Ord::min(n, j + kd + 1) {
+                l.write(i - j, j, mat.data.read(mat.ku + i - j, j));
+            }
+        }
+
+        for j in 0..n {
+            let d = l.read(0, j).faer_real();
+            if d <= E::Real::faer_zero() || d.faer_is_nan() {
+                return Err(CholeskyError {
+                    non_positive_definite_minor: j + 1,
+                });
+            }
+            let d = d.faer_sqrt();
+            l.write(0, j, E::faer_from_real(d));
+            let d_inv = d.faer_inv();
+
+            let end = Ord::min(n, j + kd + 1);
+            for i in j + 1..end {
+                l.write(i - j, j, l.read(i - j, j).faer_scale_real(d_inv));
+            }
+
+            // trailing update, restricted to the band
+            for c in j + 1..end {
+                let l_cj = l.read(c - j, j).faer_conj();
+                for r in c..end {
+                    let update = l.read(r - j, j).faer_mul(l_cj);
+                    l.write(r - c, c, l.read(r - c, c).faer_sub(update));
+                }
+            }
+        }
+
+        Ok(Self { factor: l })
+    }
+
+    /// Returns the dimension of the decomposed matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.factor.ncols()
+    }
+
+    /// Returns the bandwidth of the factor $L$.
+    #[inline]
+    pub fn bandwidth(&self) -> usize {
+        self.factor.nrows() - 1
+    }
+
+    /// Returns the factor $L$ as a banded matrix.
+    pub fn compute_l(&self) -> BandedMat<E> {
+        BandedMat::from_band_storage(self.dim(), self.bandwidth(), 0, self.factor.clone())
+    }
+
+    fn l(&self, i: usize, j: usize, conj: Conj) -> E {
+        let value = self.factor.read(i - j, j);
+        if conj == Conj::Yes {
+            value.faer_conj()
+        } else {
+            value
+        }
+    }
+
+    fn solve_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        let n = self.dim();
+        let kd = self.bandwidth();
+        assert!(rhs.nrows() == n);
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            // L y = b
+            for j in 0..n {
+                let xj = x.read(j).faer_mul(self.l(j, j, conj).faer_inv());
+                x.write(j, xj);
+                for i in j + 1..Ord::min(n, j + kd + 1) {
+                    x.write(i, x.read(i).faer_sub(self.l(i, j, conj).faer_mul(xj)));
+                }
+            }
+            // Lᴴ x = y
+            for j in (0..n).rev() {
+                let mut xj = x.read(j);
+                for i in j + 1..Ord::min(n, j + kd + 1) {
+                    xj = xj.faer_sub(self.l(i, j, conj).faer_conj().faer_mul(x.read(i)));
+                }
+                x.write(j, xj.faer_mul(self.l(j, j, conj).faer_conj().faer_inv()));
+            }
+        }
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for BandedCholesky<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        // Aᵀ = conj(A), since A is hermitian
+        self.solve_impl(rhs, conj.compose(Conj::Yes));
+    }
+}
+
+impl<E: ComplexField> BandedLu<E> {
+    /// Returns the LU decomposition of `mat` with partial pivoting.
+    ///
+    /// If `mat` is singular, the decomposition is still computed, but the solves return
+    /// non-finite values.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn new(mat: &BandedMat<E>) -> Self {
+        let n = mat.nrows();
+        assert!(mat.ncols() == n);
+        let kl = mat.kl;
+        let ku = mat.ku;
+        let kv = kl + ku;
+
+        // the entry (i, j) is stored at (kv + i - j, j)
+        let mut a = Mat::<E>::zeros(2 * kl + ku + 1, n);
+        for j in 0..n {
+            for i in j.saturating_sub(ku)..Ord::min(n, j + kl + 1) {
+                a.write(kv + i - j, j, mat.data.read(ku + i - j, j));
+            }
+        }
+
+        let mut pivots = alloc::vec![0usize; n];
+        // last column of the nonzero part of U
+        let mut ju = 0usize;
+        for j in 0..n {
+            let km = Ord::min(kl, n - 1 - j);
+
+            let mut p = j;
+            let mut max = a.read(kv, j).faer_abs();
+            for i in j + 1..=j + km {
+                let abs = a.read(kv + i - j, j).faer_abs();
+                if abs > max {
+                    max = abs;
+                    p = i;
+                }
+            }
+            pivots[j] = p;
+            if max == E::Real::faer_zero() {
+                // singular matrix, nothing to eliminate
+                continue;
+            }
+
+            ju = Ord::max(ju, Ord::min(p + ku, n - 1));
+            if p != j {
+                for c in j..=ju {
+                    let x = a.read(kv + j - c, c);
+                    let y = a.read(kv + p - c, c);
+                    a.write(kv + j - c, c, y);
+                    a.write(kv + p - c, c, x);
+                }
+            }
+
+            let pivot_inv = a.read(kv, j).faer_inv();
+            for i in j + 1..=j + km {
+                a.write(kv + i - j, j, a.read(kv + i - j, j).faer_mul(pivot_inv));
+            }
+            for c in j + 1..=ju {
+                let u_jc = a.read(kv + j - c, c);
+                for i in j + 1..=j + km {
+                    let update = a.read(kv + i - j, j).faer_mul(u_jc);
+                    a.write(kv + i - c, c, a.read(kv + i - c, c).faer_sub(update));
+                }
+            }
+        }
+
+        Self {
+            kl,
+            ku,
+            factors: a,
+            pivots,
+        }
+    }
+
+    /// Returns the dimension of the decomposed matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.factors.ncols()
+    }
+
+    /// Returns the row interchanges, where the row `j` was interchanged with the row
+    /// `pivots[j]` at step `j` of the elimination.
+    #[inline]
+    pub fn pivots(&self) -> &[usize] {
+        &self.pivots
+    }
+
+    /// Returns the factor $U$ as a banded matrix with upper bandwidth $k_l + k_u$.
+    pub fn compute_u(&self) -> BandedMat<E> {
+        let kv = self.kl + self.ku;
+        BandedMat::from_band_storage(
+            self.dim(),
+            0,
+            kv,
+            self.factors.as_ref().subrows(0, kv + 1).to_owned(),
+        )
+    }
+
+    fn entry(&self, i: usize, j: usize, conj: Conj) -> E {
+        let value = self.factors.read(self.kl + self.ku + i - j, j);
+        if conj == Conj::Yes {
+            value.faer_conj()
+        } else {
+            value
+        }
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for BandedLu<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        let n = self.dim();
+        let kl = self.kl;
+        let kv = self.kl + self.ku;
+        assert!(rhs.nrows() == n);
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            // L y = P b, applying the interchanges as they happened during the factorization
+            for j in 0..n {
+                let p = self.pivots[j];
+                if p != j {
+                    let (xj, xp) = (x.read(j), x.read(p));
+                    x.write(j, xp);
+                    x.write(p, xj);
+                }
+                let xj = x.read(j);
+                for i in j + 1..Ord::min(n, j + kl + 1) {
+                    x.write(i, x.read(i).faer_sub(self.entry(i, j, conj).faer_mul(xj)));
+                }
+            }
+            // U x = y
+            for j in (0..n).rev() {
+                let xj = x.read(j).faer_mul(self.entry(j, j, conj).faer_inv());
+                x.write(j, xj);
+                for i in j.saturating_sub(kv)..j {
+                    x.write(i, x.read(i).faer_sub(self.entry(i, j, conj).faer_mul(xj)));
+                }
+            }
+        }
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        let n = self.dim();
+        let kl = self.kl;
+        let kv = self.kl + self.ku;
+        assert!(rhs.nrows() == n);
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            // Uᵀ y = b
+            for j in 0..n {
+                let mut xj = x.read(j);
+                for i in j.saturating_sub(kv)..j {
+                    xj = xj.faer_sub(self.entry(i, j, conj).faer_mul(x.read(i)));
+                }
+                x.write(j, xj.faer_mul(self.entry(j, j, conj).faer_inv()));
+            }
+            // Lᵀ Pᵀ x = y, undoing the interchanges in reverse order
+            for j in (0..n).rev() {
+                let mut xj = x.read(j);
+                for i in j + 1..Ord::min(n, j + kl + 1) {
+                    xj = xj.faer_sub(self.entry(i, j, conj).faer_mul(x.read(i)));
+                }
+                x.write(j, xj);
+                let p = self.pivots[j];
+                if p != j {
+                    let (xj, xp) = (x.read(j), x.read(p));
+                    x.write(j, xp);
+                    x.write(p, xj);
+                }
+            }
+        }
+    }
+}
+
+impl<E: ComplexField> LinOp<E> for BandedMat<E> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        self.matmul_with_conj(out, rhs, Conj::No);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        self.matmul_with_conj(out, rhs, Conj::Yes);
+    }
+}
+
+impl<E: ComplexField> BiLinOp<E> for BandedMat<E> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        self.transpose_matmul_with_conj(out, rhs, Conj::No);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        self.transpose_matmul_with_conj(out, rhs, Conj::Yes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, linalg::solvers::SpSolver};
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    #[test]
+    fn test_banded_storage() {
+        let a = BandedMat::<f64>::from_fn(5, 4, 2, 1, |i, j| (10 * i + j) as f64);
+        let dense = a.to_dense();
+        for i in 0..5 {
+            for j in 0..4 {
+                let expected = if i + 1 >= j && i <= j + 2 {
+                    (10 * i + j) as f64
+                } else {
+                    0.0
+                };
+                assert!(dense.read(i, j) == expected);
+                assert!(a.read(i, j) == expected);
+            }
+        }
+        assert!(a.as_band_storage().nrows() == 4);
+        let b = BandedMat::from_dense(dense.as_ref(), 2, 1);
+        assert!(b.to_dense() == dense);
+    }
+
+    #[test]
+    fn test_banded_matmul() {
+        let a = BandedMat::<c64>::from_fn(7, 6, 1, 2, |_, _| random_c64());
+        let dense = a.to_dense();
+        let x = Mat::<c64>::from_fn(6, 3, |_, _| random_c64());
+        let y = Mat::<c64>::from_fn(7, 3, |_, _| random_c64());
+
+        let mut out = Mat::<c64>::zeros(7, 3);
+        a.apply(
+            out.as_mut(),
+            x.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut []),
+        );
+        assert!((&out - &dense * &x).norm_max() < 1e-12);
+        a.conj_apply(
+            out.as_mut(),
+            x.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut []),
+        );
+        assert!((&out - dense.conjugate() * &x).norm_max() < 1e-12);
+
+        let mut out = Mat::<c64>::zeros(6, 3);
+        a.transpose_apply(
+            out.as_mut(),
+            y.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut []),
+        );
+        assert!((&out - dense.transpose() * &y).norm_max() < 1e-12);
+        a.adjoint_apply(
+            out.as_mut(),
+            y.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut []),
+        );
+        assert!((&out - dense.adjoint() * &y).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn test_banded_cholesky() {
+        let n = 40;
+        let kd = 3;
+        let b = BandedMat::<c64>::from_fn(n, n, kd, 0, |_, _| random_c64());
+        // diagonally dominant hermitian matrix
+        let mut a = BandedMat::<c64>::from_fn(n, n, kd, kd, |i, j| {
+            if i >= j {
+                b.read(i, j)
+            } else {
+                b.read(j, i).faer_conj()
+            }
+        });
+        for i in 0..n {
+            a.write(i, i, c64::new(2.0 * kd as f64 + 1.0, 0.0));
+        }
+        let dense = a.to_dense();
+
+        let chol = a.cholesky().unwrap();
+        let l = chol.compute_l().to_dense();
+        assert!((&l * l.adjoint() - &dense).norm_max() < 1e-12);
+
+        let rhs = Mat::<c64>::from_fn(n, 2, |_, _| random_c64());
+        for (x, op) in [
+            (chol.solve(&rhs), dense.clone()),
+            (chol.solve_conj(&rhs), dense.conjugate().to_owned()),
+            (chol.solve_transpose(&rhs), dense.transpose().to_owned()),
+            (chol.solve_conj_transpose(&rhs), dense.adjoint().to_owned()),
+        ] {
+            assert!((&op * &x - &rhs).norm_max() < 1e-10);
+        }
+
+        a.write(5, 5, c64::new(-1.0, 0.0));
+        assert!(a.cholesky().unwrap_err().non_positive_definite_minor == 6);
+    }
+
+    #[test]
+    fn test_banded_lu() {
+        for (n, kl, ku) in [(1, 0, 0), (10, 0, 2), (10, 3, 0), (50, 2, 3), (30, 5, 1)] {
+            let a = BandedMat::<c64>::from_fn(n, n, kl, ku, |_, _| random_c64());
+            let dense = a.to_dense();
+            let lu = a.lu();
+            assert!(lu.compute_u().upper_bandwidth() == kl + ku);
+
+            let rhs = Mat::<c64>::from_fn(n, 3, |_, _| random_c64());
+            for (x, op) in [
+                (lu.solve(&rhs), dense.clone()),
+                (lu.solve_conj(&rhs), dense.conjugate().to_owned()),
+                (lu.solve_transpose(&rhs), dense.transpose().to_owned()),
+                (lu.solve_conj_transpose(&rhs), dense.adjoint().to_owned()),
+            ] {
+                assert!((&op * &x - &rhs).norm_max() < 1e-8);
+            }
+        }
+    }
+
+    #[test]
+    fn test_banded_lu_pivoting() {
+        // zero diagonal, which requires pivoting
+        let n = 6;
+        let a = BandedMat::<f64>::from_fn(n, n, 1, 1, |i, j| if i == j { 0.0 } else { 1.0 });
+        let lu = a.lu();
+        assert!(lu.pivots()[0] == 1);
+        let rhs = Mat::<f64>::from_fn(n, 1, |i, _| i as f64);
+        let x = lu.solve(&rhs);
+        assert!((&a.to_dense() * &x - &rhs).norm_max() < 1e-12);
+    }
+}
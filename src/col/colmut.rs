@@ -549,6 +549,28 @@ impl<'a, E: Entity> ColMut<'a, E> {
         unsafe { self.into_const().reverse_rows().const_cast() }
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(self, step: usize) -> ColRef<'a, E> {
+        self.into_const().step_by_rows(step)
+    }
+
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows_mut(self, step: usize) -> Self {
+        unsafe { self.into_const().step_by_rows(step).const_cast() }
+    }
+
     /// Returns a view over the subvector starting at row `row_start`, and with number of rows
     /// `nrows`.
     ///
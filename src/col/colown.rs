@@ -148,6 +148,34 @@ impl<E: Entity> Col<E> {
         self.as_mut().as_2d_mut()
     }
 
+    /// Returns a value that formats `self` as a matrix according to the given options.
+    ///
+    /// See [`MatRef::fmt_with`] for more details.
+    #[inline]
+    pub fn fmt_with(&self, options: crate::mat::FormatOptions) -> crate::mat::MatFormat<'_, E> {
+        self.as_2d().fmt_with(options)
+    }
+
+    /// Returns a new column vector with the entries of `self` cast to `T`. The imaginary parts
+    /// are discarded when casting complex values to a real type.
+    #[inline]
+    pub fn cast<T: Entity>(&self) -> Col<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.cast_with(crate::mat::ImagPolicy::Discard)
+    }
+
+    /// Returns a new column vector with the entries of `self` cast to `T`, where `policy`
+    /// specifies how the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(&self, policy: crate::mat::ImagPolicy) -> Col<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.as_ref().cast_with(policy)
+    }
+
     /// Returns raw pointers to the element at the given index.
     #[inline(always)]
     pub fn ptr_at(&self, row: usize) -> GroupFor<E, *const E::Unit> {
@@ -696,6 +724,28 @@ impl<E: Entity> Col<E> {
         self.as_mut().reverse_rows_mut()
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(&self, step: usize) -> ColRef<'_, E> {
+        self.as_ref().step_by_rows(step)
+    }
+
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows_mut(&mut self, step: usize) -> ColMut<'_, E> {
+        self.as_mut().step_by_rows_mut(step)
+    }
+
     /// Returns a view over the subvector starting at row `row_start`, and with number of rows
     /// `nrows`.
     ///
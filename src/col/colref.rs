@@ -1,5 +1,5 @@
 use super::*;
-use crate::{assert, debug_assert, diag::DiagRef, row::RowRef};
+use crate::{assert, debug_assert, diag::DiagRef, row::RowRef, utils::DivCeil};
 
 /// Immutable view over a column vector, similar to an immutable reference to a strided
 /// [prim@slice].
@@ -122,6 +122,37 @@ impl<'a, E: Entity> ColRef<'a, E> {
         unsafe { crate::mat::from_raw_parts(self.as_ptr(), nrows, 1, row_stride, isize::MAX) }
     }
 
+    /// Returns a value that formats `self` as a matrix according to the given options.
+    ///
+    /// See [`MatRef::fmt_with`] for more details.
+    #[inline]
+    pub fn fmt_with(self, options: crate::mat::FormatOptions) -> crate::mat::MatFormat<'a, E> {
+        self.as_2d().fmt_with(options)
+    }
+
+    /// Returns a new column vector with the entries of `self` cast to `T`. The imaginary parts
+    /// are discarded when casting complex values to a real type.
+    #[inline]
+    pub fn cast<T: Entity>(self) -> Col<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.cast_with(crate::mat::ImagPolicy::Discard)
+    }
+
+    /// Returns a new column vector with the entries of `self` cast to `T`, where `policy`
+    /// specifies how the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(self, policy: crate::mat::ImagPolicy) -> Col<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        crate::zipped!(self).map(
+            #[inline(always)]
+            |crate::unzipped!(x)| x.read().cast_with(policy),
+        )
+    }
+
     /// Returns raw pointers to the element at the given index.
     #[inline(always)]
     pub fn ptr_at(self, row: usize) -> GroupFor<E, *const E::Unit> {
@@ -362,6 +393,20 @@ impl<'a, E: Entity> ColRef<'a, E> {
         unsafe { Self::__from_raw_parts(ptr, nrows, row_stride) }
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(self, step: usize) -> Self {
+        assert!(step > 0);
+        let nrows = self.nrows().msrv_div_ceil(step);
+        let row_stride = self.row_stride().wrapping_mul(step as isize);
+        unsafe { Self::__from_raw_parts(self.as_ptr(), nrows, row_stride) }
+    }
+
     /// Returns a view over the subvector starting at row `row_start`, and with number of rows
     /// `nrows`.
     ///
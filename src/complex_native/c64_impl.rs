@@ -609,6 +609,20 @@ impl core::fmt::Display for c64 {
     }
 }
 
+impl core::fmt::LowerExp for c64 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::LowerExp::fmt(&self.re, f)?;
+        let im_abs = self.im.faer_abs();
+        if self.im.is_sign_positive() {
+            f.write_str(" + ")?;
+        } else {
+            f.write_str(" - ")?;
+        }
+        core::fmt::LowerExp::fmt(&im_abs, f)?;
+        f.write_str(" * I")
+    }
+}
+
 impl ComplexField for c64 {
     type Real = f64;
     type Simd = pulp::Arch;
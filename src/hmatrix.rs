@@ -0,0 +1,807 @@
+//! Hierarchically off-diagonal low-rank (HODLR) matrices.
+//!
+//! A HODLR matrix of dimension $n$ is recursively split into a $2 \times 2$ block matrix
+//! $$A = \begin{bmatrix} A_{11} & U_{12} V_{12}^\top \\ U_{21} V_{21}^\top & A_{22}
+//! \end{bmatrix},$$
+//! where the diagonal blocks are themselves HODLR matrices, down to a leaf size below which they
+//! are stored densely, and the off-diagonal blocks are stored in low-rank form.
+//!
+//! Kernel matrices arising from integral equations or Gaussian process regression are typically
+//! well approximated by this structure once the points are ordered so that nearby indices
+//! correspond to nearby points.
+//!
+//! The off-diagonal blocks are compressed with one of the methods of [`HodlrCompression`]. By
+//! default, adaptive cross approximation (ACA) with partial pivoting is used, which only needs to
+//! evaluate $\mathcal{O}(k(m + n))$ entries of an $m\times n$ block of rank $k$, so the dense
+//! matrix is never formed. The interpolative decomposition evaluates every entry of the block, but
+//! controls the approximation error more reliably.
+//!
+//! This module is experimental, and its API may change in future releases.
+
+use crate::{
+    assert,
+    linalg::{
+        matmul::matmul_with_conj,
+        solvers::{ColPivQr, PartialPivLu, SpSolver},
+        triangular_solve::solve_upper_triangular_in_place,
+    },
+    linop::LinOp,
+    unzipped, zipped, ComplexField, Conj, Mat, MatMut, MatRef, Parallelism, RealField,
+};
+use alloc::{boxed::Box, vec::Vec};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Specifies how the off-diagonal blocks of a [`HodlrMat`] are compressed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum HodlrCompression {
+    /// Adaptive cross approximation with partial pivoting, which evaluates
+    /// $\mathcal{O}(k(m + n))$ entries of an $m\times n$ block of rank $k$. The stopping criterion
+    /// is a heuristic, which may stop early for blocks with a nonsmooth structure.
+    Aca,
+    /// Interpolative decomposition, which approximates the block by a subset of its columns,
+    /// selected with a column pivoted QR decomposition. All the $mn$ entries of the block are
+    /// evaluated, and the rank is chosen from the norm of the discarded part of the
+    /// decomposition.
+    Interpolative,
+}
+
+/// Parameters for the construction of a [`HodlrMat`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct HodlrParams<E: ComplexField> {
+    /// Diagonal blocks with a dimension smaller than or equal to this value are stored densely.
+    pub leaf_size: usize,
+    /// Relative tolerance of the low-rank approximation of the off-diagonal blocks, in the
+    /// Frobenius norm.
+    pub tolerance: E::Real,
+    /// Maximum rank of the low-rank approximation of each off-diagonal block.
+    pub max_rank: usize,
+    /// Compression method of the off-diagonal blocks.
+    pub compression: HodlrCompression,
+}
+
+impl<E: ComplexField> Default for HodlrParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            leaf_size: 64,
+            tolerance: E::Real::faer_epsilon().faer_mul(E::Real::faer_from_f64(1024.0)),
+            max_rank: usize::MAX,
+            compression: HodlrCompression::Aca,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Node<E: ComplexField> {
+    Leaf {
+        dense: Mat<E>,
+    },
+    Split {
+        mid: usize,
+        left: Box<Node<E>>,
+        right: Box<Node<E>>,
+        // top right block, equal to `u12 * v12^T`
+        u12: Mat<E>,
+        v12: Mat<E>,
+        // bottom left block, equal to `u21 * v21^T`
+        u21: Mat<E>,
+        v21: Mat<E>,
+    },
+}
+
+enum FactorNode<E: ComplexField> {
+    Leaf {
+        lu: PartialPivLu<E>,
+    },
+    Split {
+        mid: usize,
+        left: Box<FactorNode<E>>,
+        right: Box<FactorNode<E>>,
+        v12: Mat<E>,
+        v21: Mat<E>,
+        // `A11^{-1} u12`
+        w12: Mat<E>,
+        // `A22^{-1} u21`
+        w21: Mat<E>,
+        // LU factors of the capacitance matrix `I + V^T D^{-1} U`
+        capacitance: Option<PartialPivLu<E>>,
+    },
+}
+
+/// Square matrix stored in hierarchically off-diagonal low-rank form.
+#[derive(Debug)]
+pub struct HodlrMat<E: ComplexField> {
+    dim: usize,
+    root: Node<E>,
+}
+
+/// Approximate factorization of a [`HodlrMat`], that can be used to solve linear systems.
+pub struct HodlrLu<E: ComplexField> {
+    dim: usize,
+    root: FactorNode<E>,
+}
+
+#[inline]
+fn dot<E: ComplexField>(lhs: MatRef<'_, E>, rhs: MatRef<'_, E>) -> E {
+    // computes `lhs^H rhs` for two column vectors
+    let mut acc = E::faer_zero();
+    for i in 0..lhs.nrows() {
+        acc = acc.faer_add(lhs.read(i, 0).faer_conj().faer_mul(rhs.read(i, 0)));
+    }
+    acc
+}
+
+/// Computes a low-rank approximation `U V^T` of the block `f(row_start + i, col_start + j)` for
+/// `i < nrows`, `j < ncols` using adaptive cross approximation with partial pivoting.
+fn aca<E: ComplexField>(
+    f: &mut dyn FnMut(usize, usize) -> E,
+    row_start: usize,
+    col_start: usize,
+    nrows: usize,
+    ncols: usize,
+    params: HodlrParams<E>,
+) -> (Mat<E>, Mat<E>) {
+    let max_rank = Ord::min(params.max_rank, Ord::min(nrows, ncols));
+
+    let mut us = Vec::<Mat<E>>::new();
+    let mut vs = Vec::<Mat<E>>::new();
+    let mut used_rows = alloc::vec![false; nrows];
+    let mut norm2 = E::Real::faer_zero();
+
+    let mut i = 0usize;
+    while us.len() < max_rank {
+        used_rows[i] = true;
+
+        // residual of the `i`-th row
+        let mut row = Mat::<E>::from_fn(ncols, 1, |j, _| f(row_start + i, col_start + j));
+        for (u, v) in core::iter::zip(&us, &vs) {
+            let ui = u.read(i, 0);
+            zipped!(row.as_mut(), v.as_ref())
+                .for_each(|unzipped!(mut r, v)| r.write(r.read().faer_sub(ui.faer_mul(v.read()))));
+        }
+
+        let mut pivot_col = 0usize;
+        let mut pivot_abs = E::Real::faer_zero();
+        for j in 0..ncols {
+            let abs = row.read(j, 0).faer_abs();
+            if abs > pivot_abs {
+                pivot_abs = abs;
+                pivot_col = j;
+            }
+        }
+
+        if pivot_abs == E::Real::faer_zero() {
+            // the row is already well approximated, try the next unused one
+            match used_rows.iter().position(|&used| !used) {
+                Some(next) => {
+                    i = next;
+                    continue;
+                }
+                None => break,
+            }
+        }
+
+        let pivot_inv = row.read(pivot_col, 0).faer_inv();
+        zipped!(row.as_mut()).for_each(|unzipped!(mut r)| r.write(r.read().faer_mul(pivot_inv)));
+        let v = row;
+
+        // residual of the `pivot_col`-th column
+        let mut u = Mat::<E>::from_fn(nrows, 1, |k, _| f(row_start + k, col_start + pivot_col));
+        for (u_prev, v_prev) in core::iter::zip(&us, &vs) {
+            let vj = v_prev.read(pivot_col, 0);
+            zipped!(u.as_mut(), u_prev.as_ref())
+                .for_each(|unzipped!(mut u, p)| u.write(u.read().faer_sub(vj.faer_mul(p.read()))));
+        }
+
+        // update the estimate of the squared Frobenius norm of the approximation
+        let u_norm2 = u.squared_norm_l2();
+        let v_norm2 = v.squared_norm_l2();
+        let uv_norm2 = u_norm2.faer_mul(v_norm2);
+        let mut cross = E::Real::faer_zero();
+        for (u_prev, v_prev) in core::iter::zip(&us, &vs) {
+            cross = cross.faer_add(
+                dot(u_prev.as_ref(), u.as_ref())
+                    .faer_mul(dot(v_prev.as_ref(), v.as_ref()))
+                    .faer_real(),
+            );
+        }
+        norm2 = norm2.faer_add(uv_norm2).faer_add(cross.faer_add(cross));
+
+        // next row: largest entry of the new column among the rows that have not been used yet
+        let mut next_row = None;
+        let mut next_abs = E::Real::faer_zero();
+        for k in 0..nrows {
+            if !used_rows[k] {
+                let abs = u.read(k, 0).faer_abs();
+                if next_row.is_none() || abs > next_abs {
+                    next_row = Some(k);
+                    next_abs = abs;
+                }
+            }
+        }
+
+        us.push(u);
+        vs.push(v);
+
+        let tol = params.tolerance;
+        if uv_norm2 <= tol.faer_mul(tol).faer_mul(norm2) {
+            break;
+        }
+        match next_row {
+            Some(next) => i = next,
+            None => break,
+        }
+    }
+
+    let rank = us.len();
+    let mut u = Mat::<E>::zeros(nrows, rank);
+    let mut v = Mat::<E>::zeros(ncols, rank);
+    for k in 0..rank {
+        u.as_mut().col_mut(k).copy_from(us[k].as_ref().col(0));
+        v.as_mut().col_mut(k).copy_from(vs[k].as_ref().col(0));
+    }
+    (u, v)
+}
+
+/// Computes a low-rank approximation `U V^T` of the block `f(row_start + i, col_start + j)` for
+/// `i < nrows`, `j < ncols` using an interpolative decomposition, where the columns of `U` are
+/// columns of the block.
+fn interpolative<E: ComplexField>(
+    f: &mut dyn FnMut(usize, usize) -> E,
+    row_start: usize,
+    col_start: usize,
+    nrows: usize,
+    ncols: usize,
+    params: HodlrParams<E>,
+) -> (Mat<E>, Mat<E>) {
+    let block = Mat::<E>::from_fn(nrows, ncols, |i, j| f(row_start + i, col_start + j));
+    let qr = ColPivQr::new(block.as_ref());
+    let r = qr.compute_thin_r();
+    let (perm, _) = qr.col_permutation().arrays();
+    let size = r.nrows();
+
+    // `block[:, perm] = Q R`, and discarding the rows `rank..` of `R` leaves an error whose
+    // Frobenius norm is the norm of these rows
+    let mut tail_norm2 = alloc::vec![E::Real::faer_zero(); size + 1];
+    for i in (0..size).rev() {
+        tail_norm2[i] = tail_norm2[i + 1].faer_add(r.as_ref().row(i).squared_norm_l2());
+    }
+    let tol = params.tolerance;
+    let threshold = tol.faer_mul(tol).faer_mul(tail_norm2[0]);
+    let mut rank = Ord::min(params.max_rank, size);
+    if let Some(k) = tail_norm2[..rank].iter().position(|&t| t <= threshold) {
+        rank = k;
+    }
+
+    // `block[:, perm] ≈ block[:, perm[..rank]] [I, R11^{-1} R12]`
+    let mut coeffs = r.as_ref().submatrix(0, rank, rank, ncols - rank).to_owned();
+    solve_upper_triangular_in_place(
+        r.as_ref().submatrix(0, 0, rank, rank),
+        coeffs.as_mut(),
+        Parallelism::None,
+    );
+
+    let u = Mat::<E>::from_fn(nrows, rank, |i, k| block.read(i, perm[k]));
+    let mut v = Mat::<E>::zeros(ncols, rank);
+    for (k, &p) in perm[..rank].iter().enumerate() {
+        v.write(p, k, E::faer_one());
+    }
+    for (j, &p) in perm[rank..].iter().enumerate() {
+        for k in 0..rank {
+            v.write(p, k, coeffs.read(k, j));
+        }
+    }
+    (u, v)
+}
+
+fn compress<E: ComplexField>(
+    f: &mut dyn FnMut(usize, usize) -> E,
+    row_start: usize,
+    col_start: usize,
+    nrows: usize,
+    ncols: usize,
+    params: HodlrParams<E>,
+) -> (Mat<E>, Mat<E>) {
+    match params.compression {
+        HodlrCompression::Aca => aca(f, row_start, col_start, nrows, ncols, params),
+        HodlrCompression::Interpolative => {
+            interpolative(f, row_start, col_start, nrows, ncols, params)
+        }
+    }
+}
+
+fn build<E: ComplexField>(
+    f: &mut dyn FnMut(usize, usize) -> E,
+    start: usize,
+    dim: usize,
+    params: HodlrParams<E>,
+) -> Node<E> {
+    if dim <= Ord::max(params.leaf_size, 1) {
+        return Node::Leaf {
+            dense: Mat::from_fn(dim, dim, |i, j| f(start + i, start + j)),
+        };
+    }
+
+    let mid = dim / 2;
+    let left = build(f, start, mid, params);
+    let right = build(f, start + mid, dim - mid, params);
+    let (u12, v12) = compress(f, start, start + mid, mid, dim - mid, params);
+    let (u21, v21) = compress(f, start + mid, start, dim - mid, mid, params);
+
+    Node::Split {
+        mid,
+        left: Box::new(left),
+        right: Box::new(right),
+        u12,
+        v12,
+        u21,
+        v21,
+    }
+}
+
+fn apply<E: ComplexField>(
+    node: &Node<E>,
+    out: MatMut<'_, E>,
+    rhs: MatRef<'_, E>,
+    conj: Conj,
+    parallelism: Parallelism,
+) {
+    match node {
+        Node::Leaf { dense } => {
+            matmul_with_conj(
+                out,
+                dense.as_ref(),
+                conj,
+                rhs,
+                Conj::No,
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+        }
+        Node::Split {
+            mid,
+            left,
+            right,
+            u12,
+            v12,
+            u21,
+            v21,
+        } => {
+            let (mut out1, mut out2) = out.split_at_row_mut(*mid);
+            let (rhs1, rhs2) = rhs.split_at_row(*mid);
+
+            apply(left, out1.rb_mut(), rhs1, conj, parallelism);
+            apply(right, out2.rb_mut(), rhs2, conj, parallelism);
+
+            let mut tmp = Mat::<E>::zeros(v12.ncols(), rhs.ncols());
+            matmul_with_conj(
+                tmp.as_mut(),
+                v12.as_ref().transpose(),
+                conj,
+                rhs2,
+                Conj::No,
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            matmul_with_conj(
+                out1,
+                u12.as_ref(),
+                conj,
+                tmp.as_ref(),
+                Conj::No,
+                Some(E::faer_one()),
+                E::faer_one(),
+                parallelism,
+            );
+
+            let mut tmp = Mat::<E>::zeros(v21.ncols(), rhs.ncols());
+            matmul_with_conj(
+                tmp.as_mut(),
+                v21.as_ref().transpose(),
+                conj,
+                rhs1,
+                Conj::No,
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            matmul_with_conj(
+                out2,
+                u21.as_ref(),
+                conj,
+                tmp.as_ref(),
+                Conj::No,
+                Some(E::faer_one()),
+                E::faer_one(),
+                parallelism,
+            );
+        }
+    }
+}
+
+fn factorize<E: ComplexField>(node: &Node<E>, parallelism: Parallelism) -> FactorNode<E> {
+    match node {
+        Node::Leaf { dense } => FactorNode::Leaf {
+            lu: PartialPivLu::new(dense.as_ref()),
+        },
+        Node::Split {
+            mid,
+            left,
+            right,
+            u12,
+            v12,
+            u21,
+            v21,
+        } => {
+            let left = factorize(left, parallelism);
+            let right = factorize(right, parallelism);
+
+            let mut w12 = u12.clone();
+            let mut w21 = u21.clone();
+            solve_in_place(&left, w12.as_mut(), parallelism);
+            solve_in_place(&right, w21.as_mut(), parallelism);
+
+            let k12 = u12.ncols();
+            let k21 = u21.ncols();
+            let k = k12 + k21;
+
+            // capacitance matrix
+            // [I                 V12^T A22^{-1} U21]
+            // [V21^T A11^{-1} U12                 I]
+            let capacitance = if k == 0 {
+                None
+            } else {
+                let mut cap = Mat::<E>::identity(k, k);
+                {
+                    let (_, mut top_right, mut bot_left, _) = cap.as_mut().split_at_mut(k12, k12);
+                    crate::linalg::matmul::matmul(
+                        top_right.rb_mut(),
+                        v12.as_ref().transpose(),
+                        w21.as_ref(),
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    );
+                    crate::linalg::matmul::matmul(
+                        bot_left.rb_mut(),
+                        v21.as_ref().transpose(),
+                        w12.as_ref(),
+                        None,
+                        E::faer_one(),
+                        parallelism,
+                    );
+                }
+                Some(PartialPivLu::new(cap.as_ref()))
+            };
+
+            FactorNode::Split {
+                mid: *mid,
+                left: Box::new(left),
+                right: Box::new(right),
+                v12: v12.clone(),
+                v21: v21.clone(),
+                w12,
+                w21,
+                capacitance,
+            }
+        }
+    }
+}
+
+fn solve_in_place<E: ComplexField>(
+    node: &FactorNode<E>,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    match node {
+        FactorNode::Leaf { lu } => lu.solve_in_place(rhs),
+        FactorNode::Split {
+            mid,
+            left,
+            right,
+            v12,
+            v21,
+            w12,
+            w21,
+            capacitance,
+        } => {
+            let (mut rhs1, mut rhs2) = rhs.split_at_row_mut(*mid);
+
+            // y = D^{-1} b
+            solve_in_place(left, rhs1.rb_mut(), parallelism);
+            solve_in_place(right, rhs2.rb_mut(), parallelism);
+
+            let capacitance = match capacitance {
+                Some(capacitance) => capacitance,
+                None => return,
+            };
+
+            // z = K^{-1} V^T y
+            let k12 = v12.ncols();
+            let k21 = v21.ncols();
+            let mut z = Mat::<E>::zeros(k12 + k21, rhs1.ncols());
+            {
+                let (mut z1, mut z2) = z.as_mut().split_at_row_mut(k12);
+                crate::linalg::matmul::matmul(
+                    z1.rb_mut(),
+                    v12.as_ref().transpose(),
+                    rhs2.rb(),
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+                crate::linalg::matmul::matmul(
+                    z2.rb_mut(),
+                    v21.as_ref().transpose(),
+                    rhs1.rb(),
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+            }
+            capacitance.solve_in_place(z.as_mut());
+
+            // x = y - D^{-1} U z
+            let (z1, z2) = z.as_ref().split_at_row(k12);
+            crate::linalg::matmul::matmul(
+                rhs1,
+                w12.as_ref(),
+                z1,
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                parallelism,
+            );
+            crate::linalg::matmul::matmul(
+                rhs2,
+                w21.as_ref(),
+                z2,
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                parallelism,
+            );
+        }
+    }
+}
+
+fn max_rank<E: ComplexField>(node: &Node<E>) -> usize {
+    match node {
+        Node::Leaf { .. } => 0,
+        Node::Split {
+            left,
+            right,
+            u12,
+            u21,
+            ..
+        } => Ord::max(
+            Ord::max(u12.ncols(), u21.ncols()),
+            Ord::max(max_rank(left), max_rank(right)),
+        ),
+    }
+}
+
+impl<E: ComplexField> HodlrMat<E> {
+    /// Builds the HODLR approximation of the `dim×dim` matrix whose entries are given by `f(i,
+    /// j)`.
+    ///
+    /// The entries of the dense diagonal blocks are all evaluated, while only a subset of the
+    /// entries of the off-diagonal blocks are evaluated with [`HodlrCompression::Aca`].
+    #[track_caller]
+    pub fn new(dim: usize, f: impl FnMut(usize, usize) -> E, params: HodlrParams<E>) -> Self {
+        let mut f = f;
+        Self {
+            dim,
+            root: build(&mut f, 0, dim, params),
+        }
+    }
+
+    /// Builds the HODLR approximation of the square matrix `mat`.
+    #[track_caller]
+    pub fn from_dense(mat: MatRef<'_, E>, params: HodlrParams<E>) -> Self {
+        assert!(mat.nrows() == mat.ncols());
+        Self::new(mat.nrows(), |i, j| mat.read(i, j), params)
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Returns the largest rank of the off-diagonal blocks.
+    #[inline]
+    pub fn max_rank(&self) -> usize {
+        max_rank(&self.root)
+    }
+
+    /// Computes `self * rhs` and stores the result in `out`.
+    ///
+    /// # Panics
+    /// Panics if `out` and `rhs` don't have the same number of columns, or if either doesn't have
+    /// a number of rows equal to the dimension of `self`.
+    #[track_caller]
+    pub fn matmul(&self, out: MatMut<'_, E>, rhs: MatRef<'_, E>, parallelism: Parallelism) {
+        assert!(all(
+            out.nrows() == self.dim,
+            rhs.nrows() == self.dim,
+            out.ncols() == rhs.ncols(),
+        ));
+        apply(&self.root, out, rhs, Conj::No, parallelism);
+    }
+
+    /// Returns the dense matrix represented by `self`.
+    pub fn to_dense(&self) -> Mat<E> {
+        let mut out = Mat::<E>::zeros(self.dim, self.dim);
+        let id = Mat::<E>::identity(self.dim, self.dim);
+        apply(
+            &self.root,
+            out.as_mut(),
+            id.as_ref(),
+            Conj::No,
+            Parallelism::None,
+        );
+        out
+    }
+
+    /// Computes an approximate factorization of `self`, using the Sherman-Morrison-Woodbury
+    /// formula recursively on each level of the hierarchy.
+    pub fn factorize(&self, parallelism: Parallelism) -> HodlrLu<E> {
+        HodlrLu {
+            dim: self.dim,
+            root: factorize(&self.root, parallelism),
+        }
+    }
+}
+
+impl<E: ComplexField> HodlrLu<E> {
+    /// Returns the dimension of the factorized matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    /// Solves the linear system `A X = B`, where `B` is stored in `rhs`. The solution is stored
+    /// in `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have a number of rows equal to the dimension of the matrix.
+    #[track_caller]
+    pub fn solve_in_place(&self, rhs: MatMut<'_, E>, parallelism: Parallelism) {
+        assert!(rhs.nrows() == self.dim);
+        solve_in_place(&self.root, rhs, parallelism);
+    }
+}
+
+impl<E: ComplexField> LinOp<E> for HodlrMat<E> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.matmul(out, rhs, parallelism);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        assert!(all(
+            out.nrows() == self.dim,
+            rhs.nrows() == self.dim,
+            out.ncols() == rhs.ncols(),
+        ));
+        apply(&self.root, out, rhs, Conj::Yes, parallelism);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert;
+
+    fn kernel(n: usize) -> impl Fn(usize, usize) -> f64 {
+        move |i, j| {
+            let xi = i as f64 / n as f64;
+            let xj = j as f64 / n as f64;
+            let d = xi - xj;
+            let k = f64::exp(-d * d / 0.1);
+            if i == j {
+                k + 1.0
+            } else {
+                k
+            }
+        }
+    }
+
+    #[test]
+    fn test_hodlr_matvec_and_solve() {
+        let n = 300;
+        let f = kernel(n);
+        let dense = Mat::from_fn(n, n, &f);
+
+        let mut params = HodlrParams::<f64>::default();
+        params.leaf_size = 32;
+        params.tolerance = 1e-12;
+        let hodlr = HodlrMat::new(n, &f, params);
+        assert!(hodlr.max_rank() < n / 4);
+
+        let rhs = Mat::from_fn(n, 3, |i, j| (i + 2 * j) as f64 / n as f64);
+        let mut out = Mat::<f64>::zeros(n, 3);
+        hodlr.matmul(out.as_mut(), rhs.as_ref(), Parallelism::None);
+        let expected = &dense * &rhs;
+        assert!((&out - &expected).norm_max() < 1e-8);
+
+        let lu = hodlr.factorize(Parallelism::None);
+        let mut sol = expected.clone();
+        lu.solve_in_place(sol.as_mut(), Parallelism::None);
+        assert!((&sol - &rhs).norm_max() < 1e-6);
+    }
+
+    #[test]
+    fn test_hodlr_interpolative() {
+        let n = 300;
+        let f = kernel(n);
+        let dense = Mat::from_fn(n, n, &f);
+
+        let mut params = HodlrParams::<f64>::default();
+        params.leaf_size = 32;
+        params.tolerance = 1e-12;
+        params.compression = HodlrCompression::Interpolative;
+        let hodlr = HodlrMat::new(n, &f, params);
+        assert!(hodlr.max_rank() < n / 4);
+        assert!((hodlr.to_dense() - &dense).norm_max() < 1e-9);
+
+        let rhs = Mat::from_fn(n, 2, |i, j| (i + 2 * j) as f64 / n as f64);
+        let lu = hodlr.factorize(Parallelism::None);
+        let mut sol = &dense * &rhs;
+        lu.solve_in_place(sol.as_mut(), Parallelism::None);
+        assert!((&sol - &rhs).norm_max() < 1e-6);
+
+        // the rank is capped by `max_rank`
+        params.max_rank = 3;
+        let hodlr = HodlrMat::new(n, &f, params);
+        assert!(hodlr.max_rank() == 3);
+    }
+
+    #[test]
+    fn test_hodlr_small_is_dense() {
+        let n = 10;
+        let f = kernel(n);
+        let dense = Mat::from_fn(n, n, &f);
+        let hodlr = HodlrMat::new(n, &f, HodlrParams::default());
+        assert!(hodlr.max_rank() == 0);
+        assert!((hodlr.to_dense() - &dense).norm_max() == 0.0);
+    }
+}
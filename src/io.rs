@@ -206,3 +206,10 @@ impl<'a> Npy<'a> {
         mat
     }
 }
+
+/// Reading and writing matrices in the Matrix Market exchange format.
+pub mod matrix_market;
+
+/// Zero-copy views over matrices stored in a raw binary layout.
+#[cfg(target_endian = "little")]
+pub mod raw;
@@ -0,0 +1,651 @@
+//! Reading and writing matrices in the
+//! [Matrix Market](https://math.nist.gov/MatrixMarket/formats.html) exchange format.
+//!
+//! Both the `coordinate` (sparse) and `array` (dense) formats can be read into either a
+//! [`SparseColMat`] or a [`Mat`], with any of the `real`, `integer`, `complex` or `pattern`
+//! fields, and any of the `general`, `symmetric`, `skew-symmetric` or `hermitian` storage
+//! schemes. Matrices with symmetric storage are expanded to their full form when read. Entries
+//! of a `pattern` matrix are read as ones.
+//!
+//! Matrices are always written with the `general` storage scheme, using the `coordinate` format
+//! for sparse matrices and the `array` format for dense ones.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{io::matrix_market, mat};
+//!
+//! let data = b"\
+//! %%MatrixMarket matrix coordinate real symmetric
+//! % lower triangular part of a 3x3 matrix
+//! 3 3 4
+//! 1 1 2.0
+//! 2 1 -1.0
+//! 2 2 2.0
+//! 3 3 1.5
+//! ";
+//!
+//! let a = matrix_market::read_dense::<f64>(&data[..]).unwrap();
+//! assert!(a == mat![[2.0, -1.0, 0.0], [-1.0, 2.0, 0.0], [0.0, 0.0, 1.5]]);
+//!
+//! let a = matrix_market::read_sparse::<usize, f64>(&data[..]).unwrap();
+//! assert!(a.compute_nnz() == 5);
+//!
+//! let mut out = Vec::new();
+//! matrix_market::write_sparse(&mut out, a.as_ref()).unwrap();
+//! let b = matrix_market::read_sparse::<usize, f64>(&out[..]).unwrap();
+//! assert!(a.to_dense() == b.to_dense());
+//! ```
+
+use crate::{
+    complex_native::{c32, c64},
+    sparse::{CreationError, FaerError, SparseColMat, SparseColMatRef},
+    ComplexField, Index, Mat, MatRef, SignedIndex,
+};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+
+/// Trait implemented for native types that can be read from and written to a Matrix Market file.
+pub trait MatrixMarketEntity: ComplexField + faer_entity::SimpleEntity {
+    /// Whether the type can represent complex values.
+    const IS_COMPLEX: bool;
+
+    /// Creates a value from its real and imaginary parts. The imaginary part is ignored for
+    /// real types.
+    fn from_parts(re: f64, im: f64) -> Self;
+
+    /// Returns the real and imaginary parts of the value.
+    fn to_parts(self) -> (f64, f64);
+}
+
+impl MatrixMarketEntity for f32 {
+    const IS_COMPLEX: bool = false;
+
+    #[inline]
+    fn from_parts(re: f64, _: f64) -> Self {
+        re as f32
+    }
+    #[inline]
+    fn to_parts(self) -> (f64, f64) {
+        (self as f64, 0.0)
+    }
+}
+impl MatrixMarketEntity for f64 {
+    const IS_COMPLEX: bool = false;
+
+    #[inline]
+    fn from_parts(re: f64, _: f64) -> Self {
+        re
+    }
+    #[inline]
+    fn to_parts(self) -> (f64, f64) {
+        (self, 0.0)
+    }
+}
+impl MatrixMarketEntity for c32 {
+    const IS_COMPLEX: bool = true;
+
+    #[inline]
+    fn from_parts(re: f64, im: f64) -> Self {
+        c32::new(re as f32, im as f32)
+    }
+    #[inline]
+    fn to_parts(self) -> (f64, f64) {
+        (self.re as f64, self.im as f64)
+    }
+}
+impl MatrixMarketEntity for c64 {
+    const IS_COMPLEX: bool = true;
+
+    #[inline]
+    fn from_parts(re: f64, im: f64) -> Self {
+        c64::new(re, im)
+    }
+    #[inline]
+    fn to_parts(self) -> (f64, f64) {
+        (self.re, self.im)
+    }
+}
+
+/// Error that can occur while reading a Matrix Market file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MatrixMarketError {
+    /// Error from the underlying reader.
+    Io(std::io::Error),
+    /// The banner or the size line is missing or malformed, or describes an unsupported format.
+    InvalidHeader,
+    /// The file contains complex values, but the requested type is real.
+    ComplexToReal,
+    /// A data line could not be parsed.
+    InvalidEntry {
+        /// One-based line number of the entry.
+        line: usize,
+    },
+    /// The indices of an entry are out of bounds.
+    OutOfBounds {
+        /// One-based line number of the entry.
+        line: usize,
+    },
+    /// The number of entries does not match the size line.
+    WrongEntryCount,
+    /// Generic error (allocation or index overflow) while assembling the matrix.
+    Generic(FaerError),
+}
+
+impl From<std::io::Error> for MatrixMarketError {
+    #[inline]
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl core::fmt::Display for MatrixMarketError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for MatrixMarketError {}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Format {
+    Coordinate,
+    Array,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Field {
+    Real,
+    Integer,
+    Complex,
+    Pattern,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Symmetry {
+    General,
+    Symmetric,
+    SkewSymmetric,
+    Hermitian,
+}
+
+#[derive(Copy, Clone, Debug)]
+struct Header {
+    format: Format,
+    field: Field,
+    symmetry: Symmetry,
+    nrows: usize,
+    ncols: usize,
+    // number of stored entries, before expanding the symmetric storage
+    len: usize,
+}
+
+struct Parser<R> {
+    reader: R,
+    buf: String,
+    line: usize,
+}
+
+impl<R: BufRead> Parser<R> {
+    /// Reads the next line into `self.buf`, returning `false` at the end of the input.
+    fn next_line(&mut self) -> Result<bool, MatrixMarketError> {
+        self.buf.clear();
+        self.line += 1;
+        Ok(self.reader.read_line(&mut self.buf)? != 0)
+    }
+
+    /// Reads the next line that is neither empty nor a comment into `self.buf`.
+    fn next_data_line(&mut self) -> Result<bool, MatrixMarketError> {
+        loop {
+            if !self.next_line()? {
+                return Ok(false);
+            }
+            let line = self.buf.trim_start();
+            if !line.is_empty() && !line.starts_with('%') {
+                return Ok(true);
+            }
+        }
+    }
+
+    fn read_header<E: MatrixMarketEntity>(&mut self) -> Result<Header, MatrixMarketError> {
+        use MatrixMarketError::InvalidHeader;
+
+        if !self.next_line()? {
+            return Err(InvalidHeader);
+        }
+        let banner = self.buf.to_ascii_lowercase();
+        let mut banner = banner.split_whitespace();
+        if banner.next() != Some("%%matrixmarket") || banner.next() != Some("matrix") {
+            return Err(InvalidHeader);
+        }
+        let format = match banner.next() {
+            Some("coordinate") => Format::Coordinate,
+            Some("array") => Format::Array,
+            _ => return Err(InvalidHeader),
+        };
+        let field = match banner.next() {
+            Some("real") => Field::Real,
+            Some("double") => Field::Real,
+            Some("integer") => Field::Integer,
+            Some("complex") => Field::Complex,
+            Some("pattern") => Field::Pattern,
+            _ => return Err(InvalidHeader),
+        };
+        let symmetry = match banner.next() {
+            Some("general") => Symmetry::General,
+            Some("symmetric") => Symmetry::Symmetric,
+            Some("skew-symmetric") => Symmetry::SkewSymmetric,
+            Some("hermitian") => Symmetry::Hermitian,
+            _ => return Err(InvalidHeader),
+        };
+        if format == Format::Array && field == Field::Pattern {
+            return Err(InvalidHeader);
+        }
+        if field == Field::Complex && !E::IS_COMPLEX {
+            return Err(MatrixMarketError::ComplexToReal);
+        }
+
+        if !self.next_data_line()? {
+            return Err(InvalidHeader);
+        }
+        let mut sizes = self
+            .buf
+            .split_whitespace()
+            .map(|size| size.parse::<usize>().map_err(|_| InvalidHeader));
+        let nrows = sizes.next().ok_or(InvalidHeader)??;
+        let ncols = sizes.next().ok_or(InvalidHeader)??;
+        let len = match format {
+            Format::Coordinate => sizes.next().ok_or(InvalidHeader)??,
+            Format::Array => match symmetry {
+                Symmetry::General => nrows.checked_mul(ncols).ok_or(InvalidHeader)?,
+                Symmetry::Symmetric | Symmetry::Hermitian => (0..ncols)
+                    .map(|j| nrows.saturating_sub(j))
+                    .try_fold(0usize, |acc, len| acc.checked_add(len))
+                    .ok_or(InvalidHeader)?,
+                Symmetry::SkewSymmetric => (0..ncols)
+                    .map(|j| nrows.saturating_sub(j + 1))
+                    .try_fold(0usize, |acc, len| acc.checked_add(len))
+                    .ok_or(InvalidHeader)?,
+            },
+        };
+        if sizes.next().is_some() || (symmetry != Symmetry::General && nrows != ncols) {
+            return Err(InvalidHeader);
+        }
+
+        Ok(Header {
+            format,
+            field,
+            symmetry,
+            nrows,
+            ncols,
+            len,
+        })
+    }
+
+    /// Calls `f` with the row, column and value of each entry of the matrix, after expanding the
+    /// symmetric storage.
+    fn read_entries<E: MatrixMarketEntity>(
+        &mut self,
+        header: Header,
+        mut f: impl FnMut(usize, usize, E),
+    ) -> Result<(), MatrixMarketError> {
+        let (nrows, ncols) = (header.nrows, header.ncols);
+
+        // position of the next entry, for the array format
+        let mut i = 0usize;
+        let mut j = 0usize;
+        let next_array_pos = |i: &mut usize, j: &mut usize| {
+            *i += 1;
+            if *i == nrows {
+                *j += 1;
+                *i = match header.symmetry {
+                    Symmetry::General => 0,
+                    Symmetry::Symmetric | Symmetry::Hermitian => *j,
+                    Symmetry::SkewSymmetric => *j + 1,
+                };
+            }
+        };
+        if header.symmetry == Symmetry::SkewSymmetric {
+            i = 1;
+        }
+
+        for _ in 0..header.len {
+            if !self.next_data_line()? {
+                return Err(MatrixMarketError::WrongEntryCount);
+            }
+            let line = self.line;
+            let invalid = || MatrixMarketError::InvalidEntry { line };
+
+            let mut tokens = self.buf.split_whitespace();
+            let (row, col) = match header.format {
+                Format::Coordinate => {
+                    let mut index = || -> Result<usize, MatrixMarketError> {
+                        let index = tokens
+                            .next()
+                            .ok_or_else(invalid)?
+                            .parse::<usize>()
+                            .map_err(|_| invalid())?;
+                        index
+                            .checked_sub(1)
+                            .ok_or(MatrixMarketError::OutOfBounds { line })
+                    };
+                    (index()?, index()?)
+                }
+                Format::Array => {
+                    let pos = (i, j);
+                    next_array_pos(&mut i, &mut j);
+                    pos
+                }
+            };
+            if row >= nrows || col >= ncols {
+                return Err(MatrixMarketError::OutOfBounds { line });
+            }
+
+            let mut number = || -> Result<f64, MatrixMarketError> {
+                tokens
+                    .next()
+                    .ok_or_else(invalid)?
+                    .parse::<f64>()
+                    .map_err(|_| invalid())
+            };
+            let value = match header.field {
+                Field::Pattern => E::from_parts(1.0, 0.0),
+                Field::Real | Field::Integer => E::from_parts(number()?, 0.0),
+                Field::Complex => {
+                    let re = number()?;
+                    let im = number()?;
+                    E::from_parts(re, im)
+                }
+            };
+            if tokens.next().is_some() {
+                return Err(invalid());
+            }
+
+            f(row, col, value);
+            if row != col {
+                match header.symmetry {
+                    Symmetry::General => {}
+                    Symmetry::Symmetric => f(col, row, value),
+                    Symmetry::SkewSymmetric => f(col, row, value.faer_neg()),
+                    Symmetry::Hermitian => f(col, row, value.faer_conj()),
+                }
+            }
+        }
+
+        if self.next_data_line()? {
+            return Err(MatrixMarketError::WrongEntryCount);
+        }
+        Ok(())
+    }
+}
+
+/// Reads a dense matrix in the Matrix Market format from `reader`.
+///
+/// Files in the `coordinate` format are also accepted, in which case the missing entries are set
+/// to zero, and duplicate entries are summed.
+pub fn read_dense<E: MatrixMarketEntity>(reader: impl Read) -> Result<Mat<E>, MatrixMarketError> {
+    let mut parser = Parser {
+        reader: BufReader::new(reader),
+        buf: String::new(),
+        line: 0,
+    };
+    let header = parser.read_header::<E>()?;
+
+    let mut mat = Mat::<E>::zeros(header.nrows, header.ncols);
+    parser.read_entries::<E>(header, |i, j, value| {
+        mat.write(i, j, mat.read(i, j).faer_add(value));
+    })?;
+    Ok(mat)
+}
+
+/// Reads a sparse matrix in the Matrix Market format from `reader`.
+///
+/// Files in the `array` format are also accepted, in which case only the nonzero entries are
+/// stored. Duplicate entries of files in the `coordinate` format are summed, and explicit zeros
+/// are kept in the sparsity structure.
+pub fn read_sparse<I: Index, E: MatrixMarketEntity>(
+    reader: impl Read,
+) -> Result<SparseColMat<I, E>, MatrixMarketError> {
+    let mut parser = Parser {
+        reader: BufReader::new(reader),
+        buf: String::new(),
+        line: 0,
+    };
+    let header = parser.read_header::<E>()?;
+    if Ord::max(header.nrows, header.ncols) > I::Signed::MAX.zx() {
+        return Err(MatrixMarketError::Generic(FaerError::IndexOverflow));
+    }
+
+    let capacity = match (header.format, header.symmetry) {
+        (Format::Array, _) => 0,
+        (Format::Coordinate, Symmetry::General) => header.len,
+        (Format::Coordinate, _) => header.len.saturating_mul(2),
+    };
+    let mut triplets = Vec::new();
+    triplets
+        .try_reserve_exact(capacity)
+        .map_err(|_| MatrixMarketError::Generic(FaerError::OutOfMemory))?;
+
+    let is_array = header.format == Format::Array;
+    parser.read_entries::<E>(header, |i, j, value| {
+        if !(is_array && value.to_parts() == (0.0, 0.0)) {
+            triplets.push((I::truncate(i), I::truncate(j), value));
+        }
+    })?;
+
+    SparseColMat::try_new_from_triplets(header.nrows, header.ncols, &triplets).map_err(|err| {
+        match err {
+            CreationError::Generic(err) => MatrixMarketError::Generic(err),
+            CreationError::OutOfBounds { .. } => unreachable!(),
+        }
+    })
+}
+
+fn write_value<E: MatrixMarketEntity>(
+    writer: &mut impl Write,
+    value: E,
+) -> Result<(), std::io::Error> {
+    let (re, im) = value.to_parts();
+    if E::IS_COMPLEX {
+        writeln!(writer, "{re:e} {im:e}")
+    } else {
+        writeln!(writer, "{re:e}")
+    }
+}
+
+fn field_name<E: MatrixMarketEntity>() -> &'static str {
+    if E::IS_COMPLEX {
+        "complex"
+    } else {
+        "real"
+    }
+}
+
+/// Writes a dense matrix to `writer` in the Matrix Market `array` format.
+pub fn write_dense<E: MatrixMarketEntity>(
+    writer: impl Write,
+    matrix: MatRef<'_, E>,
+) -> Result<(), std::io::Error> {
+    let mut writer = BufWriter::new(writer);
+    writeln!(
+        writer,
+        "%%MatrixMarket matrix array {} general",
+        field_name::<E>()
+    )?;
+    writeln!(writer, "{} {}", matrix.nrows(), matrix.ncols())?;
+    for j in 0..matrix.ncols() {
+        for i in 0..matrix.nrows() {
+            write_value(&mut writer, matrix.read(i, j))?;
+        }
+    }
+    writer.flush()
+}
+
+/// Writes a sparse matrix to `writer` in the Matrix Market `coordinate` format.
+pub fn write_sparse<I: Index, E: MatrixMarketEntity>(
+    writer: impl Write,
+    matrix: SparseColMatRef<'_, I, E>,
+) -> Result<(), std::io::Error> {
+    let mut writer = BufWriter::new(writer);
+    writeln!(
+        writer,
+        "%%MatrixMarket matrix coordinate {} general",
+        field_name::<E>()
+    )?;
+    writeln!(
+        writer,
+        "{} {} {}",
+        matrix.nrows(),
+        matrix.ncols(),
+        matrix.compute_nnz()
+    )?;
+    for j in 0..matrix.ncols() {
+        for (i, &value) in matrix.row_indices_of_col(j).zip(matrix.values_of_col(j)) {
+            write!(writer, "{} {} ", i + 1, j + 1)?;
+            write_value(&mut writer, value)?;
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, mat};
+
+    #[test]
+    fn test_read_coordinate() {
+        let data = b"%%MatrixMarket matrix coordinate real general
+% comment
+
+3 2 4
+1 1 1.5
+3 1 -2
+2 2 3e1
+3 1 0.5
+";
+        let expected = mat![[1.5, 0.0], [0.0, 30.0], [-1.5, 0.0]];
+        assert!(read_dense::<f64>(&data[..]).unwrap() == expected);
+
+        let sparse = read_sparse::<u32, f64>(&data[..]).unwrap();
+        assert!(sparse.compute_nnz() == 3);
+        assert!(sparse.to_dense() == expected);
+    }
+
+    #[test]
+    fn test_read_symmetric() {
+        let data = b"%%MatrixMarket matrix coordinate integer symmetric
+3 3 3
+1 1 4
+3 1 -1
+3 2 2
+";
+        let a = read_dense::<f64>(&data[..]).unwrap();
+        assert!(a == mat![[4.0, 0.0, -1.0], [0.0, 0.0, 2.0], [-1.0, 2.0, 0.0]]);
+
+        let data = b"%%MatrixMarket matrix array real skew-symmetric
+3 3
+1.0
+2.0
+3.0
+";
+        let a = read_dense::<f64>(&data[..]).unwrap();
+        assert!(a == mat![[0.0, -1.0, -2.0], [1.0, 0.0, -3.0], [2.0, 3.0, 0.0]]);
+
+        let data = b"%%MatrixMarket matrix coordinate complex hermitian
+2 2 2
+1 1 1.0 0.0
+2 1 2.0 3.0
+";
+        let a = read_sparse::<usize, c64>(&data[..]).unwrap().to_dense();
+        assert!(
+            a == mat![
+                [c64::new(1.0, 0.0), c64::new(2.0, -3.0)],
+                [c64::new(2.0, 3.0), c64::new(0.0, 0.0)]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_pattern_and_array() {
+        let data = b"%%MatrixMarket matrix coordinate pattern general
+2 3 2
+1 3
+2 1
+";
+        let a = read_dense::<f32>(&data[..]).unwrap();
+        assert!(a == mat![[0.0, 0.0, 1.0], [1.0, 0.0, 0.0f32]]);
+
+        let data = b"%%MatrixMarket matrix array real symmetric
+2 2
+1.0
+0.0
+2.0
+";
+        let a = read_sparse::<usize, f64>(&data[..]).unwrap();
+        assert!(a.compute_nnz() == 2);
+        assert!(a.to_dense() == mat![[1.0, 0.0], [0.0, 2.0]]);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        let read = |data: &[u8]| read_dense::<f64>(data);
+
+        assert!(matches!(
+            read(b"%%MatrixMarket matrix coordinate complex general\n1 1 0\n"),
+            Err(MatrixMarketError::ComplexToReal)
+        ));
+        assert!(matches!(
+            read(b"%%MatrixMarket tensor coordinate real general\n1 1 0\n"),
+            Err(MatrixMarketError::InvalidHeader)
+        ));
+        assert!(matches!(
+            read(b"%%MatrixMarket matrix coordinate real general\n1 1 1\n2 1 1.0\n"),
+            Err(MatrixMarketError::OutOfBounds { line: 3 })
+        ));
+        assert!(matches!(
+            read(b"%%MatrixMarket matrix coordinate real general\n1 1 1\n1 1 x\n"),
+            Err(MatrixMarketError::InvalidEntry { line: 3 })
+        ));
+        assert!(matches!(
+            read(b"%%MatrixMarket matrix coordinate real general\n1 1 2\n1 1 1.0\n"),
+            Err(MatrixMarketError::WrongEntryCount)
+        ));
+        assert!(matches!(
+            read(b"%%MatrixMarket matrix array real general\n1 1\n1.0\n2.0\n"),
+            Err(MatrixMarketError::WrongEntryCount)
+        ));
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let a = Mat::from_fn(4, 3, |i, j| {
+            c64::new(i as f64 / 3.0 - j as f64, 1.0 / (1.0 + i as f64 + j as f64))
+        });
+        let mut out = Vec::new();
+        write_dense(&mut out, a.as_ref()).unwrap();
+        assert!(read_dense::<c64>(&out[..]).unwrap() == a);
+
+        let a = SparseColMat::<usize, f64>::try_new_from_triplets(
+            4,
+            5,
+            &[(0, 0, 0.1), (3, 0, 1e-300), (2, 4, -7.25), (1, 2, 0.0)],
+        )
+        .unwrap();
+        let mut out = Vec::new();
+        write_sparse(&mut out, a.as_ref()).unwrap();
+        let b = read_sparse::<usize, f64>(&out[..]).unwrap();
+        assert!(b.compute_nnz() == 4);
+        assert!(b.to_dense() == a.to_dense());
+    }
+
+    #[test]
+    fn test_read_file() {
+        let a =
+            read_sparse::<usize, f64>(std::fs::File::open("test_data/YAO.mtx").unwrap()).unwrap();
+        assert!(a.nrows() == 4005);
+        assert!(a.ncols() == 4005);
+        assert!(a.compute_nnz() == 10008);
+    }
+}
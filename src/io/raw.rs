@@ -0,0 +1,448 @@
+//! Zero-copy views over matrices stored in a simple raw binary layout.
+//!
+//! The layout is designed so that a dense or sparse matrix can be referenced in place from a
+//! memory buffer, typically a memory-mapped file, without any deserialization copies. Only the
+//! structure of sparse matrices is validated when a view is created, which takes a single pass
+//! over the indices.
+//!
+//! # Layout
+//!
+//! All values are stored in little endian order, so the layout is only available on little endian
+//! targets. The file starts with a header of [`HEADER_LEN`] bytes:
+//!
+//! | offset | size | content                                                    |
+//! |--------|------|------------------------------------------------------------|
+//! | 0      | 8    | magic bytes `b"FAERRAW\0"`                                 |
+//! | 8      | 4    | format version, currently `1`                              |
+//! | 12     | 4    | matrix kind, `0` for dense and `1` for sparse column-major |
+//! | 16     | 4    | scalar type, see [`RawDType`]                              |
+//! | 20     | 4    | size of the index type in bytes, `0` for dense matrices    |
+//! | 24     | 8    | number of rows                                             |
+//! | 32     | 8    | number of columns                                          |
+//! | 40     | 8    | number of stored entries                                   |
+//! | 48     | 16   | reserved, zero                                             |
+//!
+//! It is followed by the data sections, each of which starts at an offset that is a multiple of
+//! [`ALIGN`] bytes, and is padded with zeros up to the next section:
+//! - dense matrices contain a single section with the values in column-major order.
+//! - sparse matrices contain the column pointers (`ncols + 1` indices), the row indices
+//! (`nnz` indices), then the values (`nnz` scalars), in compressed column-major format with
+//! sorted row indices.
+//!
+//! The buffer itself must be aligned to [`ALIGN`] bytes for the views to be created, which is
+//! always the case for memory-mapped files.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{io::raw, mat};
+//!
+//! let a = mat![[1.0, 2.0], [3.0, 4.0f64]];
+//! let mut bytes = Vec::new();
+//! raw::write_dense(&mut bytes, a.as_ref()).unwrap();
+//!
+//! // copy the bytes into an aligned buffer, which a memory map would provide directly
+//! #[repr(align(64))]
+//! struct Aligned([u8; 128]);
+//! let mut buffer = Aligned([0; 128]);
+//! buffer.0[..bytes.len()].copy_from_slice(&bytes);
+//!
+//! let view = raw::view_dense::<f64>(&buffer.0[..bytes.len()]).unwrap();
+//! assert!(view == a);
+//! ```
+
+use crate::{
+    complex_native::{c32, c64},
+    mat::{from_column_major_slice, MatRef},
+    sparse::{SparseColMatRef, SymbolicSparseColMatRef},
+    Index, SignedIndex,
+};
+use std::io::Write;
+
+/// Length of the header in bytes.
+pub const HEADER_LEN: usize = 64;
+/// Alignment in bytes of the data sections.
+pub const ALIGN: usize = 64;
+
+const MAGIC: &[u8; 8] = b"FAERRAW\0";
+const VERSION: u32 = 1;
+const KIND_DENSE: u32 = 0;
+const KIND_SPARSE: u32 = 1;
+
+/// Scalar type of the values of a raw matrix.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(u32)]
+pub enum RawDType {
+    /// 32-bit floating point.
+    F32 = 0,
+    /// 64-bit floating point.
+    F64 = 1,
+    /// 32-bit complex floating point.
+    C32 = 2,
+    /// 64-bit complex floating point.
+    C64 = 3,
+}
+
+/// Trait implemented for native types that can be stored in the raw layout.
+pub trait RawEntity: faer_entity::SimpleEntity + bytemuck::Pod {
+    /// Data type of the values.
+    const DTYPE: RawDType;
+}
+
+impl RawEntity for f32 {
+    const DTYPE: RawDType = RawDType::F32;
+}
+impl RawEntity for f64 {
+    const DTYPE: RawDType = RawDType::F64;
+}
+impl RawEntity for c32 {
+    const DTYPE: RawDType = RawDType::C32;
+}
+impl RawEntity for c64 {
+    const DTYPE: RawDType = RawDType::C64;
+}
+
+/// Error that can occur while creating a view over a raw buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RawError {
+    /// The header is missing or malformed, or describes an unsupported version.
+    InvalidHeader,
+    /// The matrix kind, scalar type or index size differs from the requested one.
+    TypeMismatch,
+    /// The buffer is too short to contain the data described by the header.
+    Truncated,
+    /// The buffer is not aligned to [`ALIGN`] bytes.
+    Unaligned,
+    /// The dimensions don't fit in the index type or in memory.
+    Overflow,
+    /// The structure of the sparse matrix is invalid.
+    InvalidStructure,
+}
+
+impl core::fmt::Display for RawError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+impl std::error::Error for RawError {}
+
+/// Header of a raw matrix buffer.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct RawHeader {
+    /// Whether the matrix is sparse.
+    pub sparse: bool,
+    /// Scalar type of the values.
+    pub dtype: RawDType,
+    /// Size of the index type in bytes, zero for dense matrices.
+    pub index_size: usize,
+    /// Number of rows.
+    pub nrows: usize,
+    /// Number of columns.
+    pub ncols: usize,
+    /// Number of stored entries.
+    pub nnz: usize,
+}
+
+#[inline]
+fn padded(len: usize) -> Option<usize> {
+    len.checked_add(ALIGN - 1).map(|len| len / ALIGN * ALIGN)
+}
+
+#[inline]
+fn read_u32(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+}
+
+#[inline]
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(bytes[offset..offset + 8].try_into().unwrap())
+}
+
+/// Parses the header of a raw matrix buffer.
+pub fn read_header(bytes: &[u8]) -> Result<RawHeader, RawError> {
+    if bytes.len() < HEADER_LEN || &bytes[..8] != MAGIC || read_u32(bytes, 8) != VERSION {
+        return Err(RawError::InvalidHeader);
+    }
+    let sparse = match read_u32(bytes, 12) {
+        KIND_DENSE => false,
+        KIND_SPARSE => true,
+        _ => return Err(RawError::InvalidHeader),
+    };
+    let dtype = match read_u32(bytes, 16) {
+        0 => RawDType::F32,
+        1 => RawDType::F64,
+        2 => RawDType::C32,
+        3 => RawDType::C64,
+        _ => return Err(RawError::InvalidHeader),
+    };
+    let size = |offset| usize::try_from(read_u64(bytes, offset)).map_err(|_| RawError::Overflow);
+    Ok(RawHeader {
+        sparse,
+        dtype,
+        index_size: read_u32(bytes, 20) as usize,
+        nrows: size(24)?,
+        ncols: size(32)?,
+        nnz: size(40)?,
+    })
+}
+
+fn write_header(mut writer: impl Write, header: RawHeader) -> std::io::Result<()> {
+    let mut bytes = [0u8; HEADER_LEN];
+    bytes[..8].copy_from_slice(MAGIC);
+    bytes[8..12].copy_from_slice(&VERSION.to_le_bytes());
+    let kind = if header.sparse {
+        KIND_SPARSE
+    } else {
+        KIND_DENSE
+    };
+    bytes[12..16].copy_from_slice(&kind.to_le_bytes());
+    bytes[16..20].copy_from_slice(&(header.dtype as u32).to_le_bytes());
+    bytes[20..24].copy_from_slice(&(header.index_size as u32).to_le_bytes());
+    bytes[24..32].copy_from_slice(&(header.nrows as u64).to_le_bytes());
+    bytes[32..40].copy_from_slice(&(header.ncols as u64).to_le_bytes());
+    bytes[40..48].copy_from_slice(&(header.nnz as u64).to_le_bytes());
+    writer.write_all(&bytes)
+}
+
+// writes the `len` elements produced by `iter` as a data section, followed by the padding
+fn write_section<T: bytemuck::Pod>(
+    mut writer: impl Write,
+    len: usize,
+    iter: impl Iterator<Item = T>,
+) -> std::io::Result<()> {
+    for value in iter {
+        writer.write_all(bytemuck::bytes_of(&value))?;
+    }
+    let bytes = len * core::mem::size_of::<T>();
+    let padding = padded(bytes).unwrap() - bytes;
+    writer.write_all(&[0u8; ALIGN][..padding])
+}
+
+/// Writes a dense matrix in the raw layout.
+pub fn write_dense<E: RawEntity>(writer: impl Write, mat: MatRef<'_, E>) -> std::io::Result<()> {
+    let mut writer = std::io::BufWriter::new(writer);
+    let (m, n) = (mat.nrows(), mat.ncols());
+    write_header(
+        &mut writer,
+        RawHeader {
+            sparse: false,
+            dtype: E::DTYPE,
+            index_size: 0,
+            nrows: m,
+            ncols: n,
+            nnz: m * n,
+        },
+    )?;
+    write_section(
+        &mut writer,
+        m * n,
+        (0..n).flat_map(|j| (0..m).map(move |i| mat.read(i, j))),
+    )?;
+    writer.flush()
+}
+
+/// Writes a sparse matrix in the raw layout. Uncompressed matrices are stored in compressed form.
+///
+/// # Panics
+///
+/// Panics if the row indices of `mat` are not sorted within each column.
+#[track_caller]
+pub fn write_sparse<I: Index, E: RawEntity>(
+    writer: impl Write,
+    mat: SparseColMatRef<'_, I, E>,
+) -> std::io::Result<()> {
+    let mut writer = std::io::BufWriter::new(writer);
+    let n = mat.ncols();
+    let nnz = mat.compute_nnz();
+    for j in 0..n {
+        let row_indices = mat.row_indices_of_col_raw(j);
+        crate::assert!(row_indices.windows(2).all(|w| w[0] <= w[1]));
+    }
+
+    write_header(
+        &mut writer,
+        RawHeader {
+            sparse: true,
+            dtype: E::DTYPE,
+            index_size: core::mem::size_of::<I>(),
+            nrows: mat.nrows(),
+            ncols: n,
+            nnz,
+        },
+    )?;
+    let mut ptr = 0usize;
+    write_section(
+        &mut writer,
+        n + 1,
+        core::iter::once(I::truncate(0)).chain((0..n).map(|j| {
+            ptr += mat.col_range(j).len();
+            I::truncate(ptr)
+        })),
+    )?;
+    write_section(
+        &mut writer,
+        nnz,
+        (0..n).flat_map(|j| mat.row_indices_of_col_raw(j).iter().copied()),
+    )?;
+    write_section(
+        &mut writer,
+        nnz,
+        (0..n).flat_map(|j| mat.values_of_col(j).iter().copied()),
+    )?;
+    writer.flush()
+}
+
+// returns the section of `len` elements starting at `offset`, and the offset of the next section
+fn section<T: bytemuck::Pod>(
+    bytes: &[u8],
+    offset: usize,
+    len: usize,
+) -> Result<(&[T], usize), RawError> {
+    let size = len
+        .checked_mul(core::mem::size_of::<T>())
+        .ok_or(RawError::Overflow)?;
+    let end = offset.checked_add(size).ok_or(RawError::Overflow)?;
+    if end > bytes.len() {
+        return Err(RawError::Truncated);
+    }
+    let data = bytemuck::try_cast_slice(&bytes[offset..end]).map_err(|_| RawError::Unaligned)?;
+    Ok((data, padded(end).ok_or(RawError::Overflow)?))
+}
+
+fn check_buffer(bytes: &[u8]) -> Result<(), RawError> {
+    if bytes.as_ptr().align_offset(ALIGN) != 0 {
+        return Err(RawError::Unaligned);
+    }
+    Ok(())
+}
+
+/// Returns a view over a dense matrix stored in the raw layout, without copying its values.
+pub fn view_dense<E: RawEntity>(bytes: &[u8]) -> Result<MatRef<'_, E>, RawError> {
+    check_buffer(bytes)?;
+    let header = read_header(bytes)?;
+    if header.sparse || header.dtype != E::DTYPE {
+        return Err(RawError::TypeMismatch);
+    }
+    let len = header
+        .nrows
+        .checked_mul(header.ncols)
+        .ok_or(RawError::Overflow)?;
+    if header.nnz != len {
+        return Err(RawError::InvalidHeader);
+    }
+    let (values, _) = section::<E>(bytes, HEADER_LEN, len)?;
+    Ok(from_column_major_slice::<E>(
+        values,
+        header.nrows,
+        header.ncols,
+    ))
+}
+
+/// Returns a view over a sparse matrix stored in the raw layout, without copying its indices or
+/// values.
+///
+/// The structure of the matrix is validated, so that invalid buffers are reported as an error
+/// instead of causing a panic or undefined behavior later on.
+pub fn view_sparse<I: Index, E: RawEntity>(
+    bytes: &[u8],
+) -> Result<SparseColMatRef<'_, I, E>, RawError> {
+    check_buffer(bytes)?;
+    let header = read_header(bytes)?;
+    if !header.sparse || header.dtype != E::DTYPE || header.index_size != core::mem::size_of::<I>()
+    {
+        return Err(RawError::TypeMismatch);
+    }
+    let (m, n, nnz) = (header.nrows, header.ncols, header.nnz);
+    let max = I::Signed::MAX.zx();
+    if m > max || n > max || nnz > max {
+        return Err(RawError::Overflow);
+    }
+
+    let (col_ptrs, offset) = section::<I>(bytes, HEADER_LEN, n + 1)?;
+    let (row_indices, offset) = section::<I>(bytes, offset, nnz)?;
+    let (values, _) = section::<E>(bytes, offset, nnz)?;
+
+    if col_ptrs[0].zx() != 0 || col_ptrs[n].zx() != nnz {
+        return Err(RawError::InvalidStructure);
+    }
+    for j in 0..n {
+        let (start, end) = (col_ptrs[j].zx(), col_ptrs[j + 1].zx());
+        if start > end || end > nnz {
+            return Err(RawError::InvalidStructure);
+        }
+        let row_indices = &row_indices[start..end];
+        if !row_indices.windows(2).all(|w| w[0] <= w[1])
+            || row_indices.last().map_or(false, |&i| i.zx() >= m)
+        {
+            return Err(RawError::InvalidStructure);
+        }
+    }
+
+    // SAFETY: the invariants were checked above
+    let symbolic =
+        unsafe { SymbolicSparseColMatRef::new_unchecked(m, n, col_ptrs, None, row_indices) };
+    Ok(SparseColMatRef::<'_, I, E>::new(symbolic, values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, sparse::SparseColMat, Mat};
+
+    // copies `bytes` into a buffer aligned to `ALIGN` bytes, as a memory map would provide
+    fn aligned(bytes: &[u8]) -> (Vec<u8>, usize) {
+        let mut buffer = vec![0u8; bytes.len() + ALIGN];
+        let offset = buffer.as_ptr().align_offset(ALIGN);
+        buffer[offset..][..bytes.len()].copy_from_slice(bytes);
+        (buffer, offset)
+    }
+
+    #[test]
+    fn test_raw_dense() {
+        let a = Mat::from_fn(5, 3, |i, j| c64::new(i as f64, j as f64));
+        let mut bytes = Vec::new();
+        write_dense(&mut bytes, a.as_ref()).unwrap();
+        assert!(bytes.len() % ALIGN == 0);
+
+        let (buffer, offset) = aligned(&bytes);
+        let data = &buffer[offset..][..bytes.len()];
+        let view = view_dense::<c64>(data).unwrap();
+        assert!(view == a);
+        assert!(view_dense::<f64>(data).unwrap_err() == RawError::TypeMismatch);
+        assert!(view_sparse::<u32, c64>(data).unwrap_err() == RawError::TypeMismatch);
+        assert!(view_dense::<c64>(&data[..HEADER_LEN + 8]).unwrap_err() == RawError::Truncated);
+        assert!(view_dense::<c64>(&buffer[offset + 1..]).unwrap_err() == RawError::Unaligned);
+    }
+
+    #[test]
+    fn test_raw_sparse() {
+        let a = SparseColMat::<u32, f64>::try_new_from_triplets(
+            4,
+            3,
+            &[(0, 0, 1.0), (3, 0, 2.0), (1, 2, 3.0), (2, 2, 4.0)],
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        write_sparse(&mut bytes, a.as_ref()).unwrap();
+
+        let (mut buffer, offset) = aligned(&bytes);
+        let view = view_sparse::<u32, f64>(&buffer[offset..][..bytes.len()]).unwrap();
+        assert!(view == a.as_ref());
+        assert!(
+            view_sparse::<u64, f64>(&buffer[offset..][..bytes.len()]).unwrap_err()
+                == RawError::TypeMismatch
+        );
+
+        // unsorted row indices are rejected
+        let row_indices = HEADER_LEN + ALIGN;
+        buffer[offset + row_indices..][..4].copy_from_slice(&3u32.to_le_bytes());
+        buffer[offset + row_indices + 4..][..4].copy_from_slice(&0u32.to_le_bytes());
+        assert!(
+            view_sparse::<u32, f64>(&buffer[offset..][..bytes.len()]).unwrap_err()
+                == RawError::InvalidStructure
+        );
+    }
+}
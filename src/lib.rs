@@ -158,8 +158,11 @@
 //!   as cpu feature detection at runtime.
 //! - `rayon`: enabled by default. Enables the `rayon` parallel backend and enables global
 //!   parallelism by default.
-//! - `serde`: Enables serialization and deserialization of [`Mat`].
+//! - `serde`: Enables serialization and deserialization of [`Mat`], [`Col`], [`Row`], the sparse
+//!   matrices, the permutations and the dense factorizations.
 //! - `npy`: Enables conversions to/from numpy's matrix file format.
+//! - `approx`: Implements the tolerance-based comparison traits of the `approx` crate for the
+//!   dense and sparse matrix types.
 //! - `perf-warn`: Produces performance warnings when matrix operations are called with suboptimal
 //! data layout.
 //! - `nightly`: Requires the nightly compiler. Enables experimental SIMD features such as AVX512.
@@ -192,10 +195,14 @@ pub use reborrow;
 /// Various utilities for low level implementations in generic code.
 pub mod utils;
 
+/// Banded matrix type.
+pub mod banded;
 /// Column vector type.
 pub mod col;
 /// Diagonal matrix type.
 pub mod diag;
+/// Hierarchical matrices (experimental).
+pub mod hmatrix;
 /// Matrix-free linear operator traits and algorithms.
 pub mod linop;
 /// Matrix type.
@@ -426,65 +433,7 @@ macro_rules! __perf_warn {
 #[doc(hidden)]
 #[track_caller]
 pub fn concat_impl<E: ComplexField>(blocks: &[&[(mat::MatRef<'_, E>, Conj)]]) -> mat::Mat<E> {
-    #[inline(always)]
-    fn count_total_columns<E: ComplexField>(block_row: &[(mat::MatRef<'_, E>, Conj)]) -> usize {
-        let mut out: usize = 0;
-        for (elem, _) in block_row.iter() {
-            out += elem.ncols();
-        }
-        out
-    }
-
-    #[inline(always)]
-    #[track_caller]
-    fn count_rows<E: ComplexField>(block_row: &[(mat::MatRef<'_, E>, Conj)]) -> usize {
-        let mut out: usize = 0;
-        for (i, (e, _)) in block_row.iter().enumerate() {
-            if i == 0 {
-                out = e.nrows();
-            } else {
-                assert!(e.nrows() == out);
-            }
-        }
-        out
-    }
-
-    // get size of result while doing checks
-    let mut n: usize = 0;
-    let mut m: usize = 0;
-    for row in blocks.iter() {
-        n += count_rows(row);
-    }
-    for (i, row) in blocks.iter().enumerate() {
-        let cols = count_total_columns(row);
-        if i == 0 {
-            m = cols;
-        } else {
-            assert!(cols == m);
-        }
-    }
-
-    let mut mat = mat::Mat::<E>::zeros(n, m);
-    let mut ni: usize = 0;
-    let mut mj: usize;
-    for row in blocks.iter() {
-        mj = 0;
-
-        for (elem, conj) in row.iter() {
-            let mut dst = mat
-                .as_mut()
-                .submatrix_mut(ni, mj, elem.nrows(), elem.ncols());
-            if *conj == Conj::No {
-                dst.copy_from(elem);
-            } else {
-                dst.copy_from(elem.conjugate());
-            }
-            mj += elem.ncols();
-        }
-        ni += row[0].0.nrows();
-    }
-
-    mat
+    mat::concat::block_impl(blocks.len(), |i| blocks[i], |block| block)
 }
 
 /// Concatenates the matrices in each row horizontally,
@@ -509,6 +458,59 @@ macro_rules! concat {
     };
 }
 
+/// Concatenates the matrices horizontally.
+///
+/// `hstack![a, b, c]` results in the matrix `[a | b | c]`. See also [`mat::hstack`].
+///
+/// ```
+/// use faer::{hstack, mat};
+///
+/// let a = mat![[1.0], [4.0]];
+/// let b = mat![[2.0, 3.0], [5.0, 6.0]];
+/// assert!(hstack![a, b] == mat![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// ```
+#[macro_export]
+macro_rules! hstack {
+    () => {
+        {
+            compile_error!("the element type of the matrix is ambiguous");
+        }
+    };
+
+    ($($v:expr),+ $(,)?) => {
+        {
+            $crate::concat_impl(&[&[$(($v).as_ref().canonicalize(),)+]])
+        }
+    };
+}
+
+/// Concatenates the matrices vertically.
+///
+/// `vstack![a, b]` results in the matrix with the rows of `a` followed by the rows of `b`. See
+/// also [`mat::vstack`].
+///
+/// ```
+/// use faer::{mat, vstack};
+///
+/// let a = mat![[1.0, 2.0]];
+/// let b = mat![[3.0, 4.0], [5.0, 6.0]];
+/// assert!(vstack![a, b] == mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+/// ```
+#[macro_export]
+macro_rules! vstack {
+    () => {
+        {
+            compile_error!("the element type of the matrix is ambiguous");
+        }
+    };
+
+    ($($v:expr),+ $(,)?) => {
+        {
+            $crate::concat_impl(&[$(&[($v).as_ref().canonicalize()],)+])
+        }
+    };
+}
+
 /// Creates a [`col::Col`] containing the arguments.
 ///
 /// ```
@@ -900,6 +902,9 @@ pub mod io;
 #[cfg(feature = "serde")]
 mod serde;
 
+#[cfg(feature = "approx")]
+mod approx;
+
 /// faer prelude. Includes useful types and traits for solving linear systems.
 pub mod prelude {
     pub use crate::{
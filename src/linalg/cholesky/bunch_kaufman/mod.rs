@@ -5,6 +5,7 @@
 
 use crate::{
     linalg::{
+        cholesky::Inertia,
         matmul::triangular::{self, BlockStructure},
         temp_mat_req, temp_mat_uninit,
         triangular_solve::{
@@ -30,6 +31,11 @@ pub mod compute {
     pub enum PivotingStrategy {
         /// Diagonal pivoting.
         Diagonal,
+        /// Rook pivoting, which searches for an off-diagonal pivot that is the largest element in
+        /// absolute value of both its row and its column. This bounds the entries of $L$, at the
+        /// cost of more comparisons. The decomposition is always computed with the unblocked
+        /// algorithm.
+        Rook,
     }
 
     /// Tuning parameters for the decomposition.
@@ -434,7 +440,7 @@ pub mod compute {
             if k_step == 1 {
                 pivots[k] = I::from_signed(truncate(kp));
             } else {
-                pivots[k] = I::from_signed(truncate(!kp));
+                pivots[k] = I::from_signed(truncate(!k));
                 pivots[k + 1] = I::from_signed(truncate(!kp));
             }
 
@@ -485,6 +491,7 @@ pub mod compute {
         regularization: BunchKaufmanRegularization<'_, E>,
         pivots: &mut [I],
         alpha: E::Real,
+        rook: bool,
     ) -> (usize, usize) {
         let truncate = <I::Signed as SignedIndex>::truncate;
 
@@ -508,6 +515,8 @@ pub mod compute {
             };
 
             let mut k_step = 1;
+            // the 2x2 pivot is made of the rows `p` and `kp`
+            let mut p = k;
 
             let abs_akk = a.read(k, k).faer_abs();
             let imax;
@@ -543,6 +552,36 @@ pub mod compute {
             } else {
                 if abs_akk >= colmax.faer_mul(alpha) {
                     kp = k;
+                } else if rook {
+                    let mut imax = imax;
+                    let mut colmax = colmax;
+                    loop {
+                        // largest off-diagonal element in the row and column `imax`
+                        let (_, j, mut rowmax) =
+                            best_score_idx(a.rb().row(imax).subcols(k, imax - k).as_2d()).unwrap();
+                        let mut jmax = j + k;
+                        if let Some((i, _, score)) =
+                            best_score_idx(a.rb().subrows(imax + 1, n - imax - 1).col(imax).as_2d())
+                        {
+                            if score > rowmax {
+                                rowmax = score;
+                                jmax = i + imax + 1;
+                            }
+                        }
+
+                        if a.read(imax, imax).faer_real().faer_abs() >= alpha.faer_mul(rowmax) {
+                            kp = imax;
+                            break;
+                        } else if p == jmax || rowmax <= colmax {
+                            kp = imax;
+                            k_step = 2;
+                            break;
+                        } else {
+                            p = imax;
+                            colmax = rowmax;
+                            imax = jmax;
+                        }
+                    }
                 } else {
                     let rowmax = max(
                         best_score(a.rb().row(imax).subcols(k, imax - k).as_2d()),
@@ -559,6 +598,17 @@ pub mod compute {
                     }
                 }
 
+                if k_step == 2 && p != k {
+                    pivot_count += 1;
+                    swap_cols(a.rb_mut().subrows_mut(p + 1, n - p - 1), k, p);
+                    for j in k + 1..p {
+                        swap_elems_conj(a.rb_mut(), j, k, p, j);
+                    }
+
+                    a.write(p, k, a.read(p, k).faer_conj());
+                    swap_elems(a.rb_mut(), k, k, p, p);
+                }
+
                 let kk = k + k_step - 1;
 
                 if kp != kk {
@@ -700,7 +750,7 @@ pub mod compute {
             if k_step == 1 {
                 pivots[k] = I::from_signed(truncate(kp));
             } else {
-                pivots[k] = I::from_signed(truncate(!kp));
+                pivots[k] = I::from_signed(truncate(!p));
                 pivots[k + 1] = I::from_signed(truncate(!kp));
             }
 
@@ -735,8 +785,10 @@ pub mod compute {
         while i < n {
             let p = pivots[i].to_signed().sx();
             if (p as isize) < 0 {
-                let p = !p;
-                swap_rows(a.rb_mut().subcols_mut(0, i), i + 1, p);
+                let p0 = !p;
+                let p1 = !pivots[i + 1].to_signed().sx();
+                swap_rows(a.rb_mut().subcols_mut(0, i), i, p0);
+                swap_rows(a.rb_mut().subcols_mut(0, i), i + 1, p1);
                 i += 2;
             } else {
                 swap_rows(a.rb_mut().subcols_mut(0, i), i, p);
@@ -754,7 +806,7 @@ pub mod compute {
     ) -> Result<StackReq, SizeOverflow> {
         let _ = parallelism;
         let mut bs = params.blocksize;
-        if bs < 2 || dim <= bs {
+        if bs < 2 || dim <= bs || matches!(params.pivoting, PivotingStrategy::Rook) {
             bs = 0;
         }
         StackReq::try_new::<I>(dim)?.try_and(temp_mat_req::<E>(dim, bs)?)
@@ -823,7 +875,7 @@ pub mod compute {
         let (pivots, stack) = stack.make_raw::<I>(n);
 
         let mut bs = params.blocksize;
-        if bs < 2 || n <= bs {
+        if bs < 2 || n <= bs || matches!(params.pivoting, PivotingStrategy::Rook) {
             bs = 0;
         }
         let mut work = temp_mat_uninit(n, bs, stack).0;
@@ -859,6 +911,7 @@ pub mod compute {
                     regularization,
                     &mut pivots[k..],
                     alpha,
+                    matches!(params.pivoting, PivotingStrategy::Rook),
                 );
                 kb = n - k;
             }
@@ -886,8 +939,10 @@ pub mod compute {
         while i < n {
             let p = pivots[i].to_signed().sx();
             if (p as isize) < 0 {
-                let p = !p;
-                perm.swap(i + 1, p);
+                let p0 = !p;
+                let p1 = !pivots[i + 1].to_signed().sx();
+                perm.swap(i, p0);
+                perm.swap(i + 1, p1);
                 i += 2;
             } else {
                 perm.swap(i, p);
@@ -908,6 +963,61 @@ pub mod compute {
     }
 }
 
+/// Computes the inertia of the matrix from its Bunch-Kaufman factors, as computed by
+/// [`compute::cholesky_in_place`].
+///
+/// Pivots that are exactly zero are counted as zero eigenvalues.
+///
+/// # Panics
+/// Panics if the dimensions of `matrix` and `subdiag` don't match.
+#[track_caller]
+pub fn inertia<E: ComplexField>(matrix: MatRef<'_, E>, subdiag: MatRef<'_, E>) -> Inertia {
+    let mut inertia = Inertia::default();
+    accumulate_inertia(&mut inertia, matrix, subdiag);
+    inertia
+}
+
+/// Adds the inertia of the block diagonal factor stored in `matrix` and `subdiag` to `inertia`.
+#[track_caller]
+pub(crate) fn accumulate_inertia<E: ComplexField>(
+    inertia: &mut Inertia,
+    matrix: MatRef<'_, E>,
+    subdiag: MatRef<'_, E>,
+) {
+    crate::assert!(all(
+        matrix.nrows() == matrix.ncols(),
+        subdiag.nrows() == matrix.nrows(),
+        subdiag.ncols() == 1,
+    ));
+    let n = matrix.nrows();
+    let zero = E::Real::faer_zero();
+
+    // the diagonal blocks of the factorization are stored as their inverses, which have the same
+    // inertia
+    let mut j = 0;
+    while j < n {
+        if subdiag.read(j, 0) == E::faer_zero() {
+            inertia.push_pivot(matrix.read(j, j).faer_real());
+            j += 1;
+        } else {
+            let a = matrix.read(j, j).faer_real();
+            let c = matrix.read(j + 1, j + 1).faer_real();
+            let det = a.faer_mul(c).faer_sub(subdiag.read(j, 0).faer_abs2());
+            if det < zero {
+                inertia.positive += 1;
+                inertia.negative += 1;
+            } else if det > zero {
+                inertia.push_pivot(a);
+                inertia.push_pivot(a);
+            } else {
+                inertia.push_pivot(zero);
+                inertia.push_pivot(a.faer_add(c));
+            }
+            j += 2;
+        }
+    }
+}
+
 /// Solving a linear system using the decomposition.
 pub mod solve {
     use super::*;
@@ -1150,4 +1260,115 @@ mod tests {
             assert!(max < 1e-9);
         }
     }
+
+    #[test]
+    fn test_rook() {
+        use crate::{linalg::solvers::Lblt, sparse::linalg::solvers::SpSolver, Side};
+
+        let params = BunchKaufmanParams {
+            pivoting: compute::PivotingStrategy::Rook,
+            blocksize: 32,
+        };
+        for n in [1, 3, 6, 19, 100] {
+            let a = Mat::<c64>::from_fn(n, n, |_, _| c64::new(random(), random()));
+            let a = &a + a.adjoint();
+            let rhs = Mat::<c64>::from_fn(n, 2, |_, _| c64::new(random(), random()));
+
+            let lblt = Lblt::new_with_params(a.as_ref(), Side::Lower, params);
+            let x = lblt.solve(&rhs);
+            assert!((&a * &x - &rhs).norm_max() < 1e-9);
+            let x = lblt.solve_conj(&rhs);
+            assert!((a.conjugate() * &x - &rhs).norm_max() < 1e-9);
+        }
+
+        // saddle point matrix with a zero block, which requires 2x2 pivots
+        let (n, m) = (8, 3);
+        let h = Mat::<f64>::from_fn(n, n, |_, _| random());
+        let h = &h * h.transpose() + Mat::<f64>::identity(n, n);
+        let b = Mat::<f64>::from_fn(m, n, |_, _| random());
+        let mut kkt = Mat::<f64>::zeros(n + m, n + m);
+        kkt.as_mut().submatrix_mut(0, 0, n, n).copy_from(&h);
+        kkt.as_mut().submatrix_mut(n, 0, m, n).copy_from(&b);
+        kkt.as_mut()
+            .submatrix_mut(0, n, n, m)
+            .copy_from(b.transpose());
+
+        let rhs = Mat::<f64>::from_fn(n + m, 1, |_, _| random());
+        let lblt = Lblt::new_with_params(kkt.as_ref(), Side::Lower, params);
+        let x = lblt.solve(&rhs);
+        assert!((&kkt * &x - &rhs).norm_max() < 1e-9);
+
+        let inertia = lblt.inertia();
+        assert!(inertia.positive == n);
+        assert!(inertia.negative == m);
+        assert!(inertia.zero == 0);
+    }
+
+    #[test]
+    fn test_inertia() {
+        use crate::{linalg::solvers::Lblt, Side};
+
+        for pivoting in [
+            compute::PivotingStrategy::Diagonal,
+            compute::PivotingStrategy::Rook,
+        ] {
+            for n in [1, 4, 17, 80] {
+                let a = Mat::<f64>::from_fn(n, n, |_, _| random::<f64>() - 0.5);
+                let a = &a + a.transpose();
+                let params = BunchKaufmanParams {
+                    pivoting,
+                    blocksize: 16,
+                };
+                let inertia = Lblt::new_with_params(a.as_ref(), Side::Lower, params).inertia();
+
+                let eigs = a.selfadjoint_eigenvalues(Side::Lower);
+                let positive = eigs.iter().filter(|&&x| x > 0.0).count();
+                assert!(inertia.positive == positive);
+                assert!(inertia.negative == n - positive);
+                assert!(inertia.zero == 0);
+            }
+        }
+
+        // singular matrix
+        let a = crate::mat![[1.0, 0.0, 0.0], [0.0, 0.0, 0.0], [0.0, 0.0, -2.0f64]];
+        let inertia = Lblt::new(a.as_ref(), Side::Lower).inertia();
+        assert!(inertia.positive == 1);
+        assert!(inertia.negative == 1);
+        assert!(inertia.zero == 1);
+    }
+
+    #[test]
+    fn test_shifted_inertia() {
+        use crate::{
+            linalg::cholesky::{shifted_inertia, shifted_inertia_req},
+            Side,
+        };
+
+        for n in [1, 5, 33] {
+            let a = Mat::<f64>::from_fn(n, n, |_, _| random::<f64>() - 0.5);
+            let a = &a + a.transpose();
+            let mut eigs = a.selfadjoint_eigenvalues(Side::Lower);
+            eigs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let mut mem =
+                GlobalPodBuffer::new(shifted_inertia_req::<f64>(n, Parallelism::None).unwrap());
+            for k in 0..=n {
+                // shift halfway between two consecutive eigenvalues
+                let shift = match k {
+                    0 => eigs[0] - 1.0,
+                    k if k == n => eigs[n - 1] + 1.0,
+                    k => (eigs[k - 1] + eigs[k]) / 2.0,
+                };
+                let inertia = shifted_inertia(
+                    a.as_ref(),
+                    shift,
+                    Parallelism::None,
+                    PodStack::new(&mut mem),
+                );
+                assert!(inertia.negative == k);
+                assert!(inertia.positive == n - k);
+                assert!(inertia.zero == 0);
+            }
+        }
+    }
 }
@@ -14,6 +14,26 @@ pub mod solve;
 /// Updating the decomposition.
 pub mod update;
 
+use crate::{linalg::cholesky::Inertia, ComplexField, MatRef};
+
+/// Computes the inertia of the matrix from its $LDL^H$ factors, as computed by
+/// [`compute::raw_cholesky_in_place`], i.e., the signs of the diagonal elements of $D$.
+///
+/// Pivots that are exactly zero are counted as zero eigenvalues.
+///
+/// # Panics
+/// Panics if `cholesky_factors` is not square.
+#[track_caller]
+pub fn inertia<E: ComplexField>(cholesky_factors: MatRef<'_, E>) -> Inertia {
+    crate::assert!(cholesky_factors.nrows() == cholesky_factors.ncols());
+    let mut inertia = Inertia::default();
+    // the inverses of the diagonal elements of D are stored on the diagonal
+    for i in 0..cholesky_factors.nrows() {
+        inertia.push_pivot(cholesky_factors.read(i, i).faer_real());
+    }
+    inertia
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{complex_native::c64, mat, Conj};
@@ -539,4 +559,35 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_inertia() {
+        for (n0, n1) in [(0, 3), (4, 0), (7, 5), (40, 24)] {
+            let n = n0 + n1;
+            // quasi-definite matrix with `n0` positive and `n1` negative eigenvalues
+            let p = random_positive_definite(n0);
+            let q = random_positive_definite(n1);
+            let b = Mat::from_fn(n1, n0, |_, _| random());
+            let mut a = Mat::<E>::zeros(n, n);
+            a.as_mut().submatrix_mut(0, 0, n0, n0).copy_from(&p);
+            a.as_mut().submatrix_mut(n0, n0, n1, n1).copy_from(-&q);
+            a.as_mut().submatrix_mut(n0, 0, n1, n0).copy_from(&b);
+
+            raw_cholesky_in_place(
+                a.as_mut(),
+                Default::default(),
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    raw_cholesky_in_place_req::<E>(n, Parallelism::None, Default::default())
+                        .unwrap(),
+                )),
+                Default::default(),
+            );
+
+            let inertia = inertia(a.as_ref());
+            assert!(inertia.positive == n0);
+            assert!(inertia.negative == n1);
+            assert!(inertia.zero == 0);
+        }
+    }
 }
@@ -1,7 +1,14 @@
 //! Low level implementation of the various Cholesky-like decompositions.
 
-use crate::{assert, perm::PermRef, ComplexField, Index, MatRef, SignedIndex};
+use crate::{
+    assert,
+    linalg::{temp_mat_req, temp_mat_uninit},
+    perm::PermRef,
+    ComplexField, Entity, Index, MatRef, Parallelism, RealField, SignedIndex,
+};
 use core::cmp::Ordering;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
 
 pub mod bunch_kaufman;
 pub mod ldlt_diagonal;
@@ -9,6 +16,106 @@ pub mod llt;
 
 pub(crate) mod piv_llt;
 
+/// Inertia of a Hermitian matrix, i.e., the number of its positive, negative and zero
+/// eigenvalues.
+///
+/// By Sylvester's law of inertia, it is equal to the inertia of the (block) diagonal factor of
+/// an $LDL^H$ or $LBL^H$ decomposition of the matrix.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Inertia {
+    /// Number of positive eigenvalues.
+    pub positive: usize,
+    /// Number of negative eigenvalues.
+    pub negative: usize,
+    /// Number of zero eigenvalues.
+    pub zero: usize,
+}
+
+impl Inertia {
+    /// Counts a pivot of the diagonal factor, given either its value or its inverse. Zero pivots
+    /// have a non-finite inverse.
+    #[inline]
+    pub(crate) fn push_pivot<R: RealField>(&mut self, value: R) {
+        let zero = R::faer_zero();
+        if !value.faer_is_finite() || value == zero {
+            self.zero += 1;
+        } else if value > zero {
+            self.positive += 1;
+        } else {
+            self.negative += 1;
+        }
+    }
+}
+
+/// Computes the size and alignment of required workspace for computing the inertia of a shifted
+/// Hermitian matrix with [`shifted_inertia`].
+pub fn shifted_inertia_req<E: Entity>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<E>(dim, dim)?,
+        temp_mat_req::<E>(dim, 1)?,
+        StackReq::try_new::<usize>(dim)?,
+        StackReq::try_new::<usize>(dim)?,
+        bunch_kaufman::compute::cholesky_in_place_req::<usize, E>(
+            dim,
+            parallelism,
+            Default::default(),
+        )?,
+    ])
+}
+
+/// Computes the inertia of $A - \sigma I$, where $A$ is the Hermitian matrix whose lower triangular
+/// half is stored in `matrix`, using a Bunch-Kaufman decomposition.
+///
+/// By Sylvester's law of inertia, `negative` is the number of eigenvalues of $A$ that are less than
+/// $\sigma$, and `positive` is the number of those that are greater than $\sigma$. Counting the
+/// eigenvalues in an interval this way is the building block of bisection eigenvalue algorithms.
+///
+/// # Panics
+///
+/// Panics if the input matrix is not square.
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`shifted_inertia_req`]).
+#[track_caller]
+pub fn shifted_inertia<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    shift: E::Real,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Inertia {
+    assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+
+    let (mut factors, stack) = temp_mat_uninit::<E>(n, n, stack);
+    let (mut subdiag, stack) = temp_mat_uninit::<E>(n, 1, stack);
+    let (perm, stack) = stack.make_raw::<usize>(n);
+    let (perm_inv, stack) = stack.make_raw::<usize>(n);
+
+    factors.copy_from_triangular_lower(matrix);
+    for i in 0..n {
+        factors.write(
+            i,
+            i,
+            E::faer_from_real(factors.read(i, i).faer_real().faer_sub(shift)),
+        );
+    }
+
+    bunch_kaufman::compute::cholesky_in_place(
+        factors.rb_mut(),
+        subdiag.rb_mut(),
+        Default::default(),
+        perm,
+        perm_inv,
+        parallelism,
+        stack,
+        Default::default(),
+    );
+    bunch_kaufman::inertia(factors.rb(), subdiag.rb())
+}
+
 /// Computes a permutation that reduces the chance of numerical errors during the $LDL^H$
 /// factorization with diagonal $D$, then stores the result in `perm_indices` and
 /// `perm_inv_indices`.
@@ -0,0 +1,264 @@
+//! Estimation of the condition number of a matrix from an existing factorization.
+//!
+//! Computing the condition number exactly requires either the inverse of the matrix or its
+//! singular values. When a factorization of the matrix is already available, for example after
+//! solving a linear system, the 1-norm of the inverse can instead be estimated using a handful of
+//! solves with the matrix and its adjoint, using Hager's method as refined by Higham (the same
+//! algorithm as LAPACK's `xLACN2`, used by `xGECON` and `xPOCON`).
+//!
+//! The estimate is a lower bound of the exact value, and is usually within a factor of three of
+//! it, which is more than enough to judge whether a computed solution can be trusted.
+//!
+//! The estimators accept any [`SpSolverCore`], which includes the dense decompositions as well as
+//! the sparse ones from [`crate::sparse::linalg::solvers`]. In the sparse case, only sparse
+//! triangular solves with the factors are performed, and the matrix is never densified.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::cond_est, mat};
+//!
+//! let a = mat![[4.0, 1.0, 0.5], [1.0, 3.0, 1.0], [0.5, 1.0, 2.0f64]];
+//! let lu = a.partial_piv_lu();
+//!
+//! let cond = cond_est::cond1_est(&lu, cond_est::norm1(a.as_ref()));
+//! assert!(cond >= 1.0);
+//! ```
+
+use crate::{
+    col::Col, mat::MatRef, sparse::linalg::solvers::SpSolverCore, ComplexField, Conj, RealField,
+};
+
+/// Maximum number of iterations of the estimator. LAPACK uses the same value, and in practice the
+/// iteration almost always stops after two or three steps.
+const MAX_ITER: usize = 5;
+
+/// Returns the 1-norm of `mat`, i.e., its maximum absolute column sum.
+///
+/// This is the norm that is expected by [`cond1_est`].
+pub fn norm1<E: ComplexField>(mat: MatRef<'_, E>) -> E::Real {
+    let mut norm = E::Real::faer_zero();
+    for j in 0..mat.ncols() {
+        let col_norm = mat.col(j).norm_l1();
+        if col_norm > norm {
+            norm = col_norm;
+        }
+    }
+    norm
+}
+
+/// Returns an estimate of the 1-norm of the inverse of the matrix decomposed by `solver`.
+///
+/// The estimate is computed using a few solves with the matrix and its adjoint, and is a lower
+/// bound of the exact value, up to rounding errors.
+///
+/// # Panics
+/// Panics if the decomposed matrix is not square.
+#[track_caller]
+pub fn inverse_norm1_est<E: ComplexField, S: ?Sized + SpSolverCore<E>>(solver: &S) -> E::Real {
+    let n = solver.nrows();
+    crate::assert!(solver.ncols() == n);
+    if n == 0 {
+        return E::Real::faer_zero();
+    }
+
+    let zero = E::Real::faer_zero();
+    let n_inv = from_usize::<E::Real>(n).faer_inv();
+
+    let solve = |x: &mut Col<E>| solver.solve_in_place_with_conj_impl(x.as_2d_mut(), Conj::No);
+    let solve_adjoint =
+        |x: &mut Col<E>| solver.solve_transpose_in_place_with_conj_impl(x.as_2d_mut(), Conj::Yes);
+
+    let mut x = Col::<E>::from_fn(n, |_| E::faer_from_real(n_inv));
+    let mut est = zero;
+    let mut prev_j = usize::MAX;
+
+    for iter in 0..MAX_ITER {
+        solve(&mut x);
+        let new_est = x.norm_l1();
+        // the estimate is monotonic, so stop as soon as it stops increasing
+        if iter > 0 && new_est <= est {
+            break;
+        }
+        est = new_est;
+        if n == 1 {
+            return est;
+        }
+
+        // x = sign(A⁻¹ x)
+        for i in 0..n {
+            let xi = x.read(i);
+            let abs = xi.faer_abs();
+            x.write(
+                i,
+                if abs == zero {
+                    E::faer_one()
+                } else {
+                    xi.faer_scale_real(abs.faer_inv())
+                },
+            );
+        }
+
+        // z = A⁻ᴴ sign(A⁻¹ x), then restart from the unit vector with the largest component of z
+        solve_adjoint(&mut x);
+        let mut j = 0;
+        let mut max = zero;
+        for i in 0..n {
+            let abs = x.read(i).faer_abs();
+            if abs > max {
+                max = abs;
+                j = i;
+            }
+        }
+        if j == prev_j {
+            break;
+        }
+        if iter > 0 {
+            // local maximum reached when |z_j| <= Re(zᴴ x), where x is the previous unit vector
+            let zx = x.read(prev_j).faer_real();
+            if max <= zx {
+                break;
+            }
+        }
+        prev_j = j;
+
+        x.fill_zero();
+        x.write(j, E::faer_one());
+    }
+
+    // alternative estimate, which guards against the cases where the iteration is fooled by
+    // cancellation. see Higham, "FORTRAN codes for estimating the one-norm of a real or complex
+    // matrix", 1988
+    let mut alt = Col::<E>::from_fn(n, |i| {
+        let mag = E::Real::faer_one()
+            .faer_add(from_usize::<E::Real>(i).faer_div(from_usize::<E::Real>(n - 1)));
+        E::faer_from_real(if i % 2 == 0 { mag } else { mag.faer_neg() })
+    });
+    solve(&mut alt);
+    let alt_est = alt
+        .norm_l1()
+        .faer_scale_power_of_two(E::Real::faer_from_f64(2.0))
+        .faer_div(from_usize::<E::Real>(3 * n));
+
+    if alt_est > est {
+        alt_est
+    } else {
+        est
+    }
+}
+
+/// Returns an estimate of the condition number in the 1-norm of the matrix decomposed by
+/// `solver`, given the 1-norm of the matrix (see [`norm1`]).
+///
+/// This is the product of `norm1` and [`inverse_norm1_est`], and is infinite if the
+/// decomposed matrix is numerically singular.
+///
+/// # Panics
+/// Panics if the decomposed matrix is not square.
+#[track_caller]
+pub fn cond1_est<E: ComplexField, S: ?Sized + SpSolverCore<E>>(
+    solver: &S,
+    norm1: E::Real,
+) -> E::Real {
+    let inv_norm = inverse_norm1_est(solver);
+    if inv_norm.faer_is_finite() {
+        norm1.faer_mul(inv_norm)
+    } else {
+        E::Real::faer_one().faer_div(E::Real::faer_zero())
+    }
+}
+
+/// Returns an estimate of the reciprocal of the condition number in the 1-norm of the matrix
+/// decomposed by `solver`, given the 1-norm of the matrix (see [`norm1`]).
+///
+/// This is the quantity computed by LAPACK's `xGECON` and `xPOCON`. Unlike [`cond1_est`], it is
+/// zero rather than infinite for numerically singular matrices, and can be compared directly to
+/// the machine epsilon.
+///
+/// # Panics
+/// Panics if the decomposed matrix is not square.
+#[track_caller]
+pub fn rcond1_est<E: ComplexField, S: ?Sized + SpSolverCore<E>>(
+    solver: &S,
+    norm1: E::Real,
+) -> E::Real {
+    let zero = E::Real::faer_zero();
+    if solver.nrows() == 0 {
+        return E::Real::faer_one();
+    }
+    if norm1 == zero {
+        return zero;
+    }
+    let inv_norm = inverse_norm1_est(solver);
+    if inv_norm == zero {
+        E::Real::faer_one()
+    } else if !inv_norm.faer_is_finite() {
+        zero
+    } else {
+        E::Real::faer_one().faer_div(inv_norm).faer_div(norm1)
+    }
+}
+
+#[inline]
+fn from_usize<E: RealField>(n: usize) -> E {
+    E::faer_from_f64(n as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Mat, Side};
+
+    fn exact_inverse_norm1<E: ComplexField>(a: &Mat<E>) -> E::Real {
+        use crate::linalg::solvers::SolverCore;
+        norm1(a.partial_piv_lu().inverse().as_ref())
+    }
+
+    #[test]
+    fn test_cond_est_lu() {
+        for n in [1, 2, 5, 20, 50] {
+            let a = Mat::<f64>::from_fn(n, n, |_, _| rand::random::<f64>() - 0.5);
+            let exact = exact_inverse_norm1(&a);
+
+            for est in [
+                inverse_norm1_est(&a.partial_piv_lu()),
+                inverse_norm1_est(&a.full_piv_lu()),
+            ] {
+                assert!(est <= exact * (1.0 + 1e-8));
+                assert!(est >= exact / 10.0);
+            }
+
+            let cond = cond1_est(&a.partial_piv_lu(), norm1(a.as_ref()));
+            let rcond = rcond1_est(&a.partial_piv_lu(), norm1(a.as_ref()));
+            assert!(cond >= 1.0 - 1e-12);
+            assert!((cond * rcond - 1.0).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_cond_est_cholesky() {
+        let n = 20;
+        let b = Mat::<c64>::from_fn(n, n, |_, _| c64::new(rand::random(), rand::random()));
+        let a = &b * b.adjoint() + Mat::<c64>::identity(n, n);
+        let exact = exact_inverse_norm1(&a);
+
+        let est = inverse_norm1_est(&a.cholesky(Side::Lower).unwrap());
+        assert!(est <= exact * (1.0 + 1e-8));
+        assert!(est >= exact / 10.0);
+    }
+
+    #[test]
+    fn test_cond_est_ill_conditioned() {
+        // the hilbert matrix is notoriously ill conditioned, with κ₁ ≈ 2.9e7 for n = 6
+        let n = 6;
+        let a = Mat::<f64>::from_fn(n, n, |i, j| 1.0 / (i + j + 1) as f64);
+        let cond = cond1_est(&a.partial_piv_lu(), norm1(a.as_ref()));
+        assert!(cond > 1e7);
+        assert!(cond < 1e8);
+
+        // exactly singular matrix
+        let a = Mat::<f64>::from_fn(3, 3, |i, j| (i + j) as f64);
+        assert!(rcond1_est(&a.partial_piv_lu(), norm1(a.as_ref())) == 0.0);
+        assert!(cond1_est(&a.partial_piv_lu(), norm1(a.as_ref())) == f64::INFINITY);
+    }
+}
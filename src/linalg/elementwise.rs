@@ -0,0 +1,753 @@
+//! Elementwise math functions on real matrices, columns and rows.
+//!
+//! The functions in [`UnaryFn`] are applied to each element of a matrix with [`apply_in_place`]
+//! or [`apply`], which accept any [`Mat`](crate::Mat), [`Col`](crate::Col) or
+//! [`Row`](crate::Row) and their views. The elementary functions are evaluated by a
+//! [`MathPolicy`], which decides the tradeoff between speed and accuracy. [`StdMath`] uses the
+//! implementations from the standard library, and a custom policy can plug in faster
+//! approximations, such as vectorized polynomial approximations of `exp` for activation functions.
+//!
+//! Contiguous columns or rows are processed one SIMD register at a time, and other layouts one
+//! element at a time.
+//!
+//! With the `std` feature, the shorthands [`exp_in_place`], [`exp`], [`ln_in_place`], ... apply
+//! a single function with [`StdMath`].
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::elementwise::*, mat};
+//!
+//! let mut a = mat![[0.0, 1.0], [4.0, 9.0f64]];
+//! sqrt_in_place(&mut a);
+//! assert!(a == mat![[0.0, 1.0], [2.0, 3.0]]);
+//!
+//! let mut b = a.clone();
+//! apply(&mut b, &a, UnaryFn::Powf(2.0), &StdMath);
+//! assert!(b == mat![[0.0, 1.0], [4.0, 9.0]]);
+//! ```
+
+use crate::{
+    assert,
+    linalg::entity::{from_copy, into_copy, pulp, SimdCtx, SimdGroupFor, SimdUnitFor, UnitFor},
+    mat::{As2D, As2DMut, MatMut, MatRef},
+    unzipped,
+    utils::{
+        simd::SimdFor,
+        slice::{SliceGroup, SliceGroupMut},
+    },
+    zipped, RealField,
+};
+use core::iter::zip;
+use reborrow::*;
+
+/// Applies `f` to each lane of `x`.
+///
+/// This is how the vectorized methods of [`MathPolicy`] are implemented by default, and it can be
+/// used by policies that only vectorize some of the functions.
+#[inline(always)]
+pub fn map_lanes<E: RealField, S: pulp::Simd>(
+    x: SimdGroupFor<E, S>,
+    mut f: impl FnMut(E) -> E,
+) -> SimdGroupFor<E, S> {
+    let mut x = from_copy::<E, _>(x);
+    let mut lanes = SliceGroupMut::<'_, E>::new(E::faer_map(
+        E::faer_as_mut(&mut x),
+        #[inline(always)]
+        |x| bytemuck::cast_slice_mut::<SimdUnitFor<E, S>, UnitFor<E>>(core::slice::from_mut(x)),
+    ));
+    for i in 0..lanes.len() {
+        let value = f(lanes.read(i));
+        lanes.write(i, value);
+    }
+    into_copy::<E, _>(x)
+}
+
+/// Implementation of the elementary functions used by [`apply`] and [`apply_in_place`].
+///
+/// The `simd_*` methods are used on the SIMD registers of contiguous data, and the scalar methods
+/// on the remaining elements. By default, the `simd_*` methods evaluate the scalar method on each
+/// lane, and they can be overridden with vectorized implementations.
+pub trait MathPolicy<E: RealField>: Sync {
+    /// Returns $e^x$.
+    fn exp(&self, x: E) -> E;
+    /// Returns the natural logarithm of `x`.
+    fn ln(&self, x: E) -> E;
+    /// Returns the square root of `x`.
+    fn sqrt(&self, x: E) -> E;
+    /// Returns the hyperbolic tangent of `x`.
+    fn tanh(&self, x: E) -> E;
+    /// Returns `x` raised to the power `y`.
+    fn powf(&self, x: E, y: E) -> E;
+
+    /// Returns $e^x$ for each lane of `x`.
+    #[inline(always)]
+    fn simd_exp<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        let _ = simd;
+        map_lanes(x, |x| self.exp(x))
+    }
+    /// Returns the natural logarithm of each lane of `x`.
+    #[inline(always)]
+    fn simd_ln<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        let _ = simd;
+        map_lanes(x, |x| self.ln(x))
+    }
+    /// Returns the square root of each lane of `x`.
+    #[inline(always)]
+    fn simd_sqrt<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        let _ = simd;
+        map_lanes(x, |x| self.sqrt(x))
+    }
+    /// Returns the hyperbolic tangent of each lane of `x`.
+    #[inline(always)]
+    fn simd_tanh<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        let _ = simd;
+        map_lanes(x, |x| self.tanh(x))
+    }
+    /// Returns each lane of `x` raised to the power `y`.
+    #[inline(always)]
+    fn simd_powf<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+        y: E,
+    ) -> SimdGroupFor<E, S> {
+        let _ = simd;
+        map_lanes(x, |x| self.powf(x, y))
+    }
+}
+
+impl<E: RealField, P: ?Sized + MathPolicy<E>> MathPolicy<E> for &P {
+    #[inline]
+    fn exp(&self, x: E) -> E {
+        (**self).exp(x)
+    }
+    #[inline]
+    fn ln(&self, x: E) -> E {
+        (**self).ln(x)
+    }
+    #[inline]
+    fn sqrt(&self, x: E) -> E {
+        (**self).sqrt(x)
+    }
+    #[inline]
+    fn tanh(&self, x: E) -> E {
+        (**self).tanh(x)
+    }
+    #[inline]
+    fn powf(&self, x: E, y: E) -> E {
+        (**self).powf(x, y)
+    }
+
+    #[inline(always)]
+    fn simd_exp<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        (**self).simd_exp(simd, x)
+    }
+    #[inline(always)]
+    fn simd_ln<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        (**self).simd_ln(simd, x)
+    }
+    #[inline(always)]
+    fn simd_sqrt<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        (**self).simd_sqrt(simd, x)
+    }
+    #[inline(always)]
+    fn simd_tanh<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+    ) -> SimdGroupFor<E, S> {
+        (**self).simd_tanh(simd, x)
+    }
+    #[inline(always)]
+    fn simd_powf<S: pulp::Simd>(
+        &self,
+        simd: SimdFor<E, S>,
+        x: SimdGroupFor<E, S>,
+        y: E,
+    ) -> SimdGroupFor<E, S> {
+        (**self).simd_powf(simd, x, y)
+    }
+}
+
+/// Policy that evaluates the elementary functions with the implementations from the standard
+/// library, which are correctly rounded or nearly so.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct StdMath;
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<E: RealField + num_traits::Float> MathPolicy<E> for StdMath {
+    #[inline]
+    fn exp(&self, x: E) -> E {
+        num_traits::Float::exp(x)
+    }
+    #[inline]
+    fn ln(&self, x: E) -> E {
+        num_traits::Float::ln(x)
+    }
+    #[inline]
+    fn sqrt(&self, x: E) -> E {
+        num_traits::Float::sqrt(x)
+    }
+    #[inline]
+    fn tanh(&self, x: E) -> E {
+        num_traits::Float::tanh(x)
+    }
+    #[inline]
+    fn powf(&self, x: E, y: E) -> E {
+        num_traits::Float::powf(x, y)
+    }
+}
+
+/// Elementwise function applied by [`apply`] and [`apply_in_place`].
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UnaryFn<E> {
+    /// $e^x$.
+    Exp,
+    /// Natural logarithm.
+    Ln,
+    /// Square root.
+    Sqrt,
+    /// Absolute value.
+    Abs,
+    /// $x^p$, with the given exponent $p$.
+    Powf(E),
+    /// Hyperbolic tangent.
+    Tanh,
+    /// Logistic sigmoid, $1 / (1 + e^{-x})$.
+    Sigmoid,
+}
+
+// a single elementwise function, so that the `match` on `UnaryFn` is resolved once, outside of
+// the loops
+trait Kernel<E: RealField>: Copy {
+    fn scalar(self, x: E) -> E;
+    fn simd<S: pulp::Simd>(self, simd: SimdFor<E, S>, x: SimdGroupFor<E, S>) -> SimdGroupFor<E, S>;
+}
+
+macro_rules! policy_kernels {
+    ($($name: ident, $f: ident, $simd_f: ident;)*) => {$(
+        struct $name<'a, P>(&'a P);
+
+        impl<P> Copy for $name<'_, P> {}
+        impl<P> Clone for $name<'_, P> {
+            #[inline]
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+
+        impl<E: RealField, P: MathPolicy<E>> Kernel<E> for $name<'_, P> {
+            #[inline(always)]
+            fn scalar(self, x: E) -> E {
+                self.0.$f(x)
+            }
+            #[inline(always)]
+            fn simd<S: pulp::Simd>(
+                self,
+                simd: SimdFor<E, S>,
+                x: SimdGroupFor<E, S>,
+            ) -> SimdGroupFor<E, S> {
+                self.0.$simd_f(simd, x)
+            }
+        }
+    )*};
+}
+
+policy_kernels! {
+    Exp, exp, simd_exp;
+    Ln, ln, simd_ln;
+    Sqrt, sqrt, simd_sqrt;
+    Tanh, tanh, simd_tanh;
+}
+
+#[derive(Copy, Clone)]
+struct Abs;
+
+impl<E: RealField> Kernel<E> for Abs {
+    #[inline(always)]
+    fn scalar(self, x: E) -> E {
+        x.faer_abs()
+    }
+    #[inline(always)]
+    fn simd<S: pulp::Simd>(self, simd: SimdFor<E, S>, x: SimdGroupFor<E, S>) -> SimdGroupFor<E, S> {
+        simd.abs(x)
+    }
+}
+
+struct Powf<'a, P, E>(&'a P, E);
+
+impl<P, E: Copy> Copy for Powf<'_, P, E> {}
+impl<P, E: Copy> Clone for Powf<'_, P, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: RealField, P: MathPolicy<E>> Kernel<E> for Powf<'_, P, E> {
+    #[inline(always)]
+    fn scalar(self, x: E) -> E {
+        self.0.powf(x, self.1)
+    }
+    #[inline(always)]
+    fn simd<S: pulp::Simd>(self, simd: SimdFor<E, S>, x: SimdGroupFor<E, S>) -> SimdGroupFor<E, S> {
+        self.0.simd_powf(simd, x, self.1)
+    }
+}
+
+struct Sigmoid<'a, P>(&'a P);
+
+impl<P> Copy for Sigmoid<'_, P> {}
+impl<P> Clone for Sigmoid<'_, P> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: RealField, P: MathPolicy<E>> Kernel<E> for Sigmoid<'_, P> {
+    #[inline(always)]
+    fn scalar(self, x: E) -> E {
+        // exponentiates a nonpositive value, so that it can't overflow
+        let one = E::faer_one();
+        if x >= E::faer_zero() {
+            one.faer_div(one.faer_add(self.0.exp(x.faer_neg())))
+        } else {
+            let e = self.0.exp(x);
+            e.faer_div(one.faer_add(e))
+        }
+    }
+    #[inline(always)]
+    fn simd<S: pulp::Simd>(self, simd: SimdFor<E, S>, x: SimdGroupFor<E, S>) -> SimdGroupFor<E, S> {
+        let one = simd.splat(E::faer_one());
+        let e = self.0.simd_exp(simd, simd.neg(simd.abs(x)));
+        let is_nonnegative = simd.greater_than_or_equal(x, simd.splat(E::faer_zero()));
+        let num = simd.select(is_nonnegative, one, e);
+        let den = simd.add(one, e);
+
+        // there is no vectorized division, so only the quotient is computed lane by lane
+        let den = from_copy::<E, _>(den);
+        let den = SliceGroup::<'_, E>::new(E::faer_map(
+            E::faer_as_ref(&den),
+            #[inline(always)]
+            |x| bytemuck::cast_slice::<SimdUnitFor<E, S>, UnitFor<E>>(core::slice::from_ref(x)),
+        ));
+        let mut i = 0;
+        map_lanes(num, |num| {
+            let q = num.faer_div(den.read(i));
+            i += 1;
+            q
+        })
+    }
+}
+
+struct InPlace<'a, E: RealField, K> {
+    mat: MatMut<'a, E>,
+    kernel: K,
+}
+
+impl<E: RealField, K: Kernel<E>> pulp::WithSimd for InPlace<'_, E, K> {
+    type Output = ();
+
+    #[inline(always)]
+    fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+        let Self { mut mat, kernel } = self;
+        let simd = SimdFor::<E, S>::new(simd);
+
+        for j in 0..mat.ncols() {
+            let col =
+                SliceGroupMut::<'_, E>::new(mat.rb_mut().col_mut(j).try_as_slice_mut().unwrap());
+            let (body, tail) = simd.as_simd_mut(col);
+            for mut x in body.into_mut_iter() {
+                x.set(kernel.simd(simd, x.get()));
+            }
+            for mut x in tail.into_mut_iter() {
+                x.write(kernel.scalar(x.read()));
+            }
+        }
+    }
+}
+
+struct OutOfPlace<'a, E: RealField, K> {
+    out: MatMut<'a, E>,
+    src: MatRef<'a, E>,
+    kernel: K,
+}
+
+impl<E: RealField, K: Kernel<E>> pulp::WithSimd for OutOfPlace<'_, E, K> {
+    type Output = ();
+
+    #[inline(always)]
+    fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+        let Self {
+            mut out,
+            src,
+            kernel,
+        } = self;
+        let simd = SimdFor::<E, S>::new(simd);
+
+        for j in 0..out.ncols() {
+            let out =
+                SliceGroupMut::<'_, E>::new(out.rb_mut().col_mut(j).try_as_slice_mut().unwrap());
+            let src = SliceGroup::<'_, E>::new(src.col(j).try_as_slice().unwrap());
+            let (out_body, out_tail) = simd.as_simd_mut(out);
+            let (src_body, src_tail) = simd.as_simd(src);
+            for (mut out, src) in zip(out_body.into_mut_iter(), src_body.into_ref_iter()) {
+                out.set(kernel.simd(simd, src.get()));
+            }
+            for (mut out, src) in zip(out_tail.into_mut_iter(), src_tail.into_ref_iter()) {
+                out.write(kernel.scalar(src.read()));
+            }
+        }
+    }
+}
+
+// whether a matrix with the given layout should be transposed so that the SIMD kernels run along
+// its contiguous dimension, or along the longer one if both are contiguous
+#[inline]
+fn should_transpose(
+    nrows: usize,
+    ncols: usize,
+    rows_contiguous: bool,
+    cols_contiguous: bool,
+) -> bool {
+    cols_contiguous && (!rows_contiguous || ncols > nrows)
+}
+
+// contiguous columns (or rows) are processed with SIMD instructions, other layouts element by
+// element
+fn apply_in_place_kernel<E: RealField, K: Kernel<E>>(mat: MatMut<'_, E>, kernel: K) {
+    let mut mat = mat;
+    if should_transpose(
+        mat.nrows(),
+        mat.ncols(),
+        mat.row_stride() == 1,
+        mat.col_stride() == 1,
+    ) {
+        mat = mat.transpose_mut();
+    }
+    if mat.row_stride() == 1 {
+        E::Simd::default().dispatch(InPlace { mat, kernel });
+    } else {
+        zipped!(mat).for_each(
+            #[inline(always)]
+            |unzipped!(mut x)| x.write(kernel.scalar(x.read())),
+        );
+    }
+}
+
+fn apply_kernel<E: RealField, K: Kernel<E>>(out: MatMut<'_, E>, src: MatRef<'_, E>, kernel: K) {
+    let (mut out, mut src) = (out, src);
+    if should_transpose(
+        out.nrows(),
+        out.ncols(),
+        out.row_stride() == 1 && src.row_stride() == 1,
+        out.col_stride() == 1 && src.col_stride() == 1,
+    ) {
+        out = out.transpose_mut();
+        src = src.transpose();
+    }
+    if out.row_stride() == 1 && src.row_stride() == 1 {
+        E::Simd::default().dispatch(OutOfPlace { out, src, kernel });
+    } else {
+        zipped!(out, src).for_each(
+            #[inline(always)]
+            |unzipped!(mut out, src)| out.write(kernel.scalar(src.read())),
+        );
+    }
+}
+
+fn apply_in_place_impl<E: RealField, P: MathPolicy<E>>(
+    mat: MatMut<'_, E>,
+    f: UnaryFn<E>,
+    policy: &P,
+) {
+    match f {
+        UnaryFn::Exp => apply_in_place_kernel(mat, Exp(policy)),
+        UnaryFn::Ln => apply_in_place_kernel(mat, Ln(policy)),
+        UnaryFn::Sqrt => apply_in_place_kernel(mat, Sqrt(policy)),
+        UnaryFn::Abs => apply_in_place_kernel(mat, Abs),
+        UnaryFn::Powf(p) => apply_in_place_kernel(mat, Powf(policy, p)),
+        UnaryFn::Tanh => apply_in_place_kernel(mat, Tanh(policy)),
+        UnaryFn::Sigmoid => apply_in_place_kernel(mat, Sigmoid(policy)),
+    }
+}
+
+fn apply_impl<E: RealField, P: MathPolicy<E>>(
+    out: MatMut<'_, E>,
+    src: MatRef<'_, E>,
+    f: UnaryFn<E>,
+    policy: &P,
+) {
+    match f {
+        UnaryFn::Exp => apply_kernel(out, src, Exp(policy)),
+        UnaryFn::Ln => apply_kernel(out, src, Ln(policy)),
+        UnaryFn::Sqrt => apply_kernel(out, src, Sqrt(policy)),
+        UnaryFn::Abs => apply_kernel(out, src, Abs),
+        UnaryFn::Powf(p) => apply_kernel(out, src, Powf(policy, p)),
+        UnaryFn::Tanh => apply_kernel(out, src, Tanh(policy)),
+        UnaryFn::Sigmoid => apply_kernel(out, src, Sigmoid(policy)),
+    }
+}
+
+/// Replaces each element $x$ of `mat` by $f(x)$, with the elementary functions evaluated by
+/// `policy`.
+pub fn apply_in_place<E: RealField, P: MathPolicy<E>>(
+    mat: impl As2DMut<E>,
+    f: UnaryFn<E>,
+    policy: &P,
+) {
+    let mut mat = mat;
+    apply_in_place_impl(mat.as_2d_mut(), f, policy);
+}
+
+/// Stores $f(x)$ in `out` for each element $x$ of `src`, with the elementary functions evaluated
+/// by `policy`.
+///
+/// # Panics
+/// Panics if `out` and `src` don't have the same dimensions.
+#[track_caller]
+pub fn apply<E: RealField, P: MathPolicy<E>>(
+    out: impl As2DMut<E>,
+    src: impl As2D<E>,
+    f: UnaryFn<E>,
+    policy: &P,
+) {
+    let mut out = out;
+    let out = out.as_2d_mut();
+    let src = src.as_2d_ref();
+    assert!(all(out.nrows() == src.nrows(), out.ncols() == src.ncols()));
+    apply_impl(out, src, f, policy);
+}
+
+macro_rules! shorthands {
+    ($($in_place: ident, $out_of_place: ident, $f: expr, $name: literal;)*) => {$(
+        #[doc = concat!("Replaces each element of `mat` by its ", $name, ", using [`StdMath`].")]
+        #[cfg(feature = "std")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        pub fn $in_place<E: RealField + num_traits::Float>(mat: impl As2DMut<E>) {
+            apply_in_place(mat, $f, &StdMath);
+        }
+
+        #[doc = concat!("Stores the ", $name, " of each element of `src` in `out`, using [`StdMath`].")]
+        ///
+        /// # Panics
+        /// Panics if `out` and `src` don't have the same dimensions.
+        #[cfg(feature = "std")]
+        #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+        #[track_caller]
+        pub fn $out_of_place<E: RealField + num_traits::Float>(
+            out: impl As2DMut<E>,
+            src: impl As2D<E>,
+        ) {
+            apply(out, src, $f, &StdMath);
+        }
+    )*};
+}
+
+shorthands! {
+    exp_in_place, exp, UnaryFn::Exp, "exponential";
+    ln_in_place, ln, UnaryFn::Ln, "natural logarithm";
+    sqrt_in_place, sqrt, UnaryFn::Sqrt, "square root";
+    abs_in_place, abs, UnaryFn::Abs, "absolute value";
+    tanh_in_place, tanh, UnaryFn::Tanh, "hyperbolic tangent";
+    sigmoid_in_place, sigmoid, UnaryFn::Sigmoid, "logistic sigmoid";
+}
+
+/// Replaces each element of `mat` by its power `p`, using [`StdMath`].
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub fn powf_in_place<E: RealField + num_traits::Float>(mat: impl As2DMut<E>, p: E) {
+    apply_in_place(mat, UnaryFn::Powf(p), &StdMath);
+}
+
+/// Stores the power `p` of each element of `src` in `out`, using [`StdMath`].
+///
+/// # Panics
+/// Panics if `out` and `src` don't have the same dimensions.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[track_caller]
+pub fn powf<E: RealField + num_traits::Float>(out: impl As2DMut<E>, src: impl As2D<E>, p: E) {
+    apply(out, src, UnaryFn::Powf(p), &StdMath);
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::{Col, Mat, Row};
+
+    // rounds `exp` to single precision, as a stand-in for a fast approximation
+    struct Rounded;
+    impl MathPolicy<f64> for Rounded {
+        fn exp(&self, x: f64) -> f64 {
+            (x as f32).exp() as f64
+        }
+        fn ln(&self, x: f64) -> f64 {
+            x.ln()
+        }
+        fn sqrt(&self, x: f64) -> f64 {
+            x.sqrt()
+        }
+        fn tanh(&self, x: f64) -> f64 {
+            x.tanh()
+        }
+        fn powf(&self, x: f64, y: f64) -> f64 {
+            x.powf(y)
+        }
+    }
+
+    // vectorized `exp` that rounds to single precision, while the scalar `exp` is exact
+    struct RoundedSimd;
+    impl MathPolicy<f64> for RoundedSimd {
+        fn exp(&self, x: f64) -> f64 {
+            x.exp()
+        }
+        fn ln(&self, x: f64) -> f64 {
+            x.ln()
+        }
+        fn sqrt(&self, x: f64) -> f64 {
+            x.sqrt()
+        }
+        fn tanh(&self, x: f64) -> f64 {
+            x.tanh()
+        }
+        fn powf(&self, x: f64, y: f64) -> f64 {
+            x.powf(y)
+        }
+        fn simd_exp<S: pulp::Simd>(
+            &self,
+            simd: SimdFor<f64, S>,
+            x: SimdGroupFor<f64, S>,
+        ) -> SimdGroupFor<f64, S> {
+            let _ = simd;
+            map_lanes(x, |x| (x as f32).exp() as f64)
+        }
+    }
+
+    #[test]
+    fn test_elementwise_simd() {
+        let a = Mat::from_fn(67, 9, |i, j| (i as f64 - 30.0) * 0.1 + j as f64 * 0.01);
+
+        // the registers go through `simd_exp`, and the tail through `exp`
+        let mut out = Mat::zeros(67, 9);
+        apply(&mut out, &a, UnaryFn::Exp, &RoundedSimd);
+        let mut rounded = 0;
+        for j in 0..9 {
+            for i in 0..67 {
+                let x = a.read(i, j);
+                if out.read(i, j) != x.exp() {
+                    assert!(out.read(i, j) == (x as f32).exp() as f64);
+                    rounded += 1;
+                }
+            }
+        }
+        assert!(rounded > 0);
+
+        // every layout gives the same result as the column-major one
+        for f in [UnaryFn::Exp, UnaryFn::Abs, UnaryFn::Tanh, UnaryFn::Sigmoid] {
+            let mut target = Mat::zeros(67, 9);
+            apply(&mut target, &a, f, &StdMath);
+
+            let src = a.transpose().to_owned();
+            let mut out = Mat::zeros(9, 67);
+            apply(out.as_mut().transpose_mut(), src.transpose(), f, &StdMath);
+            assert!(out.transpose() == target);
+
+            let mut out = src.clone();
+            apply_in_place(out.as_mut().transpose_mut(), f, &StdMath);
+            assert!(out.transpose() == target);
+
+            let mut out = Mat::zeros(67, 9);
+            apply(
+                out.as_mut().reverse_rows_mut(),
+                a.as_ref().reverse_rows(),
+                f,
+                &StdMath,
+            );
+            assert!(out == target);
+        }
+    }
+
+    #[test]
+    fn test_elementwise() {
+        let a = Mat::from_fn(5, 3, |i, j| i as f64 * 0.75 - j as f64 * 2.5);
+        let pos = Mat::from_fn(5, 3, |i, j| 0.5 + (i + 3 * j) as f64);
+
+        let check = |f: UnaryFn<f64>, src: &Mat<f64>, expected: &dyn Fn(f64) -> f64| {
+            let mut out = Mat::zeros(5, 3);
+            apply(&mut out, src, f, &StdMath);
+            let mut in_place = src.clone();
+            apply_in_place(&mut in_place, f, &StdMath);
+            for j in 0..3 {
+                for i in 0..5 {
+                    let target = expected(src.read(i, j));
+                    assert!((out.read(i, j) - target).abs() <= 1e-14 * target.abs());
+                    assert!(in_place.read(i, j) == out.read(i, j));
+                }
+            }
+        };
+        check(UnaryFn::Exp, &a, &|x| x.exp());
+        check(UnaryFn::Ln, &pos, &|x| x.ln());
+        check(UnaryFn::Sqrt, &pos, &|x| x.sqrt());
+        check(UnaryFn::Abs, &a, &|x| x.abs());
+        check(UnaryFn::Powf(1.5), &pos, &|x| x.powf(1.5));
+        check(UnaryFn::Tanh, &a, &|x| x.tanh());
+        check(UnaryFn::Sigmoid, &a, &|x| 1.0 / (1.0 + (-x).exp()));
+
+        // the sigmoid doesn't overflow for large inputs
+        let mut x = Col::from_fn(2, |i| if i == 0 { -1000.0 } else { 1000.0 });
+        sigmoid_in_place(&mut x);
+        assert!(all(x.read(0) == 0.0, x.read(1) == 1.0));
+
+        let r = Row::from_fn(4, |j| j as f64);
+        let mut out = Row::zeros(4);
+        exp(&mut out, &r);
+        for j in 0..4 {
+            assert!(out.read(j) == (j as f64).exp());
+        }
+        powf_in_place(out.as_mut(), 2.0);
+        for j in 0..4 {
+            assert!((out.read(j) - (2.0 * j as f64).exp()).abs() < 1e-12 * out.read(j));
+        }
+
+        let mut fast = Mat::zeros(5, 3);
+        apply(&mut fast, &a, UnaryFn::Exp, &Rounded);
+        for j in 0..3 {
+            for i in 0..5 {
+                let target = a.read(i, j).exp();
+                assert!((fast.read(i, j) - target).abs() <= 1e-6 * target);
+            }
+        }
+    }
+}
@@ -0,0 +1,424 @@
+//! Equilibration of badly scaled matrices.
+//!
+//! A matrix is badly scaled when the magnitudes of its entries vary widely across its rows or its
+//! columns, which can degrade the accuracy of its factorizations. Equilibration computes positive
+//! diagonal scaling matrices $R$ and $C$ such that the entries of $RAC$ have similar magnitudes,
+//! after which a system $Ax = b$ can be solved as $(RAC) y = Rb$ and $x = Cy$.
+//!
+//! [`row_col_scaling`] computes general row and column scaling factors, and [`symmetric_scaling`]
+//! computes a symmetric scaling $R = C$ that preserves the Hermitian structure of a matrix, using
+//! the same methods as LAPACK's `xGEEQUB` and `xPOEQUB`. The scaling factors are powers of two, so
+//! that applying them introduces no rounding errors.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::equilibration, mat};
+//!
+//! let mut a = mat![[1e10, 2e10], [3e-10, 1e-10f64]];
+//!
+//! let scaling = equilibration::row_col_scaling(a.as_ref()).unwrap();
+//! equilibration::equilibrate_in_place(a.as_mut(), &scaling);
+//!
+//! // the largest entry of each row and column of the scaled matrix is in (0.5, 1]
+//! assert!(a.norm_max() <= 1.0);
+//! assert!(a.read(0, 1) > 0.5);
+//! assert!(a.read(1, 0) > 0.5);
+//! ```
+
+use crate::{
+    assert,
+    col::{Col, ColRef},
+    mat::{MatMut, MatRef},
+    ComplexField, RealField,
+};
+use reborrow::*;
+
+/// Matrices whose ratio between the smallest and largest scaling factors is above this threshold
+/// are considered to be well scaled, and do not need to be equilibrated.
+const THRESHOLD: f64 = 0.1;
+
+/// Scaling that was applied to a matrix.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Equilibration {
+    /// The matrix was not scaled.
+    None,
+    /// The rows of the matrix were scaled.
+    Row,
+    /// The columns of the matrix were scaled.
+    Col,
+    /// Both the rows and the columns of the matrix were scaled.
+    Both,
+}
+
+/// This error signifies that the scaling factors could not be computed.
+#[derive(Debug, Clone, Copy)]
+pub enum EquilibrationError {
+    /// The matrix has a row that is exactly zero.
+    ZeroRow {
+        /// Index of the first zero row.
+        index: usize,
+    },
+    /// The matrix has a column that is exactly zero.
+    ZeroCol {
+        /// Index of the first zero column.
+        index: usize,
+    },
+    /// The matrix has a diagonal element that is not positive, so that it is not positive
+    /// definite.
+    NonPositiveDiagonal {
+        /// Index of the first non positive diagonal element.
+        index: usize,
+    },
+}
+
+impl core::fmt::Display for EquilibrationError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for EquilibrationError {}
+
+/// Row and column scaling factors of a matrix, computed by [`row_col_scaling`].
+#[derive(Clone, Debug)]
+pub struct RowColScaling<E: ComplexField> {
+    /// Row scaling factors $R$.
+    pub row_scale: Col<E::Real>,
+    /// Column scaling factors $C$.
+    pub col_scale: Col<E::Real>,
+    /// Ratio between the smallest and the largest row scaling factors.
+    pub row_ratio: E::Real,
+    /// Ratio between the smallest and the largest column scaling factors.
+    pub col_ratio: E::Real,
+    /// Largest absolute value of the entries of the matrix.
+    pub amax: E::Real,
+}
+
+/// Symmetric scaling factors of a Hermitian matrix, computed by [`symmetric_scaling`].
+#[derive(Clone, Debug)]
+pub struct SymmetricScaling<E: ComplexField> {
+    /// Scaling factors $S$, to be applied to both the rows and the columns of the matrix.
+    pub scale: Col<E::Real>,
+    /// Ratio between the smallest and the largest scaling factors.
+    pub ratio: E::Real,
+    /// Largest diagonal element of the matrix.
+    pub amax: E::Real,
+}
+
+// whether the largest absolute value of the matrix is close to underflow or overflow
+fn is_extreme<E: RealField>(amax: E) -> bool {
+    let small = E::faer_min_positive().faer_div(E::faer_epsilon());
+    let large = small.faer_inv();
+    amax < small || amax > large
+}
+
+// largest power of two that is smaller than or equal to `value`, which must be positive and finite
+fn pow2_floor<E: RealField>(value: E) -> E {
+    let two = E::faer_from_f64(2.0);
+    let half = E::faer_from_f64(0.5);
+    let mut p = E::faer_one();
+    while p.faer_mul(two) <= value {
+        p = p.faer_mul(two);
+    }
+    while p > value {
+        p = p.faer_mul(half);
+    }
+    p
+}
+
+// returns the smallest and largest elements of `values`
+fn min_max<E: RealField>(values: ColRef<'_, E>) -> (E, E) {
+    let mut min = E::faer_one().faer_div(E::faer_zero());
+    let mut max = E::faer_zero();
+    for i in 0..values.nrows() {
+        let v = values.read(i);
+        if v < min {
+            min = v;
+        }
+        if v > max {
+            max = v;
+        }
+    }
+    (min, max)
+}
+
+impl<E: ComplexField> RowColScaling<E> {
+    /// Returns the scaling that is recommended for the matrix, i.e., rows are scaled if their
+    /// norms vary widely or if the entries of the matrix are close to underflow or overflow, and
+    /// columns are scaled if their norms vary widely after the row scaling.
+    pub fn recommended(&self) -> Equilibration {
+        let threshold = E::Real::faer_from_f64(THRESHOLD);
+        let rows = self.row_ratio < threshold || is_extreme(self.amax);
+        let cols = self.col_ratio < threshold;
+        match (rows, cols) {
+            (false, false) => Equilibration::None,
+            (true, false) => Equilibration::Row,
+            (false, true) => Equilibration::Col,
+            (true, true) => Equilibration::Both,
+        }
+    }
+}
+
+impl<E: ComplexField> SymmetricScaling<E> {
+    /// Returns whether the scaling is recommended for the matrix, i.e., if its diagonal elements
+    /// vary widely or are close to underflow or overflow.
+    pub fn is_recommended(&self) -> bool {
+        self.ratio < E::Real::faer_from_f64(THRESHOLD) || is_extreme(self.amax)
+    }
+}
+
+/// Computes row and column scaling factors of `matrix`, such that the largest absolute value in
+/// each row and column of $RAC$ is in $(1/2, 1]$.
+///
+/// The row factors are computed first, then the column factors are computed for the row-scaled
+/// matrix. Returns an error if the matrix has a zero row or column.
+pub fn row_col_scaling<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+) -> Result<RowColScaling<E>, EquilibrationError> {
+    let (m, n) = matrix.shape();
+    let zero = E::Real::faer_zero();
+    let min_positive = E::Real::faer_min_positive();
+
+    let mut row_scale = Col::<E::Real>::zeros(m);
+    for j in 0..n {
+        for i in 0..m {
+            let abs = matrix.read(i, j).faer_abs();
+            if abs > row_scale.read(i) {
+                row_scale.write(i, abs);
+            }
+        }
+    }
+    for i in 0..m {
+        if row_scale.read(i) == zero {
+            return Err(EquilibrationError::ZeroRow { index: i });
+        }
+    }
+    let (rmin, amax) = min_max(row_scale.as_ref());
+    let row_ratio = if m == 0 {
+        E::Real::faer_one()
+    } else {
+        rmin.faer_div(amax)
+    };
+    for i in 0..m {
+        let r = row_scale.read(i);
+        let r = if r > min_positive { r } else { min_positive };
+        row_scale.write(i, pow2_floor(r.faer_inv()));
+    }
+
+    let mut col_scale = Col::<E::Real>::zeros(n);
+    for j in 0..n {
+        for i in 0..m {
+            let abs = matrix.read(i, j).faer_abs().faer_mul(row_scale.read(i));
+            if abs > col_scale.read(j) {
+                col_scale.write(j, abs);
+            }
+        }
+        if col_scale.read(j) == zero {
+            return Err(EquilibrationError::ZeroCol { index: j });
+        }
+    }
+    let (cmin, cmax) = min_max(col_scale.as_ref());
+    let col_ratio = if n == 0 {
+        E::Real::faer_one()
+    } else {
+        cmin.faer_div(cmax)
+    };
+    for j in 0..n {
+        let c = col_scale.read(j);
+        let c = if c > min_positive { c } else { min_positive };
+        col_scale.write(j, pow2_floor(c.faer_inv()));
+    }
+
+    Ok(RowColScaling {
+        row_scale,
+        col_scale,
+        row_ratio,
+        col_ratio,
+        amax,
+    })
+}
+
+/// Computes symmetric scaling factors of the Hermitian positive definite matrix `matrix`, such
+/// that the diagonal elements of $SAS$ are in $(1/4, 1]$.
+///
+/// Only the diagonal of the matrix is accessed. Returns an error if one of the diagonal elements
+/// is not positive.
+///
+/// # Panics
+/// Panics if the matrix is not square.
+#[track_caller]
+pub fn symmetric_scaling<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+) -> Result<SymmetricScaling<E>, EquilibrationError> {
+    assert!(matrix.nrows() == matrix.ncols());
+    let n = matrix.nrows();
+    let zero = E::Real::faer_zero();
+    let min_positive = E::Real::faer_min_positive();
+
+    let mut scale = Col::<E::Real>::zeros(n);
+    for i in 0..n {
+        let d = matrix.read(i, i).faer_real();
+        if d <= zero || d.faer_is_nan() {
+            return Err(EquilibrationError::NonPositiveDiagonal { index: i });
+        }
+        scale.write(i, d);
+    }
+    let (dmin, amax) = min_max(scale.as_ref());
+    let ratio = if n == 0 {
+        E::Real::faer_one()
+    } else {
+        dmin.faer_sqrt().faer_div(amax.faer_sqrt())
+    };
+    for i in 0..n {
+        let d = scale.read(i);
+        let d = if d > min_positive { d } else { min_positive };
+        scale.write(i, pow2_floor(d.faer_sqrt().faer_inv()));
+    }
+
+    Ok(SymmetricScaling { scale, ratio, amax })
+}
+
+/// Multiplies each row of `matrix` by the corresponding element of `scale`.
+///
+/// # Panics
+/// Panics if `scale.nrows() != matrix.nrows()`.
+#[track_caller]
+pub fn scale_rows_in_place<E: ComplexField>(matrix: MatMut<'_, E>, scale: ColRef<'_, E::Real>) {
+    let mut matrix = matrix;
+    assert!(scale.nrows() == matrix.nrows());
+    for j in 0..matrix.ncols() {
+        for i in 0..matrix.nrows() {
+            matrix.write(i, j, matrix.read(i, j).faer_scale_real(scale.read(i)));
+        }
+    }
+}
+
+/// Multiplies each column of `matrix` by the corresponding element of `scale`.
+///
+/// # Panics
+/// Panics if `scale.nrows() != matrix.ncols()`.
+#[track_caller]
+pub fn scale_cols_in_place<E: ComplexField>(matrix: MatMut<'_, E>, scale: ColRef<'_, E::Real>) {
+    let mut matrix = matrix;
+    assert!(scale.nrows() == matrix.ncols());
+    for j in 0..matrix.ncols() {
+        let s = scale.read(j);
+        for i in 0..matrix.nrows() {
+            matrix.write(i, j, matrix.read(i, j).faer_scale_real(s));
+        }
+    }
+}
+
+/// Applies the [recommended](RowColScaling::recommended) scaling to `matrix`, and returns it.
+///
+/// # Panics
+/// Panics if the dimensions of `matrix` don't match the ones of the scaling factors.
+#[track_caller]
+pub fn equilibrate_in_place<E: ComplexField>(
+    matrix: MatMut<'_, E>,
+    scaling: &RowColScaling<E>,
+) -> Equilibration {
+    let mut matrix = matrix;
+    let equilibration = scaling.recommended();
+    if matches!(equilibration, Equilibration::Row | Equilibration::Both) {
+        scale_rows_in_place(matrix.rb_mut(), scaling.row_scale.as_ref());
+    }
+    if matches!(equilibration, Equilibration::Col | Equilibration::Both) {
+        scale_cols_in_place(matrix.rb_mut(), scaling.col_scale.as_ref());
+    }
+    equilibration
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, mat::Mat};
+
+    fn is_pow2(x: f64) -> bool {
+        x > 0.0 && x.log2().fract() == 0.0
+    }
+
+    #[test]
+    fn test_row_col_scaling() {
+        let n = 12;
+        let a = Mat::<c64>::from_fn(n, n + 3, |i, j| {
+            c64::new(rand::random::<f64>() + 0.1, rand::random::<f64>())
+                .faer_scale_real(10.0f64.powi(3 * i as i32 - 2 * j as i32))
+        });
+
+        let scaling = row_col_scaling(a.as_ref()).unwrap();
+        assert!(scaling.recommended() == Equilibration::Both);
+        for i in 0..n {
+            assert!(is_pow2(scaling.row_scale.read(i)));
+        }
+        for j in 0..n + 3 {
+            assert!(is_pow2(scaling.col_scale.read(j)));
+        }
+
+        let mut b = a.clone();
+        assert!(equilibrate_in_place(b.as_mut(), &scaling) == Equilibration::Both);
+        for j in 0..n + 3 {
+            let max = b.as_ref().col(j).norm_max();
+            assert!(max > 0.5 && max <= 1.0);
+        }
+        for i in 0..n {
+            let max = b.as_ref().row(i).transpose().norm_max();
+            assert!(max > 0.5 && max <= 1.0);
+        }
+
+        // powers of two are applied exactly
+        for j in 0..n + 3 {
+            for i in 0..n {
+                let expected = a
+                    .read(i, j)
+                    .faer_scale_real(scaling.row_scale.read(i) * scaling.col_scale.read(j));
+                assert!(b.read(i, j) == expected);
+            }
+        }
+
+        let a = crate::mat![[1.0, 2.0], [3.0, 4.0f64]];
+        let scaling = row_col_scaling(a.as_ref()).unwrap();
+        assert!(scaling.recommended() == Equilibration::None);
+
+        let a = crate::mat![[1.0, 0.0], [0.0, 0.0f64]];
+        assert!(matches!(
+            row_col_scaling(a.as_ref()),
+            Err(EquilibrationError::ZeroRow { index: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_symmetric_scaling() {
+        let n = 10;
+        let m = Mat::<f64>::from_fn(n, n, |_, _| rand::random::<f64>());
+        let d = Mat::<f64>::from_fn(n, n, |i, j| {
+            if i == j {
+                10.0f64.powi(i as i32 - 5)
+            } else {
+                0.0
+            }
+        });
+        let a = &d * (&m * m.transpose() + Mat::<f64>::identity(n, n)) * &d;
+
+        let scaling = symmetric_scaling(a.as_ref()).unwrap();
+        assert!(scaling.is_recommended());
+
+        let mut b = a.clone();
+        scale_rows_in_place(b.as_mut(), scaling.scale.as_ref());
+        scale_cols_in_place(b.as_mut(), scaling.scale.as_ref());
+        for i in 0..n {
+            assert!(is_pow2(scaling.scale.read(i)));
+            assert!(b.read(i, i) > 0.25 && b.read(i, i) <= 1.0);
+        }
+
+        let a = crate::mat![[1.0, 0.0], [0.0, -1.0f64]];
+        assert!(matches!(
+            symmetric_scaling(a.as_ref()),
+            Err(EquilibrationError::NonPositiveDiagonal { index: 1 })
+        ));
+    }
+}
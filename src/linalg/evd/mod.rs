@@ -31,7 +31,7 @@ use crate::{
     },
     unzipped,
     utils::thread::parallelism_degree,
-    zipped, ColMut, ComplexField, Conj, MatMut, MatRef, Parallelism, RealField,
+    zipped, ColMut, ColRef, ComplexField, Conj, MatMut, MatRef, Parallelism, RealField,
 };
 use coe::Coerce;
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
@@ -44,7 +44,6 @@ pub mod tridiag_qr_algorithm;
 #[doc(hidden)]
 pub mod tridiag_real_evd;
 
-#[doc(hidden)]
 pub mod tridiag;
 
 #[doc(hidden)]
@@ -55,6 +54,11 @@ pub mod hessenberg_cplx_evd;
 #[doc(hidden)]
 pub mod hessenberg_real_evd;
 
+mod tridiag_inverse_iteration;
+pub use tridiag_inverse_iteration::{
+    compute_tridiag_eigenvectors, compute_tridiag_eigenvectors_req,
+};
+
 /// Indicates whether the eigenvectors are fully computed, partially computed, or skipped.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ComputeVectors {
@@ -335,6 +339,155 @@ pub fn compute_hermitian_evd_custom_epsilon<E: ComplexField>(
     );
 }
 
+/// Computes the size and alignment of required workspace for performing a symmetric tridiagonal
+/// eigenvalue decomposition. The eigenvectors may be optionally computed.
+pub fn compute_tridiag_evd_req<E: RealField>(
+    n: usize,
+    compute_eigenvectors: ComputeVectors,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        StackReq::try_new::<E>(n)?,
+        StackReq::try_new::<E>(n.saturating_sub(1))?,
+        match compute_eigenvectors {
+            ComputeVectors::No => StackReq::empty(),
+            ComputeVectors::Yes => {
+                tridiag_real_evd::compute_tridiag_real_evd_req::<E>(n, parallelism)?
+            }
+        },
+    ])
+}
+
+/// Computes the eigenvalue decomposition of a real symmetric tridiagonal matrix $T$, given its
+/// diagonal `diag` and its subdiagonal `offdiag`, such that $T = U S U^\top$.
+///
+/// `s` represents the diagonal of the matrix $S$, and must have size equal to the dimension of the
+/// matrix. The eigenvalues are sorted in nondecreasing order.
+///
+/// If `u` is `None`, then only the eigenvalues are computed, using the implicit QR algorithm.
+/// Otherwise, the eigenvectors are computed with a divide and conquer algorithm and stored in `u`.
+///
+/// The tridiagonal matrix can come from [`tridiag::tridiagonalize_in_place`], or from an external
+/// source, e.g. the Lanczos process.
+///
+/// # Panics
+/// Panics if any of the conditions described above is violated, if `offdiag` doesn't have size
+/// equal to the dimension of the matrix minus one, or if the type `E` does not have a fixed
+/// precision at compile time, e.g. a dynamic multiprecision floating point type.
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`compute_tridiag_evd_req`]).
+#[track_caller]
+pub fn compute_tridiag_evd<E: RealField>(
+    diag: ColRef<'_, E>,
+    offdiag: ColRef<'_, E>,
+    s: ColMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    compute_tridiag_evd_custom_epsilon(
+        diag,
+        offdiag,
+        s,
+        u,
+        E::faer_epsilon(),
+        E::faer_zero_threshold(),
+        parallelism,
+        stack,
+    );
+}
+
+/// See [`compute_tridiag_evd`].
+///
+/// This function takes an additional `epsilon` and `zero_threshold` parameters. `epsilon`
+/// represents the precision of the values in the matrix, and `zero_threshold` is the value below
+/// which the precision starts to deteriorate, e.g. due to denormalized numbers.
+///
+/// These values need to be provided manually for types that do not have a known precision at
+/// compile time, e.g. a dynamic multiprecision floating point type.
+#[track_caller]
+pub fn compute_tridiag_evd_custom_epsilon<E: RealField>(
+    diag: ColRef<'_, E>,
+    offdiag: ColRef<'_, E>,
+    s: ColMut<'_, E>,
+    u: Option<MatMut<'_, E>>,
+    epsilon: E,
+    zero_threshold: E,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let n = diag.nrows();
+    assert!(all(offdiag.nrows() == n.saturating_sub(1), s.nrows() == n,));
+    if let Some(u) = u.rb() {
+        assert!(all(u.nrows() == n, u.ncols() == n));
+    }
+
+    if n == 0 {
+        return;
+    }
+
+    let mut s = s;
+
+    let mut all_finite = true;
+    for i in 0..n {
+        all_finite &= diag.read(i).faer_is_finite();
+    }
+    for i in 0..n - 1 {
+        all_finite &= offdiag.read(i).faer_is_finite();
+    }
+
+    if !all_finite {
+        s.fill(E::faer_nan());
+        if let Some(mut u) = u {
+            u.fill(E::faer_nan());
+        }
+        return;
+    }
+
+    let (d, stack) = stack.make_with(n, |i| diag.read(i));
+    let (e, stack) = stack.make_with(n - 1, |i| offdiag.read(i));
+
+    match u {
+        None => tridiag_qr_algorithm::compute_tridiag_real_evd_qr_algorithm(
+            d,
+            e,
+            None,
+            epsilon,
+            zero_threshold,
+        ),
+        Some(mut u) => {
+            tridiag_real_evd::compute_tridiag_real_evd(
+                d,
+                e,
+                u.rb_mut(),
+                epsilon,
+                zero_threshold,
+                parallelism,
+                stack,
+            );
+
+            // the divide and conquer algorithm doesn't guarantee the order of the eigenvalues
+            for i in 0..n {
+                let mut min_idx = i;
+                for j in i + 1..n {
+                    if d[j] < d[min_idx] {
+                        min_idx = j;
+                    }
+                }
+                if min_idx != i {
+                    d.swap(i, min_idx);
+                    crate::perm::swap_cols_idx(u.rb_mut(), i, min_idx);
+                }
+            }
+        }
+    }
+
+    for (i, &d) in d.iter().enumerate() {
+        s.write(i, d);
+    }
+}
+
 /// Computes the eigenvalue decomposition of a square real `matrix`.
 ///
 /// `s_re` and `s_im` respectively represent the real and imaginary parts of the diagonal of the
@@ -1442,7 +1595,7 @@ pub fn compute_evd_complex_custom_epsilon<E: ComplexField>(
 #[cfg(test)]
 mod herm_tests {
     use super::*;
-    use crate::{assert, complex_native::c64, Mat};
+    use crate::{assert, complex_native::c64, Col, Mat};
     use assert_approx_eq::assert_approx_eq;
 
     macro_rules! make_stack {
@@ -1518,6 +1671,67 @@ mod herm_tests {
         }
     }
 
+    #[test]
+    fn test_tridiag() {
+        for n in [1, 2, 3, 5, 10, 25, 64] {
+            let diag = Col::<f64>::from_fn(n, |_| rand::random());
+            let offdiag = Col::<f64>::from_fn(n - 1, |_| rand::random::<f64>() - 0.5);
+            let mat = Mat::from_fn(n, n, |i, j| {
+                if i == j {
+                    diag.read(i)
+                } else if i == j + 1 {
+                    offdiag.read(j)
+                } else if j == i + 1 {
+                    offdiag.read(i)
+                } else {
+                    0.0
+                }
+            });
+
+            let mut s = Mat::zeros(n, n);
+            let mut u = Mat::zeros(n, n);
+            compute_tridiag_evd(
+                diag.as_ref(),
+                offdiag.as_ref(),
+                s.as_mut().diagonal_mut().column_vector_mut(),
+                Some(u.as_mut()),
+                Parallelism::None,
+                make_stack!(compute_tridiag_evd_req::<f64>(
+                    n,
+                    ComputeVectors::Yes,
+                    Parallelism::None,
+                )),
+            );
+
+            let reconstructed = &u * &s * u.transpose();
+            for j in 0..n {
+                for i in 0..n {
+                    assert_approx_eq!(reconstructed.read(i, j), mat.read(i, j), 1e-10);
+                }
+            }
+
+            let mut s_only = Col::<f64>::zeros(n);
+            compute_tridiag_evd(
+                diag.as_ref(),
+                offdiag.as_ref(),
+                s_only.as_mut(),
+                None,
+                Parallelism::None,
+                make_stack!(compute_tridiag_evd_req::<f64>(
+                    n,
+                    ComputeVectors::No,
+                    Parallelism::None,
+                )),
+            );
+            for i in 0..n {
+                assert_approx_eq!(s_only.read(i), s.read(i, i), 1e-10);
+                if i > 0 {
+                    assert!(s_only.read(i - 1) <= s_only.read(i));
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_real_identity() {
         for n in [2, 3, 4, 5, 6, 7, 10, 15, 25] {
@@ -1,3 +1,10 @@
+//! Reduction of a Hermitian matrix to real symmetric or complex Hermitian tridiagonal form, using
+//! a sequence of Householder reflections.
+//!
+//! The reduction computes $A = Q T Q^H$, where $Q$ is unitary and $T$ is tridiagonal, and is the
+//! first step of the Hermitian eigenvalue decomposition. The tridiagonal matrix can then be passed
+//! to [`compute_tridiag_evd`](super::compute_tridiag_evd).
+
 use crate::{
     assert, debug_assert,
     linalg::{matmul::inner_prod::inner_prod_with_conj, temp_mat_req, temp_mat_zeroed},
@@ -10,6 +17,8 @@ use dyn_stack::{PodStack, SizeOverflow, StackReq};
 use faer_entity::*;
 use reborrow::*;
 
+/// Computes the size and alignment of required workspace for performing a tridiagonal
+/// reduction.
 pub fn tridiagonalize_in_place_req<E: Entity>(
     n: usize,
     parallelism: Parallelism,
@@ -974,6 +983,26 @@ impl<E: ComplexField> pulp::WithSimd for SymMatVec<'_, E> {
     }
 }
 
+/// Computes the tridiagonal reduction $A = Q T Q^H$ of the Hermitian matrix `a`, in place. Only
+/// the lower triangular half of the matrix is accessed.
+///
+/// On output, the diagonal and the subdiagonal of `a` contain those of $T$, and the entries below
+/// the subdiagonal contain the essential parts of the Householder reflections, whose product is
+/// the bottom right `(n-1)×(n-1)` block of $Q$. The coefficient of the `k`-th reflection is stored
+/// in `householder[(k, 0)]`, and the block Householder factor can then be built using
+/// [`upgrade_householder_factor`](crate::linalg::householder::upgrade_householder_factor).
+///
+/// In the complex case, the subdiagonal of $T$ is complex, and can be made real by scaling the
+/// rows and columns of $T$ by a diagonal unitary matrix, e.g. before passing it to
+/// [`compute_tridiag_evd`](super::compute_tridiag_evd).
+///
+/// # Panics
+/// Panics if `a` is not a square column-major matrix, or if `householder` has fewer than `n - 1`
+/// rows.
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`tridiagonalize_in_place_req`]).
+#[track_caller]
 pub fn tridiagonalize_in_place<E: ComplexField>(
     mut a: MatMut<'_, E>,
     mut householder: MatMut<'_, E>,
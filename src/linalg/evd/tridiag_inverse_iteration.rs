@@ -0,0 +1,314 @@
+use crate::{assert, ColRef, Entity, MatMut, RealField};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+
+/// Number of inverse iteration steps for each eigenvector. Since the shifts are accurate to
+/// working precision, the iteration usually converges after the first step, and the remaining
+/// steps only serve to purify the vector.
+const N_ITER: usize = 3;
+
+/// Computes the size and alignment of required workspace for computing `k` eigenvectors of a
+/// symmetric tridiagonal matrix of dimension `n` with [`compute_tridiag_eigenvectors`].
+pub fn compute_tridiag_eigenvectors_req<E: Entity>(
+    n: usize,
+    k: usize,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = k;
+    StackReq::try_all_of([
+        StackReq::try_new::<E>(n)?,     // lower
+        StackReq::try_new::<E>(n)?,     // diag
+        StackReq::try_new::<E>(n)?,     // upper
+        StackReq::try_new::<E>(n)?,     // upper2
+        StackReq::try_new::<usize>(n)?, // pivots
+        StackReq::try_new::<E>(n)?,     // x
+    ])
+}
+
+/// Computes the eigenvectors of the real symmetric tridiagonal matrix $T$ given by its diagonal
+/// `diag` and its subdiagonal `offdiag`, that correspond to the eigenvalues in `eigenvalues`,
+/// using inverse iteration, and stores them in the columns of `u`.
+///
+/// The cost is $\mathcal{O}(n)$ per eigenvector, plus the cost of reorthogonalizing the
+/// eigenvectors whose eigenvalues are close to each other, which makes this considerably cheaper
+/// than [`compute_tridiag_evd`](super::compute_tridiag_evd) when only a few eigenvectors are
+/// needed. The eigenvalues should be accurate to working precision, e.g. computed by
+/// [`compute_tridiag_evd`](super::compute_tridiag_evd) without eigenvectors, and must be sorted
+/// in either nondecreasing or nonincreasing order.
+///
+/// # Panics
+/// Panics if `offdiag` doesn't have size `diag.nrows() - 1`, if `u` doesn't have `diag.nrows()`
+/// rows and `eigenvalues.nrows()` columns, or if the provided memory in `stack` is insufficient
+/// (see [`compute_tridiag_eigenvectors_req`]).
+#[track_caller]
+pub fn compute_tridiag_eigenvectors<E: RealField>(
+    diag: ColRef<'_, E>,
+    offdiag: ColRef<'_, E>,
+    eigenvalues: ColRef<'_, E>,
+    u: MatMut<'_, E>,
+    epsilon: E,
+    stack: PodStack<'_>,
+) {
+    let n = diag.nrows();
+    let k = eigenvalues.nrows();
+    assert!(all(
+        offdiag.nrows() == n.saturating_sub(1),
+        u.nrows() == n,
+        u.ncols() == k,
+    ));
+    if n == 0 || k == 0 {
+        return;
+    }
+
+    let mut u = u;
+    let zero = E::faer_zero();
+    let one = E::faer_one();
+
+    // infinity norm of T, used to scale the perturbations and the clustering tolerance
+    let mut norm = zero;
+    for i in 0..n {
+        let mut row = diag.read(i).faer_abs();
+        if i > 0 {
+            row = row.faer_add(offdiag.read(i - 1).faer_abs());
+        }
+        if i + 1 < n {
+            row = row.faer_add(offdiag.read(i).faer_abs());
+        }
+        if row > norm {
+            norm = row;
+        }
+    }
+    if norm == zero {
+        norm = one;
+    }
+    let cluster_tol = norm.faer_mul(E::faer_from_f64(1e-3));
+    let pivot_tol = norm.faer_mul(epsilon);
+
+    let (lower, stack) = stack.make_with(n, |_| zero);
+    let (d, stack) = stack.make_with(n, |_| zero);
+    let (upper, stack) = stack.make_with(n, |_| zero);
+    let (upper2, stack) = stack.make_with(n, |_| zero);
+    let (pivots, stack) = stack.make_with(n, |_| 0usize);
+    let (x, _) = stack.make_with(n, |_| zero);
+
+    let mut cluster_start = 0;
+    let mut prev_shift = zero;
+
+    for j in 0..k {
+        let lambda = eigenvalues.read(j);
+        if j > 0 && lambda.faer_sub(eigenvalues.read(j - 1)).faer_abs() > cluster_tol {
+            cluster_start = j;
+        }
+
+        // identical shifts would produce identical vectors before reorthogonalization, so they
+        // are perturbed slightly
+        let mut shift = lambda;
+        if j > cluster_start {
+            let pert = E::faer_from_f64(10.0).faer_mul(epsilon).faer_mul(norm);
+            if shift.faer_sub(prev_shift).faer_abs() < pert {
+                let dir = if eigenvalues.read(j) >= eigenvalues.read(j - 1) {
+                    one
+                } else {
+                    one.faer_neg()
+                };
+                shift = prev_shift.faer_add(dir.faer_mul(pert));
+            }
+        }
+        prev_shift = shift;
+
+        // lu factorization of T - shift I with partial pivoting
+        for i in 0..n {
+            d[i] = diag.read(i).faer_sub(shift);
+            if i + 1 < n {
+                lower[i] = offdiag.read(i);
+                upper[i] = offdiag.read(i);
+            }
+            upper2[i] = zero;
+        }
+        for i in 0..n - 1 {
+            if d[i].faer_abs() >= lower[i].faer_abs() {
+                pivots[i] = i;
+                if d[i] != zero {
+                    let factor = lower[i].faer_div(d[i]);
+                    lower[i] = factor;
+                    d[i + 1] = d[i + 1].faer_sub(factor.faer_mul(upper[i]));
+                } else {
+                    lower[i] = zero;
+                }
+            } else {
+                pivots[i] = i + 1;
+                let factor = d[i].faer_div(lower[i]);
+                d[i] = lower[i];
+                lower[i] = factor;
+                let tmp = upper[i];
+                upper[i] = d[i + 1];
+                d[i + 1] = tmp.faer_sub(factor.faer_mul(d[i + 1]));
+                if i + 2 < n {
+                    upper2[i] = upper[i + 1];
+                    upper[i + 1] = factor.faer_neg().faer_mul(upper[i + 1]);
+                }
+            }
+        }
+        // the shifted matrix is nearly singular by construction, so tiny pivots are replaced
+        // to avoid overflow
+        for d in d.iter_mut() {
+            if d.faer_abs() < pivot_tol {
+                *d = if *d < zero {
+                    pivot_tol.faer_neg()
+                } else {
+                    pivot_tol
+                };
+            }
+        }
+
+        // deterministic starting vector
+        let mut seed = (j as u64)
+            .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+            .wrapping_add(0x2545_F491_4F6C_DD1D);
+        for x in x.iter_mut() {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            *x = E::faer_from_f64((seed >> 11) as f64 / (1u64 << 53) as f64 - 0.5);
+        }
+
+        for _ in 0..N_ITER {
+            // apply the row interchanges and the unit lower factor
+            for i in 0..n - 1 {
+                if pivots[i] != i {
+                    x.swap(i, i + 1);
+                }
+                x[i + 1] = x[i + 1].faer_sub(lower[i].faer_mul(x[i]));
+            }
+            // solve with the upper factor
+            for i in (0..n).rev() {
+                let mut acc = x[i];
+                if i + 1 < n {
+                    acc = acc.faer_sub(upper[i].faer_mul(x[i + 1]));
+                }
+                if i + 2 < n {
+                    acc = acc.faer_sub(upper2[i].faer_mul(x[i + 2]));
+                }
+                x[i] = acc.faer_div(d[i]);
+            }
+
+            // reorthogonalize against the previous vectors of the cluster
+            for p in cluster_start..j {
+                let mut dot = zero;
+                for i in 0..n {
+                    dot = dot.faer_add(u.read(i, p).faer_mul(x[i]));
+                }
+                for i in 0..n {
+                    x[i] = x[i].faer_sub(dot.faer_mul(u.read(i, p)));
+                }
+            }
+
+            // scale by the largest entry first to avoid overflow
+            let mut max = zero;
+            for x in x.iter() {
+                if x.faer_abs() > max {
+                    max = x.faer_abs();
+                }
+            }
+            if max == zero {
+                // the vector was entirely in the span of the cluster, restart from a unit vector
+                x[j % n] = one;
+                max = one;
+            }
+            let max_inv = max.faer_inv();
+            let mut norm2 = zero;
+            for x in x.iter_mut() {
+                *x = x.faer_mul(max_inv);
+                norm2 = norm2.faer_add(x.faer_abs2());
+            }
+            let norm_inv = norm2.faer_sqrt().faer_inv();
+            for x in x.iter_mut() {
+                *x = x.faer_mul(norm_inv);
+            }
+        }
+
+        for i in 0..n {
+            u.write(i, j, x[i]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Col, Mat};
+    use assert_approx_eq::assert_approx_eq;
+    use dyn_stack::GlobalPodBuffer;
+
+    #[test]
+    fn test_tridiag_eigenvectors() {
+        for n in [1, 2, 5, 30] {
+            let diag = Col::<f64>::from_fn(n, |_| rand::random());
+            let offdiag = Col::<f64>::from_fn(n - 1, |_| rand::random::<f64>() - 0.5);
+            let mat = Mat::from_fn(n, n, |i, j| {
+                if i == j {
+                    diag.read(i)
+                } else if i == j + 1 {
+                    offdiag.read(j)
+                } else if j == i + 1 {
+                    offdiag.read(i)
+                } else {
+                    0.0
+                }
+            });
+
+            let evd = mat.selfadjoint_eigendecomposition(crate::Side::Lower);
+            let mut eigenvalues = Col::<f64>::from_fn(n, |i| evd.s().column_vector().read(i));
+            eigenvalues
+                .as_slice_mut()
+                .sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let k = Ord::min(n, 3);
+            let eigenvalues = eigenvalues.as_ref().subrows(n - k, k);
+            let mut u = Mat::<f64>::zeros(n, k);
+            compute_tridiag_eigenvectors(
+                diag.as_ref(),
+                offdiag.as_ref(),
+                eigenvalues,
+                u.as_mut(),
+                f64::EPSILON,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    compute_tridiag_eigenvectors_req::<f64>(n, k).unwrap(),
+                )),
+            );
+
+            let residual =
+                &mat * &u
+                    - &u * Mat::<f64>::from_fn(k, k, |i, j| {
+                        if i == j {
+                            eigenvalues.read(i)
+                        } else {
+                            0.0
+                        }
+                    });
+            assert_approx_eq!(residual.norm_max(), 0.0, 1e-10);
+            let ortho = u.transpose() * &u - Mat::<f64>::identity(k, k);
+            assert_approx_eq!(ortho.norm_max(), 0.0, 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_tridiag_eigenvectors_cluster() {
+        // block diagonal matrix with a repeated eigenvalue
+        let diag = Col::<f64>::from_fn(4, |_| 2.0);
+        let offdiag = Col::<f64>::from_fn(3, |i| if i == 1 { 0.0 } else { 1.0 });
+        let eigenvalues = Col::<f64>::from_fn(4, |i| if i < 2 { 1.0 } else { 3.0 });
+
+        let mut u = Mat::<f64>::zeros(4, 4);
+        compute_tridiag_eigenvectors(
+            diag.as_ref(),
+            offdiag.as_ref(),
+            eigenvalues.as_ref(),
+            u.as_mut(),
+            f64::EPSILON,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                compute_tridiag_eigenvectors_req::<f64>(4, 4).unwrap(),
+            )),
+        );
+        let ortho = u.transpose() * &u - Mat::<f64>::identity(4, 4);
+        assert_approx_eq!(ortho.norm_max(), 0.0, 1e-10);
+    }
+}
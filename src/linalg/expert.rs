@@ -0,0 +1,517 @@
+//! Expert drivers for solving linear systems, with equilibration and error bounds.
+//!
+//! [`lu_solve_expert`] and [`cholesky_solve_expert`] are the counterparts of LAPACK's `xGESVX`
+//! and `xPOSVX`. In addition to the solution of the system, they:
+//! - equilibrate the matrix if it is badly scaled, to improve the accuracy of the factorization,
+//! - estimate the reciprocal condition number of the (equilibrated) matrix,
+//! - refine the solution with a few steps of iterative refinement in the working precision,
+//! - return the componentwise backward error of each column of the solution, as well as a bound on
+//! its relative forward error.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::expert, mat};
+//!
+//! let a = mat![[4.0, 1.0, 0.5], [1.0, 3.0, 1.0], [0.5, 1.0, 2.0f64]];
+//! let b = mat![[1.0], [2.0], [3.0]];
+//!
+//! let sol = expert::lu_solve_expert(a.as_ref(), b.as_ref(), Default::default()).unwrap();
+//! assert!((&a * &sol.solution - &b).norm_max() < 1e-12);
+//! assert!(sol.rcond > 0.1);
+//! assert!(sol.backward_error.read(0) < 1e-15);
+//! assert!(sol.forward_error.read(0) < 1e-12);
+//! ```
+
+use crate::{
+    assert,
+    col::{Col, ColRef},
+    get_global_parallelism,
+    linalg::{
+        cond_est,
+        equilibration::{
+            row_col_scaling, scale_cols_in_place, scale_rows_in_place, symmetric_scaling,
+        },
+        matmul::matmul,
+        solvers::{Cholesky, PartialPivLu},
+    },
+    mat::{Mat, MatMut, MatRef},
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj, Side,
+};
+use reborrow::*;
+
+pub use crate::linalg::{cholesky::llt::CholeskyError, equilibration::Equilibration};
+
+/// This error signifies that the LU decomposition has an exactly zero pivot, so that the matrix is
+/// singular and no solution was computed.
+#[derive(Debug, Clone, Copy)]
+pub struct SingularMatrixError {
+    /// The index of the first zero pivot.
+    pub zero_pivot: usize,
+}
+
+impl core::fmt::Display for SingularMatrixError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for SingularMatrixError {}
+
+/// Parameters of the expert drivers.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct ExpertParams {
+    /// Whether the matrix should be equilibrated when it is badly scaled.
+    pub equilibrate: bool,
+    /// Maximum number of steps of iterative refinement per column of the right-hand side.
+    pub max_refinement_iters: usize,
+}
+
+impl Default for ExpertParams {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            equilibrate: true,
+            max_refinement_iters: 5,
+        }
+    }
+}
+
+/// Solution of a linear system computed by an expert driver, along with error estimates.
+#[derive(Clone, Debug)]
+pub struct ExpertSolution<E: ComplexField> {
+    /// Solution of the linear system.
+    pub solution: Mat<E>,
+    /// Estimate of the reciprocal of the condition number in the 1-norm of the equilibrated
+    /// matrix. A value smaller than the machine epsilon means that the matrix is singular to
+    /// working precision.
+    pub rcond: E::Real,
+    /// Estimated bound on the relative forward error of each column of the solution, in the
+    /// infinity norm.
+    pub forward_error: Col<E::Real>,
+    /// Componentwise relative backward error of each column of the solution, i.e., the smallest
+    /// relative perturbation of the entries of the matrix and the right-hand side that makes it an
+    /// exact solution.
+    pub backward_error: Col<E::Real>,
+    /// Scaling that was applied to the matrix.
+    pub equilibration: Equilibration,
+    /// Row scaling factors $R$, such that the factorized matrix is $RAC$.
+    pub row_scale: Col<E::Real>,
+    /// Column scaling factors $C$, such that the factorized matrix is $RAC$.
+    pub col_scale: Col<E::Real>,
+}
+
+// the operator diag(w) A^{-H} diag(c), whose 1-norm is the infinity norm of
+// diag(c) A^{-1} diag(w)
+struct WeightedInverse<'a, E: ComplexField, S: ?Sized> {
+    solver: &'a S,
+    weights: ColRef<'a, E::Real>,
+    col_scale: ColRef<'a, E::Real>,
+}
+
+impl<E: ComplexField, S: ?Sized + SpSolverCore<E>> SpSolverCore<E> for WeightedInverse<'_, E, S> {
+    fn nrows(&self) -> usize {
+        self.solver.nrows()
+    }
+
+    fn ncols(&self) -> usize {
+        self.solver.ncols()
+    }
+
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        scale_rows_in_place(rhs.rb_mut(), self.col_scale);
+        self.solver
+            .solve_transpose_in_place_with_conj_impl(rhs.rb_mut(), conj.compose(Conj::Yes));
+        scale_rows_in_place(rhs, self.weights);
+    }
+
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        scale_rows_in_place(rhs.rb_mut(), self.weights);
+        self.solver
+            .solve_in_place_with_conj_impl(rhs.rb_mut(), conj.compose(Conj::Yes));
+        scale_rows_in_place(rhs, self.col_scale);
+    }
+}
+
+// iterative refinement of the solution of the scaled system in the working precision, followed by
+// the computation of the componentwise backward error and of a bound on the forward error of the
+// unscaled solution diag(c) x, as in LAPACK's xGERFS
+fn refine<E: ComplexField, S: ?Sized + SpSolverCore<E>>(
+    a: MatRef<'_, E>,
+    solver: &S,
+    b: MatRef<'_, E>,
+    x: MatMut<'_, E>,
+    col_scale: ColRef<'_, E::Real>,
+    max_iters: usize,
+) -> (Col<E::Real>, Col<E::Real>) {
+    let mut x = x;
+    let n = a.nrows();
+    let k = b.ncols();
+
+    let zero = E::Real::faer_zero();
+    let eps = E::Real::faer_epsilon();
+    let nz = E::Real::faer_from_f64((n + 1) as f64);
+    let safe1 = nz.faer_mul(E::Real::faer_min_positive());
+    let safe2 = safe1.faer_div(eps);
+
+    let mut forward_error = Col::<E::Real>::zeros(k);
+    let mut backward_error = Col::<E::Real>::zeros(k);
+    let mut r = Mat::<E>::zeros(n, 1);
+    let mut w = Col::<E::Real>::zeros(n);
+
+    for j in 0..k {
+        let bj = b.col(j);
+        let mut last_berr = E::Real::faer_from_f64(3.0);
+        let mut count = 0;
+
+        loop {
+            // r = b - A x, w = |A| |x| + |b|
+            r.as_mut().col_mut(0).copy_from(bj);
+            matmul(
+                r.as_mut(),
+                a,
+                x.rb().col(j).as_2d(),
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                get_global_parallelism(),
+            );
+            for i in 0..n {
+                w.write(i, bj.read(i).faer_abs());
+            }
+            for p in 0..n {
+                let xp = x.read(p, j).faer_abs();
+                for i in 0..n {
+                    w.write(i, w.read(i).faer_add(a.read(i, p).faer_abs().faer_mul(xp)));
+                }
+            }
+
+            let mut berr = zero;
+            for i in 0..n {
+                let ri = r.read(i, 0).faer_abs();
+                let wi = w.read(i);
+                let ratio = if wi > safe2 {
+                    ri.faer_div(wi)
+                } else {
+                    ri.faer_add(safe1).faer_div(wi.faer_add(safe1))
+                };
+                if ratio > berr {
+                    berr = ratio;
+                }
+            }
+            backward_error.write(j, berr);
+
+            if berr > eps && berr.faer_add(berr) <= last_berr && count < max_iters {
+                solver.solve_in_place_with_conj_impl(r.as_mut(), Conj::No);
+                for i in 0..n {
+                    x.write(i, j, x.read(i, j).faer_add(r.read(i, 0)));
+                }
+                last_berr = berr;
+                count += 1;
+            } else {
+                break;
+            }
+        }
+
+        // the error of the scaled solution is bounded by |A^{-1}| (|r| + nz eps (|A| |x| + |b|))
+        for i in 0..n {
+            let wi = w.read(i);
+            let ri = r.read(i, 0).faer_abs();
+            let wi = if wi > safe2 {
+                ri.faer_add(nz.faer_mul(eps).faer_mul(wi))
+            } else {
+                ri.faer_add(nz.faer_mul(eps).faer_mul(wi)).faer_add(safe1)
+            };
+            w.write(i, wi);
+        }
+        let bound = cond_est::inverse_norm1_est(&WeightedInverse {
+            solver,
+            weights: w.as_ref(),
+            col_scale,
+        });
+        let mut x_norm = zero;
+        for i in 0..n {
+            let xi = x.read(i, j).faer_abs().faer_mul(col_scale.read(i));
+            if xi > x_norm {
+                x_norm = xi;
+            }
+        }
+        forward_error.write(
+            j,
+            if x_norm != zero {
+                bound.faer_div(x_norm)
+            } else {
+                bound
+            },
+        );
+    }
+
+    (forward_error, backward_error)
+}
+
+/// Solves the linear system $AX = B$ using an LU decomposition with partial pivoting, with
+/// equilibration, iterative refinement and error bounds.
+///
+/// Returns an error if the matrix has an exactly zero pivot, in which case no solution is
+/// computed.
+///
+/// # Panics
+/// Panics if `matrix` is not square, or if `rhs.nrows() != matrix.nrows()`.
+#[track_caller]
+pub fn lu_solve_expert<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    rhs: MatRef<'_, E>,
+    params: ExpertParams,
+) -> Result<ExpertSolution<E>, SingularMatrixError> {
+    assert!(all(
+        matrix.nrows() == matrix.ncols(),
+        rhs.nrows() == matrix.nrows(),
+    ));
+    let n = matrix.nrows();
+
+    let mut row_scale = Col::<E::Real>::from_fn(n, |_| E::Real::faer_one());
+    let mut col_scale = Col::<E::Real>::from_fn(n, |_| E::Real::faer_one());
+    let mut equilibration = Equilibration::None;
+
+    // a matrix with a zero row or column is singular, which is reported by the factorization
+    if params.equilibrate {
+        if let Ok(scaling) = row_col_scaling(matrix) {
+            equilibration = scaling.recommended();
+            if matches!(equilibration, Equilibration::Row | Equilibration::Both) {
+                row_scale = scaling.row_scale;
+            }
+            if matches!(equilibration, Equilibration::Col | Equilibration::Both) {
+                col_scale = scaling.col_scale;
+            }
+        }
+    }
+
+    let mut a = matrix.to_owned();
+    scale_rows_in_place(a.as_mut(), row_scale.as_ref());
+    scale_cols_in_place(a.as_mut(), col_scale.as_ref());
+    let mut b = rhs.to_owned();
+    scale_rows_in_place(b.as_mut(), row_scale.as_ref());
+
+    let lu = PartialPivLu::new(a.as_ref());
+    for i in 0..n {
+        if lu.factors.read(i, i) == E::faer_zero() {
+            return Err(SingularMatrixError { zero_pivot: i });
+        }
+    }
+
+    let rcond = cond_est::rcond1_est(&lu, cond_est::norm1(a.as_ref()));
+
+    let mut x = b.clone();
+    lu.solve_in_place_with_conj_impl(x.as_mut(), Conj::No);
+    let (forward_error, backward_error) = refine(
+        a.as_ref(),
+        &lu,
+        b.as_ref(),
+        x.as_mut(),
+        col_scale.as_ref(),
+        params.max_refinement_iters,
+    );
+    scale_rows_in_place(x.as_mut(), col_scale.as_ref());
+
+    Ok(ExpertSolution {
+        solution: x,
+        rcond,
+        forward_error,
+        backward_error,
+        equilibration,
+        row_scale,
+        col_scale,
+    })
+}
+
+/// Solves the linear system $AX = B$ using a Cholesky decomposition, where $A$ is Hermitian
+/// positive definite, with equilibration, iterative refinement and error bounds.
+///
+/// The matrix is interpreted as Hermitian, but only the provided side is accessed. When the
+/// matrix is equilibrated, the same scaling is applied to its rows and columns, and the returned
+/// [`Equilibration`] is [`Equilibration::Both`].
+///
+/// Returns an error if the matrix is not numerically positive definite, in which case no solution
+/// is computed.
+///
+/// # Panics
+/// Panics if `matrix` is not square, or if `rhs.nrows() != matrix.nrows()`.
+#[track_caller]
+pub fn cholesky_solve_expert<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    side: Side,
+    rhs: MatRef<'_, E>,
+    params: ExpertParams,
+) -> Result<ExpertSolution<E>, CholeskyError> {
+    assert!(all(
+        matrix.nrows() == matrix.ncols(),
+        rhs.nrows() == matrix.nrows(),
+    ));
+    let n = matrix.nrows();
+
+    let full = Mat::<E>::from_fn(n, n, |i, j| {
+        let lower = match side {
+            Side::Lower => i >= j,
+            Side::Upper => i <= j,
+        };
+        if i == j {
+            E::faer_from_real(matrix.read(i, i).faer_real())
+        } else if lower {
+            matrix.read(i, j)
+        } else {
+            matrix.read(j, i).faer_conj()
+        }
+    });
+
+    let mut scale = Col::<E::Real>::from_fn(n, |_| E::Real::faer_one());
+    let mut equilibration = Equilibration::None;
+
+    // a non positive diagonal element means that the matrix is not positive definite, which is
+    // reported by the factorization
+    if params.equilibrate {
+        if let Ok(scaling) = symmetric_scaling(full.as_ref()) {
+            if scaling.is_recommended() {
+                scale = scaling.scale;
+                equilibration = Equilibration::Both;
+            }
+        }
+    }
+
+    let mut a = full;
+    scale_rows_in_place(a.as_mut(), scale.as_ref());
+    scale_cols_in_place(a.as_mut(), scale.as_ref());
+    let mut b = rhs.to_owned();
+    scale_rows_in_place(b.as_mut(), scale.as_ref());
+
+    let llt = Cholesky::try_new(a.as_ref(), Side::Lower)?;
+    let rcond = cond_est::rcond1_est(&llt, cond_est::norm1(a.as_ref()));
+
+    let mut x = b.clone();
+    llt.solve_in_place_with_conj_impl(x.as_mut(), Conj::No);
+    let (forward_error, backward_error) = refine(
+        a.as_ref(),
+        &llt,
+        b.as_ref(),
+        x.as_mut(),
+        scale.as_ref(),
+        params.max_refinement_iters,
+    );
+    scale_rows_in_place(x.as_mut(), scale.as_ref());
+
+    Ok(ExpertSolution {
+        solution: x,
+        rcond,
+        forward_error,
+        backward_error,
+        equilibration,
+        row_scale: scale.clone(),
+        col_scale: scale,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64};
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    fn relative_error(x: MatRef<'_, c64>, x_true: MatRef<'_, c64>, j: usize) -> f64 {
+        let x = x.col(j);
+        let x_true = x_true.col(j);
+        (x - x_true).norm_max() / x_true.norm_max()
+    }
+
+    #[test]
+    fn test_lu_expert() {
+        for n in [1, 4, 30] {
+            // badly scaled rows and columns
+            let a = Mat::<c64>::from_fn(n, n, |i, j| {
+                let d = if i == j {
+                    c64::new(4.0, 0.0)
+                } else {
+                    c64::new(0.0, 0.0)
+                };
+                random_c64()
+                    .faer_add(d)
+                    .faer_scale_real(10.0f64.powi(i as i32 - 10) * 10.0f64.powi(2 * j as i32))
+            });
+            // the scaled solution is well balanced, so that its small entries are still computed
+            // accurately relative to its norm
+            let x_true = Mat::<c64>::from_fn(n, 2, |i, _| {
+                random_c64().faer_scale_real(10.0f64.powi(-2 * i as i32))
+            });
+            let b = &a * &x_true;
+
+            let sol = lu_solve_expert(a.as_ref(), b.as_ref(), Default::default()).unwrap();
+            if n > 1 {
+                assert!(sol.equilibration != Equilibration::None);
+            }
+            assert!(sol.rcond > 1e-4);
+            for j in 0..2 {
+                assert!(sol.backward_error.read(j) < 1e-14);
+                assert!(sol.forward_error.read(j) < 1e-10);
+                assert!(
+                    relative_error(sol.solution.as_ref(), x_true.as_ref(), j)
+                        <= sol.forward_error.read(j)
+                );
+            }
+        }
+
+        let a = crate::mat![[1.0, 2.0], [2.0, 4.0f64]];
+        let b = crate::mat![[1.0], [1.0f64]];
+        let err = lu_solve_expert(a.as_ref(), b.as_ref(), Default::default()).unwrap_err();
+        assert!(err.zero_pivot == 1);
+    }
+
+    #[test]
+    fn test_cholesky_expert() {
+        for n in [1, 4, 30] {
+            let m = Mat::<c64>::from_fn(n, n, |_, _| random_c64());
+            let a = &m * m.adjoint().to_owned() + Mat::<c64>::identity(n, n);
+            // badly scaled Hermitian matrix
+            let d = Mat::<c64>::from_fn(n, n, |i, j| {
+                if i == j {
+                    c64::new(10.0f64.powi(i as i32 - 5), 0.0)
+                } else {
+                    c64::new(0.0, 0.0)
+                }
+            });
+            let a = &d * &a * &d;
+            let x_true = Mat::<c64>::from_fn(n, 3, |i, _| {
+                random_c64().faer_scale_real(10.0f64.powi(5 - i as i32))
+            });
+            let b = &a * &x_true;
+
+            for side in [Side::Lower, Side::Upper] {
+                let sol = cholesky_solve_expert(a.as_ref(), side, b.as_ref(), Default::default())
+                    .unwrap();
+                if n > 1 {
+                    assert!(sol.equilibration == Equilibration::Both);
+                }
+                for j in 0..3 {
+                    assert!(sol.backward_error.read(j) < 1e-14);
+                    assert!(sol.forward_error.read(j) < 1e-10);
+                    assert!(
+                        relative_error(sol.solution.as_ref(), x_true.as_ref(), j)
+                            <= sol.forward_error.read(j)
+                    );
+                }
+            }
+        }
+
+        let a = crate::mat![[1.0, 2.0], [2.0, 1.0f64]];
+        let b = crate::mat![[1.0], [1.0f64]];
+        assert!(
+            cholesky_solve_expert(a.as_ref(), Side::Lower, b.as_ref(), Default::default()).is_err()
+        );
+    }
+}
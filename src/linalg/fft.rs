@@ -0,0 +1,425 @@
+//! Fast Fourier transform of arbitrary length, and the convolution kernels built on it.
+//!
+//! Powers of two use an iterative radix-2 algorithm, and other lengths use Bluestein's algorithm,
+//! which expresses the transform as a convolution of power of two length.
+
+use crate::{
+    assert,
+    col::{ColMut, ColRef},
+    ComplexField, RealField,
+};
+use alloc::{vec, vec::Vec};
+
+#[inline]
+fn twiddle<R: RealField>(angle: f64) -> (R, R) {
+    (
+        R::faer_from_f64(libm::cos(angle)),
+        R::faer_from_f64(libm::sin(angle)),
+    )
+}
+
+fn radix2_in_place<R: RealField>(re: &mut [R], im: &mut [R], inverse: bool) {
+    let n = re.len();
+
+    // bit reversal permutation
+    let mut j = 0usize;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    let sign = if inverse { 1.0 } else { -1.0 };
+    let mut len = 2;
+    while len <= n {
+        let half = len / 2;
+        for k in 0..half {
+            let (wr, wi) = twiddle::<R>(sign * 2.0 * core::f64::consts::PI * k as f64 / len as f64);
+            for start in (0..n).step_by(len) {
+                let a = start + k;
+                let b = a + half;
+                let tr = re[b].faer_mul(wr).faer_sub(im[b].faer_mul(wi));
+                let ti = re[b].faer_mul(wi).faer_add(im[b].faer_mul(wr));
+                re[b] = re[a].faer_sub(tr);
+                im[b] = im[a].faer_sub(ti);
+                re[a] = re[a].faer_add(tr);
+                im[a] = im[a].faer_add(ti);
+            }
+        }
+        len *= 2;
+    }
+}
+
+fn bluestein_in_place<R: RealField>(re: &mut [R], im: &mut [R], inverse: bool) {
+    let n = re.len();
+    let m = (2 * n - 1).next_power_of_two();
+    let sign = if inverse { 1.0 } else { -1.0 };
+
+    // chirp w_k = exp(∓iπk²/n), with k² reduced modulo 2n to keep the angle accurate
+    let (wr, wi): (Vec<R>, Vec<R>) = (0..n)
+        .map(|k| {
+            let k2 = ((k as u128 * k as u128) % (2 * n as u128)) as f64;
+            twiddle::<R>(sign * core::f64::consts::PI * k2 / n as f64)
+        })
+        .unzip();
+
+    let zero = R::faer_zero();
+    let mut ar = vec![zero; m];
+    let mut ai = vec![zero; m];
+    let mut br = vec![zero; m];
+    let mut bi = vec![zero; m];
+    for k in 0..n {
+        ar[k] = re[k].faer_mul(wr[k]).faer_sub(im[k].faer_mul(wi[k]));
+        ai[k] = re[k].faer_mul(wi[k]).faer_add(im[k].faer_mul(wr[k]));
+    }
+    br[0] = wr[0];
+    bi[0] = wi[0].faer_neg();
+    for k in 1..n {
+        br[k] = wr[k];
+        bi[k] = wi[k].faer_neg();
+        br[m - k] = wr[k];
+        bi[m - k] = wi[k].faer_neg();
+    }
+
+    radix2_in_place(&mut ar, &mut ai, false);
+    radix2_in_place(&mut br, &mut bi, false);
+    for k in 0..m {
+        let r = ar[k].faer_mul(br[k]).faer_sub(ai[k].faer_mul(bi[k]));
+        let i = ar[k].faer_mul(bi[k]).faer_add(ai[k].faer_mul(br[k]));
+        ar[k] = r;
+        ai[k] = i;
+    }
+    radix2_in_place(&mut ar, &mut ai, true);
+
+    let scale = R::faer_from_f64(m as f64).faer_inv();
+    for k in 0..n {
+        let r = ar[k].faer_mul(scale);
+        let i = ai[k].faer_mul(scale);
+        re[k] = r.faer_mul(wr[k]).faer_sub(i.faer_mul(wi[k]));
+        im[k] = r.faer_mul(wi[k]).faer_add(i.faer_mul(wr[k]));
+    }
+}
+
+/// Direction of a discrete Fourier transform.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FftDirection {
+    /// Forward transform, $X_k = \sum_j x_j e^{-2i\pi jk/n}$.
+    Forward,
+    /// Inverse transform without normalization, $x_j = \sum_k X_k e^{2i\pi jk/n}$.
+    Inverse,
+}
+
+/// Complex fast Fourier transform backend.
+///
+/// The structured algorithms in faer use [`NativeFft`] by default, and can be given another
+/// implementation of this trait, e.g., a wrapper around an external FFT library.
+pub trait Fft<R: RealField>: Sync + core::fmt::Debug {
+    /// Computes the unnormalized discrete Fourier transform in the given direction of the complex
+    /// sequence with real parts `re` and imaginary parts `im`, and stores the result in place.
+    ///
+    /// `re` and `im` must have the same length, which may be arbitrary.
+    fn process(&self, re: &mut [R], im: &mut [R], direction: FftDirection);
+}
+
+/// FFT implemented natively in faer.
+///
+/// Powers of two use an iterative radix-2 algorithm, and other lengths use Bluestein's algorithm.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NativeFft;
+
+impl<R: RealField> Fft<R> for NativeFft {
+    #[inline]
+    #[track_caller]
+    fn process(&self, re: &mut [R], im: &mut [R], direction: FftDirection) {
+        fft_in_place(re, im, direction);
+    }
+}
+
+impl<R: RealField, F: ?Sized + Fft<R>> Fft<R> for &F {
+    #[inline]
+    #[track_caller]
+    fn process(&self, re: &mut [R], im: &mut [R], direction: FftDirection) {
+        (**self).process(re, im, direction)
+    }
+}
+
+/// Computes the unnormalized discrete Fourier transform in the given direction of the complex
+/// sequence with real parts `re` and imaginary parts `im`, and stores the result in place.
+///
+/// # Panics
+/// Panics if `re` and `im` don't have the same length.
+#[track_caller]
+pub fn fft_in_place<R: RealField>(re: &mut [R], im: &mut [R], direction: FftDirection) {
+    assert!(re.len() == im.len());
+    let n = re.len();
+    if n <= 1 {
+        return;
+    }
+    let inverse = direction == FftDirection::Inverse;
+    if n.is_power_of_two() {
+        radix2_in_place(re, im, inverse);
+    } else {
+        bluestein_in_place(re, im, inverse);
+    }
+}
+
+/// Computes the discrete Fourier transform of the real sequence `input`, and stores the first
+/// `n / 2 + 1` coefficients in `out_re` and `out_im`. The remaining coefficients are the
+/// conjugates of these by symmetry.
+///
+/// # Panics
+/// Panics if `out_re` or `out_im` don't have the length `input.len() / 2 + 1`.
+#[track_caller]
+pub fn rfft<R: RealField>(input: &[R], out_re: &mut [R], out_im: &mut [R], fft: &impl Fft<R>) {
+    let n = input.len();
+    assert!(all(out_re.len() == n / 2 + 1, out_im.len() == n / 2 + 1));
+    let mut re = input.to_vec();
+    let mut im = vec![R::faer_zero(); n];
+    fft.process(&mut re, &mut im, FftDirection::Forward);
+    out_re.copy_from_slice(&re[..n / 2 + 1]);
+    out_im.copy_from_slice(&im[..n / 2 + 1]);
+}
+
+/// Computes the inverse of [`rfft`], i.e., the real sequence of length `out.len()` whose first
+/// `n / 2 + 1` Fourier coefficients are given by `in_re` and `in_im`, normalized so that
+/// `irfft(rfft(x)) == x`.
+///
+/// The imaginary part of the coefficients that must be real for the sequence to be real is
+/// ignored.
+///
+/// # Panics
+/// Panics if `in_re` or `in_im` don't have the length `out.len() / 2 + 1`.
+#[track_caller]
+pub fn irfft<R: RealField>(in_re: &[R], in_im: &[R], out: &mut [R], fft: &impl Fft<R>) {
+    let n = out.len();
+    assert!(all(in_re.len() == n / 2 + 1, in_im.len() == n / 2 + 1));
+    if n == 0 {
+        return;
+    }
+    let mut re = vec![R::faer_zero(); n];
+    let mut im = vec![R::faer_zero(); n];
+    re[..n / 2 + 1].copy_from_slice(in_re);
+    im[..n / 2 + 1].copy_from_slice(in_im);
+    for k in n / 2 + 1..n {
+        re[k] = in_re[n - k];
+        im[k] = in_im[n - k].faer_neg();
+    }
+    fft.process(&mut re, &mut im, FftDirection::Inverse);
+    let scale = R::faer_from_f64(n as f64).faer_inv();
+    for (out, re) in out.iter_mut().zip(&re) {
+        *out = re.faer_mul(scale);
+    }
+}
+
+/// Splits `x` into its real and imaginary parts.
+pub(crate) fn split_parts<E: ComplexField>(
+    x: ColRef<'_, E>,
+    re: &mut [E::Real],
+    im: &mut [E::Real],
+) {
+    for i in 0..x.nrows() {
+        let value = x.read(i);
+        re[i] = value.faer_real();
+        im[i] = value.faer_imag();
+    }
+}
+
+/// Writes `(re + i im) * scale` to `out`, ignoring the imaginary part if `E` is real.
+pub(crate) fn join_parts<E: ComplexField>(
+    out: ColMut<'_, E>,
+    re: &[E::Real],
+    im: &[E::Real],
+    scale: E::Real,
+) {
+    let mut out = out;
+    let is_real = coe::is_same::<E, E::Real>();
+    let i_unit = if is_real {
+        E::faer_zero()
+    } else {
+        E::faer_from_f64(-1.0).faer_sqrt()
+    };
+    for i in 0..out.nrows() {
+        let mut value = E::faer_from_real(re[i].faer_mul(scale));
+        if !is_real {
+            value = value.faer_add(i_unit.faer_mul(E::faer_from_real(im[i].faer_mul(scale))));
+        }
+        out.write(i, value);
+    }
+}
+
+/// Computes the linear convolution of `lhs` and `rhs`, $z_k = \sum_j x_j y_{k - j}$, and stores
+/// the result in `out`.
+///
+/// # Panics
+/// Panics if `out` doesn't have the length `lhs.nrows() + rhs.nrows() - 1`, or zero if either
+/// input is empty.
+#[track_caller]
+pub fn convolve<E: ComplexField>(
+    out: ColMut<'_, E>,
+    lhs: ColRef<'_, E>,
+    rhs: ColRef<'_, E>,
+    fft: &impl Fft<E::Real>,
+) {
+    let (m, n) = (lhs.nrows(), rhs.nrows());
+    let len = if m == 0 || n == 0 { 0 } else { m + n - 1 };
+    assert!(out.nrows() == len);
+    if len == 0 {
+        return;
+    }
+
+    let size = len.next_power_of_two();
+    let zero = E::Real::faer_zero();
+    let (mut ar, mut ai) = (vec![zero; size], vec![zero; size]);
+    let (mut br, mut bi) = (vec![zero; size], vec![zero; size]);
+    split_parts(lhs, &mut ar, &mut ai);
+    split_parts(rhs, &mut br, &mut bi);
+    fft.process(&mut ar, &mut ai, FftDirection::Forward);
+    fft.process(&mut br, &mut bi, FftDirection::Forward);
+    for k in 0..size {
+        let r = ar[k].faer_mul(br[k]).faer_sub(ai[k].faer_mul(bi[k]));
+        let i = ar[k].faer_mul(bi[k]).faer_add(ai[k].faer_mul(br[k]));
+        ar[k] = r;
+        ai[k] = i;
+    }
+    fft.process(&mut ar, &mut ai, FftDirection::Inverse);
+    join_parts(
+        out,
+        &ar,
+        &ai,
+        E::Real::faer_from_f64(size as f64).faer_inv(),
+    );
+}
+
+/// Computes the autocorrelation of `x`, $r_k = \sum_j x_{j + k} \bar x_j$ for
+/// $0 \leq k < $ `out.nrows()`, and stores the result in `out`.
+///
+/// # Panics
+/// Panics if `out` has more elements than `x`.
+#[track_caller]
+pub fn autocorrelation<E: ComplexField>(
+    out: ColMut<'_, E>,
+    x: ColRef<'_, E>,
+    fft: &impl Fft<E::Real>,
+) {
+    let n = x.nrows();
+    assert!(out.nrows() <= n);
+    if n == 0 {
+        return;
+    }
+
+    // zero padding to avoid the circular wrap around
+    let size = (2 * n - 1).next_power_of_two();
+    let zero = E::Real::faer_zero();
+    let (mut re, mut im) = (vec![zero; size], vec![zero; size]);
+    split_parts(x, &mut re, &mut im);
+    fft.process(&mut re, &mut im, FftDirection::Forward);
+    for k in 0..size {
+        re[k] = re[k].faer_abs2().faer_add(im[k].faer_abs2());
+        im[k] = zero;
+    }
+    fft.process(&mut re, &mut im, FftDirection::Inverse);
+    let len = out.nrows();
+    join_parts(
+        out,
+        &re[..len],
+        &im[..len],
+        E::Real::faer_from_f64(size as f64).faer_inv(),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, col::Col, complex_native::c64};
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    #[test]
+    fn test_fft() {
+        for n in [0, 1, 2, 3, 5, 8, 12, 17, 64] {
+            let x = (0..n).map(|_| random_c64()).collect::<Vec<_>>();
+            let mut re = x.iter().map(|x| x.re).collect::<Vec<_>>();
+            let mut im = x.iter().map(|x| x.im).collect::<Vec<_>>();
+
+            fft_in_place(&mut re, &mut im, FftDirection::Forward);
+            for k in 0..n {
+                let mut expected = c64::new(0.0, 0.0);
+                for (j, x) in x.iter().enumerate() {
+                    let angle = -2.0 * core::f64::consts::PI * (j * k % n) as f64 / n as f64;
+                    expected = expected + *x * c64::new(angle.cos(), angle.sin());
+                }
+                assert!((c64::new(re[k], im[k]) - expected).norm() < 1e-12);
+            }
+
+            NativeFft.process(&mut re, &mut im, FftDirection::Inverse);
+            for k in 0..n {
+                assert!((c64::new(re[k], im[k]) * (1.0 / n as f64) - x[k]).norm() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rfft() {
+        for n in [1, 2, 7, 16] {
+            let x = (0..n).map(|_| rand::random::<f64>()).collect::<Vec<_>>();
+            let mut re = vec![0.0; n / 2 + 1];
+            let mut im = vec![0.0; n / 2 + 1];
+            rfft(&x, &mut re, &mut im, &NativeFft);
+
+            let mut full_re = x.clone();
+            let mut full_im = vec![0.0; n];
+            fft_in_place(&mut full_re, &mut full_im, FftDirection::Forward);
+            for k in 0..n / 2 + 1 {
+                assert!((re[k] - full_re[k]).abs() < 1e-12);
+                assert!((im[k] - full_im[k]).abs() < 1e-12);
+            }
+
+            let mut y = vec![0.0; n];
+            irfft(&re, &im, &mut y, &NativeFft);
+            for k in 0..n {
+                assert!((y[k] - x[k]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_convolve_autocorrelation() {
+        let x = Col::<c64>::from_fn(5, |_| random_c64());
+        let y = Col::<c64>::from_fn(3, |_| random_c64());
+        let mut z = Col::<c64>::zeros(7);
+        convolve(z.as_mut(), x.as_ref(), y.as_ref(), &NativeFft);
+        for k in 0..7 {
+            let mut expected = c64::new(0.0, 0.0);
+            for j in 0..5 {
+                if k >= j && k - j < 3 {
+                    expected = expected + x.read(j) * y.read(k - j);
+                }
+            }
+            assert!((z.read(k) - expected).norm() < 1e-12);
+        }
+
+        let mut r = Col::<c64>::zeros(4);
+        autocorrelation(r.as_mut(), x.as_ref(), &NativeFft);
+        for k in 0..4 {
+            let mut expected = c64::new(0.0, 0.0);
+            for j in 0..5 - k {
+                expected = expected + x.read(j + k) * x.read(j).faer_conj();
+            }
+            assert!((r.read(k) - expected).norm() < 1e-12);
+        }
+
+        let x = Col::<f64>::from_fn(6, |i| i as f64);
+        let mut r = Col::<f64>::zeros(6);
+        autocorrelation(r.as_mut(), x.as_ref(), &NativeFft);
+        assert!((r.read(1) - 40.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,540 @@
+//! Inverse iteration for the eigenvectors of a dense matrix, given approximations of its
+//! eigenvalues.
+//!
+//! Given an approximation $\sigma$ of an eigenvalue of $A$, the inverse iteration repeatedly
+//! solves linear systems with $A - \sigma I$. Since $\sigma$ is close to an eigenvalue, the
+//! solution is dominated by the corresponding eigenvector, and one or two iterations are usually
+//! enough when $\sigma$ is accurate to working precision. This is the method used by LAPACK's
+//! `xSTEIN` and `xHSEIN` to compute eigenvectors from eigenvalues obtained from the tridiagonal
+//! or Schur form, or from any external estimate.
+//!
+//! The LU factorization of $A - \sigma I$ only depends on the shift, so it is computed once by
+//! [`InverseIteration::new`], and can be reused for several starting vectors, e.g., for an
+//! eigenvalue with multiplicity larger than one.
+//!
+//! The eigenvalues of a real matrix may be complex, in which case the matrix must first be
+//! converted to the corresponding complex type.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{
+//!     col,
+//!     linalg::inverse_iteration::{InverseIteration, InverseIterationParams},
+//!     mat,
+//! };
+//!
+//! let a = mat![[2.0, 1.0], [1.0, 2.0f64]];
+//!
+//! // approximation of the eigenvalue 3, with eigenvector [1, 1] / sqrt(2)
+//! let solver = InverseIteration::new(a.as_ref(), 3.001);
+//! let mut x = col![1.0, 0.0];
+//! solver
+//!     .refine(x.as_mut(), InverseIterationParams::default())
+//!     .unwrap();
+//! assert!((x.read(0) - x.read(1)).abs() < 1e-6);
+//! ```
+
+use crate::{
+    col::{Col, ColMut, ColRef},
+    get_global_parallelism,
+    linalg::{
+        lu::partial_pivoting::{
+            compute::{lu_in_place, lu_in_place_req},
+            solve::{solve_in_place, solve_in_place_req},
+        },
+        power_iteration::fill_pseudo_random,
+    },
+    mat::{Mat, MatMut, MatRef},
+    perm::PermRef,
+    unzipped, zipped, ComplexField, Conj, Conjugate,
+};
+use dyn_stack::{GlobalPodBuffer, PodStack};
+use reborrow::*;
+
+/// Parameters of the inverse iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct InverseIterationParams<E: ComplexField> {
+    /// Absolute tolerance on the residual $\|(A - \theta I) x\|$, where $\theta$ is the refined
+    /// eigenvalue estimate.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residual, relative to the 1-norm of $A - \sigma I$.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of iterations.
+    pub max_iters: usize,
+}
+
+/// Information about a successful run of the inverse iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct InverseIterationInfo<E: ComplexField> {
+    /// Residual norm $\|(A - \theta I) x\|$, where $x$ has unit norm, and $\theta$ is the
+    /// eigenvalue estimate that minimizes it. When several eigenvectors are computed, this is the
+    /// largest one.
+    pub abs_residual: E::Real,
+    /// Residual norm, relative to the 1-norm of $A - \sigma I$.
+    pub rel_residual: E::Real,
+    /// Number of iterations until convergence. When several eigenvectors are computed, this is
+    /// the largest one.
+    pub iter_count: usize,
+}
+
+/// Error from the inverse iteration.
+#[derive(Copy, Clone, Debug)]
+pub enum InverseIterationError<E: ComplexField> {
+    /// The iteration did not converge within the maximum number of iterations. This usually
+    /// means that the shift is not an accurate approximation of an eigenvalue.
+    NoConvergence {
+        /// Residual norm.
+        abs_residual: E::Real,
+        /// Residual norm, relative to the 1-norm of $A - \sigma I$.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for InverseIterationParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_sqrt(),
+            max_iters: 10,
+        }
+    }
+}
+
+/// Factorization of a shifted matrix $A - \sigma I$, used to compute eigenvectors of $A$ for
+/// eigenvalues close to $\sigma$ with the inverse iteration.
+#[derive(Clone, Debug)]
+pub struct InverseIteration<E: ComplexField> {
+    shift: E,
+    norm: E::Real,
+    factors: Mat<E>,
+    row_perm: alloc::vec::Vec<usize>,
+    row_perm_inv: alloc::vec::Vec<usize>,
+}
+
+impl<E: ComplexField> InverseIteration<E> {
+    /// Computes the LU factorization with partial pivoting of `mat - shift * I`.
+    ///
+    /// The shifted matrix is nearly singular by design, which is what makes the inverse iteration
+    /// converge quickly. If it is exactly singular, the shift is perturbed by a small multiple of
+    /// $\epsilon \|A - \sigma I\|_1$ before computing the factorization.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(mat: MatRef<'_, ViewE>, shift: E) -> Self {
+        let n = mat.nrows();
+        crate::assert!(mat.ncols() == n);
+
+        let mat = mat.to_owned();
+        let mut norm = E::Real::faer_zero();
+        for j in 0..n {
+            let mut col_norm = E::Real::faer_zero();
+            for i in 0..n {
+                let value = if i == j {
+                    mat.read(i, i).faer_sub(shift)
+                } else {
+                    mat.read(i, j)
+                };
+                col_norm = col_norm.faer_add(value.faer_abs());
+            }
+            if col_norm > norm {
+                norm = col_norm;
+            }
+        }
+
+        let parallelism = get_global_parallelism();
+        let params = Default::default();
+        let mut factors = Mat::<E>::zeros(n, n);
+        let mut row_perm = alloc::vec![0usize; n];
+        let mut row_perm_inv = alloc::vec![0usize; n];
+
+        let mut perturbation = E::Real::faer_zero();
+        for _ in 0..64 {
+            let shift = shift.faer_add(E::faer_from_real(perturbation));
+            factors.copy_from(&mat);
+            for i in 0..n {
+                factors.write(i, i, factors.read(i, i).faer_sub(shift));
+            }
+            lu_in_place(
+                factors.as_mut(),
+                &mut row_perm,
+                &mut row_perm_inv,
+                parallelism,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    lu_in_place_req::<usize, E>(n, n, parallelism, params).unwrap(),
+                )),
+                params,
+            );
+
+            let singular = (0..n).any(|i| {
+                let pivot = factors.read(i, i).faer_abs();
+                pivot == E::Real::faer_zero() || !pivot.faer_is_finite()
+            });
+            if !singular {
+                break;
+            }
+            // the shifted matrix is exactly singular, so the shift is moved by a tiny amount,
+            // which is within the accuracy of the eigenvalue anyway
+            perturbation = if perturbation == E::Real::faer_zero() {
+                let unit = if norm > E::Real::faer_zero() {
+                    norm
+                } else {
+                    E::Real::faer_one()
+                };
+                E::Real::faer_epsilon().faer_mul(unit)
+            } else {
+                perturbation.faer_add(perturbation)
+            };
+        }
+
+        Self {
+            shift,
+            norm,
+            factors,
+            row_perm,
+            row_perm_inv,
+        }
+    }
+
+    /// Returns the shift $\sigma$.
+    #[inline]
+    pub fn shift(&self) -> E {
+        self.shift
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.factors.nrows()
+    }
+
+    #[track_caller]
+    fn solve_in_place(&self, rhs: ColMut<'_, E>) {
+        let parallelism = get_global_parallelism();
+        solve_in_place(
+            self.factors.as_ref(),
+            Conj::No,
+            unsafe { PermRef::new_unchecked(&self.row_perm, &self.row_perm_inv) },
+            rhs.as_2d_mut(),
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                solve_in_place_req::<usize, E>(self.dim(), self.dim(), 1, parallelism).unwrap(),
+            )),
+        );
+    }
+
+    /// Refines `eigenvector` with the inverse iteration, so that it approximates an eigenvector
+    /// of the matrix for the eigenvalue closest to the shift.
+    ///
+    /// `eigenvector` contains the starting vector on input, and the normalized eigenvector on
+    /// output. If it is zero, a pseudo-random starting vector is used instead. The output is
+    /// filled even if the iteration does not converge.
+    ///
+    /// # Panics
+    /// Panics if `eigenvector` doesn't have `self.dim()` rows.
+    #[track_caller]
+    pub fn refine(
+        &self,
+        eigenvector: ColMut<'_, E>,
+        params: InverseIterationParams<E>,
+    ) -> Result<InverseIterationInfo<E>, InverseIterationError<E>> {
+        self.refine_orthogonal(eigenvector, Mat::<E>::zeros(self.dim(), 0).as_ref(), params)
+    }
+
+    /// Same as [`Self::refine`], except that the iterates are kept orthogonal to the orthonormal
+    /// columns of `basis`.
+    ///
+    /// This is used to compute several independent eigenvectors of an eigenvalue with
+    /// multiplicity larger than one, in which case `basis` should contain the previously computed
+    /// eigenvectors.
+    ///
+    /// # Panics
+    /// Panics if `eigenvector` or `basis` don't have `self.dim()` rows.
+    #[track_caller]
+    pub fn refine_orthogonal(
+        &self,
+        eigenvector: ColMut<'_, E>,
+        basis: MatRef<'_, E>,
+        params: InverseIterationParams<E>,
+    ) -> Result<InverseIterationInfo<E>, InverseIterationError<E>> {
+        let mut x = eigenvector;
+        let n = self.dim();
+        crate::assert!(all(x.nrows() == n, basis.nrows() == n));
+
+        let zero = E::Real::faer_zero();
+        if n == 0 {
+            return Ok(InverseIterationInfo {
+                abs_residual: zero,
+                rel_residual: zero,
+                iter_count: 0,
+            });
+        }
+
+        let norm = if self.norm > zero {
+            self.norm
+        } else {
+            E::Real::faer_one()
+        };
+        let threshold = params
+            .rel_tolerance
+            .faer_mul(norm)
+            .faer_add(params.abs_tolerance);
+
+        orthogonalize(x.rb_mut(), basis);
+        normalize(x.rb_mut(), 0);
+
+        let mut y = Col::<E>::zeros(n);
+        let mut abs_residual = zero;
+        for iter in 0..=params.max_iters {
+            // y = (A - σI)⁻¹ x
+            y.copy_from(x.rb());
+            self.solve_in_place(y.as_mut());
+            orthogonalize(y.as_mut(), basis);
+
+            // (A - θI) y = x - μ y, with θ = σ + μ, and μ is chosen to minimize the residual
+            let y_norm2 = y.squared_norm_l2();
+            let mut dot = E::faer_zero();
+            for i in 0..n {
+                dot = dot.faer_add(y.read(i).faer_conj().faer_mul(x.read(i)));
+            }
+            let mu = dot.faer_scale_real(y_norm2.faer_inv());
+            let mut r = zero;
+            for i in 0..n {
+                r = r.faer_add(x.read(i).faer_sub(mu.faer_mul(y.read(i))).faer_abs2());
+            }
+            abs_residual = r.faer_div(y_norm2).faer_sqrt();
+
+            x.copy_from(y.as_ref());
+            normalize(x.rb_mut(), iter as u64 + 1);
+
+            if abs_residual <= threshold {
+                return Ok(InverseIterationInfo {
+                    abs_residual,
+                    rel_residual: abs_residual.faer_div(norm),
+                    iter_count: iter,
+                });
+            }
+        }
+
+        Err(InverseIterationError::NoConvergence {
+            abs_residual,
+            rel_residual: abs_residual.faer_div(norm),
+        })
+    }
+}
+
+/// Orthogonalizes `x` against the orthonormal columns of `basis`, using classical Gram-Schmidt
+/// with reorthogonalization.
+fn orthogonalize<E: ComplexField>(x: ColMut<'_, E>, basis: MatRef<'_, E>) {
+    let mut x = x;
+    for _ in 0..2 {
+        for j in 0..basis.ncols() {
+            let q = basis.col(j);
+            let mut dot = E::faer_zero();
+            for i in 0..x.nrows() {
+                dot = dot.faer_add(q.read(i).faer_conj().faer_mul(x.read(i)));
+            }
+            for i in 0..x.nrows() {
+                x.write(i, x.read(i).faer_sub(q.read(i).faer_mul(dot)));
+            }
+        }
+    }
+}
+
+/// Scales `x` to unit norm and returns its previous norm. If the norm is zero or not finite, `x`
+/// is replaced by a pseudo-random unit vector.
+fn normalize<E: ComplexField>(x: ColMut<'_, E>, seed: u64) -> E::Real {
+    let mut x = x;
+    let norm = x.rb().norm_l2();
+    let (scale, norm) = if norm > E::Real::faer_zero() && norm.faer_is_finite() {
+        (norm.faer_inv(), norm)
+    } else {
+        fill_pseudo_random(x.rb_mut(), seed);
+        (x.rb().norm_l2().faer_inv(), E::Real::faer_zero())
+    };
+    zipped!(x.as_2d_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(scale)));
+    norm
+}
+
+/// Computes the eigenvectors of `mat` corresponding to the approximate eigenvalues
+/// `eigenvalues`, and stores them in the columns of `eigenvectors`.
+///
+/// The factorization of the shifted matrix is computed once for each distinct eigenvalue, and
+/// the eigenvectors of repeated eigenvalues are kept orthogonal to each other. The eigenvectors
+/// are normalized to unit norm, and are filled even if the iteration does not converge.
+///
+/// # Panics
+/// Panics if `mat` is not square, or if `eigenvectors` doesn't have `mat.nrows()` rows and
+/// `eigenvalues.nrows()` columns.
+#[track_caller]
+pub fn inverse_iteration<E: ComplexField>(
+    eigenvectors: MatMut<'_, E>,
+    mat: MatRef<'_, E>,
+    eigenvalues: ColRef<'_, E>,
+    params: InverseIterationParams<E>,
+) -> Result<InverseIterationInfo<E>, InverseIterationError<E>> {
+    let mut x = eigenvectors;
+    let n = mat.nrows();
+    let k = eigenvalues.nrows();
+    crate::assert!(all(mat.ncols() == n, x.nrows() == n, x.ncols() == k));
+
+    let zero = E::Real::faer_zero();
+    let mut info = InverseIterationInfo {
+        abs_residual: zero,
+        rel_residual: zero,
+        iter_count: 0,
+    };
+    let mut converged = true;
+
+    // indices of the eigenvalues with the same shift, which are contiguous in `order`
+    let mut order = (0..k).collect::<alloc::vec::Vec<_>>();
+    order.sort_by(|&i, &j| {
+        let (a, b) = (eigenvalues.read(i), eigenvalues.read(j));
+        let (a, b) = (
+            (a.faer_real(), a.faer_imag()),
+            (b.faer_real(), b.faer_imag()),
+        );
+        a.partial_cmp(&b).unwrap_or(core::cmp::Ordering::Equal)
+    });
+
+    let mut start = 0;
+    while start < k {
+        let shift = eigenvalues.read(order[start]);
+        let mut end = start + 1;
+        while end < k && eigenvalues.read(order[end]) == shift {
+            end += 1;
+        }
+
+        let solver = InverseIteration::new(mat, shift);
+        let mut basis = Mat::<E>::zeros(n, 0);
+        for &j in &order[start..end] {
+            let mut col = x.rb_mut().col_mut(j);
+            fill_pseudo_random(col.rb_mut(), j as u64 + 1);
+            let result = solver.refine_orthogonal(col.rb_mut(), basis.as_ref(), params);
+            let (abs_residual, rel_residual) = match result {
+                Ok(this) => {
+                    if this.iter_count > info.iter_count {
+                        info.iter_count = this.iter_count;
+                    }
+                    (this.abs_residual, this.rel_residual)
+                }
+                Err(InverseIterationError::NoConvergence {
+                    abs_residual,
+                    rel_residual,
+                }) => {
+                    converged = false;
+                    info.iter_count = params.max_iters;
+                    (abs_residual, rel_residual)
+                }
+            };
+            if abs_residual > info.abs_residual {
+                info.abs_residual = abs_residual;
+            }
+            if rel_residual > info.rel_residual {
+                info.rel_residual = rel_residual;
+            }
+
+            let m = basis.ncols();
+            basis.resize_with(n, m + 1, |i, _| col.read(i));
+        }
+        start = end;
+    }
+
+    if converged {
+        Ok(info)
+    } else {
+        Err(InverseIterationError::NoConvergence {
+            abs_residual: info.abs_residual,
+            rel_residual: info.rel_residual,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64};
+
+    #[test]
+    fn test_inverse_iteration_real() {
+        let n = 30;
+        let a = Mat::<f64>::from_fn(n, n, |_, _| rand::random::<f64>() - 0.5);
+        let a = &a + a.transpose();
+        let evd = a.selfadjoint_eigendecomposition(crate::Side::Lower);
+        let s = evd.s().column_vector().to_owned();
+
+        // perturbed eigenvalues
+        let approx = Col::<f64>::from_fn(n, |i| s.read(i) * (1.0 + 1e-10));
+        let mut x = Mat::<f64>::zeros(n, n);
+        let info =
+            inverse_iteration(x.as_mut(), a.as_ref(), approx.as_ref(), Default::default()).unwrap();
+        assert!(info.iter_count <= 3);
+
+        for j in 0..n {
+            let r = &a * x.col(j) - x.col(j) * crate::scale(s.read(j));
+            assert!(r.norm_l2() < 1e-8);
+            assert!((x.col(j).norm_l2() - 1.0).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_inverse_iteration_complex_eigenvalues() {
+        // real matrix with complex eigenvalues, converted to complex
+        let n = 20;
+        let a = Mat::<f64>::from_fn(n, n, |_, _| rand::random::<f64>() - 0.5);
+        let eigenvalues = a.eigenvalues::<c64>();
+        let a = Mat::<c64>::from_fn(n, n, |i, j| c64::new(a.read(i, j), 0.0));
+        let s = Col::<c64>::from_fn(n, |i| eigenvalues[i]);
+
+        let mut x = Mat::<c64>::zeros(n, n);
+        inverse_iteration(x.as_mut(), a.as_ref(), s.as_ref(), Default::default()).unwrap();
+        for j in 0..n {
+            let r = &a * x.col(j) - x.col(j) * crate::scale(s.read(j));
+            assert!(r.norm_l2() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_inverse_iteration_repeated() {
+        // the eigenvalue 2 has multiplicity 3, and the shift is exact
+        let n = 6;
+        let a = Mat::<f64>::from_fn(n, n, |i, j| {
+            if i != j {
+                0.0
+            } else if i < 3 {
+                2.0
+            } else {
+                (i + 2) as f64
+            }
+        });
+        let s = Col::<f64>::from_fn(3, |_| 2.0);
+        let mut x = Mat::<f64>::zeros(n, 3);
+        inverse_iteration(x.as_mut(), a.as_ref(), s.as_ref(), Default::default()).unwrap();
+
+        assert!((x.transpose() * &x - Mat::<f64>::identity(3, 3)).norm_max() < 1e-10);
+        for j in 0..3 {
+            let r = &a * x.col(j) - x.col(j) * crate::scale(2.0);
+            assert!(r.norm_l2() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_inverse_iteration_reuse() {
+        let a = crate::mat![[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0f64]];
+        let evd = a.selfadjoint_eigendecomposition(crate::Side::Lower);
+        let lambda = evd.s().column_vector().read(0);
+        let solver = InverseIteration::new(a.as_ref(), lambda + 1e-3);
+        assert!(solver.shift() == lambda + 1e-3);
+
+        for start in [[1.0, 0.0, 0.0], [0.0, 1.0, 1.0]] {
+            let mut x = Col::<f64>::from_fn(3, |i| start[i]);
+            solver.refine(x.as_mut(), Default::default()).unwrap();
+            let r = &a * &x - &x * crate::scale(lambda);
+            assert!(r.norm_l2() < 1e-6);
+        }
+    }
+}
@@ -18,6 +18,9 @@ use reborrow::*;
 /// Panics if `dst` does not have the correct dimensions. The dimensions
 /// of `dst` must be `nrows(A) * nrows(B)` by `ncols(A) * ncols(B)`.
 ///
+/// See [`KronOp`](crate::linop::combinators::KronOp) to apply the Kronecker
+/// product to a matrix without forming it.
+///
 /// # Example
 ///
 /// ```
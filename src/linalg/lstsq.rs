@@ -0,0 +1,449 @@
+//! High level least squares solvers.
+//!
+//! [`lstsq`] computes the solution of $\min_X \|AX - B\|_F$ for a matrix $A$ of any shape and
+//! rank, similarly to LAPACK's `xGELSY` and `xGELSD`. A QR decomposition with column pivoting is
+//! computed first, and is used directly when it shows that $A$ has full column rank. Otherwise,
+//! the problem is solved with the SVD of $A$, which yields the solution of minimal norm.
+//!
+//! [`lse`] computes the solution of the equality constrained problem $\min_X \|AX - B\|_F$
+//! subject to $CX = D$, similarly to LAPACK's `xGGLSE`.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::lstsq, mat};
+//!
+//! // fit a line through three points
+//! let a = mat![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0f64]];
+//! let b = mat![[1.0], [2.0], [4.0]];
+//!
+//! let sol = lstsq::lstsq(a.as_ref(), b.as_ref(), 1e-12);
+//! assert!(sol.rank == 2);
+//! assert!(sol.method == lstsq::LstsqMethod::Qr);
+//! assert!((sol.solution.read(1, 0) - 1.5).abs() < 1e-12);
+//! assert!((sol.residual_norms.read(0) - (1.0f64 / 6.0).sqrt()).abs() < 1e-12);
+//! ```
+
+use crate::{
+    assert,
+    col::Col,
+    get_global_parallelism,
+    linalg::{
+        matmul::matmul,
+        solvers::{ColPivQr, Qr, ThinSvd},
+        triangular_solve::solve_lower_triangular_in_place,
+    },
+    mat::{Mat, MatRef},
+    sparse::linalg::solvers::SpSolverLstsqCore,
+    ComplexField, Conj, RealField,
+};
+
+/// Error that can occur when solving an equality constrained least squares problem with [`lse`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LseError {
+    /// The constraint matrix $C$ does not have full row rank, so the constraints are either
+    /// redundant or inconsistent.
+    ConstraintRankDeficient,
+    /// The stacked matrix $\begin{bmatrix} A \\\\ C \end{bmatrix}$ does not have full column
+    /// rank, so the solution is not unique.
+    RankDeficient,
+}
+
+impl core::fmt::Display for LseError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for LseError {}
+
+/// Decomposition that was used to compute the least squares solution.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LstsqMethod {
+    /// QR decomposition with column pivoting, used when the matrix has full column rank.
+    Qr,
+    /// Singular value decomposition, used when the matrix is rank deficient or has more columns
+    /// than rows.
+    Svd,
+}
+
+/// Least squares solution computed by [`lstsq`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LstsqSolution<E: ComplexField> {
+    /// Solution of the least squares problem. When the matrix is rank deficient, this is the
+    /// solution of minimal norm.
+    pub solution: Mat<E>,
+    /// Effective rank of the matrix, with respect to the provided tolerance.
+    pub rank: usize,
+    /// Euclidean norm of the residual $b - Ax$ of each column of the right-hand side.
+    pub residual_norms: Col<E::Real>,
+    /// Fitted values $Ax$.
+    pub fitted: Mat<E>,
+    /// Decomposition that was used to compute the solution.
+    pub method: LstsqMethod,
+}
+
+/// Computes the least squares solution of `matrix * X = rhs`.
+///
+/// The matrix is considered rank deficient if its QR decomposition with column pivoting has a
+/// diagonal element of $R$ whose absolute value is not greater than `tolerance` times the first
+/// one. In that case, the singular values that are not greater than `tolerance` times the largest
+/// singular value are treated as zero. A common choice of tolerance is
+/// $\max(m, n) \varepsilon$, where $\varepsilon$ is the machine epsilon.
+///
+/// # Panics
+/// Panics if `rhs` doesn't have the same number of rows as `matrix`.
+#[track_caller]
+pub fn lstsq<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    rhs: MatRef<'_, E>,
+    tolerance: E::Real,
+) -> LstsqSolution<E> {
+    assert!(rhs.nrows() == matrix.nrows());
+
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let k = rhs.ncols();
+    let parallelism = get_global_parallelism();
+
+    let mut qr_solution = None;
+    if m >= n && n > 0 {
+        let qr = ColPivQr::<E>::new(matrix);
+        if qr.rank(tolerance) == n {
+            let mut x = rhs.to_owned();
+            qr.solve_lstsq_in_place_with_conj_impl(x.as_mut(), Conj::No);
+            qr_solution = Some(x.as_ref().subrows(0, n).to_owned());
+        }
+    }
+
+    let (solution, rank, method) = match qr_solution {
+        Some(solution) => (solution, n, LstsqMethod::Qr),
+        None => {
+            let mut solution = Mat::<E>::zeros(n, k);
+            let mut rank = 0;
+
+            if Ord::min(m, n) > 0 {
+                let svd = ThinSvd::<E>::new(matrix);
+                let s = svd.s_diagonal();
+                let threshold = tolerance.faer_mul(s.read(0).faer_real());
+                while rank < s.nrows() && s.read(rank).faer_real() > threshold {
+                    rank += 1;
+                }
+
+                // X = V_r S_r^{-1} U_r^H B
+                let mut tmp = Mat::<E>::zeros(rank, k);
+                matmul(
+                    tmp.as_mut(),
+                    svd.u().subcols(0, rank).adjoint(),
+                    rhs,
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+                for j in 0..k {
+                    for i in 0..rank {
+                        let inv = s.read(i).faer_real().faer_inv();
+                        tmp.write(i, j, tmp.read(i, j).faer_scale_real(inv));
+                    }
+                }
+                matmul(
+                    solution.as_mut(),
+                    svd.v().subcols(0, rank),
+                    tmp.as_ref(),
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+            }
+
+            (solution, rank, LstsqMethod::Svd)
+        }
+    };
+
+    let mut fitted = Mat::<E>::zeros(m, k);
+    matmul(
+        fitted.as_mut(),
+        matrix,
+        solution.as_ref(),
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+
+    let residual_norms = Col::<E::Real>::from_fn(k, |j| {
+        let mut norm2 = E::Real::faer_zero();
+        for i in 0..m {
+            norm2 = norm2.faer_add(rhs.read(i, j).faer_sub(fitted.read(i, j)).faer_abs2());
+        }
+        norm2.faer_sqrt()
+    });
+
+    LstsqSolution {
+        solution,
+        rank,
+        residual_norms,
+        fitted,
+        method,
+    }
+}
+
+// checks whether the square triangular factor `r` is singular to working precision
+fn is_singular<E: ComplexField>(r: MatRef<'_, E>) -> bool {
+    let n = r.nrows();
+    let mut max_diag = E::Real::faer_zero();
+    for i in 0..n {
+        let d = r.read(i, i).faer_abs();
+        if d > max_diag {
+            max_diag = d;
+        }
+    }
+    let threshold = E::Real::faer_epsilon()
+        .faer_mul(E::Real::faer_from_f64(n as f64))
+        .faer_mul(max_diag);
+    (0..n).any(|i| r.read(i, i).faer_abs() <= threshold)
+}
+
+/// Computes the solution of the equality constrained least squares problem
+/// $\min_X \|AX - B\|_F$ subject to $CX = D$, where $A$ is `matrix`, $B$ is `rhs`, $C$ is
+/// `constraint` and $D$ is `constraint_rhs`.
+///
+/// The problem is reduced to an unconstrained one using the QR decomposition of $C^H$, i.e., the
+/// RQ decomposition of $C$, as in the generalized RQ factorization used by LAPACK. Writing $C^H =
+/// \begin{bmatrix} Q_1 & Q_2 \end{bmatrix} \begin{bmatrix} R \\\\ 0 \end{bmatrix}$, the
+/// solution is $X = Q_1 R^{-H} D + Q_2 Y$, where $Y$ is the least squares solution of
+/// $\min_Y \|A Q_2 Y - (B - A Q_1 R^{-H} D)\|_F$.
+///
+/// # Errors
+/// - Returns [`LseError::ConstraintRankDeficient`] if $C$ does not have full row rank.
+/// - Returns [`LseError::RankDeficient`] if $\begin{bmatrix} A \\\\ C \end{bmatrix}$ does not
+/// have full column rank.
+///
+/// # Panics
+/// Panics if the dimensions of the inputs are not compatible.
+#[track_caller]
+pub fn lse<E: ComplexField>(
+    matrix: MatRef<'_, E>,
+    rhs: MatRef<'_, E>,
+    constraint: MatRef<'_, E>,
+    constraint_rhs: MatRef<'_, E>,
+) -> Result<Mat<E>, LseError> {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let p = constraint.nrows();
+    let k = rhs.ncols();
+    assert!(all(
+        rhs.nrows() == m,
+        constraint.ncols() == n,
+        constraint_rhs.nrows() == p,
+        constraint_rhs.ncols() == k,
+    ));
+
+    if p > n {
+        return Err(LseError::ConstraintRankDeficient);
+    }
+    if n > m + p {
+        return Err(LseError::RankDeficient);
+    }
+
+    let parallelism = get_global_parallelism();
+
+    let (q, r) = if p == 0 {
+        (Mat::<E>::identity(n, n), Mat::<E>::zeros(0, 0))
+    } else {
+        let qr_c = Qr::<E>::new(constraint.adjoint());
+        (qr_c.compute_q(), qr_c.compute_thin_r())
+    };
+    if is_singular(r.as_ref()) {
+        return Err(LseError::ConstraintRankDeficient);
+    }
+    let (q1, q2) = q.as_ref().split_at_col(p);
+
+    // C Q1 = R^H, and C Q2 = 0
+    let mut y1 = constraint_rhs.to_owned();
+    solve_lower_triangular_in_place(r.adjoint(), y1.as_mut(), parallelism);
+
+    let mut x = Mat::<E>::zeros(n, k);
+    matmul(
+        x.as_mut(),
+        q1,
+        y1.as_ref(),
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+
+    if n > p {
+        let mut aq2 = Mat::<E>::zeros(m, n - p);
+        matmul(aq2.as_mut(), matrix, q2, None, E::faer_one(), parallelism);
+
+        let mut residual = rhs.to_owned();
+        matmul(
+            residual.as_mut(),
+            matrix,
+            x.as_ref(),
+            Some(E::faer_one()),
+            E::faer_one().faer_neg(),
+            parallelism,
+        );
+
+        let qr = Qr::<E>::new(aq2.as_ref());
+        if is_singular(qr.compute_thin_r().as_ref()) {
+            return Err(LseError::RankDeficient);
+        }
+        qr.solve_lstsq_in_place_with_conj_impl(residual.as_mut(), Conj::No);
+
+        matmul(
+            x.as_mut(),
+            q2,
+            residual.as_ref().subrows(0, n - p),
+            Some(E::faer_one()),
+            E::faer_one(),
+            parallelism,
+        );
+    }
+
+    Ok(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64};
+
+    fn random_mat(m: usize, n: usize) -> Mat<c64> {
+        Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()))
+    }
+
+    // the normal equations A^H (B - AX) = 0 hold for any least squares solution
+    fn check_normal_equations(a: &Mat<c64>, b: &Mat<c64>, sol: &LstsqSolution<c64>) {
+        let residual = b - &sol.fitted;
+        assert!((a.adjoint() * &residual).norm_max() < 1e-10);
+        assert!((&sol.fitted - a * &sol.solution).norm_max() < 1e-12);
+        for j in 0..b.ncols() {
+            let norm = residual.as_ref().col(j).norm_l2();
+            assert!((sol.residual_norms.read(j) - norm).abs() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_lstsq_full_rank() {
+        let a = random_mat(8, 5);
+        let b = random_mat(8, 3);
+
+        let sol = lstsq(a.as_ref(), b.as_ref(), 1e-12);
+        assert!(sol.rank == 5);
+        assert!(sol.method == LstsqMethod::Qr);
+        check_normal_equations(&a, &b, &sol);
+        assert!(sol.residual_norms.read(0) > 1e-3);
+    }
+
+    #[test]
+    fn test_lstsq_rank_deficient() {
+        let a = &random_mat(8, 3) * &random_mat(3, 5);
+        let b = random_mat(8, 2);
+
+        let sol = lstsq(a.as_ref(), b.as_ref(), 1e-10);
+        assert!(sol.rank == 3);
+        assert!(sol.method == LstsqMethod::Svd);
+        check_normal_equations(&a, &b, &sol);
+
+        // the minimal norm solution is orthogonal to the null space
+        let null = a.svd().null_space(1e-10);
+        assert!(null.ncols() == 2);
+        assert!((null.adjoint() * &sol.solution).norm_max() < 1e-10);
+    }
+
+    #[test]
+    fn test_lstsq_underdetermined() {
+        let a = random_mat(3, 6);
+        let b = random_mat(3, 2);
+
+        let sol = lstsq(a.as_ref(), b.as_ref(), 1e-12);
+        assert!(sol.rank == 3);
+        assert!(sol.method == LstsqMethod::Svd);
+        check_normal_equations(&a, &b, &sol);
+        for j in 0..2 {
+            assert!(sol.residual_norms.read(j) < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_lstsq_empty() {
+        let a = Mat::<f64>::zeros(4, 0);
+        let b = Mat::<f64>::from_fn(4, 1, |i, _| i as f64);
+
+        let sol = lstsq(a.as_ref(), b.as_ref(), 1e-12);
+        assert!(sol.rank == 0);
+        assert!(sol.solution.nrows() == 0);
+        assert!((sol.residual_norms.read(0) - 14.0f64.sqrt()).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_lse() {
+        let m = 8;
+        let n = 5;
+        let p = 2;
+        let a = random_mat(m, n);
+        let b = random_mat(m, 3);
+        let c = random_mat(p, n);
+        let d = random_mat(p, 3);
+
+        let x = lse(a.as_ref(), b.as_ref(), c.as_ref(), d.as_ref()).unwrap();
+        assert!((&c * &x - &d).norm_max() < 1e-10);
+
+        // the gradient of the objective is orthogonal to the feasible directions
+        let null = c.svd().null_space(1e-10);
+        assert!(null.ncols() == n - p);
+        let grad = a.adjoint() * (&a * &x - &b);
+        assert!((null.adjoint() * grad).norm_max() < 1e-10);
+
+        // without constraints, this is the unconstrained least squares solution
+        let c0 = Mat::<c64>::zeros(0, n);
+        let d0 = Mat::<c64>::zeros(0, 3);
+        let x0 = lse(a.as_ref(), b.as_ref(), c0.as_ref(), d0.as_ref()).unwrap();
+        let sol = lstsq(a.as_ref(), b.as_ref(), 1e-12);
+        assert!((x0 - sol.solution).norm_max() < 1e-10);
+
+        // with n constraints, the solution is fully determined by them
+        let c1 = random_mat(n, n);
+        let x1 = lse(
+            a.as_ref(),
+            b.as_ref(),
+            c1.as_ref(),
+            random_mat(n, 3).as_ref(),
+        )
+        .unwrap();
+        assert!(x1.nrows() == n);
+    }
+
+    #[test]
+    fn test_lse_errors() {
+        let a = random_mat(6, 4);
+        let b = random_mat(6, 1);
+
+        let row = random_mat(1, 4);
+        let c = Mat::from_fn(2, 4, |i, j| {
+            if i == 0 {
+                row.read(0, j)
+            } else {
+                c64::new(0.0, 0.0)
+            }
+        });
+        let d = random_mat(2, 1);
+        assert!(matches!(
+            lse(a.as_ref(), b.as_ref(), c.as_ref(), d.as_ref()),
+            Err(LseError::ConstraintRankDeficient)
+        ));
+
+        let a = Mat::<c64>::zeros(6, 4);
+        let c = random_mat(2, 4);
+        assert!(matches!(
+            lse(a.as_ref(), b.as_ref(), c.as_ref(), d.as_ref()),
+            Err(LseError::RankDeficient)
+        ));
+    }
+}
@@ -1918,6 +1918,95 @@ pub fn matmul<E: ComplexField, LhsE: Conjugate<Canonical = E>, RhsE: Conjugate<C
     matmul_with_conj::<E>(acc, lhs, conj_lhs, rhs, conj_rhs, alpha, beta, parallelism);
 }
 
+/// Computes the trace of the matrix product `lhs * rhs`, without forming the product.
+///
+/// This requires $\mathcal{O}(mn)$ operations, where `(m, n)` are the dimensions of `lhs`,
+/// instead of $\mathcal{O}(m^2 n)$ for the full product.
+///
+/// # Panics
+///
+/// Panics if the product is not a square matrix, i.e. if `lhs.ncols() != rhs.nrows()` or
+/// `lhs.nrows() != rhs.ncols()`.
+///
+/// # Example
+///
+/// ```
+/// use faer::{linalg::matmul::trace_of_product, mat};
+///
+/// let lhs = mat![[1.0, 2.0, 0.5], [3.0, 4.0, -1.0]];
+/// let rhs = mat![[5.0, 6.0], [7.0, 8.0], [2.0, 2.0]];
+///
+/// let trace = trace_of_product(lhs.as_ref(), rhs.as_ref());
+/// assert_eq!(trace, (5.0 + 14.0 + 1.0) + (18.0 + 32.0 - 2.0));
+/// ```
+#[track_caller]
+pub fn trace_of_product<
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    lhs: MatRef<'_, LhsE>,
+    rhs: MatRef<'_, RhsE>,
+) -> E {
+    assert!(all(lhs.ncols() == rhs.nrows(), lhs.nrows() == rhs.ncols()));
+    let (lhs, conj_lhs) = lhs.canonicalize();
+    let (rhs, conj_rhs) = rhs.canonicalize();
+
+    let mut acc = E::faer_zero();
+    for i in 0..lhs.nrows() {
+        acc = acc.faer_add(inner_prod::inner_prod_with_conj(
+            lhs.row(i).transpose().as_2d(),
+            conj_lhs,
+            rhs.col(i).as_2d(),
+            conj_rhs,
+        ));
+    }
+    acc
+}
+
+/// Computes the diagonal of the matrix product `lhs * rhs`, and stores the result in `out`,
+/// without forming the product.
+///
+/// Each diagonal entry is computed as the inner product of a row of `lhs` with the corresponding
+/// column of `rhs`, which requires $\mathcal{O}(mn)$ operations in total, where `(m, n)` are the
+/// dimensions of `lhs`.
+///
+/// # Panics
+///
+/// Panics if the product is not a square matrix, i.e. if `lhs.ncols() != rhs.nrows()` or
+/// `lhs.nrows() != rhs.ncols()`, or if `out.nrows() != lhs.nrows()`.
+#[track_caller]
+pub fn diag_of_product<
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    out: crate::col::ColMut<'_, E>,
+    lhs: MatRef<'_, LhsE>,
+    rhs: MatRef<'_, RhsE>,
+) {
+    assert!(all(
+        lhs.ncols() == rhs.nrows(),
+        lhs.nrows() == rhs.ncols(),
+        out.nrows() == lhs.nrows(),
+    ));
+    let (lhs, conj_lhs) = lhs.canonicalize();
+    let (rhs, conj_rhs) = rhs.canonicalize();
+
+    let mut out = out;
+    for i in 0..lhs.nrows() {
+        out.write(
+            i,
+            inner_prod::inner_prod_with_conj(
+                lhs.row(i).transpose().as_2d(),
+                conj_lhs,
+                rhs.col(i).as_2d(),
+                conj_rhs,
+            ),
+        );
+    }
+}
+
 macro_rules! stack_mat_16x16_begin {
     ($name: ident, $nrows: expr, $ncols: expr, $rs: expr, $cs: expr, $ty: ty) => {
         let __nrows: usize = $nrows;
@@ -1996,6 +2085,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_product_diag_trace() {
+        let random = |_, _| c64 {
+            re: rand::random(),
+            im: rand::random(),
+        };
+
+        for (m, n) in [(0, 0), (1, 3), (7, 7), (20, 13)] {
+            let lhs = Mat::from_fn(m, n, random);
+            let rhs = Mat::from_fn(n, m, random);
+            let rhs_t = rhs.transpose().to_owned();
+
+            // the strides and conjugations are handled for both operands
+            for (lhs, rhs) in [
+                (lhs.as_ref(), rhs.as_ref()),
+                (lhs.as_ref(), rhs_t.transpose()),
+            ] {
+                let mut prod = Mat::<c64>::zeros(m, m);
+                matmul(
+                    prod.as_mut(),
+                    lhs,
+                    rhs,
+                    None,
+                    c64::faer_one(),
+                    Parallelism::None,
+                );
+                let mut prod_conj = Mat::<c64>::zeros(m, m);
+                matmul(
+                    prod_conj.as_mut(),
+                    lhs.conjugate(),
+                    rhs,
+                    None,
+                    c64::faer_one(),
+                    Parallelism::None,
+                );
+
+                let mut diag = crate::Col::<c64>::zeros(m);
+                diag_of_product(diag.as_mut(), lhs, rhs);
+                let mut diag_conj = crate::Col::<c64>::zeros(m);
+                diag_of_product(diag_conj.as_mut(), lhs.conjugate(), rhs);
+                let mut trace = c64::faer_zero();
+                for i in 0..m {
+                    assert!((diag.read(i) - prod.read(i, i)).faer_abs() < 1e-10);
+                    assert!((diag_conj.read(i) - prod_conj.read(i, i)).faer_abs() < 1e-10);
+                    trace += prod.read(i, i);
+                }
+                assert!((trace_of_product(lhs, rhs) - trace).faer_abs() < 1e-10);
+            }
+        }
+    }
+
     #[test]
     #[ignore = "takes too long in CI"]
     fn test_matmul() {
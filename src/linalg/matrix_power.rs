@@ -0,0 +1,226 @@
+//! Integer powers of square matrices.
+//!
+//! [`matrix_power`] computes $A^k$ by binary exponentiation, which requires $O(\log k)$ matrix
+//! multiplications instead of the $k - 1$ needed by repeated multiplication. All the intermediate
+//! products are stored in a fixed workspace of two $n \times n$ matrices, so no allocation
+//! happens regardless of the exponent.
+//!
+//! For self-adjoint matrices, the high level
+//! [`MatRef::selfadjoint_matrix_power`](crate::mat::MatRef::selfadjoint_matrix_power) instead goes
+//! through the eigendecomposition $A = U \Lambda U^H$, so that $A^k = U \Lambda^k U^H$, which
+//! also supports negative exponents.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::matrix_power, mat, Mat, Parallelism};
+//! use dyn_stack::{GlobalPodBuffer, PodStack};
+//!
+//! let a = mat![[1.0, 1.0], [1.0, 0.0f64]];
+//! let mut out = Mat::<f64>::zeros(2, 2);
+//!
+//! matrix_power::matrix_power(
+//!     out.as_mut(),
+//!     a.as_ref(),
+//!     10,
+//!     Parallelism::None,
+//!     PodStack::new(&mut GlobalPodBuffer::new(
+//!         matrix_power::matrix_power_req::<f64>(2, Parallelism::None).unwrap(),
+//!     )),
+//! );
+//!
+//! // fibonacci numbers
+//! assert!(out.read(0, 0) == 89.0);
+//! assert!(out.read(0, 1) == 55.0);
+//! ```
+
+use crate::{
+    assert,
+    linalg::{matmul::matmul, temp_mat_req, temp_mat_uninit},
+    mat::{MatMut, MatRef},
+    ComplexField, Conjugate, Entity, Parallelism, RealField,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Computes the size and alignment of required workspace for computing the power of a square
+/// matrix with dimension `dim`.
+pub fn matrix_power_req<E: Entity>(
+    dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let tmp = temp_mat_req::<E>(dim, dim)?;
+    StackReq::try_all_of([tmp, tmp])
+}
+
+/// Computes `matrix` raised to the power `k`, and stores the result in `out`.
+///
+/// `matrix^0` is the identity matrix.
+///
+/// # Panics
+/// - Panics if `matrix` is not square.
+/// - Panics if `out` does not have the same dimensions as `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`matrix_power_req`]).
+#[track_caller]
+pub fn matrix_power<E: ComplexField, ViewE: Conjugate<Canonical = E>>(
+    out: MatMut<'_, E>,
+    matrix: MatRef<'_, ViewE>,
+    k: u32,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let n = matrix.nrows();
+    assert!(all(matrix.ncols() == n, out.nrows() == n, out.ncols() == n,));
+
+    let mut out = out;
+    if k == 0 {
+        out.fill_zero();
+        out.rb_mut()
+            .diagonal_mut()
+            .column_vector_mut()
+            .fill(E::faer_one());
+        return;
+    }
+
+    let (mut base, stack) = temp_mat_uninit::<E>(n, n, stack);
+    let (mut prod, _) = temp_mat_uninit::<E>(n, n, stack);
+
+    base.copy_from(matrix);
+
+    // square the base until we reach the lowest set bit of the exponent, so that the result can
+    // be initialized with a copy instead of a multiplication by the identity
+    let mut k = k;
+    while k % 2 == 0 {
+        matmul(
+            prod.rb_mut(),
+            base.rb(),
+            base.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        core::mem::swap(&mut base, &mut prod);
+        k /= 2;
+    }
+    out.copy_from(base.rb());
+    k /= 2;
+
+    while k > 0 {
+        matmul(
+            prod.rb_mut(),
+            base.rb(),
+            base.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        core::mem::swap(&mut base, &mut prod);
+
+        if k % 2 == 1 {
+            matmul(
+                prod.rb_mut(),
+                out.rb(),
+                base.rb(),
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            out.copy_from(prod.rb());
+        }
+        k /= 2;
+    }
+}
+
+/// Returns `value` raised to the integer power `k`, by binary exponentiation.
+pub(crate) fn powi<R: RealField>(value: R, k: i32) -> R {
+    let mut base = value;
+    let mut acc = R::faer_one();
+    let mut e = k.unsigned_abs();
+    while e > 0 {
+        if e % 2 == 1 {
+            acc = acc.faer_mul(base);
+        }
+        base = base.faer_mul(base);
+        e /= 2;
+    }
+    if k < 0 {
+        acc.faer_inv()
+    } else {
+        acc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Mat};
+    use dyn_stack::GlobalPodBuffer;
+
+    fn power<E: ComplexField, ViewE: Conjugate<Canonical = E>>(
+        a: MatRef<'_, ViewE>,
+        k: u32,
+    ) -> Mat<E> {
+        let n = a.nrows();
+        let mut out = Mat::<E>::zeros(n, n);
+        matrix_power(
+            out.as_mut(),
+            a,
+            k,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                matrix_power_req::<E>(n, Parallelism::None).unwrap(),
+            )),
+        );
+        out
+    }
+
+    #[test]
+    fn test_matrix_power() {
+        let n = 7;
+        let a = Mat::from_fn(n, n, |i, j| {
+            c64::new(
+                ((i * 3 + j * 5) % 7) as f64 / 10.0 - 0.3,
+                ((i + 2 * j) % 5) as f64 / 10.0 - 0.2,
+            )
+        });
+
+        let mut expected = Mat::<c64>::identity(n, n);
+        for k in 0..20u32 {
+            let out = power(a.as_ref(), k);
+            let scale = expected.norm_max() + 1.0;
+            for j in 0..n {
+                for i in 0..n {
+                    let err = out.read(i, j).faer_sub(expected.read(i, j)).faer_abs();
+                    assert!(err < 1e-10 * scale);
+                }
+            }
+            expected = &expected * &a;
+        }
+    }
+
+    #[test]
+    fn test_matrix_power_conj() {
+        let n = 4;
+        let a = Mat::from_fn(n, n, |i, j| {
+            c64::new((i + j) as f64 / 8.0, (i as f64 - j as f64) / 8.0)
+        });
+
+        let k = 5;
+        let out = power(a.conjugate(), k);
+        let expected = power(a.conjugate().to_owned().as_ref(), k);
+        for j in 0..n {
+            for i in 0..n {
+                let err = out.read(i, j).faer_sub(expected.read(i, j)).faer_abs();
+                assert!(err < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_powi() {
+        assert!(powi(2.0f64, 10) == 1024.0);
+        assert!(powi(2.0f64, -2) == 0.25);
+        assert!(powi(3.0f64, 0) == 1.0);
+    }
+}
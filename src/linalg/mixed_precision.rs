@@ -0,0 +1,636 @@
+//! Mixed precision linear solvers.
+//!
+//! [`MixedPrecisionLu`] computes the LU decomposition with partial pivoting of a matrix in a lower
+//! precision (e.g., `f32` for an `f64` matrix), which halves the memory traffic and doubles the
+//! SIMD throughput of the $\mathcal{O}(n^3)$ factorization. The solution is then refined to the
+//! working precision with $\mathcal{O}(n^2)$ operations per iteration, using either:
+//! - classical iterative refinement, where each correction is obtained by solving with the low
+//! precision factors. This converges when the condition number of the matrix is small compared to
+//! the inverse of the unit roundoff of the low precision type.
+//! - GMRES-based iterative refinement, where each correction is obtained with GMRES in the working
+//! precision, preconditioned by the low precision factors. This converges for a wider range of
+//! condition numbers, at the cost of more products with the matrix.
+//!
+//! The refinement stops when the componentwise backward error of the solution is of the order of
+//! the working precision. If it fails to converge, the system is solved with a working precision
+//! LU decomposition instead, so that the solver can be used as a drop-in replacement for
+//! [`PartialPivLu`].
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::mixed_precision::MixedPrecisionLu, prelude::*};
+//!
+//! let n = 100;
+//! let a = Mat::<f64>::from_fn(n, n, |i, j| if i == j { 4.0 } else { 1.0 / (1 + i + j) as f64 });
+//! let b = Mat::<f64>::from_fn(n, 2, |i, j| (i + j) as f64);
+//!
+//! let lu = MixedPrecisionLu::new(a.as_ref());
+//! let x = lu.solve(&b);
+//!
+//! assert!((&a * &x - &b).norm_max() < 1e-10);
+//! ```
+
+use crate::{
+    assert,
+    complex_native::{c32, c64},
+    get_global_parallelism,
+    linalg::{matmul::matmul_with_conj, solvers::PartialPivLu},
+    mat::{Mat, MatMut, MatRef},
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj, Conjugate, RealField,
+};
+use alloc::vec;
+use reborrow::*;
+
+/// Scalar type that can be converted to and from a lower precision type, used for the
+/// factorization in [`MixedPrecisionLu`].
+pub trait MixedPrecision: ComplexField {
+    /// Lower precision type.
+    type Low: ComplexField;
+
+    /// Rounds the value to the lower precision type.
+    fn to_low(self) -> Self::Low;
+    /// Converts the value from the lower precision type.
+    fn from_low(value: Self::Low) -> Self;
+}
+
+impl MixedPrecision for f64 {
+    type Low = f32;
+
+    #[inline]
+    fn to_low(self) -> Self::Low {
+        self as f32
+    }
+    #[inline]
+    fn from_low(value: Self::Low) -> Self {
+        value as f64
+    }
+}
+
+impl MixedPrecision for c64 {
+    type Low = c32;
+
+    #[inline]
+    fn to_low(self) -> Self::Low {
+        c32 {
+            re: self.re as f32,
+            im: self.im as f32,
+        }
+    }
+    #[inline]
+    fn from_low(value: Self::Low) -> Self {
+        c64 {
+            re: value.re as f64,
+            im: value.im as f64,
+        }
+    }
+}
+
+/// Method used to compute the corrections during iterative refinement.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RefinementMethod {
+    /// Solve with the low precision factors.
+    Classical,
+    /// Solve with GMRES in the working precision, preconditioned by the low precision factors.
+    Gmres {
+        /// Maximum number of GMRES iterations per correction.
+        max_inner_iters: usize,
+    },
+}
+
+/// Parameters of the mixed precision solver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct MixedPrecisionParams<E: ComplexField> {
+    /// Method used to compute the corrections.
+    pub method: RefinementMethod,
+    /// Maximum number of refinement steps before falling back to a working precision
+    /// factorization.
+    pub max_iters: usize,
+    /// Relative tolerance of the inner GMRES solves, when using [`RefinementMethod::Gmres`].
+    pub gmres_rel_tolerance: E::Real,
+}
+
+impl<E: ComplexField> Default for MixedPrecisionParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            method: RefinementMethod::Classical,
+            max_iters: 30,
+            gmres_rel_tolerance: E::Real::faer_from_f64(1e-6),
+        }
+    }
+}
+
+/// Information about the refinement of a solution computed by [`MixedPrecisionLu`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct RefinementInfo {
+    /// Number of refinement steps that were performed.
+    pub iter_count: usize,
+    /// Whether the refinement failed to converge, and the solution was computed with a working
+    /// precision factorization instead.
+    pub used_fallback: bool,
+}
+
+/// LU decomposition with partial pivoting computed in a lower precision, with iterative
+/// refinement of the solutions to the working precision.
+///
+/// See the [module level documentation](self) for more details.
+pub struct MixedPrecisionLu<E: MixedPrecision> {
+    matrix: Mat<E>,
+    norm_inf: E::Real,
+    scale: E::Real,
+    lu: PartialPivLu<E::Low>,
+    params: MixedPrecisionParams<E>,
+}
+
+impl<E: MixedPrecision> MixedPrecisionLu<E> {
+    /// Returns the low precision LU decomposition of the input matrix, with the default
+    /// refinement parameters.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(matrix: MatRef<'_, ViewE>) -> Self {
+        Self::new_with_params(matrix, Default::default())
+    }
+
+    /// Returns the low precision LU decomposition of the input matrix, with the given refinement
+    /// parameters.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square.
+    #[track_caller]
+    pub fn new_with_params<ViewE: Conjugate<Canonical = E>>(
+        matrix: MatRef<'_, ViewE>,
+        params: MixedPrecisionParams<E>,
+    ) -> Self {
+        assert!(matrix.nrows() == matrix.ncols());
+        let n = matrix.nrows();
+        let matrix = matrix.to_owned();
+
+        let mut row_norms = vec![E::Real::faer_zero(); n];
+        for j in 0..n {
+            for (i, norm) in row_norms.iter_mut().enumerate() {
+                *norm = norm.faer_add(matrix.read(i, j).faer_abs());
+            }
+        }
+        let mut norm_inf = E::Real::faer_zero();
+        for norm in row_norms {
+            if norm > norm_inf {
+                norm_inf = norm;
+            }
+        }
+
+        // scale the matrix before rounding it, so that it fits in the range of the low precision
+        // type
+        let mut scale = matrix.norm_max();
+        if scale == E::Real::faer_zero() || !scale.faer_is_finite() {
+            scale = E::Real::faer_one();
+        }
+        let scale_inv = scale.faer_inv();
+        let low = Mat::<E::Low>::from_fn(n, n, |i, j| {
+            matrix.read(i, j).faer_scale_real(scale_inv).to_low()
+        });
+
+        Self {
+            norm_inf,
+            scale,
+            lu: PartialPivLu::new(low.as_ref()),
+            matrix,
+            params,
+        }
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.matrix.nrows()
+    }
+
+    /// Returns the low precision LU decomposition of the scaled matrix $A / \|A\|_{\max}$.
+    #[inline]
+    pub fn low_precision_lu(&self) -> &PartialPivLu<E::Low> {
+        &self.lu
+    }
+
+    /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
+    /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
+    ///
+    /// # Panics
+    /// Panics if `rhs.nrows() != self.dim()`.
+    #[track_caller]
+    pub fn solve_in_place_with_info(&self, rhs: MatMut<'_, E>, conj: Conj) -> RefinementInfo {
+        self.solve_impl(rhs, false, conj)
+    }
+
+    /// Solves the equation $\text{Op}(A)^\top x = \text{rhs}$ and stores the result in `rhs`,
+    /// where $\text{Op}$ is either the identity or the conjugate, depending on the value of
+    /// `conj`.
+    ///
+    /// # Panics
+    /// Panics if `rhs.nrows() != self.dim()`.
+    #[track_caller]
+    pub fn solve_transpose_in_place_with_info(
+        &self,
+        rhs: MatMut<'_, E>,
+        conj: Conj,
+    ) -> RefinementInfo {
+        self.solve_impl(rhs, true, conj)
+    }
+
+    fn op(&self, transpose: bool) -> MatRef<'_, E> {
+        if transpose {
+            self.matrix.as_ref().transpose()
+        } else {
+            self.matrix.as_ref()
+        }
+    }
+
+    // solves with the low precision factors, scaling each column to avoid overflow and underflow
+    fn low_precision_solve_in_place(&self, x: MatMut<'_, E>, transpose: bool, conj: Conj) {
+        let mut x = x;
+        let n = x.nrows();
+        let k = x.ncols();
+
+        let mut col_scale = vec![E::Real::faer_one(); k];
+        for (j, scale) in col_scale.iter_mut().enumerate() {
+            let norm = x.rb().col(j).norm_max();
+            if norm > E::Real::faer_zero() && norm.faer_is_finite() {
+                *scale = norm;
+            }
+        }
+
+        let mut low = Mat::<E::Low>::from_fn(n, k, |i, j| {
+            x.read(i, j)
+                .faer_scale_real(col_scale[j].faer_inv())
+                .to_low()
+        });
+        if transpose {
+            self.lu
+                .solve_transpose_in_place_with_conj_impl(low.as_mut(), conj);
+        } else {
+            self.lu.solve_in_place_with_conj_impl(low.as_mut(), conj);
+        }
+
+        for j in 0..k {
+            let scale = col_scale[j].faer_mul(self.scale.faer_inv());
+            for i in 0..n {
+                x.write(i, j, E::from_low(low.read(i, j)).faer_scale_real(scale));
+            }
+        }
+    }
+
+    // computes rhs - Op(A) x
+    fn residual(
+        &self,
+        r: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        x: MatRef<'_, E>,
+        transpose: bool,
+        conj: Conj,
+    ) {
+        let mut r = r;
+        r.copy_from(rhs);
+        matmul_with_conj(
+            r,
+            self.op(transpose),
+            conj,
+            x,
+            Conj::No,
+            Some(E::faer_one()),
+            E::faer_one().faer_neg(),
+            get_global_parallelism(),
+        );
+    }
+
+    // solves Op(A) d = r for a single column with GMRES, left preconditioned by the low precision
+    // factors
+    fn gmres(
+        &self,
+        d: MatMut<'_, E>,
+        r: MatRef<'_, E>,
+        max_iters: usize,
+        transpose: bool,
+        conj: Conj,
+    ) {
+        let mut d = d;
+        let n = self.dim();
+        let m = Ord::max(Ord::min(max_iters, n), 1);
+        let tol = self.params.gmres_rel_tolerance;
+
+        let mut v = Mat::<E>::zeros(n, m + 1);
+        let mut h = Mat::<E>::zeros(m + 1, m);
+        let mut g = vec![E::faer_zero(); m + 1];
+        let mut cs = vec![E::Real::faer_zero(); m];
+        let mut sn = vec![E::faer_zero(); m];
+
+        v.as_mut().col_mut(0).as_2d_mut().copy_from(r);
+        self.low_precision_solve_in_place(v.as_mut().col_mut(0).as_2d_mut(), transpose, conj);
+        let beta = v.as_ref().col(0).norm_l2();
+        d.fill_zero();
+        if beta == E::Real::faer_zero() || !beta.faer_is_finite() {
+            return;
+        }
+        let beta_inv = beta.faer_inv();
+        for i in 0..n {
+            v.write(i, 0, v.read(i, 0).faer_scale_real(beta_inv));
+        }
+        g[0] = E::faer_from_real(beta);
+
+        let mut k_used = 0;
+        for k in 0..m {
+            let (vk, mut w) = v.as_mut().split_at_col_mut(k + 1);
+            let mut w = w.rb_mut().col_mut(0).as_2d_mut();
+            matmul_with_conj(
+                w.rb_mut(),
+                self.op(transpose),
+                conj,
+                vk.rb().col(k).as_2d(),
+                Conj::No,
+                None,
+                E::faer_one(),
+                get_global_parallelism(),
+            );
+            self.low_precision_solve_in_place(w.rb_mut(), transpose, conj);
+
+            // modified Gram-Schmidt
+            for i in 0..k + 1 {
+                let mut hik = E::faer_zero();
+                for p in 0..n {
+                    hik = hik.faer_add(vk.read(p, i).faer_conj().faer_mul(w.read(p, 0)));
+                }
+                for p in 0..n {
+                    w.write(p, 0, w.read(p, 0).faer_sub(vk.read(p, i).faer_mul(hik)));
+                }
+                h.write(i, k, hik);
+            }
+            let norm = w.rb().norm_l2();
+            h.write(k + 1, k, E::faer_from_real(norm));
+            if norm > E::Real::faer_zero() {
+                let norm_inv = norm.faer_inv();
+                for p in 0..n {
+                    w.write(p, 0, w.read(p, 0).faer_scale_real(norm_inv));
+                }
+            }
+
+            // apply the previous rotations to the new column of the Hessenberg matrix
+            for i in 0..k {
+                let x = h.read(i, k);
+                let y = h.read(i + 1, k);
+                h.write(i, k, x.faer_scale_real(cs[i]).faer_add(sn[i].faer_mul(y)));
+                h.write(
+                    i + 1,
+                    k,
+                    y.faer_scale_real(cs[i])
+                        .faer_sub(sn[i].faer_conj().faer_mul(x)),
+                );
+            }
+
+            // compute the rotation that eliminates the subdiagonal element
+            let a = h.read(k, k);
+            let b = h.read(k + 1, k);
+            let abs_a = a.faer_abs();
+            let abs_b = b.faer_abs();
+            if abs_b == E::Real::faer_zero() {
+                cs[k] = E::Real::faer_one();
+                sn[k] = E::faer_zero();
+            } else if abs_a == E::Real::faer_zero() {
+                cs[k] = E::Real::faer_zero();
+                sn[k] = b.faer_conj().faer_scale_real(abs_b.faer_inv());
+            } else {
+                let r = abs_a.faer_abs2().faer_add(abs_b.faer_abs2()).faer_sqrt();
+                let r_inv = r.faer_inv();
+                cs[k] = abs_a.faer_mul(r_inv);
+                sn[k] = a
+                    .faer_scale_real(abs_a.faer_inv())
+                    .faer_mul(b.faer_conj())
+                    .faer_scale_real(r_inv);
+            }
+            h.write(k, k, a.faer_scale_real(cs[k]).faer_add(sn[k].faer_mul(b)));
+            h.write(k + 1, k, E::faer_zero());
+            g[k + 1] = sn[k].faer_conj().faer_mul(g[k]).faer_neg();
+            g[k] = g[k].faer_scale_real(cs[k]);
+
+            k_used = k + 1;
+            if norm == E::Real::faer_zero() || g[k + 1].faer_abs() <= tol.faer_mul(beta) {
+                break;
+            }
+        }
+
+        // solve the upper triangular least squares system, and accumulate the correction
+        let mut y = vec![E::faer_zero(); k_used];
+        for i in (0..k_used).rev() {
+            let mut acc = g[i];
+            for p in i + 1..k_used {
+                acc = acc.faer_sub(h.read(i, p).faer_mul(y[p]));
+            }
+            y[i] = acc.faer_mul(h.read(i, i).faer_inv());
+        }
+        for (p, yp) in y.iter().enumerate() {
+            for i in 0..n {
+                d.write(i, 0, d.read(i, 0).faer_add(v.read(i, p).faer_mul(*yp)));
+            }
+        }
+    }
+
+    #[track_caller]
+    fn solve_impl(&self, rhs: MatMut<'_, E>, transpose: bool, conj: Conj) -> RefinementInfo {
+        let mut x = rhs;
+        let n = self.dim();
+        let k = x.ncols();
+        assert!(x.nrows() == n);
+
+        let b = x.to_owned();
+        let mut r = Mat::<E>::zeros(n, k);
+        let mut d = Mat::<E>::zeros(n, k);
+
+        // componentwise backward error threshold, as in LAPACK's dsgesv
+        let threshold = self
+            .norm_inf
+            .faer_mul(E::Real::faer_epsilon())
+            .faer_mul(E::Real::faer_from_f64(n as f64).faer_sqrt());
+
+        self.low_precision_solve_in_place(x.rb_mut(), transpose, conj);
+
+        let mut iter_count = 0;
+        loop {
+            self.residual(r.as_mut(), b.as_ref(), x.rb(), transpose, conj);
+
+            let mut converged = true;
+            let mut finite = true;
+            for j in 0..k {
+                let r_norm = r.as_ref().col(j).norm_max();
+                let x_norm = x.rb().col(j).norm_max();
+                if !r_norm.faer_is_finite() || !x_norm.faer_is_finite() {
+                    finite = false;
+                }
+                if r_norm > x_norm.faer_mul(threshold) {
+                    converged = false;
+                }
+            }
+
+            if converged && finite {
+                return RefinementInfo {
+                    iter_count,
+                    used_fallback: false,
+                };
+            }
+            if !finite || iter_count == self.params.max_iters {
+                break;
+            }
+
+            match self.params.method {
+                RefinementMethod::Classical => {
+                    d.copy_from(&r);
+                    self.low_precision_solve_in_place(d.as_mut(), transpose, conj);
+                }
+                RefinementMethod::Gmres { max_inner_iters } => {
+                    for j in 0..k {
+                        self.gmres(
+                            d.as_mut().col_mut(j).as_2d_mut(),
+                            r.as_ref().col(j).as_2d(),
+                            max_inner_iters,
+                            transpose,
+                            conj,
+                        );
+                    }
+                }
+            }
+            for j in 0..k {
+                for i in 0..n {
+                    x.write(i, j, x.read(i, j).faer_add(d.read(i, j)));
+                }
+            }
+            iter_count += 1;
+        }
+
+        // the refinement failed to converge, fall back to a working precision factorization
+        let lu = PartialPivLu::new(self.matrix.as_ref());
+        x.copy_from(&b);
+        if transpose {
+            lu.solve_transpose_in_place_with_conj_impl(x, conj);
+        } else {
+            lu.solve_in_place_with_conj_impl(x, conj);
+        }
+        RefinementInfo {
+            iter_count,
+            used_fallback: true,
+        }
+    }
+}
+
+impl<E: MixedPrecision> SpSolverCore<E> for MixedPrecisionLu<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, false, conj);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, true, conj);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, sparse::linalg::solvers::SpSolver};
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    fn check<E: MixedPrecision>(a: &Mat<E>, method: RefinementMethod) {
+        let n = a.nrows();
+        let params = MixedPrecisionParams {
+            method,
+            ..Default::default()
+        };
+        let lu = MixedPrecisionLu::new_with_params(a.as_ref(), params);
+        let b = Mat::<E>::from_fn(n, 3, |i, j| E::faer_from_f64((i + 2 * j) as f64 - 10.0));
+        let tol = E::Real::faer_from_f64(1e-12);
+
+        let mut x = b.clone();
+        let info = lu.solve_in_place_with_info(x.as_mut(), Conj::No);
+        assert!(!info.used_fallback);
+        assert!((a * &x - &b).norm_max() <= tol.faer_mul(b.norm_max()));
+
+        let x = lu.solve_conj(&b);
+        assert!((a.conjugate().to_owned() * &x - &b).norm_max() <= tol.faer_mul(b.norm_max()));
+        let x = lu.solve_transpose(&b);
+        assert!((a.transpose() * &x - &b).norm_max() <= tol.faer_mul(b.norm_max()));
+        let x = lu.solve_conj_transpose(&b);
+        assert!((a.adjoint().to_owned() * &x - &b).norm_max() <= tol.faer_mul(b.norm_max()));
+    }
+
+    #[test]
+    fn test_mixed_precision() {
+        for n in [1, 4, 50, 200] {
+            let a = Mat::<f64>::from_fn(n, n, |i, j| {
+                rand::random::<f64>() - 0.5 + if i == j { 4.0 } else { 0.0 }
+            });
+            let b = Mat::<c64>::from_fn(n, n, |i, j| {
+                random_c64().faer_add(if i == j {
+                    c64::new(4.0, 0.0)
+                } else {
+                    c64::new(0.0, 0.0)
+                })
+            });
+            for method in [
+                RefinementMethod::Classical,
+                RefinementMethod::Gmres {
+                    max_inner_iters: 20,
+                },
+            ] {
+                check(&a, method);
+                check(&b, method);
+            }
+        }
+    }
+
+    #[test]
+    fn test_gmres_refinement() {
+        // moderately ill-conditioned matrix that classical refinement struggles with
+        let n = 6;
+        let a = Mat::<f64>::from_fn(n, n, |i, j| 1.0 / (i + j + 1) as f64);
+        let b = Mat::<f64>::from_fn(n, 1, |i, _| i as f64 + 1.0);
+
+        let lu = MixedPrecisionLu::new_with_params(
+            a.as_ref(),
+            MixedPrecisionParams {
+                method: RefinementMethod::Gmres { max_inner_iters: n },
+                ..Default::default()
+            },
+        );
+        let mut x = b.clone();
+        let info = lu.solve_in_place_with_info(x.as_mut(), Conj::No);
+        assert!(!info.used_fallback);
+        assert!((&a * &x - &b).norm_max() <= 1e-13 * x.norm_max());
+    }
+
+    #[test]
+    fn test_fallback() {
+        // the hilbert matrix is too ill-conditioned for a single precision factorization
+        let n = 14;
+        let a = Mat::<f64>::from_fn(n, n, |i, j| 1.0 / (i + j + 1) as f64);
+        let b = Mat::<f64>::from_fn(n, 1, |i, _| i as f64 + 1.0);
+
+        let lu = MixedPrecisionLu::new(a.as_ref());
+        let mut x = b.clone();
+        let info = lu.solve_in_place_with_info(x.as_mut(), Conj::No);
+        assert!(info.used_fallback);
+
+        let x_ref = PartialPivLu::new(a.as_ref()).solve(&b);
+        assert!((&x - &x_ref).norm_max() == 0.0);
+    }
+}
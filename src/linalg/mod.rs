@@ -73,6 +73,21 @@ pub mod qr;
 pub mod evd;
 pub mod svd;
 
+pub mod cond_est;
+pub mod elementwise;
+pub mod equilibration;
+pub mod expert;
+pub mod fft;
+pub mod inverse_iteration;
+pub mod lstsq;
+pub mod matrix_power;
+pub mod mixed_precision;
+pub mod power_iteration;
+pub mod ridge;
+pub mod structured;
+pub mod subspace_iteration;
+pub mod tridiagonal;
+
 /// High level linear system solvers.
 pub mod solvers;
 
@@ -81,6 +96,8 @@ mod mat_ops;
 pub(crate) mod reductions;
 
 pub use kron_impl::kron;
+pub use lstsq::lstsq;
+pub use reductions::relative_error;
 
 #[inline]
 pub(crate) fn col_stride<Unit: 'static>(nrows: usize) -> usize {
@@ -0,0 +1,420 @@
+//! Power iteration for the dominant eigenpair of a linear operator, and estimation of the
+//! spectral norm.
+//!
+//! These are much cheaper than a full eigenvalue or singular value decomposition, since they only
+//! need a few products with the operator (and its adjoint, for [`norm_l2_est`]), and are typically
+//! used to choose step sizes in first order optimization methods, or to scale a problem before
+//! solving it.
+//!
+//! The convergence rate of the power iteration is determined by the ratio of the magnitudes of
+//! the second largest and the largest eigenvalues, so it can be slow if they are close. For
+//! several eigenpairs, or when the gap is small, the Krylov solvers in [`crate::linop`] should be
+//! preferred.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{
+//!     linalg::power_iteration::{norm_l2_est, norm_l2_est_req, PowerIterationParams},
+//!     mat, Parallelism,
+//! };
+//! use dyn_stack::{GlobalPodBuffer, PodStack};
+//!
+//! let a = mat![[3.0, 0.0], [0.0, -1.0], [0.0, 0.0f64]];
+//! let norm = norm_l2_est(
+//!     a.as_ref(),
+//!     PowerIterationParams::default(),
+//!     Parallelism::None,
+//!     PodStack::new(&mut GlobalPodBuffer::new(
+//!         norm_l2_est_req::<f64>(a.as_ref(), Parallelism::None).unwrap(),
+//!     )),
+//! );
+//! assert!((norm - 3.0).abs() < 1e-6);
+//! ```
+
+use crate::{
+    col::ColMut,
+    linalg::{temp_mat_req, temp_mat_uninit},
+    linop::{BiLinOp, LinOp},
+    unzipped, zipped, ComplexField, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Parameters of the power iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct PowerIterationParams<E: ComplexField> {
+    /// Absolute tolerance on the residual.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residual, relative to the magnitude of the eigenvalue.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of iterations.
+    pub max_iters: usize,
+}
+
+/// Information about a successful run of the power iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct PowerIterationInfo<E: ComplexField> {
+    /// Estimate of the dominant eigenvalue.
+    pub eigenvalue: E,
+    /// Norm of the residual $\|Ax - \lambda x\|$, where $x$ has unit norm.
+    pub abs_residual: E::Real,
+    /// Norm of the residual, relative to the magnitude of the eigenvalue.
+    pub rel_residual: E::Real,
+    /// Number of iterations until convergence.
+    pub iter_count: usize,
+}
+
+/// Error from the power iteration.
+#[derive(Copy, Clone, Debug)]
+pub enum PowerIterationError<E: ComplexField> {
+    /// The iteration did not converge within the maximum number of iterations. This is usually
+    /// the case when several distinct eigenvalues have the largest magnitude, e.g., a complex
+    /// conjugate pair for a real matrix.
+    NoConvergence {
+        /// Last estimate of the dominant eigenvalue.
+        eigenvalue: E,
+        /// Norm of the residual.
+        abs_residual: E::Real,
+        /// Norm of the residual, relative to the magnitude of the eigenvalue.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for PowerIterationParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_sqrt(),
+            max_iters: 1000,
+        }
+    }
+}
+
+/// Fills `col` with deterministic pseudo-random values in $[-1/2, 1/2)$, which are used as
+/// starting vectors when the caller doesn't provide one.
+pub(crate) fn fill_pseudo_random<E: ComplexField>(col: ColMut<'_, E>, seed: u64) {
+    let mut col = col;
+    let mut state = seed
+        .wrapping_mul(0x9E37_79B9_7F4A_7C15)
+        .wrapping_add(0x2545_F491_4F6C_DD1D);
+    for i in 0..col.nrows() {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        col.write(
+            i,
+            E::faer_from_f64((state >> 11) as f64 / (1u64 << 53) as f64 - 0.5),
+        );
+    }
+}
+
+/// Scales `col` to unit norm, or fills it with a pseudo-random unit vector if it is zero.
+fn normalize_or_random<E: ComplexField>(col: ColMut<'_, E>) {
+    let mut col = col;
+    let mut norm = col.rb().norm_l2();
+    if norm == E::Real::faer_zero() {
+        fill_pseudo_random(col.rb_mut(), 0);
+        norm = col.rb().norm_l2();
+    }
+    let inv = norm.faer_inv();
+    zipped!(col.as_2d_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(inv)));
+}
+
+/// Computes the size and alignment of the workspace required by [`power_iteration`].
+pub fn power_iteration_req<E: ComplexField>(
+    mat: impl LinOp<E>,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        A: &dyn LinOp<E>,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        temp_mat_req::<E>(A.nrows(), 1)?.try_and(A.apply_req(1, parallelism)?)
+    }
+    implementation(&mat, parallelism)
+}
+
+/// Computes the size and alignment of the workspace required by [`norm_l2_est`].
+pub fn norm_l2_est_req<E: ComplexField>(
+    mat: impl BiLinOp<E>,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        A: &dyn BiLinOp<E>,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        StackReq::try_all_of([
+            temp_mat_req::<E>(A.ncols(), 1)?,
+            temp_mat_req::<E>(A.nrows(), 1)?,
+            StackReq::try_any_of([
+                A.apply_req(1, parallelism)?,
+                A.transpose_apply_req(1, parallelism)?,
+            ])?,
+        ])
+    }
+    implementation(&mat, parallelism)
+}
+
+/// Computes the dominant eigenvalue of the square operator `mat`, i.e., the one with the largest
+/// magnitude, along with the corresponding eigenvector.
+///
+/// `eigenvector` contains the starting vector on input, and the normalized eigenvector on output.
+/// If it is zero, a pseudo-random starting vector is used instead.
+///
+/// The iteration stops when $\|Ax - \lambda x\| \leq \text{rel\_tol} |\lambda| + \text{abs\_tol}$,
+/// where $\lambda = x^H A x$ is the Rayleigh quotient of the current unit vector $x$.
+///
+/// # Panics
+/// Panics if `mat` is not square, if `eigenvector` doesn't have `mat.nrows()` rows, or if the
+/// provided memory in `stack` is insufficient (see [`power_iteration_req`]).
+#[track_caller]
+pub fn power_iteration<E: ComplexField>(
+    eigenvector: ColMut<'_, E>,
+    mat: impl LinOp<E>,
+    params: PowerIterationParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<PowerIterationInfo<E>, PowerIterationError<E>> {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut x: ColMut<'_, E>,
+        A: &dyn LinOp<E>,
+        params: PowerIterationParams<E>,
+        par: Parallelism,
+        stack: PodStack<'_>,
+    ) -> Result<PowerIterationInfo<E>, PowerIterationError<E>> {
+        let n = A.nrows();
+        crate::assert!(all(A.ncols() == n, x.nrows() == n));
+
+        let zero = E::Real::faer_zero();
+        if n == 0 {
+            return Ok(PowerIterationInfo {
+                eigenvalue: E::faer_zero(),
+                abs_residual: zero,
+                rel_residual: zero,
+                iter_count: 0,
+            });
+        }
+
+        let (y, mut stack) = temp_mat_uninit::<E>(n, 1, stack);
+        let mut y = y.col_mut(0);
+        normalize_or_random(x.rb_mut());
+
+        let mut eigenvalue = E::faer_zero();
+        let mut abs_residual = zero;
+        let mut rel_residual = zero;
+        for iter in 0..=params.max_iters {
+            A.apply(y.rb_mut().as_2d_mut(), x.rb().as_2d(), par, stack.rb_mut());
+
+            // rayleigh quotient, and residual y - λx
+            eigenvalue = E::faer_zero();
+            for i in 0..n {
+                eigenvalue = eigenvalue.faer_add(x.read(i).faer_conj().faer_mul(y.read(i)));
+            }
+            abs_residual = zero;
+            for i in 0..n {
+                abs_residual = abs_residual.faer_add(
+                    y.read(i)
+                        .faer_sub(eigenvalue.faer_mul(x.read(i)))
+                        .faer_abs2(),
+                );
+            }
+            abs_residual = abs_residual.faer_sqrt();
+
+            let abs_eigenvalue = eigenvalue.faer_abs();
+            rel_residual = if abs_eigenvalue > zero {
+                abs_residual.faer_div(abs_eigenvalue)
+            } else {
+                abs_residual
+            };
+            let threshold = params
+                .rel_tolerance
+                .faer_mul(abs_eigenvalue)
+                .faer_add(params.abs_tolerance);
+            if abs_residual <= threshold {
+                return Ok(PowerIterationInfo {
+                    eigenvalue,
+                    abs_residual,
+                    rel_residual,
+                    iter_count: iter,
+                });
+            }
+            if iter == params.max_iters {
+                break;
+            }
+
+            x.copy_from(y.rb());
+            normalize_or_random(x.rb_mut());
+        }
+
+        Err(PowerIterationError::NoConvergence {
+            eigenvalue,
+            abs_residual,
+            rel_residual,
+        })
+    }
+
+    implementation(eigenvector, &mat, params, parallelism, stack)
+}
+
+/// Returns an estimate of the spectral norm of `mat`, i.e., its largest singular value, by
+/// applying the power iteration to $A^H A$.
+///
+/// The iteration stops when the relative change in the estimate is at most `rel_tolerance`, or
+/// its absolute change is at most `abs_tolerance`, or after `max_iters` iterations. In all cases,
+/// the estimate is a lower bound of the exact value, up to rounding errors.
+///
+/// # Panics
+/// Panics if the provided memory in `stack` is insufficient (see [`norm_l2_est_req`]).
+#[track_caller]
+pub fn norm_l2_est<E: ComplexField>(
+    mat: impl BiLinOp<E>,
+    params: PowerIterationParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> E::Real {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        A: &dyn BiLinOp<E>,
+        params: PowerIterationParams<E>,
+        par: Parallelism,
+        stack: PodStack<'_>,
+    ) -> E::Real {
+        let (m, n) = (A.nrows(), A.ncols());
+        let zero = E::Real::faer_zero();
+        if m == 0 || n == 0 {
+            return zero;
+        }
+
+        let (x, stack) = temp_mat_uninit::<E>(n, 1, stack);
+        let (y, mut stack) = temp_mat_uninit::<E>(m, 1, stack);
+        let mut x = x.col_mut(0);
+        let mut y = y.col_mut(0);
+        fill_pseudo_random(x.rb_mut(), 0);
+        normalize_or_random(x.rb_mut());
+
+        let mut norm = zero;
+        for iter in 0..=params.max_iters {
+            A.apply(y.rb_mut().as_2d_mut(), x.rb().as_2d(), par, stack.rb_mut());
+            let new_norm = y.rb().norm_l2();
+            if new_norm == zero {
+                // x is in the null space of A, which can only happen by chance if A is nonzero
+                return norm;
+            }
+
+            let converged = iter > 0
+                && new_norm.faer_sub(norm).faer_abs()
+                    <= params
+                        .rel_tolerance
+                        .faer_mul(new_norm)
+                        .faer_add(params.abs_tolerance);
+            // the estimate is nondecreasing in exact arithmetic
+            if new_norm > norm {
+                norm = new_norm;
+            }
+            if converged || iter == params.max_iters {
+                break;
+            }
+
+            A.adjoint_apply(x.rb_mut().as_2d_mut(), y.rb().as_2d(), par, stack.rb_mut());
+            normalize_or_random(x.rb_mut());
+        }
+        norm
+    }
+
+    implementation(&mat, params, parallelism, stack)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Col, Mat};
+    use dyn_stack::GlobalPodBuffer;
+
+    #[test]
+    fn test_power_iteration() {
+        let n = 30;
+        let b = Mat::<f64>::from_fn(n, n, |_, _| rand::random::<f64>() - 0.5);
+        let q = b.qr().compute_q();
+        let d = Col::<f64>::from_fn(n, |i| if i == 3 { -10.0 } else { i as f64 / 5.0 });
+        let a = &q * d.as_ref().column_vector_as_diagonal() * q.transpose();
+
+        let mut x = Col::<f64>::zeros(n);
+        let params = PowerIterationParams::default();
+        let info = power_iteration(
+            x.as_mut(),
+            a.as_ref(),
+            params,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                power_iteration_req::<f64>(a.as_ref(), Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        assert!((info.eigenvalue + 10.0).abs() < 1e-6);
+        assert!(info.rel_residual <= params.rel_tolerance);
+        assert!((x.norm_l2() - 1.0).abs() < 1e-12);
+        // the eigenvector is ±q[:, 3]
+        assert!((x.transpose() * q.col(3)).abs() > 1.0 - 1e-6);
+    }
+
+    #[test]
+    fn test_power_iteration_no_convergence() {
+        // rotation, the eigenvalues ±i have the same magnitude
+        let a = crate::mat![[0.0, -1.0], [1.0, 0.0f64]];
+        let mut x = Col::<f64>::from_fn(2, |i| i as f64 + 1.0);
+        let mut params = PowerIterationParams::default();
+        params.max_iters = 50;
+        let result = power_iteration(
+            x.as_mut(),
+            a.as_ref(),
+            params,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                power_iteration_req::<f64>(a.as_ref(), Parallelism::None).unwrap(),
+            )),
+        );
+        assert!(matches!(
+            result,
+            Err(PowerIterationError::NoConvergence { .. })
+        ));
+    }
+
+    #[test]
+    fn test_norm_l2_est() {
+        for (m, n) in [(1, 1), (40, 10), (10, 40), (25, 25)] {
+            let a = Mat::<c64>::from_fn(m, n, |_, _| {
+                c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+            });
+            let exact = a.singular_values()[0];
+            let mut params = PowerIterationParams::default();
+            params.rel_tolerance = 1e-12;
+            let est = norm_l2_est(
+                a.as_ref(),
+                params,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    norm_l2_est_req::<c64>(a.as_ref(), Parallelism::None).unwrap(),
+                )),
+            );
+            assert!(est <= exact * (1.0 + 1e-12));
+            assert!(est >= exact * (1.0 - 1e-4));
+        }
+
+        let a = Mat::<f64>::zeros(4, 3);
+        let est = norm_l2_est(
+            a.as_ref(),
+            PowerIterationParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                norm_l2_est_req::<f64>(a.as_ref(), Parallelism::None).unwrap(),
+            )),
+        );
+        assert!(est == 0.0);
+    }
+}
@@ -0,0 +1,388 @@
+pub use crate::linalg::qr::no_pivoting::compute::recommended_blocksize;
+use crate::{
+    assert,
+    linalg::{householder::upgrade_householder_factor, matmul::inner_prod::inner_prod_with_conj},
+    perm::{swap_cols_idx as swap_cols, swap_rows_idx as swap_rows, PermRef},
+    unzipped,
+    utils::DivCeil,
+    zipped, Conj, Index, MatMut, Parallelism, SignedIndex,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use faer_entity::*;
+use reborrow::*;
+
+fn qr_in_place_unblocked<E: ComplexField>(
+    mut matrix: MatMut<'_, E>,
+    mut householder_coeffs: MatMut<'_, E>,
+    row_transpositions: &mut [usize],
+    col_transpositions: &mut [usize],
+) -> usize {
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = Ord::min(m, n);
+
+    let mut n_transpositions = 0;
+
+    for k in 0..size {
+        // pick the column with the largest remaining norm
+        let mut biggest_col_idx = k;
+        let mut biggest_col_value = E::Real::faer_zero();
+        for j in k..n {
+            let col_value = matrix.rb().col(j).subrows(k, m - k).norm_l2();
+            if col_value > biggest_col_value {
+                biggest_col_value = col_value;
+                biggest_col_idx = j;
+            }
+        }
+        col_transpositions[k] = biggest_col_idx;
+        if biggest_col_idx != k {
+            n_transpositions += 1;
+            swap_cols(matrix.rb_mut(), k, biggest_col_idx);
+        }
+
+        // then the row with the largest entry in that column. swapping entire rows also permutes
+        // the essential parts of the previous householder reflections, which keeps them
+        // consistent with the permuted matrix
+        let mut biggest_row_idx = k;
+        let mut biggest_row_value = E::Real::faer_zero();
+        for i in k..m {
+            let row_value = matrix.read(i, k).faer_abs2();
+            if row_value > biggest_row_value {
+                biggest_row_value = row_value;
+                biggest_row_idx = i;
+            }
+        }
+        row_transpositions[k] = biggest_row_idx;
+        if biggest_row_idx != k {
+            n_transpositions += 1;
+            swap_rows(matrix.rb_mut(), k, biggest_row_idx);
+        }
+
+        let mat_rem = matrix.rb_mut().submatrix_mut(k, k, m - k, n - k);
+        let (_, _, first_col, mut last_cols) = mat_rem.split_at_mut(0, 1);
+        let (mut first_col_head, mut first_col_tail) = first_col.col_mut(0).split_at_mut(1);
+
+        let tail_norm = first_col_tail.norm_l2();
+
+        let (tau, beta) = crate::linalg::householder::make_householder_in_place(
+            Some(first_col_tail.rb_mut().as_2d_mut()),
+            first_col_head.read(0),
+            tail_norm,
+        );
+        householder_coeffs.write(k, 0, tau);
+        let tau_inv = tau.faer_inv();
+
+        first_col_head.write(0, beta);
+
+        for idx in 0..last_cols.ncols() {
+            let col = last_cols.rb_mut().col_mut(idx);
+            let (mut col_head, col_tail) = col.split_at_mut(1);
+            let col_head_ = col_head.read(0);
+
+            let dot = col_head_.faer_add(inner_prod_with_conj(
+                first_col_tail.rb().as_2d(),
+                Conj::Yes,
+                col_tail.rb().as_2d(),
+                Conj::No,
+            ));
+            let k = (dot.faer_mul(tau_inv)).faer_neg();
+            col_head.write(0, col_head_.faer_add(k));
+            zipped!(col_tail.as_2d_mut(), first_col_tail.rb().as_2d())
+                .for_each(|unzipped!(mut a, b)| a.write(a.read().faer_add(k.faer_mul(b.read()))));
+        }
+    }
+
+    n_transpositions
+}
+
+/// QR factorization tuning parameters.
+#[derive(Default, Copy, Clone)]
+#[non_exhaustive]
+pub struct FullPivQrComputeParams {}
+
+/// Computes the size and alignment of required workspace for performing a QR decomposition
+/// with full pivoting.
+pub fn qr_in_place_req<I: Index, E: Entity>(
+    nrows: usize,
+    ncols: usize,
+    blocksize: usize,
+    parallelism: Parallelism,
+    params: FullPivQrComputeParams,
+) -> Result<StackReq, SizeOverflow> {
+    let _ = parallelism;
+    let _ = blocksize;
+    let _ = &params;
+    let size = Ord::min(nrows, ncols);
+    StackReq::try_new::<usize>(size)?.try_and(StackReq::try_new::<usize>(size)?)
+}
+
+/// Information about the resulting QR factorization.
+#[derive(Copy, Clone, Debug)]
+pub struct FullPivQrInfo {
+    /// Number of transpositions that were performed, can be used to compute the determinant of
+    /// $P_0 P_1$.
+    pub transposition_count: usize,
+}
+
+/// Computes the QR decomposition with full pivoting of a rectangular matrix $A$, into a unitary
+/// matrix $Q$, represented as a block Householder sequence, and an upper trapezoidal matrix $R$,
+/// such that $$P_0 A P_1^\top = QR.$$
+///
+/// The Householder bases of $Q$ are stored in the strictly lower trapezoidal part of `matrix` with
+/// an implicit unit diagonal, and its upper triangular Householder factors are stored in
+/// `householder_factor`, blockwise in chunks of `blocksize×blocksize`.
+///
+/// The block size is chosed as the number of rows of `householder_factor`.
+///
+/// After the function returns, `row_perm` and `col_perm` contain the order of the rows and
+/// columns after pivoting, i.e. the result is the same as computing the non-pivoted QR
+/// decomposition of the matrix `matrix[row_perm, col_perm]`. `row_perm_inv` and `col_perm_inv`
+/// contain their inverse permutations.
+///
+/// # Output
+///
+/// - The number of transpositions that constitute the permutations.
+/// - a structure representing the permutation $P_0$.
+/// - a structure representing the permutation $P_1$.
+///
+/// # Panics
+///
+/// - Panics if the number of columns of the householder factor is not equal to the minimum of the
+/// number of rows and the number of columns of the input matrix.
+/// - Panics if the block size is zero.
+/// - Panics if the length of `row_perm` and `row_perm_inv` is not equal to the number of rows of
+/// `matrix`.
+/// - Panics if the length of `col_perm` and `col_perm_inv` is not equal to the number of columns
+/// of `matrix`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`qr_in_place_req`]).
+#[track_caller]
+pub fn qr_in_place<'out, I: Index, E: ComplexField>(
+    matrix: MatMut<'_, E>,
+    householder_factor: MatMut<'_, E>,
+    row_perm: &'out mut [I],
+    row_perm_inv: &'out mut [I],
+    col_perm: &'out mut [I],
+    col_perm_inv: &'out mut [I],
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+    params: FullPivQrComputeParams,
+) -> (FullPivQrInfo, PermRef<'out, I>, PermRef<'out, I>) {
+    let _ = &params;
+    let truncate = <I::Signed as SignedIndex>::truncate;
+
+    let m = matrix.nrows();
+    let n = matrix.ncols();
+    let size = Ord::min(m, n);
+
+    assert!(all(
+        row_perm.len() == m,
+        row_perm_inv.len() == m,
+        col_perm.len() == n,
+        col_perm_inv.len() == n,
+        householder_factor.ncols() == size,
+        householder_factor.nrows() > 0,
+    ));
+
+    let (row_transpositions, stack) = stack.make_with(size, |_| 0usize);
+    let (col_transpositions, _) = stack.make_with(size, |_| 0usize);
+
+    let mut matrix = matrix;
+    let mut householder_factor = householder_factor;
+
+    let n_transpositions = qr_in_place_unblocked(
+        matrix.rb_mut(),
+        householder_factor
+            .rb_mut()
+            .row_mut(0)
+            .transpose_mut()
+            .as_2d_mut(),
+        row_transpositions,
+        col_transpositions,
+    );
+
+    let blocksize = householder_factor.nrows();
+    if blocksize > 1 {
+        let n_blocks = size.msrv_div_ceil(blocksize);
+
+        let qr_factors = matrix.rb();
+
+        let func = |idx: usize| {
+            let j = idx * blocksize;
+            let blocksize = Ord::min(blocksize, size - j);
+            let mut householder = unsafe { householder_factor.rb().const_cast() }
+                .submatrix_mut(0, j, blocksize, blocksize);
+
+            for i in 0..blocksize {
+                let coeff = householder.read(0, i);
+                householder.write(i, i, coeff);
+            }
+
+            let qr = qr_factors.submatrix(j, j, m - j, blocksize);
+
+            upgrade_householder_factor(householder, qr, blocksize, 1, parallelism);
+        };
+
+        match parallelism {
+            Parallelism::None => (0..n_blocks).for_each(func),
+            #[cfg(feature = "rayon")]
+            Parallelism::Rayon(_) => {
+                use rayon::prelude::*;
+                (0..n_blocks).into_par_iter().for_each(func)
+            }
+        }
+    }
+
+    for (i, p) in row_perm.iter_mut().enumerate() {
+        *p = I::from_signed(truncate(i));
+    }
+    for (i, &t) in row_transpositions.iter().enumerate() {
+        row_perm.swap(i, t);
+    }
+    for (j, p) in col_perm.iter_mut().enumerate() {
+        *p = I::from_signed(truncate(j));
+    }
+    for (j, &t) in col_transpositions.iter().enumerate() {
+        col_perm.swap(j, t);
+    }
+
+    for (i, p) in row_perm.iter().copied().enumerate() {
+        row_perm_inv[p.to_signed().zx()] = I::from_signed(truncate(i));
+    }
+    for (j, p) in col_perm.iter().copied().enumerate() {
+        col_perm_inv[p.to_signed().zx()] = I::from_signed(truncate(j));
+    }
+
+    unsafe {
+        (
+            FullPivQrInfo {
+                transposition_count: n_transpositions,
+            },
+            PermRef::new_unchecked(row_perm, row_perm_inv),
+            PermRef::new_unchecked(col_perm, col_perm_inv),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert,
+        complex_native::c64,
+        linalg::qr::full_pivoting::reconstruct::{reconstruct, reconstruct_req},
+        Mat,
+    };
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr $(,)?) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    fn test_qr<E: ComplexField>(mut random: impl FnMut() -> E, epsilon: E::Real) {
+        for parallelism in [Parallelism::None, Parallelism::Rayon(4)] {
+            for (m, n) in [(2, 2), (2, 4), (4, 2), (4, 4), (31, 17), (17, 31), (63, 63)] {
+                let mat_orig = Mat::<E>::from_fn(m, n, |_, _| random());
+                let mut mat = mat_orig.clone();
+                let size = m.min(n);
+                let blocksize = 8;
+                let mut householder = Mat::zeros(blocksize, size);
+                let mut row_perm = vec![0usize; m];
+                let mut row_perm_inv = vec![0usize; m];
+                let mut col_perm = vec![0usize; n];
+                let mut col_perm_inv = vec![0usize; n];
+
+                let (_, row_perm, col_perm) = qr_in_place(
+                    mat.as_mut(),
+                    householder.as_mut(),
+                    &mut row_perm,
+                    &mut row_perm_inv,
+                    &mut col_perm,
+                    &mut col_perm_inv,
+                    parallelism,
+                    make_stack!(qr_in_place_req::<usize, E>(
+                        m,
+                        n,
+                        blocksize,
+                        parallelism,
+                        Default::default(),
+                    )),
+                    Default::default(),
+                );
+
+                // the diagonal of R is non increasing in magnitude
+                let slack = E::Real::faer_one().faer_add(epsilon);
+                for k in 1..size {
+                    assert!(
+                        mat.read(k, k).faer_abs()
+                            <= mat.read(k - 1, k - 1).faer_abs().faer_mul(slack)
+                    );
+                }
+
+                let mut reconstructed = Mat::<E>::zeros(m, n);
+                reconstruct(
+                    reconstructed.as_mut(),
+                    mat.as_ref(),
+                    householder.as_ref(),
+                    row_perm,
+                    col_perm,
+                    parallelism,
+                    make_stack!(reconstruct_req::<usize, E>(m, n, blocksize, parallelism)),
+                );
+                assert!((&reconstructed - &mat_orig).norm_max() < epsilon);
+            }
+        }
+    }
+
+    #[test]
+    fn test_qr_f64() {
+        test_qr(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_qr_c64() {
+        test_qr(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_rank_detection() {
+        // a rank 2 matrix whose rows have wildly different scales
+        let m = 6;
+        let n = 4;
+        let u = Mat::<f64>::from_fn(m, 2, |i, j| {
+            10.0f64.powi(2 * i as i32 - 5) * ((i + 2 * j) as f64).sin()
+        });
+        let v = Mat::<f64>::from_fn(2, n, |i, j| ((3 * i + j) as f64).cos());
+        let mut mat = &u * &v;
+
+        let mut householder = Mat::zeros(1, n);
+        let mut row_perm = vec![0usize; m];
+        let mut row_perm_inv = vec![0usize; m];
+        let mut col_perm = vec![0usize; n];
+        let mut col_perm_inv = vec![0usize; n];
+        qr_in_place(
+            mat.as_mut(),
+            householder.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(qr_in_place_req::<usize, f64>(
+                m,
+                n,
+                1,
+                Parallelism::None,
+                Default::default(),
+            )),
+            Default::default(),
+        );
+
+        let r00 = mat.read(0, 0).abs();
+        assert!(mat.read(1, 1).abs() > 1e-12 * r00);
+        for k in 2..n {
+            assert!(mat.read(k, k).abs() < 1e-14 * r00);
+        }
+    }
+}
@@ -0,0 +1,18 @@
+//! The QR decomposition with full pivoting decomposes a matrix $A$ into the product
+//! $$P_0 A P_1^T = QR,$$
+//! where $P_0$ and $P_1$ are permutation matrices, $Q$ is a unitary matrix (represented as a block
+//! Householder sequence), and $R$ is an upper trapezoidal matrix.
+//!
+//! At each step, the column with the largest remaining norm is chosen, as in the QR decomposition
+//! with column pivoting, and the row holding the largest entry of that column is moved to the
+//! diagonal. The additional row pivoting makes the decomposition more reliable for detecting the
+//! rank of badly row-scaled matrices, at the cost of an unblocked factorization.
+
+/// Computing the decomposition.
+pub mod compute;
+/// Reconstructing the inverse of the original matrix from the decomposition.
+pub mod inverse;
+/// Reconstructing the original matrix from the decomposition.
+pub mod reconstruct;
+/// Solving a linear system using the decomposition.
+pub mod solve;
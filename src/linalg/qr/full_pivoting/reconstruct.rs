@@ -0,0 +1,194 @@
+use crate::{
+    linalg::{qr::col_pivoting, temp_mat_req, temp_mat_uninit},
+    perm::{permute_rows_in_place, permute_rows_in_place_req, PermRef},
+    unzipped, zipped, ComplexField, Entity, Index, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Computes the reconstructed matrix, given its QR decomposition with full pivoting, and stores
+/// the result in `dst`.
+///
+/// # Panics
+///
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` doesn't have the same dimension as the number of rows of `qr_factors`.
+/// - Panics if `col_perm` doesn't have the same dimension as the number of columns of
+/// `qr_factors`.
+/// - Panics if `dst` doesn't have the same shape as `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`reconstruct_req`]).
+#[track_caller]
+pub fn reconstruct<I: Index, E: ComplexField>(
+    dst: MatMut<'_, E>,
+    qr_factors: MatRef<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut dst = dst;
+    let mut stack = stack;
+    col_pivoting::reconstruct::reconstruct(
+        dst.rb_mut(),
+        qr_factors,
+        householder_factor,
+        col_perm,
+        parallelism,
+        stack.rb_mut(),
+    );
+    permute_rows_in_place(dst, row_perm.inverse(), stack);
+}
+
+/// Computes the reconstructed matrix, given its QR decomposition with full pivoting, and stores
+/// the result in `qr_factors`.
+///
+/// # Panics
+///
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` doesn't have the same dimension as the number of rows of `qr_factors`.
+/// - Panics if `col_perm` doesn't have the same dimension as the number of columns of
+/// `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`reconstruct_in_place_req`]).
+#[track_caller]
+pub fn reconstruct_in_place<I: Index, E: ComplexField>(
+    qr_factors: MatMut<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let (mut dst, stack) = temp_mat_uninit::<E>(qr_factors.nrows(), qr_factors.ncols(), stack);
+    let mut dst = dst.as_mut();
+
+    reconstruct(
+        dst.rb_mut(),
+        qr_factors.rb(),
+        householder_factor,
+        row_perm,
+        col_perm,
+        parallelism,
+        stack,
+    );
+
+    zipped!(qr_factors, dst.rb()).for_each(|unzipped!(mut dst, src)| dst.write(src.read()));
+}
+
+/// Computes the size and alignment of required workspace for reconstructing a matrix out of place,
+/// given its QR decomposition with full pivoting.
+pub fn reconstruct_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_ncols: usize,
+    blocksize: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        col_pivoting::reconstruct::reconstruct_req::<I, E>(
+            qr_nrows,
+            qr_ncols,
+            blocksize,
+            parallelism,
+        )?,
+        permute_rows_in_place_req::<I, E>(qr_nrows, qr_ncols)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for reconstructing a matrix in place,
+/// given its QR decomposition with full pivoting.
+pub fn reconstruct_in_place_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_ncols: usize,
+    blocksize: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<E>(qr_nrows, qr_ncols)?,
+        reconstruct_req::<I, E>(qr_nrows, qr_ncols, blocksize, parallelism)?,
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert,
+        complex_native::c64,
+        linalg::qr::full_pivoting::compute::{qr_in_place, qr_in_place_req, recommended_blocksize},
+        Mat,
+    };
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    type E = c64;
+
+    #[test]
+    fn test_reconstruct() {
+        for (m, n) in [(31, 31), (32, 20), (20, 32), (65, 65)] {
+            let mat = Mat::from_fn(m, n, |_, _| E::new(random(), random()));
+            let blocksize = recommended_blocksize::<E>(m, n);
+            let mut qr = mat.clone();
+            let mut householder_factor = Mat::zeros(blocksize, Ord::min(m, n));
+
+            let parallelism = crate::Parallelism::Rayon(0);
+            let mut row_perm = vec![0usize; m];
+            let mut row_perm_inv = vec![0; m];
+            let mut col_perm = vec![0usize; n];
+            let mut col_perm_inv = vec![0; n];
+
+            let (_, row_perm, col_perm) = qr_in_place(
+                qr.as_mut(),
+                householder_factor.as_mut(),
+                &mut row_perm,
+                &mut row_perm_inv,
+                &mut col_perm,
+                &mut col_perm_inv,
+                parallelism,
+                make_stack!(qr_in_place_req::<usize, E>(
+                    m,
+                    n,
+                    blocksize,
+                    parallelism,
+                    Default::default()
+                )),
+                Default::default(),
+            );
+
+            let mut reconstructed = Mat::zeros(m, n);
+            reconstruct(
+                reconstructed.as_mut(),
+                qr.as_ref(),
+                householder_factor.as_ref(),
+                row_perm.rb(),
+                col_perm.rb(),
+                parallelism,
+                make_stack!(reconstruct_req::<usize, E>(m, n, blocksize, parallelism)),
+            );
+            assert!((&reconstructed - &mat).norm_max() < 1e-10);
+
+            reconstruct_in_place(
+                qr.as_mut(),
+                householder_factor.as_ref(),
+                row_perm.rb(),
+                col_perm.rb(),
+                parallelism,
+                make_stack!(reconstruct_in_place_req::<usize, E>(
+                    m,
+                    n,
+                    blocksize,
+                    parallelism
+                )),
+            );
+            assert!((&qr - &mat).norm_max() < 1e-10);
+        }
+    }
+}
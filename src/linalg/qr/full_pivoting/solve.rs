@@ -0,0 +1,407 @@
+use crate::{
+    linalg::qr::col_pivoting,
+    perm::{permute_rows, permute_rows_in_place, permute_rows_in_place_req, PermRef},
+    ComplexField, Conj, Entity, Index, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix in place, given its QR decomposition with full pivoting.
+#[inline]
+pub fn solve_in_place_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_blocksize: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        col_pivoting::solve::solve_in_place_req::<I, E>(qr_nrows, qr_blocksize, rhs_ncols)?,
+        permute_rows_in_place_req::<I, E>(qr_nrows, rhs_ncols)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by
+/// the transpose of a matrix in place, given its QR decomposition with full pivoting.
+#[inline]
+pub fn solve_transpose_in_place_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_blocksize: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        col_pivoting::solve::solve_transpose_in_place_req::<I, E>(
+            qr_nrows,
+            qr_blocksize,
+            rhs_ncols,
+        )?,
+        permute_rows_in_place_req::<I, E>(qr_nrows, rhs_ncols)?,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by a
+/// matrix out of place, given its QR decomposition with full pivoting.
+#[inline]
+pub fn solve_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_blocksize: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    col_pivoting::solve::solve_in_place_req::<I, E>(qr_nrows, qr_blocksize, rhs_ncols)
+}
+
+/// Computes the size and alignment of required workspace for solving a linear system defined by
+/// the transpose of a matrix out of place, given its QR decomposition with full pivoting.
+#[inline]
+pub fn solve_transpose_req<I: Index, E: Entity>(
+    qr_nrows: usize,
+    qr_blocksize: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        col_pivoting::solve::solve_transpose_req::<I, E>(qr_nrows, qr_blocksize, rhs_ncols)?,
+        permute_rows_in_place_req::<I, E>(qr_nrows, rhs_ncols)?,
+    ])
+}
+
+/// Given the QR factors with full pivoting of a matrix $A$ and a matrix $B$ stored in `rhs`,
+/// this function computes the solution of the linear system in the sense of least squares:
+/// $$\text{Op}_A(A)X = B.$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.
+///
+/// The solution of the linear system is stored in the top rows of `rhs`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` is not a tall matrix.
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` doesn't have the same dimension as the number of rows of `qr_factors`.
+/// - Panics if `col_perm` doesn't have the same dimension as the number of columns of
+/// `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`solve_in_place_req`]).
+#[track_caller]
+pub fn solve_in_place<I: Index, E: ComplexField>(
+    qr_factors: MatRef<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut rhs = rhs;
+    let mut stack = stack;
+    permute_rows_in_place(rhs.rb_mut(), row_perm, stack.rb_mut());
+    col_pivoting::solve::solve_in_place(
+        qr_factors,
+        householder_factor,
+        col_perm,
+        conj_lhs,
+        rhs,
+        parallelism,
+        stack,
+    );
+}
+
+/// Given the QR factors with full pivoting of a matrix $A$ and a matrix $B$ stored in `rhs`,
+/// this function computes the solution of the linear system:
+/// $$\text{Op}_A(A)^\top X = B.$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.
+///
+/// The solution of the linear system is stored in `rhs`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` is not a square matrix.
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` or `col_perm` doesn't have the same dimension as `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `qr_factors`.
+/// - Panics if the provided memory in `stack` is insufficient (see
+///   [`solve_transpose_in_place_req`]).
+#[track_caller]
+pub fn solve_transpose_in_place<I: Index, E: ComplexField>(
+    qr_factors: MatRef<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    conj_lhs: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut rhs = rhs;
+    let mut stack = stack;
+    col_pivoting::solve::solve_transpose_in_place(
+        qr_factors,
+        householder_factor,
+        col_perm,
+        conj_lhs,
+        rhs.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+    );
+    permute_rows_in_place(rhs, row_perm.inverse(), stack);
+}
+
+/// Given the QR factors with full pivoting of a matrix $A$ and a matrix $B$ stored in `rhs`,
+/// this function computes the solution of the linear system:
+/// $$\text{Op}_A(A)X = B.$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` is not a square matrix.
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` or `col_perm` doesn't have the same dimension as `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `qr_factors`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if the provided memory in `stack` is insufficient (see [`solve_req`]).
+#[track_caller]
+pub fn solve<I: Index, E: ComplexField>(
+    dst: MatMut<'_, E>,
+    qr_factors: MatRef<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut dst = dst;
+    permute_rows(dst.rb_mut(), rhs, row_perm);
+    col_pivoting::solve::solve_in_place(
+        qr_factors,
+        householder_factor,
+        col_perm,
+        conj_lhs,
+        dst,
+        parallelism,
+        stack,
+    );
+}
+
+/// Given the QR factors with full pivoting of a matrix $A$ and a matrix $B$ stored in `rhs`,
+/// this function computes the solution of the linear system:
+/// $$\text{Op}_A(A)^\top X = B.$$
+///
+/// $\text{Op}_A$ is either the identity or the conjugation depending on the value of `conj_lhs`.
+///
+/// The solution of the linear system is stored in `dst`.
+///
+/// # Panics
+///
+/// - Panics if `qr_factors` is not a square matrix.
+/// - Panics if the number of columns of `householder_factor` isn't the same as the minimum of the
+/// number of rows and the number of columns of `qr_factors`.
+/// - Panics if the block size is zero.
+/// - Panics if `row_perm` or `col_perm` doesn't have the same dimension as `qr_factors`.
+/// - Panics if `rhs` doesn't have the same number of rows as the dimension of `qr_factors`.
+/// - Panics if `rhs` and `dst` don't have the same shape.
+/// - Panics if the provided memory in `stack` is insufficient (see [`solve_transpose_req`]).
+#[track_caller]
+pub fn solve_transpose<I: Index, E: ComplexField>(
+    dst: MatMut<'_, E>,
+    qr_factors: MatRef<'_, E>,
+    householder_factor: MatRef<'_, E>,
+    row_perm: PermRef<'_, I>,
+    col_perm: PermRef<'_, I>,
+    conj_lhs: Conj,
+    rhs: MatRef<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut dst = dst;
+    let mut stack = stack;
+    col_pivoting::solve::solve_transpose(
+        dst.rb_mut(),
+        qr_factors,
+        householder_factor,
+        col_perm,
+        conj_lhs,
+        rhs,
+        parallelism,
+        stack.rb_mut(),
+    );
+    permute_rows_in_place(dst, row_perm.inverse(), stack);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert,
+        complex_native::c64,
+        linalg::qr::full_pivoting::compute::{qr_in_place, qr_in_place_req},
+        Mat,
+    };
+    use rand::random;
+
+    macro_rules! make_stack {
+        ($req: expr) => {
+            ::dyn_stack::PodStack::new(&mut ::dyn_stack::GlobalPodBuffer::new($req.unwrap()))
+        };
+    }
+
+    fn test_solve<E: ComplexField>(mut random: impl FnMut() -> E, epsilon: E::Real) {
+        let n = 32;
+        let k = 6;
+        let blocksize = 4;
+
+        let a = Mat::<E>::from_fn(n, n, |_, _| random());
+        let rhs = Mat::<E>::from_fn(n, k, |_, _| random());
+
+        let mut qr = a.clone();
+        let mut householder = Mat::<E>::zeros(blocksize, n);
+        let mut row_perm = vec![0usize; n];
+        let mut row_perm_inv = vec![0usize; n];
+        let mut col_perm = vec![0usize; n];
+        let mut col_perm_inv = vec![0usize; n];
+        let (_, row_perm, col_perm) = qr_in_place(
+            qr.as_mut(),
+            householder.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(qr_in_place_req::<usize, E>(
+                n,
+                n,
+                blocksize,
+                Parallelism::None,
+                Default::default()
+            )),
+            Default::default(),
+        );
+
+        for conj_lhs in [Conj::No, Conj::Yes] {
+            let a = if conj_lhs == Conj::Yes {
+                Mat::<E>::from_fn(n, n, |i, j| a.read(i, j).faer_conj())
+            } else {
+                a.clone()
+            };
+
+            let mut sol = rhs.clone();
+            solve_in_place(
+                qr.as_ref(),
+                householder.as_ref(),
+                row_perm,
+                col_perm,
+                conj_lhs,
+                sol.as_mut(),
+                Parallelism::None,
+                make_stack!(solve_in_place_req::<usize, E>(n, blocksize, k)),
+            );
+            assert!((&a * &sol - &rhs).norm_max() < epsilon);
+
+            let mut sol2 = Mat::<E>::zeros(n, k);
+            solve(
+                sol2.as_mut(),
+                qr.as_ref(),
+                householder.as_ref(),
+                row_perm,
+                col_perm,
+                conj_lhs,
+                rhs.as_ref(),
+                Parallelism::None,
+                make_stack!(solve_req::<usize, E>(n, blocksize, k)),
+            );
+            assert!((&sol2 - &sol).norm_max() < epsilon);
+
+            let mut sol = rhs.clone();
+            solve_transpose_in_place(
+                qr.as_ref(),
+                householder.as_ref(),
+                row_perm,
+                col_perm,
+                conj_lhs,
+                sol.as_mut(),
+                Parallelism::None,
+                make_stack!(solve_transpose_in_place_req::<usize, E>(n, blocksize, k)),
+            );
+            assert!((a.transpose() * &sol - &rhs).norm_max() < epsilon);
+
+            solve_transpose(
+                sol2.as_mut(),
+                qr.as_ref(),
+                householder.as_ref(),
+                row_perm,
+                col_perm,
+                conj_lhs,
+                rhs.as_ref(),
+                Parallelism::None,
+                make_stack!(solve_transpose_req::<usize, E>(n, blocksize, k)),
+            );
+            assert!((&sol2 - &sol).norm_max() < epsilon);
+        }
+    }
+
+    #[test]
+    fn test_solve_f64() {
+        test_solve(random::<f64>, 1e-8);
+    }
+
+    #[test]
+    fn test_solve_c64() {
+        test_solve(|| c64::new(random(), random()), 1e-8);
+    }
+
+    #[test]
+    fn test_solve_least_squares() {
+        let m = 20;
+        let n = 5;
+        let a = Mat::<f64>::from_fn(m, n, |_, _| random());
+        let x = Mat::<f64>::from_fn(n, 2, |_, _| random());
+        let rhs = &a * &x;
+
+        let mut qr = a.clone();
+        let mut householder = Mat::<f64>::zeros(2, n);
+        let mut row_perm = vec![0usize; m];
+        let mut row_perm_inv = vec![0usize; m];
+        let mut col_perm = vec![0usize; n];
+        let mut col_perm_inv = vec![0usize; n];
+        let (_, row_perm, col_perm) = qr_in_place(
+            qr.as_mut(),
+            householder.as_mut(),
+            &mut row_perm,
+            &mut row_perm_inv,
+            &mut col_perm,
+            &mut col_perm_inv,
+            Parallelism::None,
+            make_stack!(qr_in_place_req::<usize, f64>(
+                m,
+                n,
+                2,
+                Parallelism::None,
+                Default::default()
+            )),
+            Default::default(),
+        );
+
+        let mut sol = rhs.clone();
+        solve_in_place(
+            qr.as_ref(),
+            householder.as_ref(),
+            row_perm,
+            col_perm,
+            Conj::No,
+            sol.as_mut(),
+            Parallelism::None,
+            make_stack!(solve_in_place_req::<usize, f64>(m, 2, 2)),
+        );
+        assert!((sol.as_ref().subrows(0, n) - &x).norm_max() < 1e-10);
+    }
+}
@@ -129,7 +129,9 @@
 #![allow(clippy::too_many_arguments)]
 
 pub mod col_pivoting;
+pub mod full_pivoting;
 pub mod no_pivoting;
+pub mod update;
 
 #[cfg(test)]
 mod tests {
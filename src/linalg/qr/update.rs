@@ -0,0 +1,461 @@
+//! Updating an explicit QR factorization $A = QR$ after a row or column of $A$ is inserted or
+//! deleted, without recomputing it from scratch.
+//!
+//! The factors are stored explicitly: $Q$ is a unitary matrix of dimension `A.nrows()`, and $R$
+//! is an upper trapezoidal matrix with the same dimensions as $A$. Each update is performed with a
+//! sequence of Givens rotations applied to $R$ from the left and to $Q$ from the right, and costs
+//! $\mathcal{O}(m^2 + mn)$ operations instead of the $\mathcal{O}(mn^2)$ operations of a full
+//! factorization.
+//!
+//! Since the functions in this module work on matrix views which can't be resized, the factors
+//! are passed using views of the largest of the old and new dimensions, and the documentation of
+//! each function specifies which part of the views holds the input and the output.
+
+use crate::{
+    assert,
+    linalg::{matmul::matmul_with_conj, temp_mat_req, temp_mat_uninit},
+    ColRef, ComplexField, Conj, Entity, MatMut, Parallelism, RowRef,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Computes the Givens rotation $G = \begin{pmatrix} c & s \\ -\bar{s} & c \end{pmatrix}$, with
+/// real $c$, such that $G \begin{pmatrix} a \\ b \end{pmatrix} = \begin{pmatrix} r \\ 0
+/// \end{pmatrix}$, and returns $(c, s, r)$.
+#[inline]
+fn make_givens<E: ComplexField>(a: E, b: E) -> (E::Real, E, E) {
+    let abs_b = b.faer_abs();
+    if abs_b == E::Real::faer_zero() {
+        return (E::Real::faer_one(), E::faer_zero(), a);
+    }
+    let abs_a = a.faer_abs();
+    if abs_a == E::Real::faer_zero() {
+        return (
+            E::Real::faer_zero(),
+            b.faer_conj().faer_scale_real(abs_b.faer_inv()),
+            E::faer_from_real(abs_b),
+        );
+    }
+
+    // avoids overflow when computing the norm
+    let scale = if abs_a > abs_b { abs_a } else { abs_b };
+    let scale_inv = scale.faer_inv();
+    let norm = a
+        .faer_scale_real(scale_inv)
+        .faer_abs2()
+        .faer_add(b.faer_scale_real(scale_inv).faer_abs2())
+        .faer_sqrt()
+        .faer_mul(scale);
+
+    let phase = a.faer_scale_real(abs_a.faer_inv());
+    let norm_inv = norm.faer_inv();
+    (
+        abs_a.faer_mul(norm_inv),
+        phase.faer_mul(b.faer_conj()).faer_scale_real(norm_inv),
+        phase.faer_scale_real(norm),
+    )
+}
+
+/// Applies the rotation computed by [`make_givens`] to the rows `i` and `j` of `mat`, starting
+/// from the column `start`.
+#[inline]
+fn rotate_rows<E: ComplexField>(
+    mat: MatMut<'_, E>,
+    i: usize,
+    j: usize,
+    start: usize,
+    c: E::Real,
+    s: E,
+) {
+    let mut mat = mat;
+    let s_conj = s.faer_conj();
+    for col in start..mat.ncols() {
+        let x = mat.read(i, col);
+        let y = mat.read(j, col);
+        mat.write(i, col, x.faer_scale_real(c).faer_add(s.faer_mul(y)));
+        mat.write(j, col, y.faer_scale_real(c).faer_sub(s_conj.faer_mul(x)));
+    }
+}
+
+/// Multiplies the columns `i` and `j` of `mat` by the adjoint of the rotation computed by
+/// [`make_givens`].
+#[inline]
+fn rotate_cols_adjoint<E: ComplexField>(mat: MatMut<'_, E>, i: usize, j: usize, c: E::Real, s: E) {
+    let mut mat = mat;
+    let s_conj = s.faer_conj();
+    for row in 0..mat.nrows() {
+        let x = mat.read(row, i);
+        let y = mat.read(row, j);
+        mat.write(row, i, x.faer_scale_real(c).faer_add(s_conj.faer_mul(y)));
+        mat.write(row, j, y.faer_scale_real(c).faer_sub(s.faer_mul(x)));
+    }
+}
+
+/// Zeroes the subdiagonal of the columns `start..` of `r`, which is assumed to be upper
+/// Hessenberg, and accumulates the rotations in `q`.
+fn hessenberg_to_triangular<E: ComplexField>(q: MatMut<'_, E>, r: MatMut<'_, E>, start: usize) {
+    let mut q = q;
+    let mut r = r;
+    let end = Ord::min(r.ncols(), r.nrows().saturating_sub(1));
+    for k in start..end {
+        let (c, s, rr) = make_givens(r.read(k, k), r.read(k + 1, k));
+        r.write(k, k, rr);
+        r.write(k + 1, k, E::faer_zero());
+        rotate_rows(r.rb_mut(), k, k + 1, k + 1, c, s);
+        rotate_cols_adjoint(q.rb_mut(), k, k + 1, c, s);
+    }
+}
+
+/// Computes the size and alignment of required workspace for deleting a row from a QR
+/// factorization.
+pub fn delete_row_req<E: Entity>(nrows: usize, ncols: usize) -> Result<StackReq, SizeOverflow> {
+    let _ = ncols;
+    temp_mat_req::<E>(nrows, 1)
+}
+
+/// Updates the QR factorization of an `m×n` matrix $A$ after `row` is inserted into $A$ at
+/// position `row_idx`.
+///
+/// On input, the top left `m×m` block of `q` and the top `m` rows of `r` contain the factors of
+/// $A$. On output, `q` and `r` contain the factors of the updated `(m+1)×n` matrix.
+///
+/// # Panics
+///
+/// - Panics if `q` is not a square matrix with the same number of rows as `r`.
+/// - Panics if `r` has no rows, or if `row_idx > r.nrows() - 1`.
+/// - Panics if `row` doesn't have the same number of columns as `r`.
+#[track_caller]
+pub fn insert_row<E: ComplexField>(
+    q: MatMut<'_, E>,
+    r: MatMut<'_, E>,
+    row_idx: usize,
+    row: RowRef<'_, E>,
+) {
+    let m1 = r.nrows();
+    let n = r.ncols();
+    assert!(all(
+        m1 > 0,
+        q.nrows() == m1,
+        q.ncols() == m1,
+        row_idx < m1,
+        row.ncols() == n,
+    ));
+    let m = m1 - 1;
+
+    let mut q = q;
+    let mut r = r;
+
+    // move the new row to the top of r, above the old factor
+    for i in (0..m).rev() {
+        for j in 0..n {
+            r.write(i + 1, j, r.read(i, j));
+        }
+    }
+    for j in 0..n {
+        r.write(0, j, row.read(j));
+    }
+
+    // q becomes the old factor bordered with an identity block, with the rows shifted to match
+    // the position of the new row. iterating backwards doesn't overwrite unread values
+    for i in (0..m).rev() {
+        let dst_i = if i >= row_idx { i + 1 } else { i };
+        for j in (0..m).rev() {
+            q.write(dst_i, j + 1, q.read(i, j));
+        }
+    }
+    for i in 0..m1 {
+        q.write(i, 0, E::faer_zero());
+    }
+    for j in 0..m1 {
+        q.write(row_idx, j, E::faer_zero());
+    }
+    q.write(row_idx, 0, E::faer_one());
+
+    hessenberg_to_triangular(q, r, 0);
+}
+
+/// Updates the QR factorization of an `m×n` matrix $A$ after the row at position `row_idx` is
+/// deleted from $A$.
+///
+/// On input, `q` and `r` contain the factors of $A$. On output, the top left `(m-1)×(m-1)` block
+/// of `q` and the top `m-1` rows of `r` contain the factors of the updated `(m-1)×n` matrix.
+///
+/// # Panics
+///
+/// - Panics if `q` is not a square matrix with the same number of rows as `r`.
+/// - Panics if `row_idx >= r.nrows()`.
+/// - Panics if the provided memory in `stack` is insufficient (see [`delete_row_req`]).
+#[track_caller]
+pub fn delete_row<E: ComplexField>(
+    q: MatMut<'_, E>,
+    r: MatMut<'_, E>,
+    row_idx: usize,
+    stack: PodStack<'_>,
+) {
+    let m = r.nrows();
+    let n = r.ncols();
+    assert!(all(q.nrows() == m, q.ncols() == m, row_idx < m));
+
+    let mut q = q;
+    let mut r = r;
+
+    let (mut v, _) = temp_mat_uninit::<E>(m, 1, stack);
+    let mut v = v.as_mut().col_mut(0);
+    for j in 0..m {
+        v.write(j, q.read(row_idx, j).faer_conj());
+    }
+
+    // rotate the conjugate of the deleted row of q onto the first unit vector, from the bottom up,
+    // which turns r into an upper hessenberg matrix
+    for j in (1..m).rev() {
+        let (c, s, rr) = make_givens(v.read(j - 1), v.read(j));
+        v.write(j - 1, rr);
+        v.write(j, E::faer_zero());
+        rotate_rows(r.rb_mut(), j - 1, j, j - 1, c, s);
+        rotate_cols_adjoint(q.rb_mut(), j - 1, j, c, s);
+    }
+
+    // the deleted row of q is now a multiple of the first unit vector, and so is the first column
+    // of q, up to rounding errors. removing both along with the first row of r leaves the factors
+    // of the updated matrix
+    for i in 0..m - 1 {
+        let src_i = if i >= row_idx { i + 1 } else { i };
+        for j in 0..m - 1 {
+            q.write(i, j, q.read(src_i, j + 1));
+        }
+    }
+    for i in 0..m - 1 {
+        for j in 0..n {
+            r.write(i, j, r.read(i + 1, j));
+        }
+    }
+}
+
+/// Updates the QR factorization of an `m×n` matrix $A$ after `col` is inserted into $A$ at
+/// position `col_idx`.
+///
+/// On input, `q` and the left `n` columns of `r` contain the factors of $A$. On output, `q` and
+/// `r` contain the factors of the updated `m×(n+1)` matrix.
+///
+/// # Panics
+///
+/// - Panics if `q` is not a square matrix with the same number of rows as `r`.
+/// - Panics if `r` has no columns, or if `col_idx > r.ncols() - 1`.
+/// - Panics if `col` doesn't have the same number of rows as `r`.
+#[track_caller]
+pub fn insert_col<E: ComplexField>(
+    q: MatMut<'_, E>,
+    r: MatMut<'_, E>,
+    col_idx: usize,
+    col: ColRef<'_, E>,
+    parallelism: Parallelism,
+) {
+    let m = r.nrows();
+    let n1 = r.ncols();
+    assert!(all(
+        n1 > 0,
+        q.nrows() == m,
+        q.ncols() == m,
+        col_idx < n1,
+        col.nrows() == m,
+    ));
+
+    let mut q = q;
+    let mut r = r;
+
+    for j in (col_idx..n1 - 1).rev() {
+        for i in 0..m {
+            r.write(i, j + 1, r.read(i, j));
+        }
+    }
+
+    // the new column of r is Qᴴ col
+    matmul_with_conj(
+        r.rb_mut().col_mut(col_idx).as_2d_mut(),
+        q.rb().transpose(),
+        Conj::Yes,
+        col.as_2d(),
+        Conj::No,
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+
+    // zero the new column below the diagonal, from the bottom up. the fill-in in the columns to
+    // the right lands on their diagonal, so r stays upper trapezoidal
+    for j in (col_idx + 1..m).rev() {
+        let (c, s, rr) = make_givens(r.read(j - 1, col_idx), r.read(j, col_idx));
+        r.write(j - 1, col_idx, rr);
+        r.write(j, col_idx, E::faer_zero());
+        rotate_rows(r.rb_mut(), j - 1, j, col_idx + 1, c, s);
+        rotate_cols_adjoint(q.rb_mut(), j - 1, j, c, s);
+    }
+}
+
+/// Updates the QR factorization of an `m×n` matrix $A$ after the column at position `col_idx` is
+/// deleted from $A$.
+///
+/// On input, `q` and `r` contain the factors of $A$. On output, `q` and the left `n-1` columns
+/// of `r` contain the factors of the updated `m×(n-1)` matrix, and the last column of `r` is
+/// zeroed.
+///
+/// # Panics
+///
+/// - Panics if `q` is not a square matrix with the same number of rows as `r`.
+/// - Panics if `col_idx >= r.ncols()`.
+#[track_caller]
+pub fn delete_col<E: ComplexField>(q: MatMut<'_, E>, r: MatMut<'_, E>, col_idx: usize) {
+    let m = r.nrows();
+    let n = r.ncols();
+    assert!(all(q.nrows() == m, q.ncols() == m, col_idx < n));
+
+    let mut r = r;
+    for j in col_idx..n - 1 {
+        for i in 0..m {
+            r.write(i, j, r.read(i, j + 1));
+        }
+    }
+    r.rb_mut().col_mut(n - 1).fill_zero();
+
+    hessenberg_to_triangular(q, r.subcols_mut(0, n - 1), col_idx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Mat};
+    use dyn_stack::GlobalPodBuffer;
+    use rand::random;
+
+    fn check<E: ComplexField>(q: &Mat<E>, r: &Mat<E>, a: &Mat<E>, epsilon: E::Real) {
+        let m = a.nrows();
+        let n = a.ncols();
+        assert!((q * r - a).norm_max() < epsilon);
+        assert!((q.adjoint() * q - Mat::<E>::identity(m, m)).norm_max() < epsilon);
+        for j in 0..n {
+            for i in j + 1..m {
+                assert!(r.read(i, j) == E::faer_zero());
+            }
+        }
+    }
+
+    fn test_update<E: ComplexField>(mut random: impl FnMut() -> E, epsilon: E::Real) {
+        let m = 9;
+        let n = 5;
+        let mut a = Mat::<E>::from_fn(m, n, |_, _| random());
+        let qr = a.qr();
+        let mut q = qr.compute_q();
+        let mut r = qr.compute_r();
+
+        // insert a row
+        let row = Mat::<E>::from_fn(1, n, |_, _| random());
+        let row_idx = 4;
+        q.resize_with(m + 1, m + 1, |_, _| E::faer_zero());
+        r.resize_with(m + 1, n, |_, _| E::faer_zero());
+        insert_row(q.as_mut(), r.as_mut(), row_idx, row.as_ref().row(0));
+        let a_new = Mat::<E>::from_fn(m + 1, n, |i, j| {
+            if i < row_idx {
+                a.read(i, j)
+            } else if i == row_idx {
+                row.read(0, j)
+            } else {
+                a.read(i - 1, j)
+            }
+        });
+        a = a_new;
+        check(&q, &r, &a, epsilon);
+
+        // delete a row
+        let m = m + 1;
+        let row_idx = 2;
+        delete_row(
+            q.as_mut(),
+            r.as_mut(),
+            row_idx,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                delete_row_req::<E>(m, n).unwrap(),
+            )),
+        );
+        q.truncate(m - 1, m - 1);
+        r.truncate(m - 1, n);
+        let a_new = Mat::<E>::from_fn(m - 1, n, |i, j| {
+            a.read(if i >= row_idx { i + 1 } else { i }, j)
+        });
+        a = a_new;
+        check(&q, &r, &a, epsilon);
+
+        // insert a column
+        let m = m - 1;
+        let col = Mat::<E>::from_fn(m, 1, |_, _| random());
+        let col_idx = 1;
+        r.resize_with(m, n + 1, |_, _| E::faer_zero());
+        insert_col(
+            q.as_mut(),
+            r.as_mut(),
+            col_idx,
+            col.as_ref().col(0),
+            Parallelism::None,
+        );
+        let a_new = Mat::<E>::from_fn(m, n + 1, |i, j| {
+            if j < col_idx {
+                a.read(i, j)
+            } else if j == col_idx {
+                col.read(i, 0)
+            } else {
+                a.read(i, j - 1)
+            }
+        });
+        a = a_new;
+        check(&q, &r, &a, epsilon);
+
+        // delete a column
+        let n = n + 1;
+        let col_idx = 0;
+        delete_col(q.as_mut(), r.as_mut(), col_idx);
+        r.truncate(m, n - 1);
+        let a_new = Mat::<E>::from_fn(m, n - 1, |i, j| {
+            a.read(i, if j >= col_idx { j + 1 } else { j })
+        });
+        a = a_new;
+        check(&q, &r, &a, epsilon);
+    }
+
+    #[test]
+    fn test_update_f64() {
+        test_update(random::<f64>, 1e-10);
+    }
+
+    #[test]
+    fn test_update_c64() {
+        test_update(|| c64::new(random(), random()), 1e-10);
+    }
+
+    #[test]
+    fn test_update_wide() {
+        // more columns than rows
+        let m = 3;
+        let n = 5;
+        let a = Mat::<f64>::from_fn(m, n, |_, _| random());
+        let qr = a.qr();
+        let mut q = qr.compute_q();
+        let mut r = qr.compute_r();
+
+        q.resize_with(m + 1, m + 1, |_, _| 0.0);
+        r.resize_with(m + 1, n, |_, _| 0.0);
+        let row = Mat::<f64>::from_fn(1, n, |_, _| random());
+        insert_row(q.as_mut(), r.as_mut(), m, row.as_ref().row(0));
+
+        let a = Mat::<f64>::from_fn(
+            m + 1,
+            n,
+            |i, j| {
+                if i < m {
+                    a.read(i, j)
+                } else {
+                    row.read(0, j)
+                }
+            },
+        );
+        check(&q, &r, &a, 1e-10);
+    }
+}
@@ -4,3 +4,47 @@ pub mod norm_l1;
 pub mod norm_l2;
 pub mod norm_max;
 pub mod sum;
+
+/// Returns the relative error of `a` with respect to `b`, `‖a - b‖ / ‖b‖`, where the norm is the
+/// Frobenius norm.
+///
+/// If `b` is zero, the absolute error `‖a - b‖` is returned instead.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` don't have the same dimensions.
+#[track_caller]
+pub fn relative_error<E: crate::ComplexField>(
+    a: crate::mat::MatRef<'_, E>,
+    b: crate::mat::MatRef<'_, E>,
+) -> E::Real {
+    use crate::{assert, RealField};
+
+    assert!(all(a.nrows() == b.nrows(), a.ncols() == b.ncols()));
+    let diff = crate::Mat::<E>::from_fn(a.nrows(), a.ncols(), |i, j| {
+        a.read(i, j).faer_sub(b.read(i, j))
+    });
+    let err = norm_l2::norm_l2(diff.as_ref());
+    let norm = norm_l2::norm_l2(b);
+    if norm == E::Real::faer_zero() {
+        err
+    } else {
+        err.faer_div(norm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, mat};
+
+    #[test]
+    fn test_relative_error() {
+        let a = mat![[1.0, 2.0], [2.0, 4.0f64]];
+        let b = mat![[1.0, 2.0], [2.0, 4.0 + 1e-3]];
+        assert!((relative_error(a.as_ref(), b.as_ref()) - 1e-3 / b.norm_l2()).abs() < 1e-12);
+        assert!(relative_error(a.as_ref(), a.as_ref()) == 0.0);
+        let z = crate::Mat::<f64>::zeros(2, 2);
+        assert!(relative_error(a.as_ref(), z.as_ref()) == 5.0);
+    }
+}
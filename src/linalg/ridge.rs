@@ -0,0 +1,349 @@
+//! Ridge (Tikhonov regularized) least squares.
+//!
+//! [`Ridge`] computes the solution of
+//! $$\min_x \|Ax - b\|_2^2 + \lambda \|Lx\|_2^2,$$
+//! for any number of regularization parameters $\lambda \ge 0$, where $L$ is either the identity
+//! (standard form) or a general regularization operator, such as a discrete derivative.
+//!
+//! The matrix is decomposed once, after which each value of $\lambda$ only costs a matrix
+//! multiplication:
+//! - in standard form, the thin SVD $A = U \Sigma V^H$ gives
+//! $x = V (\Sigma^2 + \lambda I)^{-1} \Sigma U^H b$,
+//! - in general form, a generalized SVD of the pair $(A, L)$ is computed from the QR
+//! decomposition of the stacked matrix $\begin{bmatrix} A \\\\ L \end{bmatrix} = \begin{bmatrix}
+//! Q_1 \\\\ Q_2 \end{bmatrix} R$, followed by the SVD of $Q_1 = U C W^H$. Writing $S$ for the
+//! norms of the columns of $Q_2 W$, we have $C^2 + S^2 = I$, and
+//! $x = R^{-1} W (C^2 + \lambda S^2)^{-1} C U^H b$.
+//!
+//! Unlike forming the normal equations $(A^H A + \lambda L^H L) x = A^H b$, neither approach
+//! squares the condition number of the problem.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{linalg::ridge::Ridge, mat};
+//!
+//! let a = mat![[1.0, 0.0], [1.0, 1.0], [1.0, 2.0f64]];
+//! let b = mat![[1.0], [2.0], [4.0]];
+//!
+//! let ridge = Ridge::new(a.as_ref());
+//! let xs = ridge.solve_many(b.as_ref(), &[0.0, 1.0, 100.0]);
+//!
+//! // the solution shrinks as the regularization grows
+//! assert!(xs[0].norm_l2() > xs[1].norm_l2());
+//! assert!(xs[1].norm_l2() > xs[2].norm_l2());
+//! ```
+
+use crate::{
+    assert,
+    col::Col,
+    get_global_parallelism,
+    linalg::{
+        matmul::matmul,
+        solvers::{Qr, ThinSvd},
+        triangular_solve::solve_upper_triangular_in_place,
+    },
+    mat::{Mat, MatRef},
+    ComplexField, RealField,
+};
+
+/// This error signifies that the null spaces of the matrix and of the regularization operator
+/// have a nontrivial intersection, so that the regularized problem has no unique solution.
+#[derive(Debug, Clone, Copy)]
+pub struct RegularizationRankError;
+
+impl core::fmt::Display for RegularizationRankError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for RegularizationRankError {}
+
+/// Decomposition of a regularized least squares problem, which can be solved for any value of
+/// the regularization parameter.
+#[derive(Clone, Debug)]
+pub struct Ridge<E: ComplexField> {
+    // x(λ) = T diag(c / (c² + λ s²)) U^H b
+    u: Mat<E>,
+    c: Col<E::Real>,
+    s: Col<E::Real>,
+    t: Mat<E>,
+}
+
+impl<E: ComplexField> Ridge<E> {
+    /// Returns the decomposition of the problem $\min_x \|Ax - b\|^2 + \lambda \|x\|^2$, where $A$
+    /// is `matrix`.
+    #[track_caller]
+    pub fn new(matrix: MatRef<'_, E>) -> Self {
+        let svd = ThinSvd::<E>::new(matrix);
+        let r = svd.s_diagonal().nrows();
+
+        Self {
+            u: svd.u().to_owned(),
+            c: Col::from_fn(r, |i| svd.s_diagonal().read(i).faer_real()),
+            s: Col::from_fn(r, |_| E::Real::faer_one()),
+            t: svd.v().to_owned(),
+        }
+    }
+
+    /// Returns the decomposition of the problem $\min_x \|Ax - b\|^2 + \lambda \|Lx\|^2$, where $A$
+    /// is `matrix` and $L$ is `regularization`.
+    ///
+    /// # Errors
+    /// Returns an error if the stacked matrix $\begin{bmatrix} A \\\\ L \end{bmatrix}$ is rank
+    /// deficient, i.e., if some nonzero $x$ satisfies both $Ax = 0$ and $Lx = 0$.
+    ///
+    /// # Panics
+    /// Panics if `matrix` and `regularization` don't have the same number of columns.
+    #[track_caller]
+    pub fn new_with_regularization(
+        matrix: MatRef<'_, E>,
+        regularization: MatRef<'_, E>,
+    ) -> Result<Self, RegularizationRankError> {
+        assert!(matrix.ncols() == regularization.ncols());
+
+        let m = matrix.nrows();
+        let p = regularization.nrows();
+        let n = matrix.ncols();
+        let parallelism = get_global_parallelism();
+
+        if m + p < n {
+            return Err(RegularizationRankError);
+        }
+
+        let mut stacked = Mat::<E>::zeros(m + p, n);
+        stacked.as_mut().subrows_mut(0, m).copy_from(matrix);
+        stacked.as_mut().subrows_mut(m, p).copy_from(regularization);
+
+        let qr = Qr::<E>::new(stacked.as_ref());
+        let factor_r = qr.compute_thin_r();
+
+        let mut max_diag = E::Real::faer_zero();
+        for i in 0..n {
+            let d = factor_r.read(i, i).faer_abs();
+            if d > max_diag {
+                max_diag = d;
+            }
+        }
+        let threshold = E::Real::faer_epsilon()
+            .faer_mul(E::Real::faer_from_f64(n as f64))
+            .faer_mul(max_diag);
+        for i in 0..n {
+            if factor_r.read(i, i).faer_abs() <= threshold {
+                return Err(RegularizationRankError);
+            }
+        }
+
+        let q = qr.compute_thin_q();
+        let (q1, q2) = q.as_ref().split_at_row(m);
+
+        let svd = ThinSvd::<E>::new(q1);
+        let r = svd.s_diagonal().nrows();
+        let w = svd.v().subcols(0, r);
+
+        // computing s from Q2 W rather than as sqrt(1 - c²) keeps its relative accuracy when c is
+        // close to one
+        let mut q2w = Mat::<E>::zeros(p, r);
+        matmul(q2w.as_mut(), q2, w, None, E::faer_one(), parallelism);
+
+        let mut t = w.to_owned();
+        solve_upper_triangular_in_place(factor_r.as_ref(), t.as_mut(), parallelism);
+
+        Ok(Self {
+            u: svd.u().to_owned(),
+            c: Col::from_fn(r, |i| svd.s_diagonal().read(i).faer_real()),
+            s: Col::from_fn(r, |i| q2w.as_ref().col(i).norm_l2()),
+            t,
+        })
+    }
+
+    /// Returns the number of rows of the matrix.
+    pub fn nrows(&self) -> usize {
+        self.u.nrows()
+    }
+
+    /// Returns the number of columns of the matrix.
+    pub fn ncols(&self) -> usize {
+        self.t.nrows()
+    }
+
+    /// Returns the generalized singular value pairs $(c_i, s_i)$ of the problem, as two columns.
+    ///
+    /// In standard form, $c_i$ are the singular values of the matrix and $s_i = 1$. Otherwise,
+    /// $c_i^2 + s_i^2 = 1$, and the generalized singular values are $c_i / s_i$.
+    pub fn generalized_singular_values(&self) -> (Col<E::Real>, Col<E::Real>) {
+        (self.c.clone(), self.s.clone())
+    }
+
+    /// Computes the regularized solution for the regularization parameter `lambda`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the matrix.
+    #[track_caller]
+    pub fn solve(&self, rhs: MatRef<'_, E>, lambda: E::Real) -> Mat<E> {
+        let uhb = self.project_rhs(rhs);
+        self.solve_projected(uhb.as_ref(), lambda)
+    }
+
+    /// Computes the regularized solutions for each of the regularization parameters in `lambdas`.
+    ///
+    /// The right-hand side is projected only once, so that each additional parameter only costs
+    /// a matrix multiplication.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the matrix.
+    #[track_caller]
+    pub fn solve_many(&self, rhs: MatRef<'_, E>, lambdas: &[E::Real]) -> alloc::vec::Vec<Mat<E>> {
+        let uhb = self.project_rhs(rhs);
+        lambdas
+            .iter()
+            .map(|&lambda| self.solve_projected(uhb.as_ref(), lambda))
+            .collect()
+    }
+
+    #[track_caller]
+    fn project_rhs(&self, rhs: MatRef<'_, E>) -> Mat<E> {
+        assert!(rhs.nrows() == self.nrows());
+        let mut uhb = Mat::<E>::zeros(self.u.ncols(), rhs.ncols());
+        matmul(
+            uhb.as_mut(),
+            self.u.adjoint(),
+            rhs,
+            None,
+            E::faer_one(),
+            get_global_parallelism(),
+        );
+        uhb
+    }
+
+    fn solve_projected(&self, uhb: MatRef<'_, E>, lambda: E::Real) -> Mat<E> {
+        let r = self.c.nrows();
+        let k = uhb.ncols();
+
+        let mut z = uhb.to_owned();
+        for i in 0..r {
+            let c = self.c.read(i);
+            let s = self.s.read(i);
+            let denom = c.faer_mul(c).faer_add(lambda.faer_mul(s.faer_mul(s)));
+            let factor = if denom == E::Real::faer_zero() {
+                E::Real::faer_zero()
+            } else {
+                c.faer_div(denom)
+            };
+            for j in 0..k {
+                z.write(i, j, z.read(i, j).faer_scale_real(factor));
+            }
+        }
+
+        let mut x = Mat::<E>::zeros(self.ncols(), k);
+        matmul(
+            x.as_mut(),
+            self.t.as_ref(),
+            z.as_ref(),
+            None,
+            E::faer_one(),
+            get_global_parallelism(),
+        );
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, linalg::solvers::SpSolverLstsq};
+
+    fn random_mat(m: usize, n: usize) -> Mat<c64> {
+        Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()))
+    }
+
+    // residual of the normal equations (A^H A + λ L^H L) x = A^H b
+    fn normal_residual(a: &Mat<c64>, l: &Mat<c64>, b: &Mat<c64>, x: &Mat<c64>, lambda: f64) -> f64 {
+        let data = a.adjoint() * (a * x);
+        let reg = l.adjoint() * (l * x);
+        let lhs = Mat::from_fn(x.nrows(), x.ncols(), |i, j| {
+            data.read(i, j) + reg.read(i, j) * lambda
+        });
+        (lhs - a.adjoint() * b).norm_max()
+    }
+
+    #[test]
+    fn test_ridge_standard_form() {
+        let m = 8;
+        let n = 5;
+        let a = random_mat(m, n);
+        let b = random_mat(m, 2);
+        let identity = Mat::<c64>::identity(n, n);
+
+        let lambdas = [0.0, 1e-3, 0.5, 10.0];
+        let ridge = Ridge::new(a.as_ref());
+        let xs = ridge.solve_many(b.as_ref(), &lambdas);
+
+        for (x, &lambda) in xs.iter().zip(lambdas.iter()) {
+            assert!(normal_residual(&a, &identity, &b, x, lambda) < 1e-10);
+            let single = ridge.solve(b.as_ref(), lambda);
+            assert!((&single - x).norm_max() < 1e-14);
+        }
+
+        // λ = 0 is the least squares solution
+        let lstsq = a.col_piv_qr().solve_lstsq(&b);
+        assert!((&xs[0] - lstsq).norm_max() < 1e-10);
+    }
+
+    #[test]
+    fn test_ridge_general_form() {
+        let m = 4;
+        let n = 6;
+        let a = random_mat(m, n);
+        let b = random_mat(m, 3);
+
+        // first order finite differences, whose null space is spanned by the constant vector
+        let l = Mat::<c64>::from_fn(n - 1, n, |i, j| {
+            if j == i {
+                c64::new(-1.0, 0.0)
+            } else if j == i + 1 {
+                c64::new(1.0, 0.0)
+            } else {
+                c64::new(0.0, 0.0)
+            }
+        });
+
+        let ridge = Ridge::new_with_regularization(a.as_ref(), l.as_ref()).unwrap();
+        let (c, s) = ridge.generalized_singular_values();
+        for i in 0..c.nrows() {
+            assert!((c.read(i) * c.read(i) + s.read(i) * s.read(i) - 1.0).abs() < 1e-12);
+        }
+
+        let lambdas = [1e-2, 1.0, 1e2];
+        let xs = ridge.solve_many(b.as_ref(), &lambdas);
+        for (x, &lambda) in xs.iter().zip(lambdas.iter()) {
+            assert!(normal_residual(&a, &l, &b, x, lambda) < 1e-9);
+        }
+
+        // an invertible regularization operator gives the same result as standard form after a
+        // change of variables, and the identity gives exactly the standard form
+        let identity = Mat::<c64>::identity(n, n);
+        let general = Ridge::new_with_regularization(a.as_ref(), identity.as_ref()).unwrap();
+        let standard = Ridge::new(a.as_ref());
+        for &lambda in &lambdas {
+            let x0 = general.solve(b.as_ref(), lambda);
+            let x1 = standard.solve(b.as_ref(), lambda);
+            assert!((x0 - x1).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_ridge_rank_error() {
+        let n = 4;
+        let mut a = Mat::<f64>::from_fn(6, n, |i, j| ((i * 7 + j * 3) % 5) as f64);
+        let mut l = Mat::<f64>::identity(n, n);
+        a.col_mut(2).fill(0.0);
+        l.write(2, 2, 0.0);
+
+        assert!(Ridge::new_with_regularization(a.as_ref(), l.as_ref()).is_err());
+    }
+}
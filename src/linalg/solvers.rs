@@ -53,6 +53,7 @@ pub struct Lblt<E: Entity> {
 }
 
 /// LU decomposition with partial pivoting.
+#[derive(Clone, Debug)]
 pub struct PartialPivLu<E: Entity> {
     pub(crate) factors: Mat<E>,
     row_perm: alloc::vec::Vec<usize>,
@@ -82,6 +83,20 @@ pub struct ColPivQr<E: Entity> {
     col_perm_inv: alloc::vec::Vec<usize>,
 }
 
+/// QR decomposition with explicitly stored factors, which can be updated when a row or column
+/// is inserted into or deleted from the original matrix.
+pub struct QrUpdate<E: Entity> {
+    q: Mat<E>,
+    r: Mat<E>,
+}
+
+/// Bidiagonal decomposition.
+pub struct Bidiag<E: Entity> {
+    factors: Mat<E>,
+    householder_left: Mat<E>,
+    householder_right: Mat<E>,
+}
+
 /// Singular value decomposition.
 pub struct Svd<E: Entity> {
     s: Mat<E>,
@@ -99,6 +114,28 @@ pub struct SelfAdjointEigendecomposition<E: Entity> {
     u: Mat<E>,
 }
 
+/// Partial self-adjoint eigendecomposition, containing only some of the eigenpairs.
+pub struct PartialSelfAdjointEigendecomposition<E: Entity> {
+    s: Col<E>,
+    u: Mat<E>,
+}
+
+/// Partial singular value decomposition, containing only some of the singular triplets.
+pub struct PartialSvd<E: Entity> {
+    s: Col<E>,
+    u: Mat<E>,
+    v: Mat<E>,
+}
+
+/// Specifies which end of the spectrum is computed by a partial decomposition.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Which {
+    /// The largest values, in nonincreasing order.
+    Largest,
+    /// The smallest values, in nondecreasing order.
+    Smallest,
+}
+
 /// Complex eigendecomposition.
 pub struct Eigendecomposition<E: Entity> {
     s: Col<E>,
@@ -263,6 +300,19 @@ impl<E: ComplexField> Lblt<E> {
     /// The matrix is interpreted as Hermitian, but only the provided side is accessed.
     #[track_caller]
     pub fn new<ViewE: Conjugate<Canonical = E>>(matrix: MatRef<'_, ViewE>, side: Side) -> Self {
+        Self::new_with_params(matrix, side, Default::default())
+    }
+
+    /// Returns the Bunch-Kaufman factorization of the input matrix, computed with the given
+    /// parameters, e.g., to select rook pivoting.
+    ///
+    /// The matrix is interpreted as Hermitian, but only the provided side is accessed.
+    #[track_caller]
+    pub fn new_with_params<ViewE: Conjugate<Canonical = E>>(
+        matrix: MatRef<'_, ViewE>,
+        side: Side,
+        params: crate::linalg::cholesky::bunch_kaufman::compute::BunchKaufmanParams,
+    ) -> Self {
         assert!(matrix.nrows() == matrix.ncols());
 
         let dim = matrix.nrows();
@@ -288,8 +338,6 @@ impl<E: ComplexField> Lblt<E> {
             }
         }
 
-        let params = Default::default();
-
         crate::linalg::cholesky::bunch_kaufman::compute::cholesky_in_place(
             factors.as_mut(),
             subdiag.as_mut(),
@@ -318,6 +366,15 @@ impl<E: ComplexField> Lblt<E> {
     fn dim(&self) -> usize {
         self.factors.nrows()
     }
+
+    /// Returns the inertia of the original matrix, i.e., the number of its positive, negative and
+    /// zero eigenvalues.
+    pub fn inertia(&self) -> crate::linalg::cholesky::Inertia {
+        crate::linalg::cholesky::bunch_kaufman::inertia(
+            self.factors.as_ref(),
+            self.subdiag.as_ref(),
+        )
+    }
 }
 
 impl<E: ComplexField> SpSolverCore<E> for Lblt<E> {
@@ -1164,6 +1221,71 @@ impl<E: ComplexField> ColPivQr<E> {
     pub fn compute_thin_q(&self) -> Mat<E> {
         Qr::<E>::__compute_q_impl(self.factors.as_ref(), self.householder.as_ref(), true)
     }
+
+    /// Returns the numerical rank of the input matrix, i.e., the number of leading diagonal
+    /// elements of $R$ whose absolute value is greater than `tolerance` times the absolute value
+    /// of the first one.
+    pub fn rank(&self, tolerance: E::Real) -> usize {
+        let size = Ord::min(self.nrows(), self.ncols());
+        if size == 0 {
+            return 0;
+        }
+
+        let threshold = tolerance.faer_mul(self.factors.read(0, 0).faer_abs());
+        let mut rank = 0;
+        while rank < size && self.factors.read(rank, rank).faer_abs() > threshold {
+            rank += 1;
+        }
+        rank
+    }
+
+    /// Returns an orthonormal basis of the column space of the input matrix, made of the leftmost
+    /// [`rank(tolerance)`](Self::rank) columns of $Q$.
+    pub fn column_space(&self, tolerance: E::Real) -> Mat<E> {
+        let rank = self.rank(tolerance);
+        self.compute_thin_q().as_ref().subcols(0, rank).to_owned()
+    }
+
+    /// Returns an orthonormal basis of the null space of the input matrix, with
+    /// `ncols - rank(tolerance)` columns (see [`rank`](Self::rank)).
+    ///
+    /// Writing $R = \begin{bmatrix} R_{11} & R_{12} \\ 0 & 0 \end{bmatrix}$ with $R_{11}$ of
+    /// size $r \times r$, the columns of $P^\top \begin{bmatrix} -R_{11}^{-1} R_{12} \\ I
+    /// \end{bmatrix}$ span the null space, and are orthonormalized with a second QR
+    /// decomposition.
+    pub fn null_space(&self, tolerance: E::Real) -> Mat<E> {
+        let n = self.ncols();
+        let rank = self.rank(tolerance);
+        let nullity = n - rank;
+        if nullity == 0 {
+            return Mat::<E>::zeros(n, 0);
+        }
+
+        let parallelism = get_global_parallelism();
+        let r = self.factors.as_ref();
+
+        let mut basis = Mat::<E>::zeros(n, nullity);
+        {
+            let (mut top, bot) = basis.as_mut().split_at_row_mut(rank);
+            zipped!(top.rb_mut(), r.submatrix(0, rank, rank, nullity))
+                .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_neg()));
+            crate::linalg::triangular_solve::solve_upper_triangular_in_place(
+                r.submatrix(0, 0, rank, rank),
+                top,
+                parallelism,
+            );
+            bot.diagonal_mut().column_vector_mut().fill(E::faer_one());
+        }
+
+        let mut permuted = Mat::<E>::zeros(n, nullity);
+        crate::perm::permute_rows(
+            permuted.as_mut(),
+            basis.as_ref(),
+            self.col_permutation().inverse(),
+        );
+
+        Qr::<E>::new(permuted.as_ref()).compute_thin_q()
+    }
 }
 impl<E: ComplexField> SpSolverCore<E> for ColPivQr<E> {
     #[track_caller]
@@ -1283,6 +1405,489 @@ impl<E: ComplexField> SpSolverLstsqCore<E> for ColPivQr<E> {
 }
 impl<E: ComplexField> SolverLstsqCore<E> for ColPivQr<E> {}
 
+impl<E: ComplexField> QrUpdate<E> {
+    /// Returns the QR decomposition of the input matrix without pivoting, with its factors stored
+    /// explicitly so that it can be updated.
+    ///
+    /// The factorization is such that $A = QR$, where $R$ is upper trapezoidal and $Q$ is unitary.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(matrix: MatRef<'_, ViewE>) -> Self {
+        let qr = Qr::new(matrix);
+        Self {
+            q: qr.compute_q(),
+            r: qr.compute_r(),
+        }
+    }
+
+    /// Returns the factor $Q$ of the QR decomposition.
+    pub fn q(&self) -> MatRef<'_, E> {
+        self.q.as_ref()
+    }
+
+    /// Returns the factor $R$ of the QR decomposition.
+    pub fn r(&self) -> MatRef<'_, E> {
+        self.r.as_ref()
+    }
+
+    /// Updates the decomposition after `row` is inserted into the original matrix at position
+    /// `row_idx`.
+    ///
+    /// # Panics
+    /// Panics if `row_idx > self.nrows()`, or if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn insert_row(&mut self, row_idx: usize, row: RowRef<'_, E>) {
+        let m = self.r.nrows();
+        let n = self.r.ncols();
+        assert!(all(row_idx <= m, row.ncols() == n));
+
+        self.q.resize_with(m + 1, m + 1, |_, _| E::faer_zero());
+        self.r.resize_with(m + 1, n, |_, _| E::faer_zero());
+        crate::linalg::qr::update::insert_row(self.q.as_mut(), self.r.as_mut(), row_idx, row);
+    }
+
+    /// Updates the decomposition after `row` is appended to the original matrix.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn append_row(&mut self, row: RowRef<'_, E>) {
+        self.insert_row(self.r.nrows(), row);
+    }
+
+    /// Updates the decomposition after the row at position `row_idx` is deleted from the original
+    /// matrix.
+    ///
+    /// # Panics
+    /// Panics if `row_idx >= self.nrows()`.
+    #[track_caller]
+    pub fn delete_row(&mut self, row_idx: usize) {
+        let m = self.r.nrows();
+        let n = self.r.ncols();
+        assert!(row_idx < m);
+
+        crate::linalg::qr::update::delete_row(
+            self.q.as_mut(),
+            self.r.as_mut(),
+            row_idx,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::qr::update::delete_row_req::<E>(m, n).unwrap(),
+            )),
+        );
+        self.q.truncate(m - 1, m - 1);
+        self.r.truncate(m - 1, n);
+    }
+
+    /// Updates the decomposition after `col` is inserted into the original matrix at position
+    /// `col_idx`.
+    ///
+    /// # Panics
+    /// Panics if `col_idx > self.ncols()`, or if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn insert_col(&mut self, col_idx: usize, col: ColRef<'_, E>) {
+        let m = self.r.nrows();
+        let n = self.r.ncols();
+        assert!(all(col_idx <= n, col.nrows() == m));
+
+        self.r.resize_with(m, n + 1, |_, _| E::faer_zero());
+        crate::linalg::qr::update::insert_col(
+            self.q.as_mut(),
+            self.r.as_mut(),
+            col_idx,
+            col,
+            get_global_parallelism(),
+        );
+    }
+
+    /// Updates the decomposition after `col` is appended to the original matrix.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn append_col(&mut self, col: ColRef<'_, E>) {
+        self.insert_col(self.r.ncols(), col);
+    }
+
+    /// Updates the decomposition after the column at position `col_idx` is deleted from the
+    /// original matrix.
+    ///
+    /// # Panics
+    /// Panics if `col_idx >= self.ncols()`.
+    #[track_caller]
+    pub fn delete_col(&mut self, col_idx: usize) {
+        let m = self.r.nrows();
+        let n = self.r.ncols();
+        assert!(col_idx < n);
+
+        crate::linalg::qr::update::delete_col(self.q.as_mut(), self.r.as_mut(), col_idx);
+        self.r.truncate(m, n - 1);
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for QrUpdate<E> {
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        assert!(self.nrows() == self.ncols());
+        self.solve_lstsq_in_place_with_conj_impl(rhs, conj);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        assert!(self.nrows() == self.ncols());
+
+        let parallelism = get_global_parallelism();
+        let mut rhs = rhs;
+
+        // Aᵀ = Rᵀ Qᵀ, so x = conj(Q) R⁻ᵀ b
+        crate::linalg::triangular_solve::solve_lower_triangular_in_place_with_conj(
+            self.r.as_ref().transpose(),
+            conj,
+            rhs.rb_mut(),
+            parallelism,
+        );
+        let tmp = rhs.to_owned();
+        crate::linalg::matmul::matmul_with_conj(
+            rhs,
+            self.q.as_ref(),
+            conj.compose(Conj::Yes),
+            tmp.as_ref(),
+            Conj::No,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+
+    fn nrows(&self) -> usize {
+        self.r.nrows()
+    }
+
+    fn ncols(&self) -> usize {
+        self.r.ncols()
+    }
+}
+impl<E: ComplexField> SolverCore<E> for QrUpdate<E> {
+    fn reconstruct(&self) -> Mat<E> {
+        &self.q * &self.r
+    }
+
+    fn inverse(&self) -> Mat<E> {
+        assert!(self.nrows() == self.ncols());
+
+        let mut inv = Mat::<E>::identity(self.nrows(), self.ncols());
+        self.solve_in_place_with_conj_impl(inv.as_mut(), Conj::No);
+        inv
+    }
+}
+
+impl<E: ComplexField> SpSolverLstsqCore<E> for QrUpdate<E> {
+    #[track_caller]
+    fn solve_lstsq_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let parallelism = get_global_parallelism();
+        let n = self.ncols();
+        let mut rhs = rhs;
+
+        // A = QR, so the least squares solution is the top part of R⁻¹ Qᴴ b
+        let tmp = rhs.to_owned();
+        crate::linalg::matmul::matmul_with_conj(
+            rhs.rb_mut(),
+            self.q.as_ref().transpose(),
+            conj.compose(Conj::Yes),
+            tmp.as_ref(),
+            Conj::No,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        crate::linalg::triangular_solve::solve_upper_triangular_in_place_with_conj(
+            self.r.as_ref().subrows(0, n),
+            conj,
+            rhs.subrows_mut(0, n),
+            parallelism,
+        );
+    }
+}
+impl<E: ComplexField> SolverLstsqCore<E> for QrUpdate<E> {}
+
+impl<E: ComplexField> Bidiag<E> {
+    /// Returns the bidiagonal decomposition of the input matrix.
+    ///
+    /// The decomposition is such that $A = U B V^H$, where $U$ and $V$ are unitary, and $B$ is
+    /// upper bidiagonal with the same dimensions as $A$. $U$ and $V$ are represented as sequences
+    /// of Householder reflections, which can be applied to a matrix with
+    /// [`Self::apply_u_in_place`] and [`Self::apply_v_in_place`], or formed explicitly.
+    ///
+    /// # Panics
+    /// Panics if the input matrix has fewer rows than columns. The bidiagonal decomposition of
+    /// its transpose may be used instead.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(matrix: MatRef<'_, ViewE>) -> Self {
+        let parallelism = get_global_parallelism();
+        let m = matrix.nrows();
+        let n = matrix.ncols();
+        assert!(m >= n);
+
+        let mut factors = matrix.to_owned();
+        let blocksize = crate::linalg::qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
+        let mut householder_left = Mat::<E>::zeros(blocksize, n);
+        let mut householder_right = Mat::<E>::zeros(blocksize, n.saturating_sub(1));
+
+        crate::linalg::svd::bidiag::bidiagonalize_in_place(
+            factors.as_mut(),
+            householder_left
+                .as_mut()
+                .row_mut(0)
+                .transpose_mut()
+                .as_2d_mut(),
+            householder_right
+                .as_mut()
+                .row_mut(0)
+                .transpose_mut()
+                .as_2d_mut(),
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::svd::bidiag::bidiagonalize_in_place_req::<E>(m, n, parallelism)
+                    .unwrap(),
+            )),
+        );
+
+        let factors_ref = factors.as_ref();
+        let mut j_base = 0;
+        while j_base < n {
+            let bs = Ord::min(blocksize, n - j_base);
+            let mut householder = householder_left.as_mut().submatrix_mut(0, j_base, bs, bs);
+            let essentials = factors_ref.submatrix(j_base, j_base, m - j_base, bs);
+            for j in 0..bs {
+                householder.write(j, j, householder.read(0, j));
+            }
+            crate::linalg::householder::upgrade_householder_factor(
+                householder,
+                essentials,
+                bs,
+                1,
+                parallelism,
+            );
+            j_base += bs;
+        }
+        let mut j_base = 0;
+        while j_base + 1 < n {
+            let bs = Ord::min(blocksize, n - 1 - j_base);
+            let mut householder = householder_right.as_mut().submatrix_mut(0, j_base, bs, bs);
+            let full_essentials = factors_ref.submatrix(0, 1, m, n - 1).transpose();
+            let essentials = full_essentials.submatrix(j_base, j_base, n - 1 - j_base, bs);
+            for j in 0..bs {
+                householder.write(j, j, householder.read(0, j));
+            }
+            crate::linalg::householder::upgrade_householder_factor(
+                householder,
+                essentials,
+                bs,
+                1,
+                parallelism,
+            );
+            j_base += bs;
+        }
+
+        Self {
+            factors,
+            householder_left,
+            householder_right,
+        }
+    }
+
+    fn blocksize(&self) -> usize {
+        self.householder_left.nrows()
+    }
+
+    /// Returns the number of rows of the decomposed matrix.
+    pub fn nrows(&self) -> usize {
+        self.factors.nrows()
+    }
+
+    /// Returns the number of columns of the decomposed matrix.
+    pub fn ncols(&self) -> usize {
+        self.factors.ncols()
+    }
+
+    /// Returns the diagonal of the factor $B$.
+    pub fn diagonal(&self) -> Col<E> {
+        Col::from_fn(self.ncols(), |i| self.factors.read(i, i))
+    }
+
+    /// Returns the superdiagonal of the factor $B$.
+    pub fn superdiagonal(&self) -> Col<E> {
+        Col::from_fn(self.ncols().saturating_sub(1), |i| {
+            self.factors.read(i, i + 1)
+        })
+    }
+
+    /// Returns the top $n$ rows of the factor $B$, where $n$ is the number of columns of the
+    /// decomposed matrix. The remaining rows are zero.
+    pub fn compute_b(&self) -> Mat<E> {
+        let n = self.ncols();
+        Mat::from_fn(n, n, |i, j| {
+            if i == j || i + 1 == j {
+                self.factors.read(i, j)
+            } else {
+                E::faer_zero()
+            }
+        })
+    }
+
+    fn apply_u_impl(&self, rhs: MatMut<'_, E>, adjoint: bool) {
+        let parallelism = get_global_parallelism();
+        let m = self.nrows();
+        let n = self.ncols();
+        assert!(rhs.nrows() == m);
+        if n == 0 {
+            return;
+        }
+
+        let stack_req = if adjoint {
+            crate::linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_req::<E>(
+                m,
+                self.blocksize(),
+                rhs.ncols(),
+            )
+        } else {
+            crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                m,
+                self.blocksize(),
+                rhs.ncols(),
+            )
+        };
+        let stack = PodStack::new(&mut GlobalPodBuffer::new(stack_req.unwrap()));
+
+        if adjoint {
+            crate::linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+                self.factors.as_ref(),
+                self.householder_left.as_ref(),
+                Conj::Yes,
+                rhs,
+                parallelism,
+                stack,
+            );
+        } else {
+            crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+                self.factors.as_ref(),
+                self.householder_left.as_ref(),
+                Conj::No,
+                rhs,
+                parallelism,
+                stack,
+            );
+        }
+    }
+
+    fn apply_v_impl(&self, rhs: MatMut<'_, E>, adjoint: bool) {
+        let parallelism = get_global_parallelism();
+        let m = self.nrows();
+        let n = self.ncols();
+        assert!(rhs.nrows() == n);
+        // the first row is left untouched by the right reflections
+        if n <= 1 {
+            return;
+        }
+
+        let essentials = self.factors.as_ref().submatrix(0, 1, m, n - 1).transpose();
+        let rhs = rhs.subrows_mut(1, n - 1);
+
+        let stack_req = if adjoint {
+            crate::linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_req::<E>(
+                n - 1,
+                self.blocksize(),
+                rhs.ncols(),
+            )
+        } else {
+            crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                n - 1,
+                self.blocksize(),
+                rhs.ncols(),
+            )
+        };
+        let stack = PodStack::new(&mut GlobalPodBuffer::new(stack_req.unwrap()));
+
+        if adjoint {
+            crate::linalg::householder::apply_block_householder_sequence_transpose_on_the_left_in_place_with_conj(
+                essentials,
+                self.householder_right.as_ref(),
+                Conj::Yes,
+                rhs,
+                parallelism,
+                stack,
+            );
+        } else {
+            crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+                essentials,
+                self.householder_right.as_ref(),
+                Conj::No,
+                rhs,
+                parallelism,
+                stack,
+            );
+        }
+    }
+
+    /// Computes $U \times M$, where $M$ is `rhs`, and stores the result in `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the decomposed matrix.
+    #[track_caller]
+    pub fn apply_u_in_place(&self, rhs: MatMut<'_, E>) {
+        self.apply_u_impl(rhs, false);
+    }
+
+    /// Computes $U^H \times M$, where $M$ is `rhs`, and stores the result in `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the decomposed matrix.
+    #[track_caller]
+    pub fn apply_u_adjoint_in_place(&self, rhs: MatMut<'_, E>) {
+        self.apply_u_impl(rhs, true);
+    }
+
+    /// Computes $V \times M$, where $M$ is `rhs`, and stores the result in `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the number of columns of the
+    /// decomposed matrix.
+    #[track_caller]
+    pub fn apply_v_in_place(&self, rhs: MatMut<'_, E>) {
+        self.apply_v_impl(rhs, false);
+    }
+
+    /// Computes $V^H \times M$, where $M$ is `rhs`, and stores the result in `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have the same number of rows as the number of columns of the
+    /// decomposed matrix.
+    #[track_caller]
+    pub fn apply_v_adjoint_in_place(&self, rhs: MatMut<'_, E>) {
+        self.apply_v_impl(rhs, true);
+    }
+
+    /// Returns the factor $U$ of the bidiagonal decomposition.
+    pub fn compute_u(&self) -> Mat<E> {
+        let mut u = Mat::<E>::identity(self.nrows(), self.nrows());
+        self.apply_u_in_place(u.as_mut());
+        u
+    }
+
+    /// Returns the first $n$ columns of the factor $U$ of the bidiagonal decomposition, where
+    /// $n$ is the number of columns of the decomposed matrix.
+    pub fn compute_thin_u(&self) -> Mat<E> {
+        let mut u = Mat::<E>::identity(self.nrows(), self.ncols());
+        self.apply_u_in_place(u.as_mut());
+        u
+    }
+
+    /// Returns the factor $V$ of the bidiagonal decomposition.
+    pub fn compute_v(&self) -> Mat<E> {
+        let mut v = Mat::<E>::identity(self.ncols(), self.ncols());
+        self.apply_v_in_place(v.as_mut());
+        v
+    }
+}
+
 impl<E: ComplexField> Svd<E> {
     #[track_caller]
     fn __new_impl((matrix, conj): (MatRef<'_, E>, Conj), thin: bool) -> Self {
@@ -1361,6 +1966,35 @@ impl<E: ComplexField> Svd<E> {
             self.v(),
         )
     }
+
+    /// Returns the numerical rank of the input matrix, i.e., the number of singular values that
+    /// are greater than `tolerance` times the largest singular value.
+    pub fn rank(&self, tolerance: E::Real) -> usize {
+        let s = self.s_diagonal();
+        if s.nrows() == 0 {
+            return 0;
+        }
+
+        let threshold = tolerance.faer_mul(s.read(0).faer_real());
+        (0..s.nrows())
+            .filter(|&i| s.read(i).faer_real() > threshold)
+            .count()
+    }
+
+    /// Returns an orthonormal basis of the column space of the input matrix, made of the leftmost
+    /// [`rank(tolerance)`](Self::rank) columns of $U$.
+    pub fn column_space(&self, tolerance: E::Real) -> Mat<E> {
+        let rank = self.rank(tolerance);
+        self.u().subcols(0, rank).to_owned()
+    }
+
+    /// Returns an orthonormal basis of the null space of the input matrix, made of the rightmost
+    /// `ncols - rank(tolerance)` columns of $V$ (see [`rank`](Self::rank)).
+    pub fn null_space(&self, tolerance: E::Real) -> Mat<E> {
+        let rank = self.rank(tolerance);
+        let n = self.v.ncols();
+        self.v().subcols(rank, n - rank).to_owned()
+    }
 }
 
 fn div_by_s<E: ComplexField>(rhs: MatMut<'_, E>, s: MatRef<'_, E>) {
@@ -1658,7 +2292,385 @@ impl<E: ComplexField> SolverCore<E> for SelfAdjointEigendecomposition<E> {
             u.read(i, j).faer_mul(s.read(j, 0).faer_inv())
         });
 
-        us_inv * u.adjoint()
+        us_inv * u.adjoint()
+    }
+}
+
+impl<E: ComplexField> PartialSelfAdjointEigendecomposition<E> {
+    #[track_caller]
+    fn __new_impl(
+        (matrix, conj): (MatRef<'_, E>, Conj),
+        side: Side,
+        k: usize,
+        which: Which,
+    ) -> Self {
+        let n = matrix.nrows();
+        assert!(all(matrix.ncols() == n, k <= n));
+        let parallelism = get_global_parallelism();
+
+        let matrix = match side {
+            Side::Lower => matrix,
+            Side::Upper => matrix.transpose(),
+        };
+        let conj = conj.compose(match side {
+            Side::Lower => Conj::No,
+            Side::Upper => Conj::Yes,
+        });
+
+        let mut s = Col::<E>::zeros(k);
+        let mut u = Mat::<E>::zeros(n, k);
+        if k == 0 {
+            return Self { s, u };
+        }
+
+        let mut trid = Mat::<E>::zeros(n, n);
+        zipped!(trid.as_mut(), matrix).for_each_triangular_lower(
+            crate::linalg::zip::Diag::Include,
+            |unzipped!(mut dst, src)| dst.write(src.read()),
+        );
+        let blocksize =
+            crate::linalg::qr::no_pivoting::compute::recommended_blocksize::<E>(n - 1, n - 1);
+        let mut householder = Mat::<E>::zeros(blocksize, n - 1);
+
+        crate::linalg::evd::tridiag::tridiagonalize_in_place(
+            trid.as_mut(),
+            householder.as_mut().transpose_mut(),
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::evd::tridiag::tridiagonalize_in_place_req::<E>(n, parallelism)
+                    .unwrap(),
+            )),
+        );
+        let trid = trid.as_ref();
+
+        // the subdiagonal is made real by scaling the rows and columns of the tridiagonal matrix
+        // by a diagonal unitary matrix
+        let diag = Col::<E::Real>::from_fn(n, |i| trid.read(i, i).faer_real());
+        let offdiag = Col::<E::Real>::from_fn(n - 1, |i| trid.read(i + 1, i).faer_abs());
+        let normalized = |x: E| {
+            if x == E::faer_zero() {
+                E::faer_one()
+            } else {
+                x.faer_scale_real(x.faer_abs().faer_inv())
+            }
+        };
+        let mut mul = Col::<E>::zeros(n);
+        let mut x = E::faer_one();
+        mul.write(0, x);
+        for i in 1..n {
+            x = normalized(trid.read(i, i - 1).faer_mul(x.faer_conj())).faer_conj();
+            mul.write(i, x.faer_conj());
+        }
+
+        let mut eigenvalues = Col::<E::Real>::zeros(n);
+        crate::linalg::evd::compute_tridiag_evd(
+            diag.as_ref(),
+            offdiag.as_ref(),
+            eigenvalues.as_mut(),
+            None,
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::evd::compute_tridiag_evd_req::<E::Real>(
+                    n,
+                    crate::linalg::evd::ComputeVectors::No,
+                    parallelism,
+                )
+                .unwrap(),
+            )),
+        );
+        let selected = Col::<E::Real>::from_fn(k, |j| match which {
+            Which::Largest => eigenvalues.read(n - 1 - j),
+            Which::Smallest => eigenvalues.read(j),
+        });
+
+        let mut z = Mat::<E::Real>::zeros(n, k);
+        crate::linalg::evd::compute_tridiag_eigenvectors(
+            diag.as_ref(),
+            offdiag.as_ref(),
+            selected.as_ref(),
+            z.as_mut(),
+            E::Real::faer_epsilon(),
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::evd::compute_tridiag_eigenvectors_req::<E::Real>(n, k).unwrap(),
+            )),
+        );
+
+        for j in 0..k {
+            s.write(j, E::faer_from_real(selected.read(j)));
+            for i in 0..n {
+                u.write(i, j, mul.read(i).faer_scale_real(z.read(i, j)));
+            }
+        }
+
+        if n > 1 {
+            let mut j_base = 0;
+            while j_base < n - 1 {
+                let bs = Ord::min(blocksize, n - 1 - j_base);
+                let mut householder = householder.as_mut().submatrix_mut(0, j_base, bs, bs);
+                let full_essentials = trid.submatrix(1, 0, n - 1, n);
+                let essentials = full_essentials.submatrix(j_base, j_base, n - 1 - j_base, bs);
+                for j in 0..bs {
+                    householder.write(j, j, householder.read(0, j));
+                }
+                crate::linalg::householder::upgrade_householder_factor(
+                    householder,
+                    essentials,
+                    bs,
+                    1,
+                    parallelism,
+                );
+                j_base += bs;
+            }
+
+            crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+                trid.submatrix(1, 0, n - 1, n - 1),
+                householder.as_ref(),
+                Conj::No,
+                u.as_mut().subrows_mut(1, n - 1),
+                parallelism,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    crate::linalg::householder::apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                        n - 1,
+                        blocksize,
+                        k,
+                    )
+                    .unwrap(),
+                )),
+            );
+        }
+
+        if matches!(conj, Conj::Yes) {
+            zipped!(u.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_conj()));
+        }
+
+        Self { s, u }
+    }
+
+    /// Returns the `k` largest or smallest eigenvalues of the Hermitian input matrix, as
+    /// specified by `which`, along with the corresponding eigenvectors.
+    ///
+    /// The factorization is such that $A U = U S$, where $S$ is a `k×k` diagonal matrix, and $U$
+    /// has orthonormal columns. The matrix is reduced to tridiagonal form, but the eigenvectors
+    /// are only computed for the selected eigenvalues, which avoids accumulating the full
+    /// eigenvector matrix.
+    ///
+    /// Only the provided side is accessed.
+    ///
+    /// # Panics
+    /// Panics if the matrix is not square, or if `k` is larger than its dimension.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(
+        matrix: MatRef<'_, ViewE>,
+        side: Side,
+        k: usize,
+        which: Which,
+    ) -> Self {
+        Self::__new_impl(matrix.canonicalize(), side, k, which)
+    }
+
+    /// Returns the factor $U$ of the partial eigenvalue decomposition.
+    pub fn u(&self) -> MatRef<'_, E> {
+        self.u.as_ref()
+    }
+    /// Returns the factor $S$ of the partial eigenvalue decomposition.
+    pub fn s(&self) -> DiagRef<'_, E> {
+        self.s.as_ref().column_vector_as_diagonal()
+    }
+}
+
+impl<E: ComplexField> PartialSvd<E> {
+    #[track_caller]
+    fn __new_impl(bidiag: Bidiag<E>, k: usize, which: Which) -> Self {
+        let m = bidiag.nrows();
+        let n = bidiag.ncols();
+        assert!(k <= n);
+        let parallelism = get_global_parallelism();
+
+        let mut s = Col::<E>::zeros(k);
+        let mut u = Mat::<E>::zeros(m, k);
+        let mut v = Mat::<E>::zeros(n, k);
+        if k == 0 {
+            return Self { s, u, v };
+        }
+
+        // B = D1 Br D2ᴴ, where D1 and D2 are diagonal and unitary, and Br is real and nonnegative
+        let d = bidiag.diagonal();
+        let e = bidiag.superdiagonal();
+        let normalized = |x: E| {
+            if x == E::faer_zero() {
+                E::faer_one()
+            } else {
+                x.faer_scale_real(x.faer_abs().faer_inv())
+            }
+        };
+        let mut left = Col::<E>::zeros(n);
+        let mut right = Col::<E>::zeros(n);
+        right.write(0, E::faer_one());
+        for i in 0..n {
+            left.write(i, normalized(d.read(i).faer_mul(right.read(i))));
+            if i + 1 < n {
+                right.write(
+                    i + 1,
+                    normalized(left.read(i).faer_conj().faer_mul(e.read(i))).faer_conj(),
+                );
+            }
+        }
+
+        // the eigenvalues of the Golub-Kahan tridiagonal matrix, with zero diagonal and
+        // subdiagonal (d0, e0, d1, e1, ...), are the singular values of Br and their opposites.
+        // the eigenvector of σ is (v0, u0, v1, u1, ...) / √2
+        let diag = Col::<E::Real>::zeros(2 * n);
+        let offdiag = Col::<E::Real>::from_fn(2 * n - 1, |i| {
+            if i % 2 == 0 {
+                d.read(i / 2).faer_abs()
+            } else {
+                e.read(i / 2).faer_abs()
+            }
+        });
+
+        let mut eigenvalues = Col::<E::Real>::zeros(2 * n);
+        crate::linalg::evd::compute_tridiag_evd(
+            diag.as_ref(),
+            offdiag.as_ref(),
+            eigenvalues.as_mut(),
+            None,
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::evd::compute_tridiag_evd_req::<E::Real>(
+                    2 * n,
+                    crate::linalg::evd::ComputeVectors::No,
+                    parallelism,
+                )
+                .unwrap(),
+            )),
+        );
+        let selected = Col::<E::Real>::from_fn(k, |j| match which {
+            Which::Largest => eigenvalues.read(2 * n - 1 - j),
+            Which::Smallest => eigenvalues.read(n + j),
+        });
+
+        let mut z = Mat::<E::Real>::zeros(2 * n, k);
+        crate::linalg::evd::compute_tridiag_eigenvectors(
+            diag.as_ref(),
+            offdiag.as_ref(),
+            selected.as_ref(),
+            z.as_mut(),
+            E::Real::faer_epsilon(),
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::evd::compute_tridiag_eigenvectors_req::<E::Real>(2 * n, k).unwrap(),
+            )),
+        );
+
+        let mut u_r = Mat::<E::Real>::from_fn(n, k, |i, j| z.read(2 * i + 1, j));
+        let mut v_r = Mat::<E::Real>::from_fn(n, k, |i, j| z.read(2 * i, j));
+        for j in 0..k {
+            let sigma = selected.read(j);
+            s.write(j, E::faer_from_real(sigma.faer_abs()));
+            // the eigenvector of -σ is (v0, -u0, v1, -u1, ...), which can only be selected for
+            // σ close to zero
+            if sigma < E::Real::faer_zero() {
+                for i in 0..n {
+                    u_r.write(i, j, u_r.read(i, j).faer_neg());
+                }
+            }
+            normalize_or_complete(u_r.as_mut(), j);
+            normalize_or_complete(v_r.as_mut(), j);
+        }
+
+        for j in 0..k {
+            for i in 0..n {
+                u.write(i, j, left.read(i).faer_scale_real(u_r.read(i, j)));
+                v.write(i, j, right.read(i).faer_scale_real(v_r.read(i, j)));
+            }
+        }
+        bidiag.apply_u_in_place(u.as_mut());
+        bidiag.apply_v_in_place(v.as_mut());
+
+        Self { s, u, v }
+    }
+
+    /// Returns the `k` largest or smallest singular values of the input matrix, as specified by
+    /// `which`, along with the corresponding left and right singular vectors.
+    ///
+    /// The factorization is such that $A V = U S$, where $S$ is a `k×k` diagonal matrix with
+    /// nonnegative entries, and $U$ and $V$ have orthonormal columns. The matrix is reduced to
+    /// bidiagonal form, but the singular vectors are only computed for the selected singular
+    /// values, which avoids accumulating the full singular vector matrices.
+    ///
+    /// # Panics
+    /// Panics if `k` is larger than the minimum of the number of rows and columns of the matrix.
+    #[track_caller]
+    pub fn new<ViewE: Conjugate<Canonical = E>>(
+        matrix: MatRef<'_, ViewE>,
+        k: usize,
+        which: Which,
+    ) -> Self {
+        if matrix.nrows() >= matrix.ncols() {
+            Self::__new_impl(Bidiag::new(matrix), k, which)
+        } else {
+            // A = U S Vᴴ if and only if Aᴴ = V S Uᴴ
+            let Self { s, u, v } = Self::__new_impl(Bidiag::new(matrix.adjoint()), k, which);
+            Self { s, u: v, v: u }
+        }
+    }
+
+    /// Returns the factor $U$ of the partial SVD.
+    pub fn u(&self) -> MatRef<'_, E> {
+        self.u.as_ref()
+    }
+    /// Returns the factor $S$ of the partial SVD.
+    pub fn s(&self) -> DiagRef<'_, E> {
+        self.s.as_ref().column_vector_as_diagonal()
+    }
+    /// Returns the factor $V$ of the partial SVD.
+    pub fn v(&self) -> MatRef<'_, E> {
+        self.v.as_ref()
+    }
+}
+
+/// Normalizes the column `j` of `mat`, or replaces it with a unit vector orthogonal to the
+/// previous columns if it is too small to be normalized reliably. This happens for the half of
+/// the eigenvector of the Golub-Kahan matrix that corresponds to a zero singular value.
+fn normalize_or_complete<E: RealField>(mut mat: MatMut<'_, E>, j: usize) {
+    let n = mat.nrows();
+    let half = E::faer_from_f64(0.5);
+
+    let norm = mat.as_ref().col(j).norm_l2();
+    // the expected norm is 1/√2
+    if norm > half.faer_mul(half) {
+        let norm_inv = norm.faer_inv();
+        for i in 0..n {
+            mat.write(i, j, mat.read(i, j).faer_mul(norm_inv));
+        }
+        return;
+    }
+
+    for c in 0..n {
+        for i in 0..n {
+            mat.write(
+                i,
+                j,
+                if i == c {
+                    E::faer_one()
+                } else {
+                    E::faer_zero()
+                },
+            );
+        }
+        for p in 0..j {
+            let dot = mat.read(c, p);
+            for i in 0..n {
+                mat.write(i, j, mat.read(i, j).faer_sub(dot.faer_mul(mat.read(i, p))));
+            }
+        }
+        let norm = mat.as_ref().col(j).norm_l2();
+        if norm > half {
+            let norm_inv = norm.faer_inv();
+            for i in 0..n {
+                mat.write(i, j, mat.read(i, j).faer_mul(norm_inv));
+            }
+            return;
+        }
     }
 }
 
@@ -2067,6 +3079,24 @@ where
         SelfAdjointEigendecomposition::<E::Canonical>::new(self.as_ref(), side)
     }
 
+    /// Returns the `k` largest or smallest eigenpairs of `self`, as specified by `which`,
+    /// assuming it is self-adjoint. Only the provided side is accessed.
+    #[track_caller]
+    pub fn partial_selfadjoint_eigendecomposition(
+        &self,
+        side: Side,
+        k: usize,
+        which: Which,
+    ) -> PartialSelfAdjointEigendecomposition<E::Canonical> {
+        PartialSelfAdjointEigendecomposition::<E::Canonical>::new(self.as_ref(), side, k, which)
+    }
+
+    /// Returns the `k` largest or smallest singular triplets of `self`, as specified by `which`.
+    #[track_caller]
+    pub fn partial_svd(&self, k: usize, which: Which) -> PartialSvd<E::Canonical> {
+        PartialSvd::<E::Canonical>::new(self.as_ref(), k, which)
+    }
+
     /// Returns the eigendecomposition of `self`, as a complex matrix.
     #[track_caller]
     pub fn eigendecomposition<
@@ -2112,6 +3142,61 @@ where
         }
     }
 
+    /// Returns `self` raised to the power `k`, computed by binary exponentiation.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    #[track_caller]
+    pub fn matrix_power(&self, k: u32) -> Mat<E::Canonical> {
+        assert!(self.nrows() == self.ncols());
+        let dim = self.nrows();
+        let parallelism = get_global_parallelism();
+
+        let mut out = Mat::<E::Canonical>::zeros(dim, dim);
+        crate::linalg::matrix_power::matrix_power(
+            out.as_mut(),
+            *self,
+            k,
+            parallelism,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                crate::linalg::matrix_power::matrix_power_req::<E::Canonical>(dim, parallelism)
+                    .unwrap(),
+            )),
+        );
+        out
+    }
+
+    /// Returns `self` raised to the power `k`, assuming it is self-adjoint. Only the provided
+    /// side is accessed.
+    ///
+    /// The power is computed from the eigendecomposition of `self`, which allows negative
+    /// exponents, in which case `self` must be invertible.
+    #[track_caller]
+    pub fn selfadjoint_matrix_power(&self, side: Side, k: i32) -> Mat<E::Canonical> {
+        let evd = self.selfadjoint_eigendecomposition(side);
+        let u = evd.u();
+        let s = evd.s().column_vector();
+        let dim = u.nrows();
+        let parallelism = get_global_parallelism();
+
+        let pow: alloc::vec::Vec<_> = (0..dim)
+            .map(|j| crate::linalg::matrix_power::powi(s.read(j).faer_real(), k))
+            .collect();
+        let scaled =
+            Mat::<E::Canonical>::from_fn(dim, dim, |i, j| u.read(i, j).faer_scale_real(pow[j]));
+
+        let mut out = Mat::<E::Canonical>::zeros(dim, dim);
+        crate::linalg::matmul::matmul(
+            out.as_mut(),
+            scaled.as_ref(),
+            u.adjoint(),
+            None,
+            E::Canonical::faer_one(),
+            parallelism,
+        );
+        out
+    }
+
     /// Returns the eigenvalues of `self`, assuming it is self-adjoint. Only the provided
     /// side is accessed. The order of the eigenvalues is currently unspecified.
     #[track_caller]
@@ -2353,6 +3438,25 @@ where
         self.as_ref().selfadjoint_eigendecomposition(side)
     }
 
+    /// Returns the `k` largest or smallest eigenpairs of `self`, as specified by `which`,
+    /// assuming it is self-adjoint. Only the provided side is accessed.
+    #[track_caller]
+    pub fn partial_selfadjoint_eigendecomposition(
+        &self,
+        side: Side,
+        k: usize,
+        which: Which,
+    ) -> PartialSelfAdjointEigendecomposition<E::Canonical> {
+        self.as_ref()
+            .partial_selfadjoint_eigendecomposition(side, k, which)
+    }
+
+    /// Returns the `k` largest or smallest singular triplets of `self`, as specified by `which`.
+    #[track_caller]
+    pub fn partial_svd(&self, k: usize, which: Which) -> PartialSvd<E::Canonical> {
+        self.as_ref().partial_svd(k, which)
+    }
+
     /// Returns the eigendecomposition of `self`, as a complex matrix.
     #[track_caller]
     pub fn eigendecomposition<
@@ -2375,6 +3479,25 @@ where
         self.as_ref().determinant()
     }
 
+    /// Returns `self` raised to the power `k`, computed by binary exponentiation.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    #[track_caller]
+    pub fn matrix_power(&self, k: u32) -> Mat<E::Canonical> {
+        self.as_ref().matrix_power(k)
+    }
+
+    /// Returns `self` raised to the power `k`, assuming it is self-adjoint. Only the provided
+    /// side is accessed.
+    ///
+    /// The power is computed from the eigendecomposition of `self`, which allows negative
+    /// exponents, in which case `self` must be invertible.
+    #[track_caller]
+    pub fn selfadjoint_matrix_power(&self, side: Side, k: i32) -> Mat<E::Canonical> {
+        self.as_ref().selfadjoint_matrix_power(side, k)
+    }
+
     /// Returns the eigenvalues of `self`, assuming it is self-adjoint. Only the provided
     /// side is accessed. The order of the eigenvalues is currently unspecified.
     #[track_caller]
@@ -2550,6 +3673,25 @@ where
         self.as_ref().selfadjoint_eigendecomposition(side)
     }
 
+    /// Returns the `k` largest or smallest eigenpairs of `self`, as specified by `which`,
+    /// assuming it is self-adjoint. Only the provided side is accessed.
+    #[track_caller]
+    pub fn partial_selfadjoint_eigendecomposition(
+        &self,
+        side: Side,
+        k: usize,
+        which: Which,
+    ) -> PartialSelfAdjointEigendecomposition<E::Canonical> {
+        self.as_ref()
+            .partial_selfadjoint_eigendecomposition(side, k, which)
+    }
+
+    /// Returns the `k` largest or smallest singular triplets of `self`, as specified by `which`.
+    #[track_caller]
+    pub fn partial_svd(&self, k: usize, which: Which) -> PartialSvd<E::Canonical> {
+        self.as_ref().partial_svd(k, which)
+    }
+
     /// Returns the eigendecomposition of `self`, as a complex matrix.
     #[track_caller]
     pub fn eigendecomposition<
@@ -2572,6 +3714,25 @@ where
         self.as_ref().determinant()
     }
 
+    /// Returns `self` raised to the power `k`, computed by binary exponentiation.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square.
+    #[track_caller]
+    pub fn matrix_power(&self, k: u32) -> Mat<E::Canonical> {
+        self.as_ref().matrix_power(k)
+    }
+
+    /// Returns `self` raised to the power `k`, assuming it is self-adjoint. Only the provided
+    /// side is accessed.
+    ///
+    /// The power is computed from the eigendecomposition of `self`, which allows negative
+    /// exponents, in which case `self` must be invertible.
+    #[track_caller]
+    pub fn selfadjoint_matrix_power(&self, side: Side, k: i32) -> Mat<E::Canonical> {
+        self.as_ref().selfadjoint_matrix_power(side, k)
+    }
+
     /// Returns the eigenvalues of `self`, assuming it is self-adjoint. Only the provided
     /// side is accessed. The order of the eigenvalues is currently unspecified.
     #[track_caller]
@@ -2606,6 +3767,457 @@ where
     }
 }
 
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::*;
+    use ::serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    // the factorizations are serialized as their stored factors, with the inverse permutations
+    // omitted since they are recomputed on deserialization
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Cholesky")]
+    struct CholeskyRepr<M> {
+        factors: M,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Lblt")]
+    struct LbltRepr<M, P> {
+        factors: M,
+        subdiag: M,
+        perm: P,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "PartialPivLu")]
+    struct PartialPivLuRepr<M, P> {
+        factors: M,
+        row_perm: P,
+        n_transpositions: usize,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "FullPivLu")]
+    struct FullPivLuRepr<M, P> {
+        factors: M,
+        row_perm: P,
+        col_perm: P,
+        n_transpositions: usize,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Qr")]
+    struct QrRepr<M> {
+        factors: M,
+        householder: M,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "ColPivQr")]
+    struct ColPivQrRepr<M, P> {
+        factors: M,
+        householder: M,
+        col_perm: P,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "Svd")]
+    struct SvdRepr<M> {
+        s: M,
+        u: M,
+        v: M,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "ThinSvd")]
+    struct ThinSvdRepr<M> {
+        s: M,
+        u: M,
+        v: M,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename = "SelfAdjointEigendecomposition")]
+    struct SelfAdjointEigendecompositionRepr<M> {
+        s: M,
+        u: M,
+    }
+
+    fn check<Err: Error>(cond: bool, msg: &'static str) -> Result<(), Err> {
+        if cond {
+            Ok(())
+        } else {
+            Err(Err::custom(msg))
+        }
+    }
+
+    fn is_square<E: Entity>(mat: &Mat<E>) -> bool {
+        mat.nrows() == mat.ncols()
+    }
+
+    fn check_householder<E: Entity, Err: Error>(
+        factors: &Mat<E>,
+        householder: &Mat<E>,
+    ) -> Result<(), Err> {
+        let size = Ord::min(factors.nrows(), factors.ncols());
+        check(
+            householder.ncols() == size && (size == 0 || (1..=size).contains(&householder.nrows())),
+            "invalid householder factor dimensions",
+        )
+    }
+
+    fn inverse_of<Err: Error>(perm: &[usize], len: usize) -> Result<alloc::vec::Vec<usize>, Err> {
+        check(perm.len() == len, "invalid permutation length")?;
+        crate::serde::perm::inverse_of(perm)
+    }
+
+    impl<E: Entity + Serialize> Serialize for Cholesky<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            CholeskyRepr {
+                factors: self.factors.as_ref(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for Cholesky<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = CholeskyRepr::<Mat<E>>::deserialize(d)?;
+            check(is_square(&repr.factors), "the factor must be square")?;
+            Ok(Self {
+                factors: repr.factors,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for Lblt<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            LbltRepr {
+                factors: self.factors.as_ref(),
+                subdiag: self.subdiag.as_ref(),
+                perm: &*self.perm,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for Lblt<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = LbltRepr::<Mat<E>, alloc::vec::Vec<usize>>::deserialize(d)?;
+            let dim = repr.factors.nrows();
+            check(is_square(&repr.factors), "the factor must be square")?;
+            check(
+                (repr.subdiag.nrows(), repr.subdiag.ncols()) == (dim, 1),
+                "invalid subdiagonal dimensions",
+            )?;
+            let perm_inv = inverse_of(&repr.perm, dim)?;
+            Ok(Self {
+                factors: repr.factors,
+                subdiag: repr.subdiag,
+                perm: repr.perm,
+                perm_inv,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for PartialPivLu<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            PartialPivLuRepr {
+                factors: self.factors.as_ref(),
+                row_perm: &*self.row_perm,
+                n_transpositions: self.n_transpositions,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for PartialPivLu<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = PartialPivLuRepr::<Mat<E>, alloc::vec::Vec<usize>>::deserialize(d)?;
+            check(is_square(&repr.factors), "the factors must be square")?;
+            let row_perm_inv = inverse_of(&repr.row_perm, repr.factors.nrows())?;
+            Ok(Self {
+                factors: repr.factors,
+                row_perm: repr.row_perm,
+                row_perm_inv,
+                n_transpositions: repr.n_transpositions,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for FullPivLu<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            FullPivLuRepr {
+                factors: self.factors.as_ref(),
+                row_perm: &*self.row_perm,
+                col_perm: &*self.col_perm,
+                n_transpositions: self.n_transpositions,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for FullPivLu<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = FullPivLuRepr::<Mat<E>, alloc::vec::Vec<usize>>::deserialize(d)?;
+            let row_perm_inv = inverse_of(&repr.row_perm, repr.factors.nrows())?;
+            let col_perm_inv = inverse_of(&repr.col_perm, repr.factors.ncols())?;
+            Ok(Self {
+                factors: repr.factors,
+                row_perm: repr.row_perm,
+                row_perm_inv,
+                col_perm: repr.col_perm,
+                col_perm_inv,
+                n_transpositions: repr.n_transpositions,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for Qr<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            QrRepr {
+                factors: self.factors.as_ref(),
+                householder: self.householder.as_ref(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for Qr<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = QrRepr::<Mat<E>>::deserialize(d)?;
+            check_householder(&repr.factors, &repr.householder)?;
+            Ok(Self {
+                factors: repr.factors,
+                householder: repr.householder,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for ColPivQr<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            ColPivQrRepr {
+                factors: self.factors.as_ref(),
+                householder: self.householder.as_ref(),
+                col_perm: &*self.col_perm,
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for ColPivQr<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = ColPivQrRepr::<Mat<E>, alloc::vec::Vec<usize>>::deserialize(d)?;
+            check_householder(&repr.factors, &repr.householder)?;
+            let col_perm_inv = inverse_of(&repr.col_perm, repr.factors.ncols())?;
+            Ok(Self {
+                factors: repr.factors,
+                householder: repr.householder,
+                col_perm: repr.col_perm,
+                col_perm_inv,
+            })
+        }
+    }
+
+    // checks the dimensions of the factors of an svd, where the singular vectors are either
+    // square, or have one column per singular value
+    fn check_svd<E: Entity, Err: Error>(
+        s: &Mat<E>,
+        u: &Mat<E>,
+        v: &Mat<E>,
+        thin: bool,
+    ) -> Result<(), Err> {
+        let size = Ord::min(u.nrows(), v.nrows());
+        let (u_ncols, v_ncols) = if thin {
+            (size, size)
+        } else {
+            (u.nrows(), v.nrows())
+        };
+        check(
+            (s.nrows(), s.ncols()) == (size, 1) && u.ncols() == u_ncols && v.ncols() == v_ncols,
+            "invalid singular value decomposition dimensions",
+        )
+    }
+
+    impl<E: Entity + Serialize> Serialize for Svd<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            SvdRepr {
+                s: self.s.as_ref(),
+                u: self.u.as_ref(),
+                v: self.v.as_ref(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for Svd<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = SvdRepr::<Mat<E>>::deserialize(d)?;
+            check_svd(&repr.s, &repr.u, &repr.v, false)?;
+            Ok(Self {
+                s: repr.s,
+                u: repr.u,
+                v: repr.v,
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for ThinSvd<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            ThinSvdRepr {
+                s: self.inner.s.as_ref(),
+                u: self.inner.u.as_ref(),
+                v: self.inner.v.as_ref(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for ThinSvd<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = ThinSvdRepr::<Mat<E>>::deserialize(d)?;
+            check_svd(&repr.s, &repr.u, &repr.v, true)?;
+            Ok(Self {
+                inner: Svd {
+                    s: repr.s,
+                    u: repr.u,
+                    v: repr.v,
+                },
+            })
+        }
+    }
+
+    impl<E: Entity + Serialize> Serialize for SelfAdjointEigendecomposition<E> {
+        fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+            SelfAdjointEigendecompositionRepr {
+                s: self.s.as_ref(),
+                u: self.u.as_ref(),
+            }
+            .serialize(s)
+        }
+    }
+
+    impl<'de, E: Entity + Deserialize<'de>> Deserialize<'de> for SelfAdjointEigendecomposition<E> {
+        fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+            let repr = SelfAdjointEigendecompositionRepr::<Mat<E>>::deserialize(d)?;
+            let dim = repr.u.nrows();
+            check(
+                is_square(&repr.u) && (repr.s.nrows(), repr.s.ncols()) == (dim, 1),
+                "invalid eigendecomposition dimensions",
+            )?;
+            Ok(Self {
+                s: repr.s,
+                u: repr.u,
+            })
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use serde_test::{assert_de_tokens_error, assert_ser_tokens, Token};
+
+        #[test]
+        fn test_serde_factorizations() {
+            let a = mat![[4.0f64]];
+            assert_ser_tokens(
+                &a.partial_piv_lu(),
+                &[
+                    Token::Struct {
+                        name: "PartialPivLu",
+                        len: 3,
+                    },
+                    Token::Str("factors"),
+                    Token::Struct {
+                        name: "Mat",
+                        len: 3,
+                    },
+                    Token::Str("nrows"),
+                    Token::U64(1),
+                    Token::Str("ncols"),
+                    Token::U64(1),
+                    Token::Str("data"),
+                    Token::Seq { len: Some(1) },
+                    Token::F64(4.0),
+                    Token::SeqEnd,
+                    Token::StructEnd,
+                    Token::Str("row_perm"),
+                    Token::Seq { len: Some(1) },
+                    Token::U64(0),
+                    Token::SeqEnd,
+                    Token::Str("n_transpositions"),
+                    Token::U64(0),
+                    Token::StructEnd,
+                ],
+            );
+
+            // invalid permutations are rejected
+            assert_de_tokens_error::<PartialPivLu<f64>>(
+                &[
+                    Token::Struct {
+                        name: "PartialPivLu",
+                        len: 3,
+                    },
+                    Token::Str("factors"),
+                    Token::Struct {
+                        name: "Mat",
+                        len: 3,
+                    },
+                    Token::Str("nrows"),
+                    Token::U64(2),
+                    Token::Str("ncols"),
+                    Token::U64(2),
+                    Token::Str("data"),
+                    Token::Seq { len: Some(4) },
+                    Token::F64(1.0),
+                    Token::F64(0.0),
+                    Token::F64(0.0),
+                    Token::F64(1.0),
+                    Token::SeqEnd,
+                    Token::StructEnd,
+                    Token::Str("row_perm"),
+                    Token::Seq { len: Some(2) },
+                    Token::U64(1),
+                    Token::U64(1),
+                    Token::SeqEnd,
+                    Token::Str("n_transpositions"),
+                    Token::U64(0),
+                    Token::StructEnd,
+                ],
+                "invalid permutation",
+            );
+
+            // the factors must have consistent dimensions
+            assert_de_tokens_error::<Cholesky<f64>>(
+                &[
+                    Token::Struct {
+                        name: "Cholesky",
+                        len: 1,
+                    },
+                    Token::Str("factors"),
+                    Token::Struct {
+                        name: "Mat",
+                        len: 3,
+                    },
+                    Token::Str("nrows"),
+                    Token::U64(1),
+                    Token::Str("ncols"),
+                    Token::U64(2),
+                    Token::Str("data"),
+                    Token::Seq { len: Some(2) },
+                    Token::F64(1.0),
+                    Token::F64(0.0),
+                    Token::SeqEnd,
+                    Token::StructEnd,
+                    Token::StructEnd,
+                ],
+                "the factor must be square",
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2806,6 +4418,142 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_qr_update() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let H = Mat::from_fn(9, 5, random);
+        let mut qr = QrUpdate::new(H.as_ref());
+        assert_approx_eq(qr.reconstruct(), &H);
+        test_solver_lstsq(&H, &qr);
+
+        // sliding window: drop the oldest row and append a new one
+        let row = Mat::from_fn(1, 5, random);
+        qr.delete_row(0);
+        qr.append_row(row.as_ref().row(0));
+        let H = Mat::from_fn(9, 5, |i, j| {
+            if i < 8 {
+                H.read(i + 1, j)
+            } else {
+                row.read(0, j)
+            }
+        });
+        assert_approx_eq(qr.q() * qr.r(), &H);
+        test_solver_lstsq(&H, &qr);
+
+        let col = Mat::from_fn(9, 1, random);
+        qr.delete_col(2);
+        qr.insert_col(0, col.as_ref().col(0));
+        let H = Mat::from_fn(9, 5, |i, j| match j {
+            0 => col.read(i, 0),
+            1 | 2 => H.read(i, j - 1),
+            _ => H.read(i, j),
+        });
+        assert_approx_eq(qr.reconstruct(), &H);
+        test_solver_lstsq(&H, &qr);
+
+        let H = Mat::from_fn(7, 7, random);
+        let mut qr = QrUpdate::new(H.as_ref());
+        qr.append_col(Mat::from_fn(7, 1, random).as_ref().col(0));
+        qr.delete_col(7);
+        test_solver(&H, &qr);
+    }
+
+    #[test]
+    fn test_bidiag() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        for (m, n) in [(0, 0), (1, 1), (5, 1), (7, 5), (7, 7), (100, 60)] {
+            let H = Mat::from_fn(m, n, random);
+            let bidiag = Bidiag::new(H.as_ref());
+
+            let u = bidiag.compute_u();
+            let v = bidiag.compute_v();
+            assert_approx_eq(u.adjoint() * &u, Mat::<c64>::identity(m, m));
+            assert_approx_eq(v.adjoint() * &v, Mat::<c64>::identity(n, n));
+            assert_approx_eq(
+                bidiag.compute_thin_u() * bidiag.compute_b() * v.adjoint(),
+                &H,
+            );
+
+            let b = bidiag.compute_b();
+            for j in 0..n {
+                assert!(b.read(j, j) == bidiag.diagonal().read(j));
+                if j + 1 < n {
+                    assert!(b.read(j, j + 1) == bidiag.superdiagonal().read(j));
+                }
+            }
+
+            let mut rhs = Mat::from_fn(m, 3, random);
+            let copy = rhs.clone();
+            bidiag.apply_u_adjoint_in_place(rhs.as_mut());
+            assert_approx_eq(&rhs, u.adjoint() * &copy);
+            bidiag.apply_u_in_place(rhs.as_mut());
+            assert_approx_eq(&rhs, &copy);
+
+            let mut rhs = Mat::from_fn(n, 3, random);
+            let copy = rhs.clone();
+            bidiag.apply_v_adjoint_in_place(rhs.as_mut());
+            assert_approx_eq(&rhs, v.adjoint() * &copy);
+            bidiag.apply_v_in_place(rhs.as_mut());
+            assert_approx_eq(&rhs, &copy);
+        }
+    }
+
+    #[test]
+    fn test_partial_selfadjoint_eigendecomposition() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        for n in [1, 2, 7, 40] {
+            let H = Mat::from_fn(n, n, random);
+            let H = &H + H.adjoint();
+            let mut eigenvalues = H.selfadjoint_eigenvalues(Side::Lower);
+            eigenvalues.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            let k = Ord::min(n, 3);
+            for (which, side) in [
+                (Which::Largest, Side::Lower),
+                (Which::Smallest, Side::Upper),
+            ] {
+                let evd = H.partial_selfadjoint_eigendecomposition(side, k, which);
+                let u = evd.u();
+                let s = evd.s().column_vector();
+                for j in 0..k {
+                    let expected = match which {
+                        Which::Largest => eigenvalues[n - 1 - j],
+                        Which::Smallest => eigenvalues[j],
+                    };
+                    assert!((s.read(j).re - expected).abs() < 1e-10);
+                }
+                assert_approx_eq(&H * u, u * evd.s());
+                assert_approx_eq(u.adjoint() * u, Mat::<c64>::identity(k, k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_svd() {
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        for (m, n) in [(1, 1), (8, 5), (5, 8), (40, 30)] {
+            let H = Mat::from_fn(m, n, random);
+            let singular_values = H.singular_values();
+            let size = Ord::min(m, n);
+
+            let k = Ord::min(size, 3);
+            for which in [Which::Largest, Which::Smallest] {
+                let svd = H.partial_svd(k, which);
+                let s = svd.s().column_vector();
+                for j in 0..k {
+                    let expected = match which {
+                        Which::Largest => singular_values[j],
+                        Which::Smallest => singular_values[size - 1 - j],
+                    };
+                    assert!((s.read(j).re - expected).abs() < 1e-10);
+                }
+                assert_approx_eq(&H * svd.v(), svd.u() * svd.s());
+                assert_approx_eq(svd.u().adjoint() * svd.u(), Mat::<c64>::identity(k, k));
+                assert_approx_eq(svd.v().adjoint() * svd.v(), Mat::<c64>::identity(k, k));
+            }
+        }
+    }
+
     #[test]
     fn test_col_piv_qr() {
         let n = 7;
@@ -2955,6 +4703,90 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_null_space() {
+        let m = 6;
+        let n = 5;
+        let rank = 3;
+
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let B = Mat::from_fn(m, rank, random);
+        let C = Mat::from_fn(rank, n, random);
+        let A = &B * &C;
+        let tol = 1e-10;
+
+        let check = |range: Mat<c64>, null: Mat<c64>| {
+            assert!(range.nrows() == m);
+            assert!(range.ncols() == rank);
+            assert!(null.nrows() == n);
+            assert!(null.ncols() == n - rank);
+
+            assert_approx_eq(range.adjoint() * &range, Mat::<c64>::identity(rank, rank));
+            assert_approx_eq(
+                null.adjoint() * &null,
+                Mat::<c64>::identity(n - rank, n - rank),
+            );
+            assert_approx_eq(&range * (range.adjoint() * &A), &A);
+            assert_approx_eq(&A * &null, Mat::<c64>::zeros(m, n - rank));
+        };
+
+        let svd = A.svd();
+        assert!(svd.rank(tol) == rank);
+        check(svd.column_space(tol), svd.null_space(tol));
+
+        let qr = A.col_piv_qr();
+        assert!(qr.rank(tol) == rank);
+        check(qr.column_space(tol), qr.null_space(tol));
+
+        let full = A.adjoint().svd();
+        assert!(full.rank(tol) == rank);
+        assert!(full.null_space(tol).ncols() == m - rank);
+
+        let zero = Mat::<c64>::zeros(m, n);
+        assert!(zero.svd().rank(tol) == 0);
+        assert!(zero.col_piv_qr().rank(tol) == 0);
+        assert!(zero.col_piv_qr().null_space(tol).ncols() == n);
+    }
+
+    #[test]
+    fn test_matrix_power() {
+        let n = 7;
+
+        let random = |_, _| c64::new(rand::random(), rand::random());
+        let R = Mat::from_fn(n, n, random);
+        let RRH = &R * R.adjoint();
+        let H = Mat::from_fn(n, n, |i, j| {
+            let diag = if i == j { 1.0 } else { 0.0 };
+            RRH.read(i, j) * (1.0 / n as f64) + c64::new(diag, 0.0)
+        });
+
+        assert_approx_eq(H.matrix_power(0), Mat::<c64>::identity(n, n));
+        assert_approx_eq(H.matrix_power(1), &H);
+        assert_approx_eq(H.matrix_power(5), &H * &H * &H * &H * &H);
+        assert_approx_eq(R.matrix_power(4), &R * &R * &R * &R);
+        assert_approx_eq(
+            R.conjugate().matrix_power(3),
+            R.conjugate().to_owned() * R.conjugate() * R.conjugate(),
+        );
+
+        assert_approx_eq(
+            H.selfadjoint_matrix_power(Side::Lower, 0),
+            Mat::<c64>::identity(n, n),
+        );
+        assert_approx_eq(
+            H.selfadjoint_matrix_power(Side::Lower, 3),
+            H.matrix_power(3),
+        );
+        assert_approx_eq(
+            H.selfadjoint_matrix_power(Side::Upper, 3),
+            H.matrix_power(3),
+        );
+        assert_approx_eq(
+            H.selfadjoint_matrix_power(Side::Lower, -2) * H.matrix_power(2),
+            Mat::<c64>::identity(n, n),
+        );
+    }
+
     #[test]
     fn test_eigendecomposition() {
         let n = 7;
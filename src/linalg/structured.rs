@@ -0,0 +1,1031 @@
+//! Solvers for linear systems with Toeplitz and circulant structure.
+//!
+//! A Toeplitz matrix is constant along its diagonals, and is determined by its first row and
+//! column. [`toeplitz_solve_in_place`] solves systems with a Hermitian Toeplitz matrix using the
+//! Levinson recursion in $\mathcal{O}(n^2)$ operations, and [`levinson_durbin`] solves the
+//! Yule-Walker equations that arise when fitting autoregressive models.
+//!
+//! A circulant matrix is a Toeplitz matrix where each column is a cyclic shift of the previous
+//! one. It is diagonalized by the discrete Fourier transform, so that products and solves with
+//! [`Circulant`] take $\mathcal{O}(n \log n)$ operations. The same idea gives a fast product
+//! with a general Toeplitz matrix in [`toeplitz_matmul`].
+//!
+//! A block-diagonal matrix is stored as a list of dense blocks in [`BlockDiag`]. Its products
+//! and the solves with its LU factorization [`BlockDiagLu`] are computed block by block, with the
+//! blocks processed in parallel.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{col, linalg::structured::Circulant, mat, prelude::*};
+//!
+//! let c = Circulant::new(col![4.0, 1.0, 0.0, 1.0f64].as_ref());
+//! let b = mat![[1.0], [2.0], [3.0], [4.0]];
+//! let x = c.solve(&b);
+//!
+//! assert!((&c.to_dense() * &x - &b).norm_max() < 1e-12);
+//! ```
+
+use crate::{
+    assert,
+    col::{Col, ColMut, ColRef},
+    linalg::{
+        fft::{convolve, join_parts, split_parts, Fft, FftDirection, NativeFft},
+        matmul::matmul_with_conj,
+        solvers::PartialPivLu,
+        temp_mat_req, temp_mat_uninit,
+    },
+    linop::{BiLinOp, LinOp},
+    mat::{Mat, MatMut, MatRef},
+    sparse::linalg::solvers::SpSolverCore,
+    utils::thread::for_each_raw,
+    ComplexField, Conj, Entity, Parallelism,
+};
+use alloc::{vec, vec::Vec};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// This error signifies that the Levinson recursion could not be completed due to a singular
+/// top-left corner of the Toeplitz matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct ToeplitzError {
+    /// The dimension of the first singular top-left corner of the matrix.
+    pub singular_minor: usize,
+}
+
+impl core::fmt::Display for ToeplitzError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for ToeplitzError {}
+
+/// Computes the size and alignment of required workspace for solving a Hermitian Toeplitz
+/// system with [`toeplitz_solve_in_place`].
+pub fn toeplitz_solve_in_place_req<E: Entity>(
+    dim: usize,
+    rhs_ncols: usize,
+) -> Result<StackReq, SizeOverflow> {
+    _ = rhs_ncols;
+    temp_mat_req::<E>(dim, 1)
+}
+
+/// Solves the system $Tx = b$, where $T$ is the Hermitian Toeplitz matrix with first column
+/// `col`, i.e., $t_{ij} = c_{i - j}$ for $i \geq j$ and $t_{ij} = \bar c_{j - i}$ otherwise, and
+/// stores the result in `rhs`.
+///
+/// The imaginary part of `col[0]` is ignored. The Levinson recursion requires all the top-left
+/// corners of $T$ to be nonsingular, which is the case when $T$ is positive definite.
+///
+/// # Panics
+/// Panics if `rhs` doesn't have the same number of rows as `col`.
+#[track_caller]
+pub fn toeplitz_solve_in_place<E: ComplexField>(
+    col: ColRef<'_, E>,
+    rhs: MatMut<'_, E>,
+    stack: PodStack<'_>,
+) -> Result<(), ToeplitzError> {
+    let mut x = rhs;
+    let n = col.nrows();
+    assert!(x.nrows() == n);
+    if n == 0 {
+        return Ok(());
+    }
+
+    let t0 = col.read(0).faer_real();
+    if t0 == E::Real::faer_zero() {
+        return Err(ToeplitzError { singular_minor: 1 });
+    }
+
+    // forward vector, such that T_m f = e_0
+    // the backward vector, such that T_m b = e_{m-1}, is the reversed conjugate of f
+    let (f, _) = temp_mat_uninit::<E>(n, 1, stack);
+    let mut f = f.col_mut(0);
+    let t0_inv = E::faer_from_real(t0.faer_inv());
+    f.write(0, t0_inv);
+    for k in 0..x.ncols() {
+        x.write(0, k, x.read(0, k).faer_mul(t0_inv));
+    }
+
+    for m in 1..n {
+        let mut ef = E::faer_zero();
+        for j in 0..m {
+            ef = ef.faer_add(col.read(m - j).faer_mul(f.read(j)));
+        }
+        let d = E::Real::faer_one().faer_sub(ef.faer_abs2());
+        if d == E::Real::faer_zero() {
+            return Err(ToeplitzError {
+                singular_minor: m + 1,
+            });
+        }
+        let d_inv = d.faer_inv();
+
+        // f' = ([f; 0] - ef [0; b]) / d
+        f.write(m, E::faer_zero());
+        for j in 0..(m + 2) / 2 {
+            let lo = f.read(j);
+            let hi = f.read(m - j);
+            f.write(
+                j,
+                lo.faer_sub(ef.faer_mul(hi.faer_conj()))
+                    .faer_scale_real(d_inv),
+            );
+            f.write(
+                m - j,
+                hi.faer_sub(ef.faer_mul(lo.faer_conj()))
+                    .faer_scale_real(d_inv),
+            );
+        }
+
+        // x' = [x; 0] + (b_m - ex) b'
+        for k in 0..x.ncols() {
+            let mut ex = E::faer_zero();
+            for j in 0..m {
+                ex = ex.faer_add(col.read(m - j).faer_mul(x.read(j, k)));
+            }
+            let factor = x.read(m, k).faer_sub(ex);
+            for j in 0..m {
+                let update = factor.faer_mul(f.read(m - j).faer_conj());
+                x.write(j, k, x.read(j, k).faer_add(update));
+            }
+            x.write(m, k, factor.faer_mul(f.read(0).faer_conj()));
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes the coefficients $a$ of the autoregressive model of order $p$ with the given
+/// autocorrelation sequence $r_0, \dots, r_p$, by solving the Yule-Walker equations
+/// $\sum_j r_{i - j} a_j = -r_i$ for $1 \leq i \leq p$ with the Levinson-Durbin recursion, and
+/// stores them in `coeffs`.
+///
+/// Returns the variance of the prediction error, $r_0 + \sum_j \bar r_j a_j$.
+///
+/// # Panics
+/// Panics if `autocorrelation` doesn't have one more element than `coeffs`.
+#[track_caller]
+pub fn levinson_durbin<E: ComplexField>(
+    autocorrelation: ColRef<'_, E>,
+    coeffs: ColMut<'_, E>,
+) -> Result<E::Real, ToeplitzError> {
+    let r = autocorrelation;
+    let mut a = coeffs;
+    let p = a.nrows();
+    assert!(r.nrows() == p + 1);
+
+    let mut err = r.read(0).faer_real();
+    for k in 0..p {
+        if err == E::Real::faer_zero() {
+            return Err(ToeplitzError {
+                singular_minor: k + 1,
+            });
+        }
+
+        let mut acc = r.read(k + 1);
+        for j in 0..k {
+            acc = acc.faer_add(a.read(j).faer_mul(r.read(k - j)));
+        }
+        // reflection coefficient
+        let kappa = acc.faer_scale_real(err.faer_inv()).faer_neg();
+
+        for j in 0..(k + 1) / 2 {
+            let lo = a.read(j);
+            let hi = a.read(k - 1 - j);
+            a.write(j, lo.faer_add(kappa.faer_mul(hi.faer_conj())));
+            if j != k - 1 - j {
+                a.write(k - 1 - j, hi.faer_add(kappa.faer_mul(lo.faer_conj())));
+            }
+        }
+        a.write(k, kappa);
+
+        err = err.faer_mul(E::Real::faer_one().faer_sub(kappa.faer_abs2()));
+    }
+
+    Ok(err)
+}
+
+/// Computes the product of the Toeplitz matrix $T$ with first column `col` and first row `row`
+/// by `rhs`, and stores the result in `out`, i.e., $t_{ij} = c_{i - j}$ for $i \geq j$ and
+/// $t_{ij} = r_{j - i}$ otherwise.
+///
+/// The first element of `row` is ignored. The product is computed with the FFT in
+/// $\mathcal{O}((m + n) \log (m + n))$ operations per column.
+///
+/// # Panics
+/// Panics if the dimensions of `out` and `rhs` don't match the ones of the matrix.
+#[track_caller]
+pub fn toeplitz_matmul<E: ComplexField>(
+    out: MatMut<'_, E>,
+    col: ColRef<'_, E>,
+    row: ColRef<'_, E>,
+    rhs: MatRef<'_, E>,
+    fft: &impl Fft<E::Real>,
+) {
+    let mut out = out;
+    let (m, n) = (col.nrows(), row.nrows());
+    assert!(all(
+        out.nrows() == m,
+        rhs.nrows() == n,
+        out.ncols() == rhs.ncols(),
+    ));
+    if m == 0 {
+        return;
+    }
+    if n == 0 {
+        out.fill_zero();
+        return;
+    }
+
+    // s_k = t_{k - (n - 1), 0} for 0 <= k < m + n - 1, such that y = (s * x)[n - 1..n - 1 + m]
+    let s = Col::<E>::from_fn(m + n - 1, |k| {
+        if k >= n - 1 {
+            col.read(k - (n - 1))
+        } else {
+            row.read(n - 1 - k)
+        }
+    });
+    let mut conv = Col::<E>::zeros(m + 2 * n - 2);
+    for k in 0..rhs.ncols() {
+        convolve(conv.as_mut(), s.as_ref(), rhs.col(k), fft);
+        out.rb_mut()
+            .col_mut(k)
+            .copy_from(conv.as_ref().subrows(n - 1, m));
+    }
+}
+
+/// Circulant matrix, stored as its first column and its eigenvalues.
+///
+/// The circulant matrix with first column $c$ has the entries $a_{ij} = c_{(i - j) \bmod n}$. Its
+/// eigenvalues are the discrete Fourier transform of $c$, which is used to compute products and
+/// solves in $\mathcal{O}(n \log n)$ operations, using the FFT backend `F`.
+#[derive(Clone, Debug)]
+pub struct Circulant<E: ComplexField, F: Fft<E::Real> = NativeFft> {
+    col: Col<E>,
+    eigenvalues_re: Vec<E::Real>,
+    eigenvalues_im: Vec<E::Real>,
+    fft: F,
+}
+
+impl<E: ComplexField> Circulant<E> {
+    /// Returns the circulant matrix with the given first column.
+    #[inline]
+    pub fn new(col: ColRef<'_, E>) -> Self {
+        Self::new_with_fft(col, NativeFft)
+    }
+}
+
+impl<E: ComplexField, F: Fft<E::Real>> Circulant<E, F> {
+    /// Returns the circulant matrix with the given first column, using `fft` to compute the
+    /// Fourier transforms.
+    pub fn new_with_fft(col: ColRef<'_, E>, fft: F) -> Self {
+        let n = col.nrows();
+        let zero = E::Real::faer_zero();
+        let mut eigenvalues_re = vec![zero; n];
+        let mut eigenvalues_im = vec![zero; n];
+        split_parts(col, &mut eigenvalues_re, &mut eigenvalues_im);
+        fft.process(
+            &mut eigenvalues_re,
+            &mut eigenvalues_im,
+            FftDirection::Forward,
+        );
+        Self {
+            col: col.to_owned(),
+            eigenvalues_re,
+            eigenvalues_im,
+            fft,
+        }
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.col.nrows()
+    }
+
+    /// Returns the first column of the matrix.
+    #[inline]
+    pub fn first_column(&self) -> ColRef<'_, E> {
+        self.col.as_ref()
+    }
+
+    /// Returns the real and imaginary parts of the eigenvalues of the matrix, such that the
+    /// `k`-th eigenvalue is $\sum_j c_j e^{-2i\pi jk/n}$.
+    #[inline]
+    pub fn eigenvalues(&self) -> (&[E::Real], &[E::Real]) {
+        (&self.eigenvalues_re, &self.eigenvalues_im)
+    }
+
+    /// Returns the matrix as a dense matrix.
+    pub fn to_dense(&self) -> Mat<E> {
+        let n = self.dim();
+        Mat::from_fn(n, n, |i, j| self.col.read((i + n - j) % n))
+    }
+
+    /// Multiplies each column of `x` by `op(self)`, or by its inverse if `inverse` is `true`,
+    /// where `op` is the identity, conjugation, transposition or adjoint.
+    fn apply_spectrum_in_place(
+        &self,
+        x: MatMut<'_, E>,
+        conj: Conj,
+        transpose: bool,
+        inverse: bool,
+    ) {
+        let mut x = x;
+        let n = self.dim();
+        assert!(x.nrows() == n);
+        if n == 0 {
+            return;
+        }
+        let scale = E::Real::faer_from_f64(n as f64).faer_inv();
+
+        let zero = E::Real::faer_zero();
+        let mut re = vec![zero; n];
+        let mut im = vec![zero; n];
+        for k in 0..x.ncols() {
+            split_parts(x.rb().col(k), &mut re, &mut im);
+            self.fft.process(&mut re, &mut im, FftDirection::Forward);
+
+            for i in 0..n {
+                // the eigenvalues of conj(C) and transpose(C) are conj(λ_{-i}) and λ_{-i}
+                let idx = if (conj == Conj::Yes) == transpose {
+                    i
+                } else {
+                    (n - i) % n
+                };
+                let mut lr = self.eigenvalues_re[idx];
+                let mut li = self.eigenvalues_im[idx];
+                if conj == Conj::Yes {
+                    li = li.faer_neg();
+                }
+                if inverse {
+                    let norm_inv = lr.faer_abs2().faer_add(li.faer_abs2()).faer_inv();
+                    lr = lr.faer_mul(norm_inv);
+                    li = li.faer_neg().faer_mul(norm_inv);
+                }
+                let r = re[i].faer_mul(lr).faer_sub(im[i].faer_mul(li));
+                let s = re[i].faer_mul(li).faer_add(im[i].faer_mul(lr));
+                re[i] = r;
+                im[i] = s;
+            }
+
+            self.fft.process(&mut re, &mut im, FftDirection::Inverse);
+            join_parts(x.rb_mut().col_mut(k), &re, &im, scale);
+        }
+    }
+}
+
+impl<E: ComplexField, F: Fft<E::Real>> SpSolverCore<E> for Circulant<E, F> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.apply_spectrum_in_place(rhs, conj, false, true);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.apply_spectrum_in_place(rhs, conj, true, true);
+    }
+}
+
+impl<E: ComplexField, F: Fft<E::Real>> LinOp<E> for Circulant<E, F> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        let mut out = out;
+        out.copy_from(rhs);
+        self.apply_spectrum_in_place(out, Conj::No, false, false);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        let mut out = out;
+        out.copy_from(rhs);
+        self.apply_spectrum_in_place(out, Conj::Yes, false, false);
+    }
+}
+
+impl<E: ComplexField, F: Fft<E::Real>> BiLinOp<E> for Circulant<E, F> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        let mut out = out;
+        out.copy_from(rhs);
+        self.apply_spectrum_in_place(out, Conj::No, true, false);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        let mut out = out;
+        out.copy_from(rhs);
+        self.apply_spectrum_in_place(out, Conj::Yes, true, false);
+    }
+}
+
+/// Block-diagonal matrix, stored as the list of its diagonal blocks.
+///
+/// The blocks don't need to be square, in which case the matrix is rectangular. Products with the
+/// matrix, through its [`LinOp`] implementation, are computed with one dense product per block,
+/// and the blocks are processed in parallel.
+#[derive(Clone, Debug)]
+pub struct BlockDiag<E: Entity> {
+    blocks: Vec<Mat<E>>,
+    // start of the rows and columns of each block, followed by the dimensions of the matrix
+    row_offsets: Vec<usize>,
+    col_offsets: Vec<usize>,
+}
+
+fn offsets(dims: impl Iterator<Item = usize>) -> Vec<usize> {
+    let mut offsets = vec![0usize];
+    let mut offset = 0usize;
+    for dim in dims {
+        offset += dim;
+        offsets.push(offset);
+    }
+    offsets
+}
+
+// calls `f(i, rhs_i)` for each block `i`, where `rhs_i` is the block of rows of `rhs` starting at
+// `offsets[i]`. the blocks are processed in parallel, each with sequential kernels
+fn for_each_block<E: ComplexField>(
+    rhs: MatMut<'_, E>,
+    offsets: &[usize],
+    f: impl Send + Sync + Fn(usize, MatMut<'_, E>, Parallelism),
+    parallelism: Parallelism,
+) {
+    let n_blocks = offsets.len() - 1;
+    let inner_parallelism = if n_blocks > 1 {
+        Parallelism::None
+    } else {
+        parallelism
+    };
+    let rhs = rhs.into_const();
+    for_each_raw(
+        n_blocks,
+        |i| {
+            // SAFETY: the blocks of rows are disjoint
+            let rhs = unsafe {
+                rhs.subrows(offsets[i], offsets[i + 1] - offsets[i])
+                    .const_cast()
+            };
+            f(i, rhs, inner_parallelism);
+        },
+        parallelism,
+    );
+}
+
+impl<E: ComplexField> BlockDiag<E> {
+    /// Returns the block-diagonal matrix with the diagonal blocks `blocks`.
+    pub fn new(blocks: &[MatRef<'_, E>]) -> Self {
+        Self::from_blocks(blocks.iter().map(|block| block.to_owned()).collect())
+    }
+
+    /// Returns the block-diagonal matrix with the diagonal blocks `blocks`, taking ownership of
+    /// them.
+    pub fn from_blocks(blocks: Vec<Mat<E>>) -> Self {
+        let row_offsets = offsets(blocks.iter().map(|block| block.nrows()));
+        let col_offsets = offsets(blocks.iter().map(|block| block.ncols()));
+        Self {
+            blocks,
+            row_offsets,
+            col_offsets,
+        }
+    }
+
+    /// Returns the number of rows of the matrix.
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        *self.row_offsets.last().unwrap()
+    }
+
+    /// Returns the number of columns of the matrix.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        *self.col_offsets.last().unwrap()
+    }
+
+    /// Returns the number of diagonal blocks.
+    #[inline]
+    pub fn n_blocks(&self) -> usize {
+        self.blocks.len()
+    }
+
+    /// Returns the `i`-th diagonal block.
+    #[inline]
+    #[track_caller]
+    pub fn block(&self, i: usize) -> MatRef<'_, E> {
+        self.blocks[i].as_ref()
+    }
+
+    /// Returns the index of the first row of each block, followed by the number of rows of the
+    /// matrix.
+    #[inline]
+    pub fn row_offsets(&self) -> &[usize] {
+        &self.row_offsets
+    }
+
+    /// Returns the index of the first column of each block, followed by the number of columns
+    /// of the matrix.
+    #[inline]
+    pub fn col_offsets(&self) -> &[usize] {
+        &self.col_offsets
+    }
+
+    /// Returns the matrix as a dense matrix.
+    pub fn to_dense(&self) -> Mat<E> {
+        let blocks = self
+            .blocks
+            .iter()
+            .map(|block| block.as_ref())
+            .collect::<Vec<_>>();
+        crate::mat::block_diag(&blocks)
+    }
+
+    /// Returns the LU decomposition with partial pivoting of each block.
+    ///
+    /// # Panics
+    /// Panics if one of the blocks is not square.
+    #[track_caller]
+    pub fn partial_piv_lu(&self) -> BlockDiagLu<E> {
+        for block in &self.blocks {
+            assert!(block.nrows() == block.ncols());
+        }
+        let mut blocks = Vec::with_capacity(self.n_blocks());
+        blocks.resize_with(self.n_blocks(), || None);
+        {
+            let blocks = crate::utils::thread::Ptr(blocks.as_mut_ptr());
+            for_each_raw(
+                self.n_blocks(),
+                |i| {
+                    let lu = PartialPivLu::new(self.blocks[i].as_ref());
+                    // SAFETY: each task writes to a different element
+                    unsafe { *{ blocks }.0.add(i) = Some(lu) };
+                },
+                crate::get_global_parallelism(),
+            );
+        }
+        BlockDiagLu {
+            blocks: blocks.into_iter().map(Option::unwrap).collect(),
+            offsets: self.row_offsets.clone(),
+        }
+    }
+
+    fn apply_impl(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj: Conj,
+        transpose: bool,
+        parallelism: Parallelism,
+    ) {
+        let (out_offsets, rhs_offsets) = if transpose {
+            (&self.col_offsets, &self.row_offsets)
+        } else {
+            (&self.row_offsets, &self.col_offsets)
+        };
+        assert!(all(
+            out.nrows() == *out_offsets.last().unwrap(),
+            rhs.nrows() == *rhs_offsets.last().unwrap(),
+            out.ncols() == rhs.ncols(),
+        ));
+        for_each_block(
+            out,
+            out_offsets,
+            |i, out, parallelism| {
+                let block = if transpose {
+                    self.blocks[i].transpose()
+                } else {
+                    self.blocks[i].as_ref()
+                };
+                let rhs = rhs.subrows(rhs_offsets[i], rhs_offsets[i + 1] - rhs_offsets[i]);
+                matmul_with_conj(
+                    out,
+                    block,
+                    conj,
+                    rhs,
+                    Conj::No,
+                    None,
+                    E::faer_one(),
+                    parallelism,
+                );
+            },
+            parallelism,
+        );
+    }
+}
+
+impl<E: ComplexField> LinOp<E> for BlockDiag<E> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.nrows()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, false, parallelism);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, false, parallelism);
+    }
+}
+
+impl<E: ComplexField> BiLinOp<E> for BlockDiag<E> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, true, parallelism);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, true, parallelism);
+    }
+}
+
+/// LU decomposition with partial pivoting of a square block-diagonal matrix, computed block by
+/// block.
+///
+/// See [`BlockDiag::partial_piv_lu`]. The solves are computed with one dense solve per block, and
+/// the blocks are processed in parallel.
+pub struct BlockDiagLu<E: Entity> {
+    blocks: Vec<PartialPivLu<E>>,
+    offsets: Vec<usize>,
+}
+
+impl<E: ComplexField> BlockDiagLu<E> {
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    /// Returns the LU decomposition of the `i`-th diagonal block.
+    #[inline]
+    #[track_caller]
+    pub fn block(&self, i: usize) -> &PartialPivLu<E> {
+        &self.blocks[i]
+    }
+
+    fn solve_impl(&self, rhs: MatMut<'_, E>, conj: Conj, transpose: bool) {
+        assert!(rhs.nrows() == self.dim());
+        for_each_block(
+            rhs,
+            &self.offsets,
+            |i, rhs, _| {
+                if transpose {
+                    self.blocks[i].solve_transpose_in_place_with_conj_impl(rhs, conj);
+                } else {
+                    self.blocks[i].solve_in_place_with_conj_impl(rhs, conj);
+                }
+            },
+            crate::get_global_parallelism(),
+        );
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for BlockDiagLu<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, false);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, sparse::linalg::solvers::SpSolver};
+    use dyn_stack::GlobalPodBuffer;
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    fn hermitian_toeplitz(col: ColRef<'_, c64>) -> Mat<c64> {
+        let n = col.nrows();
+        Mat::from_fn(n, n, |i, j| {
+            if i > j {
+                col.read(i - j)
+            } else if i == j {
+                c64::new(col.read(0).re, 0.0)
+            } else {
+                col.read(j - i).faer_conj()
+            }
+        })
+    }
+
+    #[test]
+    fn test_toeplitz_solve() {
+        for n in [1, 2, 7, 30] {
+            let mut col = Col::<c64>::from_fn(n, |_| random_c64());
+            col.write(0, c64::new(n as f64 + 1.0, 0.0));
+            let dense = hermitian_toeplitz(col.as_ref());
+
+            let rhs = Mat::<c64>::from_fn(n, 3, |_, _| random_c64());
+            let mut x = rhs.clone();
+            toeplitz_solve_in_place(
+                col.as_ref(),
+                x.as_mut(),
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    toeplitz_solve_in_place_req::<c64>(n, 3).unwrap(),
+                )),
+            )
+            .unwrap();
+            assert!((&dense * &x - &rhs).norm_max() < 1e-10);
+        }
+
+        // singular leading 2x2 block
+        let col = crate::col![1.0, 1.0, 0.5f64];
+        let mut x = Mat::<f64>::zeros(3, 1);
+        let err = toeplitz_solve_in_place(
+            col.as_ref(),
+            x.as_mut(),
+            PodStack::new(&mut GlobalPodBuffer::new(
+                toeplitz_solve_in_place_req::<f64>(3, 1).unwrap(),
+            )),
+        )
+        .unwrap_err();
+        assert!(err.singular_minor == 2);
+    }
+
+    #[test]
+    fn test_levinson_durbin() {
+        let p = 6;
+        let mut r = Col::<c64>::from_fn(p + 1, |_| random_c64());
+        r.write(0, c64::new(p as f64 + 2.0, 0.0));
+        let mut a = Col::<c64>::zeros(p);
+        let err = levinson_durbin(r.as_ref(), a.as_mut()).unwrap();
+
+        let dense = hermitian_toeplitz(r.as_ref().subrows(0, p));
+        let residual = &dense * &a + r.as_ref().subrows(1, p);
+        assert!(residual.norm_max() < 1e-10);
+
+        let mut expected = r.read(0);
+        for j in 0..p {
+            expected += r.read(j + 1).faer_conj() * a.read(j);
+        }
+        assert!((expected - c64::new(err, 0.0)).norm() < 1e-10);
+    }
+
+    #[test]
+    fn test_circulant() {
+        for n in [1, 2, 5, 8, 12] {
+            let mut col = Col::<c64>::from_fn(n, |_| random_c64());
+            col.write(0, col.read(0) + c64::new(n as f64, 0.0));
+            let c = Circulant::new(col.as_ref());
+            let dense = c.to_dense();
+
+            let rhs = Mat::<c64>::from_fn(n, 2, |_, _| random_c64());
+            for (x, op) in [
+                (c.solve(&rhs), dense.clone()),
+                (c.solve_conj(&rhs), dense.conjugate().to_owned()),
+                (c.solve_transpose(&rhs), dense.transpose().to_owned()),
+                (c.solve_conj_transpose(&rhs), dense.adjoint().to_owned()),
+            ] {
+                assert!((&op * &x - &rhs).norm_max() < 1e-10);
+            }
+
+            let mut out = Mat::<c64>::zeros(n, 2);
+            let stack = PodStack::new(&mut []);
+            c.apply(out.as_mut(), rhs.as_ref(), Parallelism::None, stack);
+            assert!((&out - &dense * &rhs).norm_max() < 1e-10);
+            let stack = PodStack::new(&mut []);
+            c.adjoint_apply(out.as_mut(), rhs.as_ref(), Parallelism::None, stack);
+            assert!((&out - dense.adjoint() * &rhs).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_toeplitz_matmul() {
+        for (m, n) in [(0, 3), (3, 0), (1, 1), (5, 3), (4, 9)] {
+            let col = Col::<c64>::from_fn(m, |_| random_c64());
+            let row = Col::<c64>::from_fn(n, |_| random_c64());
+            let dense = Mat::<c64>::from_fn(m, n, |i, j| {
+                if i >= j {
+                    col.read(i - j)
+                } else {
+                    row.read(j - i)
+                }
+            });
+
+            let rhs = Mat::<c64>::from_fn(n, 2, |_, _| random_c64());
+            let mut out = Mat::<c64>::from_fn(m, 2, |_, _| random_c64());
+            toeplitz_matmul(
+                out.as_mut(),
+                col.as_ref(),
+                row.as_ref(),
+                rhs.as_ref(),
+                &NativeFft,
+            );
+            assert!((&out - &dense * &rhs).norm_max() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_block_diag() {
+        let blocks = [(3, 3), (1, 1), (4, 4), (0, 0), (2, 2)].map(|(m, n)| {
+            Mat::<c64>::from_fn(m, n, |i, j| {
+                random_c64()
+                    + if i == j {
+                        c64::new(4.0, 0.0)
+                    } else {
+                        c64::new(0.0, 0.0)
+                    }
+            })
+        });
+        let views = blocks
+            .iter()
+            .map(|block| block.as_ref())
+            .collect::<Vec<_>>();
+        let diag = BlockDiag::new(&views);
+        let dense = diag.to_dense();
+        assert!(all(
+            diag.nrows() == 10,
+            diag.ncols() == 10,
+            diag.n_blocks() == 5
+        ));
+
+        let rhs = Mat::<c64>::from_fn(10, 3, |_, _| random_c64());
+        for parallelism in [Parallelism::None, crate::get_global_parallelism()] {
+            let mut out = Mat::<c64>::zeros(10, 3);
+            let stack = PodStack::new(&mut []);
+            diag.apply(out.as_mut(), rhs.as_ref(), parallelism, stack);
+            assert!((&out - &dense * &rhs).norm_max() < 1e-12);
+            let stack = PodStack::new(&mut []);
+            diag.conj_apply(out.as_mut(), rhs.as_ref(), parallelism, stack);
+            assert!((&out - dense.conjugate() * &rhs).norm_max() < 1e-12);
+            let stack = PodStack::new(&mut []);
+            diag.adjoint_apply(out.as_mut(), rhs.as_ref(), parallelism, stack);
+            assert!((&out - dense.adjoint() * &rhs).norm_max() < 1e-12);
+        }
+
+        let lu = diag.partial_piv_lu();
+        for (x, op) in [
+            (lu.solve(&rhs), dense.clone()),
+            (lu.solve_conj(&rhs), dense.conjugate().to_owned()),
+            (lu.solve_transpose(&rhs), dense.transpose().to_owned()),
+            (lu.solve_conj_transpose(&rhs), dense.adjoint().to_owned()),
+        ] {
+            assert!((&op * &x - &rhs).norm_max() < 1e-10);
+        }
+
+        // rectangular blocks
+        let a = Mat::<c64>::from_fn(2, 3, |_, _| random_c64());
+        let b = Mat::<c64>::from_fn(4, 1, |_, _| random_c64());
+        let diag = BlockDiag::new(&[a.as_ref(), b.as_ref()]);
+        let dense = diag.to_dense();
+        let rhs = Mat::<c64>::from_fn(6, 2, |_, _| random_c64());
+        let mut out = Mat::<c64>::zeros(4, 2);
+        let stack = PodStack::new(&mut []);
+        diag.transpose_apply(out.as_mut(), rhs.as_ref(), Parallelism::None, stack);
+        assert!((&out - dense.transpose() * &rhs).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn test_circulant_real() {
+        let c = Circulant::new_with_fft(
+            crate::col![3.0, 1.0, -1.0, 0.5, 0.25f64].as_ref(),
+            &NativeFft,
+        );
+        let rhs = Mat::<f64>::from_fn(5, 1, |i, _| i as f64);
+        let x = c.solve(&rhs);
+        assert!((&c.to_dense() * &x - &rhs).norm_max() < 1e-12);
+    }
+}
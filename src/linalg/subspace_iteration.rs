@@ -0,0 +1,402 @@
+//! Subspace iteration for a block of extremal eigenpairs of a dense self-adjoint matrix.
+//!
+//! Subspace (or orthogonal) iteration is the block generalization of the power iteration: a block
+//! of vectors is repeatedly multiplied by the matrix and orthonormalized, and a Rayleigh-Ritz
+//! projection onto the block is used at each step to extract the eigenpair approximations. Since
+//! the work is dominated by matrix-matrix products, it makes good use of the parallel [`matmul`],
+//! and is a middle ground between a full eigendecomposition and the Krylov solvers in
+//! [`crate::linop`], which need fewer products but each of them with a single vector.
+//!
+//! The matrix is shifted so that the wanted end of the spectrum has the largest magnitude. The
+//! convergence rate of the $j$-th eigenpair then depends on the ratio between the shifted
+//! eigenvalues $\lambda_{p+1}$ and $\lambda_j$, where $p$ is the block size, so using a block
+//! larger than the number of wanted eigenpairs can speed up the convergence considerably.
+
+use crate::{
+    linalg::{
+        evd::{compute_hermitian_evd, compute_hermitian_evd_req, ComputeVectors},
+        matmul::matmul,
+        power_iteration::fill_pseudo_random,
+        solvers::Which,
+        temp_mat_req, temp_mat_uninit,
+    },
+    linop::randomized::{orthonormalize_in_place, orthonormalize_in_place_req},
+    ColMut, ComplexField, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Parameters of the subspace iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct SubspaceIterationParams<E: ComplexField> {
+    /// End of the spectrum to compute.
+    pub which: Which,
+    /// Absolute tolerance on the residuals.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residuals, relative to the largest computed eigenvalue magnitude.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of iterations.
+    pub max_iters: usize,
+}
+
+/// Information about a successful run of the subspace iteration.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct SubspaceIterationInfo<E: ComplexField> {
+    /// Largest residual norm $\|Ax - \lambda x\|$ of the computed eigenpairs.
+    pub abs_residual: E::Real,
+    /// Largest residual norm, relative to the largest computed eigenvalue magnitude.
+    pub rel_residual: E::Real,
+    /// Number of iterations until convergence.
+    pub iter_count: usize,
+}
+
+/// Error from the subspace iteration.
+#[derive(Copy, Clone, Debug)]
+pub enum SubspaceIterationError<E: ComplexField> {
+    /// The iteration did not converge within the maximum number of iterations.
+    NoConvergence {
+        /// Largest residual norm of the computed eigenpairs.
+        abs_residual: E::Real,
+        /// Largest residual norm, relative to the largest computed eigenvalue magnitude.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for SubspaceIterationParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            which: Which::Largest,
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_sqrt(),
+            max_iters: 1000,
+        }
+    }
+}
+
+/// Computes the size and alignment of the workspace required by [`subspace_iteration`], for a
+/// matrix of dimension `dim` and a block with `block_size` columns.
+pub fn subspace_iteration_req<E: ComplexField>(
+    dim: usize,
+    block_size: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let n = dim;
+    let p = block_size;
+    StackReq::try_all_of([
+        temp_mat_req::<E>(n, p)?,       // x
+        temp_mat_req::<E>(n, p)?,       // y
+        temp_mat_req::<E>(n, p)?,       // ritz vectors
+        temp_mat_req::<E>(p, p)?,       // projected matrix
+        temp_mat_req::<E>(p, p)?,       // u
+        temp_mat_req::<E>(p, 1)?,       // s
+        StackReq::try_new::<usize>(p)?, // perm
+        StackReq::try_any_of([
+            compute_hermitian_evd_req::<E>(
+                p,
+                ComputeVectors::Yes,
+                parallelism,
+                Default::default(),
+            )?,
+            orthonormalize_in_place_req::<E>(n, p, parallelism)?,
+        ])?,
+    ])
+}
+
+/// Computes `eigenvalues.nrows()` eigenpairs at the end of the spectrum of the self-adjoint
+/// matrix `mat` specified by `params.which`, using a block of `block_size` vectors.
+///
+/// Both triangular halves of `mat` are used for the products, so it must be stored in full.
+///
+/// The eigenvalues are stored in `eigenvalues`, sorted according to `params.which`, and the
+/// corresponding orthonormal eigenvectors are stored in `eigenvectors`. The outputs are filled
+/// even if the iteration does not converge.
+///
+/// # Panics
+/// Panics if `mat` is not square, if `eigenvectors` doesn't have `mat.nrows()` rows and
+/// `eigenvalues.nrows()` columns, if `block_size` is smaller than the number of eigenvalues or
+/// larger than `mat.nrows()`, or if the provided memory in `stack` is insufficient (see
+/// [`subspace_iteration_req`]).
+#[track_caller]
+pub fn subspace_iteration<E: ComplexField>(
+    eigenvectors: MatMut<'_, E>,
+    eigenvalues: ColMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    block_size: usize,
+    params: SubspaceIterationParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<SubspaceIterationInfo<E>, SubspaceIterationError<E>> {
+    let mut eigenvectors = eigenvectors;
+    let mut eigenvalues = eigenvalues;
+    let n = mat.nrows();
+    let k = eigenvalues.nrows();
+    let p = block_size;
+    crate::assert!(all(
+        mat.ncols() == n,
+        eigenvectors.nrows() == n,
+        eigenvectors.ncols() == k,
+        k <= p,
+        p <= n,
+    ));
+
+    let zero = E::Real::faer_zero();
+    if k == 0 {
+        return Ok(SubspaceIterationInfo {
+            abs_residual: zero,
+            rel_residual: zero,
+            iter_count: 0,
+        });
+    }
+
+    // the shifted matrix σI ± A is positive semidefinite, and the wanted eigenvalues are the ones
+    // with the largest magnitude. σ is the infinity norm of A, which bounds its spectral radius
+    let mut shift = zero;
+    for i in 0..n {
+        let row_norm = mat.row(i).norm_l1();
+        if row_norm > shift {
+            shift = row_norm;
+        }
+    }
+    let sign = match params.which {
+        Which::Largest => E::faer_one(),
+        Which::Smallest => E::faer_one().faer_neg(),
+    };
+    let to_eigenvalue = |mu: E::Real| match params.which {
+        Which::Largest => mu.faer_sub(shift),
+        Which::Smallest => shift.faer_sub(mu),
+    };
+
+    let (mut x, stack) = temp_mat_uninit::<E>(n, p, stack);
+    let (mut y, stack) = temp_mat_uninit::<E>(n, p, stack);
+    let (mut ritz, stack) = temp_mat_uninit::<E>(n, p, stack);
+    let (mut h, stack) = temp_mat_uninit::<E>(p, p, stack);
+    let (mut u, stack) = temp_mat_uninit::<E>(p, p, stack);
+    let (mut s, stack) = temp_mat_uninit::<E>(p, 1, stack);
+    let (perm, mut stack) = stack.make_with(p, |i| i);
+
+    for j in 0..p {
+        fill_pseudo_random(x.rb_mut().col_mut(j), j as u64 + 1);
+    }
+    orthonormalize_in_place(x.rb_mut(), parallelism, stack.rb_mut());
+
+    let mut abs_residual = zero;
+    let mut rel_residual = zero;
+    for iter in 0..=params.max_iters {
+        // Y = (σI ± A) X
+        y.copy_from(x.rb());
+        matmul(
+            y.rb_mut(),
+            mat,
+            x.rb(),
+            Some(E::faer_from_real(shift)),
+            sign,
+            parallelism,
+        );
+
+        // rayleigh-ritz projection onto the block
+        matmul(
+            h.rb_mut(),
+            x.rb().adjoint(),
+            y.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        compute_hermitian_evd(
+            h.rb(),
+            s.rb_mut(),
+            Some(u.rb_mut()),
+            parallelism,
+            stack.rb_mut(),
+            Default::default(),
+        );
+        for (i, idx) in perm.iter_mut().enumerate() {
+            *idx = i;
+        }
+        perm.sort_unstable_by(|&i, &j| {
+            let (si, sj) = (s.read(i, 0).faer_real(), s.read(j, 0).faer_real());
+            sj.partial_cmp(&si).unwrap_or(core::cmp::Ordering::Equal)
+        });
+        let mut u_sorted = h.rb_mut();
+        for (j, &i) in perm.iter().enumerate() {
+            u_sorted.rb_mut().col_mut(j).copy_from(u.rb().col(i));
+        }
+
+        // ritz vectors X U, and their images (σI ± A) X U
+        matmul(
+            ritz.rb_mut(),
+            x.rb(),
+            u_sorted.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        matmul(
+            x.rb_mut(),
+            y.rb(),
+            u_sorted.rb(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+
+        let mut norm = zero;
+        for &i in perm.iter() {
+            let abs = to_eigenvalue(s.read(i, 0).faer_real()).faer_abs();
+            if abs > norm {
+                norm = abs;
+            }
+        }
+        let threshold = params
+            .rel_tolerance
+            .faer_mul(norm)
+            .faer_add(params.abs_tolerance);
+
+        // the residual of the shifted problem is the same as the residual of the original one
+        let mut converged = true;
+        abs_residual = zero;
+        for (j, &idx) in perm[..k].iter().enumerate() {
+            let mu = s.read(idx, 0).faer_real();
+            let mut r = zero;
+            for i in 0..n {
+                r = r.faer_add(
+                    x.read(i, j)
+                        .faer_sub(ritz.read(i, j).faer_scale_real(mu))
+                        .faer_abs2(),
+                );
+            }
+            let r = r.faer_sqrt();
+            if r > threshold || r.faer_is_nan() {
+                converged = false;
+            }
+            if r > abs_residual {
+                abs_residual = r;
+            }
+        }
+        rel_residual = if norm > zero {
+            abs_residual.faer_div(norm)
+        } else {
+            abs_residual
+        };
+
+        if converged || iter == params.max_iters {
+            for (j, &i) in perm[..k].iter().enumerate() {
+                eigenvalues.write(j, to_eigenvalue(s.read(i, 0).faer_real()));
+            }
+            eigenvectors.copy_from(ritz.rb().subcols(0, k));
+            if converged {
+                return Ok(SubspaceIterationInfo {
+                    abs_residual,
+                    rel_residual,
+                    iter_count: iter,
+                });
+            }
+            break;
+        }
+
+        // X = orth((σI ± A) X U)
+        orthonormalize_in_place(x.rb_mut(), parallelism, stack.rb_mut());
+    }
+
+    Err(SubspaceIterationError::NoConvergence {
+        abs_residual,
+        rel_residual,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Col, Mat};
+    use dyn_stack::GlobalPodBuffer;
+    use rand::prelude::*;
+
+    fn test_matrix<E: ComplexField<Real = f64>>(n: usize, d: &Col<f64>) -> Mat<E> {
+        let q: Mat<E> =
+            crate::stats::UnitaryMat { dimension: n }.sample(&mut StdRng::seed_from_u64(n as u64));
+        let d = Mat::<E>::from_fn(n, n, |i, j| {
+            if i == j {
+                E::faer_from_real(d.read(i))
+            } else {
+                E::faer_zero()
+            }
+        });
+        &q * d * q.adjoint()
+    }
+
+    #[test]
+    fn test_subspace_iteration() {
+        let n = 60;
+        let k = 4;
+        // well separated ends of the spectrum
+        let d = Col::<f64>::from_fn(n, |i| match i {
+            0..=3 => 20.0 - 2.0 * i as f64,
+            4..=7 => -30.0 + 2.0 * (i - 4) as f64,
+            _ => (i as f64 - 30.0) / 10.0,
+        });
+
+        for which in [Which::Largest, Which::Smallest] {
+            let a = test_matrix::<c64>(n, &d);
+            let mut params = SubspaceIterationParams::default();
+            params.which = which;
+            let mut x = Mat::<c64>::zeros(n, k);
+            let mut s = Col::<f64>::zeros(k);
+            let info = subspace_iteration(
+                x.as_mut(),
+                s.as_mut(),
+                a.as_ref(),
+                8,
+                params,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    subspace_iteration_req::<c64>(n, 8, Parallelism::None).unwrap(),
+                )),
+            )
+            .unwrap();
+            assert!(info.rel_residual <= params.rel_tolerance);
+
+            for j in 0..k {
+                let expected = match which {
+                    Which::Largest => 20.0 - 2.0 * j as f64,
+                    Which::Smallest => -30.0 + 2.0 * j as f64,
+                };
+                assert!((s.read(j) - expected).abs() < 1e-8);
+                let s_j = c64::new(s.read(j), 0.0);
+                let r = &a * x.col(j) - x.col(j) * crate::scale(s_j);
+                assert!(r.norm_l2() < 1e-6);
+            }
+            assert!((x.adjoint() * &x - Mat::<c64>::identity(k, k)).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_subspace_iteration_no_convergence() {
+        let n = 20;
+        let d = Col::<f64>::from_fn(n, |i| (n - i) as f64);
+        let a = test_matrix::<f64>(n, &d);
+        let mut params = SubspaceIterationParams::default();
+        params.max_iters = 2;
+        let mut x = Mat::<f64>::zeros(n, 3);
+        let mut s = Col::<f64>::zeros(3);
+        let result = subspace_iteration(
+            x.as_mut(),
+            s.as_mut(),
+            a.as_ref(),
+            3,
+            params,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                subspace_iteration_req::<f64>(n, 3, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!(matches!(
+            result,
+            Err(SubspaceIterationError::NoConvergence { .. })
+        ));
+        // the outputs are still filled with the current approximations
+        assert!((x.transpose() * &x - Mat::<f64>::identity(3, 3)).norm_max() < 1e-10);
+    }
+}
@@ -1,3 +1,10 @@
+//! Reduction of a matrix to upper bidiagonal form (Golub–Kahan bidiagonalization), using
+//! Householder reflections applied alternately from the left and from the right.
+//!
+//! The reduction computes $A = U B V^H$, where $U$ and $V$ are unitary and $B$ is upper
+//! bidiagonal, and is the first step of the singular value decomposition. A high level wrapper is
+//! available in [`Bidiag`](crate::linalg::solvers::Bidiag).
+
 use crate::{
     assert,
     linalg::{matmul::matmul, temp_mat_req, temp_mat_uninit, temp_mat_zeroed},
@@ -11,6 +18,7 @@ use faer_entity::*;
 use pulp::Simd;
 use reborrow::*;
 
+/// Computes the size and alignment of required workspace for performing a bidiagonal reduction.
 pub fn bidiagonalize_in_place_req<E: Entity>(
     m: usize,
     n: usize,
@@ -23,6 +31,24 @@ pub fn bidiagonalize_in_place_req<E: Entity>(
     ])
 }
 
+/// Computes the bidiagonal reduction $A = U B V^H$ of the matrix `a`, in place.
+///
+/// On output, the diagonal and the superdiagonal of `a` contain those of $B$. The entries below
+/// the diagonal contain the essential parts of the Householder reflections whose product is $U$,
+/// and the entries to the right of the superdiagonal contain the conjugates of the essential parts
+/// of the Householder reflections whose product is the bottom right `(n-1)×(n-1)` block of $V$.
+/// The coefficient of the `k`-th left (resp. right) reflection is stored in
+/// `householder_left[(k, 0)]` (resp. `householder_right[(k, 0)]`). The block Householder
+/// factors can then be built using
+/// [`upgrade_householder_factor`](crate::linalg::householder::upgrade_householder_factor).
+///
+/// # Panics
+/// Panics if `a` has fewer rows than columns, or if `householder_left` (resp.
+/// `householder_right`) has fewer than `n` (resp. `n - 1`) rows.
+///
+/// This can also panic if the provided memory in `stack` is insufficient (see
+/// [`bidiagonalize_in_place_req`]).
+#[track_caller]
 pub fn bidiagonalize_in_place<E: ComplexField>(
     mut a: MatMut<'_, E>,
     mut householder_left: MatMut<'_, E>,
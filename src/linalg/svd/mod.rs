@@ -31,7 +31,6 @@ use reborrow::*;
 
 use bidiag_real_svd::{bidiag_real_svd_req, compute_bidiag_real_svd};
 
-#[doc(hidden)]
 pub mod bidiag;
 #[doc(hidden)]
 pub mod bidiag_real_svd;
@@ -0,0 +1,493 @@
+//! Direct solvers for tridiagonal and block tridiagonal linear systems.
+//!
+//! [`TridiagonalLu`] implements the Thomas algorithm, which is Gaussian elimination without
+//! pivoting specialized to tridiagonal matrices. It takes $\mathcal{O}(n)$ operations for the
+//! factorization and for each right-hand side, and is stable for diagonally dominant and
+//! Hermitian positive definite matrices.
+//!
+//! [`BlockTridiagonalLu`] computes the block LU decomposition of a block tridiagonal matrix, where
+//! the Schur complements on the diagonal are factorized with a dense LU decomposition with partial
+//! pivoting. It takes $\mathcal{O}(N b^3)$ operations for the factorization, where $N$ is the
+//! number of diagonal blocks and $b$ is their size.
+//!
+//! Both decompositions implement [`SpSolver`](crate::sparse::linalg::solvers::SpSolver), and can
+//! solve systems with multiple right-hand sides at once.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{col, linalg::tridiagonal::TridiagonalLu, mat, prelude::*};
+//!
+//! let lower = col![1.0, 1.0];
+//! let diag = col![4.0, 4.0, 4.0];
+//! let upper = col![2.0, 2.0];
+//!
+//! let lu = TridiagonalLu::new(lower.as_ref(), diag.as_ref(), upper.as_ref());
+//! let b = mat![[6.0], [7.0], [5.0f64]];
+//! let x = lu.solve(&b);
+//!
+//! let a = mat![[4.0, 2.0, 0.0], [1.0, 4.0, 2.0], [0.0, 1.0, 4.0]];
+//! assert!((&a * &x - &b).norm_max() < 1e-12);
+//! ```
+
+use crate::{
+    assert,
+    col::{Col, ColRef},
+    get_global_parallelism,
+    linalg::{matmul::matmul_with_conj, solvers::PartialPivLu},
+    mat::{Mat, MatMut, MatRef},
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj,
+};
+use alloc::vec::Vec;
+use reborrow::*;
+
+/// LU decomposition without pivoting of a tridiagonal matrix.
+///
+/// The factorization is such that $A = LU$, where $L$ is a unit lower bidiagonal matrix, and $U$
+/// is an upper bidiagonal matrix whose superdiagonal is the same as the one of $A$.
+#[derive(Clone, Debug)]
+pub struct TridiagonalLu<E: ComplexField> {
+    // subdiagonal of L
+    l: Col<E>,
+    // diagonal of U
+    u: Col<E>,
+    // superdiagonal of U
+    upper: Col<E>,
+}
+
+/// Block LU decomposition of a block tridiagonal matrix.
+///
+/// The factorization is such that $A = LU$, where $L$ is block lower bidiagonal with the
+/// subdiagonal blocks of $A$ and the Schur complements $S_i$ on its diagonal, and $U$ is block
+/// unit upper bidiagonal with the superdiagonal blocks $S_i^{-1} C_i$, where $C_i$ are the
+/// superdiagonal blocks of $A$.
+#[derive(Clone, Debug)]
+pub struct BlockTridiagonalLu<E: ComplexField> {
+    offsets: Vec<usize>,
+    schur: Vec<PartialPivLu<E>>,
+    lower: Vec<Mat<E>>,
+    upper: Vec<Mat<E>>,
+}
+
+#[inline]
+fn conj_if<E: ComplexField>(value: E, conj: Conj) -> E {
+    if conj == Conj::Yes {
+        value.faer_conj()
+    } else {
+        value
+    }
+}
+
+impl<E: ComplexField> TridiagonalLu<E> {
+    /// Returns the LU decomposition of the tridiagonal matrix with the given subdiagonal,
+    /// diagonal and superdiagonal.
+    ///
+    /// If a zero pivot is encountered, the decomposition is still computed, but the solves return
+    /// non-finite values.
+    ///
+    /// # Panics
+    /// Panics if `lower` or `upper` don't have one element less than `diag`.
+    #[track_caller]
+    pub fn new(lower: ColRef<'_, E>, diag: ColRef<'_, E>, upper: ColRef<'_, E>) -> Self {
+        let n = diag.nrows();
+        assert!(all(
+            lower.nrows() == n.saturating_sub(1),
+            upper.nrows() == n.saturating_sub(1),
+        ));
+
+        let mut l = Col::<E>::zeros(n.saturating_sub(1));
+        let mut u = Col::<E>::zeros(n);
+        if n > 0 {
+            u.write(0, diag.read(0));
+        }
+        for i in 1..n {
+            let li = lower.read(i - 1).faer_mul(u.read(i - 1).faer_inv());
+            l.write(i - 1, li);
+            u.write(i, diag.read(i).faer_sub(li.faer_mul(upper.read(i - 1))));
+        }
+
+        Self {
+            l,
+            u,
+            upper: upper.to_owned(),
+        }
+    }
+
+    /// Returns the dimension of the decomposed matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.u.nrows()
+    }
+
+    /// Returns the subdiagonal of the factor $L$.
+    #[inline]
+    pub fn l_subdiagonal(&self) -> ColRef<'_, E> {
+        self.l.as_ref()
+    }
+
+    /// Returns the diagonal of the factor $U$.
+    #[inline]
+    pub fn u_diagonal(&self) -> ColRef<'_, E> {
+        self.u.as_ref()
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for TridiagonalLu<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        let n = self.dim();
+        assert!(rhs.nrows() == n);
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            // L y = b
+            for i in 1..n {
+                let li = conj_if(self.l.read(i - 1), conj);
+                x.write(i, x.read(i).faer_sub(li.faer_mul(x.read(i - 1))));
+            }
+            // U x = y
+            for i in (0..n).rev() {
+                let mut xi = x.read(i);
+                if i + 1 < n {
+                    let ci = conj_if(self.upper.read(i), conj);
+                    xi = xi.faer_sub(ci.faer_mul(x.read(i + 1)));
+                }
+                x.write(i, xi.faer_mul(conj_if(self.u.read(i), conj).faer_inv()));
+            }
+        }
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        let n = self.dim();
+        assert!(rhs.nrows() == n);
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            // Uᵀ y = b
+            for i in 0..n {
+                let mut xi = x.read(i);
+                if i > 0 {
+                    let ci = conj_if(self.upper.read(i - 1), conj);
+                    xi = xi.faer_sub(ci.faer_mul(x.read(i - 1)));
+                }
+                x.write(i, xi.faer_mul(conj_if(self.u.read(i), conj).faer_inv()));
+            }
+            // Lᵀ x = y
+            for i in (0..n.saturating_sub(1)).rev() {
+                let li = conj_if(self.l.read(i), conj);
+                x.write(i, x.read(i).faer_sub(li.faer_mul(x.read(i + 1))));
+            }
+        }
+    }
+}
+
+impl<E: ComplexField> BlockTridiagonalLu<E> {
+    /// Returns the block LU decomposition of the block tridiagonal matrix with the given
+    /// subdiagonal, diagonal and superdiagonal blocks.
+    ///
+    /// The diagonal blocks must be square, and may have different sizes. The `i`-th subdiagonal
+    /// block has the number of rows of `diag[i + 1]` and the number of columns of `diag[i]`, and
+    /// the `i`-th superdiagonal block has the number of rows of `diag[i]` and the number of
+    /// columns of `diag[i + 1]`.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of the blocks don't match.
+    #[track_caller]
+    pub fn new(lower: &[MatRef<'_, E>], diag: &[MatRef<'_, E>], upper: &[MatRef<'_, E>]) -> Self {
+        let nblocks = diag.len();
+        assert!(all(
+            lower.len() == nblocks.saturating_sub(1),
+            upper.len() == nblocks.saturating_sub(1),
+        ));
+        for (i, d) in diag.iter().enumerate() {
+            assert!(d.nrows() == d.ncols());
+            if i + 1 < nblocks {
+                let next = diag[i + 1].nrows();
+                assert!(all(
+                    lower[i].nrows() == next,
+                    lower[i].ncols() == d.nrows(),
+                    upper[i].nrows() == d.nrows(),
+                    upper[i].ncols() == next,
+                ));
+            }
+        }
+
+        let parallelism = get_global_parallelism();
+        let mut offsets = Vec::with_capacity(nblocks + 1);
+        offsets.push(0usize);
+        for d in diag {
+            offsets.push(offsets[offsets.len() - 1] + d.nrows());
+        }
+
+        let mut schur = Vec::with_capacity(nblocks);
+        let mut upper_factors = Vec::with_capacity(nblocks.saturating_sub(1));
+        let mut s = diag.first().map(|d| d.to_owned());
+        for i in 0..nblocks {
+            let lu = PartialPivLu::new(s.take().unwrap().as_ref());
+            if i + 1 < nblocks {
+                // X_i = S_i⁻¹ C_i
+                let mut x = upper[i].to_owned();
+                lu.solve_in_place_with_conj_impl(x.as_mut(), Conj::No);
+
+                // S_{i+1} = D_{i+1} - B_i X_i
+                let mut next = diag[i + 1].to_owned();
+                matmul_with_conj(
+                    next.as_mut(),
+                    lower[i],
+                    Conj::No,
+                    x.as_ref(),
+                    Conj::No,
+                    Some(E::faer_one()),
+                    E::faer_one().faer_neg(),
+                    parallelism,
+                );
+                s = Some(next);
+                upper_factors.push(x);
+            }
+            schur.push(lu);
+        }
+
+        Self {
+            offsets,
+            schur,
+            lower: lower.iter().map(|b| b.to_owned()).collect(),
+            upper: upper_factors,
+        }
+    }
+
+    /// Returns the dimension of the decomposed matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    /// Returns the number of diagonal blocks of the decomposed matrix.
+    #[inline]
+    pub fn nblocks(&self) -> usize {
+        self.schur.len()
+    }
+
+    fn block_size(&self, i: usize) -> usize {
+        self.offsets[i + 1] - self.offsets[i]
+    }
+}
+
+impl<E: ComplexField> SpSolverCore<E> for BlockTridiagonalLu<E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        assert!(rhs.nrows() == self.dim());
+        let parallelism = get_global_parallelism();
+        let nblocks = self.nblocks();
+
+        // L y = b
+        for i in 0..nblocks {
+            let (prev, rest) = rhs.rb_mut().split_at_row_mut(self.offsets[i]);
+            let mut yi = rest.subrows_mut(0, self.block_size(i));
+            if i > 0 {
+                let prev = prev
+                    .rb()
+                    .subrows(self.offsets[i - 1], self.block_size(i - 1));
+                matmul_with_conj(
+                    yi.rb_mut(),
+                    self.lower[i - 1].as_ref(),
+                    conj,
+                    prev,
+                    Conj::No,
+                    Some(E::faer_one()),
+                    E::faer_one().faer_neg(),
+                    parallelism,
+                );
+            }
+            self.schur[i].solve_in_place_with_conj_impl(yi, conj);
+        }
+
+        // U x = y
+        for i in (0..nblocks.saturating_sub(1)).rev() {
+            let (head, next) = rhs.rb_mut().split_at_row_mut(self.offsets[i + 1]);
+            let xi = head.subrows_mut(self.offsets[i], self.block_size(i));
+            let next = next.rb().subrows(0, self.block_size(i + 1));
+            matmul_with_conj(
+                xi,
+                self.upper[i].as_ref(),
+                conj,
+                next,
+                Conj::No,
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                parallelism,
+            );
+        }
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        let mut rhs = rhs;
+        assert!(rhs.nrows() == self.dim());
+        let parallelism = get_global_parallelism();
+        let nblocks = self.nblocks();
+
+        // Uᵀ y = b
+        for i in 1..nblocks {
+            let (prev, rest) = rhs.rb_mut().split_at_row_mut(self.offsets[i]);
+            let yi = rest.subrows_mut(0, self.block_size(i));
+            let prev = prev
+                .rb()
+                .subrows(self.offsets[i - 1], self.block_size(i - 1));
+            matmul_with_conj(
+                yi,
+                self.upper[i - 1].transpose(),
+                conj,
+                prev,
+                Conj::No,
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                parallelism,
+            );
+        }
+
+        // Lᵀ x = y
+        for i in (0..nblocks).rev() {
+            let (head, next) = rhs.rb_mut().split_at_row_mut(self.offsets[i + 1]);
+            let mut xi = head.subrows_mut(self.offsets[i], self.block_size(i));
+            if i + 1 < nblocks {
+                let next = next.rb().subrows(0, self.block_size(i + 1));
+                matmul_with_conj(
+                    xi.rb_mut(),
+                    self.lower[i].transpose(),
+                    conj,
+                    next,
+                    Conj::No,
+                    Some(E::faer_one()),
+                    E::faer_one().faer_neg(),
+                    parallelism,
+                );
+            }
+            self.schur[i].solve_transpose_in_place_with_conj_impl(xi, conj);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, sparse::linalg::solvers::SpSolver};
+
+    fn random_c64() -> c64 {
+        c64::new(rand::random::<f64>() - 0.5, rand::random::<f64>() - 0.5)
+    }
+
+    fn check_solves<S: SpSolver<c64>>(solver: &S, dense: &Mat<c64>) {
+        let rhs = Mat::<c64>::from_fn(dense.nrows(), 3, |_, _| random_c64());
+        for (x, op) in [
+            (solver.solve(&rhs), dense.clone()),
+            (solver.solve_conj(&rhs), dense.conjugate().to_owned()),
+            (solver.solve_transpose(&rhs), dense.transpose().to_owned()),
+            (
+                solver.solve_conj_transpose(&rhs),
+                dense.adjoint().to_owned(),
+            ),
+        ] {
+            assert!((&op * &x - &rhs).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_tridiagonal() {
+        for n in [0, 1, 2, 10, 100] {
+            let m = n.saturating_sub(1);
+            let lower = Col::<c64>::from_fn(m, |_| random_c64());
+            let upper = Col::<c64>::from_fn(m, |_| random_c64());
+            // diagonally dominant
+            let diag = Col::<c64>::from_fn(n, |_| random_c64() + c64::new(3.0, 0.0));
+
+            let mut dense = Mat::<c64>::zeros(n, n);
+            for i in 0..n {
+                dense.write(i, i, diag.read(i));
+            }
+            for i in 0..m {
+                dense.write(i + 1, i, lower.read(i));
+                dense.write(i, i + 1, upper.read(i));
+            }
+
+            let lu = TridiagonalLu::new(lower.as_ref(), diag.as_ref(), upper.as_ref());
+            check_solves(&lu, &dense);
+        }
+    }
+
+    #[test]
+    fn test_block_tridiagonal() {
+        let sizes = [3, 1, 4, 2, 3];
+        let nblocks = sizes.len();
+        let n: usize = sizes.iter().sum();
+        let diag = (0..nblocks)
+            .map(|i| {
+                Mat::<c64>::from_fn(sizes[i], sizes[i], |r, c| {
+                    // diagonally dominant
+                    if r == c {
+                        random_c64() + c64::new(8.0, 0.0)
+                    } else {
+                        random_c64()
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        let lower = (0..nblocks - 1)
+            .map(|i| Mat::<c64>::from_fn(sizes[i + 1], sizes[i], |_, _| random_c64()))
+            .collect::<Vec<_>>();
+        let upper = (0..nblocks - 1)
+            .map(|i| Mat::<c64>::from_fn(sizes[i], sizes[i + 1], |_, _| random_c64()))
+            .collect::<Vec<_>>();
+
+        let mut dense = Mat::<c64>::zeros(n, n);
+        let mut offset = 0;
+        for i in 0..nblocks {
+            let next = offset + sizes[i];
+            dense
+                .as_mut()
+                .submatrix_mut(offset, offset, sizes[i], sizes[i])
+                .copy_from(&diag[i]);
+            if i + 1 < nblocks {
+                dense
+                    .as_mut()
+                    .submatrix_mut(next, offset, sizes[i + 1], sizes[i])
+                    .copy_from(&lower[i]);
+                dense
+                    .as_mut()
+                    .submatrix_mut(offset, next, sizes[i], sizes[i + 1])
+                    .copy_from(&upper[i]);
+            }
+            offset = next;
+        }
+
+        let lu = BlockTridiagonalLu::new(
+            &lower.iter().map(|m| m.as_ref()).collect::<Vec<_>>(),
+            &diag.iter().map(|m| m.as_ref()).collect::<Vec<_>>(),
+            &upper.iter().map(|m| m.as_ref()).collect::<Vec<_>>(),
+        );
+        assert!(lu.nblocks() == nblocks);
+        check_solves(&lu, &dense);
+    }
+}
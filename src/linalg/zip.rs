@@ -1841,6 +1841,100 @@ fn for_each_row<Z: for<'a> MatIndex<'a, Rows = (), Cols = usize, Index = ((), us
     }
 }
 
+/// Matrix views that can be split into two disjoint views. Used for parallel zipping.
+pub trait MatSplit: MatShape<Rows = usize, Cols = usize> + Sized {
+    /// Splits the view at the given row, returning the top and bottom parts.
+    fn split_at_row(self, row: usize) -> (Self, Self);
+    /// Splits the view at the given column, returning the left and right parts.
+    fn split_at_col(self, col: usize) -> (Self, Self);
+}
+
+impl<E: Entity> MatSplit for MatRef<'_, E> {
+    #[inline(always)]
+    fn split_at_row(self, row: usize) -> (Self, Self) {
+        MatRef::split_at_row(self, row)
+    }
+    #[inline(always)]
+    fn split_at_col(self, col: usize) -> (Self, Self) {
+        MatRef::split_at_col(self, col)
+    }
+}
+
+impl<E: Entity> MatSplit for MatMut<'_, E> {
+    #[inline(always)]
+    fn split_at_row(self, row: usize) -> (Self, Self) {
+        self.split_at_row_mut(row)
+    }
+    #[inline(always)]
+    fn split_at_col(self, col: usize) -> (Self, Self) {
+        self.split_at_col_mut(col)
+    }
+}
+
+impl<M: MatSplit> MatSplit for LastEq<usize, usize, M> {
+    #[inline(always)]
+    fn split_at_row(self, row: usize) -> (Self, Self) {
+        let (top, bot) = self.0.split_at_row(row);
+        (LastEq(top), LastEq(bot))
+    }
+    #[inline(always)]
+    fn split_at_col(self, col: usize) -> (Self, Self) {
+        let (left, right) = self.0.split_at_col(col);
+        (LastEq(left), LastEq(right))
+    }
+}
+
+impl<Head: MatSplit, Tail: MatSplit> MatSplit for ZipEq<usize, usize, Head, Tail> {
+    #[inline(always)]
+    fn split_at_row(self, row: usize) -> (Self, Self) {
+        let (head_top, head_bot) = self.0.split_at_row(row);
+        let (tail_top, tail_bot) = self.1.split_at_row(row);
+        (
+            ZipEq::new_unchecked(head_top, tail_top),
+            ZipEq::new_unchecked(head_bot, tail_bot),
+        )
+    }
+    #[inline(always)]
+    fn split_at_col(self, col: usize) -> (Self, Self) {
+        let (head_left, head_right) = self.0.split_at_col(col);
+        let (tail_left, tail_right) = self.1.split_at_col(col);
+        (
+            ZipEq::new_unchecked(head_left, tail_left),
+            ZipEq::new_unchecked(head_right, tail_right),
+        )
+    }
+}
+
+// splits `z` in halves recursively until the parallelism is exhausted, and calls `op` on each
+// part along with the position of its top left corner in `z`
+fn par_split_mat<Z: MatSplit + Send>(
+    z: Z,
+    row_offset: usize,
+    col_offset: usize,
+    parallelism: Parallelism,
+    op: &(impl Sync + Fn(Z, usize, usize)),
+) {
+    let (m, n) = (z.nrows(), z.ncols());
+    if crate::utils::thread::parallelism_degree(parallelism) <= 1 || (m < 2 && n < 2) {
+        op(z, row_offset, col_offset);
+    } else if n >= 2 {
+        // the columns are split first, since they are contiguous for column major matrices
+        let (left, right) = z.split_at_col(n / 2);
+        crate::utils::thread::join_raw(
+            |parallelism| par_split_mat(left, row_offset, col_offset, parallelism, op),
+            |parallelism| par_split_mat(right, row_offset, col_offset + n / 2, parallelism, op),
+            parallelism,
+        );
+    } else {
+        let (top, bot) = z.split_at_row(m / 2);
+        crate::utils::thread::join_raw(
+            |parallelism| par_split_mat(top, row_offset, col_offset, parallelism, op),
+            |parallelism| par_split_mat(bot, row_offset + m / 2, col_offset, parallelism, op),
+            parallelism,
+        );
+    }
+}
+
 impl<
         M: for<'a> MatIndex<
             'a,
@@ -1956,6 +2050,33 @@ impl<
         unsafe { out.set_dims(m, n) };
         out
     }
+
+    /// Applies `f` to each element of `self`, possibly in parallel by splitting the matrices
+    /// into disjoint blocks that are processed by different threads.
+    pub fn par_for_each(
+        self,
+        parallelism: Parallelism,
+        f: impl Sync + for<'a> Fn(<Self as MatIndex<'a>>::Item),
+    ) where
+        Self: MatSplit + Send,
+    {
+        par_split_mat(self, 0, 0, parallelism, &|z: Self, _, _| z.for_each(&f));
+    }
+
+    /// Applies `f` to each element of `self`, while passing the indices of the position of the
+    /// current element, possibly in parallel by splitting the matrices into disjoint blocks that
+    /// are processed by different threads.
+    pub fn par_for_each_with_index(
+        self,
+        parallelism: Parallelism,
+        f: impl Sync + for<'a> Fn(usize, usize, <Self as MatIndex<'a>>::Item),
+    ) where
+        Self: MatSplit + Send,
+    {
+        par_split_mat(self, 0, 0, parallelism, &|z: Self, i0, j0| {
+            z.for_each_with_index(|i, j, item| f(i0 + i, j0 + j, item))
+        });
+    }
 }
 
 impl<
@@ -2274,6 +2395,33 @@ impl<
         unsafe { out.set_dims(m, n) };
         out
     }
+
+    /// Applies `f` to each element of `self`, possibly in parallel by splitting the matrices
+    /// into disjoint blocks that are processed by different threads.
+    pub fn par_for_each(
+        self,
+        parallelism: Parallelism,
+        f: impl Sync + for<'a> Fn(<Self as MatIndex<'a>>::Item),
+    ) where
+        Self: MatSplit + Send,
+    {
+        par_split_mat(self, 0, 0, parallelism, &|z: Self, _, _| z.for_each(&f));
+    }
+
+    /// Applies `f` to each element of `self`, while passing the indices of the position of the
+    /// current element, possibly in parallel by splitting the matrices into disjoint blocks that
+    /// are processed by different threads.
+    pub fn par_for_each_with_index(
+        self,
+        parallelism: Parallelism,
+        f: impl Sync + for<'a> Fn(usize, usize, <Self as MatIndex<'a>>::Item),
+    ) where
+        Self: MatSplit + Send,
+    {
+        par_split_mat(self, 0, 0, parallelism, &|z: Self, i0, j0| {
+            z.for_each_with_index(|i, j, item| f(i0 + i, j0 + j, item))
+        });
+    }
 }
 
 #[cfg(test)]
@@ -2596,4 +2744,30 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_par_zip() {
+        let parallelism = Parallelism::None;
+        #[cfg(feature = "rayon")]
+        let parallelism = Parallelism::Rayon(4);
+
+        let a = Mat::from_fn(13, 7, |i, j| (i + 100 * j) as f64);
+        let b = Mat::from_fn(13, 7, |i, j| (i * j) as f64);
+        let mut sum = Mat::<f64>::zeros(13, 7);
+        zipped!(sum.as_mut(), a.as_ref(), b.as_ref().reverse_rows())
+            .par_for_each(parallelism, |unzipped!(mut sum, a, b)| {
+                sum.write(a.read() + b.read())
+            });
+        assert!(sum == &a + b.as_ref().reverse_rows());
+
+        let mut idx = Mat::<f64>::zeros(1, 9);
+        zipped!(idx.as_mut().transpose_mut()).par_for_each_with_index(
+            parallelism,
+            |i, j, unzipped!(mut x)| {
+                assert!(j == 0);
+                x.write(i as f64)
+            },
+        );
+        assert!(idx == Mat::from_fn(1, 9, |_, j| j as f64));
+    }
 }
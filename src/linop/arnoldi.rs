@@ -0,0 +1,463 @@
+//! Restarted Arnoldi eigensolver for general operators.
+//!
+//! [`arnoldi`] computes a few eigenpairs of a possibly non-self-adjoint operator, accessing it
+//! only through the [`LinOp`] trait. The Krylov basis is kept orthonormal with full
+//! reorthogonalization, and restarted with the Krylov-Schur method, which is mathematically
+//! equivalent to the implicitly restarted Arnoldi method with exact shifts.
+//!
+//! The eigenvalues of a real operator are either real or come in complex conjugate pairs, so the
+//! eigenpairs are always returned in a complex scalar type.
+//!
+//! Interior eigenvalues close to a shift $\sigma$ can be computed in shift-invert mode, by
+//! passing a [`ShiftInvert`](crate::linop::ShiftInvert) operator wrapping a factorization of
+//! $A - \sigma I$, and targeting the eigenvalues with the largest magnitude.
+
+use crate::{
+    linalg::{matmul::matmul, temp_mat_req, temp_mat_uninit},
+    linop::{
+        krylov_schur::{is_real, krylov_schur, krylov_schur_req, pair_start, KrylovSchurParams},
+        EigenvalueTarget, LinOp,
+    },
+    prelude::*,
+    ComplexField, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use equator::assert;
+use reborrow::*;
+
+/// Parameters of the restarted Arnoldi eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct ArnoldiParams<E: ComplexField> {
+    /// Which end of the spectrum the computed eigenvalues are taken from.
+    pub target: EigenvalueTarget,
+    /// Absolute tolerance on the residual norm of each Ritz pair.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residual norm of each Ritz pair, relative to the largest absolute Ritz
+    /// value.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of restarts.
+    pub max_restarts: usize,
+}
+
+/// Information about a successful run of the restarted Arnoldi eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct ArnoldiInfo<E: ComplexField> {
+    /// Largest residual norm of the computed Ritz pairs.
+    pub abs_residual: E::Real,
+    /// Largest residual norm of the computed Ritz pairs, relative to the largest absolute Ritz
+    /// value.
+    pub rel_residual: E::Real,
+    /// Number of restarts performed.
+    pub iter_count: usize,
+}
+
+/// Error of the restarted Arnoldi eigensolver.
+#[derive(Copy, Clone, Debug)]
+pub enum ArnoldiError<E: ComplexField> {
+    /// The Ritz pairs did not converge within the maximum number of restarts.
+    NoConvergence {
+        /// Largest residual norm of the last computed Ritz pairs.
+        abs_residual: E::Real,
+        /// Largest residual norm of the last computed Ritz pairs, relative to the largest
+        /// absolute Ritz value.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for ArnoldiParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            target: EigenvalueTarget::LargestMagnitude,
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_mul(E::Real::faer_from_f64(128.0)),
+            max_restarts: 1000,
+        }
+    }
+}
+
+/// Computes the size and alignment of the workspace required to compute `n_eigs` eigenpairs
+/// with [`arnoldi`], using a Krylov basis of dimension `krylov_dim`.
+pub fn arnoldi_req<E: ComplexField>(
+    mat: impl LinOp<E>,
+    n_eigs: usize,
+    krylov_dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        A: &dyn LinOp<E>,
+        k: usize,
+        m: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = k;
+        let n = A.nrows();
+        StackReq::try_all_of([
+            temp_mat_req::<E>(n, m + 1)?,   // v
+            temp_mat_req::<E>(m + 1, m)?,   // h
+            temp_mat_req::<E::Real>(m, 1)?, // s_re
+            temp_mat_req::<E::Real>(m, 1)?, // s_im
+            temp_mat_req::<E>(m, m)?,       // u
+            StackReq::try_new::<usize>(m)?, // perm
+            StackReq::try_any_of([
+                krylov_schur_req::<E>(A, m, false, parallelism)?,
+                temp_mat_req::<E>(n, 2)?, // real and imaginary parts of a ritz vector
+            ])?,
+        ])
+    }
+    implementation(&mat, n_eigs, krylov_dim, parallelism)
+}
+
+/// Computes `eigenvalues.nrows()` eigenpairs of the operator `mat`, using a Krylov basis of
+/// dimension `krylov_dim`, and the starting vector `start`.
+///
+/// The eigenvalues are sorted according to `params.target`, and stored in `eigenvalues`, and the
+/// corresponding eigenvectors, normalized to unit norm, are stored in `eigenvectors`. The outputs
+/// are filled even if the iteration does not converge.
+///
+/// `E` may be a real type, in which case `C` is the corresponding complex type, or a complex
+/// type, in which case `C` must be the same as `E`.
+///
+/// # Panics
+/// Panics if `C` is not a complex type compatible with `E`, if `mat` is not square, if `start` or
+/// `eigenvectors` don't have `mat.nrows()` rows, if `eigenvectors` doesn't have
+/// `eigenvalues.nrows()` columns, if `krylov_dim` is smaller than the number of eigenvalues plus
+/// two or is larger than `mat.nrows()`, or if the provided memory in `stack` is insufficient (see
+/// [`arnoldi_req`]).
+#[track_caller]
+pub fn arnoldi<E: ComplexField, C: ComplexField<Real = E::Real>>(
+    eigenvectors: MatMut<'_, C>,
+    eigenvalues: ColMut<'_, C>,
+    mat: impl LinOp<E>,
+    start: ColRef<'_, E>,
+    krylov_dim: usize,
+    params: ArnoldiParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<ArnoldiInfo<E>, ArnoldiError<E>> {
+    #[track_caller]
+    fn implementation<E: ComplexField, C: ComplexField<Real = E::Real>>(
+        mut x: MatMut<'_, C>,
+        mut eigenvalues: ColMut<'_, C>,
+        A: &dyn LinOp<E>,
+        start: ColRef<'_, E>,
+        m: usize,
+        params: ArnoldiParams<E>,
+        par: Parallelism,
+        stack: PodStack<'_>,
+    ) -> Result<ArnoldiInfo<E>, ArnoldiError<E>> {
+        let n = A.nrows();
+        let k = eigenvalues.nrows();
+        assert!(all(
+            !is_real::<C>(),
+            any(is_real::<E>(), coe::is_same::<E, C>()),
+        ));
+        assert!(all(
+            A.ncols() == n,
+            start.nrows() == n,
+            x.nrows() == n,
+            x.ncols() == k,
+            k + 2 <= m,
+            m <= n,
+        ));
+
+        let (mut v, stack) = temp_mat_uninit::<E>(n, m + 1, stack);
+        let (mut h, stack) = temp_mat_uninit::<E>(m + 1, m, stack);
+        let (mut s_re, stack) = temp_mat_uninit::<E::Real>(m, 1, stack);
+        let (mut s_im, stack) = temp_mat_uninit::<E::Real>(m, 1, stack);
+        let (mut u, stack) = temp_mat_uninit::<E>(m, m, stack);
+        let (perm, mut stack) = stack.make_with(m, |i| i);
+
+        let info = krylov_schur(
+            v.rb_mut(),
+            h.rb_mut(),
+            s_re.rb_mut().col_mut(0),
+            s_im.rb_mut().col_mut(0),
+            u.rb_mut(),
+            perm,
+            A,
+            start,
+            k,
+            KrylovSchurParams {
+                target: params.target,
+                abs_tolerance: params.abs_tolerance,
+                rel_tolerance: params.rel_tolerance,
+                max_restarts: params.max_restarts,
+                hermitian: false,
+            },
+            par,
+            stack.rb_mut(),
+        );
+
+        let i_unit = C::faer_from_f64(-1.0).faer_sqrt();
+        let (mut y, _) = temp_mat_uninit::<E>(n, 2, stack.rb_mut());
+        let v = v.rb().get(.., ..m);
+        let s_im = s_im.rb().col(0);
+        for (j, &idx) in perm[..k].iter().enumerate() {
+            eigenvalues.write(
+                j,
+                C::faer_from_real(s_re.read(idx, 0))
+                    .faer_add(i_unit.faer_scale_real(s_im.read(idx))),
+            );
+
+            let mut x = x.rb_mut().col_mut(j);
+            let pair = if is_real::<E>() {
+                pair_start(s_im, idx)
+            } else {
+                None
+            };
+            match pair {
+                Some(p) => {
+                    // the eigenvector is (u_re ± i u_im), where the sign is that of the
+                    // imaginary part of the eigenvalue
+                    matmul(
+                        y.rb_mut(),
+                        v,
+                        u.rb().subcols(p, 2),
+                        None,
+                        E::faer_one(),
+                        par,
+                    );
+                    let sign = if idx == p {
+                        E::Real::faer_one()
+                    } else {
+                        E::Real::faer_one().faer_neg()
+                    };
+                    for i in 0..n {
+                        let re = y.read(i, 0).faer_real();
+                        let im = y.read(i, 1).faer_real().faer_mul(sign);
+                        x.write(
+                            i,
+                            C::faer_from_real(re).faer_add(i_unit.faer_scale_real(im)),
+                        );
+                    }
+                }
+                None => {
+                    let mut y = y.rb_mut().col_mut(0);
+                    matmul(
+                        y.rb_mut().as_2d_mut(),
+                        v,
+                        u.rb().col(idx).as_2d(),
+                        None,
+                        E::faer_one(),
+                        par,
+                    );
+                    for i in 0..n {
+                        let value = y.read(i);
+                        if is_real::<E>() {
+                            x.write(i, C::faer_from_real(value.faer_real()));
+                        } else {
+                            x.write(i, coe::coerce_static::<E, C>(value));
+                        }
+                    }
+                }
+            }
+
+            let norm = x.rb().norm_l2();
+            if norm > E::Real::faer_zero() {
+                let inv = norm.faer_inv();
+                zipped!(x.as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(inv)));
+            }
+        }
+
+        if info.converged {
+            Ok(ArnoldiInfo {
+                abs_residual: info.abs_residual,
+                rel_residual: info.rel_residual,
+                iter_count: info.iter_count,
+            })
+        } else {
+            Err(ArnoldiError::NoConvergence {
+                abs_residual: info.abs_residual,
+                rel_residual: info.rel_residual,
+            })
+        }
+    }
+
+    implementation(
+        eigenvectors,
+        eigenvalues,
+        &mat,
+        start,
+        krylov_dim,
+        params,
+        parallelism,
+        stack,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linop::ShiftInvert, sparse::SparseColMat};
+    use dyn_stack::GlobalPodBuffer;
+    use equator::assert;
+    use rand::prelude::*;
+
+    fn check_eigenpairs<E: ComplexField<Real = f64>>(
+        A: MatRef<'_, E>,
+        x: MatRef<'_, c64>,
+        eigenvalues: ColRef<'_, c64>,
+    ) {
+        let n = A.nrows();
+        let A = Mat::<c64>::from_fn(n, n, |i, j| {
+            let a = A.read(i, j);
+            c64::new(a.faer_real(), a.faer_imag())
+        });
+        for j in 0..x.ncols() {
+            let r = &A * x.col(j) - x.col(j) * crate::scale(eigenvalues.read(j));
+            assert!(r.norm_l2() < 1e-8);
+            assert!((x.col(j).norm_l2() - 1.0).abs() < 1e-10);
+        }
+    }
+
+    fn sorted_by_magnitude(mut eigenvalues: alloc::vec::Vec<c64>) -> alloc::vec::Vec<c64> {
+        eigenvalues.sort_by(|a, b| b.norm().partial_cmp(&a.norm()).unwrap());
+        eigenvalues
+    }
+
+    #[test]
+    fn test_arnoldi_real() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let n = 60;
+        let k = 3;
+        let m = 20;
+        // well separated dominant eigenvalues, including a complex conjugate pair
+        let mut A = Mat::<f64>::from_fn(n, n, |_, _| 0.05 * rng.gen::<f64>());
+        for i in 0..n {
+            A.write(i, i, A.read(i, i) + 1.0 + i as f64 / n as f64);
+        }
+        A.write(0, 0, 10.0);
+        A.write(1, 1, 8.0);
+        A.write(1, 2, 3.0);
+        A.write(2, 1, -3.0);
+        A.write(2, 2, 8.0);
+        let start = Col::<f64>::from_fn(n, |i| 1.0 + (i % 3) as f64);
+
+        let mut x = Mat::<c64>::zeros(n, k);
+        let mut eigenvalues = Col::<c64>::zeros(k);
+        arnoldi(
+            x.as_mut(),
+            eigenvalues.as_mut(),
+            A.as_ref(),
+            start.as_ref(),
+            m,
+            ArnoldiParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                arnoldi_req::<f64>(A.as_ref(), k, m, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        let expected = sorted_by_magnitude(A.eigenvalues::<c64>());
+        for j in 0..k {
+            // the order within a conjugate pair is unspecified
+            let lambda = eigenvalues.read(j);
+            assert!(expected[..k].iter().any(|&mu| (lambda - mu).norm() < 1e-8));
+        }
+        check_eigenpairs(A.as_ref(), x.as_ref(), eigenvalues.as_ref());
+    }
+
+    #[test]
+    fn test_arnoldi_complex() {
+        let rng = &mut StdRng::seed_from_u64(1);
+        let n = 50;
+        let k = 3;
+        let m = 16;
+        let mut A = Mat::<c64>::from_fn(n, n, |_, _| {
+            c64::new(0.05 * rng.gen::<f64>(), 0.05 * rng.gen::<f64>())
+        });
+        for i in 0..n {
+            A.write(i, i, A.read(i, i) + c64::new(i as f64 / n as f64, 0.0));
+        }
+        A.write(0, 0, c64::new(5.0, 5.0));
+        A.write(1, 1, c64::new(-6.0, 0.0));
+        A.write(2, 2, c64::new(0.0, 4.0));
+        let start = Col::<c64>::from_fn(n, |i| c64::new(1.0, (i % 4) as f64));
+
+        let mut x = Mat::<c64>::zeros(n, k);
+        let mut eigenvalues = Col::<c64>::zeros(k);
+        arnoldi(
+            x.as_mut(),
+            eigenvalues.as_mut(),
+            A.as_ref(),
+            start.as_ref(),
+            m,
+            ArnoldiParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                arnoldi_req::<c64>(A.as_ref(), k, m, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        let expected = sorted_by_magnitude(A.eigenvalues::<c64>());
+        for j in 0..k {
+            assert!((eigenvalues.read(j) - expected[j]).norm() < 1e-8);
+        }
+        check_eigenpairs(A.as_ref(), x.as_ref(), eigenvalues.as_ref());
+    }
+
+    #[test]
+    fn test_arnoldi_shift_invert() {
+        // convection-diffusion operator, with real eigenvalues
+        let n = 100;
+        let k = 3;
+        let m = 12;
+        let shift = 1.0;
+        let make = |shift: f64| {
+            let mut triplets = alloc::vec::Vec::new();
+            for i in 0..n {
+                triplets.push((i, i, 2.0 - shift));
+                if i + 1 < n {
+                    triplets.push((i + 1, i, -0.5));
+                    triplets.push((i, i + 1, -1.5));
+                }
+            }
+            SparseColMat::<usize, f64>::try_new_from_triplets(n, n, &triplets).unwrap()
+        };
+        let A = make(0.0);
+        let lu = make(shift).sp_lu().unwrap();
+        let op = ShiftInvert::new(&lu, shift);
+        let start = Col::<f64>::from_fn(n, |i| 1.0 + (i % 5) as f64);
+
+        let mut x = Mat::<c64>::zeros(n, k);
+        let mut theta = Col::<c64>::zeros(k);
+        arnoldi(
+            x.as_mut(),
+            theta.as_mut(),
+            op,
+            start.as_ref(),
+            m,
+            ArnoldiParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                arnoldi_req::<f64>(op, k, m, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        // eigenvalues of the tridiagonal toeplitz matrix: 2 - 2 sqrt(0.75) cos(jπ / (n + 1))
+        let mut exact = (1..=n)
+            .map(|j| {
+                2.0 - 2.0
+                    * f64::sqrt(0.75)
+                    * f64::cos(j as f64 * core::f64::consts::PI / (n + 1) as f64)
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        exact.sort_by(|a, b| (a - shift).abs().partial_cmp(&(b - shift).abs()).unwrap());
+
+        let eigenvalues = Col::<c64>::from_fn(k, |j| {
+            let theta = theta.read(j);
+            c64::new(shift, 0.0) + theta.inv()
+        });
+        for j in 0..k {
+            assert!((eigenvalues.read(j) - c64::new(exact[j], 0.0)).norm() < 1e-8);
+        }
+        check_eigenpairs(A.to_dense().as_ref(), x.as_ref(), eigenvalues.as_ref());
+    }
+}
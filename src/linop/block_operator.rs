@@ -0,0 +1,1002 @@
+//! Block structured linear operators.
+//!
+//! A [`BlockOperator`] is a linear operator partitioned into a grid of sub-blocks, each of which
+//! may be stored in a different format (dense, sparse, diagonal, low-rank, or an arbitrary
+//! [`BiLinOp`]). This allows saddle-point and KKT systems such as
+//! $$\begin{bmatrix} A & B^H \\ B & -C \end{bmatrix}$$
+//! to be passed to the iterative solvers without assembling the full matrix.
+//!
+//! Block preconditioners can be built from the block structure using
+//! [`BlockOperator::block_diagonal_precond`] and [`BlockOperator::schur_complement_precond`].
+
+use crate::{
+    assert,
+    col::ColRef,
+    linalg::{matmul::matmul_with_conj, temp_mat_req, temp_mat_uninit},
+    linop::{BiLinOp, LinOp, Precond},
+    sparse::{
+        linalg::matmul::{dense_sparse_matmul, sparse_dense_matmul},
+        SparseColMatRef,
+    },
+    unzipped, zipped, ComplexField, Conj, Index, MatMut, MatRef, Parallelism,
+};
+use alloc::vec::Vec;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Sub-block of a [`BlockOperator`].
+#[derive(Copy, Clone, Debug)]
+pub enum Block<'a, I: Index, E: ComplexField> {
+    /// Block with all entries equal to zero.
+    Zero,
+    /// Dense block.
+    Dense(MatRef<'a, E>),
+    /// Sparse block.
+    Sparse(SparseColMatRef<'a, I, E>),
+    /// Diagonal block, whose diagonal entries are given by the column vector.
+    Diagonal(ColRef<'a, E>),
+    /// Low-rank block, equal to $UV^H$.
+    LowRank {
+        /// Left factor.
+        u: MatRef<'a, E>,
+        /// Right factor.
+        v: MatRef<'a, E>,
+    },
+    /// Block given by an arbitrary linear operator.
+    Op(&'a dyn BiLinOp<E>),
+}
+
+impl<I: Index, E: ComplexField> Block<'_, I, E> {
+    /// Returns the dimensions of the block, or `None` if the block is [`Block::Zero`].
+    ///
+    /// # Panics
+    /// Panics if the block is [`Block::LowRank`] and its factors don't have the same number of
+    /// columns.
+    #[track_caller]
+    pub fn dims(&self) -> Option<(usize, usize)> {
+        match *self {
+            Block::Zero => None,
+            Block::Dense(mat) => Some((mat.nrows(), mat.ncols())),
+            Block::Sparse(mat) => Some((mat.nrows(), mat.ncols())),
+            Block::Diagonal(diag) => Some((diag.nrows(), diag.nrows())),
+            Block::LowRank { u, v } => {
+                assert!(u.ncols() == v.ncols());
+                Some((u.nrows(), v.nrows()))
+            }
+            Block::Op(op) => Some((op.nrows(), op.ncols())),
+        }
+    }
+
+    fn req(
+        &self,
+        transpose: bool,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        match *self {
+            Block::Zero | Block::Dense(_) | Block::Sparse(_) | Block::Diagonal(_) => {
+                Ok(StackReq::empty())
+            }
+            Block::LowRank { u, .. } => temp_mat_req::<E>(u.ncols(), rhs_ncols),
+            Block::Op(op) => {
+                let (nrows, req) = if transpose {
+                    (op.ncols(), op.transpose_apply_req(rhs_ncols, parallelism)?)
+                } else {
+                    (op.nrows(), op.apply_req(rhs_ncols, parallelism)?)
+                };
+                temp_mat_req::<E>(nrows, rhs_ncols)?.try_and(req)
+            }
+        }
+    }
+
+    /// Computes `acc += beta * op(self) * rhs`, where `op` is either the identity or the
+    /// transpose, optionally followed by a conjugation.
+    fn acc_apply(
+        &self,
+        acc: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj: Conj,
+        transpose: bool,
+        beta: E,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let one = E::faer_one();
+        let mut acc = acc;
+        match *self {
+            Block::Zero => {}
+            Block::Dense(mat) => {
+                let mat = if transpose { mat.transpose() } else { mat };
+                matmul_with_conj(acc, mat, conj, rhs, Conj::No, Some(one), beta, parallelism);
+            }
+            Block::Sparse(mat) => match (transpose, conj) {
+                (false, Conj::No) => {
+                    sparse_dense_matmul(acc, mat, rhs, Some(one), beta, parallelism)
+                }
+                (false, Conj::Yes) => {
+                    sparse_dense_matmul(acc, mat.conjugate(), rhs, Some(one), beta, parallelism)
+                }
+                (true, Conj::No) => dense_sparse_matmul(
+                    acc.transpose_mut(),
+                    rhs.transpose(),
+                    mat,
+                    Some(one),
+                    beta,
+                    parallelism,
+                ),
+                (true, Conj::Yes) => dense_sparse_matmul(
+                    acc.transpose_mut(),
+                    rhs.transpose(),
+                    mat.conjugate(),
+                    Some(one),
+                    beta,
+                    parallelism,
+                ),
+            },
+            Block::Diagonal(diag) => {
+                for j in 0..rhs.ncols() {
+                    for i in 0..rhs.nrows() {
+                        let d = diag.read(i);
+                        let d = if conj == Conj::Yes { d.faer_conj() } else { d };
+                        acc.write(
+                            i,
+                            j,
+                            acc.read(i, j)
+                                .faer_add(beta.faer_mul(d.faer_mul(rhs.read(i, j)))),
+                        );
+                    }
+                }
+            }
+            Block::LowRank { u, v } => {
+                // op(U V^H) is either U V^H, or conj(V) U^T
+                let (left, left_conj, right, right_conj) = if transpose {
+                    (v, Conj::Yes.compose(conj), u, conj)
+                } else {
+                    (u, conj, v, Conj::Yes.compose(conj))
+                };
+                let (mut tmp, _) = temp_mat_uninit::<E>(u.ncols(), rhs.ncols(), stack);
+                matmul_with_conj(
+                    tmp.rb_mut(),
+                    right.transpose(),
+                    right_conj,
+                    rhs,
+                    Conj::No,
+                    None,
+                    one,
+                    parallelism,
+                );
+                matmul_with_conj(
+                    acc,
+                    left,
+                    left_conj,
+                    tmp.rb(),
+                    Conj::No,
+                    Some(one),
+                    beta,
+                    parallelism,
+                );
+            }
+            Block::Op(op) => {
+                let nrows = if transpose { op.ncols() } else { op.nrows() };
+                let (mut tmp, stack) = temp_mat_uninit::<E>(nrows, rhs.ncols(), stack);
+                match (transpose, conj) {
+                    (false, Conj::No) => op.apply(tmp.rb_mut(), rhs, parallelism, stack),
+                    (false, Conj::Yes) => op.conj_apply(tmp.rb_mut(), rhs, parallelism, stack),
+                    (true, Conj::No) => op.transpose_apply(tmp.rb_mut(), rhs, parallelism, stack),
+                    (true, Conj::Yes) => op.adjoint_apply(tmp.rb_mut(), rhs, parallelism, stack),
+                }
+                zipped!(acc, tmp.rb()).for_each(|unzipped!(mut acc, tmp)| {
+                    acc.write(acc.read().faer_add(beta.faer_mul(tmp.read())))
+                });
+            }
+        }
+    }
+}
+
+/// Linear operator partitioned into a grid of sub-blocks.
+#[derive(Clone, Debug)]
+pub struct BlockOperator<'a, I: Index, E: ComplexField> {
+    row_dims: Vec<usize>,
+    col_dims: Vec<usize>,
+    row_offsets: Vec<usize>,
+    col_offsets: Vec<usize>,
+    // stored in column-major order
+    blocks: Vec<Block<'a, I, E>>,
+}
+
+fn offsets(dims: &[usize]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(dims.len() + 1);
+    let mut acc = 0usize;
+    offsets.push(0);
+    for &dim in dims {
+        acc += dim;
+        offsets.push(acc);
+    }
+    offsets
+}
+
+impl<'a, I: Index, E: ComplexField> BlockOperator<'a, I, E> {
+    /// Creates a new block operator where the `i`-th block row has `row_dims[i]` rows and the
+    /// `j`-th block column has `col_dims[j]` columns. All the blocks are initially zero.
+    pub fn new(row_dims: &[usize], col_dims: &[usize]) -> Self {
+        Self {
+            row_dims: row_dims.to_vec(),
+            col_dims: col_dims.to_vec(),
+            row_offsets: offsets(row_dims),
+            col_offsets: offsets(col_dims),
+            blocks: alloc::vec![Block::Zero; row_dims.len() * col_dims.len()],
+        }
+    }
+
+    /// Returns the number of block rows.
+    #[inline]
+    pub fn nrow_blocks(&self) -> usize {
+        self.row_dims.len()
+    }
+
+    /// Returns the number of block columns.
+    #[inline]
+    pub fn ncol_blocks(&self) -> usize {
+        self.col_dims.len()
+    }
+
+    /// Returns the number of rows of each block row.
+    #[inline]
+    pub fn row_dims(&self) -> &[usize] {
+        &self.row_dims
+    }
+
+    /// Returns the number of columns of each block column.
+    #[inline]
+    pub fn col_dims(&self) -> &[usize] {
+        &self.col_dims
+    }
+
+    /// Returns the block at position `(i, j)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn block(&self, i: usize, j: usize) -> &Block<'a, I, E> {
+        assert!(all(i < self.nrow_blocks(), j < self.ncol_blocks()));
+        &self.blocks[i + self.nrow_blocks() * j]
+    }
+
+    /// Sets the block at position `(i, j)` to `block`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds, or if the dimensions of the block don't match the
+    /// dimensions of the block row and column.
+    #[track_caller]
+    pub fn set_block(&mut self, i: usize, j: usize, block: Block<'a, I, E>) {
+        assert!(all(i < self.nrow_blocks(), j < self.ncol_blocks()));
+        if let Some((nrows, ncols)) = block.dims() {
+            assert!(all(nrows == self.row_dims[i], ncols == self.col_dims[j]));
+        }
+        let nrow_blocks = self.nrow_blocks();
+        self.blocks[i + nrow_blocks * j] = block;
+    }
+
+    /// Sets the block at position `(i, j)` to `block`, and returns `self`.
+    ///
+    /// # Panics
+    /// See [`Self::set_block`].
+    #[track_caller]
+    pub fn with_block(mut self, i: usize, j: usize, block: Block<'a, I, E>) -> Self {
+        self.set_block(i, j, block);
+        self
+    }
+
+    /// Returns a block diagonal preconditioner, where the `i`-th diagonal block is given by
+    /// `inverses[i]`, which should approximate the inverse of the `i`-th diagonal block of
+    /// `self`.
+    ///
+    /// # Panics
+    /// Panics if `self` is not square with matching block row and block column dimensions, or if
+    /// the dimensions of the preconditioners don't match the dimensions of the diagonal blocks.
+    #[track_caller]
+    pub fn block_diagonal_precond(
+        &self,
+        inverses: &[&'a dyn Precond<E>],
+    ) -> BlockDiagPrecond<'a, E> {
+        assert!(all(
+            self.row_dims == self.col_dims,
+            inverses.len() == self.nrow_blocks(),
+        ));
+        for (inv, &dim) in core::iter::zip(inverses, &self.row_dims) {
+            assert!(all(inv.nrows() == dim, inv.ncols() == dim));
+        }
+        BlockDiagPrecond {
+            dims: self.row_dims.clone(),
+            offsets: self.row_offsets.clone(),
+            blocks: inverses.to_vec(),
+        }
+    }
+
+    /// Returns a block lower triangular preconditioner for the $2\times 2$ block operator
+    /// $$\begin{bmatrix} A & B_{12} \\ B_{21} & C \end{bmatrix},$$
+    /// equal to the inverse of
+    /// $$\begin{bmatrix} A & 0 \\ B_{21} & S \end{bmatrix},$$
+    /// where `a_inv` approximates $A^{-1}$, and `s_inv` approximates the inverse of the Schur
+    /// complement $S = C - B_{21} A^{-1} B_{12}$.
+    ///
+    /// # Panics
+    /// Panics if `self` is not a square $2\times 2$ block operator, or if the dimensions of the
+    /// preconditioners don't match the dimensions of the diagonal blocks.
+    #[track_caller]
+    pub fn schur_complement_precond(
+        &self,
+        a_inv: &'a dyn Precond<E>,
+        s_inv: &'a dyn Precond<E>,
+    ) -> SchurComplementPrecond<'a, I, E> {
+        assert!(all(
+            self.nrow_blocks() == 2,
+            self.row_dims == self.col_dims,
+            a_inv.nrows() == self.row_dims[0],
+            a_inv.ncols() == self.row_dims[0],
+            s_inv.nrows() == self.row_dims[1],
+            s_inv.ncols() == self.row_dims[1],
+        ));
+        SchurComplementPrecond {
+            a_inv,
+            s_inv,
+            b21: *self.block(1, 0),
+        }
+    }
+
+    fn apply_impl(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj: Conj,
+        transpose: bool,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (out_dims, out_offsets, rhs_offsets) = if transpose {
+            (&self.col_dims, &self.col_offsets, &self.row_offsets)
+        } else {
+            (&self.row_dims, &self.row_offsets, &self.col_offsets)
+        };
+        assert!(all(
+            out.nrows() == *out_offsets.last().unwrap(),
+            rhs.nrows() == *rhs_offsets.last().unwrap(),
+            out.ncols() == rhs.ncols(),
+        ));
+
+        let mut out = out;
+        let mut stack = stack;
+        for i in 0..out_dims.len() {
+            let mut out_i = out
+                .rb_mut()
+                .subrows_mut(out_offsets[i], out_offsets[i + 1] - out_offsets[i]);
+            out_i.fill_zero();
+            for j in 0..rhs_offsets.len() - 1 {
+                let rhs_j = rhs.subrows(rhs_offsets[j], rhs_offsets[j + 1] - rhs_offsets[j]);
+                let block = if transpose {
+                    self.block(j, i)
+                } else {
+                    self.block(i, j)
+                };
+                block.acc_apply(
+                    out_i.rb_mut(),
+                    rhs_j,
+                    conj,
+                    transpose,
+                    E::faer_one(),
+                    parallelism,
+                    stack.rb_mut(),
+                );
+            }
+        }
+    }
+
+    fn req_impl(
+        &self,
+        transpose: bool,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let mut req = StackReq::empty();
+        for block in &self.blocks {
+            req = req.try_or(block.req(transpose, rhs_ncols, parallelism)?)?;
+        }
+        Ok(req)
+    }
+}
+
+impl<I: Index, E: ComplexField> LinOp<E> for BlockOperator<'_, I, E> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        self.req_impl(false, rhs_ncols, parallelism)
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        *self.row_offsets.last().unwrap()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        *self.col_offsets.last().unwrap()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::No, false, parallelism, stack)
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::Yes, false, parallelism, stack)
+    }
+}
+
+impl<I: Index, E: ComplexField> BiLinOp<E> for BlockOperator<'_, I, E> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        self.req_impl(true, rhs_ncols, parallelism)
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::No, true, parallelism, stack)
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::Yes, true, parallelism, stack)
+    }
+}
+
+/// Block diagonal preconditioner.
+///
+/// See [`BlockOperator::block_diagonal_precond`].
+#[derive(Clone, Debug)]
+pub struct BlockDiagPrecond<'a, E: ComplexField> {
+    dims: Vec<usize>,
+    offsets: Vec<usize>,
+    blocks: Vec<&'a dyn Precond<E>>,
+}
+
+impl<E: ComplexField> BlockDiagPrecond<'_, E> {
+    fn apply_impl(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj: Conj,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let dim = *self.offsets.last().unwrap();
+        assert!(all(
+            out.nrows() == dim,
+            rhs.nrows() == dim,
+            out.ncols() == rhs.ncols(),
+        ));
+
+        let mut out = out;
+        let mut stack = stack;
+        for (i, block) in self.blocks.iter().enumerate() {
+            let out_i = out.rb_mut().subrows_mut(self.offsets[i], self.dims[i]);
+            let rhs_i = rhs.subrows(self.offsets[i], self.dims[i]);
+            match conj {
+                Conj::No => block.apply(out_i, rhs_i, parallelism, stack.rb_mut()),
+                Conj::Yes => block.conj_apply(out_i, rhs_i, parallelism, stack.rb_mut()),
+            }
+        }
+    }
+}
+
+impl<E: ComplexField> LinOp<E> for BlockDiagPrecond<'_, E> {
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let mut req = StackReq::empty();
+        for block in &self.blocks {
+            req = req.try_or(block.apply_req(rhs_ncols, parallelism)?)?;
+        }
+        Ok(req)
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        *self.offsets.last().unwrap()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::No, parallelism, stack)
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::Yes, parallelism, stack)
+    }
+}
+
+impl<E: ComplexField> Precond<E> for BlockDiagPrecond<'_, E> {
+    fn apply_in_place_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let mut req = StackReq::empty();
+        for block in &self.blocks {
+            req = req.try_or(block.apply_in_place_req(rhs_ncols, parallelism)?)?;
+        }
+        Ok(req)
+    }
+
+    #[track_caller]
+    fn apply_in_place(&self, rhs: MatMut<'_, E>, parallelism: Parallelism, stack: PodStack<'_>) {
+        assert!(rhs.nrows() == self.nrows());
+        let mut rhs = rhs;
+        let mut stack = stack;
+        for (i, block) in self.blocks.iter().enumerate() {
+            let rhs_i = rhs.rb_mut().subrows_mut(self.offsets[i], self.dims[i]);
+            block.apply_in_place(rhs_i, parallelism, stack.rb_mut());
+        }
+    }
+
+    #[track_caller]
+    fn conj_apply_in_place(
+        &self,
+        rhs: MatMut<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        assert!(rhs.nrows() == self.nrows());
+        let mut rhs = rhs;
+        let mut stack = stack;
+        for (i, block) in self.blocks.iter().enumerate() {
+            let rhs_i = rhs.rb_mut().subrows_mut(self.offsets[i], self.dims[i]);
+            block.conj_apply_in_place(rhs_i, parallelism, stack.rb_mut());
+        }
+    }
+}
+
+/// Block lower triangular preconditioner based on an approximation of the Schur complement.
+///
+/// See [`BlockOperator::schur_complement_precond`].
+#[derive(Clone, Debug)]
+pub struct SchurComplementPrecond<'a, I: Index, E: ComplexField> {
+    a_inv: &'a dyn Precond<E>,
+    s_inv: &'a dyn Precond<E>,
+    b21: Block<'a, I, E>,
+}
+
+impl<I: Index, E: ComplexField> SchurComplementPrecond<'_, I, E> {
+    fn apply_impl(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj: Conj,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let n1 = self.a_inv.nrows();
+        let n2 = self.s_inv.nrows();
+        assert!(all(
+            out.nrows() == n1 + n2,
+            rhs.nrows() == n1 + n2,
+            out.ncols() == rhs.ncols(),
+        ));
+
+        let (mut out1, out2) = out.split_at_row_mut(n1);
+        let (rhs1, rhs2) = rhs.split_at_row(n1);
+        let mut stack = stack;
+
+        // x1 = A^{-1} r1
+        match conj {
+            Conj::No => self
+                .a_inv
+                .apply(out1.rb_mut(), rhs1, parallelism, stack.rb_mut()),
+            Conj::Yes => self
+                .a_inv
+                .conj_apply(out1.rb_mut(), rhs1, parallelism, stack.rb_mut()),
+        }
+
+        // x2 = S^{-1} (r2 - B21 x1)
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(n2, rhs.ncols(), stack);
+        tmp.copy_from(rhs2);
+        self.b21.acc_apply(
+            tmp.rb_mut(),
+            out1.rb(),
+            conj,
+            false,
+            E::faer_one().faer_neg(),
+            parallelism,
+            stack.rb_mut(),
+        );
+        match conj {
+            Conj::No => self.s_inv.apply(out2, tmp.rb(), parallelism, stack),
+            Conj::Yes => self.s_inv.conj_apply(out2, tmp.rb(), parallelism, stack),
+        }
+    }
+}
+
+impl<I: Index, E: ComplexField> LinOp<E> for SchurComplementPrecond<'_, I, E> {
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        self.a_inv.apply_req(rhs_ncols, parallelism)?.try_or(
+            temp_mat_req::<E>(self.s_inv.nrows(), rhs_ncols)?.try_and(StackReq::try_any_of([
+                self.b21.req(false, rhs_ncols, parallelism)?,
+                self.s_inv.apply_req(rhs_ncols, parallelism)?,
+            ])?)?,
+        )
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.a_inv.nrows() + self.s_inv.nrows()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.a_inv.ncols() + self.s_inv.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::No, parallelism, stack)
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.apply_impl(out, rhs, Conj::Yes, parallelism, stack)
+    }
+}
+
+impl<I: Index, E: ComplexField> Precond<E> for SchurComplementPrecond<'_, I, E> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert, complex_native::c64, linalg::solvers::SolverCore, sparse::SparseColMat, Col, Mat,
+    };
+    use dyn_stack::GlobalPodBuffer;
+
+    #[test]
+    fn test_block_operator_apply() {
+        let n1 = 6;
+        let n2 = 3;
+
+        let a = Mat::<f64>::from_fn(n1, n1, |i, j| {
+            if i == j {
+                4.0 + i as f64
+            } else {
+                1.0 / (1.0 + (i + j) as f64)
+            }
+        });
+        let b = SparseColMat::<usize, f64>::try_new_from_triplets(
+            n2,
+            n1,
+            &[
+                (0, 0, 1.0),
+                (1, 2, 2.0),
+                (2, 3, -1.0),
+                (0, 5, 0.5),
+                (2, 1, 3.0),
+            ],
+        )
+        .unwrap();
+        let bt = b.to_dense().transpose().to_owned();
+        let d = Col::<f64>::from_fn(n2, |i| -1.0 - i as f64);
+        let u = Mat::<f64>::from_fn(n2, 2, |i, j| (i + j) as f64);
+        let v = Mat::<f64>::from_fn(n2, 2, |i, j| 1.0 / (1.0 + i as f64 + 2.0 * j as f64));
+
+        let op = BlockOperator::<usize, f64>::new(&[n1, n2], &[n1, n2])
+            .with_block(0, 0, Block::Dense(a.as_ref()))
+            .with_block(0, 1, Block::Op(&bt))
+            .with_block(1, 0, Block::Sparse(b.as_ref()))
+            .with_block(1, 1, Block::Diagonal(d.as_ref()));
+
+        let mut dense = Mat::<f64>::zeros(n1 + n2, n1 + n2);
+        dense.as_mut().submatrix_mut(0, 0, n1, n1).copy_from(&a);
+        dense.as_mut().submatrix_mut(0, n1, n1, n2).copy_from(&bt);
+        dense
+            .as_mut()
+            .submatrix_mut(n1, 0, n2, n1)
+            .copy_from(b.to_dense());
+        for i in 0..n2 {
+            dense.write(n1 + i, n1 + i, d.read(i));
+        }
+
+        let rhs = Mat::<f64>::from_fn(n1 + n2, 2, |i, j| (i as f64) - 2.0 * j as f64);
+        let mut out = Mat::<f64>::zeros(n1 + n2, 2);
+
+        op.apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                op.apply_req(2, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((&out - &dense * &rhs).norm_max() < 1e-12);
+
+        op.transpose_apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                op.transpose_apply_req(2, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((&out - dense.transpose() * &rhs).norm_max() < 1e-12);
+
+        let op = op.with_block(
+            1,
+            1,
+            Block::LowRank {
+                u: u.as_ref(),
+                v: v.as_ref(),
+            },
+        );
+        let uv = &u * v.transpose();
+        dense.as_mut().submatrix_mut(n1, n1, n2, n2).copy_from(&uv);
+
+        op.apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                op.apply_req(2, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((&out - &dense * &rhs).norm_max() < 1e-12);
+
+        op.transpose_apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                op.transpose_apply_req(2, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((&out - dense.transpose() * &rhs).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn test_block_operator_apply_complex() {
+        let n1 = 5;
+        let n2 = 3;
+
+        let a = Mat::<c64>::from_fn(n1, n1, |i, j| {
+            if i == j {
+                c64::new(4.0 + i as f64, 1.0)
+            } else {
+                c64::new(1.0 / (1.0 + (i + j) as f64), (i as f64 - j as f64) / 4.0)
+            }
+        });
+        let b = SparseColMat::<usize, c64>::try_new_from_triplets(
+            n2,
+            n1,
+            &[
+                (0, 0, c64::new(1.0, 2.0)),
+                (1, 2, c64::new(2.0, -1.0)),
+                (2, 3, c64::new(-1.0, 0.5)),
+                (0, 4, c64::new(0.5, -3.0)),
+                (2, 1, c64::new(3.0, 1.0)),
+            ],
+        )
+        .unwrap();
+        let c = Mat::<c64>::from_fn(n1, n2, |i, j| c64::new((i + 2 * j) as f64, 1.0 - i as f64));
+        let d = Col::<c64>::from_fn(n2, |i| c64::new(-1.0 - i as f64, 0.5 * i as f64));
+        let u = Mat::<c64>::from_fn(n2, 2, |i, j| c64::new((i + j) as f64, 1.0 + j as f64));
+        let v = Mat::<c64>::from_fn(n2, 2, |i, j| {
+            c64::new(1.0 / (1.0 + i as f64 + 2.0 * j as f64), i as f64 - 1.0)
+        });
+
+        let mut dense = Mat::<c64>::zeros(n1 + n2, n1 + n2);
+        dense.as_mut().submatrix_mut(0, 0, n1, n1).copy_from(&a);
+        dense.as_mut().submatrix_mut(0, n1, n1, n2).copy_from(&c);
+        dense
+            .as_mut()
+            .submatrix_mut(n1, 0, n2, n1)
+            .copy_from(b.to_dense());
+        let mut dense_diag = dense.clone();
+        for i in 0..n2 {
+            dense_diag.write(n1 + i, n1 + i, d.read(i));
+        }
+        dense
+            .as_mut()
+            .submatrix_mut(n1, n1, n2, n2)
+            .copy_from(&u * v.adjoint());
+
+        let rhs = Mat::<c64>::from_fn(n1 + n2, 2, |i, j| c64::new(i as f64, -2.0 * j as f64));
+        let mut out = Mat::<c64>::zeros(n1 + n2, 2);
+
+        for (block, dense) in [
+            (Block::Diagonal(d.as_ref()), &dense_diag),
+            (
+                Block::LowRank {
+                    u: u.as_ref(),
+                    v: v.as_ref(),
+                },
+                &dense,
+            ),
+        ] {
+            let op = BlockOperator::<usize, c64>::new(&[n1, n2], &[n1, n2])
+                .with_block(0, 0, Block::Dense(a.as_ref()))
+                .with_block(0, 1, Block::Op(&c))
+                .with_block(1, 0, Block::Sparse(b.as_ref()))
+                .with_block(1, 1, block);
+
+            let req = op.apply_req(2, Parallelism::None).unwrap();
+            let transpose_req = op.transpose_apply_req(2, Parallelism::None).unwrap();
+
+            op.apply(
+                out.as_mut(),
+                rhs.as_ref(),
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(req)),
+            );
+            assert!((&out - dense * &rhs).norm_max() < 1e-12);
+
+            op.conj_apply(
+                out.as_mut(),
+                rhs.as_ref(),
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(req)),
+            );
+            assert!((&out - dense.conjugate() * &rhs).norm_max() < 1e-12);
+
+            op.transpose_apply(
+                out.as_mut(),
+                rhs.as_ref(),
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(transpose_req)),
+            );
+            assert!((&out - dense.transpose() * &rhs).norm_max() < 1e-12);
+
+            op.adjoint_apply(
+                out.as_mut(),
+                rhs.as_ref(),
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(transpose_req)),
+            );
+            assert!((&out - dense.adjoint() * &rhs).norm_max() < 1e-12);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_block_low_rank_mismatched_factors() {
+        let u = Mat::<f64>::zeros(3, 2);
+        let v = Mat::<f64>::zeros(3, 1);
+        let _ = Block::<usize, f64>::LowRank {
+            u: u.as_ref(),
+            v: v.as_ref(),
+        }
+        .dims();
+    }
+
+    #[test]
+    fn test_block_precond() {
+        let n1 = 5;
+        let n2 = 2;
+
+        let a = Mat::<f64>::from_fn(n1, n1, |i, j| if i == j { 3.0 } else { 0.25 });
+        let b = Mat::<f64>::from_fn(n2, n1, |i, j| (i + 2 * j) as f64 / 4.0);
+        let bt = b.transpose().to_owned();
+
+        let op = BlockOperator::<usize, f64>::new(&[n1, n2], &[n1, n2])
+            .with_block(0, 0, Block::Dense(a.as_ref()))
+            .with_block(0, 1, Block::Dense(bt.as_ref()))
+            .with_block(1, 0, Block::Dense(b.as_ref()));
+
+        let a_inv = a.partial_piv_lu().inverse();
+        let s = -(&b * &a_inv * &bt);
+        let s_inv = s.partial_piv_lu().inverse();
+
+        let rhs = Mat::<f64>::from_fn(n1 + n2, 1, |i, _| 1.0 + i as f64);
+
+        // exact block lower triangular factor
+        let mut lower = Mat::<f64>::zeros(n1 + n2, n1 + n2);
+        lower.as_mut().submatrix_mut(0, 0, n1, n1).copy_from(&a);
+        lower.as_mut().submatrix_mut(n1, 0, n2, n1).copy_from(&b);
+        lower.as_mut().submatrix_mut(n1, n1, n2, n2).copy_from(&s);
+
+        let precond = op.schur_complement_precond(&a_inv, &s_inv);
+        let mut out = Mat::<f64>::zeros(n1 + n2, 1);
+        precond.apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                precond.apply_req(1, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((&lower * &out - &rhs).norm_max() < 1e-10);
+
+        let precond = op.block_diagonal_precond(&[&a_inv, &s_inv]);
+        let mut out = rhs.clone();
+        precond.apply_in_place(
+            out.as_mut(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                precond.apply_in_place_req(1, Parallelism::None).unwrap(),
+            )),
+        );
+        let mut block_diag = Mat::<f64>::zeros(n1 + n2, n1 + n2);
+        block_diag
+            .as_mut()
+            .submatrix_mut(0, 0, n1, n1)
+            .copy_from(&a);
+        block_diag
+            .as_mut()
+            .submatrix_mut(n1, n1, n2, n2)
+            .copy_from(&s);
+        assert!((&block_diag * &out - &rhs).norm_max() < 1e-10);
+    }
+}
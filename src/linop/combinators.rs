@@ -0,0 +1,1029 @@
+//! Combinators for building linear operators out of other operators and closures.
+//!
+//! - [`Scaled`] is the operator $\alpha A$,
+//! - [`Sum`] is the operator $A + B$,
+//! - [`Product`] is the operator $AB$,
+//! - [`KronOp`] is the Kronecker product $A \otimes B$,
+//! - [`FnOp`] and [`FnBiOp`] wrap user closures that apply an implicit operator.
+//!
+//! None of them store an explicit matrix, so they can be used to pass implicit operators such as
+//! $A^H A + \sigma I$ to the iterative solvers and eigensolvers.
+//!
+//! # Example
+//!
+//! ```
+//! use faer::{
+//!     linop::{combinators::*, IdentityPrecond, LinOp},
+//!     mat, Mat, Parallelism,
+//! };
+//! use dyn_stack::{GlobalPodBuffer, PodStack};
+//!
+//! let a = mat![[1.0, 2.0], [3.0, 4.0f64]];
+//!
+//! // A + 2I, without forming the matrix
+//! let op = Sum::new(a.as_ref(), Scaled::new(2.0, IdentityPrecond { dim: 2 }));
+//!
+//! let x = mat![[1.0], [1.0f64]];
+//! let mut y = Mat::<f64>::zeros(2, 1);
+//! op.apply(
+//!     y.as_mut(),
+//!     x.as_ref(),
+//!     Parallelism::None,
+//!     PodStack::new(&mut GlobalPodBuffer::new(
+//!         op.apply_req(1, Parallelism::None).unwrap(),
+//!     )),
+//! );
+//! assert!(y == mat![[5.0], [9.0]]);
+//! ```
+
+use crate::{
+    assert,
+    linalg::{temp_mat_req, temp_mat_uninit},
+    linop::{BiLinOp, BiPrecond, LinOp, Precond},
+    unzipped, zipped, ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+fn scale_in_place<E: ComplexField>(out: MatMut<'_, E>, factor: E) {
+    zipped!(out).for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(factor)));
+}
+
+fn add_in_place<E: ComplexField>(out: MatMut<'_, E>, rhs: MatRef<'_, E>) {
+    zipped!(out, rhs).for_each(|unzipped!(mut x, y)| x.write(x.read().faer_add(y.read())));
+}
+
+/// Linear operator $\alpha A$.
+#[derive(Copy, Clone, Debug)]
+pub struct Scaled<E: ComplexField, A> {
+    factor: E,
+    op: A,
+}
+
+impl<E: ComplexField, A> Scaled<E, A> {
+    /// Returns the operator `factor * op`.
+    #[inline]
+    pub fn new(factor: E, op: A) -> Self {
+        Self { factor, op }
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>> LinOp<E> for Scaled<E, A> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        self.op.apply_req(rhs_ncols, parallelism)
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.op.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.op.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        self.op.apply(out.rb_mut(), rhs, parallelism, stack);
+        scale_in_place(out, self.factor);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        self.op.conj_apply(out.rb_mut(), rhs, parallelism, stack);
+        scale_in_place(out, self.factor.faer_conj());
+    }
+}
+
+impl<E: ComplexField, A: BiLinOp<E>> BiLinOp<E> for Scaled<E, A> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        self.op.transpose_apply_req(rhs_ncols, parallelism)
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        self.op
+            .transpose_apply(out.rb_mut(), rhs, parallelism, stack);
+        scale_in_place(out, self.factor);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        self.op.adjoint_apply(out.rb_mut(), rhs, parallelism, stack);
+        scale_in_place(out, self.factor.faer_conj());
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>> Precond<E> for Scaled<E, A> {}
+impl<E: ComplexField, A: BiLinOp<E>> BiPrecond<E> for Scaled<E, A> {}
+
+/// Linear operator $A + B$.
+#[derive(Copy, Clone, Debug)]
+pub struct Sum<A, B> {
+    lhs: A,
+    rhs: B,
+}
+
+impl<A, B> Sum<A, B> {
+    /// Returns the operator `lhs + rhs`.
+    ///
+    /// # Panics
+    /// Panics if `lhs` and `rhs` don't have the same dimensions.
+    #[inline]
+    #[track_caller]
+    pub fn new<E: ComplexField>(lhs: A, rhs: B) -> Self
+    where
+        A: LinOp<E>,
+        B: LinOp<E>,
+    {
+        assert!(all(lhs.nrows() == rhs.nrows(), lhs.ncols() == rhs.ncols()));
+        Self { lhs, rhs }
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> LinOp<E> for Sum<A, B> {
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        temp_mat_req::<E>(self.lhs.nrows(), rhs_ncols)?.try_and(StackReq::try_any_of([
+            self.lhs.apply_req(rhs_ncols, parallelism)?,
+            self.rhs.apply_req(rhs_ncols, parallelism)?,
+        ])?)
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.lhs.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.lhs.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.nrows(), rhs.ncols(), stack);
+        self.lhs
+            .apply(out.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs.apply(tmp.rb_mut(), rhs, parallelism, stack);
+        add_in_place(out, tmp.rb());
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.nrows(), rhs.ncols(), stack);
+        self.lhs
+            .conj_apply(out.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs.conj_apply(tmp.rb_mut(), rhs, parallelism, stack);
+        add_in_place(out, tmp.rb());
+    }
+}
+
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiLinOp<E> for Sum<A, B> {
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        temp_mat_req::<E>(self.lhs.ncols(), rhs_ncols)?.try_and(StackReq::try_any_of([
+            self.lhs.transpose_apply_req(rhs_ncols, parallelism)?,
+            self.rhs.transpose_apply_req(rhs_ncols, parallelism)?,
+        ])?)
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.ncols(), rhs.ncols(), stack);
+        self.lhs
+            .transpose_apply(out.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs
+            .transpose_apply(tmp.rb_mut(), rhs, parallelism, stack);
+        add_in_place(out, tmp.rb());
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.ncols(), rhs.ncols(), stack);
+        self.lhs
+            .adjoint_apply(out.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs
+            .adjoint_apply(tmp.rb_mut(), rhs, parallelism, stack);
+        add_in_place(out, tmp.rb());
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> Precond<E> for Sum<A, B> {}
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiPrecond<E> for Sum<A, B> {}
+
+/// Linear operator $AB$.
+#[derive(Copy, Clone, Debug)]
+pub struct Product<A, B> {
+    lhs: A,
+    rhs: B,
+}
+
+impl<A, B> Product<A, B> {
+    /// Returns the operator `lhs * rhs`.
+    ///
+    /// # Panics
+    /// Panics if the number of columns of `lhs` is not equal to the number of rows of `rhs`.
+    #[inline]
+    #[track_caller]
+    pub fn new<E: ComplexField>(lhs: A, rhs: B) -> Self
+    where
+        A: LinOp<E>,
+        B: LinOp<E>,
+    {
+        assert!(lhs.ncols() == rhs.nrows());
+        Self { lhs, rhs }
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> LinOp<E> for Product<A, B> {
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        temp_mat_req::<E>(self.rhs.nrows(), rhs_ncols)?.try_and(StackReq::try_any_of([
+            self.lhs.apply_req(rhs_ncols, parallelism)?,
+            self.rhs.apply_req(rhs_ncols, parallelism)?,
+        ])?)
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.lhs.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.rhs.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.rhs.nrows(), rhs.ncols(), stack);
+        self.rhs
+            .apply(tmp.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.lhs.apply(out, tmp.rb(), parallelism, stack);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.rhs.nrows(), rhs.ncols(), stack);
+        self.rhs
+            .conj_apply(tmp.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.lhs.conj_apply(out, tmp.rb(), parallelism, stack);
+    }
+}
+
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiLinOp<E> for Product<A, B> {
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        temp_mat_req::<E>(self.lhs.ncols(), rhs_ncols)?.try_and(StackReq::try_any_of([
+            self.lhs.transpose_apply_req(rhs_ncols, parallelism)?,
+            self.rhs.transpose_apply_req(rhs_ncols, parallelism)?,
+        ])?)
+    }
+
+    // (AB)^T = B^T A^T
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.ncols(), rhs.ncols(), stack);
+        self.lhs
+            .transpose_apply(tmp.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs.transpose_apply(out, tmp.rb(), parallelism, stack);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (mut tmp, mut stack) = temp_mat_uninit::<E>(self.lhs.ncols(), rhs.ncols(), stack);
+        self.lhs
+            .adjoint_apply(tmp.rb_mut(), rhs, parallelism, stack.rb_mut());
+        self.rhs.adjoint_apply(out, tmp.rb(), parallelism, stack);
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> Precond<E> for Product<A, B> {}
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiPrecond<E> for Product<A, B> {}
+
+// applies A ⊗ B to `rhs`, where `apply_lhs` applies A and `apply_rhs` applies B. each column of
+// `rhs` is the column-major vectorization of a matrix X with dimensions `ncols(B) × ncols(A)`, and
+// the corresponding column of `out` is the vectorization of B X A^T
+fn kron_apply<E: ComplexField>(
+    out: MatMut<'_, E>,
+    rhs: MatRef<'_, E>,
+    lhs_dims: (usize, usize),
+    rhs_dims: (usize, usize),
+    apply_lhs: impl FnOnce(MatMut<'_, E>, MatRef<'_, E>, PodStack<'_>),
+    apply_rhs: impl FnOnce(MatMut<'_, E>, MatRef<'_, E>, PodStack<'_>),
+    stack: PodStack<'_>,
+) {
+    let (m, n) = lhs_dims;
+    let (p, q) = rhs_dims;
+    let k = rhs.ncols();
+    let mut out = out;
+
+    let (mut x, stack) = temp_mat_uninit::<E>(q, n * k, stack);
+    for c in 0..k {
+        for j in 0..n {
+            for r in 0..q {
+                x.write(r, j + n * c, rhs.read(j * q + r, c));
+            }
+        }
+    }
+    let (mut bx, mut stack) = temp_mat_uninit::<E>(p, n * k, stack);
+    apply_rhs(bx.rb_mut(), x.rb(), stack.rb_mut());
+
+    let (mut bx_t, stack) = temp_mat_uninit::<E>(n, p * k, stack);
+    for c in 0..k {
+        for r in 0..p {
+            for j in 0..n {
+                bx_t.write(j, r + p * c, bx.read(r, j + n * c));
+            }
+        }
+    }
+    let (mut y, stack) = temp_mat_uninit::<E>(m, p * k, stack);
+    apply_lhs(y.rb_mut(), bx_t.rb(), stack);
+
+    for c in 0..k {
+        for i in 0..m {
+            for r in 0..p {
+                out.write(i * p + r, c, y.read(i, r + p * c));
+            }
+        }
+    }
+}
+
+fn kron_apply_req<E: ComplexField>(
+    rhs_ncols: usize,
+    lhs_dims: (usize, usize),
+    rhs_dims: (usize, usize),
+    lhs_req: StackReq,
+    rhs_req: StackReq,
+) -> Result<StackReq, SizeOverflow> {
+    let (m, n) = lhs_dims;
+    let (p, q) = rhs_dims;
+    let nk = n.checked_mul(rhs_ncols).ok_or(SizeOverflow)?;
+    let pk = p.checked_mul(rhs_ncols).ok_or(SizeOverflow)?;
+    StackReq::try_all_of([
+        temp_mat_req::<E>(q, nk)?,
+        temp_mat_req::<E>(p, nk)?,
+        temp_mat_req::<E>(n, pk)?,
+        temp_mat_req::<E>(m, pk)?,
+        StackReq::try_any_of([lhs_req, rhs_req])?,
+    ])
+}
+
+/// Linear operator $A \otimes B$, the Kronecker product of $A$ and $B$.
+///
+/// The product is applied without forming the Kronecker product, using the identity
+/// $(A \otimes B) \operatorname{vec}(X) = \operatorname{vec}(B X A^\top)$, where
+/// $\operatorname{vec}$ stacks the columns of a matrix. This costs one application of $B$ with
+/// `ncols(A)` columns and one application of $A$ with `nrows(B)` columns for each column of the
+/// right-hand side. See [`kron`](crate::linalg::kron) to form the matrix explicitly.
+#[derive(Copy, Clone, Debug)]
+pub struct KronOp<A, B> {
+    lhs: A,
+    rhs: B,
+}
+
+impl<A, B> KronOp<A, B> {
+    /// Returns the operator `lhs ⊗ rhs`.
+    #[inline]
+    pub fn new(lhs: A, rhs: B) -> Self {
+        Self { lhs, rhs }
+    }
+
+    #[inline]
+    fn dims<E: ComplexField>(&self) -> ((usize, usize), (usize, usize))
+    where
+        A: LinOp<E>,
+        B: LinOp<E>,
+    {
+        (
+            (self.lhs.nrows(), self.lhs.ncols()),
+            (self.rhs.nrows(), self.rhs.ncols()),
+        )
+    }
+
+    #[inline]
+    fn transpose_dims<E: ComplexField>(&self) -> ((usize, usize), (usize, usize))
+    where
+        A: LinOp<E>,
+        B: LinOp<E>,
+    {
+        let ((m, n), (p, q)) = self.dims::<E>();
+        ((n, m), (q, p))
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> LinOp<E> for KronOp<A, B> {
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        kron_apply_req::<E>(
+            rhs_ncols,
+            (self.lhs.nrows(), self.lhs.ncols()),
+            (self.rhs.nrows(), self.rhs.ncols()),
+            self.lhs
+                .apply_req(self.rhs.nrows() * rhs_ncols, parallelism)?,
+            self.rhs
+                .apply_req(self.lhs.ncols() * rhs_ncols, parallelism)?,
+        )
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.lhs.nrows() * self.rhs.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.lhs.ncols() * self.rhs.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (lhs_dims, rhs_dims) = self.dims::<E>();
+        kron_apply(
+            out,
+            rhs,
+            lhs_dims,
+            rhs_dims,
+            |out, rhs, stack| self.lhs.apply(out, rhs, parallelism, stack),
+            |out, rhs, stack| self.rhs.apply(out, rhs, parallelism, stack),
+            stack,
+        );
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (lhs_dims, rhs_dims) = self.dims::<E>();
+        kron_apply(
+            out,
+            rhs,
+            lhs_dims,
+            rhs_dims,
+            |out, rhs, stack| self.lhs.conj_apply(out, rhs, parallelism, stack),
+            |out, rhs, stack| self.rhs.conj_apply(out, rhs, parallelism, stack),
+            stack,
+        );
+    }
+}
+
+// (A ⊗ B)^T = A^T ⊗ B^T
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiLinOp<E> for KronOp<A, B> {
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        kron_apply_req::<E>(
+            rhs_ncols,
+            (self.lhs.ncols(), self.lhs.nrows()),
+            (self.rhs.ncols(), self.rhs.nrows()),
+            self.lhs
+                .transpose_apply_req(self.rhs.ncols() * rhs_ncols, parallelism)?,
+            self.rhs
+                .transpose_apply_req(self.lhs.nrows() * rhs_ncols, parallelism)?,
+        )
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (lhs_dims, rhs_dims) = self.transpose_dims::<E>();
+        kron_apply(
+            out,
+            rhs,
+            lhs_dims,
+            rhs_dims,
+            |out, rhs, stack| self.lhs.transpose_apply(out, rhs, parallelism, stack),
+            |out, rhs, stack| self.rhs.transpose_apply(out, rhs, parallelism, stack),
+            stack,
+        );
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let (lhs_dims, rhs_dims) = self.transpose_dims::<E>();
+        kron_apply(
+            out,
+            rhs,
+            lhs_dims,
+            rhs_dims,
+            |out, rhs, stack| self.lhs.adjoint_apply(out, rhs, parallelism, stack),
+            |out, rhs, stack| self.rhs.adjoint_apply(out, rhs, parallelism, stack),
+            stack,
+        );
+    }
+}
+
+impl<E: ComplexField, A: LinOp<E>, B: LinOp<E>> Precond<E> for KronOp<A, B> {}
+impl<E: ComplexField, A: BiLinOp<E>, B: BiLinOp<E>> BiPrecond<E> for KronOp<A, B> {}
+
+/// Linear operator defined by a closure.
+///
+/// The closure is called as `f(out, rhs, conj)`, and must store in `out` the product of the
+/// operator (or of its conjugate, if `conj` is [`Conj::Yes`]) with `rhs`.
+#[derive(Copy, Clone)]
+pub struct FnOp<F> {
+    nrows: usize,
+    ncols: usize,
+    apply: F,
+}
+
+impl<F> FnOp<F> {
+    /// Returns the operator with dimensions `nrows × ncols` whose action is computed by `apply`.
+    #[inline]
+    pub fn new(nrows: usize, ncols: usize, apply: F) -> Self {
+        Self {
+            nrows,
+            ncols,
+            apply,
+        }
+    }
+}
+
+impl<F> core::fmt::Debug for FnOp<F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FnOp")
+            .field("nrows", &self.nrows)
+            .field("ncols", &self.ncols)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E: ComplexField, F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj)> LinOp<E> for FnOp<F> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.nrows
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.apply)(out, rhs, Conj::No);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.apply)(out, rhs, Conj::Yes);
+    }
+}
+
+impl<E: ComplexField, F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj)> Precond<E> for FnOp<F> {}
+
+/// Linear operator defined by a pair of closures, which can also be applied from the left side.
+///
+/// The closures are called as `apply(out, rhs, conj)` and `transpose_apply(out, rhs, conj)`, and
+/// must store in `out` the product of the operator (respectively its transpose) with `rhs`,
+/// conjugated if `conj` is [`Conj::Yes`].
+#[derive(Copy, Clone)]
+pub struct FnBiOp<F, G> {
+    nrows: usize,
+    ncols: usize,
+    apply: F,
+    transpose_apply: G,
+}
+
+impl<F, G> FnBiOp<F, G> {
+    /// Returns the operator with dimensions `nrows × ncols` whose action is computed by `apply`,
+    /// and the action of its transpose by `transpose_apply`.
+    #[inline]
+    pub fn new(nrows: usize, ncols: usize, apply: F, transpose_apply: G) -> Self {
+        Self {
+            nrows,
+            ncols,
+            apply,
+            transpose_apply,
+        }
+    }
+}
+
+impl<F, G> core::fmt::Debug for FnBiOp<F, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FnBiOp")
+            .field("nrows", &self.nrows)
+            .field("ncols", &self.ncols)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<
+        E: ComplexField,
+        F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+        G: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+    > LinOp<E> for FnBiOp<F, G>
+{
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.nrows
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.apply)(out, rhs, Conj::No);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.apply)(out, rhs, Conj::Yes);
+    }
+}
+
+impl<
+        E: ComplexField,
+        F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+        G: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+    > BiLinOp<E> for FnBiOp<F, G>
+{
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.transpose_apply)(out, rhs, Conj::No);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = (parallelism, stack);
+        (self.transpose_apply)(out, rhs, Conj::Yes);
+    }
+}
+
+impl<
+        E: ComplexField,
+        F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+        G: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+    > Precond<E> for FnBiOp<F, G>
+{
+}
+impl<
+        E: ComplexField,
+        F: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+        G: Sync + Fn(MatMut<'_, E>, MatRef<'_, E>, Conj),
+    > BiPrecond<E> for FnBiOp<F, G>
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert,
+        complex_native::c64,
+        linop::{
+            conjugate_gradient::{conjugate_gradient, conjugate_gradient_req, CgParams},
+            IdentityPrecond,
+        },
+        Mat,
+    };
+    use dyn_stack::GlobalPodBuffer;
+
+    fn random_mat(m: usize, n: usize) -> Mat<c64> {
+        Mat::from_fn(m, n, |_, _| c64::new(rand::random(), rand::random()))
+    }
+
+    // applies every operation of `op` to a random rhs, and compares with the explicit matrix
+    fn check_op(op: &dyn BiLinOp<c64>, expected: &Mat<c64>) {
+        let m = op.nrows();
+        let n = op.ncols();
+        assert!(all(m == expected.nrows(), n == expected.ncols()));
+        let k = 3;
+        let par = Parallelism::None;
+
+        let req = StackReq::try_any_of([
+            op.apply_req(k, par).unwrap(),
+            op.transpose_apply_req(k, par).unwrap(),
+        ])
+        .unwrap();
+        let mut mem = GlobalPodBuffer::new(req);
+
+        let rhs = random_mat(n, k);
+        let mut out = Mat::<c64>::zeros(m, k);
+
+        op.apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!((&out - expected * &rhs).norm_max() < 1e-12);
+        op.conj_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!((&out - expected.conjugate() * &rhs).norm_max() < 1e-12);
+
+        let rhs = random_mat(m, k);
+        let mut out = Mat::<c64>::zeros(n, k);
+
+        op.transpose_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!((&out - expected.transpose() * &rhs).norm_max() < 1e-12);
+        op.adjoint_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!((&out - expected.adjoint() * &rhs).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn test_combinators() {
+        let a = random_mat(4, 5);
+        let b = random_mat(4, 5);
+        let c = random_mat(5, 3);
+        let alpha = c64::new(0.5, -2.0);
+
+        let scaled = Scaled::new(alpha, a.as_ref());
+        let expected = Mat::from_fn(4, 5, |i, j| alpha * a.read(i, j));
+        check_op(&scaled, &expected);
+
+        let sum = Sum::new(a.as_ref(), b.as_ref());
+        check_op(&sum, &(&a + &b));
+
+        let product = Product::new(a.as_ref(), c.as_ref());
+        check_op(&product, &(&a * &c));
+
+        let kron = KronOp::new(a.as_ref(), c.as_ref());
+        check_op(&kron, &a.kron(&c));
+
+        // combinators can be nested
+        let nested = Product::new(Sum::new(scaled, b.as_ref()), c.as_ref());
+        check_op(&nested, &((&expected + &b) * &c));
+
+        let closure = FnBiOp::new(
+            4,
+            5,
+            |out: MatMut<'_, c64>, rhs: MatRef<'_, c64>, conj: Conj| {
+                let mat = match conj {
+                    Conj::Yes => a.conjugate().to_owned(),
+                    Conj::No => a.clone(),
+                };
+                { out }.copy_from((mat * rhs).as_ref());
+            },
+            |out: MatMut<'_, c64>, rhs: MatRef<'_, c64>, conj: Conj| {
+                let mat = match conj {
+                    Conj::Yes => a.adjoint().to_owned(),
+                    Conj::No => a.transpose().to_owned(),
+                };
+                { out }.copy_from((mat * rhs).as_ref());
+            },
+        );
+        check_op(&closure, &a);
+
+        // a closure that only implements the forward product, here a diagonal scaling
+        let d = random_mat(4, 1);
+        let diag = FnOp::new(
+            4,
+            4,
+            |out: MatMut<'_, c64>, rhs: MatRef<'_, c64>, conj: Conj| {
+                let mut out = out;
+                for j in 0..rhs.ncols() {
+                    for i in 0..rhs.nrows() {
+                        let di = match conj {
+                            Conj::Yes => d.read(i, 0).faer_conj(),
+                            Conj::No => d.read(i, 0),
+                        };
+                        out.write(i, j, di * rhs.read(i, j));
+                    }
+                }
+            },
+        );
+        let rhs = random_mat(4, 2);
+        let mut out = Mat::<c64>::zeros(4, 2);
+        diag.apply(
+            out.as_mut(),
+            rhs.as_ref(),
+            Parallelism::None,
+            PodStack::new(&mut []),
+        );
+        let expected = Mat::from_fn(4, 2, |i, j| d.read(i, 0) * rhs.read(i, j));
+        assert!((&out - &expected).norm_max() < 1e-14);
+    }
+
+    #[test]
+    fn test_implicit_cg() {
+        // solve (A^H A + σI) x = b without forming the matrix
+        let n = 6;
+        let a = random_mat(8, n);
+        let sigma = 0.5;
+        let ata = Product::new(a.adjoint(), a.as_ref());
+        let op = Sum::new(
+            ata,
+            Scaled::new(c64::new(sigma, 0.0), IdentityPrecond { dim: n }),
+        );
+        let b = random_mat(n, 1);
+
+        let mut x = Mat::<c64>::zeros(n, 1);
+        let precond = IdentityPrecond { dim: n };
+        let params = CgParams::default();
+        conjugate_gradient(
+            x.as_mut(),
+            precond,
+            &op,
+            b.as_ref(),
+            params,
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                conjugate_gradient_req::<c64>(precond, &op, 1, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        let explicit = a.adjoint() * &a
+            + Mat::from_fn(n, n, |i, j| {
+                if i == j {
+                    c64::new(sigma, 0.0)
+                } else {
+                    c64::new(0.0, 0.0)
+                }
+            });
+        assert!((explicit * &x - &b).norm_max() < 1e-10);
+    }
+}
@@ -28,6 +28,19 @@ pub struct CgInfo<E: ComplexField> {
     pub iter_count: usize,
 }
 
+/// Residual of the conjugate gradient iterate, reported after every iteration by
+/// [`conjugate_gradient_with_callback`].
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct CgIterInfo<E: ComplexField> {
+    /// Number of completed iterations, `0` for the initial guess.
+    pub iter_count: usize,
+    /// Frobenius norm of the residual `rhs - mat * out`.
+    pub abs_residual: E::Real,
+    /// Norm of the residual relative to the norm of the right-hand side.
+    pub rel_residual: E::Real,
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum CgError<E: ComplexField> {
     NonPositiveDefiniteOperator,
@@ -106,6 +119,27 @@ pub fn conjugate_gradient<E: ComplexField>(
     params: CgParams<E>,
     parallelism: Parallelism,
     stack: PodStack<'_>,
+) -> Result<CgInfo<E>, CgError<E>> {
+    conjugate_gradient_with_callback(out, precond, mat, rhs, params, |_| {}, parallelism, stack)
+}
+
+/// Same as [`conjugate_gradient`], but calls `callback` with the residual of the initial guess,
+/// then with the residual after each iteration, which can be used to record the convergence
+/// history of the method.
+///
+/// The workspace requirements are the same as those of [`conjugate_gradient`] (see
+/// [`conjugate_gradient_req`]).
+#[inline]
+#[track_caller]
+pub fn conjugate_gradient_with_callback<E: ComplexField>(
+    out: MatMut<'_, E>,
+    precond: impl Precond<E>,
+    mat: impl LinOp<E>,
+    rhs: MatRef<'_, E>,
+    params: CgParams<E>,
+    callback: impl FnMut(CgIterInfo<E>),
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
 ) -> Result<CgInfo<E>, CgError<E>> {
     #[track_caller]
     fn implementation<E: ComplexField>(
@@ -115,6 +149,7 @@ pub fn conjugate_gradient<E: ComplexField>(
         b: MatRef<'_, E>,
 
         params: CgParams<E>,
+        callback: &mut dyn FnMut(CgIterInfo<E>),
         parallelism: Parallelism,
         mut stack: PodStack<'_>,
     ) -> Result<CgInfo<E>, CgError<E>> {
@@ -125,6 +160,11 @@ pub fn conjugate_gradient<E: ComplexField>(
         let b_norm = b.norm_l2();
         if b_norm == E::Real::faer_zero() {
             x.fill_zero();
+            callback(CgIterInfo {
+                iter_count: 0,
+                abs_residual: E::Real::faer_zero(),
+                rel_residual: E::Real::faer_zero(),
+            });
             return Ok(CgInfo {
                 abs_residual: E::Real::faer_zero(),
                 rel_residual: E::Real::faer_zero(),
@@ -158,6 +198,12 @@ pub fn conjugate_gradient<E: ComplexField>(
             b_norm
         };
 
+        callback(CgIterInfo {
+            iter_count: 0,
+            abs_residual,
+            rel_residual: abs_residual.faer_div(b_norm),
+        });
+
         if abs_residual < threshold {
             return Ok(CgInfo {
                 abs_residual,
@@ -254,6 +300,11 @@ pub fn conjugate_gradient<E: ComplexField>(
             }
 
             let abs_residual = r.norm_l2();
+            callback(CgIterInfo {
+                iter_count: i + 1,
+                abs_residual,
+                rel_residual: abs_residual.faer_div(b_norm),
+            });
             if abs_residual < threshold {
                 return Ok(CgInfo {
                     abs_residual,
@@ -330,7 +381,17 @@ pub fn conjugate_gradient<E: ComplexField>(
         })
     }
 
-    implementation(out, &precond, &mat, rhs, params, parallelism, stack)
+    let mut callback = callback;
+    implementation(
+        out,
+        &precond,
+        &mat,
+        rhs,
+        params,
+        &mut callback,
+        parallelism,
+        stack,
+    )
 }
 
 #[cfg(test)]
@@ -431,4 +492,48 @@ mod tests {
         assert!((A * out - rhs).norm_l2() <= params.rel_tolerance * rhs.norm_l2());
         assert!(result.iter_count <= 1);
     }
+
+    #[test]
+    fn test_cg_history() {
+        let n = 20;
+        let ref A = Mat::<f64>::from_fn(n, n, |i, j| {
+            if i == j {
+                2.0 + i as f64 / n as f64
+            } else if i.abs_diff(j) == 1 {
+                -1.0
+            } else {
+                0.0
+            }
+        });
+        let ref rhs = Mat::<f64>::from_fn(n, 1, |i, _| (i as f64).sin());
+        let ref mut out = Mat::<f64>::zeros(n, 1);
+        let params = CgParams::default();
+        let precond = linop::IdentityPrecond { dim: n };
+
+        let mut history = alloc::vec::Vec::new();
+        let result = conjugate_gradient_with_callback(
+            out.as_mut(),
+            precond,
+            A.as_ref(),
+            rhs.as_ref(),
+            params,
+            |info| history.push(info),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                conjugate_gradient_req(precond, A.as_ref(), 1, Parallelism::None).unwrap(),
+            )),
+        );
+
+        let result = result.unwrap();
+        assert!(history.len() == result.iter_count + 1);
+        for (i, info) in history.iter().enumerate() {
+            assert!(info.iter_count == i);
+        }
+        assert!(history[0].rel_residual == 1.0);
+
+        let last = history.last().unwrap();
+        assert!(last.abs_residual == result.abs_residual);
+        assert!(last.rel_residual == result.rel_residual);
+        assert!(last.rel_residual <= params.rel_tolerance);
+    }
 }
@@ -0,0 +1,518 @@
+//! Krylov-Schur restarting, shared by the Lanczos and Arnoldi eigensolvers.
+//!
+//! The solvers maintain a Krylov decomposition $AV_m = V_m H_m + v_{m+1} b^H$, where $V_{m+1}$
+//! has orthonormal columns. When the basis is full, the Ritz vectors of the wanted part of the
+//! spectrum of $H_m$ are used to compress the decomposition to a smaller one, which is then
+//! expanded again with the Arnoldi process. This is mathematically equivalent to the implicitly
+//! restarted Arnoldi method with exact shifts used by ARPACK, but is simpler and more stable
+//! since it never applies the shifts explicitly.
+//!
+//! See Stewart, "A Krylov-Schur algorithm for large eigenproblems", 2001.
+
+use crate::{
+    linalg::{
+        evd::{
+            compute_evd_complex, compute_evd_real, compute_evd_req, compute_hermitian_evd,
+            compute_hermitian_evd_req, ComputeVectors,
+        },
+        matmul::matmul,
+        power_iteration::fill_pseudo_random,
+        temp_mat_req, temp_mat_uninit,
+    },
+    linop::{randomized, EigenvalueTarget, LinOp},
+    prelude::*,
+    ComplexField, Parallelism, RealField,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Parameters shared by the Krylov-Schur based eigensolvers.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct KrylovSchurParams<E: ComplexField> {
+    /// Which end of the spectrum the wanted Ritz values are taken from.
+    pub target: EigenvalueTarget,
+    /// Absolute tolerance on the residual norm of each wanted Ritz pair.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residual norm of each wanted Ritz pair, relative to the largest absolute
+    /// Ritz value.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of restarts.
+    pub max_restarts: usize,
+    /// Whether the operator is self-adjoint, in which case the projected matrix is self-adjoint
+    /// and its Ritz values are real.
+    pub hermitian: bool,
+}
+
+/// Outcome of a run of the Krylov-Schur iteration.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct KrylovSchurInfo<E: ComplexField> {
+    /// Whether all the wanted Ritz pairs converged.
+    pub converged: bool,
+    /// Largest residual norm of the wanted Ritz pairs.
+    pub abs_residual: E::Real,
+    /// Largest residual norm of the wanted Ritz pairs, relative to the largest absolute Ritz
+    /// value.
+    pub rel_residual: E::Real,
+    /// Number of restarts performed.
+    pub iter_count: usize,
+}
+
+#[inline]
+pub(crate) fn is_real<E: ComplexField>() -> bool {
+    coe::is_same::<E, E::Real>()
+}
+
+/// Returns the start of the complex conjugate pair containing the eigenvalue at index `i`, as
+/// stored by [`compute_evd_real`], or `None` if the eigenvalue is real.
+pub(crate) fn pair_start<E: RealField>(s_im: ColRef<'_, E>, i: usize) -> Option<usize> {
+    if s_im.read(i) == E::faer_zero() {
+        return None;
+    }
+    // pairs are stored with the positive imaginary part first
+    if s_im.read(i) > E::faer_zero() {
+        Some(i)
+    } else {
+        Some(i - 1)
+    }
+}
+
+/// Orthogonalizes `w` against the orthonormal columns of `basis` using classical Gram-Schmidt
+/// with reorthogonalization, and adds the coefficients to `coeffs`.
+fn orthogonalize<E: ComplexField>(
+    mut coeffs: ColMut<'_, E>,
+    basis: MatRef<'_, E>,
+    mut w: ColMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let (mut c, _) = temp_mat_uninit::<E>(basis.ncols(), 1, stack);
+    for _ in 0..2 {
+        matmul(
+            c.rb_mut(),
+            basis.adjoint(),
+            w.rb().as_2d(),
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        matmul(
+            w.rb_mut().as_2d_mut(),
+            basis,
+            c.rb(),
+            Some(E::faer_one()),
+            E::faer_one().faer_neg(),
+            parallelism,
+        );
+        zipped!(coeffs.rb_mut().as_2d_mut(), c.rb())
+            .for_each(|unzipped!(mut dst, src)| dst.write(dst.read().faer_add(src.read())));
+    }
+}
+
+fn small_evd_req<E: ComplexField>(
+    m: usize,
+    hermitian: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    if hermitian {
+        StackReq::try_all_of([
+            temp_mat_req::<E>(m, m)?,
+            temp_mat_req::<E>(m, 1)?,
+            compute_hermitian_evd_req::<E>(
+                m,
+                ComputeVectors::Yes,
+                parallelism,
+                Default::default(),
+            )?,
+        ])
+    } else if is_real::<E>() {
+        StackReq::try_all_of([
+            temp_mat_req::<E::Real>(m, m)?,
+            temp_mat_req::<E::Real>(m, m)?,
+            compute_evd_req::<E::Real>(m, ComputeVectors::Yes, parallelism, Default::default())?,
+        ])
+    } else {
+        StackReq::try_all_of([
+            temp_mat_req::<E>(m, 1)?,
+            compute_evd_req::<E>(m, ComputeVectors::Yes, parallelism, Default::default())?,
+        ])
+    }
+}
+
+/// Computes the eigendecomposition of `h`. For real non-hermitian matrices, the eigenvectors
+/// are stored in the format used by [`compute_evd_real`].
+fn small_evd<E: ComplexField>(
+    h: MatRef<'_, E>,
+    mut s_re: ColMut<'_, E::Real>,
+    mut s_im: ColMut<'_, E::Real>,
+    mut u: MatMut<'_, E>,
+    hermitian: bool,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let m = h.nrows();
+    if hermitian {
+        let (mut h_sym, stack) = temp_mat_uninit::<E>(m, m, stack);
+        let (mut s, stack) = temp_mat_uninit::<E>(m, 1, stack);
+        // the projected matrix is only hermitian up to rounding errors
+        let half = E::Real::faer_from_f64(0.5);
+        for j in 0..m {
+            h_sym.write(j, j, E::faer_from_real(h.read(j, j).faer_real()));
+            for i in j + 1..m {
+                let hij = h.read(i, j).faer_add(h.read(j, i).faer_conj());
+                h_sym.write(i, j, hij.faer_scale_real(half));
+            }
+        }
+        compute_hermitian_evd(
+            h_sym.rb(),
+            s.rb_mut(),
+            Some(u.rb_mut()),
+            parallelism,
+            stack,
+            Default::default(),
+        );
+        for i in 0..m {
+            s_re.write(i, s.read(i, 0).faer_real());
+            s_im.write(i, E::Real::faer_zero());
+        }
+    } else if is_real::<E>() {
+        let h: MatRef<'_, E::Real> = coe::coerce(h);
+        let (mut h_real, stack) = temp_mat_uninit::<E::Real>(m, m, stack);
+        let (mut u_real, stack) = temp_mat_uninit::<E::Real>(m, m, stack);
+        h_real.copy_from(h);
+        compute_evd_real(
+            h_real.rb(),
+            s_re.rb_mut().as_2d_mut(),
+            s_im.rb_mut().as_2d_mut(),
+            Some(u_real.rb_mut()),
+            parallelism,
+            stack,
+            Default::default(),
+        );
+        zipped!(u.rb_mut(), u_real.rb())
+            .for_each(|unzipped!(mut dst, src)| dst.write(E::faer_from_real(src.read())));
+    } else {
+        let (mut s, stack) = temp_mat_uninit::<E>(m, 1, stack);
+        compute_evd_complex(
+            h,
+            s.rb_mut(),
+            Some(u.rb_mut()),
+            parallelism,
+            stack,
+            Default::default(),
+        );
+        for i in 0..m {
+            s_re.write(i, s.read(i, 0).faer_real());
+            s_im.write(i, s.read(i, 0).faer_imag());
+        }
+    }
+}
+
+/// Returns the residual norm of the Ritz pair at index `i`, which is equal to $|b^H y| / \|y\|$,
+/// where $y$ are the coefficients of the Ritz vector in the krylov basis.
+fn ritz_residual<E: ComplexField>(
+    b: RowRef<'_, E>,
+    u: MatRef<'_, E>,
+    s_im: ColRef<'_, E::Real>,
+    i: usize,
+) -> E::Real {
+    let dot = |j: usize| {
+        let mut acc = E::faer_zero();
+        for p in 0..u.nrows() {
+            acc = acc.faer_add(b.read(p).faer_mul(u.read(p, j)));
+        }
+        acc
+    };
+    let pair = if is_real::<E>() {
+        pair_start(s_im, i)
+    } else {
+        None
+    };
+    match pair {
+        Some(start) => {
+            let re = dot(start).faer_abs2().faer_add(dot(start + 1).faer_abs2());
+            let norm = u
+                .col(start)
+                .squared_norm_l2()
+                .faer_add(u.col(start + 1).squared_norm_l2());
+            re.faer_div(norm).faer_sqrt()
+        }
+        None => dot(i).faer_abs().faer_div(u.col(i).norm_l2()),
+    }
+}
+
+pub(crate) fn krylov_schur_req<E: ComplexField>(
+    A: &dyn LinOp<E>,
+    krylov_dim: usize,
+    hermitian: bool,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let n = A.nrows();
+    let m = krylov_dim;
+    StackReq::try_any_of([
+        A.apply_req(1, parallelism)?,
+        StackReq::try_all_of([
+            temp_mat_req::<E>(m + 1, 1)?, // c
+            temp_mat_req::<E>(m + 1, 1)?, // gram-schmidt coefficients
+        ])?,
+        small_evd_req::<E>(m, hermitian, parallelism)?,
+        StackReq::try_all_of([
+            temp_mat_req::<E>(m, m)?, // q
+            StackReq::try_any_of([
+                randomized::orthonormalize_in_place_req::<E>(m, m, parallelism)?,
+                temp_mat_req::<E>(n, m)?, // vq
+                StackReq::try_all_of([
+                    temp_mat_req::<E>(m + 1, m)?, // hq
+                    temp_mat_req::<E>(m, m)?,     // hp
+                ])?,
+            ])?,
+        ])?,
+    ])
+}
+
+/// Runs the Krylov-Schur iteration, and returns the final Krylov basis in `v`, the
+/// eigendecomposition of the projected matrix in `s_re`, `s_im` and `u`, and the indices of the
+/// Ritz values sorted according to the target in `perm`.
+#[track_caller]
+pub(crate) fn krylov_schur<E: ComplexField>(
+    mut v: MatMut<'_, E>,
+    mut h: MatMut<'_, E>,
+    mut s_re: ColMut<'_, E::Real>,
+    mut s_im: ColMut<'_, E::Real>,
+    mut u: MatMut<'_, E>,
+    perm: &mut [usize],
+    A: &dyn LinOp<E>,
+    start: ColRef<'_, E>,
+    n_eigs: usize,
+    params: KrylovSchurParams<E>,
+    par: Parallelism,
+    mut stack: PodStack<'_>,
+) -> KrylovSchurInfo<E> {
+    let n = A.nrows();
+    let m = h.ncols();
+    let k = n_eigs;
+    let hermitian = params.hermitian;
+
+    let zero = E::Real::faer_zero();
+    let eps = E::Real::faer_epsilon();
+
+    // v_0 = start / ‖start‖
+    let start_norm = start.norm_l2();
+    if start_norm > zero {
+        zipped!(v.rb_mut().col_mut(0).as_2d_mut(), start.as_2d()).for_each(
+            |unzipped!(mut dst, src)| dst.write(src.read().faer_scale_real(start_norm.faer_inv())),
+        );
+    } else {
+        fill_pseudo_random(v.rb_mut().col_mut(0), 0);
+        let norm = v.rb().col(0).norm_l2();
+        zipped!(v.rb_mut().col_mut(0).as_2d_mut())
+            .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(norm.faer_inv())));
+    }
+
+    h.fill_zero();
+    let mut p = 0;
+    let mut info = KrylovSchurInfo {
+        converged: false,
+        abs_residual: zero,
+        rel_residual: zero,
+        iter_count: 0,
+    };
+
+    for iter in 0..=params.max_restarts {
+        info.iter_count = iter;
+
+        // expand the krylov decomposition
+        for j in p..m {
+            let (basis, w) = v.rb_mut().split_at_col_mut(j + 1);
+            let basis = basis.into_const();
+            let mut w = w.col_mut(0);
+            A.apply(
+                w.rb_mut().as_2d_mut(),
+                basis.col(j).as_2d(),
+                par,
+                stack.rb_mut(),
+            );
+            let w_norm = w.rb().norm_l2();
+
+            h.rb_mut().col_mut(j).fill_zero();
+            orthogonalize(
+                h.rb_mut().col_mut(j).subrows_mut(0, j + 1),
+                basis,
+                w.rb_mut(),
+                par,
+                stack.rb_mut(),
+            );
+
+            let mut beta = w.rb().norm_l2();
+            if beta <= eps.faer_mul(E::Real::faer_from_f64(16.0)).faer_mul(w_norm) {
+                // the krylov subspace is invariant, so the residual is zero
+                beta = zero;
+                if j + 1 < m {
+                    // continue with a new vector orthogonal to the basis
+                    fill_pseudo_random(w.rb_mut(), (iter * m + j + 1) as u64);
+                    let (mut c, mut stack) = temp_mat_uninit::<E>(j + 1, 1, stack.rb_mut());
+                    c.fill_zero();
+                    orthogonalize(c.col_mut(0), basis, w.rb_mut(), par, stack.rb_mut());
+                    let norm = w.rb().norm_l2();
+                    zipped!(w.rb_mut().as_2d_mut()).for_each(|unzipped!(mut x)| {
+                        x.write(x.read().faer_scale_real(norm.faer_inv()))
+                    });
+                } else {
+                    w.fill_zero();
+                }
+            } else {
+                let beta_inv = beta.faer_inv();
+                zipped!(w.rb_mut().as_2d_mut())
+                    .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(beta_inv)));
+            }
+            h.write(j + 1, j, E::faer_from_real(beta));
+        }
+
+        // rayleigh-ritz
+        small_evd(
+            h.rb().get(..m, ..),
+            s_re.rb_mut(),
+            s_im.rb_mut(),
+            u.rb_mut(),
+            hermitian,
+            par,
+            stack.rb_mut(),
+        );
+        {
+            let s_re = s_re.rb();
+            let s_im = s_im.rb();
+            let key = |i: usize| match params.target {
+                EigenvalueTarget::LargestMagnitude | EigenvalueTarget::SmallestMagnitude => {
+                    s_re.read(i).faer_abs2().faer_add(s_im.read(i).faer_abs2())
+                }
+                EigenvalueTarget::LargestRealPart | EigenvalueTarget::SmallestRealPart => {
+                    s_re.read(i)
+                }
+            };
+            let descending = matches!(
+                params.target,
+                EigenvalueTarget::LargestMagnitude | EigenvalueTarget::LargestRealPart
+            );
+            for (i, p) in perm.iter_mut().enumerate() {
+                *p = i;
+            }
+            // stable sort, so that complex conjugate pairs stay next to each other
+            for i in 1..m {
+                let mut j = i;
+                while j > 0 && {
+                    let (a, b) = (key(perm[j - 1]), key(perm[j]));
+                    if descending {
+                        a < b
+                    } else {
+                        a > b
+                    }
+                } {
+                    perm.swap(j - 1, j);
+                    j -= 1;
+                }
+            }
+        }
+
+        // convergence check
+        let mut norm = zero;
+        for i in 0..m {
+            let abs = s_re
+                .read(i)
+                .faer_abs2()
+                .faer_add(s_im.read(i).faer_abs2())
+                .faer_sqrt();
+            if abs > norm {
+                norm = abs;
+            }
+        }
+        if norm == zero {
+            norm = E::Real::faer_one();
+        }
+        let threshold = params
+            .rel_tolerance
+            .faer_mul(norm)
+            .faer_add(params.abs_tolerance);
+
+        let b = h.rb().row(m);
+        let mut converged = true;
+        let mut abs_residual = zero;
+        for &i in &perm[..k] {
+            let r = ritz_residual(b, u.rb(), s_im.rb(), i);
+            if r > threshold || r.faer_is_nan() {
+                converged = false;
+            }
+            if r > abs_residual {
+                abs_residual = r;
+            }
+        }
+        info.abs_residual = abs_residual;
+        info.rel_residual = abs_residual.faer_div(norm);
+        info.converged = converged;
+        if converged || iter == params.max_restarts {
+            break;
+        }
+
+        // keep the wanted ritz vectors, as well as some of the next ones to speed up the
+        // convergence
+        let mut new_p = Ord::min(k + (m - k) / 2, m - 1);
+        if !hermitian && is_real::<E>() {
+            // complex conjugate pairs must not be split, since the kept subspace must be real
+            let last = perm[new_p - 1];
+            if let Some(start) = pair_start(s_im.rb(), last) {
+                let partner = if last == start { start + 1 } else { start };
+                if !perm[..new_p].contains(&partner) {
+                    if new_p + 1 < m {
+                        perm.swap(new_p, perm.iter().position(|&x| x == partner).unwrap());
+                        new_p += 1;
+                    } else {
+                        new_p -= 1;
+                    }
+                }
+            }
+        }
+        p = new_p;
+
+        let (mut q, mut stack) = temp_mat_uninit::<E>(m, p, stack.rb_mut());
+        for (j, &i) in perm[..p].iter().enumerate() {
+            q.rb_mut().col_mut(j).copy_from(u.rb().col(i));
+        }
+        randomized::orthonormalize_in_place(q.rb_mut(), par, stack.rb_mut());
+
+        // V_p = V_m Q, v_{p+1} = v_{m+1}
+        {
+            let (mut vq, _) = temp_mat_uninit::<E>(n, p, stack.rb_mut());
+            matmul(
+                vq.rb_mut(),
+                v.rb().get(.., ..m),
+                q.rb(),
+                None,
+                E::faer_one(),
+                par,
+            );
+            v.rb_mut().get_mut(.., ..p).copy_from(vq.rb());
+            let (mut left, right) = v.rb_mut().split_at_col_mut(m);
+            left.rb_mut().col_mut(p).copy_from(right.rb().col(0));
+        }
+
+        // H_p = Qᴴ H_m Q, bᴴ = bᴴ Q
+        {
+            let (mut hq, mut stack) = temp_mat_uninit::<E>(m + 1, p, stack.rb_mut());
+            matmul(hq.rb_mut(), h.rb(), q.rb(), None, E::faer_one(), par);
+            let (mut hp, _) = temp_mat_uninit::<E>(p, p, stack.rb_mut());
+            matmul(
+                hp.rb_mut(),
+                q.rb().adjoint(),
+                hq.rb().get(..m, ..),
+                None,
+                E::faer_one(),
+                par,
+            );
+            h.fill_zero();
+            h.rb_mut().get_mut(..p, ..p).copy_from(hp.rb());
+            h.rb_mut()
+                .get_mut(p..p + 1, ..p)
+                .copy_from(hq.rb().get(m..m + 1, ..));
+        }
+    }
+
+    info
+}
@@ -0,0 +1,371 @@
+//! Restarted Lanczos eigensolver for self-adjoint operators.
+//!
+//! [`lanczos`] computes a few eigenpairs at one end of the spectrum of a self-adjoint operator,
+//! accessing it only through the [`LinOp`] trait. The Krylov basis is kept orthonormal with full
+//! reorthogonalization, and restarted with the Krylov-Schur method (also known as thick restart
+//! Lanczos for self-adjoint operators), which is mathematically equivalent to the implicitly
+//! restarted Lanczos method with exact shifts.
+//!
+//! Interior eigenvalues close to a shift $\sigma$ can be computed in shift-invert mode, by
+//! passing a [`ShiftInvert`](crate::linop::ShiftInvert) operator wrapping a factorization of
+//! $A - \sigma I$, and targeting the eigenvalues with the largest magnitude.
+
+use crate::{
+    linalg::{matmul::matmul, temp_mat_req, temp_mat_uninit},
+    linop::{
+        krylov_schur::{krylov_schur, krylov_schur_req, KrylovSchurParams},
+        EigenvalueTarget, LinOp,
+    },
+    prelude::*,
+    ComplexField, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use equator::assert;
+use reborrow::*;
+
+/// Parameters of the restarted Lanczos eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct LanczosParams<E: ComplexField> {
+    /// Which end of the spectrum the computed eigenvalues are taken from.
+    pub target: EigenvalueTarget,
+    /// Absolute tolerance on the residual norm of each Ritz pair.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the residual norm of each Ritz pair, relative to the largest absolute Ritz
+    /// value.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of restarts.
+    pub max_restarts: usize,
+}
+
+/// Information about a successful run of the restarted Lanczos eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct LanczosInfo<E: ComplexField> {
+    /// Largest residual norm of the computed Ritz pairs.
+    pub abs_residual: E::Real,
+    /// Largest residual norm of the computed Ritz pairs, relative to the largest absolute Ritz
+    /// value.
+    pub rel_residual: E::Real,
+    /// Number of restarts performed.
+    pub iter_count: usize,
+}
+
+/// Error of the restarted Lanczos eigensolver.
+#[derive(Copy, Clone, Debug)]
+pub enum LanczosError<E: ComplexField> {
+    /// The Ritz pairs did not converge within the maximum number of restarts.
+    NoConvergence {
+        /// Largest residual norm of the last computed Ritz pairs.
+        abs_residual: E::Real,
+        /// Largest residual norm of the last computed Ritz pairs, relative to the largest
+        /// absolute Ritz value.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for LanczosParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            target: EigenvalueTarget::LargestMagnitude,
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_mul(E::Real::faer_from_f64(128.0)),
+            max_restarts: 1000,
+        }
+    }
+}
+
+/// Computes the size and alignment of the workspace required to compute `n_eigs` eigenpairs
+/// with [`lanczos`], using a Krylov basis of dimension `krylov_dim`.
+pub fn lanczos_req<E: ComplexField>(
+    mat: impl LinOp<E>,
+    n_eigs: usize,
+    krylov_dim: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        A: &dyn LinOp<E>,
+        k: usize,
+        m: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let n = A.nrows();
+        StackReq::try_all_of([
+            temp_mat_req::<E>(n, m + 1)?,   // v
+            temp_mat_req::<E>(m + 1, m)?,   // h
+            temp_mat_req::<E::Real>(m, 1)?, // s_re
+            temp_mat_req::<E::Real>(m, 1)?, // s_im
+            temp_mat_req::<E>(m, m)?,       // u
+            StackReq::try_new::<usize>(m)?, // perm
+            StackReq::try_any_of([
+                krylov_schur_req::<E>(A, m, true, parallelism)?,
+                temp_mat_req::<E>(m, k)?, // y
+            ])?,
+        ])
+    }
+    implementation(&mat, n_eigs, krylov_dim, parallelism)
+}
+
+/// Computes `eigenvalues.nrows()` eigenpairs of the self-adjoint operator `mat`, using a Krylov
+/// basis of dimension `krylov_dim`, and the starting vector `start`.
+///
+/// The eigenvalues are sorted according to `params.target`, and stored in `eigenvalues`, and the
+/// corresponding orthonormal eigenvectors are stored in `eigenvectors`. The outputs are filled
+/// even if the iteration does not converge.
+///
+/// A larger Krylov basis dimension usually reduces the number of restarts, at the cost of more
+/// memory and orthogonalization work. A value of twice the number of eigenvalues, or at least
+/// `20`, is a reasonable default.
+///
+/// # Panics
+/// Panics if `mat` is not square, if `start` or `eigenvectors` don't have `mat.nrows()` rows, if
+/// `eigenvectors` doesn't have `eigenvalues.nrows()` columns, if `krylov_dim` is not larger than
+/// the number of eigenvalues or is larger than `mat.nrows()`, or if the provided memory in
+/// `stack` is insufficient (see [`lanczos_req`]).
+#[track_caller]
+pub fn lanczos<E: ComplexField>(
+    eigenvectors: MatMut<'_, E>,
+    eigenvalues: ColMut<'_, E::Real>,
+    mat: impl LinOp<E>,
+    start: ColRef<'_, E>,
+    krylov_dim: usize,
+    params: LanczosParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<LanczosInfo<E>, LanczosError<E>> {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut x: MatMut<'_, E>,
+        mut eigenvalues: ColMut<'_, E::Real>,
+        A: &dyn LinOp<E>,
+        start: ColRef<'_, E>,
+        m: usize,
+        params: LanczosParams<E>,
+        par: Parallelism,
+        stack: PodStack<'_>,
+    ) -> Result<LanczosInfo<E>, LanczosError<E>> {
+        let n = A.nrows();
+        let k = eigenvalues.nrows();
+        assert!(all(
+            A.ncols() == n,
+            start.nrows() == n,
+            x.nrows() == n,
+            x.ncols() == k,
+            k < m,
+            m <= n,
+        ));
+
+        let (mut v, stack) = temp_mat_uninit::<E>(n, m + 1, stack);
+        let (mut h, stack) = temp_mat_uninit::<E>(m + 1, m, stack);
+        let (mut s_re, stack) = temp_mat_uninit::<E::Real>(m, 1, stack);
+        let (mut s_im, stack) = temp_mat_uninit::<E::Real>(m, 1, stack);
+        let (mut u, stack) = temp_mat_uninit::<E>(m, m, stack);
+        let (perm, mut stack) = stack.make_with(m, |i| i);
+
+        let info = krylov_schur(
+            v.rb_mut(),
+            h.rb_mut(),
+            s_re.rb_mut().col_mut(0),
+            s_im.rb_mut().col_mut(0),
+            u.rb_mut(),
+            perm,
+            A,
+            start,
+            k,
+            KrylovSchurParams {
+                target: params.target,
+                abs_tolerance: params.abs_tolerance,
+                rel_tolerance: params.rel_tolerance,
+                max_restarts: params.max_restarts,
+                hermitian: true,
+            },
+            par,
+            stack.rb_mut(),
+        );
+
+        // x = V y
+        let (mut y, _) = temp_mat_uninit::<E>(m, k, stack.rb_mut());
+        for (j, &i) in perm[..k].iter().enumerate() {
+            eigenvalues.write(j, s_re.read(i, 0));
+            y.rb_mut().col_mut(j).copy_from(u.rb().col(i));
+        }
+        matmul(
+            x.rb_mut(),
+            v.rb().get(.., ..m),
+            y.rb(),
+            None,
+            E::faer_one(),
+            par,
+        );
+
+        if info.converged {
+            Ok(LanczosInfo {
+                abs_residual: info.abs_residual,
+                rel_residual: info.rel_residual,
+                iter_count: info.iter_count,
+            })
+        } else {
+            Err(LanczosError::NoConvergence {
+                abs_residual: info.abs_residual,
+                rel_residual: info.rel_residual,
+            })
+        }
+    }
+
+    implementation(
+        eigenvectors,
+        eigenvalues,
+        &mat,
+        start,
+        krylov_dim,
+        params,
+        parallelism,
+        stack,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linop::ShiftInvert, sparse::SparseColMat};
+    use dyn_stack::GlobalPodBuffer;
+    use equator::assert;
+    use rand::prelude::*;
+
+    fn laplacian_1d(n: usize, shift: f64) -> SparseColMat<usize, f64> {
+        let mut triplets = alloc::vec::Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.0 - shift));
+            if i + 1 < n {
+                triplets.push((i + 1, i, -1.0));
+                triplets.push((i, i + 1, -1.0));
+            }
+        }
+        SparseColMat::try_new_from_triplets(n, n, &triplets).unwrap()
+    }
+
+    fn laplacian_eigenvalue(n: usize, i: usize) -> f64 {
+        2.0 - 2.0 * f64::cos((i + 1) as f64 * core::f64::consts::PI / (n + 1) as f64)
+    }
+
+    #[test]
+    fn test_lanczos_extremal() {
+        let n = 200;
+        let k = 4;
+        let m = 20;
+        let A = laplacian_1d(n, 0.0);
+        let start = Col::<f64>::from_fn(n, |i| 1.0 + (i % 7) as f64);
+
+        for target in [
+            EigenvalueTarget::LargestRealPart,
+            EigenvalueTarget::SmallestRealPart,
+        ] {
+            let mut x = Mat::<f64>::zeros(n, k);
+            let mut eigenvalues = Col::<f64>::zeros(k);
+            let mut params = LanczosParams::default();
+            params.target = target;
+            let info = lanczos(
+                x.as_mut(),
+                eigenvalues.as_mut(),
+                A.as_ref(),
+                start.as_ref(),
+                m,
+                params,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    lanczos_req::<f64>(A.as_ref(), k, m, Parallelism::None).unwrap(),
+                )),
+            )
+            .unwrap();
+            assert!(info.rel_residual <= params.rel_tolerance);
+
+            for j in 0..k {
+                let exact = match target {
+                    EigenvalueTarget::LargestRealPart => laplacian_eigenvalue(n, n - 1 - j),
+                    _ => laplacian_eigenvalue(n, j),
+                };
+                assert!((eigenvalues.read(j) - exact).abs() < 1e-10);
+            }
+            let dense = A.to_dense();
+            let residual = &dense * &x - &x * eigenvalues.as_ref().column_vector_as_diagonal();
+            assert!(residual.norm_max() < 1e-10);
+            assert!((x.transpose() * &x - Mat::<f64>::identity(k, k)).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_lanczos_complex() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let n = 50;
+        let k = 3;
+        let Q: Mat<c64> = crate::stats::UnitaryMat { dimension: n }.sample(rng);
+        let d = Col::<c64>::from_fn(n, |i| c64::new(i as f64 - 20.0, 0.0));
+        let A = &Q * d.as_ref().column_vector_as_diagonal() * Q.adjoint();
+        let start = Col::<c64>::from_fn(n, |i| c64::new(1.0, i as f64));
+
+        let mut x = Mat::<c64>::zeros(n, k);
+        let mut eigenvalues = Col::<f64>::zeros(k);
+        lanczos(
+            x.as_mut(),
+            eigenvalues.as_mut(),
+            A.as_ref(),
+            start.as_ref(),
+            15,
+            LanczosParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                lanczos_req::<c64>(A.as_ref(), k, 15, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        // largest magnitude: 29, 28, 27
+        for j in 0..k {
+            assert!((eigenvalues.read(j) - (29.0 - j as f64)).abs() < 1e-10);
+        }
+        assert!((x.adjoint() * &x - Mat::<c64>::identity(k, k)).norm_max() < 1e-10);
+    }
+
+    #[test]
+    fn test_lanczos_shift_invert() {
+        let n = 200;
+        let k = 3;
+        let m = 12;
+        let shift = 1.0;
+        let A = laplacian_1d(n, 0.0);
+        let lu = laplacian_1d(n, shift).sp_lu().unwrap();
+        let op = ShiftInvert::new(&lu, shift);
+        assert!(op.nrows() == n);
+        let start = Col::<f64>::from_fn(n, |i| 1.0 + (i % 5) as f64);
+
+        let mut x = Mat::<f64>::zeros(n, k);
+        let mut eigenvalues = Col::<f64>::zeros(k);
+        lanczos(
+            x.as_mut(),
+            eigenvalues.as_mut(),
+            op,
+            start.as_ref(),
+            m,
+            LanczosParams::default(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                lanczos_req::<f64>(op, k, m, Parallelism::None).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        // the eigenvalues closest to the shift
+        let mut exact = (0..n)
+            .map(|i| laplacian_eigenvalue(n, i))
+            .collect::<alloc::vec::Vec<_>>();
+        exact.sort_by(|a, b| (a - shift).abs().partial_cmp(&(b - shift).abs()).unwrap());
+        let dense = A.to_dense();
+        for j in 0..k {
+            let lambda = op.eigenvalue(eigenvalues.read(j));
+            assert!((lambda - exact[j]).abs() < 1e-10);
+            let r = &dense * x.col(j) - x.col(j) * crate::scale(lambda);
+            assert!(r.norm_max() < 1e-8);
+        }
+    }
+}
@@ -1,3 +1,4 @@
 mod matmut;
 mod matown;
 mod matref;
+mod sparse;
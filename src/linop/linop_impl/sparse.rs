@@ -0,0 +1,314 @@
+use crate::{
+    linop::{BiLinOp, BiPrecond, LinOp, Precond},
+    sparse::{
+        linalg::matmul::{dense_sparse_matmul, sparse_dense_matmul},
+        SparseColMat, SparseColMatRef, SparseRowMatRef,
+    },
+    ComplexField, Conjugate, Index, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> LinOp<E>
+    for SparseColMatRef<'_, I, ViewE>
+{
+    #[inline]
+    fn nrows(&self) -> usize {
+        (*self).nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        (*self).ncols()
+    }
+
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        sparse_dense_matmul(out, *self, rhs, None, E::faer_one(), parallelism);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        let this = (*self).conjugate();
+        sparse_dense_matmul(out, this, rhs, None, E::faer_one(), parallelism);
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiLinOp<E>
+    for SparseColMatRef<'_, I, ViewE>
+{
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        // Aᵀ B = (Bᵀ A)ᵀ
+        dense_sparse_matmul(
+            out.transpose_mut(),
+            rhs.transpose(),
+            *self,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+
+    #[inline]
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        let this = (*self).conjugate();
+        dense_sparse_matmul(
+            out.transpose_mut(),
+            rhs.transpose(),
+            this,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> Precond<E>
+    for SparseColMatRef<'_, I, ViewE>
+{
+}
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiPrecond<E>
+    for SparseColMatRef<'_, I, ViewE>
+{
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> LinOp<E>
+    for SparseRowMatRef<'_, I, ViewE>
+{
+    #[inline]
+    fn nrows(&self) -> usize {
+        (*self).nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        (*self).ncols()
+    }
+
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        // A B = (Bᵀ Aᵀ)ᵀ, where Aᵀ is column-major
+        self.transpose()
+            .transpose_apply(out, rhs, parallelism, stack);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.transpose().adjoint_apply(out, rhs, parallelism, stack);
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiLinOp<E>
+    for SparseRowMatRef<'_, I, ViewE>
+{
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.transpose().apply(out, rhs, parallelism, stack);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.transpose().conj_apply(out, rhs, parallelism, stack);
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> Precond<E>
+    for SparseRowMatRef<'_, I, ViewE>
+{
+}
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiPrecond<E>
+    for SparseRowMatRef<'_, I, ViewE>
+{
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> LinOp<E>
+    for SparseColMat<I, ViewE>
+{
+    #[inline]
+    fn nrows(&self) -> usize {
+        (*self).nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        (*self).ncols()
+    }
+
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.as_ref().apply(out, rhs, parallelism, stack);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.as_ref().conj_apply(out, rhs, parallelism, stack);
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiLinOp<E>
+    for SparseColMat<I, ViewE>
+{
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.as_ref().transpose_apply(out, rhs, parallelism, stack);
+    }
+
+    #[inline]
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        self.as_ref().adjoint_apply(out, rhs, parallelism, stack);
+    }
+}
+
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> Precond<E>
+    for SparseColMat<I, ViewE>
+{
+}
+impl<I: Index, E: ComplexField, ViewE: Conjugate<Canonical = E>> BiPrecond<E>
+    for SparseColMat<I, ViewE>
+{
+}
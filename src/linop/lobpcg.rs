@@ -0,0 +1,539 @@
+//! Locally optimal block preconditioned conjugate gradient (LOBPCG) eigensolver.
+//!
+//! [`lobpcg`] computes a few of the smallest or largest eigenpairs of a self-adjoint operator,
+//! accessing it only through the [`LinOp`] trait, so that it can be used with dense matrices,
+//! sparse matrices, or matrix-free operators. An optional [`Precond`] approximating the inverse
+//! of the operator can be provided to accelerate the convergence, which is typically the case
+//! for finite element or graph Laplacian problems.
+//!
+//! At each iteration, the Rayleigh-Ritz method is applied to the subspace spanned by the current
+//! approximate eigenvectors $X$, the preconditioned residuals $W$, and the previous search
+//! directions $P$. The basis of that subspace is explicitly orthonormalized, which makes the
+//! iteration robust when the blocks become nearly linearly dependent close to convergence, at the
+//! cost of applying the operator to the whole basis.
+//!
+//! See Knyazev, "Toward the optimal preconditioned eigensolver: locally optimal block
+//! preconditioned conjugate gradient method", 2001.
+
+use crate::{
+    linalg::{
+        evd::{compute_hermitian_evd, compute_hermitian_evd_req, ComputeVectors},
+        matmul::matmul,
+        solvers::Which,
+        temp_mat_req, temp_mat_uninit,
+    },
+    linop::{randomized, LinOp, Precond},
+    prelude::*,
+    ComplexField, Parallelism, RealField,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use equator::assert;
+use reborrow::*;
+
+/// Parameters of the LOBPCG eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct LobpcgParams<E: ComplexField> {
+    /// Whether the smallest or the largest eigenvalues are computed.
+    pub which: Which,
+    /// Absolute tolerance on the norm of the residual of each eigenpair.
+    pub abs_tolerance: E::Real,
+    /// Tolerance on the norm of the residual of each eigenpair, relative to the largest absolute
+    /// Ritz value.
+    pub rel_tolerance: E::Real,
+    /// Maximum number of iterations.
+    pub max_iters: usize,
+}
+
+/// Information about a successful run of the LOBPCG eigensolver.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct LobpcgInfo<E: ComplexField> {
+    /// Largest norm of the residuals of the computed eigenpairs.
+    pub abs_residual: E::Real,
+    /// Largest norm of the residuals of the computed eigenpairs, relative to the largest absolute
+    /// Ritz value.
+    pub rel_residual: E::Real,
+    /// Number of iterations performed.
+    pub iter_count: usize,
+}
+
+/// Error of the LOBPCG eigensolver.
+#[derive(Copy, Clone, Debug)]
+pub enum LobpcgError<E: ComplexField> {
+    /// The eigenpairs did not converge within the maximum number of iterations.
+    NoConvergence {
+        /// Largest norm of the residuals of the last computed eigenpairs.
+        abs_residual: E::Real,
+        /// Largest norm of the residuals of the last computed eigenpairs, relative to the
+        /// largest absolute Ritz value.
+        rel_residual: E::Real,
+    },
+}
+
+impl<E: ComplexField> Default for LobpcgParams<E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            which: Which::Smallest,
+            abs_tolerance: E::Real::faer_zero(),
+            rel_tolerance: E::Real::faer_epsilon().faer_sqrt(),
+            max_iters: 1000,
+        }
+    }
+}
+
+fn rayleigh_ritz_req<E: ComplexField>(
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        temp_mat_req::<E>(ncols, ncols)?, // h
+        temp_mat_req::<E>(ncols, 1)?,     // theta
+        temp_mat_req::<E>(ncols, ncols)?, // u
+        StackReq::try_new::<usize>(ncols)?,
+        compute_hermitian_evd_req::<E>(
+            ncols,
+            ComputeVectors::Yes,
+            parallelism,
+            Default::default(),
+        )?,
+    ])
+}
+
+/// Computes the Ritz pairs of the operator on the range of the orthonormal basis `q`, given
+/// `aq = A q`, and stores the coefficients of the selected Ritz vectors in `c`, and the
+/// corresponding Ritz values in `eigenvalues`. Returns the largest absolute Ritz value.
+fn rayleigh_ritz<E: ComplexField>(
+    mut c: MatMut<'_, E>,
+    mut eigenvalues: ColMut<'_, E::Real>,
+    q: MatRef<'_, E>,
+    aq: MatRef<'_, E>,
+    which: Which,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> E::Real {
+    let s = q.ncols();
+    let k = c.ncols();
+
+    let (mut h, stack) = temp_mat_uninit::<E>(s, s, stack);
+    let (mut theta, stack) = temp_mat_uninit::<E>(s, 1, stack);
+    let (mut u, stack) = temp_mat_uninit::<E>(s, s, stack);
+    let (perm, stack) = stack.make_with(s, |i| i);
+
+    matmul(
+        h.rb_mut(),
+        q.adjoint(),
+        aq,
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+    // the projected matrix is only hermitian up to rounding errors
+    let half = E::Real::faer_from_f64(0.5);
+    for j in 0..s {
+        h.write(j, j, E::faer_from_real(h.read(j, j).faer_real()));
+        for i in j + 1..s {
+            let hij = h.read(i, j).faer_add(h.read(j, i).faer_conj());
+            h.write(i, j, hij.faer_scale_real(half));
+        }
+    }
+
+    compute_hermitian_evd(
+        h.rb(),
+        theta.rb_mut(),
+        Some(u.rb_mut()),
+        parallelism,
+        stack,
+        Default::default(),
+    );
+
+    let theta = theta.rb();
+    match which {
+        Which::Smallest => perm.sort_unstable_by(|&i, &j| {
+            theta
+                .read(i, 0)
+                .faer_real()
+                .partial_cmp(&theta.read(j, 0).faer_real())
+                .unwrap_or(core::cmp::Ordering::Equal)
+        }),
+        Which::Largest => perm.sort_unstable_by(|&i, &j| {
+            theta
+                .read(j, 0)
+                .faer_real()
+                .partial_cmp(&theta.read(i, 0).faer_real())
+                .unwrap_or(core::cmp::Ordering::Equal)
+        }),
+    }
+
+    let mut max = E::Real::faer_zero();
+    for i in 0..s {
+        let abs = theta.read(i, 0).faer_real().faer_abs();
+        if abs > max {
+            max = abs;
+        }
+    }
+    for j in 0..k {
+        eigenvalues.write(j, theta.read(perm[j], 0).faer_real());
+        c.rb_mut().col_mut(j).copy_from(u.rb().col(perm[j]));
+    }
+    max
+}
+
+/// Computes the size and alignment of the workspace required to compute `n_eigs` eigenpairs
+/// with [`lobpcg`].
+pub fn lobpcg_req<E: ComplexField>(
+    precond: impl Precond<E>,
+    mat: impl LinOp<E>,
+    n_eigs: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        M: &dyn Precond<E>,
+        A: &dyn LinOp<E>,
+        k: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let n = A.nrows();
+        let s = Ord::min(3 * k, n);
+
+        let nk = temp_mat_req::<E>(n, k)?;
+        let ns = temp_mat_req::<E>(n, s)?;
+        let sk = temp_mat_req::<E>(s, k)?;
+        StackReq::try_all_of([
+            nk, // ax
+            nk, // w
+            nk, // p
+            ns, // q
+            ns, // aq
+            sk, // c
+            StackReq::try_any_of([
+                A.apply_req(s, parallelism)?,
+                M.apply_in_place_req(k, parallelism)?,
+                randomized::orthonormalize_in_place_req::<E>(n, s, parallelism)?,
+                rayleigh_ritz_req::<E>(s, parallelism)?,
+            ])?,
+        ])
+    }
+    implementation(&precond, &mat, n_eigs, parallelism)
+}
+
+/// Computes the `eigenvectors.ncols()` smallest or largest eigenvalues of the self-adjoint
+/// operator `mat`, depending on `params.which`, and stores them in `eigenvalues`, with the
+/// corresponding eigenvectors in `eigenvectors`.
+///
+/// On entry, `eigenvectors` contains the initial guess, which must have full column rank. The
+/// preconditioner `precond` should approximate the inverse of `mat`, and can be
+/// [`IdentityPrecond`](crate::linop::IdentityPrecond) if none is available.
+///
+/// An eigenpair $(\lambda, x)$ is considered converged when the norm of its residual
+/// $Ax - \lambda x$ is at most `abs_tolerance + rel_tolerance * theta`, where `theta` is the
+/// largest absolute Ritz value.
+///
+/// # Panics
+/// Panics if `mat` or `precond` are not square with the same dimension as the number of rows of
+/// `eigenvectors`, if `eigenvalues` doesn't have the same number of rows as the number of columns
+/// of `eigenvectors`, or if more eigenpairs are requested than the dimension of `mat`.
+#[track_caller]
+pub fn lobpcg<E: ComplexField>(
+    eigenvectors: MatMut<'_, E>,
+    eigenvalues: ColMut<'_, E::Real>,
+    precond: impl Precond<E>,
+    mat: impl LinOp<E>,
+    params: LobpcgParams<E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<LobpcgInfo<E>, LobpcgError<E>> {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut x: MatMut<'_, E>,
+        mut eigenvalues: ColMut<'_, E::Real>,
+        M: &dyn Precond<E>,
+        A: &dyn LinOp<E>,
+        params: LobpcgParams<E>,
+        par: Parallelism,
+        mut stack: PodStack<'_>,
+    ) -> Result<LobpcgInfo<E>, LobpcgError<E>> {
+        let n = A.nrows();
+        let k = x.ncols();
+        assert!(all(
+            A.ncols() == n,
+            M.nrows() == n,
+            M.ncols() == n,
+            x.nrows() == n,
+            eigenvalues.nrows() == k,
+            k <= n,
+        ));
+
+        if k == 0 {
+            return Ok(LobpcgInfo {
+                abs_residual: E::Real::faer_zero(),
+                rel_residual: E::Real::faer_zero(),
+                iter_count: 0,
+            });
+        }
+
+        let s_max = Ord::min(3 * k, n);
+
+        let (mut ax, mut stack) = temp_mat_uninit::<E>(n, k, stack.rb_mut());
+        let (mut w, mut stack) = temp_mat_uninit::<E>(n, k, stack.rb_mut());
+        let (mut p, mut stack) = temp_mat_uninit::<E>(n, k, stack.rb_mut());
+        let (mut q, mut stack) = temp_mat_uninit::<E>(n, s_max, stack.rb_mut());
+        let (mut aq, mut stack) = temp_mat_uninit::<E>(n, s_max, stack.rb_mut());
+        let (mut c, mut stack) = temp_mat_uninit::<E>(s_max, k, stack.rb_mut());
+
+        // rayleigh-ritz on the initial guess
+        let mut norm = {
+            let mut q = q.rb_mut().get_mut(.., ..k);
+            let mut aq = aq.rb_mut().get_mut(.., ..k);
+            let mut c = c.rb_mut().get_mut(..k, ..);
+            q.copy_from(x.rb());
+            randomized::orthonormalize_in_place(q.rb_mut(), par, stack.rb_mut());
+            A.apply(aq.rb_mut(), q.rb(), par, stack.rb_mut());
+            let norm = rayleigh_ritz(
+                c.rb_mut(),
+                eigenvalues.rb_mut(),
+                q.rb(),
+                aq.rb(),
+                params.which,
+                par,
+                stack.rb_mut(),
+            );
+            matmul(x.rb_mut(), q.rb(), c.rb(), None, E::faer_one(), par);
+            matmul(ax.rb_mut(), aq.rb(), c.rb(), None, E::faer_one(), par);
+            norm
+        };
+
+        let mut n_directions = 0;
+        let mut abs_residual = E::Real::faer_zero();
+        let mut rel_residual = E::Real::faer_zero();
+        for iter in 0..=params.max_iters {
+            // w = A x - x Λ
+            let mut converged = true;
+            abs_residual = E::Real::faer_zero();
+            let norm_scale = if norm == E::Real::faer_zero() {
+                E::Real::faer_one()
+            } else {
+                norm
+            };
+            for j in 0..k {
+                let lambda = eigenvalues.read(j);
+                zipped!(w.rb_mut().col_mut(j), ax.rb().col(j), x.rb().col(j)).for_each(
+                    |unzipped!(mut w, ax, x)| {
+                        w.write(ax.read().faer_sub(x.read().faer_scale_real(lambda)))
+                    },
+                );
+                let r = w.rb().col(j).norm_l2();
+                let threshold = params
+                    .rel_tolerance
+                    .faer_mul(norm_scale)
+                    .faer_add(params.abs_tolerance);
+                if r > threshold || r.faer_is_nan() {
+                    converged = false;
+                }
+                if r > abs_residual {
+                    abs_residual = r;
+                }
+            }
+            rel_residual = abs_residual.faer_div(norm_scale);
+            if converged {
+                return Ok(LobpcgInfo {
+                    abs_residual,
+                    rel_residual,
+                    iter_count: iter,
+                });
+            }
+            if iter == params.max_iters {
+                break;
+            }
+
+            M.apply_in_place(w.rb_mut(), par, stack.rb_mut());
+
+            // q = orth([x, w, p])
+            let s = Ord::min(2 * k + n_directions, s_max);
+            {
+                let mut q = q.rb_mut().get_mut(.., ..s);
+                q.rb_mut().get_mut(.., ..k).copy_from(x.rb());
+                let n_w = Ord::min(k, s - k);
+                q.rb_mut()
+                    .get_mut(.., k..k + n_w)
+                    .copy_from(w.rb().get(.., ..n_w));
+                let n_p = s - k - n_w;
+                q.rb_mut()
+                    .get_mut(.., k + n_w..)
+                    .copy_from(p.rb().get(.., ..n_p));
+                // the leading columns of the orthonormal factor span the same subspace as x
+                randomized::orthonormalize_in_place(q, par, stack.rb_mut());
+            }
+            let q = q.rb().get(.., ..s);
+            let mut aq = aq.rb_mut().get_mut(.., ..s);
+            let mut c = c.rb_mut().get_mut(..s, ..);
+            A.apply(aq.rb_mut(), q, par, stack.rb_mut());
+
+            norm = rayleigh_ritz(
+                c.rb_mut(),
+                eigenvalues.rb_mut(),
+                q,
+                aq.rb(),
+                params.which,
+                par,
+                stack.rb_mut(),
+            );
+
+            matmul(x.rb_mut(), q, c.rb(), None, E::faer_one(), par);
+            matmul(ax.rb_mut(), aq.rb(), c.rb(), None, E::faer_one(), par);
+
+            // the new search directions are the components of the update that are orthogonal to
+            // the previous eigenvectors
+            if s > k {
+                matmul(
+                    p.rb_mut(),
+                    q.get(.., k..),
+                    c.rb().get(k.., ..),
+                    None,
+                    E::faer_one(),
+                    par,
+                );
+                n_directions = k;
+            }
+        }
+
+        Err(LobpcgError::NoConvergence {
+            abs_residual,
+            rel_residual,
+        })
+    }
+
+    implementation(
+        eigenvectors,
+        eigenvalues,
+        &precond,
+        &mat,
+        params,
+        parallelism,
+        stack,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{linop, sparse::SparseColMat};
+    use dyn_stack::GlobalPodBuffer;
+    use equator::assert;
+    use rand::prelude::*;
+
+    fn laplacian_1d(n: usize) -> SparseColMat<usize, f64> {
+        let mut triplets = alloc::vec::Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, 2.0));
+            if i + 1 < n {
+                triplets.push((i + 1, i, -1.0));
+                triplets.push((i, i + 1, -1.0));
+            }
+        }
+        SparseColMat::try_new_from_triplets(n, n, &triplets).unwrap()
+    }
+
+    #[test]
+    fn test_lobpcg_laplacian() {
+        let rng = &mut StdRng::seed_from_u64(0);
+        let n = 100;
+        let k = 4;
+        let A = laplacian_1d(n);
+        let precond = linop::IdentityPrecond { dim: n };
+
+        for which in [Which::Smallest, Which::Largest] {
+            let mut x = crate::linop::randomized::gaussian_sketch::<f64>(n, k, rng);
+            let mut eigenvalues = Col::<f64>::zeros(k);
+            let mut params = LobpcgParams::default();
+            params.which = which;
+            params.rel_tolerance = 1e-10;
+
+            let result = lobpcg(
+                x.as_mut(),
+                eigenvalues.as_mut(),
+                precond,
+                A.as_ref(),
+                params,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    lobpcg_req::<f64>(precond, A.as_ref(), k, Parallelism::None).unwrap(),
+                )),
+            );
+            assert!(result.is_ok());
+
+            // eigenvalues of the 1d laplacian are 2 - 2 cos(jπ/(n+1))
+            for j in 0..k {
+                let idx = match which {
+                    Which::Smallest => j + 1,
+                    Which::Largest => n - j,
+                };
+                let exact =
+                    2.0 - 2.0 * f64::cos(idx as f64 * core::f64::consts::PI / (n + 1) as f64);
+                assert!((eigenvalues.read(j) - exact).abs() < 1e-10);
+            }
+            let dense = A.to_dense();
+            let residual = &dense * &x - &x * eigenvalues.as_ref().column_vector_as_diagonal();
+            assert!(residual.norm_max() < 1e-8);
+            assert!((x.transpose() * &x - Mat::<f64>::identity(k, k)).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_lobpcg_precond() {
+        let rng = &mut StdRng::seed_from_u64(1);
+        let n = 60;
+        let k = 3;
+        let Q: Mat<c64> = crate::stats::UnitaryMat { dimension: n }.sample(rng);
+        let d = Col::<c64>::from_fn(n, |i| c64::new(1.0 + i as f64 * i as f64, 0.0));
+        let A = &Q * d.as_ref().column_vector_as_diagonal() * Q.adjoint();
+        // a good preconditioner makes the iteration converge much faster
+        let d_inv = Col::<c64>::from_fn(n, |i| d.read(i).faer_inv());
+        let M = &Q * d_inv.as_ref().column_vector_as_diagonal() * Q.adjoint();
+
+        let x0: Mat<c64> = crate::linop::randomized::gaussian_sketch(n, k, rng);
+        let mut iters = [0usize; 2];
+        for (idx, precond) in [None, Some(M.as_ref())].into_iter().enumerate() {
+            let mut x = x0.clone();
+            let mut eigenvalues = Col::<f64>::zeros(k);
+            let params = LobpcgParams::default();
+            let result = match precond {
+                None => lobpcg(
+                    x.as_mut(),
+                    eigenvalues.as_mut(),
+                    linop::IdentityPrecond { dim: n },
+                    A.as_ref(),
+                    params,
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        lobpcg_req::<c64>(
+                            linop::IdentityPrecond { dim: n },
+                            A.as_ref(),
+                            k,
+                            Parallelism::None,
+                        )
+                        .unwrap(),
+                    )),
+                ),
+                Some(M) => lobpcg(
+                    x.as_mut(),
+                    eigenvalues.as_mut(),
+                    M,
+                    A.as_ref(),
+                    params,
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        lobpcg_req::<c64>(M, A.as_ref(), k, Parallelism::None).unwrap(),
+                    )),
+                ),
+            };
+            let info = result.unwrap();
+            iters[idx] = info.iter_count;
+            for j in 0..k {
+                assert!((eigenvalues.read(j) - d.read(j).re).abs() < 1e-8 * d.read(n - 1).re);
+            }
+        }
+        assert!(iters[1] <= iters[0]);
+    }
+}
@@ -1,18 +1,26 @@
 use crate::{
     linalg::{temp_mat_req, temp_mat_uninit},
-    ComplexField, MatMut, MatRef, Parallelism,
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj, MatMut, MatRef, Parallelism,
 };
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
 use reborrow::*;
 
+pub mod arnoldi;
 // TODO: document this later
 #[allow(missing_docs)]
 pub mod bicgstab;
+pub mod block_operator;
+pub mod combinators;
 #[allow(missing_docs)]
 pub mod conjugate_gradient;
+pub mod lanczos;
+pub mod lobpcg;
 #[allow(missing_docs)]
 pub mod lsmr;
+pub mod randomized;
 
+mod krylov_schur;
 mod linop_impl;
 
 /// Specifies whether the initial guess should be assumed to be zero or not.
@@ -154,6 +162,163 @@ impl<E: ComplexField> BiPrecond<E> for IdentityPrecond {
     }
 }
 
+/// Specifies which eigenvalues are computed by the Krylov eigensolvers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EigenvalueTarget {
+    /// Eigenvalues with the largest magnitude.
+    LargestMagnitude,
+    /// Eigenvalues with the smallest magnitude.
+    SmallestMagnitude,
+    /// Eigenvalues with the largest real part.
+    LargestRealPart,
+    /// Eigenvalues with the smallest real part.
+    SmallestRealPart,
+}
+
+/// Shift-invert operator $(A - \sigma I)^{-1}$, applied through a factorization of
+/// $A - \sigma I$.
+///
+/// An eigenvalue $\theta$ of this operator corresponds to the eigenvalue
+/// $\lambda = \sigma + 1 / \theta$ of $A$, with the same eigenvector, so the eigenvalues of
+/// largest magnitude of the shift-invert operator are the eigenvalues of $A$ closest to the
+/// shift. This is typically used with [`EigenvalueTarget::LargestMagnitude`] to compute interior
+/// eigenvalues, with a dense or sparse factorization of $A - \sigma I$, e.g., one returned by
+/// [`SparseColMat::sp_lu`](crate::sparse::SparseColMat::sp_lu).
+pub struct ShiftInvert<'a, E: ComplexField, S: ?Sized> {
+    solver: &'a S,
+    shift: E,
+}
+
+impl<E: ComplexField, S: ?Sized> Copy for ShiftInvert<'_, E, S> {}
+impl<E: ComplexField, S: ?Sized> Clone for ShiftInvert<'_, E, S> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E: ComplexField, S: ?Sized> core::fmt::Debug for ShiftInvert<'_, E, S> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ShiftInvert")
+            .field("shift", &self.shift)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, E: ComplexField, S: ?Sized + SpSolverCore<E>> ShiftInvert<'a, E, S> {
+    /// Creates a shift-invert operator from a factorization of $A - \sigma I$, where $\sigma$ is
+    /// `shift`.
+    ///
+    /// # Panics
+    /// Panics if `solver` is not square.
+    #[inline]
+    #[track_caller]
+    pub fn new(solver: &'a S, shift: E) -> Self {
+        equator::assert!(solver.nrows() == solver.ncols());
+        Self { solver, shift }
+    }
+
+    /// Returns the shift $\sigma$.
+    #[inline]
+    pub fn shift(&self) -> E {
+        self.shift
+    }
+
+    /// Maps an eigenvalue $\theta$ of the shift-invert operator to the corresponding eigenvalue
+    /// $\sigma + 1 / \theta$ of the original matrix.
+    #[inline]
+    pub fn eigenvalue(&self, theta: E) -> E {
+        self.shift.faer_add(theta.faer_inv())
+    }
+}
+
+impl<E: ComplexField, S: ?Sized + SpSolverCore<E> + Sync> LinOp<E> for ShiftInvert<'_, E, S> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.solver.nrows()
+    }
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.solver.ncols()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        _parallelism: Parallelism,
+        _stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        out.copy_from(rhs);
+        self.solver.solve_in_place_with_conj_impl(out, Conj::No);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        _parallelism: Parallelism,
+        _stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        out.copy_from(rhs);
+        self.solver.solve_in_place_with_conj_impl(out, Conj::Yes);
+    }
+}
+
+impl<E: ComplexField, S: ?Sized + SpSolverCore<E> + Sync> BiLinOp<E> for ShiftInvert<'_, E, S> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        _parallelism: Parallelism,
+        _stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        out.copy_from(rhs);
+        self.solver
+            .solve_transpose_in_place_with_conj_impl(out, Conj::No);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        _parallelism: Parallelism,
+        _stack: PodStack<'_>,
+    ) {
+        let mut out = out;
+        out.copy_from(rhs);
+        self.solver
+            .solve_transpose_in_place_with_conj_impl(out, Conj::Yes);
+    }
+}
+
 /// Linear operator from a finite-dimensional vector space.
 pub trait LinOp<E: ComplexField>: Sync + core::fmt::Debug {
     /// Computes the workspace size and alignment required to apply `self` or the conjugate of
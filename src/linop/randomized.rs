@@ -0,0 +1,474 @@
+//! Building blocks for randomized low-rank approximation.
+//!
+//! A randomized low-rank approximation of a matrix $A$ of size `m×n` starts by computing an
+//! orthonormal basis $Q$ of size `m×l` whose range approximates the range of $A$, from the
+//! product of $A$ and a random sketch $\Omega$ of size `n×l`, optionally refined with a few power
+//! iterations. The matrix is then approximated by $A \approx QB$ where $B = Q^H A$, from which
+//! randomized SVD, eigendecomposition, CUR or NMF variants can be computed cheaply, since $B$ only
+//! has `l` rows.
+//!
+//! The functions in this module only access the matrix through the [`BiLinOp`] trait, so they
+//! also apply to sparse matrices and matrix-free operators.
+//!
+//! See Halko, Martinsson and Tropp, "Finding structure with randomness: probabilistic algorithms
+//! for constructing approximate matrix decompositions", 2011.
+
+#[cfg(feature = "rand")]
+use crate::Mat;
+use crate::{
+    assert,
+    linalg::{householder, qr, temp_mat_req, temp_mat_uninit},
+    linop::BiLinOp,
+    ComplexField, Conj, MatMut, MatRef, Parallelism,
+};
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Parameters of the randomized range finder.
+#[derive(Copy, Clone, Debug)]
+#[non_exhaustive]
+pub struct RangeFinderParams {
+    /// Number of power iterations used to refine the range. Each iteration costs two applications
+    /// of the operator, and sharpens the decay of the singular values, which improves the
+    /// accuracy when they decay slowly.
+    pub n_power_iters: usize,
+}
+
+impl Default for RangeFinderParams {
+    #[inline]
+    fn default() -> Self {
+        Self { n_power_iters: 2 }
+    }
+}
+
+/// Returns a Gaussian sketch of size `nrows×ncols`, whose entries are independent samples of the
+/// standard normal distribution.
+#[cfg(feature = "rand")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+pub fn gaussian_sketch<E: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    rng: &mut (impl rand::Rng + ?Sized),
+) -> Mat<E>
+where
+    rand_distr::StandardNormal: rand::distributions::Distribution<E>,
+{
+    rand::distributions::Distribution::sample(
+        &crate::stats::StandardNormalMat { nrows, ncols },
+        rng,
+    )
+}
+
+/// Computes the size and alignment of required workspace for orthonormalizing a matrix of size
+/// `nrows×ncols` with [`orthonormalize_in_place`].
+pub fn orthonormalize_in_place_req<E: ComplexField>(
+    nrows: usize,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let bs = qr::no_pivoting::compute::recommended_blocksize::<E>(nrows, ncols);
+    StackReq::try_all_of([
+        temp_mat_req::<E>(nrows, ncols)?,
+        temp_mat_req::<E>(bs, ncols)?,
+        StackReq::try_any_of([
+            qr::no_pivoting::compute::qr_in_place_req::<E>(
+                nrows,
+                ncols,
+                bs,
+                parallelism,
+                Default::default(),
+            )?,
+            householder::apply_block_householder_sequence_on_the_left_in_place_req::<E>(
+                nrows, bs, ncols,
+            )?,
+        ])?,
+    ])
+}
+
+/// Replaces the columns of `mat` with an orthonormal basis of their span, computed from its thin
+/// QR decomposition.
+///
+/// If the columns are linearly dependent, the basis is completed with orthonormal vectors.
+///
+/// # Panics
+/// Panics if `mat` has more columns than rows, or if the provided memory in `stack` is
+/// insufficient (see [`orthonormalize_in_place_req`]).
+#[track_caller]
+pub fn orthonormalize_in_place<E: ComplexField>(
+    mat: MatMut<'_, E>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    let mut mat = mat;
+    let m = mat.nrows();
+    let n = mat.ncols();
+    assert!(n <= m);
+    if n == 0 {
+        return;
+    }
+
+    let bs = qr::no_pivoting::compute::recommended_blocksize::<E>(m, n);
+    let (mut qr, stack) = temp_mat_uninit::<E>(m, n, stack);
+    let (mut house, mut stack) = temp_mat_uninit::<E>(bs, n, stack);
+    qr.copy_from(mat.rb());
+
+    qr::no_pivoting::compute::qr_in_place(
+        qr.rb_mut(),
+        house.rb_mut(),
+        parallelism,
+        stack.rb_mut(),
+        Default::default(),
+    );
+
+    mat.fill_zero();
+    mat.rb_mut()
+        .diagonal_mut()
+        .column_vector_mut()
+        .fill(E::faer_one());
+    householder::apply_block_householder_sequence_on_the_left_in_place_with_conj(
+        qr.rb(),
+        house.rb(),
+        Conj::No,
+        mat,
+        parallelism,
+        stack,
+    );
+}
+
+/// Computes the size and alignment of required workspace for refining a basis with `ncols`
+/// columns with [`power_iterations_in_place`].
+pub fn power_iterations_in_place_req<E: ComplexField>(
+    mat: impl BiLinOp<E>,
+    ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    fn implementation<E: ComplexField>(
+        A: &dyn BiLinOp<E>,
+        ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        let m = A.nrows();
+        let n = A.ncols();
+        StackReq::try_all_of([
+            temp_mat_req::<E>(n, ncols)?,
+            StackReq::try_any_of([
+                A.apply_req(ncols, parallelism)?,
+                A.transpose_apply_req(ncols, parallelism)?,
+                orthonormalize_in_place_req::<E>(m, ncols, parallelism)?,
+                orthonormalize_in_place_req::<E>(n, ncols, parallelism)?,
+            ])?,
+        ])
+    }
+
+    implementation(&mat, ncols, parallelism)
+}
+
+/// Refines the orthonormal basis `q` of an approximation of the range of `mat` by applying
+/// `n_iters` steps of orthonormalized subspace power iteration, i.e., replacing `q` with an
+/// orthonormal basis of $(AA^H)q$ at each step.
+///
+/// # Panics
+/// Panics if `q` doesn't have `mat.nrows()` rows, if it has more columns than
+/// `min(mat.nrows(), mat.ncols())`, or if the provided memory in `stack` is insufficient (see
+/// [`power_iterations_in_place_req`]).
+#[track_caller]
+pub fn power_iterations_in_place<E: ComplexField>(
+    q: MatMut<'_, E>,
+    mat: impl BiLinOp<E>,
+    n_iters: usize,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut q: MatMut<'_, E>,
+        A: &dyn BiLinOp<E>,
+        n_iters: usize,
+        par: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        let m = A.nrows();
+        let n = A.ncols();
+        let l = q.ncols();
+        assert!(all(q.nrows() == m, l <= Ord::min(m, n)));
+
+        let (mut w, mut stack) = temp_mat_uninit::<E>(n, l, stack);
+        for _ in 0..n_iters {
+            // orthonormalizing between each application avoids losing the directions associated
+            // with the small singular values to rounding errors
+            A.adjoint_apply(w.rb_mut(), q.rb(), par, stack.rb_mut());
+            orthonormalize_in_place(w.rb_mut(), par, stack.rb_mut());
+            A.apply(q.rb_mut(), w.rb(), par, stack.rb_mut());
+            orthonormalize_in_place(q.rb_mut(), par, stack.rb_mut());
+        }
+    }
+
+    implementation(q, &mat, n_iters, parallelism, stack)
+}
+
+/// Computes the size and alignment of required workspace for computing a basis from a sketch
+/// with `sketch_ncols` columns with [`range_finder`].
+pub fn range_finder_req<E: ComplexField>(
+    mat: impl BiLinOp<E>,
+    sketch_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        mat.apply_req(sketch_ncols, parallelism)?,
+        orthonormalize_in_place_req::<E>(mat.nrows(), sketch_ncols, parallelism)?,
+        power_iterations_in_place_req::<E>(&mat, sketch_ncols, parallelism)?,
+    ])
+}
+
+/// Computes an orthonormal basis `q` approximating the range of `mat` from the random `sketch`,
+/// i.e., an orthonormal basis of $(AA^H)^p A\Omega$, where $p$ is the number of power iterations.
+///
+/// The sketch is usually a Gaussian matrix (see [`gaussian_sketch`]) with `k + p` columns, where
+/// `k` is the target rank and `p` is a small oversampling parameter, e.g. `5` or `10`.
+///
+/// # Panics
+/// Panics if `sketch` doesn't have `mat.ncols()` rows, if `q` doesn't have `mat.nrows()` rows and
+/// `sketch.ncols()` columns, if the sketch has more columns than
+/// `min(mat.nrows(), mat.ncols())`, or if the provided memory in `stack` is insufficient (see
+/// [`range_finder_req`]).
+#[track_caller]
+pub fn range_finder<E: ComplexField>(
+    q: MatMut<'_, E>,
+    mat: impl BiLinOp<E>,
+    sketch: MatRef<'_, E>,
+    params: RangeFinderParams,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut q: MatMut<'_, E>,
+        A: &dyn BiLinOp<E>,
+        sketch: MatRef<'_, E>,
+        params: RangeFinderParams,
+        par: Parallelism,
+        mut stack: PodStack<'_>,
+    ) {
+        let m = A.nrows();
+        let n = A.ncols();
+        let l = sketch.ncols();
+        assert!(all(
+            sketch.nrows() == n,
+            q.nrows() == m,
+            q.ncols() == l,
+            l <= Ord::min(m, n),
+        ));
+
+        A.apply(q.rb_mut(), sketch, par, stack.rb_mut());
+        orthonormalize_in_place(q.rb_mut(), par, stack.rb_mut());
+        power_iterations_in_place(q, A, params.n_power_iters, par, stack);
+    }
+
+    implementation(q, &mat, sketch, params, parallelism, stack)
+}
+
+/// Computes the size and alignment of required workspace for computing a QB decomposition from
+/// a sketch with `sketch_ncols` columns with [`qb`].
+pub fn qb_req<E: ComplexField>(
+    mat: impl BiLinOp<E>,
+    sketch_ncols: usize,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_any_of([
+        range_finder_req::<E>(&mat, sketch_ncols, parallelism)?,
+        StackReq::try_all_of([
+            temp_mat_req::<E>(mat.ncols(), sketch_ncols)?,
+            mat.transpose_apply_req(sketch_ncols, parallelism)?,
+        ])?,
+    ])
+}
+
+/// Computes the QB decomposition of `mat` from the random `sketch`, such that $A \approx QB$,
+/// where `q` is an orthonormal basis approximating the range of $A$ computed by
+/// [`range_finder`], and $B = Q^H A$.
+///
+/// # Panics
+/// Panics if `sketch` doesn't have `mat.ncols()` rows, if `q` doesn't have `mat.nrows()` rows and
+/// `sketch.ncols()` columns, if `b` doesn't have `sketch.ncols()` rows and `mat.ncols()` columns,
+/// if the sketch has more columns than `min(mat.nrows(), mat.ncols())`, or if the provided memory
+/// in `stack` is insufficient (see [`qb_req`]).
+#[track_caller]
+pub fn qb<E: ComplexField>(
+    q: MatMut<'_, E>,
+    b: MatMut<'_, E>,
+    mat: impl BiLinOp<E>,
+    sketch: MatRef<'_, E>,
+    params: RangeFinderParams,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) {
+    #[track_caller]
+    fn implementation<E: ComplexField>(
+        mut q: MatMut<'_, E>,
+        mut b: MatMut<'_, E>,
+        A: &dyn BiLinOp<E>,
+        sketch: MatRef<'_, E>,
+        params: RangeFinderParams,
+        par: Parallelism,
+        mut stack: PodStack<'_>,
+    ) {
+        let n = A.ncols();
+        let l = sketch.ncols();
+        assert!(all(b.nrows() == l, b.ncols() == n));
+
+        range_finder(q.rb_mut(), A, sketch, params, par, stack.rb_mut());
+
+        // B = Qᴴ A = (Aᴴ Q)ᴴ
+        let (mut bh, mut stack) = temp_mat_uninit::<E>(n, l, stack);
+        A.adjoint_apply(bh.rb_mut(), q.rb(), par, stack.rb_mut());
+        b.copy_from(bh.rb().adjoint());
+    }
+
+    implementation(q, b, &mat, sketch, params, parallelism, stack)
+}
+
+/// Randomized QB decomposition, such that $A \approx QB$, where $Q$ has orthonormal columns.
+#[cfg(feature = "rand")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+#[derive(Clone, Debug)]
+pub struct Qb<E: ComplexField> {
+    q: Mat<E>,
+    b: Mat<E>,
+}
+
+#[cfg(feature = "rand")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+impl<E: ComplexField> Qb<E> {
+    /// Computes the QB decomposition of `mat` using a Gaussian sketch with `sketch_ncols`
+    /// columns sampled from `rng`.
+    ///
+    /// # Panics
+    /// Panics if `sketch_ncols` is larger than `min(mat.nrows(), mat.ncols())`.
+    #[track_caller]
+    pub fn new(
+        mat: impl BiLinOp<E>,
+        sketch_ncols: usize,
+        params: RangeFinderParams,
+        rng: &mut (impl rand::Rng + ?Sized),
+    ) -> Self
+    where
+        rand_distr::StandardNormal: rand::distributions::Distribution<E>,
+    {
+        let parallelism = crate::get_global_parallelism();
+        let m = mat.nrows();
+        let n = mat.ncols();
+        let sketch = gaussian_sketch::<E>(n, sketch_ncols, rng);
+
+        let mut q = Mat::<E>::zeros(m, sketch_ncols);
+        let mut b = Mat::<E>::zeros(sketch_ncols, n);
+        qb(
+            q.as_mut(),
+            b.as_mut(),
+            &mat,
+            sketch.as_ref(),
+            params,
+            parallelism,
+            PodStack::new(&mut dyn_stack::GlobalPodBuffer::new(
+                qb_req::<E>(&mat, sketch_ncols, parallelism).unwrap(),
+            )),
+        );
+        Self { q, b }
+    }
+
+    /// Returns the orthonormal factor $Q$.
+    pub fn q(&self) -> MatRef<'_, E> {
+        self.q.as_ref()
+    }
+
+    /// Returns the factor $B = Q^H A$.
+    pub fn b(&self) -> MatRef<'_, E> {
+        self.b.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Mat};
+    use dyn_stack::GlobalPodBuffer;
+    use rand::prelude::*;
+
+    #[test]
+    fn test_orthonormalize() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut q = gaussian_sketch::<c64>(20, 6, &mut rng);
+        let a = q.clone();
+        orthonormalize_in_place(
+            q.as_mut(),
+            Parallelism::None,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                orthonormalize_in_place_req::<c64>(20, 6, Parallelism::None).unwrap(),
+            )),
+        );
+        assert!((q.adjoint() * &q - Mat::<c64>::identity(6, 6)).norm_max() < 1e-12);
+        // the span is preserved
+        assert!((&q * (q.adjoint() * &a) - &a).norm_max() < 1e-12);
+    }
+
+    #[test]
+    fn test_qb_low_rank() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let (m, n, k) = (40, 30, 5);
+        let u = gaussian_sketch::<f64>(m, k, &mut rng);
+        let v = gaussian_sketch::<f64>(n, k, &mut rng);
+        let a = &u * v.transpose();
+
+        let qb = Qb::new(a.as_ref(), k + 3, RangeFinderParams::default(), &mut rng);
+        assert!(qb.q().ncols() == k + 3);
+        assert!(
+            (qb.q().transpose() * qb.q() - Mat::<f64>::identity(k + 3, k + 3)).norm_max() < 1e-12
+        );
+        assert!((qb.q() * qb.b() - &a).norm_max() < 1e-10 * a.norm_max());
+    }
+
+    #[test]
+    fn test_power_iterations() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let n = 50;
+        // slowly decaying singular values
+        let u = crate::stats::UnitaryMat { dimension: n }.sample(&mut rng);
+        let s = Mat::<f64>::from_fn(
+            n,
+            n,
+            |i, j| if i == j { 1.0 / (1.0 + i as f64) } else { 0.0 },
+        );
+        let a: Mat<f64> = &u * &s * u.transpose();
+
+        let k = 10;
+        let sketch = gaussian_sketch::<f64>(n, k, &mut rng);
+        let err = |n_power_iters: usize| {
+            let mut q = Mat::<f64>::zeros(n, k);
+            let mut b = Mat::<f64>::zeros(k, n);
+            let mut params = RangeFinderParams::default();
+            params.n_power_iters = n_power_iters;
+            qb(
+                q.as_mut(),
+                b.as_mut(),
+                a.as_ref(),
+                sketch.as_ref(),
+                params,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    qb_req::<f64>(a.as_ref(), k, Parallelism::None).unwrap(),
+                )),
+            );
+            (&q * &b - &a).norm_l2()
+        };
+
+        // the optimal rank k error in the frobenius norm is given by the trailing singular values
+        let optimal = (k..n)
+            .map(|i| 1.0 / ((1.0 + i as f64) * (1.0 + i as f64)))
+            .sum::<f64>()
+            .sqrt();
+        let err0 = err(0);
+        let err3 = err(3);
+        assert!(err3 <= err0);
+        assert!(err3 >= optimal * (1.0 - 1e-10));
+        assert!(err3 < 1.5 * optimal);
+    }
+}
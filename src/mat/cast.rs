@@ -0,0 +1,169 @@
+use crate::{
+    assert,
+    complex_native::{c32, c64},
+    mat::{Mat, MatMut, MatRef},
+    unzipped, zipped, ComplexField,
+};
+use faer_entity::*;
+
+/// Specifies how the imaginary part of a complex value is handled when it is cast to a real type.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImagPolicy {
+    /// The imaginary part is discarded, and only the real part is kept.
+    Discard,
+    /// The value is replaced by its modulus.
+    Modulus,
+}
+
+impl Default for ImagPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::Discard
+    }
+}
+
+/// Scalar type that can be cast to `T`, used by [`MatRef::cast`] and similar methods.
+///
+/// Values are rounded to the nearest representable value when casting to a lower precision type,
+/// and real values are promoted to complex values with a zero imaginary part.
+pub trait Cast<T: Entity>: Entity {
+    /// Casts the value to `T`. `policy` is only used when casting a complex value to a real
+    /// type.
+    fn cast_with(self, policy: ImagPolicy) -> T;
+}
+
+macro_rules! impl_cast_real {
+    ($from: ty, $to: ty) => {
+        impl Cast<$to> for $from {
+            #[inline(always)]
+            #[allow(clippy::unnecessary_cast)]
+            fn cast_with(self, _: ImagPolicy) -> $to {
+                self as $to
+            }
+        }
+    };
+}
+
+macro_rules! impl_cast_real_to_complex {
+    ($from: ty, $to: ident, $to_real: ty) => {
+        impl Cast<$to> for $from {
+            #[inline(always)]
+            #[allow(clippy::unnecessary_cast)]
+            fn cast_with(self, _: ImagPolicy) -> $to {
+                $to {
+                    re: self as $to_real,
+                    im: 0.0,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_cast_complex {
+    ($from: ty, $to: ident, $to_real: ty) => {
+        impl Cast<$to> for $from {
+            #[inline(always)]
+            #[allow(clippy::unnecessary_cast)]
+            fn cast_with(self, _: ImagPolicy) -> $to {
+                $to {
+                    re: self.re as $to_real,
+                    im: self.im as $to_real,
+                }
+            }
+        }
+    };
+}
+
+macro_rules! impl_cast_complex_to_real {
+    ($from: ty, $to: ty) => {
+        impl Cast<$to> for $from {
+            #[inline(always)]
+            #[allow(clippy::unnecessary_cast)]
+            fn cast_with(self, policy: ImagPolicy) -> $to {
+                match policy {
+                    ImagPolicy::Discard => self.re as $to,
+                    ImagPolicy::Modulus => self.faer_abs() as $to,
+                }
+            }
+        }
+    };
+}
+
+impl_cast_real!(f32, f32);
+impl_cast_real!(f32, f64);
+impl_cast_real!(f64, f32);
+impl_cast_real!(f64, f64);
+
+impl_cast_real_to_complex!(f32, c32, f32);
+impl_cast_real_to_complex!(f32, c64, f64);
+impl_cast_real_to_complex!(f64, c32, f32);
+impl_cast_real_to_complex!(f64, c64, f64);
+
+impl_cast_complex!(c32, c32, f32);
+impl_cast_complex!(c32, c64, f64);
+impl_cast_complex!(c64, c32, f32);
+impl_cast_complex!(c64, c64, f64);
+
+impl_cast_complex_to_real!(c32, f32);
+impl_cast_complex_to_real!(c32, f64);
+impl_cast_complex_to_real!(c64, f32);
+impl_cast_complex_to_real!(c64, f64);
+
+// the zipped loops are specialized for contiguous columns, which lets the conversions vectorize
+#[inline]
+pub(crate) fn cast<E: Cast<T>, T: Entity>(src: MatRef<'_, E>, policy: ImagPolicy) -> Mat<T> {
+    zipped!(src).map(
+        #[inline(always)]
+        |unzipped!(src)| src.read().cast_with(policy),
+    )
+}
+
+#[inline]
+#[track_caller]
+pub(crate) fn cast_into<E: Cast<T>, T: Entity>(
+    dst: MatMut<'_, T>,
+    src: MatRef<'_, E>,
+    policy: ImagPolicy,
+) {
+    assert!(all(dst.nrows() == src.nrows(), dst.ncols() == src.ncols()));
+    zipped!(dst, src).for_each(
+        #[inline(always)]
+        |unzipped!(mut dst, src)| dst.write(src.read().cast_with(policy)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, Col, Row};
+
+    #[test]
+    fn test_cast() {
+        let a = Mat::from_fn(5, 3, |i, j| (i as f64 - 2.0) / (j + 3) as f64);
+
+        let low = a.cast::<f32>();
+        assert!(low == Mat::from_fn(5, 3, |i, j| a.read(i, j) as f32));
+        assert!(low.cast::<f64>() == Mat::from_fn(5, 3, |i, j| a.read(i, j) as f32 as f64));
+
+        let z = a.cast::<c64>();
+        assert!(z == Mat::from_fn(5, 3, |i, j| c64::new(a.read(i, j), 0.0)));
+        assert!(a.transpose().cast::<c32>() == z.transpose().cast::<c32>());
+
+        let w = Mat::from_fn(2, 2, |_, j| c64::new(3.0, j as f64 * 4.0));
+        assert!(w.cast::<f64>() == Mat::from_fn(2, 2, |_, _| 3.0));
+        assert!(
+            w.cast_with::<f32>(ImagPolicy::Modulus)
+                == Mat::from_fn(2, 2, |_, j| if j == 0 { 3.0f32 } else { 5.0 })
+        );
+
+        // strided destination
+        let mut out = Mat::<f32>::zeros(3, 5);
+        a.as_ref().cast_into(out.as_mut().transpose_mut());
+        assert!(out == low.transpose());
+
+        let c = Col::from_fn(4, |i| i as f32 * 0.5);
+        assert!(c.cast::<f64>() == Col::from_fn(4, |i| i as f64 * 0.5));
+        let r = Row::from_fn(4, |j| c32::new(j as f32, 1.0));
+        assert!(r.cast::<f32>() == Row::from_fn(4, |j| j as f32));
+    }
+}
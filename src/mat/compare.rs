@@ -0,0 +1,471 @@
+use crate::{
+    assert,
+    mat::{AsMatRef, Mask, Mat, MatRef},
+    unzipped,
+    utils::{simd::SimdFor, slice::SliceGroup},
+    zipped, ComplexField, RealField,
+};
+use core::iter::zip;
+use faer_entity::*;
+
+// right hand side of a comparison
+#[derive(Copy, Clone)]
+enum Operand<'a, E: Entity> {
+    Mat(MatRef<'a, E>),
+    Scalar(E),
+}
+
+// compares each element of `lhs` to the corresponding element of `rhs` with the zip layer
+#[track_caller]
+fn compare_zip<E: Entity>(
+    lhs: MatRef<'_, E>,
+    rhs: Operand<'_, E>,
+    f: impl Fn(E, E) -> bool,
+) -> Mask {
+    let m = lhs.nrows();
+    let mut mask = Mask::full(m, lhs.ncols(), false);
+    let out = mask.as_slice_mut();
+    match rhs {
+        Operand::Mat(rhs) => {
+            assert!(all(lhs.nrows() == rhs.nrows(), lhs.ncols() == rhs.ncols()));
+            zipped!(lhs, rhs).for_each_with_index(
+                #[inline(always)]
+                |i, j, unzipped!(a, b)| out[i + j * m] = f(a.read(), b.read()),
+            );
+        }
+        Operand::Scalar(value) => {
+            zipped!(lhs).for_each_with_index(
+                #[inline(always)]
+                |i, j, unzipped!(a)| out[i + j * m] = f(a.read(), value),
+            );
+        }
+    }
+    mask
+}
+
+// comparison of real values, with the same semantics as the operators of `PartialOrd`
+#[derive(Copy, Clone)]
+enum CmpOp {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl CmpOp {
+    #[inline(always)]
+    fn scalar<E: RealField>(self, a: E, b: E) -> bool {
+        match self {
+            CmpOp::Lt => a < b,
+            CmpOp::Le => a <= b,
+            CmpOp::Gt => a > b,
+            CmpOp::Ge => a >= b,
+        }
+    }
+
+    #[inline(always)]
+    fn simd<E: RealField, S: pulp::Simd>(
+        self,
+        simd: SimdFor<E, S>,
+        a: SimdGroupFor<E, S>,
+        b: SimdGroupFor<E, S>,
+    ) -> SimdMaskFor<E, S> {
+        match self {
+            CmpOp::Lt => simd.less_than(a, b),
+            CmpOp::Le => simd.less_than_or_equal(a, b),
+            CmpOp::Gt => simd.greater_than(a, b),
+            CmpOp::Ge => simd.greater_than_or_equal(a, b),
+        }
+    }
+}
+
+// writes the lanes of `mask` to `out`
+#[inline(always)]
+fn store_mask<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    mask: SimdMaskFor<E, S>,
+    out: &mut [bool],
+) {
+    let one = E::faer_one();
+    let selected =
+        from_copy::<E, _>(simd.select(mask, simd.splat(one), simd.splat(E::faer_zero())));
+    let lanes = SliceGroup::<'_, E>::new(E::faer_map(
+        E::faer_as_ref(&selected),
+        #[inline(always)]
+        |x| bytemuck::cast_slice::<SimdUnitFor<E, S>, UnitFor<E>>(core::slice::from_ref(x)),
+    ));
+    for (i, out) in out.iter_mut().enumerate() {
+        *out = lanes.read(i) == one;
+    }
+}
+
+struct CompareImpl<'a, E: RealField> {
+    out: &'a mut [bool],
+    lhs: MatRef<'a, E>,
+    rhs: Operand<'a, E>,
+    op: CmpOp,
+}
+
+impl<E: RealField> pulp::WithSimd for CompareImpl<'_, E> {
+    type Output = ();
+
+    #[inline(always)]
+    fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+        let Self { out, lhs, rhs, op } = self;
+        let simd = SimdFor::<E, S>::new(simd);
+        let m = lhs.nrows();
+
+        for j in 0..lhs.ncols() {
+            let out = &mut out[j * m..][..m];
+            let (lhs_body, lhs_tail) =
+                simd.as_simd(SliceGroup::<'_, E>::new(lhs.col(j).try_as_slice().unwrap()));
+            let (out_body, out_tail) = out.split_at_mut(m - lhs_tail.len());
+            let lanes = if lhs_body.is_empty() {
+                1
+            } else {
+                out_body.len() / lhs_body.len()
+            };
+
+            match rhs {
+                Operand::Mat(rhs) => {
+                    let (rhs_body, rhs_tail) =
+                        simd.as_simd(SliceGroup::<'_, E>::new(rhs.col(j).try_as_slice().unwrap()));
+                    for ((a, b), out) in zip(
+                        zip(lhs_body.into_ref_iter(), rhs_body.into_ref_iter()),
+                        out_body.chunks_exact_mut(lanes),
+                    ) {
+                        store_mask(simd, op.simd(simd, a.get(), b.get()), out);
+                    }
+                    for ((a, b), out) in zip(
+                        zip(lhs_tail.into_ref_iter(), rhs_tail.into_ref_iter()),
+                        out_tail,
+                    ) {
+                        *out = op.scalar(a.read(), b.read());
+                    }
+                }
+                Operand::Scalar(value) => {
+                    let b = simd.splat(value);
+                    for (a, out) in zip(lhs_body.into_ref_iter(), out_body.chunks_exact_mut(lanes))
+                    {
+                        store_mask(simd, op.simd(simd, a.get(), b), out);
+                    }
+                    for (a, out) in zip(lhs_tail.into_ref_iter(), out_tail) {
+                        *out = op.scalar(a.read(), value);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// compares real values with SIMD instructions when the columns are contiguous, and with the zip
+// layer otherwise
+#[track_caller]
+fn compare_ord<E: RealField>(lhs: MatRef<'_, E>, rhs: Operand<'_, E>, op: CmpOp) -> Mask {
+    let contiguous = lhs.row_stride() == 1
+        && match rhs {
+            Operand::Mat(rhs) => {
+                assert!(all(lhs.nrows() == rhs.nrows(), lhs.ncols() == rhs.ncols()));
+                rhs.row_stride() == 1
+            }
+            Operand::Scalar(_) => true,
+        };
+    if !contiguous {
+        return compare_zip(lhs, rhs, |a, b| op.scalar(a, b));
+    }
+
+    let mut mask = Mask::full(lhs.nrows(), lhs.ncols(), false);
+    E::Simd::default().dispatch(CompareImpl {
+        out: mask.as_slice_mut(),
+        lhs,
+        rhs,
+        op,
+    });
+    mask
+}
+
+// returns the smaller of the two values, or nan if either of them is nan
+#[inline(always)]
+fn min_nan<E: RealField>(a: E, b: E) -> E {
+    if a.faer_is_nan() {
+        a
+    } else if b.faer_is_nan() || b < a {
+        b
+    } else {
+        a
+    }
+}
+
+// returns the larger of the two values, or nan if either of them is nan
+#[inline(always)]
+fn max_nan<E: RealField>(a: E, b: E) -> E {
+    if a.faer_is_nan() {
+        a
+    } else if b.faer_is_nan() || b > a {
+        b
+    } else {
+        a
+    }
+}
+
+macro_rules! impl_compare {
+    (
+        $bound: ident,
+        $(($name: ident, $name_scalar: ident, $compare: ident, $op: expr, $what: literal)),* $(,)?
+    ) => {
+        impl<E: $bound> MatRef<'_, E> {$(
+            #[doc = concat!(
+                "Returns a mask whose entries are `true` where the element of `self` is ",
+                $what,
+                " the corresponding element of `other`.\n\n",
+                "# Panics\n",
+                "Panics if `other` doesn't have the same dimensions as `self`.",
+            )]
+            #[track_caller]
+            pub fn $name(&self, other: impl AsMatRef<E>) -> Mask {
+                $compare(*self, Operand::Mat(other.as_mat_ref()), $op)
+            }
+
+            #[doc = concat!(
+                "Returns a mask whose entries are `true` where the element of `self` is ",
+                $what,
+                " `value`.",
+            )]
+            pub fn $name_scalar(&self, value: E) -> Mask {
+                $compare(*self, Operand::Scalar(value), $op)
+            }
+        )*}
+
+        impl<E: $bound> Mat<E> {$(
+            #[doc = concat!(
+                "Returns a mask whose entries are `true` where the element of `self` is ",
+                $what,
+                " the corresponding element of `other`.\n\n",
+                "# Panics\n",
+                "Panics if `other` doesn't have the same dimensions as `self`.",
+            )]
+            #[track_caller]
+            pub fn $name(&self, other: impl AsMatRef<E>) -> Mask {
+                self.as_ref().$name(other)
+            }
+
+            #[doc = concat!(
+                "Returns a mask whose entries are `true` where the element of `self` is ",
+                $what,
+                " `value`.",
+            )]
+            pub fn $name_scalar(&self, value: E) -> Mask {
+                self.as_ref().$name_scalar(value)
+            }
+        )*}
+    };
+}
+
+impl_compare!(
+    ComplexField,
+    (
+        eq_elementwise,
+        eq_scalar,
+        compare_zip,
+        |a: E, b: E| a == b,
+        "equal to"
+    ),
+    (
+        ne_elementwise,
+        ne_scalar,
+        compare_zip,
+        |a: E, b: E| a != b,
+        "not equal to"
+    ),
+);
+
+impl_compare!(
+    RealField,
+    (lt, lt_scalar, compare_ord, CmpOp::Lt, "less than"),
+    (
+        le,
+        le_scalar,
+        compare_ord,
+        CmpOp::Le,
+        "less than or equal to"
+    ),
+    (gt, gt_scalar, compare_ord, CmpOp::Gt, "greater than"),
+    (
+        ge,
+        ge_scalar,
+        compare_ord,
+        CmpOp::Ge,
+        "greater than or equal to"
+    ),
+);
+
+impl<E: RealField> MatRef<'_, E> {
+    /// Returns a new matrix whose elements are those of `self`, restricted to the interval
+    /// `[min, max]`. NaN elements are kept as is.
+    ///
+    /// # Panics
+    /// Panics if `min > max`, or if either bound is NaN.
+    #[track_caller]
+    pub fn clamp(&self, min: E, max: E) -> Mat<E> {
+        assert!(min <= max);
+        zipped!(*self).map(
+            #[inline(always)]
+            |unzipped!(x)| {
+                let x = x.read();
+                if x < min {
+                    min
+                } else if x > max {
+                    max
+                } else {
+                    x
+                }
+            },
+        )
+    }
+
+    /// Returns a new matrix whose elements are the minimum of the corresponding elements of
+    /// `self` and `other`. The result is NaN where either element is NaN.
+    ///
+    /// # Panics
+    /// Panics if `other` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn min_with(&self, other: impl AsMatRef<E>) -> Mat<E> {
+        zipped!(*self, other.as_mat_ref()).map(
+            #[inline(always)]
+            |unzipped!(a, b)| min_nan(a.read(), b.read()),
+        )
+    }
+
+    /// Returns a new matrix whose elements are the maximum of the corresponding elements of
+    /// `self` and `other`. The result is NaN where either element is NaN.
+    ///
+    /// # Panics
+    /// Panics if `other` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn max_with(&self, other: impl AsMatRef<E>) -> Mat<E> {
+        zipped!(*self, other.as_mat_ref()).map(
+            #[inline(always)]
+            |unzipped!(a, b)| max_nan(a.read(), b.read()),
+        )
+    }
+}
+
+impl<E: RealField> Mat<E> {
+    /// Returns a new matrix whose elements are those of `self`, restricted to the interval
+    /// `[min, max]`. NaN elements are kept as is.
+    ///
+    /// # Panics
+    /// Panics if `min > max`, or if either bound is NaN.
+    #[track_caller]
+    pub fn clamp(&self, min: E, max: E) -> Mat<E> {
+        self.as_ref().clamp(min, max)
+    }
+
+    /// Returns a new matrix whose elements are the minimum of the corresponding elements of
+    /// `self` and `other`. The result is NaN where either element is NaN.
+    ///
+    /// # Panics
+    /// Panics if `other` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn min_with(&self, other: impl AsMatRef<E>) -> Mat<E> {
+        self.as_ref().min_with(other)
+    }
+
+    /// Returns a new matrix whose elements are the maximum of the corresponding elements of
+    /// `self` and `other`. The result is NaN where either element is NaN.
+    ///
+    /// # Panics
+    /// Panics if `other` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn max_with(&self, other: impl AsMatRef<E>) -> Mat<E> {
+        self.as_ref().max_with(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{assert, complex_native::c64, mat, mat::Mask, Mat};
+
+    #[test]
+    fn test_compare() {
+        let nan = f64::NAN;
+        let a = mat![[1.0, 2.0, nan], [4.0, -1.0, 0.5]];
+        let b = mat![[1.0, 3.0, 0.0], [2.0, -1.0, nan]];
+
+        let mask = |bits: [[bool; 3]; 2]| Mask::from_fn(2, 3, |i, j| bits[i][j]);
+        assert!(a.eq_elementwise(&b) == mask([[true, false, false], [false, true, false]]));
+        assert!(a.ne_elementwise(&b) == a.eq_elementwise(&b).not());
+        assert!(a.lt(&b) == mask([[false, true, false], [false, false, false]]));
+        assert!(a.le(b.as_ref()) == mask([[true, true, false], [false, true, false]]));
+        assert!(a.gt(&b) == mask([[false, false, false], [true, false, false]]));
+        assert!(a.ge(&b) == mask([[true, false, false], [true, true, false]]));
+        assert!(a.gt_scalar(1.0) == mask([[false, true, false], [true, false, false]]));
+        assert!(
+            a.as_ref().transpose().le_scalar(1.0)
+                == mask([[true, false, false], [false, true, true]]).transpose()
+        );
+
+        let z = Mat::from_fn(2, 2, |i, j| c64::new(i as f64, j as f64));
+        assert!(z.eq_scalar(c64::new(1.0, 0.0)).count() == 1);
+
+        let c = a.clamp(0.0, 2.0);
+        assert!(c.is_nan() == a.is_nan());
+        assert!(c.get(.., ..2) == mat![[1.0, 2.0], [2.0, 0.0]]);
+        assert!(c.read(1, 2) == 0.5);
+
+        let min = a.min_with(&b);
+        let max = a.max_with(&b);
+        assert!(all(
+            min.is_nan() == a.is_nan().or(&b.is_nan()),
+            max.is_nan() == min.is_nan()
+        ));
+        assert!(min.get(.., ..2) == mat![[1.0, 2.0], [2.0, -1.0]]);
+        assert!(max.get(.., ..2) == mat![[1.0, 3.0], [4.0, -1.0]]);
+    }
+
+    #[test]
+    fn test_compare_layouts() {
+        let nan = f64::NAN;
+        let a = Mat::from_fn(37, 4, |i, j| {
+            if (i + j) % 7 == 0 {
+                nan
+            } else {
+                (i % 5) as f64 - j as f64
+            }
+        });
+        let b = Mat::from_fn(37, 4, |i, j| {
+            if (i * j) % 11 == 3 {
+                nan
+            } else {
+                (i % 3) as f64
+            }
+        });
+        let expected = |f: &dyn Fn(f64, f64) -> bool| {
+            Mask::from_fn(37, 4, |i, j| f(a.read(i, j), b.read(i, j)))
+        };
+
+        // contiguous columns, transposed and reversed views, and scalar operands
+        for (mask, target) in [
+            (a.lt(&b), expected(&|x, y| x < y)),
+            (a.le(&b), expected(&|x, y| x <= y)),
+            (a.gt(&b), expected(&|x, y| x > y)),
+            (a.ge(&b), expected(&|x, y| x >= y)),
+            (a.eq_elementwise(&b), expected(&|x, y| x == y)),
+            (a.ne_elementwise(&b), expected(&|x, y| x != y)),
+            (
+                a.as_ref().transpose().lt(b.transpose()).transpose(),
+                expected(&|x, y| x < y),
+            ),
+            (
+                a.as_ref().reverse_rows().ge(b.as_ref().reverse_rows()),
+                Mask::from_fn(37, 4, |i, j| a.read(36 - i, j) >= b.read(36 - i, j)),
+            ),
+            (
+                a.gt_scalar(0.5),
+                Mask::from_fn(37, 4, |i, j| a.read(i, j) > 0.5),
+            ),
+        ] {
+            assert!(mask == target);
+        }
+    }
+}
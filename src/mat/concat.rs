@@ -0,0 +1,297 @@
+use crate::{
+    assert,
+    mat::{Mat, MatRef},
+    ComplexField, Conj,
+};
+use reborrow::*;
+
+// builds the block matrix whose `i`-th row of blocks is `block_row(i)`, following the convention
+// of `numpy.block`: the blocks in a row must have the same number of rows, and all the rows must
+// have the same total number of columns, but the column boundaries don't need to line up
+#[track_caller]
+pub(crate) fn block_impl<'a, 'b, E: ComplexField, B: 'b + Copy>(
+    n_block_rows: usize,
+    block_row: impl Fn(usize) -> &'b [B],
+    get: impl Fn(B) -> (MatRef<'a, E>, Conj),
+) -> Mat<E> {
+    let mut nrows = 0usize;
+    let mut ncols = None;
+    for i in 0..n_block_rows {
+        let mut row_nrows = None;
+        let mut row_ncols = 0usize;
+        for &block in block_row(i) {
+            let (block, _) = get(block);
+            match row_nrows {
+                None => row_nrows = Some(block.nrows()),
+                Some(row_nrows) => assert!(block.nrows() == row_nrows),
+            }
+            row_ncols += block.ncols();
+        }
+        match ncols {
+            None => ncols = Some(row_ncols),
+            Some(ncols) => assert!(row_ncols == ncols),
+        }
+        nrows += row_nrows.unwrap_or(0);
+    }
+
+    let mut mat = Mat::<E>::zeros(nrows, ncols.unwrap_or(0));
+    let mut row_start = 0usize;
+    for i in 0..n_block_rows {
+        let mut col_start = 0usize;
+        let mut row_nrows = 0usize;
+        for &block in block_row(i) {
+            let (block, conj) = get(block);
+            let mut dst =
+                mat.as_mut()
+                    .submatrix_mut(row_start, col_start, block.nrows(), block.ncols());
+            match conj {
+                Conj::No => dst.rb_mut().copy_from(block),
+                Conj::Yes => dst.rb_mut().copy_from(block.conjugate()),
+            }
+            col_start += block.ncols();
+            row_nrows = block.nrows();
+        }
+        row_start += row_nrows;
+    }
+    mat
+}
+
+/// Concatenates the matrices in `blocks` horizontally.
+///
+/// # Panics
+/// Panics if the matrices don't all have the same number of rows.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::hstack};
+///
+/// let a = mat![[1.0], [4.0]];
+/// let b = mat![[2.0, 3.0], [5.0, 6.0]];
+/// assert!(hstack(&[a.as_ref(), b.as_ref()]) == mat![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+/// ```
+#[track_caller]
+pub fn hstack<E: ComplexField>(blocks: &[MatRef<'_, E>]) -> Mat<E> {
+    block_impl(1, |_| blocks, |block| (block, Conj::No))
+}
+
+/// Concatenates the matrices in `blocks` vertically.
+///
+/// # Panics
+/// Panics if the matrices don't all have the same number of columns.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::vstack};
+///
+/// let a = mat![[1.0, 2.0]];
+/// let b = mat![[3.0, 4.0], [5.0, 6.0]];
+/// assert!(vstack(&[a.as_ref(), b.as_ref()]) == mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]]);
+/// ```
+#[track_caller]
+pub fn vstack<E: ComplexField>(blocks: &[MatRef<'_, E>]) -> Mat<E> {
+    block_impl(
+        blocks.len(),
+        |i| core::slice::from_ref(&blocks[i]),
+        |block| (block, Conj::No),
+    )
+}
+
+/// Builds a matrix from a list of rows of blocks.
+///
+/// The matrices in each row are concatenated horizontally, then the resulting rows are
+/// concatenated vertically. This follows the convention of `numpy.block`, where the boundaries of
+/// the blocks in different rows don't need to line up. See also the [`concat!`](crate::concat)
+/// macro.
+///
+/// # Panics
+/// Panics if the matrices in a row don't all have the same number of rows, or if the rows don't
+/// all have the same total number of columns.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::block};
+///
+/// let a = mat![[1.0, 2.0], [3.0, 4.0]];
+/// let b = mat![[5.0], [6.0]];
+/// let c = mat![[7.0, 8.0, 9.0]];
+/// assert!(
+///     block(&[&[a.as_ref(), b.as_ref()], &[c.as_ref()]])
+///         == mat![[1.0, 2.0, 5.0], [3.0, 4.0, 6.0], [7.0, 8.0, 9.0]]
+/// );
+/// ```
+#[track_caller]
+pub fn block<E: ComplexField>(blocks: &[&[MatRef<'_, E>]]) -> Mat<E> {
+    block_impl(blocks.len(), |i| blocks[i], |block| (block, Conj::No))
+}
+
+/// Builds the block-diagonal matrix with the diagonal blocks `blocks`, which don't need to be
+/// square.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::block_diag};
+///
+/// let a = mat![[1.0, 2.0]];
+/// let b = mat![[3.0], [4.0]];
+/// assert!(
+///     block_diag(&[a.as_ref(), b.as_ref()])
+///         == mat![[1.0, 2.0, 0.0], [0.0, 0.0, 3.0], [0.0, 0.0, 4.0]]
+/// );
+/// ```
+pub fn block_diag<E: ComplexField>(blocks: &[MatRef<'_, E>]) -> Mat<E> {
+    let nrows = blocks.iter().map(|block| block.nrows()).sum();
+    let ncols = blocks.iter().map(|block| block.ncols()).sum();
+
+    let mut mat = Mat::<E>::zeros(nrows, ncols);
+    let mut row_start = 0usize;
+    let mut col_start = 0usize;
+    for block in blocks {
+        mat.as_mut()
+            .submatrix_mut(row_start, col_start, block.nrows(), block.ncols())
+            .copy_from(block);
+        row_start += block.nrows();
+        col_start += block.ncols();
+    }
+    mat
+}
+
+/// Returns a copy of `mat` surrounded by `top` and `bottom` rows and `left` and `right` columns
+/// filled with `value`.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::pad};
+///
+/// let a = mat![[1.0, 2.0]];
+/// assert!(
+///     pad(a.as_ref(), 1, 0, 0, 1, 0.0) == mat![[0.0, 0.0, 0.0], [1.0, 2.0, 0.0]]
+/// );
+/// ```
+#[track_caller]
+pub fn pad<E: ComplexField>(
+    mat: MatRef<'_, E>,
+    top: usize,
+    bottom: usize,
+    left: usize,
+    right: usize,
+    value: E,
+) -> Mat<E> {
+    let nrows = mat
+        .nrows()
+        .checked_add(top)
+        .and_then(|n| n.checked_add(bottom));
+    let ncols = mat
+        .ncols()
+        .checked_add(left)
+        .and_then(|n| n.checked_add(right));
+    assert!(all(nrows.is_some(), ncols.is_some()));
+
+    let mut out = Mat::<E>::with_capacity(nrows.unwrap(), ncols.unwrap());
+    out.resize_with(nrows.unwrap(), ncols.unwrap(), |_, _| value);
+    out.as_mut()
+        .submatrix_mut(top, left, mat.nrows(), mat.ncols())
+        .copy_from(mat);
+    out
+}
+
+/// Returns the matrix made of `row_reps×col_reps` copies of `mat`.
+///
+/// # Example
+/// ```
+/// use faer::{mat, mat::tile};
+///
+/// let a = mat![[1.0, 2.0]];
+/// assert!(tile(a.as_ref(), 2, 2) == mat![[1.0, 2.0, 1.0, 2.0], [1.0, 2.0, 1.0, 2.0]]);
+/// ```
+#[track_caller]
+pub fn tile<E: ComplexField>(mat: MatRef<'_, E>, row_reps: usize, col_reps: usize) -> Mat<E> {
+    let m = mat.nrows();
+    let n = mat.ncols();
+    let nrows = m.checked_mul(row_reps);
+    let ncols = n.checked_mul(col_reps);
+    assert!(all(nrows.is_some(), ncols.is_some()));
+
+    let mut out = Mat::<E>::zeros(nrows.unwrap(), ncols.unwrap());
+    for j in 0..col_reps {
+        for i in 0..row_reps {
+            out.as_mut()
+                .submatrix_mut(i * m, j * n, m, n)
+                .copy_from(mat);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex_native::c64, hstack, vstack};
+
+    #[test]
+    fn test_stack() {
+        let a = Mat::from_fn(3, 2, |i, j| c64::new(i as f64, j as f64));
+        let b = Mat::from_fn(3, 4, |i, j| c64::new(j as f64, -(i as f64)));
+        let c = Mat::from_fn(2, 6, |i, j| c64::new((i + j) as f64, 1.0));
+
+        let ab = hstack(&[a.as_ref(), b.as_ref()]);
+        assert!(all(ab.nrows() == 3, ab.ncols() == 6));
+        assert!(ab.as_ref().subcols(0, 2) == a.as_ref());
+        assert!(ab.as_ref().subcols(2, 4) == b.as_ref());
+        assert!(ab == hstack![a, b]);
+
+        let abc = vstack(&[ab.as_ref(), c.as_ref()]);
+        assert!(all(abc.nrows() == 5, abc.ncols() == 6));
+        assert!(abc.as_ref().subrows(0, 3) == ab.as_ref());
+        assert!(abc.as_ref().subrows(3, 2) == c.as_ref());
+        assert!(abc == vstack![ab, c]);
+        assert!(abc == block(&[&[a.as_ref(), b.as_ref()], &[c.as_ref()]]));
+
+        // the conjugate of a block is copied with the conjugate values
+        let ac = hstack![a.conjugate(), b];
+        assert!(ac.as_ref().subcols(0, 2) == a.conjugate().to_owned().as_ref());
+
+        let diag = block_diag(&[a.as_ref(), c.as_ref()]);
+        assert!(all(diag.nrows() == 5, diag.ncols() == 8));
+        assert!(diag.as_ref().submatrix(0, 0, 3, 2) == a.as_ref());
+        assert!(diag.as_ref().submatrix(3, 2, 2, 6) == c.as_ref());
+        assert!(diag.as_ref().submatrix(0, 2, 3, 6) == Mat::<c64>::zeros(3, 6).as_ref());
+        assert!(diag.as_ref().submatrix(3, 0, 2, 2) == Mat::<c64>::zeros(2, 2).as_ref());
+
+        let padded = pad(a.as_ref(), 1, 2, 3, 0, c64::new(-1.0, 0.0));
+        assert!(all(padded.nrows() == 6, padded.ncols() == 5));
+        assert!(padded.as_ref().submatrix(1, 3, 3, 2) == a.as_ref());
+        for (i, j) in [(0, 0), (0, 4), (4, 3), (5, 4), (2, 2)] {
+            assert!(padded.read(i, j) == c64::new(-1.0, 0.0));
+        }
+
+        let tiled = tile(a.as_ref(), 2, 3);
+        assert!(all(tiled.nrows() == 6, tiled.ncols() == 6));
+        for i in 0..2 {
+            for j in 0..3 {
+                assert!(tiled.as_ref().submatrix(3 * i, 2 * j, 3, 2) == a.as_ref());
+            }
+        }
+        assert!(tile(a.as_ref(), 0, 3).nrows() == 0);
+
+        let empty = hstack::<f64>(&[]);
+        assert!(all(empty.nrows() == 0, empty.ncols() == 0));
+        let empty = vstack::<f64>(&[]);
+        assert!(all(empty.nrows() == 0, empty.ncols() == 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_hstack_mismatch() {
+        let a = Mat::<f64>::zeros(3, 2);
+        let b = Mat::<f64>::zeros(2, 2);
+        hstack(&[a.as_ref(), b.as_ref()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_vstack_mismatch() {
+        let a = Mat::<f64>::zeros(3, 2);
+        let b = Mat::<f64>::zeros(3, 1);
+        vstack(&[a.as_ref(), b.as_ref()]);
+    }
+}
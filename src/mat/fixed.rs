@@ -0,0 +1,559 @@
+use crate::{
+    assert,
+    mat::{Mat, MatMut, MatRef},
+    ComplexField, Scale,
+};
+use faer_entity::*;
+
+/// Matrix with dimensions known at compile time, stored inline in column-major order.
+///
+/// The storage lives on the stack (or inline in the containing type), so that no allocation is
+/// performed when creating or operating on these matrices. The arithmetic operators are
+/// implemented with fully unrolled loops, which makes them suitable for small dimensions such as
+/// the `3×3` and `4×4` matrices used in geometry.
+///
+/// The matrix can be viewed as a [`MatRef`] or [`MatMut`] to use the rest of the library.
+///
+/// # Example
+/// ```
+/// use faer::mat::FixedMat;
+///
+/// let rot = FixedMat::<f64, 2, 2>::from_rows([[0.0, -1.0], [1.0, 0.0]]);
+/// let x = FixedMat::<f64, 2, 1>::from_cols([[1.0, 2.0]]);
+/// let y = rot * x;
+/// assert!(y == FixedMat::from_cols([[-2.0, 1.0]]));
+///
+/// // interoperability with the dynamically sized types
+/// assert!(rot.as_ref().transpose() == rot.transpose().as_ref());
+/// ```
+#[derive(Copy, Clone)]
+#[repr(transparent)]
+pub struct FixedMat<E: SimpleEntity, const M: usize, const N: usize> {
+    cols: [[E; M]; N],
+}
+
+/// Column vector with dimension known at compile time.
+pub type FixedCol<E, const M: usize> = FixedMat<E, M, 1>;
+
+impl<E: SimpleEntity, const M: usize, const N: usize> FixedMat<E, M, N> {
+    /// Returns a new matrix whose columns are given by `cols`.
+    #[inline]
+    pub const fn from_cols(cols: [[E; M]; N]) -> Self {
+        Self { cols }
+    }
+
+    /// Returns a new matrix whose rows are given by `rows`.
+    #[inline]
+    pub fn from_rows(rows: [[E; N]; M]) -> Self {
+        Self::from_fn(|i, j| rows[i][j])
+    }
+
+    /// Returns a new matrix with the elements computed by `f`.
+    #[inline]
+    pub fn from_fn(mut f: impl FnMut(usize, usize) -> E) -> Self {
+        Self {
+            cols: core::array::from_fn(|j| core::array::from_fn(|i| f(i, j))),
+        }
+    }
+
+    /// Returns a new matrix with the elements copied from `mat`.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of `mat` are not `(M, N)`.
+    #[inline]
+    #[track_caller]
+    pub fn from_mat_ref(mat: MatRef<'_, E>) -> Self {
+        assert!(all(mat.nrows() == M, mat.ncols() == N));
+        Self::from_fn(|i, j| mat.read(i, j))
+    }
+
+    /// Returns the columns of the matrix.
+    #[inline]
+    pub const fn into_cols(self) -> [[E; M]; N] {
+        self.cols
+    }
+
+    /// Returns the number of rows of the matrix.
+    #[inline]
+    pub const fn nrows(&self) -> usize {
+        M
+    }
+
+    /// Returns the number of columns of the matrix.
+    #[inline]
+    pub const fn ncols(&self) -> usize {
+        N
+    }
+
+    /// Returns the element at the given indices.
+    ///
+    /// # Panics
+    /// Panics if `row >= M` or `col >= N`.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, row: usize, col: usize) -> E {
+        self.cols[col][row]
+    }
+
+    /// Writes the value to the element at the given indices.
+    ///
+    /// # Panics
+    /// Panics if `row >= M` or `col >= N`.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, row: usize, col: usize, value: E) {
+        self.cols[col][row] = value;
+    }
+
+    /// Returns a view over the matrix.
+    #[inline]
+    pub fn as_ref(&self) -> MatRef<'_, E> {
+        unsafe { crate::mat::from_raw_parts(self.cols.as_ptr() as *const E, M, N, 1, M as isize) }
+    }
+
+    /// Returns a mutable view over the matrix.
+    #[inline]
+    pub fn as_mut(&mut self) -> MatMut<'_, E> {
+        unsafe {
+            crate::mat::from_raw_parts_mut(self.cols.as_mut_ptr() as *mut E, M, N, 1, M as isize)
+        }
+    }
+
+    /// Copies the matrix into a newly allocated dynamically sized matrix.
+    #[inline]
+    pub fn to_mat(&self) -> Mat<E> {
+        Mat::from_fn(M, N, |i, j| self.read(i, j))
+    }
+
+    /// Returns the transpose of the matrix.
+    #[inline]
+    pub fn transpose(&self) -> FixedMat<E, N, M> {
+        FixedMat::from_fn(|i, j| self.read(j, i))
+    }
+
+    /// Returns the `j`-th column of the matrix as a column vector.
+    ///
+    /// # Panics
+    /// Panics if `j >= N`.
+    #[inline]
+    #[track_caller]
+    pub fn col(&self, j: usize) -> FixedCol<E, M> {
+        FixedMat::from_cols([self.cols[j]])
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> FixedMat<E, M, N> {
+    /// Returns a matrix with all the elements set to zero.
+    #[inline]
+    pub fn zeros() -> Self {
+        Self::from_fn(|_, _| E::faer_zero())
+    }
+
+    /// Returns a matrix with the diagonal elements set to one and the other elements set to zero.
+    #[inline]
+    pub fn identity() -> Self {
+        Self::from_fn(|i, j| {
+            if i == j {
+                E::faer_one()
+            } else {
+                E::faer_zero()
+            }
+        })
+    }
+
+    /// Returns the adjoint (conjugate transpose) of the matrix.
+    #[inline]
+    pub fn adjoint(&self) -> FixedMat<E, N, M> {
+        FixedMat::from_fn(|i, j| self.read(j, i).faer_conj())
+    }
+
+    /// Returns the Frobenius norm of the matrix.
+    #[inline]
+    pub fn norm_l2(&self) -> E::Real {
+        let mut acc = E::Real::faer_zero();
+        for col in &self.cols {
+            for &x in col {
+                acc = acc.faer_add(x.faer_abs2());
+            }
+        }
+        acc.faer_sqrt()
+    }
+
+    /// Returns the sum of the products of the corresponding elements of `self` and `rhs`, without
+    /// conjugation.
+    #[inline]
+    pub fn dot(&self, rhs: &Self) -> E {
+        let mut acc = E::faer_zero();
+        for (lhs, rhs) in self.cols.iter().zip(&rhs.cols) {
+            for (&a, &b) in lhs.iter().zip(rhs) {
+                acc = acc.faer_add(a.faer_mul(b));
+            }
+        }
+        acc
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const N: usize> FixedMat<E, N, N> {
+    /// Returns the sum of the diagonal elements of the matrix.
+    #[inline]
+    pub fn trace(&self) -> E {
+        let mut acc = E::faer_zero();
+        for i in 0..N {
+            acc = acc.faer_add(self.read(i, i));
+        }
+        acc
+    }
+
+    /// Returns the determinant of the matrix.
+    ///
+    /// Closed form expressions are used for dimensions up to `3`, and an LU decomposition with
+    /// partial pivoting computed on the stack otherwise.
+    pub fn determinant(&self) -> E {
+        let a = |i: usize, j: usize| self.read(i, j);
+        match N {
+            0 => E::faer_one(),
+            1 => a(0, 0),
+            2 => det2(a(0, 0), a(0, 1), a(1, 0), a(1, 1)),
+            3 => {
+                let c0 = det2(a(1, 1), a(1, 2), a(2, 1), a(2, 2));
+                let c1 = det2(a(1, 0), a(1, 2), a(2, 0), a(2, 2));
+                let c2 = det2(a(1, 0), a(1, 1), a(2, 0), a(2, 1));
+                a(0, 0)
+                    .faer_mul(c0)
+                    .faer_sub(a(0, 1).faer_mul(c1))
+                    .faer_add(a(0, 2).faer_mul(c2))
+            }
+            _ => {
+                let mut lu = *self;
+                let mut det = E::faer_one();
+                for k in 0..N {
+                    let mut pivot = k;
+                    let mut best = lu.read(k, k).faer_abs();
+                    for i in k + 1..N {
+                        let value = lu.read(i, k).faer_abs();
+                        if value > best {
+                            best = value;
+                            pivot = i;
+                        }
+                    }
+                    if best == E::Real::faer_zero() {
+                        return E::faer_zero();
+                    }
+                    if pivot != k {
+                        for j in 0..N {
+                            lu.cols[j].swap(k, pivot);
+                        }
+                        det = det.faer_neg();
+                    }
+                    let diag = lu.read(k, k);
+                    det = det.faer_mul(diag);
+                    let inv = diag.faer_inv();
+                    for i in k + 1..N {
+                        let factor = lu.read(i, k).faer_mul(inv);
+                        for j in k + 1..N {
+                            let value = lu.read(i, j).faer_sub(factor.faer_mul(lu.read(k, j)));
+                            lu.write(i, j, value);
+                        }
+                    }
+                }
+                det
+            }
+        }
+    }
+
+    /// Returns the inverse of the matrix, or `None` if the matrix is singular.
+    ///
+    /// Closed form expressions are used for dimensions up to `3`, and Gauss-Jordan elimination
+    /// with partial pivoting computed on the stack otherwise.
+    pub fn inverse(&self) -> Option<Self> {
+        let zero = E::faer_zero();
+        let a = |i: usize, j: usize| self.read(i, j);
+        match N {
+            0..=3 => {
+                let det = self.determinant();
+                if det == zero {
+                    return None;
+                }
+                let inv_det = det.faer_inv();
+                // the adjugate is the transpose of the cofactor matrix
+                let adjugate = |i: usize, j: usize| -> E {
+                    match N {
+                        1 => E::faer_one(),
+                        2 => {
+                            let value = a(1 - j, 1 - i);
+                            if i == j {
+                                value
+                            } else {
+                                value.faer_neg()
+                            }
+                        }
+                        _ => {
+                            let (r0, r1) = ((j + 1) % 3, (j + 2) % 3);
+                            let (c0, c1) = ((i + 1) % 3, (i + 2) % 3);
+                            det2(a(r0, c0), a(r0, c1), a(r1, c0), a(r1, c1))
+                        }
+                    }
+                };
+                Some(Self::from_fn(|i, j| adjugate(i, j).faer_mul(inv_det)))
+            }
+            _ => {
+                let mut lhs = *self;
+                let mut inv = Self::identity();
+                for k in 0..N {
+                    let mut pivot = k;
+                    let mut best = lhs.read(k, k).faer_abs();
+                    for i in k + 1..N {
+                        let value = lhs.read(i, k).faer_abs();
+                        if value > best {
+                            best = value;
+                            pivot = i;
+                        }
+                    }
+                    if best == E::Real::faer_zero() {
+                        return None;
+                    }
+                    for j in 0..N {
+                        lhs.cols[j].swap(k, pivot);
+                        inv.cols[j].swap(k, pivot);
+                    }
+                    let diag_inv = lhs.read(k, k).faer_inv();
+                    for j in 0..N {
+                        lhs.write(k, j, lhs.read(k, j).faer_mul(diag_inv));
+                        inv.write(k, j, inv.read(k, j).faer_mul(diag_inv));
+                    }
+                    for i in 0..N {
+                        if i == k {
+                            continue;
+                        }
+                        let factor = lhs.read(i, k);
+                        for j in 0..N {
+                            lhs.write(
+                                i,
+                                j,
+                                lhs.read(i, j).faer_sub(factor.faer_mul(lhs.read(k, j))),
+                            );
+                            inv.write(
+                                i,
+                                j,
+                                inv.read(i, j).faer_sub(factor.faer_mul(inv.read(k, j))),
+                            );
+                        }
+                    }
+                }
+                Some(inv)
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn det2<E: ComplexField>(a: E, b: E, c: E, d: E) -> E {
+    a.faer_mul(d).faer_sub(b.faer_mul(c))
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> Default for FixedMat<E, M, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::zeros()
+    }
+}
+
+impl<E: SimpleEntity, const M: usize, const N: usize> PartialEq for FixedMat<E, M, N> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cols == other.cols
+    }
+}
+
+impl<E: SimpleEntity, const M: usize, const N: usize> core::fmt::Debug for FixedMat<E, M, N> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<E: SimpleEntity, const M: usize, const N: usize> core::ops::Index<(usize, usize)>
+    for FixedMat<E, M, N>
+{
+    type Output = E;
+
+    #[inline]
+    #[track_caller]
+    fn index(&self, (row, col): (usize, usize)) -> &E {
+        &self.cols[col][row]
+    }
+}
+
+impl<E: SimpleEntity, const M: usize, const N: usize> core::ops::IndexMut<(usize, usize)>
+    for FixedMat<E, M, N>
+{
+    #[inline]
+    #[track_caller]
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut E {
+        &mut self.cols[col][row]
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::Add
+    for FixedMat<E, M, N>
+{
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.read(i, j).faer_add(rhs.read(i, j)))
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::Sub
+    for FixedMat<E, M, N>
+{
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.read(i, j).faer_sub(rhs.read(i, j)))
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::Neg
+    for FixedMat<E, M, N>
+{
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_fn(|i, j| self.read(i, j).faer_neg())
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::AddAssign
+    for FixedMat<E, M, N>
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::SubAssign
+    for FixedMat<E, M, N>
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const K: usize, const N: usize>
+    core::ops::Mul<FixedMat<E, K, N>> for FixedMat<E, M, K>
+{
+    type Output = FixedMat<E, M, N>;
+
+    #[inline]
+    fn mul(self, rhs: FixedMat<E, K, N>) -> FixedMat<E, M, N> {
+        let mut out = FixedMat::<E, M, N>::zeros();
+        for j in 0..N {
+            for k in 0..K {
+                let b = rhs.read(k, j);
+                for i in 0..M {
+                    out.cols[j][i] = out.cols[j][i].faer_add(self.read(i, k).faer_mul(b));
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize> core::ops::Mul<Scale<E>>
+    for FixedMat<E, M, N>
+{
+    type Output = Self;
+
+    #[inline]
+    fn mul(self, rhs: Scale<E>) -> Self {
+        Self::from_fn(|i, j| self.read(i, j).faer_mul(rhs.0))
+    }
+}
+
+impl<E: SimpleEntity + ComplexField, const M: usize, const N: usize>
+    core::ops::Mul<FixedMat<E, M, N>> for Scale<E>
+{
+    type Output = FixedMat<E, M, N>;
+
+    #[inline]
+    fn mul(self, rhs: FixedMat<E, M, N>) -> FixedMat<E, M, N> {
+        rhs * self
+    }
+}
+
+impl<E: SimpleEntity, const M: usize, const N: usize> From<[[E; M]; N]> for FixedMat<E, M, N> {
+    #[inline]
+    fn from(cols: [[E; M]; N]) -> Self {
+        Self::from_cols(cols)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex_native::c64, scale, Side};
+
+    #[test]
+    fn test_fixed_mat() {
+        let a = FixedMat::<f64, 2, 3>::from_rows([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert!(a.as_ref() == crate::mat![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        assert!(a[(1, 0)] == 4.0);
+        assert!(a.transpose().transpose() == a);
+        assert!(FixedMat::from_mat_ref(a.to_mat().as_ref()) == a);
+
+        let b = FixedMat::<f64, 3, 2>::from_fn(|i, j| (i + 2 * j) as f64);
+        let c = a * b;
+        assert!(c.as_ref() == a.as_ref() * b.as_ref());
+        assert!((a + a).as_ref() == (a * scale(2.0)).as_ref());
+        assert!((a - a) == FixedMat::zeros());
+
+        let mut d = a;
+        d.as_mut().fill_zero();
+        assert!(d == FixedMat::zeros());
+    }
+
+    #[test]
+    fn test_fixed_inverse() {
+        fn check<const N: usize>() {
+            let a = FixedMat::<c64, N, N>::from_fn(|i, j| {
+                c64::new(
+                    if i == j {
+                        4.0
+                    } else {
+                        1.0 / (1.0 + (i + 2 * j) as f64)
+                    },
+                    (i as f64 - j as f64) * 0.25,
+                )
+            });
+            let inv = a.inverse().unwrap();
+            let err = (a * inv - FixedMat::identity()).norm_l2();
+            assert!(err < 1e-12);
+
+            let dense = a.as_ref().determinant();
+            assert!((a.determinant() - dense).faer_abs() < 1e-10);
+        }
+        check::<1>();
+        check::<2>();
+        check::<3>();
+        check::<4>();
+        check::<5>();
+
+        let singular =
+            FixedMat::<f64, 3, 3>::from_rows([[1.0, 2.0, 3.0], [2.0, 4.0, 6.0], [0.0, 1.0, 0.0]]);
+        assert!(singular.determinant() == 0.0);
+        assert!(singular.inverse().is_none());
+
+        let spd =
+            FixedMat::<f64, 3, 3>::from_rows([[4.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 2.0]]);
+        let llt = spd.as_ref().cholesky(Side::Lower).unwrap();
+        let x = llt.solve(FixedCol::<f64, 3>::from_cols([[1.0, 2.0, 3.0]]).as_ref());
+        let y = spd.inverse().unwrap() * FixedCol::from_cols([[1.0, 2.0, 3.0]]);
+        assert!((FixedCol::from_mat_ref(x.as_ref()) - y).norm_l2() < 1e-12);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::mat::MatRef;
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+use faer_entity::*;
+
+/// Notation used to format the matrix entries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Notation {
+    /// Fixed point notation, e.g. `123.45`.
+    Fixed,
+    /// Scientific notation, e.g. `1.2345e2`.
+    Scientific,
+}
+
+/// Alignment of the matrix entries within their column.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// The entries are aligned to the left of their column.
+    Left,
+    /// The entries are aligned to the right of their column.
+    Right,
+}
+
+/// Options controlling how a matrix is formatted by [`MatRef::fmt_with`].
+///
+/// By default, all the entries are shown in fixed point notation with the shortest precision that
+/// represents them exactly, and are aligned to the right.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FormatOptions {
+    precision: Option<usize>,
+    notation: Notation,
+    max_rows: Option<usize>,
+    max_cols: Option<usize>,
+    alignment: Alignment,
+}
+
+impl Default for FormatOptions {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FormatOptions {
+    /// Returns the default options.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            precision: None,
+            notation: Notation::Fixed,
+            max_rows: None,
+            max_cols: None,
+            alignment: Alignment::Right,
+        }
+    }
+
+    /// Sets the number of digits shown after the decimal point.
+    #[inline]
+    pub const fn precision(self, precision: usize) -> Self {
+        Self {
+            precision: Some(precision),
+            ..self
+        }
+    }
+
+    /// Sets the notation used for the entries.
+    #[inline]
+    pub const fn notation(self, notation: Notation) -> Self {
+        Self { notation, ..self }
+    }
+
+    /// Sets the maximum number of rows that are shown. The rows in the middle of larger matrices
+    /// are replaced by an ellipsis.
+    #[inline]
+    pub const fn max_rows(self, max_rows: usize) -> Self {
+        Self {
+            max_rows: Some(max_rows),
+            ..self
+        }
+    }
+
+    /// Sets the maximum number of columns that are shown. The columns in the middle of larger
+    /// matrices are replaced by an ellipsis.
+    #[inline]
+    pub const fn max_cols(self, max_cols: usize) -> Self {
+        Self {
+            max_cols: Some(max_cols),
+            ..self
+        }
+    }
+
+    /// Sets the alignment of the entries within their column.
+    #[inline]
+    pub const fn alignment(self, alignment: Alignment) -> Self {
+        Self { alignment, ..self }
+    }
+}
+
+/// Matrix view that is formatted according to the given options, returned by
+/// [`MatRef::fmt_with`].
+#[derive(Copy, Clone)]
+pub struct MatFormat<'a, E: Entity> {
+    mat: MatRef<'a, E>,
+    options: FormatOptions,
+}
+
+impl<'a, E: Entity> MatFormat<'a, E> {
+    #[inline]
+    pub(crate) fn new(mat: MatRef<'a, E>, options: FormatOptions) -> Self {
+        Self { mat, options }
+    }
+}
+
+// returns the indices that are shown out of `len`, or `None` in place of the elided ones
+fn shown(len: usize, max: Option<usize>) -> Vec<Option<usize>> {
+    match max {
+        Some(max) if len > max => {
+            let head = (max + 1) / 2;
+            let tail = max / 2;
+            (0..head)
+                .map(Some)
+                .chain(core::iter::once(None))
+                .chain((len - tail..len).map(Some))
+                .collect()
+        }
+        _ => (0..len).map(Some).collect(),
+    }
+}
+
+fn format_entry<E: core::fmt::Display + core::fmt::LowerExp>(
+    value: E,
+    options: &FormatOptions,
+) -> String {
+    let mut out = String::new();
+    let _ = match (options.notation, options.precision) {
+        (Notation::Fixed, None) => write!(out, "{value}"),
+        (Notation::Fixed, Some(p)) => write!(out, "{value:.p$}"),
+        (Notation::Scientific, None) => write!(out, "{value:e}"),
+        (Notation::Scientific, Some(p)) => write!(out, "{value:.p$e}"),
+    };
+    out
+}
+
+impl<E: Entity + core::fmt::Display + core::fmt::LowerExp> core::fmt::Display for MatFormat<'_, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mat = self.mat;
+        let options = &self.options;
+        let rows = shown(mat.nrows(), options.max_rows);
+        let cols = shown(mat.ncols(), options.max_cols);
+
+        // format the shown entries first, to compute the width of each column
+        let ellipsis = "...";
+        let cells = rows
+            .iter()
+            .map(|&i| {
+                cols.iter()
+                    .map(|&j| match (i, j) {
+                        (Some(i), Some(j)) => format_entry(mat.read(i, j), options),
+                        _ => String::from(ellipsis),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        let widths = (0..cols.len())
+            .map(|k| {
+                cells
+                    .iter()
+                    .map(|row| row[k].chars().count())
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect::<Vec<_>>();
+
+        f.write_str("[")?;
+        for (r, row) in cells.iter().enumerate() {
+            if r > 0 {
+                f.write_str(",\n ")?;
+            }
+            f.write_str("[")?;
+            for (k, cell) in row.iter().enumerate() {
+                if k > 0 {
+                    f.write_str(", ")?;
+                }
+                let width = widths[k];
+                match options.alignment {
+                    Alignment::Left => write!(f, "{cell:<width$}")?,
+                    Alignment::Right => write!(f, "{cell:>width$}")?,
+                }
+            }
+            f.write_str("]")?;
+        }
+        f.write_str("]")
+    }
+}
+
+impl<E: Entity + core::fmt::Display + core::fmt::LowerExp> core::fmt::Debug for MatFormat<'_, E> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Display::fmt(self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, mat, Col, Mat};
+    use alloc::format;
+
+    #[test]
+    fn test_format() {
+        let a = mat![[1.0, -2.5], [10.5, 4.0f64]];
+        assert!(
+            format!("{}", a.fmt_with(FormatOptions::new())) == "[[   1, -2.5],\n [10.5,    4]]"
+        );
+        assert!(
+            format!(
+                "{}",
+                a.fmt_with(FormatOptions::new().precision(1).alignment(Alignment::Left))
+            ) == "[[1.0 , -2.5],\n [10.5, 4.0 ]]"
+        );
+        assert!(
+            format!(
+                "{}",
+                a.fmt_with(
+                    FormatOptions::new()
+                        .precision(2)
+                        .notation(Notation::Scientific)
+                )
+            ) == "[[1.00e0, -2.50e0],\n [1.05e1,  4.00e0]]"
+        );
+
+        let b = Mat::from_fn(6, 7, |i, j| (10 * i + j) as f64);
+        assert!(
+            format!("{}", b.fmt_with(FormatOptions::new().max_rows(3).max_cols(4)))
+                == "[[  0,   1, ...,   5,   6],\n [ 10,  11, ...,  15,  16],\n [..., ..., ..., ..., ...],\n [ 50,  51, ...,  55,  56]]"
+        );
+
+        let c = Col::from_fn(2, |i| c64::new(i as f64, 1.0));
+        assert!(
+            format!("{}", c.fmt_with(FormatOptions::new().precision(1)))
+                == "[[0.0 + 1.0 * I],\n [1.0 + 1.0 * I]]"
+        );
+        assert!(format!("{}", Mat::<f64>::zeros(0, 3).fmt_with(FormatOptions::new())) == "[]");
+    }
+}
@@ -0,0 +1,270 @@
+use crate::{assert, mat::Mat, RealField};
+use alloc::vec::Vec;
+
+/// Owning boolean matrix, stored in column-major order with one byte per entry.
+///
+/// Masks are produced by elementwise predicates such as [`MatRef::is_nan`](super::MatRef::is_nan),
+/// and can be combined with logical operations. They can be used to select entries with
+/// [`MatRef::select`](super::MatRef::select), to select rows or columns with
+/// [`MatRef::select_rows`](super::MatRef::select_rows) and
+/// [`MatRef::select_cols`](super::MatRef::select_cols) through [`Mask::col_as_slice`], and with the
+/// masked statistics of [`stats`](crate::stats) through [`Mask::to_mat`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    data: Vec<bool>,
+    nrows: usize,
+    ncols: usize,
+}
+
+impl Mask {
+    /// Returns a new mask with dimensions `(nrows, ncols)`, with all the entries set to `value`.
+    #[inline]
+    pub fn full(nrows: usize, ncols: usize, value: bool) -> Self {
+        Self {
+            data: alloc::vec![value; nrows.checked_mul(ncols).unwrap()],
+            nrows,
+            ncols,
+        }
+    }
+
+    /// Returns a new mask with dimensions `(nrows, ncols)`, filled with the provided function.
+    #[inline]
+    pub fn from_fn(nrows: usize, ncols: usize, mut f: impl FnMut(usize, usize) -> bool) -> Self {
+        let mut data = Vec::with_capacity(nrows.checked_mul(ncols).unwrap());
+        for j in 0..ncols {
+            for i in 0..nrows {
+                data.push(f(i, j));
+            }
+        }
+        Self { data, nrows, ncols }
+    }
+
+    /// Returns the number of rows of the mask.
+    #[inline(always)]
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns of the mask.
+    #[inline(always)]
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the entry at the given indices.
+    ///
+    /// # Panics
+    /// Panics if `row >= self.nrows()` or `col >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn get(&self, row: usize, col: usize) -> bool {
+        assert!(all(row < self.nrows(), col < self.ncols()));
+        self.data[row + col * self.nrows]
+    }
+
+    /// Sets the entry at the given indices to `value`.
+    ///
+    /// # Panics
+    /// Panics if `row >= self.nrows()` or `col >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn set(&mut self, row: usize, col: usize, value: bool) {
+        assert!(all(row < self.nrows(), col < self.ncols()));
+        self.data[row + col * self.nrows] = value;
+    }
+
+    /// Returns the entries of the mask in column-major order.
+    #[inline]
+    pub fn as_slice(&self) -> &[bool] {
+        &self.data
+    }
+
+    // entries of the mask in column-major order, for the kernels that fill it
+    #[inline]
+    pub(super) fn as_slice_mut(&mut self) -> &mut [bool] {
+        &mut self.data
+    }
+
+    /// Returns the entries of the column at the given index, which can be passed to
+    /// [`MatRef::select_rows`](super::MatRef::select_rows).
+    ///
+    /// # Panics
+    /// Panics if `col >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn col_as_slice(&self, col: usize) -> &[bool] {
+        assert!(col < self.ncols());
+        &self.data[col * self.nrows..][..self.nrows]
+    }
+
+    /// Returns the transpose of the mask.
+    pub fn transpose(&self) -> Mask {
+        Mask::from_fn(self.ncols, self.nrows, |i, j| self.get(j, i))
+    }
+
+    #[track_caller]
+    fn zip_with(&self, other: &Mask, f: impl Fn(bool, bool) -> bool) -> Mask {
+        assert!(all(
+            self.nrows() == other.nrows(),
+            self.ncols() == other.ncols()
+        ));
+        Mask {
+            data: core::iter::zip(&self.data, &other.data)
+                .map(|(&a, &b)| f(a, b))
+                .collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+
+    /// Returns the elementwise logical and of `self` and `other`.
+    ///
+    /// # Panics
+    /// Panics if the masks don't have the same dimensions.
+    #[track_caller]
+    pub fn and(&self, other: &Mask) -> Mask {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    /// Returns the elementwise logical or of `self` and `other`.
+    ///
+    /// # Panics
+    /// Panics if the masks don't have the same dimensions.
+    #[track_caller]
+    pub fn or(&self, other: &Mask) -> Mask {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    /// Returns the elementwise logical exclusive or of `self` and `other`.
+    ///
+    /// # Panics
+    /// Panics if the masks don't have the same dimensions.
+    #[track_caller]
+    pub fn xor(&self, other: &Mask) -> Mask {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    /// Returns the elementwise logical negation of `self`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(&self) -> Mask {
+        Mask {
+            data: self.data.iter().map(|&a| !a).collect(),
+            nrows: self.nrows,
+            ncols: self.ncols,
+        }
+    }
+
+    /// Returns the number of `true` entries.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.data.iter().filter(|&&a| a).count()
+    }
+
+    /// Returns `true` if any of the entries is `true`, otherwise returns `false`.
+    #[inline]
+    pub fn any(&self) -> bool {
+        self.data.iter().any(|&a| a)
+    }
+
+    /// Returns `true` if all of the entries are `true`, otherwise returns `false`. Returns `true`
+    /// for an empty mask.
+    #[inline]
+    pub fn all(&self) -> bool {
+        self.data.iter().all(|&a| a)
+    }
+
+    /// Returns a matrix whose entries are one where the mask is `true`, and zero otherwise, in the
+    /// format expected by the masked statistics such as
+    /// [`col_mean_masked`](crate::stats::col_mean_masked).
+    pub fn to_mat<E: RealField>(&self) -> Mat<E> {
+        Mat::from_fn(self.nrows, self.ncols, |i, j| {
+            if self.get(i, j) {
+                E::faer_one()
+            } else {
+                E::faer_zero()
+            }
+        })
+    }
+}
+
+impl core::ops::Not for &Mask {
+    type Output = Mask;
+
+    #[inline]
+    fn not(self) -> Mask {
+        Mask::not(self)
+    }
+}
+
+impl core::ops::Not for Mask {
+    type Output = Mask;
+
+    #[inline]
+    fn not(mut self) -> Mask {
+        self.data.iter_mut().for_each(|a| *a = !*a);
+        self
+    }
+}
+
+macro_rules! impl_mask_op {
+    ($trait: ident, $trait_fn: ident, $fn: ident) => {
+        impl core::ops::$trait<&Mask> for &Mask {
+            type Output = Mask;
+
+            #[inline]
+            #[track_caller]
+            fn $trait_fn(self, other: &Mask) -> Mask {
+                Mask::$fn(self, other)
+            }
+        }
+
+        impl core::ops::$trait<Mask> for Mask {
+            type Output = Mask;
+
+            #[inline]
+            #[track_caller]
+            fn $trait_fn(self, other: Mask) -> Mask {
+                Mask::$fn(&self, &other)
+            }
+        }
+    };
+}
+
+impl_mask_op!(BitAnd, bitand, and);
+impl_mask_op!(BitOr, bitor, or);
+impl_mask_op!(BitXor, bitxor, xor);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, mat};
+
+    #[test]
+    fn test_mask() {
+        let nan = f64::NAN;
+        let a = mat![[1.0, nan, 3.0], [nan, 5.0, f64::INFINITY]];
+
+        let is_nan = a.is_nan();
+        let is_finite = a.is_finite();
+        assert!(is_nan == Mask::from_fn(2, 3, |i, j| (i + j) == 1));
+        assert!(all(
+            is_nan.count() == 2,
+            is_nan.any(),
+            !is_nan.all(),
+            is_finite.count() == 3,
+        ));
+        assert!(is_nan.and(&is_finite) == Mask::full(2, 3, false));
+        assert!((&is_nan | &is_finite) == !Mask::from_fn(2, 3, |i, j| (i, j) == (1, 2)));
+        assert!((!is_nan.clone()).xor(&is_finite) == Mask::from_fn(2, 3, |i, j| (i, j) == (1, 2)));
+
+        // selection
+        assert!(a.select(&is_finite) == crate::col![1.0, 5.0, 3.0]);
+        let b = mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+        let rows = Mask::from_fn(3, 1, |i, _| i != 1);
+        assert!(b.select_rows(rows.col_as_slice(0)) == mat![[1.0, 2.0], [5.0, 6.0]]);
+        assert!(rows.to_mat::<f64>() == mat![[1.0], [0.0], [1.0]]);
+
+        let empty = Mask::full(0, 4, false);
+        assert!(all(empty.all(), !empty.any(), empty.count() == 0));
+    }
+}
@@ -1,6 +1,115 @@
 use super::*;
 use crate::complex_native::*;
-use core::mem::ManuallyDrop;
+use core::{
+    alloc::GlobalAlloc,
+    mem::ManuallyDrop,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+struct DefaultAllocator;
+
+// SAFETY: forwards to the global allocator
+unsafe impl GlobalAlloc for DefaultAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: alloc::alloc::Layout) -> *mut u8 {
+        alloc::alloc::alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: alloc::alloc::Layout) {
+        alloc::alloc::dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: alloc::alloc::Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        alloc::alloc::realloc(ptr, layout, new_size)
+    }
+}
+
+static DEFAULT_ALLOCATOR: &(dyn GlobalAlloc + Sync) = &DefaultAllocator;
+
+// null until either the first matrix allocation or the first call to `set_allocator`, after which
+// it never changes, so that all the matrix memory is freed by the allocator that provided it
+static ALLOCATOR: AtomicPtr<&'static (dyn GlobalAlloc + Sync)> =
+    AtomicPtr::new(core::ptr::null_mut());
+
+/// Error returned by [`set_allocator`](crate::mat::set_allocator) when the allocator can no
+/// longer be changed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct AllocatorAlreadySet;
+
+impl core::fmt::Display for AllocatorAlreadySet {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AllocatorAlreadySet {}
+
+/// Sets the allocator used for the storage of the owned dense matrices and vectors ([`Mat`],
+/// [`Col`] and [`Row`]), instead of the global allocator of the program.
+///
+/// This makes it possible to use arena or NUMA-pinned memory for the matrices, without replacing
+/// the allocator of the whole program.
+///
+/// The allocator can only be set once, before any matrix memory is allocated, since the memory
+/// must be freed by the allocator that provided it. Otherwise, an error is returned and the
+/// allocator is unchanged.
+///
+/// # Note
+///
+/// Sparse matrices and the temporary workspaces used by the algorithms are allocated with the
+/// global allocator of the program, which can be replaced with the `#[global_allocator]`
+/// attribute.
+pub fn set_allocator(
+    allocator: &'static (dyn GlobalAlloc + Sync),
+) -> Result<(), AllocatorAlreadySet> {
+    if !ALLOCATOR.load(Ordering::Acquire).is_null() {
+        return Err(AllocatorAlreadySet);
+    }
+    // the fat reference is leaked so that it can be stored behind a thin atomic pointer
+    let ptr = alloc::boxed::Box::into_raw(alloc::boxed::Box::new(allocator));
+    match ALLOCATOR.compare_exchange(
+        core::ptr::null_mut(),
+        ptr,
+        Ordering::AcqRel,
+        Ordering::Acquire,
+    ) {
+        Ok(_) => Ok(()),
+        Err(_) => {
+            // SAFETY: `ptr` was not published, so we still own it
+            drop(unsafe { alloc::boxed::Box::from_raw(ptr) });
+            Err(AllocatorAlreadySet)
+        }
+    }
+}
+
+#[inline]
+fn allocator() -> &'static (dyn GlobalAlloc + Sync) {
+    let mut ptr = ALLOCATOR.load(Ordering::Acquire);
+    if ptr.is_null() {
+        let default = &DEFAULT_ALLOCATOR as *const &'static (dyn GlobalAlloc + Sync) as *mut _;
+        ptr = match ALLOCATOR.compare_exchange(
+            core::ptr::null_mut(),
+            default,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(_) => default,
+            Err(ptr) => ptr,
+        };
+    }
+    // SAFETY: the pointer is non null, and points to a reference with a static lifetime that is
+    // never freed
+    unsafe { *ptr }
+}
 
 #[repr(C)]
 pub struct RawMatUnit<T: 'static> {
@@ -36,7 +145,7 @@ impl<T: 'static> RawMatUnit<T> {
                 capacity_overflow::<()>();
             }
 
-            use alloc::alloc::{alloc, handle_alloc_error, Layout};
+            use alloc::alloc::{handle_alloc_error, Layout};
 
             let layout = Layout::from_size_align(cap_bytes, align_for::<T>())
                 .ok()
@@ -46,7 +155,7 @@ impl<T: 'static> RawMatUnit<T> {
                 dangling
             } else {
                 // SAFETY: we checked that layout has non zero size
-                let ptr = unsafe { alloc(layout) } as *mut T;
+                let ptr = unsafe { allocator().alloc(layout) } as *mut T;
                 if ptr.is_null() {
                     handle_alloc_error(layout)
                 }
@@ -65,16 +174,17 @@ impl<T: 'static> RawMatUnit<T> {
 
 impl<T: 'static> Drop for RawMatUnit<T> {
     fn drop(&mut self) {
-        use alloc::alloc::{dealloc, Layout};
+        use alloc::alloc::Layout;
         // this cannot overflow because we already allocated this much memory
         // self.row_capacity.wrapping_mul(self.col_capacity) may overflow if T is a zst
         // but that's fine since we immediately multiply it by 0.
         let alloc_size =
             self.row_capacity.wrapping_mul(self.col_capacity) * core::mem::size_of::<T>();
         if alloc_size != 0 {
-            // SAFETY: pointer was allocated with alloc::alloc::alloc
+            // SAFETY: pointer was allocated with allocator().alloc, and the allocator never changes
+            // after the first allocation
             unsafe {
-                dealloc(
+                allocator().dealloc(
                     self.ptr.as_ptr() as *mut u8,
                     Layout::from_size_align_unchecked(alloc_size, align_for::<T>()),
                 );
@@ -131,7 +241,7 @@ impl<T> MatUnit<T> {
             // we have enough row capacity, and we've already allocated memory.
             // use realloc to get extra column memory
 
-            use alloc::alloc::{handle_alloc_error, realloc, Layout};
+            use alloc::alloc::{handle_alloc_error, Layout};
 
             // this shouldn't overflow since we already hold this many bytes
             let old_cap = self.raw.row_capacity * self.raw.col_capacity;
@@ -166,7 +276,7 @@ impl<T> MatUnit<T> {
             // overflow, since we checked that we can create new_layout with it.
             unsafe {
                 let old_ptr = self.raw.ptr.as_ptr();
-                let new_ptr = realloc(old_ptr as *mut u8, old_layout, new_cap_bytes);
+                let new_ptr = allocator().realloc(old_ptr as *mut u8, old_layout, new_cap_bytes);
                 if new_ptr.is_null() {
                     handle_alloc_error(new_layout);
                 }
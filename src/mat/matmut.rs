@@ -773,6 +773,190 @@ impl<'a, E: Entity> MatMut<'a, E> {
         );
     }
 
+    /// Adds `row` to each row of `self`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn add_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let row = row.as_row_ref();
+        assert!(row.ncols() == self.ncols());
+        let nrows = self.nrows();
+        let rhs = from_repeated_row(row, nrows);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_add(y.read().canonicalize())),
+        );
+    }
+
+    /// Subtracts `row` from each row of `self`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{mat, row};
+    ///
+    /// // centers the columns of the matrix
+    /// let mut a = mat![[1.0, 2.0], [3.0, 6.0]];
+    /// a.as_mut().sub_broadcast_row(row![2.0, 4.0]);
+    /// assert!(a == mat![[-1.0, -2.0], [1.0, 2.0]]);
+    /// ```
+    #[track_caller]
+    pub fn sub_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let row = row.as_row_ref();
+        assert!(row.ncols() == self.ncols());
+        let nrows = self.nrows();
+        let rhs = from_repeated_row(row, nrows);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_sub(y.read().canonicalize())),
+        );
+    }
+
+    /// Multiplies each column of `self` by the corresponding element of `row`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn mul_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let row = row.as_row_ref();
+        assert!(row.ncols() == self.ncols());
+        let nrows = self.nrows();
+        let rhs = from_repeated_row(row, nrows);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_mul(y.read().canonicalize())),
+        );
+    }
+
+    /// Adds `col` to each column of `self`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn add_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let col = col.as_col_ref();
+        assert!(col.nrows() == self.nrows());
+        let ncols = self.ncols();
+        let rhs = from_repeated_col(col, ncols);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_add(y.read().canonicalize())),
+        );
+    }
+
+    /// Subtracts `col` from each column of `self`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn sub_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let col = col.as_col_ref();
+        assert!(col.nrows() == self.nrows());
+        let ncols = self.ncols();
+        let rhs = from_repeated_col(col, ncols);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_sub(y.read().canonicalize())),
+        );
+    }
+
+    /// Multiplies each row of `self` by the corresponding element of `col`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn mul_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        let col = col.as_col_ref();
+        assert!(col.nrows() == self.nrows());
+        let ncols = self.ncols();
+        let rhs = from_repeated_col(col, ncols);
+        zipped!(self.rb_mut(), rhs).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, y)| x.write(x.read().faer_mul(y.read().canonicalize())),
+        );
+    }
+
+    /// Replaces the `i`-th row of `self` by its `perm[i]`-th row, without allocating a copy of
+    /// the matrix.
+    ///
+    /// # Panics
+    /// Panics if `perm.len() != self.nrows()`.
+    #[track_caller]
+    pub fn permute_rows_in_place<I: crate::Index>(&mut self, perm: crate::perm::PermRef<'_, I>)
+    where
+        E: ComplexField,
+    {
+        super::sorting::permute_rows_in_place(self.rb_mut(), perm)
+    }
+
+    /// Replaces the `j`-th column of `self` by its `perm[j]`-th column, without allocating a copy
+    /// of the matrix.
+    ///
+    /// # Panics
+    /// Panics if `perm.len() != self.ncols()`.
+    #[track_caller]
+    pub fn permute_cols_in_place<I: crate::Index>(&mut self, perm: crate::perm::PermRef<'_, I>)
+    where
+        E: ComplexField,
+    {
+        super::sorting::permute_rows_in_place(self.rb_mut().transpose_mut(), perm)
+    }
+
+    /// Sorts the rows of `self` by their `j`-th column in increasing order, with the NaN values
+    /// at the end, and returns the applied permutation.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    ///
+    /// # Panics
+    /// Panics if `j >= self.ncols()`.
+    #[track_caller]
+    pub fn sort_rows_by_column(&mut self, j: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        let perm = self.rb().argsort_col(j, kind);
+        self.permute_rows_in_place(perm.as_ref());
+        perm
+    }
+
+    /// Sorts the columns of `self` by their `i`-th row in increasing order, with the NaN values
+    /// at the end, and returns the applied permutation.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    ///
+    /// # Panics
+    /// Panics if `i >= self.nrows()`.
+    #[track_caller]
+    pub fn sort_cols_by_row(&mut self, i: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        let perm = self.rb().argsort_row(i, kind);
+        self.permute_cols_in_place(perm.as_ref());
+        perm
+    }
+
     /// Returns a view over the transpose of `self`.
     ///
     /// # Example
@@ -1000,6 +1184,99 @@ impl<'a, E: Entity> MatMut<'a, E> {
         unsafe { self.into_const().reverse_rows_and_cols().const_cast() }
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(self, step: usize) -> MatRef<'a, E> {
+        self.into_const().step_by_rows(step)
+    }
+
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows_mut(self, step: usize) -> Self {
+        unsafe { self.into_const().step_by_rows(step).const_cast() }
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(self, step: usize) -> MatRef<'a, E> {
+        self.into_const().step_by_cols(step)
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols_mut(self, step: usize) -> Self {
+        unsafe { self.into_const().step_by_cols(step).const_cast() }
+    }
+
+    /// Returns a view over the rows `row_start, row_start + row_step, ...` and the columns
+    /// `col_start, col_start + col_step, ...` of `self`.
+    ///
+    /// # Panics
+    /// The function panics if any of the following conditions are violated:
+    /// * `row_start <= self.nrows()`.
+    /// * `col_start <= self.ncols()`.
+    /// * `row_step > 0`.
+    /// * `col_step > 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn subsample(
+        self,
+        row_start: usize,
+        row_step: usize,
+        col_start: usize,
+        col_step: usize,
+    ) -> MatRef<'a, E> {
+        self.into_const()
+            .subsample(row_start, row_step, col_start, col_step)
+    }
+
+    /// Returns a view over the rows `row_start, row_start + row_step, ...` and the columns
+    /// `col_start, col_start + col_step, ...` of `self`.
+    ///
+    /// # Panics
+    /// The function panics if any of the following conditions are violated:
+    /// * `row_start <= self.nrows()`.
+    /// * `col_start <= self.ncols()`.
+    /// * `row_step > 0`.
+    /// * `col_step > 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn subsample_mut(
+        self,
+        row_start: usize,
+        row_step: usize,
+        col_start: usize,
+        col_step: usize,
+    ) -> Self {
+        unsafe {
+            self.into_const()
+                .subsample(row_start, row_step, col_start, col_step)
+                .const_cast()
+        }
+    }
+
     /// Returns a view over the submatrix starting at indices `(row_start, col_start)`, and with
     /// dimensions `(nrows, ncols)`.
     ///
@@ -1487,6 +1764,58 @@ impl<'a, E: Entity> MatMut<'a, E> {
         self.rb().to_owned()
     }
 
+    /// Returns a view over the same data with the dimensions `(nrows, ncols)`, if the matrix is
+    /// stored contiguously in column-major order, otherwise returns `None`.
+    ///
+    /// See [`MatRef::try_reshape`].
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn try_reshape(self, nrows: usize, ncols: usize) -> Option<MatRef<'a, E>> {
+        self.into_const().try_reshape(nrows, ncols)
+    }
+
+    /// Returns a mutable view over the same data with the dimensions `(nrows, ncols)`, if the
+    /// matrix is stored contiguously in column-major order, otherwise returns `None`.
+    ///
+    /// See [`MatRef::try_reshape`].
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn try_reshape_mut(self, nrows: usize, ncols: usize) -> Option<Self> {
+        super::reshape_assert(self.nrows(), self.ncols(), nrows, ncols);
+        if super::is_column_major_contiguous(
+            self.nrows(),
+            self.ncols(),
+            self.row_stride(),
+            self.col_stride(),
+        ) {
+            Some(unsafe { from_raw_parts_mut(self.as_ptr_mut(), nrows, ncols, 1, nrows as isize) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a new matrix with the dimensions `(nrows, ncols)`, containing the elements of
+    /// `self` in column-major order.
+    ///
+    /// See [`MatRef::reshape_to_owned`].
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn reshape_to_owned(&self, nrows: usize, ncols: usize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.rb().reshape_to_owned(nrows, ncols)
+    }
+
     /// Returns `true` if any of the elements is NaN, otherwise returns `false`.
     #[inline]
     pub fn has_nan(&self) -> bool
@@ -448,6 +448,112 @@ impl<E: Entity> Mat<E> {
         self.resize_with(new_nrows, new_ncols, |_, _| unreachable!());
     }
 
+    /// Truncates the matrix so that its new number of rows is `new_nrows`, keeping the number of
+    /// columns unchanged. The capacity of the matrix is not affected.
+    ///
+    /// # Panics
+    /// Panics if `new_nrows > self.nrows()`.
+    #[inline]
+    #[track_caller]
+    pub fn truncate_rows(&mut self, new_nrows: usize) {
+        self.truncate(new_nrows, self.ncols());
+    }
+
+    /// Truncates the matrix so that its new number of columns is `new_ncols`, keeping the number
+    /// of rows unchanged. The capacity of the matrix is not affected.
+    ///
+    /// # Panics
+    /// Panics if `new_ncols > self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn truncate_cols(&mut self, new_ncols: usize) {
+        self.truncate(self.nrows(), new_ncols);
+    }
+
+    /// Reserves the capacity for at least `row_capacity` rows and `col_capacity` columns. Unlike
+    /// [`Mat::reserve_exact`], the capacity grows geometrically so that repeatedly growing the
+    /// matrix by a few rows or columns takes amortized constant time per element.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// # Panics
+    /// The function panics if the new total capacity in bytes exceeds `isize::MAX`.
+    #[inline]
+    pub fn reserve(&mut self, row_capacity: usize, col_capacity: usize) {
+        let grow = |cap: usize, required: usize| {
+            if required <= cap {
+                cap
+            } else {
+                Ord::max(required, cap.saturating_mul(2))
+            }
+        };
+        self.reserve_exact(
+            grow(self.row_capacity(), row_capacity),
+            grow(self.col_capacity(), col_capacity),
+        );
+    }
+
+    /// Appends the rows of `rows` at the bottom of the matrix, reserving the capacity
+    /// geometrically. If the matrix has no rows, its number of columns is set to that of `rows`.
+    ///
+    /// # Panics
+    /// Panics if the matrix has at least one row and `rows.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn append_rows(&mut self, rows: MatRef<'_, E>) {
+        let nrows = self.nrows();
+        let ncols = if nrows == 0 {
+            rows.ncols()
+        } else {
+            assert!(rows.ncols() == self.ncols());
+            self.ncols()
+        };
+        self.reserve(nrows + rows.nrows(), ncols);
+        // the matrix has no rows, so no new elements are needed when setting its number of columns
+        self.resize_with(nrows, ncols, |_, _| unreachable!());
+        self.resize_with(nrows + rows.nrows(), ncols, |i, j| rows.read(i - nrows, j));
+    }
+
+    /// Appends the columns of `cols` at the right of the matrix, reserving the capacity
+    /// geometrically. If the matrix has no columns, its number of rows is set to that of `cols`.
+    ///
+    /// # Panics
+    /// Panics if the matrix has at least one column and `cols.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn append_cols(&mut self, cols: MatRef<'_, E>) {
+        let ncols = self.ncols();
+        let nrows = if ncols == 0 {
+            cols.nrows()
+        } else {
+            assert!(cols.nrows() == self.nrows());
+            self.nrows()
+        };
+        self.reserve(nrows, ncols + cols.ncols());
+        // the matrix has no columns, so no new elements are needed when setting its number of rows
+        self.resize_with(nrows, ncols, |_, _| unreachable!());
+        self.resize_with(nrows, ncols + cols.ncols(), |i, j| cols.read(i, j - ncols));
+    }
+
+    /// Appends `row` at the bottom of the matrix, reserving the capacity geometrically.
+    /// If the matrix has no rows, its number of columns is set to that of `row`.
+    ///
+    /// # Panics
+    /// Panics if the matrix has at least one row and `row.ncols() != self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn push_row(&mut self, row: RowRef<'_, E>) {
+        self.append_rows(row.as_2d());
+    }
+
+    /// Appends `col` at the right of the matrix, reserving the capacity geometrically.
+    /// If the matrix has no columns, its number of rows is set to that of `col`.
+    ///
+    /// # Panics
+    /// Panics if the matrix has at least one column and `col.nrows() != self.nrows()`.
+    #[inline]
+    #[track_caller]
+    pub fn push_col(&mut self, col: ColRef<'_, E>) {
+        self.append_cols(col.as_2d());
+    }
+
     /// Returns a reference to a slice over the column at the given index.
     #[inline]
     #[track_caller]
@@ -490,6 +596,62 @@ impl<E: Entity> Mat<E> {
         }
     }
 
+    /// Returns a value that formats `self` according to the given options.
+    ///
+    /// See [`MatRef::fmt_with`] for more details.
+    #[inline]
+    pub fn fmt_with(&self, options: super::FormatOptions) -> super::MatFormat<'_, E> {
+        self.as_ref().fmt_with(options)
+    }
+
+    /// Returns a new matrix with the entries of `self` cast to `T`. The imaginary parts are
+    /// discarded when casting complex values to a real type.
+    #[inline]
+    pub fn cast<T: Entity>(&self) -> Mat<T>
+    where
+        E: super::Cast<T>,
+    {
+        self.as_ref().cast()
+    }
+
+    /// Returns a new matrix with the entries of `self` cast to `T`, where `policy` specifies how
+    /// the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(&self, policy: super::ImagPolicy) -> Mat<T>
+    where
+        E: super::Cast<T>,
+    {
+        self.as_ref().cast_with(policy)
+    }
+
+    /// Writes the entries of `self` cast to `T` into `out`. The imaginary parts are discarded
+    /// when casting complex values to a real type.
+    ///
+    /// # Panics
+    /// The function panics if `out` doesn't have the same dimensions as `self`.
+    #[inline]
+    #[track_caller]
+    pub fn cast_into<T: Entity>(&self, out: MatMut<'_, T>)
+    where
+        E: super::Cast<T>,
+    {
+        self.as_ref().cast_into(out)
+    }
+
+    /// Writes the entries of `self` cast to `T` into `out`, where `policy` specifies how the
+    /// imaginary parts are handled when casting complex values to a real type.
+    ///
+    /// # Panics
+    /// The function panics if `out` doesn't have the same dimensions as `self`.
+    #[inline]
+    #[track_caller]
+    pub fn cast_into_with<T: Entity>(&self, out: MatMut<'_, T>, policy: super::ImagPolicy)
+    where
+        E: super::Cast<T>,
+    {
+        self.as_ref().cast_into_with(out, policy)
+    }
+
     /// Returns a mutable view over the matrix.
     #[inline]
     pub fn as_mut(&mut self) -> MatMut<'_, E> {
@@ -504,6 +666,50 @@ impl<E: Entity> Mat<E> {
         }
     }
 
+    /// Returns a view over the same data with the dimensions `(nrows, ncols)`, if the matrix is
+    /// stored contiguously in column-major order, otherwise returns `None`.
+    ///
+    /// The columns of a [`Mat`] may be separated by padding for alignment, in which case this
+    /// returns `None`. See [`MatRef::try_reshape`], and [`Mat::reshape_to_owned`] for a copying
+    /// fallback.
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn try_reshape(&self, nrows: usize, ncols: usize) -> Option<MatRef<'_, E>> {
+        self.as_ref().try_reshape(nrows, ncols)
+    }
+
+    /// Returns a mutable view over the same data with the dimensions `(nrows, ncols)`, if the
+    /// matrix is stored contiguously in column-major order, otherwise returns `None`.
+    ///
+    /// See [`MatRef::try_reshape`].
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn try_reshape_mut(&mut self, nrows: usize, ncols: usize) -> Option<MatMut<'_, E>> {
+        self.as_mut().try_reshape_mut(nrows, ncols)
+    }
+
+    /// Returns a new matrix with the dimensions `(nrows, ncols)`, containing the elements of
+    /// `self` in column-major order.
+    ///
+    /// See [`MatRef::reshape_to_owned`].
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    #[inline]
+    pub fn reshape_to_owned(&self, nrows: usize, ncols: usize) -> Mat<E>
+    where
+        E: Conjugate<Canonical = E>,
+    {
+        self.as_ref().reshape_to_owned(nrows, ncols)
+    }
+
     #[doc(hidden)]
     #[inline(always)]
     pub unsafe fn const_cast(&self) -> MatMut<'_, E> {
@@ -961,6 +1167,152 @@ impl<E: Entity> Mat<E> {
         self.as_mut().fill(constant)
     }
 
+    /// Adds `row` to each row of `self`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn add_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().add_broadcast_row(row)
+    }
+
+    /// Subtracts `row` from each row of `self`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn sub_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().sub_broadcast_row(row)
+    }
+
+    /// Multiplies each column of `self` by the corresponding element of `row`.
+    ///
+    /// # Panics
+    /// Panics if `row.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn mul_broadcast_row<ViewE: Conjugate<Canonical = E>>(&mut self, row: impl AsRowRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().mul_broadcast_row(row)
+    }
+
+    /// Adds `col` to each column of `self`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn add_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().add_broadcast_col(col)
+    }
+
+    /// Subtracts `col` from each column of `self`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn sub_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().sub_broadcast_col(col)
+    }
+
+    /// Multiplies each row of `self` by the corresponding element of `col`.
+    ///
+    /// # Panics
+    /// Panics if `col.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn mul_broadcast_col<ViewE: Conjugate<Canonical = E>>(&mut self, col: impl AsColRef<ViewE>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().mul_broadcast_col(col)
+    }
+
+    /// Returns the permutation that sorts the `j`-th column of `self` in increasing order, with
+    /// the NaN values at the end.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    #[track_caller]
+    pub fn argsort_col(&self, j: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        self.as_ref().argsort_col(j, kind)
+    }
+
+    /// Returns the permutation that sorts the `i`-th row of `self` in increasing order, with the
+    /// NaN values at the end.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    #[track_caller]
+    pub fn argsort_row(&self, i: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        self.as_ref().argsort_row(i, kind)
+    }
+
+    /// Replaces the `i`-th row of `self` by its `perm[i]`-th row, without allocating a copy of
+    /// the matrix.
+    ///
+    /// # Panics
+    /// Panics if `perm.len() != self.nrows()`.
+    #[track_caller]
+    pub fn permute_rows_in_place<I: crate::Index>(&mut self, perm: crate::perm::PermRef<'_, I>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().permute_rows_in_place(perm)
+    }
+
+    /// Replaces the `j`-th column of `self` by its `perm[j]`-th column, without allocating a copy
+    /// of the matrix.
+    ///
+    /// # Panics
+    /// Panics if `perm.len() != self.ncols()`.
+    #[track_caller]
+    pub fn permute_cols_in_place<I: crate::Index>(&mut self, perm: crate::perm::PermRef<'_, I>)
+    where
+        E: ComplexField,
+    {
+        self.as_mut().permute_cols_in_place(perm)
+    }
+
+    /// Sorts the rows of `self` by their `j`-th column in increasing order, with the NaN values
+    /// at the end, and returns the applied permutation.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    #[track_caller]
+    pub fn sort_rows_by_column(&mut self, j: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        self.as_mut().sort_rows_by_column(j, kind)
+    }
+
+    /// Sorts the columns of `self` by their `i`-th row in increasing order, with the NaN values
+    /// at the end, and returns the applied permutation.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    #[track_caller]
+    pub fn sort_cols_by_row(&mut self, i: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        self.as_mut().sort_cols_by_row(i, kind)
+    }
+
     /// Returns a view over the transpose of `self`.
     #[inline]
     #[must_use]
@@ -1151,6 +1503,96 @@ impl<E: Entity> Mat<E> {
         self.as_mut().reverse_rows_and_cols_mut()
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(&self, step: usize) -> MatRef<'_, E> {
+        self.as_ref().step_by_rows(step)
+    }
+
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows_mut(&mut self, step: usize) -> MatMut<'_, E> {
+        self.as_mut().step_by_rows_mut(step)
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(&self, step: usize) -> MatRef<'_, E> {
+        self.as_ref().step_by_cols(step)
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols_mut(&mut self, step: usize) -> MatMut<'_, E> {
+        self.as_mut().step_by_cols_mut(step)
+    }
+
+    /// Returns a view over the rows `row_start, row_start + row_step, ...` and the columns
+    /// `col_start, col_start + col_step, ...` of `self`.
+    ///
+    /// # Panics
+    /// The function panics if any of the following conditions are violated:
+    /// * `row_start <= self.nrows()`.
+    /// * `col_start <= self.ncols()`.
+    /// * `row_step > 0`.
+    /// * `col_step > 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn subsample(
+        &self,
+        row_start: usize,
+        row_step: usize,
+        col_start: usize,
+        col_step: usize,
+    ) -> MatRef<'_, E> {
+        self.as_ref()
+            .subsample(row_start, row_step, col_start, col_step)
+    }
+
+    /// Returns a view over the rows `row_start, row_start + row_step, ...` and the columns
+    /// `col_start, col_start + col_step, ...` of `self`.
+    ///
+    /// # Panics
+    /// The function panics if any of the following conditions are violated:
+    /// * `row_start <= self.nrows()`.
+    /// * `col_start <= self.ncols()`.
+    /// * `row_step > 0`.
+    /// * `col_step > 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn subsample_mut(
+        &mut self,
+        row_start: usize,
+        row_step: usize,
+        col_start: usize,
+        col_step: usize,
+    ) -> MatMut<'_, E> {
+        self.as_mut()
+            .subsample_mut(row_start, row_step, col_start, col_step)
+    }
+
     /// Returns a view over the submatrix starting at indices `(row_start, col_start)`, and with
     /// dimensions `(nrows, ncols)`.
     ///
@@ -1617,6 +2059,110 @@ impl<E: Entity> Mat<E> {
         self.as_ref().to_owned()
     }
 
+    /// Returns an owning [`Mat`] whose `k`-th row is the `indices[k]`-th row of `self`.
+    ///
+    /// # Panics
+    /// Panics if any of the indices is out of bounds.
+    #[track_caller]
+    pub fn gather_rows(&self, indices: &[usize]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().gather_rows(indices)
+    }
+
+    /// Returns an owning [`Mat`] whose `k`-th column is the `indices[k]`-th column of `self`.
+    ///
+    /// # Panics
+    /// Panics if any of the indices is out of bounds.
+    #[track_caller]
+    pub fn gather_cols(&self, indices: &[usize]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().gather_cols(indices)
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` for which `mask` is `true`, in their
+    /// original order.
+    ///
+    /// # Panics
+    /// Panics if `mask.len() != self.nrows()`.
+    #[track_caller]
+    pub fn select_rows(&self, mask: &[bool]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().select_rows(mask)
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` for which `mask` is `true`, in
+    /// their original order.
+    ///
+    /// # Panics
+    /// Panics if `mask.len() != self.ncols()`.
+    #[track_caller]
+    pub fn select_cols(&self, mask: &[bool]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().select_cols(mask)
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` in reverse order.
+    #[inline]
+    pub fn reverse_rows_to_owned(&self) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().reverse_rows_to_owned()
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` in reverse order.
+    #[inline]
+    pub fn reverse_cols_to_owned(&self) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().reverse_cols_to_owned()
+    }
+
+    /// Returns an owning [`Mat`] containing `self` rotated by `k` quarter turns counterclockwise,
+    /// or clockwise if `k` is negative.
+    ///
+    /// See [`MatRef::rot90`] for the conventions.
+    #[inline]
+    pub fn rot90(&self, k: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().rot90(k)
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` shifted circularly by `shift`
+    /// positions.
+    ///
+    /// See [`MatRef::roll_rows`] for the conventions.
+    #[inline]
+    pub fn roll_rows(&self, shift: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().roll_rows(shift)
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` shifted circularly by `shift`
+    /// positions.
+    ///
+    /// See [`MatRef::roll_cols`] for the conventions.
+    #[inline]
+    pub fn roll_cols(&self, shift: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().roll_cols(shift)
+    }
+
     /// Returns `true` if any of the elements is NaN, otherwise returns `false`.
     #[inline]
     pub fn has_nan(&self) -> bool
@@ -1635,6 +2181,116 @@ impl<E: Entity> Mat<E> {
         self.as_ref().is_all_finite()
     }
 
+    /// Returns a mask whose entries are `true` where the corresponding element of `self` is NaN.
+    #[inline]
+    pub fn is_nan(&self) -> super::Mask
+    where
+        E: ComplexField,
+    {
+        self.as_ref().is_nan()
+    }
+
+    /// Returns a mask whose entries are `true` where the corresponding element of `self` is
+    /// finite.
+    #[inline]
+    pub fn is_finite(&self) -> super::Mask
+    where
+        E: ComplexField,
+    {
+        self.as_ref().is_finite()
+    }
+
+    /// Returns an owning [`Col`] containing the elements of `self` for which `mask` is `true`, in
+    /// column-major order.
+    ///
+    /// # Panics
+    /// Panics if `mask` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn select(&self, mask: &super::Mask) -> Col<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        self.as_ref().select(mask)
+    }
+
+    /// Returns the position and value of the minimum of `self`.
+    ///
+    /// See [`stats::argmin`](crate::stats::argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmin(&self, nan: crate::stats::NanHandling) -> Option<(usize, usize, E)>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmin(nan)
+    }
+
+    /// Returns the column index of the minimum of each row of `self`.
+    ///
+    /// See [`stats::col_argmin`](crate::stats::col_argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmin_of_rows(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmin_of_rows(nan)
+    }
+
+    /// Returns the row index of the minimum of each column of `self`.
+    ///
+    /// See [`stats::row_argmin`](crate::stats::row_argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmin_of_cols(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmin_of_cols(nan)
+    }
+
+    /// Returns the position and value of the maximum of `self`.
+    ///
+    /// See [`stats::argmax`](crate::stats::argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmax(&self, nan: crate::stats::NanHandling) -> Option<(usize, usize, E)>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmax(nan)
+    }
+
+    /// Returns the column index of the maximum of each row of `self`.
+    ///
+    /// See [`stats::col_argmax`](crate::stats::col_argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmax_of_rows(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmax_of_rows(nan)
+    }
+
+    /// Returns the row index of the maximum of each column of `self`.
+    ///
+    /// See [`stats::row_argmax`](crate::stats::row_argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmax_of_cols(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        self.as_ref().argmax_of_cols(nan)
+    }
+
     /// Returns the maximum norm of `self`.
     #[inline]
     pub fn norm_max(&self) -> E::Real
@@ -582,6 +582,98 @@ impl<'a, E: Entity> MatRef<'a, E> {
         unsafe { Self::__from_raw_parts(ptr, nrows, ncols, row_stride, col_stride) }
     }
 
+    /// Returns a view over every `step`-th row of `self`, starting with the first row.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// let matrix = mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+    /// let view = matrix.as_ref();
+    /// let even_rows = view.step_by_rows(2);
+    ///
+    /// let expected = mat![[1.0, 2.0], [5.0, 6.0]];
+    /// assert_eq!(expected.as_ref(), even_rows);
+    /// ```
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_rows(self, step: usize) -> Self {
+        assert!(step > 0);
+        let nrows = self.nrows().msrv_div_ceil(step);
+        let ncols = self.ncols();
+        let row_stride = self.row_stride().wrapping_mul(step as isize);
+        let col_stride = self.col_stride();
+        unsafe { Self::__from_raw_parts(self.as_ptr(), nrows, ncols, row_stride, col_stride) }
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// let matrix = mat![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+    /// let view = matrix.as_ref();
+    /// let even_cols = view.step_by_cols(2);
+    ///
+    /// let expected = mat![[1.0, 3.0], [4.0, 6.0]];
+    /// assert_eq!(expected.as_ref(), even_cols);
+    /// ```
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(self, step: usize) -> Self {
+        self.transpose().step_by_rows(step).transpose()
+    }
+
+    /// Returns a view over the rows `row_start, row_start + row_step, ...` and the columns
+    /// `col_start, col_start + col_step, ...` of `self`.
+    ///
+    /// This can be used, for example, to extract one channel of a matrix whose rows interleave
+    /// several channels, without copying.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// // the rows alternate between two channels
+    /// let matrix = mat![[1.0, 2.0], [-1.0, -2.0], [3.0, 4.0], [-3.0, -4.0]];
+    /// let view = matrix.as_ref();
+    /// let second_channel = view.subsample(1, 2, 0, 1);
+    ///
+    /// let expected = mat![[-1.0, -2.0], [-3.0, -4.0]];
+    /// assert_eq!(expected.as_ref(), second_channel);
+    /// ```
+    ///
+    /// # Panics
+    /// The function panics if any of the following conditions are violated:
+    /// * `row_start <= self.nrows()`.
+    /// * `col_start <= self.ncols()`.
+    /// * `row_step > 0`.
+    /// * `col_step > 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn subsample(
+        self,
+        row_start: usize,
+        row_step: usize,
+        col_start: usize,
+        col_step: usize,
+    ) -> Self {
+        self.split_at(row_start, col_start)
+            .3
+            .step_by_rows(row_step)
+            .step_by_cols(col_step)
+    }
+
     /// Returns a view over the submatrix starting at indices `(row_start, col_start)`, and with
     /// dimensions `(nrows, ncols)`.
     ///
@@ -871,6 +963,258 @@ impl<'a, E: Entity> MatRef<'a, E> {
         mat
     }
 
+    /// Returns an owning [`Mat`] whose `k`-th row is the `indices[k]`-th row of `self`.
+    ///
+    /// The indices may be repeated and don't need to be sorted.
+    ///
+    /// # Panics
+    /// Panics if any of the indices is out of bounds.
+    #[track_caller]
+    pub fn gather_rows(&self, indices: &[usize]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        let nrows = self.nrows();
+        for &i in indices {
+            assert!(i < nrows);
+        }
+        let mut mat = Mat::new();
+        mat.resize_with(
+            indices.len(),
+            self.ncols(),
+            #[inline(always)]
+            |row, col| unsafe {
+                self.read_unchecked(*indices.get_unchecked(row), col)
+                    .canonicalize()
+            },
+        );
+        mat
+    }
+
+    /// Returns an owning [`Mat`] whose `k`-th column is the `indices[k]`-th column of `self`.
+    ///
+    /// The indices may be repeated and don't need to be sorted.
+    ///
+    /// # Panics
+    /// Panics if any of the indices is out of bounds.
+    #[track_caller]
+    pub fn gather_cols(&self, indices: &[usize]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        let ncols = self.ncols();
+        for &j in indices {
+            assert!(j < ncols);
+        }
+        let mut mat = Mat::new();
+        mat.resize_with(
+            self.nrows(),
+            indices.len(),
+            #[inline(always)]
+            |row, col| unsafe {
+                self.read_unchecked(row, *indices.get_unchecked(col))
+                    .canonicalize()
+            },
+        );
+        mat
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` for which `mask` is `true`, in
+    /// their original order.
+    ///
+    /// # Panics
+    /// Panics if `mask.len() != self.nrows()`.
+    #[track_caller]
+    pub fn select_rows(&self, mask: &[bool]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        assert!(mask.len() == self.nrows());
+        self.gather_rows(&mask_indices(mask))
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` for which `mask` is `true`, in
+    /// their original order.
+    ///
+    /// # Panics
+    /// Panics if `mask.len() != self.ncols()`.
+    #[track_caller]
+    pub fn select_cols(&self, mask: &[bool]) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        assert!(mask.len() == self.ncols());
+        self.gather_cols(&mask_indices(mask))
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` in reverse order.
+    ///
+    /// See [`MatRef::reverse_rows`] for a view that doesn't copy the data.
+    #[inline]
+    pub fn reverse_rows_to_owned(&self) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        (*self).reverse_rows().to_owned()
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` in reverse order.
+    ///
+    /// See [`MatRef::reverse_cols`] for a view that doesn't copy the data.
+    #[inline]
+    pub fn reverse_cols_to_owned(&self) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        (*self).reverse_cols().to_owned()
+    }
+
+    /// Returns an owning [`Mat`] containing `self` rotated by `k` quarter turns counterclockwise,
+    /// or clockwise if `k` is negative.
+    ///
+    /// This matches `numpy.rot90`, so that the first row of `rot90(1)` is the last column of
+    /// `self`.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// let a = mat![[1.0, 2.0], [3.0, 4.0], [5.0, 6.0]];
+    /// assert!(a.rot90(1) == mat![[2.0, 4.0, 6.0], [1.0, 3.0, 5.0]]);
+    /// assert!(a.rot90(-1) == mat![[5.0, 3.0, 1.0], [6.0, 4.0, 2.0]]);
+    /// assert!(a.rot90(2) == mat![[6.0, 5.0], [4.0, 3.0], [2.0, 1.0]]);
+    /// ```
+    pub fn rot90(&self, k: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        let this = *self;
+        match k.rem_euclid(4) {
+            0 => this.to_owned(),
+            1 => this.transpose().reverse_rows().to_owned(),
+            2 => this.reverse_rows_and_cols().to_owned(),
+            _ => this.transpose().reverse_cols().to_owned(),
+        }
+    }
+
+    /// Returns an owning [`Mat`] containing the rows of `self` shifted circularly by `shift`
+    /// positions, so that the `i`-th row of `self` is the `(i + shift) mod nrows`-th row of the
+    /// result. A negative `shift` moves the rows up.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// let a = mat![[1.0], [2.0], [3.0]];
+    /// assert!(a.roll_rows(1) == mat![[3.0], [1.0], [2.0]]);
+    /// assert!(a.roll_rows(-1) == mat![[2.0], [3.0], [1.0]]);
+    /// ```
+    #[doc(alias = "circshift")]
+    pub fn roll_rows(&self, shift: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        let m = self.nrows();
+        let mut out = Mat::<E::Canonical>::zeros(m, self.ncols());
+        if m == 0 {
+            return out;
+        }
+        let shift = shift.rem_euclid(m as isize) as usize;
+        // the rows are moved as two blocks
+        out.as_mut()
+            .subrows_mut(shift, m - shift)
+            .copy_from((*self).subrows(0, m - shift));
+        out.as_mut()
+            .subrows_mut(0, shift)
+            .copy_from((*self).subrows(m - shift, shift));
+        out
+    }
+
+    /// Returns an owning [`Mat`] containing the columns of `self` shifted circularly by `shift`
+    /// positions, so that the `j`-th column of `self` is the `(j + shift) mod ncols`-th column of
+    /// the result. A negative `shift` moves the columns to the left.
+    #[doc(alias = "circshift")]
+    pub fn roll_cols(&self, shift: isize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        let n = self.ncols();
+        let mut out = Mat::<E::Canonical>::zeros(self.nrows(), n);
+        if n == 0 {
+            return out;
+        }
+        let shift = shift.rem_euclid(n as isize) as usize;
+        // the columns are moved as two blocks
+        out.as_mut()
+            .subcols_mut(shift, n - shift)
+            .copy_from((*self).subcols(0, n - shift));
+        out.as_mut()
+            .subcols_mut(0, shift)
+            .copy_from((*self).subcols(n - shift, shift));
+        out
+    }
+
+    /// Returns a view over the same data with the dimensions `(nrows, ncols)`, if the matrix is
+    /// stored contiguously in column-major order, otherwise returns `None`.
+    ///
+    /// The elements keep their column-major order, so that reshaping an `m×n` matrix to
+    /// `(m * n, 1)` gives its vectorization, and reshaping back gives the original matrix. See
+    /// [`MatRef::reshape_to_owned`] for a copying fallback that works with any strides.
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::mat;
+    ///
+    /// let a = mat![[1.0, 3.0, 5.0], [2.0, 4.0, 6.0]];
+    /// let b = a.as_ref().try_reshape(3, 2).unwrap();
+    /// assert!(b == mat![[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]]);
+    ///
+    /// // the transpose is not stored in column-major order
+    /// assert!(a.transpose().try_reshape(2, 3).is_none());
+    /// ```
+    #[track_caller]
+    #[inline]
+    pub fn try_reshape(self, nrows: usize, ncols: usize) -> Option<Self> {
+        super::reshape_assert(self.nrows(), self.ncols(), nrows, ncols);
+        if super::is_column_major_contiguous(
+            self.nrows(),
+            self.ncols(),
+            self.row_stride(),
+            self.col_stride(),
+        ) {
+            Some(unsafe { from_raw_parts(self.as_ptr(), nrows, ncols, 1, nrows as isize) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns a new matrix with the dimensions `(nrows, ncols)`, containing the elements of
+    /// `self` in column-major order.
+    ///
+    /// This is the same as [`MatRef::try_reshape`] followed by a copy, but works with any
+    /// strides.
+    ///
+    /// # Panics
+    /// Panics if `nrows * ncols != self.nrows() * self.ncols()`.
+    #[track_caller]
+    pub fn reshape_to_owned(&self, nrows: usize, ncols: usize) -> Mat<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        super::reshape_assert(self.nrows(), self.ncols(), nrows, ncols);
+        if let Some(this) = self.try_reshape(nrows, ncols) {
+            return this.to_owned();
+        }
+        let m = self.nrows();
+        Mat::from_fn(nrows, ncols, |i, j| {
+            let k = i + j * nrows;
+            unsafe { self.read_unchecked(k % m, k / m).canonicalize() }
+        })
+    }
+
     /// Returns `true` if any of the elements is NaN, otherwise returns `false`.
     #[inline]
     pub fn has_nan(&self) -> bool
@@ -897,6 +1241,165 @@ impl<'a, E: Entity> MatRef<'a, E> {
         all_finite
     }
 
+    /// Returns a mask whose entries are `true` where the corresponding element of `self` is NaN.
+    #[inline]
+    pub fn is_nan(&self) -> super::Mask
+    where
+        E: ComplexField,
+    {
+        super::Mask::from_fn(self.nrows(), self.ncols(), |i, j| {
+            self.read(i, j).faer_is_nan()
+        })
+    }
+
+    /// Returns a mask whose entries are `true` where the corresponding element of `self` is
+    /// finite.
+    #[inline]
+    pub fn is_finite(&self) -> super::Mask
+    where
+        E: ComplexField,
+    {
+        super::Mask::from_fn(self.nrows(), self.ncols(), |i, j| {
+            self.read(i, j).faer_is_finite()
+        })
+    }
+
+    /// Returns an owning [`Col`] containing the elements of `self` for which `mask` is `true`, in
+    /// column-major order.
+    ///
+    /// # Panics
+    /// Panics if `mask` doesn't have the same dimensions as `self`.
+    #[track_caller]
+    pub fn select(&self, mask: &super::Mask) -> Col<E::Canonical>
+    where
+        E: Conjugate,
+    {
+        assert!(all(
+            mask.nrows() == self.nrows(),
+            mask.ncols() == self.ncols()
+        ));
+        let indices = mask_indices(mask.as_slice());
+        let m = self.nrows();
+        Col::from_fn(indices.len(), |k| {
+            let idx = indices[k];
+            unsafe { self.read_unchecked(idx % m, idx / m).canonicalize() }
+        })
+    }
+
+    /// Returns the position and value of the minimum of `self`.
+    ///
+    /// See [`stats::argmin`](crate::stats::argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmin(&self, nan: crate::stats::NanHandling) -> Option<(usize, usize, E)>
+    where
+        E: RealField,
+    {
+        crate::stats::argmin((*self).rb(), nan)
+    }
+
+    /// Returns the position and value of the maximum of `self`.
+    ///
+    /// See [`stats::argmax`](crate::stats::argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    #[inline]
+    pub fn argmax(&self, nan: crate::stats::NanHandling) -> Option<(usize, usize, E)>
+    where
+        E: RealField,
+    {
+        crate::stats::argmax((*self).rb(), nan)
+    }
+
+    /// Returns the column index of the minimum of each row of `self`.
+    ///
+    /// See [`stats::col_argmin`](crate::stats::col_argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn argmin_of_rows(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        let mut out = alloc::vec![None; self.nrows()];
+        crate::stats::col_argmin(&mut out, (*self).rb(), nan);
+        out
+    }
+
+    /// Returns the row index of the minimum of each column of `self`.
+    ///
+    /// See [`stats::row_argmin`](crate::stats::row_argmin) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn argmin_of_cols(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        let mut out = alloc::vec![None; self.ncols()];
+        crate::stats::row_argmin(&mut out, (*self).rb(), nan);
+        out
+    }
+
+    /// Returns the column index of the maximum of each row of `self`.
+    ///
+    /// See [`stats::col_argmax`](crate::stats::col_argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn argmax_of_rows(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        let mut out = alloc::vec![None; self.nrows()];
+        crate::stats::col_argmax(&mut out, (*self).rb(), nan);
+        out
+    }
+
+    /// Returns the row index of the maximum of each column of `self`.
+    ///
+    /// See [`stats::row_argmax`](crate::stats::row_argmax) for the conventions.
+    #[cfg(feature = "rand")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "rand")))]
+    pub fn argmax_of_cols(&self, nan: crate::stats::NanHandling) -> alloc::vec::Vec<Option<usize>>
+    where
+        E: RealField,
+    {
+        let mut out = alloc::vec![None; self.ncols()];
+        crate::stats::row_argmax(&mut out, (*self).rb(), nan);
+        out
+    }
+
+    /// Returns the permutation that sorts the `j`-th column of `self` in increasing order, with
+    /// the NaN values at the end.
+    ///
+    /// The `k`-th forward index of the permutation is the row index of the `k`-th smallest value,
+    /// so that applying it to the rows of `self`, e.g., with
+    /// [`MatMut::permute_rows_in_place`], sorts them by their `j`-th column.
+    ///
+    /// # Panics
+    /// Panics if `j >= self.ncols()`.
+    #[track_caller]
+    pub fn argsort_col(&self, j: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        super::sorting::argsort(self.col(j), kind)
+    }
+
+    /// Returns the permutation that sorts the `i`-th row of `self` in increasing order, with the
+    /// NaN values at the end.
+    ///
+    /// See [`MatRef::argsort_col`] for the conventions.
+    ///
+    /// # Panics
+    /// Panics if `i >= self.nrows()`.
+    #[track_caller]
+    pub fn argsort_row(&self, i: usize, kind: SortKind) -> crate::perm::Perm<usize>
+    where
+        E: RealField,
+    {
+        super::sorting::argsort(self.row(i).transpose(), kind)
+    }
+
     /// Returns the maximum norm of `self`.
     #[inline]
     pub fn norm_max(&self) -> E::Real
@@ -1061,6 +1564,82 @@ impl<'a, E: Entity> MatRef<'a, E> {
             .par_col_chunks(chunk_size)
             .map(|chunk| chunk.transpose())
     }
+
+    /// Returns a value that formats `self` according to the given options, which can be used with
+    /// `format!` and similar macros.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{mat, mat::FormatOptions};
+    ///
+    /// let a = mat![[1.0, 2.5], [-3.0, 4.0f64]];
+    /// let s = format!("{}", a.fmt_with(FormatOptions::new().precision(1)));
+    /// assert_eq!(s, "[[ 1.0, 2.5],\n [-3.0, 4.0]]");
+    /// ```
+    #[inline]
+    pub fn fmt_with(self, options: super::FormatOptions) -> super::MatFormat<'a, E> {
+        super::MatFormat::new(self, options)
+    }
+
+    /// Returns a new matrix with the entries of `self` cast to `T`. The imaginary parts are
+    /// discarded when casting complex values to a real type.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{complex_native::c32, mat};
+    ///
+    /// let a = mat![[1.0, 2.5], [-3.0, 4.0f64]];
+    /// let b = a.as_ref().cast::<f32>();
+    /// let c = a.as_ref().cast::<c32>();
+    ///
+    /// assert_eq!(b, mat![[1.0, 2.5], [-3.0, 4.0f32]]);
+    /// assert_eq!(c.read(1, 0), c32::new(-3.0, 0.0));
+    /// ```
+    #[inline]
+    pub fn cast<T: Entity>(self) -> Mat<T>
+    where
+        E: super::Cast<T>,
+    {
+        super::cast::cast(self, super::ImagPolicy::Discard)
+    }
+
+    /// Returns a new matrix with the entries of `self` cast to `T`, where `policy` specifies how
+    /// the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(self, policy: super::ImagPolicy) -> Mat<T>
+    where
+        E: super::Cast<T>,
+    {
+        super::cast::cast(self, policy)
+    }
+
+    /// Writes the entries of `self` cast to `T` into `out`. The imaginary parts are discarded
+    /// when casting complex values to a real type.
+    ///
+    /// # Panics
+    /// The function panics if `out` doesn't have the same dimensions as `self`.
+    #[inline]
+    #[track_caller]
+    pub fn cast_into<T: Entity>(self, out: MatMut<'_, T>)
+    where
+        E: super::Cast<T>,
+    {
+        super::cast::cast_into(out, self, super::ImagPolicy::Discard)
+    }
+
+    /// Writes the entries of `self` cast to `T` into `out`, where `policy` specifies how the
+    /// imaginary parts are handled when casting complex values to a real type.
+    ///
+    /// # Panics
+    /// The function panics if `out` doesn't have the same dimensions as `self`.
+    #[inline]
+    #[track_caller]
+    pub fn cast_into_with<T: Entity>(self, out: MatMut<'_, T>, policy: super::ImagPolicy)
+    where
+        E: super::Cast<T>,
+    {
+        super::cast::cast_into(out, self, policy)
+    }
 }
 
 impl<'a, E: RealField> MatRef<'a, num_complex::Complex<E>> {
@@ -1406,3 +1985,11 @@ pub fn from_repeated_row<E: Entity>(row: RowRef<'_, E>, nrows: usize) -> MatRef<
 pub fn from_ref<E: Entity>(value: GroupFor<E, &E::Unit>) -> MatRef<'_, E> {
     from_repeated_ref(value, 1, 1)
 }
+
+// returns the indices of the `true` entries of `mask`
+fn mask_indices(mask: &[bool]) -> alloc::vec::Vec<usize> {
+    mask.iter()
+        .enumerate()
+        .filter_map(|(i, &selected)| if selected { Some(i) } else { None })
+        .collect()
+}
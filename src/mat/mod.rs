@@ -155,6 +155,53 @@ mod matown;
 pub use matown::Mat;
 
 pub(crate) mod matalloc;
+pub use matalloc::{set_allocator, AllocatorAlreadySet};
+
+pub(crate) mod concat;
+pub use concat::{block, block_diag, hstack, pad, tile, vstack};
+
+mod structured;
+pub use structured::{Lower, SymMat, TriMat, Triangle, Upper};
+
+mod packed;
+pub use packed::{PackedCholesky, PackedSymMat, PackedTriMat};
+
+mod sorting;
+pub use sorting::SortKind;
+
+mod format;
+pub use format::{Alignment, FormatOptions, MatFormat, Notation};
+
+mod fixed;
+pub use fixed::{FixedCol, FixedMat};
+
+mod cast;
+pub use cast::{Cast, ImagPolicy};
+
+mod mask;
+pub use mask::Mask;
+
+mod compare;
+
+#[track_caller]
+#[inline]
+fn reshape_assert(nrows: usize, ncols: usize, new_nrows: usize, new_ncols: usize) {
+    assert!(usize::checked_mul(new_nrows, new_ncols) == Some(nrows * ncols));
+}
+
+// whether the matrix with the given dimensions and strides can be viewed as a contiguous slice
+// in column-major order
+#[inline]
+fn is_column_major_contiguous(
+    nrows: usize,
+    ncols: usize,
+    row_stride: isize,
+    col_stride: isize,
+) -> bool {
+    nrows == 0
+        || ncols == 0
+        || ((nrows == 1 || row_stride == 1) && (ncols == 1 || col_stride == nrows as isize))
+}
 
 #[track_caller]
 #[inline]
@@ -227,4 +274,220 @@ mod tests {
         from_mut::<f64>(&mut c).fill(3.0);
         assert!(c == 3.0);
     }
+
+    #[test]
+    fn test_reshape() {
+        let mut data = (0..12).map(|k| k as f64).collect::<alloc::vec::Vec<_>>();
+        let x = from_column_major_slice::<f64>(&data, 3, 4);
+        let v = x.try_reshape(12, 1).unwrap();
+        for k in 0..12 {
+            assert!(v.read(k, 0) == k as f64);
+        }
+        assert!(x.try_reshape(2, 6).unwrap() == x.reshape_to_owned(2, 6));
+        assert!(x.try_reshape(2, 6).unwrap().try_reshape(3, 4).unwrap() == x);
+
+        // strided views are copied in column-major order
+        let t = x.transpose();
+        assert!(t.try_reshape(12, 1).is_none());
+        let tv = t.reshape_to_owned(12, 1);
+        for k in 0..12 {
+            assert!(tv.read(k, 0) == t.read(k % 4, k / 4));
+        }
+        assert!(x.subcols(1, 2).try_reshape(6, 1).is_some());
+        assert!(x.subrows(0, 2).try_reshape(8, 1).is_none());
+        assert!(x.subrows(1, 1).try_reshape(4, 1).is_none());
+        assert!(x.subrows(0, 0).try_reshape(0, 7).is_some());
+
+        from_column_major_slice_mut::<f64>(&mut data, 3, 4)
+            .try_reshape_mut(6, 2)
+            .unwrap()
+            .write(5, 1, -1.0);
+        assert!(data[11] == -1.0);
+
+        let m = Mat::from_fn(3, 4, |i, j| (i + 3 * j) as f64);
+        assert!(m == x.reshape_to_owned(2, 6));
+    }
+
+    #[test]
+    fn test_broadcast() {
+        use crate::complex_native::c64;
+
+        let a = Mat::from_fn(3, 4, |i, j| c64::new(i as f64, j as f64 - 1.0));
+        let row = crate::Row::from_fn(4, |j| c64::new(2.0 * j as f64, 1.0));
+        let col = crate::Col::from_fn(3, |i| c64::new(1.0, -(i as f64)));
+
+        let mut x = a.clone();
+        x.add_broadcast_row(&row);
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) + row.read(j)));
+        let mut x = a.clone();
+        x.sub_broadcast_row(row.as_ref().conjugate());
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) - row.read(j).conj()));
+        let mut x = a.clone();
+        x.mul_broadcast_row(&row);
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) * row.read(j)));
+
+        let mut x = a.clone();
+        x.add_broadcast_col(&col);
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) + col.read(i)));
+        let mut x = a.clone();
+        x.as_mut().sub_broadcast_col(&col);
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) - col.read(i)));
+        let mut x = a.clone();
+        x.as_mut()
+            .transpose_mut()
+            .mul_broadcast_row(col.as_ref().transpose());
+        assert!(x == Mat::from_fn(3, 4, |i, j| a.read(i, j) * col.read(i)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_reshape_mismatch() {
+        let x = Mat::<f64>::zeros(3, 4);
+        x.try_reshape(5, 2);
+    }
+
+    #[test]
+    fn test_select() {
+        let a = Mat::from_fn(5, 4, |i, j| (10 * i + j) as f64);
+        let rows = a.select_rows(&[true, false, false, true, true]);
+        assert!(rows == a.gather_rows(&[0, 3, 4]));
+        assert!(all(rows.nrows() == 3, rows.ncols() == 4));
+        assert!(rows.read(1, 2) == 32.0);
+
+        let cols = a.as_ref().select_cols(&[false, true, false, true]);
+        assert!(cols == a.gather_cols(&[1, 3]));
+        assert!(cols.read(4, 0) == 41.0);
+
+        // gathered indices may be repeated and unsorted
+        let g = a.as_ref().transpose().gather_cols(&[4, 0, 4]);
+        assert!(g.col(0) == a.row(4).transpose());
+        assert!(g.col(1) == a.row(0).transpose());
+        assert!(g.col(2) == a.row(4).transpose());
+
+        let empty = a.select_rows(&[false; 5]);
+        assert!(all(empty.nrows() == 0, empty.ncols() == 4));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_gather_out_of_bounds() {
+        let a = Mat::<f64>::zeros(3, 2);
+        a.gather_rows(&[0, 3]);
+    }
+
+    #[test]
+    fn test_rot_roll() {
+        use crate::complex_native::c64;
+
+        let a = Mat::from_fn(4, 3, |i, j| c64::new(i as f64, j as f64));
+        assert!(a.rot90(4) == a);
+        assert!(a.rot90(1).rot90(-1) == a);
+        assert!(a.rot90(2) == a.reverse_rows_to_owned().reverse_cols_to_owned());
+        assert!(a.rot90(3) == a.rot90(-1));
+
+        for shift in [-5isize, -1, 0, 2, 4, 7] {
+            let r = a.roll_rows(shift);
+            let c = a.roll_cols(shift);
+            for i in 0..4 {
+                for j in 0..3 {
+                    let ri = (i as isize + shift).rem_euclid(4) as usize;
+                    let cj = (j as isize + shift).rem_euclid(3) as usize;
+                    assert!(all(
+                        r.read(ri, j) == a.read(i, j),
+                        c.read(i, cj) == a.read(i, j)
+                    ));
+                }
+            }
+        }
+
+        // conjugate views are copied with the conjugate values
+        let r = a.conjugate().roll_rows(1);
+        assert!(r.read(1, 2) == a.read(0, 2).conj());
+
+        let empty = Mat::<f64>::zeros(0, 3);
+        assert!(all(
+            empty.roll_rows(2).nrows() == 0,
+            empty.roll_cols(-1).ncols() == 3
+        ));
+    }
+
+    #[test]
+    fn test_push_truncate() {
+        let a = Mat::<f64>::from_fn(5, 3, |i, j| (10 * i + j) as f64);
+
+        let mut rows = Mat::<f64>::new();
+        for i in 0..a.nrows() {
+            rows.push_row(a.row(i));
+        }
+        assert!(rows == a);
+
+        let mut cols = Mat::<f64>::new();
+        cols.push_col(a.col(0));
+        cols.append_cols(a.get(.., 1..));
+        assert!(cols == a);
+
+        // the capacity grows geometrically
+        let mut b = Mat::<f64>::new();
+        let mut reallocations = 0;
+        for i in 0..100 {
+            let cap = b.col_capacity();
+            b.push_col(Col::from_fn(2, |k| (i + k) as f64).as_ref());
+            if b.col_capacity() != cap {
+                reallocations += 1;
+            }
+        }
+        assert!(all(b.ncols() == 100, reallocations <= 8));
+        assert!(b.read(1, 99) == 100.0);
+
+        let cap = (rows.row_capacity(), rows.col_capacity());
+        rows.truncate_rows(2);
+        rows.truncate_cols(1);
+        assert!(rows == a.get(..2, ..1));
+        assert!((rows.row_capacity(), rows.col_capacity()) == cap);
+        rows.push_row(crate::row![7.0].as_ref());
+        assert!(rows.read(2, 0) == 7.0);
+    }
+
+    #[test]
+    fn test_step_by() {
+        let a = Mat::<f64>::from_fn(7, 5, |i, j| (10 * i + j) as f64);
+
+        assert!(a.step_by_rows(3) == Mat::from_fn(3, 5, |i, j| a.read(3 * i, j)));
+        assert!(a.step_by_cols(2) == Mat::from_fn(7, 3, |i, j| a.read(i, 2 * j)));
+        assert!(a.step_by_rows(1) == a);
+        assert!(a.step_by_rows(10).nrows() == 1);
+        assert!(a.subsample(1, 2, 2, 2) == Mat::from_fn(3, 2, |i, j| a.read(1 + 2 * i, 2 + 2 * j)));
+        let empty = a.subsample(7, 2, 5, 3);
+        assert!(all(empty.nrows() == 0, empty.ncols() == 0));
+        // strided views compose with the other view transformations
+        assert!(
+            a.reverse_rows().step_by_rows(2) == Mat::from_fn(4, 5, |i, j| a.read(6 - 2 * i, j))
+        );
+
+        let mut b = a.clone();
+        b.step_by_cols_mut(4).fill_zero();
+        assert!(b == Mat::from_fn(7, 5, |i, j| if j % 4 == 0 { 0.0 } else { a.read(i, j) }));
+
+        let c = Col::<f64>::from_fn(5, |i| i as f64);
+        assert!(c.step_by_rows(2) == Col::from_fn(3, |i| (2 * i) as f64));
+        let r = Row::<f64>::from_fn(5, |j| j as f64);
+        assert!(r.step_by_cols(4) == Row::from_fn(2, |j| (4 * j) as f64));
+    }
+
+    #[test]
+    fn test_allocator_already_set() {
+        struct Unused;
+
+        // SAFETY: never used, since the allocator is fixed after the first matrix allocation
+        unsafe impl core::alloc::GlobalAlloc for Unused {
+            unsafe fn alloc(&self, _: core::alloc::Layout) -> *mut u8 {
+                core::ptr::null_mut()
+            }
+            unsafe fn dealloc(&self, _: *mut u8, _: core::alloc::Layout) {}
+        }
+
+        let a = Mat::<f64>::zeros(4, 4);
+        assert!(set_allocator(&Unused) == Err(AllocatorAlreadySet));
+        drop(a);
+    }
 }
@@ -0,0 +1,706 @@
+use super::structured::{SymMat, TriMat, Triangle};
+use crate::{
+    assert,
+    linalg::cholesky::llt::CholeskyError,
+    mat::{Mat, MatMut, MatRef},
+    sparse::linalg::solvers::SpSolverCore,
+    utils::{slice::SliceGroup, vec::VecGroup},
+    ComplexField, Conj, Conjugate, Entity, Side,
+};
+use reborrow::*;
+
+// number of stored entries of a packed triangle of dimension `dim`
+#[track_caller]
+#[inline]
+fn packed_len(dim: usize) -> usize {
+    let len = dim.checked_mul(dim + 1).map(|len| len / 2);
+    assert!(len.is_some());
+    len.unwrap()
+}
+
+// index of the first stored entry of the `j`-th column, following the column-major layout of
+// LAPACK
+#[inline]
+fn col_start<T: Triangle>(dim: usize, j: usize) -> usize {
+    match T::SIDE {
+        Side::Lower => j * (2 * dim - j + 1) / 2,
+        Side::Upper => j * (j + 1) / 2,
+    }
+}
+
+// range of the stored rows of the `j`-th column
+#[inline]
+fn col_rows<T: Triangle>(dim: usize, j: usize) -> core::ops::Range<usize> {
+    match T::SIDE {
+        Side::Lower => j..dim,
+        Side::Upper => 0..j + 1,
+    }
+}
+
+#[inline]
+fn is_stored<T: Triangle>(i: usize, j: usize) -> bool {
+    match T::SIDE {
+        Side::Lower => i >= j,
+        Side::Upper => i <= j,
+    }
+}
+
+// index of the stored entry `(i, j)`, which must be in the stored half
+#[inline]
+fn packed_idx<T: Triangle>(dim: usize, i: usize, j: usize) -> usize {
+    col_start::<T>(dim, j) + i - col_rows::<T>(dim, j).start
+}
+
+#[derive(Clone, Debug)]
+struct Packed<E: Entity> {
+    values: VecGroup<E>,
+    dim: usize,
+}
+
+impl<E: ComplexField> Packed<E> {
+    #[track_caller]
+    fn zeros(dim: usize) -> Self {
+        let mut values = VecGroup::new();
+        values.resize(packed_len(dim), E::faer_zero().faer_into_units());
+        Self { values, dim }
+    }
+
+    #[track_caller]
+    fn from_fn<T: Triangle>(dim: usize, mut f: impl FnMut(usize, usize) -> E) -> Self {
+        let mut this = Self::zeros(dim);
+        let mut values = this.values.as_slice_mut();
+        let mut idx = 0;
+        for j in 0..dim {
+            for i in col_rows::<T>(dim, j) {
+                values.write(idx, f(i, j));
+                idx += 1;
+            }
+        }
+        this
+    }
+
+    #[inline]
+    #[track_caller]
+    fn read<T: Triangle>(&self, i: usize, j: usize) -> E {
+        self.values.as_slice().read(packed_idx::<T>(self.dim, i, j))
+    }
+
+    #[inline]
+    #[track_caller]
+    fn write<T: Triangle>(&mut self, i: usize, j: usize, value: E) {
+        self.values
+            .as_slice_mut()
+            .write(packed_idx::<T>(self.dim, i, j), value)
+    }
+}
+
+/// Square triangular matrix in packed storage, whose structure is part of its type.
+///
+/// Only the `n(n + 1) / 2` entries of the half selected by `T` (with the diagonal) are stored,
+/// column by column, which matches the packed format of LAPACK (`'L'` or `'U'`). This halves the
+/// memory compared to [`TriMat`], at the cost of using unblocked kernels for the products and
+/// solves.
+///
+/// The solves are exposed through [`SpSolver`](crate::sparse::linalg::solvers::SpSolver).
+///
+/// # Example
+/// ```
+/// use faer::{
+///     mat,
+///     mat::{PackedTriMat, Upper},
+///     prelude::*,
+/// };
+///
+/// let u = PackedTriMat::<f64, Upper>::from_full(mat![[2.0, 1.0], [9.0, 4.0]].as_ref());
+/// assert!(u.values().len() == 3);
+///
+/// let b = mat![[4.0], [8.0]];
+/// let x = u.solve(&b);
+/// assert!(x == mat![[1.0], [2.0]]);
+/// assert!(u.matmul(x.as_ref()) == b);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackedTriMat<E: Entity, T: Triangle = super::Lower> {
+    inner: Packed<E>,
+    __marker: core::marker::PhantomData<T>,
+}
+
+/// Square self-adjoint matrix (symmetric in the real case, Hermitian in the complex case) in
+/// packed storage, whose structure is part of its type.
+///
+/// Only the `n(n + 1) / 2` entries of the half selected by `T` (with the diagonal) are stored,
+/// column by column, which matches the packed format of LAPACK (`'L'` or `'U'`). The entries of
+/// the other half are read and written through the conjugate of their mirrored entry. The
+/// imaginary part of the diagonal is assumed to be zero.
+///
+/// The Cholesky decomposition can be computed in place with [`PackedSymMat::into_cholesky`], so
+/// that the factorization of a large positive definite matrix needs half the memory of the
+/// dense one.
+///
+/// # Example
+/// ```
+/// use faer::{
+///     mat,
+///     mat::{Lower, PackedSymMat},
+///     prelude::*,
+/// };
+///
+/// let a = PackedSymMat::<f64, Lower>::from_full(mat![[4.0, 2.0], [2.0, 3.0]].as_ref());
+/// let b = mat![[8.0], [7.0]];
+///
+/// let llt = a.clone().into_cholesky().unwrap();
+/// let x = llt.solve(&b);
+/// assert!((a.matmul(x.as_ref()) - &b).norm_max() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PackedSymMat<E: Entity, T: Triangle = super::Lower> {
+    inner: Packed<E>,
+    __marker: core::marker::PhantomData<T>,
+}
+
+/// Cholesky decomposition of a [`PackedSymMat`], stored in packed format.
+///
+/// The factorization is such that $A = LL^H$ when `T` is [`Lower`](super::Lower), and
+/// $A = U^H U$ when `T` is [`Upper`](super::Upper), where the factor is stored in the same half
+/// as the input matrix.
+#[derive(Clone, Debug)]
+pub struct PackedCholesky<E: Entity, T: Triangle = super::Lower> {
+    factor: PackedTriMat<E, T>,
+}
+
+impl<E: ComplexField, T: Triangle> PackedTriMat<E, T> {
+    /// Returns a new packed triangular matrix of dimension `dim`, filled with zeros.
+    #[track_caller]
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            inner: Packed::zeros(dim),
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a new packed triangular matrix of dimension `dim`, where the stored entries are
+    /// computed by calling `f` with their position. `f` is only called for the positions of the
+    /// stored half.
+    #[track_caller]
+    pub fn from_fn(dim: usize, f: impl FnMut(usize, usize) -> E) -> Self {
+        Self {
+            inner: Packed::from_fn::<T>(dim, f),
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a new packed triangular matrix containing a copy of the half of `mat` selected by
+    /// `T`. The other half of `mat` is not accessed.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn from_full<ViewE: Conjugate<Canonical = E>>(mat: MatRef<'_, ViewE>) -> Self {
+        assert!(mat.nrows() == mat.ncols());
+        Self::from_fn(mat.nrows(), |i, j| mat.read(i, j).canonicalize())
+    }
+
+    /// Returns a packed copy of the triangular matrix `mat`.
+    pub fn from_tri_mat(mat: &TriMat<E, T>) -> Self {
+        Self::from_fn(mat.dim(), |i, j| mat.read(i, j))
+    }
+
+    /// Returns a copy of `self` in full storage.
+    pub fn to_tri_mat(&self) -> TriMat<E, T> {
+        TriMat::from_fn(self.dim(), |i, j| self.inner.read::<T>(i, j))
+    }
+
+    /// Returns the dense matrix represented by `self`.
+    pub fn to_dense(&self) -> Mat<E> {
+        let n = self.dim();
+        Mat::from_fn(n, n, |i, j| self.read(i, j))
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.inner.dim
+    }
+
+    /// Returns the stored half.
+    #[inline]
+    pub fn side(&self) -> Side {
+        T::SIDE
+    }
+
+    /// Returns the stored entries, in the packed format of LAPACK.
+    #[inline]
+    pub fn values(&self) -> SliceGroup<'_, E> {
+        self.inner.values.as_slice()
+    }
+
+    /// Returns the value at position `(i, j)`, which is zero outside the stored half.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        assert!(all(i < self.dim(), j < self.dim()));
+        if is_stored::<T>(i, j) {
+            self.inner.read::<T>(i, j)
+        } else {
+            E::faer_zero()
+        }
+    }
+
+    /// Writes `value` at position `(i, j)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds, or if `(i, j)` is outside the stored half.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, i: usize, j: usize, value: E) {
+        assert!(all(i < self.dim(), j < self.dim(), is_stored::<T>(i, j)));
+        self.inner.write::<T>(i, j, value);
+    }
+
+    /// Returns the product of `self` and `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have `self.dim()` rows.
+    #[track_caller]
+    pub fn matmul<ViewE: Conjugate<Canonical = E>>(&self, rhs: MatRef<'_, ViewE>) -> Mat<E> {
+        let n = self.dim();
+        assert!(rhs.nrows() == n);
+        let values = self.values();
+        let mut out = Mat::<E>::zeros(n, rhs.ncols());
+        for k in 0..rhs.ncols() {
+            for j in 0..n {
+                let x = rhs.read(j, k).canonicalize();
+                let start = col_start::<T>(n, j);
+                for (idx, i) in col_rows::<T>(n, j).enumerate() {
+                    let a = values.read(start + idx);
+                    out.write(i, k, out.read(i, k).faer_add(a.faer_mul(x)));
+                }
+            }
+        }
+        out
+    }
+
+    // solves `op(self) x = rhs` in place, where `op(self)` is `self` or its transpose, optionally
+    // conjugated
+    #[track_caller]
+    fn solve_impl(&self, mut rhs: MatMut<'_, E>, conj: Conj, transpose: bool) {
+        let n = self.dim();
+        assert!(rhs.nrows() == n);
+        let values = self.values();
+        let read = |idx: usize| {
+            let a = values.read(idx);
+            if conj == Conj::Yes {
+                a.faer_conj()
+            } else {
+                a
+            }
+        };
+        // whether the effective matrix is lower triangular, i.e., the unknowns are computed from
+        // first to last
+        let forward = (T::SIDE == Side::Lower) != transpose;
+
+        for k in 0..rhs.ncols() {
+            let mut x = rhs.rb_mut().col_mut(k);
+            for step in 0..n {
+                let j = if forward { step } else { n - 1 - step };
+                let start = col_start::<T>(n, j);
+                let rows = col_rows::<T>(n, j);
+                let diag = read(start + j - rows.start);
+                if transpose {
+                    // the `j`-th column of `self` is the `j`-th row of `op(self)`
+                    let mut acc = x.read(j);
+                    for (idx, i) in rows.clone().enumerate() {
+                        if i != j {
+                            acc = acc.faer_sub(read(start + idx).faer_mul(x.read(i)));
+                        }
+                    }
+                    x.write(j, acc.faer_mul(diag.faer_inv()));
+                } else {
+                    let xj = x.read(j).faer_mul(diag.faer_inv());
+                    x.write(j, xj);
+                    for (idx, i) in rows.clone().enumerate() {
+                        if i != j {
+                            x.write(i, x.read(i).faer_sub(read(start + idx).faer_mul(xj)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<E: ComplexField, T: Triangle> SpSolverCore<E> for PackedTriMat<E, T> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, false);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, true);
+    }
+}
+
+impl<E: ComplexField, T: Triangle> PackedSymMat<E, T> {
+    /// Returns a new packed self-adjoint matrix of dimension `dim`, filled with zeros.
+    #[track_caller]
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            inner: Packed::zeros(dim),
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a new packed self-adjoint matrix of dimension `dim`, where the stored entries are
+    /// computed by calling `f` with their position. `f` is only called for the positions of the
+    /// stored half.
+    #[track_caller]
+    pub fn from_fn(dim: usize, f: impl FnMut(usize, usize) -> E) -> Self {
+        Self {
+            inner: Packed::from_fn::<T>(dim, f),
+            __marker: core::marker::PhantomData,
+        }
+    }
+
+    /// Returns a new packed self-adjoint matrix containing a copy of the half of `mat` selected
+    /// by `T`. The other half of `mat` is not accessed.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn from_full<ViewE: Conjugate<Canonical = E>>(mat: MatRef<'_, ViewE>) -> Self {
+        assert!(mat.nrows() == mat.ncols());
+        Self::from_fn(mat.nrows(), |i, j| mat.read(i, j).canonicalize())
+    }
+
+    /// Returns a packed copy of the self-adjoint matrix `mat`.
+    pub fn from_sym_mat(mat: &SymMat<E, T>) -> Self {
+        Self::from_fn(mat.dim(), |i, j| mat.read(i, j))
+    }
+
+    /// Returns a copy of `self` in full storage.
+    pub fn to_sym_mat(&self) -> SymMat<E, T> {
+        SymMat::from_fn(self.dim(), |i, j| self.inner.read::<T>(i, j))
+    }
+
+    /// Returns the dense matrix represented by `self`, with both halves filled in.
+    pub fn to_dense(&self) -> Mat<E> {
+        let n = self.dim();
+        Mat::from_fn(n, n, |i, j| self.read(i, j))
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.inner.dim
+    }
+
+    /// Returns the stored half.
+    #[inline]
+    pub fn side(&self) -> Side {
+        T::SIDE
+    }
+
+    /// Returns the stored entries, in the packed format of LAPACK.
+    #[inline]
+    pub fn values(&self) -> SliceGroup<'_, E> {
+        self.inner.values.as_slice()
+    }
+
+    /// Returns the value at position `(i, j)`. Outside the stored half, this is the conjugate
+    /// of the value at position `(j, i)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        assert!(all(i < self.dim(), j < self.dim()));
+        if is_stored::<T>(i, j) {
+            self.inner.read::<T>(i, j)
+        } else {
+            self.inner.read::<T>(j, i).faer_conj()
+        }
+    }
+
+    /// Writes `value` at position `(i, j)`, and implicitly its conjugate at position `(j, i)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, i: usize, j: usize, value: E) {
+        assert!(all(i < self.dim(), j < self.dim()));
+        if is_stored::<T>(i, j) {
+            self.inner.write::<T>(i, j, value);
+        } else {
+            self.inner.write::<T>(j, i, value.faer_conj());
+        }
+    }
+
+    /// Returns the product of `self` and `rhs`.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have `self.dim()` rows.
+    #[track_caller]
+    pub fn matmul<ViewE: Conjugate<Canonical = E>>(&self, rhs: MatRef<'_, ViewE>) -> Mat<E> {
+        let n = self.dim();
+        assert!(rhs.nrows() == n);
+        let values = self.values();
+        let mut out = Mat::<E>::zeros(n, rhs.ncols());
+        for k in 0..rhs.ncols() {
+            for j in 0..n {
+                let xj = rhs.read(j, k).canonicalize();
+                let start = col_start::<T>(n, j);
+                let mut acc = out.read(j, k);
+                for (idx, i) in col_rows::<T>(n, j).enumerate() {
+                    let a = values.read(start + idx);
+                    if i == j {
+                        acc = acc.faer_add(a.faer_mul(xj));
+                    } else {
+                        // `a` contributes to `(i, j)` and its conjugate to `(j, i)`
+                        out.write(i, k, out.read(i, k).faer_add(a.faer_mul(xj)));
+                        let xi = rhs.read(i, k).canonicalize();
+                        acc = acc.faer_add(a.faer_conj().faer_mul(xi));
+                    }
+                }
+                out.write(j, k, acc);
+            }
+        }
+        out
+    }
+
+    /// Returns the Cholesky decomposition of `self`, or an error if it is not positive definite.
+    #[track_caller]
+    #[doc(alias = "llt")]
+    pub fn cholesky(&self) -> Result<PackedCholesky<E, T>, CholeskyError> {
+        self.clone().into_cholesky()
+    }
+
+    /// Computes the Cholesky decomposition of `self` in place, without allocating, or returns an
+    /// error if it is not positive definite.
+    #[track_caller]
+    #[doc(alias = "llt")]
+    pub fn into_cholesky(self) -> Result<PackedCholesky<E, T>, CholeskyError> {
+        let n = self.dim();
+        let mut inner = self.inner;
+        let mut values = inner.values.as_slice_mut();
+
+        for j in 0..n {
+            let start_j = col_start::<T>(n, j);
+            match T::SIDE {
+                // left-looking: subtract the contributions of the previous columns from the
+                // `j`-th column, then scale it by the inverse of the diagonal
+                Side::Lower => {
+                    for k in 0..j {
+                        let start_k = col_start::<T>(n, k);
+                        let ljk = values.read(start_k + j - k).faer_conj();
+                        for i in j..n {
+                            let idx = start_j + i - j;
+                            let lik = values.read(start_k + i - k);
+                            values.write(idx, values.read(idx).faer_sub(lik.faer_mul(ljk)));
+                        }
+                    }
+                    let d = values.read(start_j).faer_real();
+                    let d = if d > E::Real::faer_zero() {
+                        d.faer_sqrt()
+                    } else {
+                        return Err(CholeskyError {
+                            non_positive_definite_minor: j + 1,
+                        });
+                    };
+                    values.write(start_j, E::faer_from_real(d));
+                    let d_inv = d.faer_inv();
+                    for idx in start_j + 1..start_j + n - j {
+                        values.write(idx, values.read(idx).faer_scale_real(d_inv));
+                    }
+                }
+                // up-looking: compute the `j`-th column by a triangular solve with the previous
+                // columns, then the diagonal from the remaining norm
+                Side::Upper => {
+                    for i in 0..j {
+                        let start_i = col_start::<T>(n, i);
+                        let mut acc = values.read(start_j + i);
+                        for k in 0..i {
+                            let uki = values.read(start_i + k).faer_conj();
+                            acc = acc.faer_sub(uki.faer_mul(values.read(start_j + k)));
+                        }
+                        let uii = values.read(start_i + i).faer_real();
+                        values.write(start_j + i, acc.faer_scale_real(uii.faer_inv()));
+                    }
+                    let mut d = values.read(start_j + j).faer_real();
+                    for k in 0..j {
+                        d = d.faer_sub(values.read(start_j + k).faer_abs2());
+                    }
+                    if d > E::Real::faer_zero() {
+                        values.write(start_j + j, E::faer_from_real(d.faer_sqrt()));
+                    } else {
+                        return Err(CholeskyError {
+                            non_positive_definite_minor: j + 1,
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(PackedCholesky {
+            factor: PackedTriMat {
+                inner,
+                __marker: core::marker::PhantomData,
+            },
+        })
+    }
+}
+
+impl<E: ComplexField, T: Triangle> PackedCholesky<E, T> {
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.factor.dim()
+    }
+
+    /// Returns the triangular factor, $L$ if `T` is [`Lower`](super::Lower), and $U$ if `T` is
+    /// [`Upper`](super::Upper).
+    #[inline]
+    pub fn factor(&self) -> &PackedTriMat<E, T> {
+        &self.factor
+    }
+
+    /// Returns the triangular factor, consuming `self`.
+    #[inline]
+    pub fn into_factor(self) -> PackedTriMat<E, T> {
+        self.factor
+    }
+
+    // the conjugate of `A = LL^H` is `conj(L) conj(L)^H`, and that of `A = U^H U` is
+    // `conj(U)^H conj(U)`
+    #[track_caller]
+    fn solve_impl(&self, mut rhs: MatMut<'_, E>, conj: Conj) {
+        let adjoint = conj.compose(Conj::Yes);
+        match T::SIDE {
+            Side::Lower => {
+                self.factor.solve_impl(rhs.rb_mut(), conj, false);
+                self.factor.solve_impl(rhs, adjoint, true);
+            }
+            Side::Upper => {
+                self.factor.solve_impl(rhs.rb_mut(), adjoint, true);
+                self.factor.solve_impl(rhs, conj, false);
+            }
+        }
+    }
+}
+
+impl<E: ComplexField, T: Triangle> SpSolverCore<E> for PackedCholesky<E, T> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj);
+    }
+
+    // the matrix is self-adjoint, so its transpose is its conjugate
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj.compose(Conj::Yes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        complex_native::c64,
+        mat::{Lower, Upper},
+        sparse::linalg::solvers::SpSolver,
+    };
+    use equator::assert;
+
+    fn check<T: Triangle>() {
+        let n = 9;
+        let a = Mat::from_fn(n, n, |i, j| {
+            if i == j {
+                c64::new(2.0 * n as f64 + i as f64, 0.0)
+            } else {
+                c64::new(
+                    (i as f64 - 0.7 * j as f64).sin(),
+                    (i as f64 - j as f64) * 0.1,
+                )
+            }
+        });
+        let rhs = Mat::from_fn(n, 3, |i, j| c64::new(i as f64, j as f64 - 1.0));
+        let approx_eq = |x: &Mat<c64>, y: &Mat<c64>| (x - y).norm_max() < 1e-10;
+
+        let tri = PackedTriMat::<c64, T>::from_full(a.as_ref());
+        assert!(tri.values().len() == n * (n + 1) / 2);
+        let dense = tri.to_dense();
+        assert!(dense == tri.to_tri_mat().to_dense());
+        assert!(PackedTriMat::from_tri_mat(&tri.to_tri_mat()).to_dense() == dense);
+        assert!(approx_eq(&tri.matmul(rhs.as_ref()), &(&dense * &rhs)));
+        assert!(approx_eq(&(&dense * tri.solve(&rhs)), &rhs));
+        assert!(approx_eq(&(dense.conjugate() * tri.solve_conj(&rhs)), &rhs));
+        assert!(approx_eq(
+            &(dense.transpose() * tri.solve_transpose(&rhs)),
+            &rhs
+        ));
+        assert!(approx_eq(
+            &(dense.adjoint() * tri.solve_conj_transpose(&rhs)),
+            &rhs
+        ));
+
+        let sym = PackedSymMat::<c64, T>::from_full(a.as_ref());
+        let dense = sym.to_dense();
+        assert!(dense == sym.to_sym_mat().to_dense());
+        assert!(PackedSymMat::from_sym_mat(&sym.to_sym_mat()).to_dense() == dense);
+        assert!(approx_eq(&sym.matmul(rhs.as_ref()), &(&dense * &rhs)));
+
+        let llt = sym.cholesky().unwrap();
+        let factor = llt.factor().to_dense();
+        let reconstructed = match T::SIDE {
+            Side::Lower => &factor * factor.adjoint(),
+            Side::Upper => factor.adjoint() * &factor,
+        };
+        assert!(approx_eq(&reconstructed, &dense));
+        assert!(approx_eq(&(&dense * llt.solve(&rhs)), &rhs));
+        assert!(approx_eq(&(dense.conjugate() * llt.solve_conj(&rhs)), &rhs));
+        assert!(approx_eq(
+            &(dense.transpose() * llt.solve_transpose(&rhs)),
+            &rhs
+        ));
+
+        // the dense and packed factorizations agree
+        let x = sym.to_sym_mat().cholesky().unwrap().solve(&rhs);
+        assert!(approx_eq(&x, &llt.solve(&rhs)));
+
+        let mut not_pd = sym;
+        not_pd.write(3, 3, c64::new(-1.0, 0.0));
+        let err = not_pd.into_cholesky().unwrap_err();
+        assert!(err.non_positive_definite_minor <= 4);
+    }
+
+    #[test]
+    fn test_packed() {
+        check::<Lower>();
+        check::<Upper>();
+    }
+}
@@ -0,0 +1,151 @@
+use crate::{
+    assert,
+    col::ColRef,
+    mat::MatMut,
+    perm::{swap_rows_idx, Perm, PermRef},
+    ComplexField, Index, RealField,
+};
+use core::cmp::Ordering;
+use reborrow::*;
+
+/// Specifies the sorting algorithm used by the sorting methods of the matrix types.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SortKind {
+    /// Equal elements keep their relative order.
+    Stable,
+    /// Equal elements may be reordered, which can be faster.
+    Unstable,
+}
+
+// orders the values in increasing order, with the nan values at the end
+#[inline]
+fn cmp_nan_last<E: RealField>(a: E, b: E) -> Ordering {
+    match (a.faer_is_nan(), b.faer_is_nan()) {
+        (false, false) => a.partial_cmp(&b).unwrap_or(Ordering::Equal),
+        (false, true) => Ordering::Less,
+        (true, false) => Ordering::Greater,
+        (true, true) => Ordering::Equal,
+    }
+}
+
+// returns the permutation that sorts `values`, such that its `k`-th forward index is the index of
+// the `k`-th smallest value
+pub(crate) fn argsort<E: RealField>(values: ColRef<'_, E>, kind: SortKind) -> Perm<usize> {
+    let n = values.nrows();
+    // read the values once, since the column may be strided
+    let values = (0..n)
+        .map(|i| values.read(i))
+        .collect::<alloc::vec::Vec<_>>();
+    let mut forward = (0..n).collect::<alloc::vec::Vec<_>>();
+    let cmp = |&i: &usize, &j: &usize| cmp_nan_last(values[i], values[j]);
+    match kind {
+        SortKind::Stable => forward.sort_by(cmp),
+        SortKind::Unstable => forward.sort_unstable_by(cmp),
+    }
+
+    let mut inverse = alloc::vec![0usize; n];
+    for (k, &i) in forward.iter().enumerate() {
+        inverse[i] = k;
+    }
+    Perm::new_checked(forward.into_boxed_slice(), inverse.into_boxed_slice())
+}
+
+// replaces the `i`-th row of `mat` by its `perm[i]`-th row, by following the cycles of the
+// permutation, so that each row is moved once and no copy of the matrix is needed
+#[track_caller]
+pub(crate) fn permute_rows_in_place<I: Index, E: ComplexField>(
+    mat: MatMut<'_, E>,
+    perm: PermRef<'_, I>,
+) {
+    let mut mat = mat;
+    let m = mat.nrows();
+    assert!(perm.len() == m);
+
+    let forward = perm.arrays().0;
+    let mut visited = alloc::vec![false; m];
+    for start in 0..m {
+        if visited[start] {
+            continue;
+        }
+        visited[start] = true;
+        let mut cur = start;
+        loop {
+            let next = forward[cur].zx();
+            if next == start {
+                break;
+            }
+            // after the swap, the row `cur` holds its final value, and the row `next` holds the
+            // original row `start`
+            swap_rows_idx(mat.rb_mut(), cur, next);
+            visited[next] = true;
+            cur = next;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        mat::{Mat, SortKind},
+        perm::permute_rows,
+    };
+    use equator::assert;
+
+    // compares the bit patterns, since the matrices contain nan
+    fn bits_eq(a: &Mat<f64>, b: &Mat<f64>) -> bool {
+        a.nrows() == b.nrows()
+            && a.ncols() == b.ncols()
+            && (0..a.ncols())
+                .all(|j| (0..a.nrows()).all(|i| a.read(i, j).to_bits() == b.read(i, j).to_bits()))
+    }
+
+    #[test]
+    fn test_sort() {
+        let nan = f64::NAN;
+        let m = 11;
+        let n = 4;
+        let a = Mat::<f64>::from_fn(m, n, |i, j| {
+            if i == 4 && j == 1 {
+                nan
+            } else {
+                ((i * 7 + j * 3) % 5) as f64
+            }
+        });
+
+        for kind in [SortKind::Stable, SortKind::Unstable] {
+            for j in 0..n {
+                let perm = a.argsort_col(j, kind);
+                let forward = perm.as_ref().arrays().0;
+                for k in 1..m {
+                    let (prev, cur) = (a.read(forward[k - 1], j), a.read(forward[k], j));
+                    assert!(prev <= cur || cur.is_nan());
+                    if kind == SortKind::Stable && prev == cur {
+                        assert!(forward[k - 1] < forward[k]);
+                    }
+                }
+
+                let mut sorted = a.clone();
+                let sort_perm = sorted.sort_rows_by_column(j, kind);
+                let mut expected = Mat::<f64>::zeros(m, n);
+                permute_rows(expected.as_mut(), a.as_ref(), sort_perm.as_ref());
+                assert!(bits_eq(&sorted, &expected));
+                if kind == SortKind::Stable {
+                    assert!(sort_perm == perm);
+                }
+            }
+        }
+
+        // the nan is sorted last
+        assert!(a.argsort_col(1, SortKind::Stable).as_ref().arrays().0[m - 1] == 4);
+
+        let mut b = a.transpose().to_owned();
+        let perm = b.sort_cols_by_row(0, SortKind::Stable);
+        let mut expected = a.clone();
+        expected.permute_rows_in_place(perm.as_ref());
+        assert!(bits_eq(&b.transpose().to_owned(), &expected));
+
+        // applying the inverse permutation restores the original matrix
+        expected.permute_rows_in_place(perm.as_ref().inverse());
+        assert!(bits_eq(&expected, &a));
+    }
+}
@@ -0,0 +1,820 @@
+use crate::{
+    assert, get_global_parallelism,
+    linalg::{
+        cholesky::llt::CholeskyError,
+        matmul::triangular::{matmul_with_conj, BlockStructure},
+        solvers::{Cholesky, Lblt, SelfAdjointEigendecomposition},
+        triangular_solve,
+    },
+    linop::{BiLinOp, LinOp},
+    mat::{Mat, MatMut, MatRef, PackedSymMat, PackedTriMat},
+    sparse::linalg::solvers::SpSolverCore,
+    ComplexField, Conj, Conjugate, Entity, Parallelism, Side,
+};
+use core::marker::PhantomData;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+
+/// Type-level marker for the triangular half of a square matrix that is stored by a [`TriMat`]
+/// or a [`SymMat`].
+///
+/// This trait is sealed, and is implemented by [`Lower`] and [`Upper`].
+pub trait Triangle: crate::seal::Seal + Copy + core::fmt::Debug + Send + Sync + 'static {
+    /// The stored half, as a runtime value.
+    const SIDE: Side;
+}
+
+/// Marker for the lower triangular half, including the diagonal.
+#[derive(Copy, Clone, Debug)]
+pub struct Lower;
+/// Marker for the upper triangular half, including the diagonal.
+#[derive(Copy, Clone, Debug)]
+pub struct Upper;
+
+impl Triangle for Lower {
+    const SIDE: Side = Side::Lower;
+}
+impl Triangle for Upper {
+    const SIDE: Side = Side::Upper;
+}
+
+#[inline]
+fn is_stored<T: Triangle>(i: usize, j: usize) -> bool {
+    match T::SIDE {
+        Side::Lower => i >= j,
+        Side::Upper => i <= j,
+    }
+}
+
+#[inline]
+fn triangular_structure(side: Side) -> BlockStructure {
+    match side {
+        Side::Lower => BlockStructure::TriangularLower,
+        Side::Upper => BlockStructure::TriangularUpper,
+    }
+}
+
+#[inline]
+fn strict_triangular_structure(side: Side) -> BlockStructure {
+    match side {
+        Side::Lower => BlockStructure::StrictTriangularLower,
+        Side::Upper => BlockStructure::StrictTriangularUpper,
+    }
+}
+
+#[inline]
+fn flip(side: Side) -> Side {
+    match side {
+        Side::Lower => Side::Upper,
+        Side::Upper => Side::Lower,
+    }
+}
+
+// copies the `side` half of the square matrix `src` into a new matrix, leaving the other half
+// zeroed
+#[track_caller]
+fn copy_triangle<E: ComplexField, ViewE: Conjugate<Canonical = E>>(
+    src: MatRef<'_, ViewE>,
+    side: Side,
+) -> Mat<E> {
+    assert!(src.nrows() == src.ncols());
+    let mut inner = Mat::<E>::zeros(src.nrows(), src.ncols());
+    match side {
+        Side::Lower => inner.copy_from_triangular_lower(src),
+        Side::Upper => inner.copy_from_triangular_upper(src),
+    }
+    inner
+}
+
+/// Square triangular matrix, whose structure is part of its type.
+///
+/// Only the half selected by `T` (with the diagonal) is stored and accessed. Reading an entry of
+/// the other half returns zero, and writing to it panics, so that the entries of the
+/// other half can never be mistaken for meaningful values.
+///
+/// The products and solves are computed with the triangular kernels from
+/// [`matmul::triangular`](crate::linalg::matmul::triangular) and
+/// [`triangular_solve`](crate::linalg::triangular_solve), and the solves are exposed through
+/// [`SpSolver`](crate::sparse::linalg::solvers::SpSolver).
+///
+/// # Example
+/// ```
+/// use faer::{
+///     mat,
+///     mat::{Lower, TriMat},
+///     prelude::*,
+/// };
+///
+/// // the upper half of the input is ignored
+/// let l = TriMat::<f64, Lower>::from_full(mat![[2.0, 9.0], [1.0, 4.0]].as_ref());
+/// assert!(l.read(0, 1) == 0.0);
+///
+/// let b = mat![[2.0], [9.0]];
+/// let x = l.solve(&b);
+/// assert!(x == mat![[1.0], [2.0]]);
+/// assert!(l.matmul(x.as_ref()) == b);
+/// ```
+#[derive(Clone, Debug)]
+pub struct TriMat<E: Entity, T: Triangle = Lower> {
+    inner: Mat<E>,
+    __marker: PhantomData<T>,
+}
+
+impl<E: ComplexField, T: Triangle> TriMat<E, T> {
+    /// Returns a new triangular matrix of dimension `dim`, filled with zeros.
+    #[inline]
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            inner: Mat::zeros(dim, dim),
+            __marker: PhantomData,
+        }
+    }
+
+    /// Returns a new triangular matrix of dimension `dim`, where the stored entries are computed
+    /// by calling `f` with their position. `f` is only called for the positions of the stored
+    /// half.
+    pub fn from_fn(dim: usize, mut f: impl FnMut(usize, usize) -> E) -> Self {
+        let mut this = Self::zeros(dim);
+        for j in 0..dim {
+            for i in 0..dim {
+                if is_stored::<T>(i, j) {
+                    this.inner.write(i, j, f(i, j));
+                }
+            }
+        }
+        this
+    }
+
+    /// Returns a new triangular matrix containing a copy of the half of `mat` selected by `T`.
+    /// The other half of `mat` is not accessed.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn from_full<ViewE: Conjugate<Canonical = E>>(mat: MatRef<'_, ViewE>) -> Self {
+        Self {
+            inner: copy_triangle(mat, T::SIDE),
+            __marker: PhantomData,
+        }
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.inner.nrows()
+    }
+
+    /// Returns the stored half.
+    #[inline]
+    pub fn side(&self) -> Side {
+        T::SIDE
+    }
+
+    /// Returns the value at position `(i, j)`, which is zero outside the stored half.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        let value = self.inner.read(i, j);
+        if is_stored::<T>(i, j) {
+            value
+        } else {
+            E::faer_zero()
+        }
+    }
+
+    /// Writes `value` at position `(i, j)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds, or if `(i, j)` is outside the stored half.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, i: usize, j: usize, value: E) {
+        assert!(is_stored::<T>(i, j));
+        self.inner.write(i, j, value);
+    }
+
+    /// Returns a view over the underlying full storage, whose entries outside the stored half
+    /// are zero.
+    #[inline]
+    pub fn as_dense(&self) -> MatRef<'_, E> {
+        self.inner.as_ref()
+    }
+
+    /// Returns the dense matrix represented by `self`.
+    #[inline]
+    pub fn to_dense(&self) -> Mat<E> {
+        self.inner.clone()
+    }
+
+    /// Returns a copy of `self` in packed storage.
+    pub fn to_packed(&self) -> PackedTriMat<E, T> {
+        PackedTriMat::from_tri_mat(self)
+    }
+
+    /// Returns the product of `self` and `rhs`, computed with the global parallelism setting.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have `self.dim()` rows.
+    #[track_caller]
+    pub fn matmul<ViewE: Conjugate<Canonical = E>>(&self, rhs: MatRef<'_, ViewE>) -> Mat<E> {
+        let (rhs, conj_rhs) = rhs.canonicalize();
+        let mut out = Mat::<E>::zeros(self.dim(), rhs.ncols());
+        self.apply_impl(
+            out.as_mut(),
+            rhs,
+            Conj::No,
+            conj_rhs,
+            false,
+            get_global_parallelism(),
+        );
+        out
+    }
+
+    #[track_caller]
+    fn apply_impl(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj_lhs: Conj,
+        conj_rhs: Conj,
+        transpose: bool,
+        parallelism: Parallelism,
+    ) {
+        let (lhs, side) = if transpose {
+            (self.inner.as_ref().transpose(), flip(T::SIDE))
+        } else {
+            (self.inner.as_ref(), T::SIDE)
+        };
+        matmul_with_conj(
+            out,
+            BlockStructure::Rectangular,
+            lhs,
+            triangular_structure(side),
+            conj_lhs,
+            rhs,
+            BlockStructure::Rectangular,
+            conj_rhs,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+    }
+
+    #[track_caller]
+    fn solve_impl(&self, rhs: MatMut<'_, E>, conj: Conj, transpose: bool) {
+        let parallelism = get_global_parallelism();
+        let (lhs, side) = if transpose {
+            (self.inner.as_ref().transpose(), flip(T::SIDE))
+        } else {
+            (self.inner.as_ref(), T::SIDE)
+        };
+        match side {
+            Side::Lower => triangular_solve::solve_lower_triangular_in_place_with_conj(
+                lhs,
+                conj,
+                rhs,
+                parallelism,
+            ),
+            Side::Upper => triangular_solve::solve_upper_triangular_in_place_with_conj(
+                lhs,
+                conj,
+                rhs,
+                parallelism,
+            ),
+        }
+    }
+}
+
+impl<E: ComplexField, T: Triangle> SpSolverCore<E> for TriMat<E, T> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, false);
+    }
+
+    #[track_caller]
+    fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
+        self.solve_impl(rhs, conj, true);
+    }
+}
+
+impl<E: ComplexField, T: Triangle> LinOp<E> for TriMat<E, T> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, Conj::No, false, parallelism);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, Conj::No, false, parallelism);
+    }
+}
+
+impl<E: ComplexField, T: Triangle> BiLinOp<E> for TriMat<E, T> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, Conj::No, true, parallelism);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, Conj::No, true, parallelism);
+    }
+}
+
+/// Square self-adjoint matrix (symmetric in the real case, Hermitian in the complex case), whose
+/// structure is part of its type.
+///
+/// Only the half selected by `T` (with the diagonal) is stored and accessed. The entries of the
+/// other half are read and written through the conjugate of their mirrored entry, so both halves
+/// can be used interchangeably. The imaginary part of the diagonal is assumed to be zero.
+///
+/// The products are computed with the triangular kernels from
+/// [`matmul::triangular`](crate::linalg::matmul::triangular), and the decompositions only access
+/// the stored half.
+///
+/// # Example
+/// ```
+/// use faer::{
+///     mat,
+///     mat::{SymMat, Upper},
+///     prelude::*,
+/// };
+///
+/// let mut a = SymMat::<f64, Upper>::zeros(2);
+/// a.write(0, 0, 4.0);
+/// // stored in the upper half, as `(0, 1)`
+/// a.write(1, 0, 2.0);
+/// a.write(1, 1, 3.0);
+/// assert!(a.to_dense() == mat![[4.0, 2.0], [2.0, 3.0]]);
+///
+/// let b = mat![[8.0], [7.0]];
+/// let x = a.cholesky().unwrap().solve(&b);
+/// assert!((a.matmul(x.as_ref()) - &b).norm_max() < 1e-12);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SymMat<E: Entity, T: Triangle = Lower> {
+    inner: Mat<E>,
+    __marker: PhantomData<T>,
+}
+
+impl<E: ComplexField, T: Triangle> SymMat<E, T> {
+    /// Returns a new self-adjoint matrix of dimension `dim`, filled with zeros.
+    #[inline]
+    pub fn zeros(dim: usize) -> Self {
+        Self {
+            inner: Mat::zeros(dim, dim),
+            __marker: PhantomData,
+        }
+    }
+
+    /// Returns a new self-adjoint matrix of dimension `dim`, where the stored entries are
+    /// computed by calling `f` with their position. `f` is only called for the positions of the
+    /// stored half.
+    pub fn from_fn(dim: usize, mut f: impl FnMut(usize, usize) -> E) -> Self {
+        let mut this = Self::zeros(dim);
+        for j in 0..dim {
+            for i in 0..dim {
+                if is_stored::<T>(i, j) {
+                    this.inner.write(i, j, f(i, j));
+                }
+            }
+        }
+        this
+    }
+
+    /// Returns a new self-adjoint matrix containing a copy of the half of `mat` selected by `T`.
+    /// The other half of `mat` is not accessed.
+    ///
+    /// # Panics
+    /// Panics if `mat` is not square.
+    #[track_caller]
+    pub fn from_full<ViewE: Conjugate<Canonical = E>>(mat: MatRef<'_, ViewE>) -> Self {
+        Self {
+            inner: copy_triangle(mat, T::SIDE),
+            __marker: PhantomData,
+        }
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.inner.nrows()
+    }
+
+    /// Returns the stored half.
+    #[inline]
+    pub fn side(&self) -> Side {
+        T::SIDE
+    }
+
+    /// Returns the value at position `(i, j)`. Outside the stored half, this is the conjugate
+    /// of the value at position `(j, i)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn read(&self, i: usize, j: usize) -> E {
+        if is_stored::<T>(i, j) {
+            self.inner.read(i, j)
+        } else {
+            self.inner.read(j, i).faer_conj()
+        }
+    }
+
+    /// Writes `value` at position `(i, j)`, and implicitly its conjugate at position `(j, i)`.
+    ///
+    /// # Panics
+    /// Panics if `i` or `j` is out of bounds.
+    #[inline]
+    #[track_caller]
+    pub fn write(&mut self, i: usize, j: usize, value: E) {
+        if is_stored::<T>(i, j) {
+            self.inner.write(i, j, value);
+        } else {
+            self.inner.write(j, i, value.faer_conj());
+        }
+    }
+
+    /// Returns a view over the underlying full storage. Only the half selected by `T` is
+    /// meaningful, and the other half is zero.
+    #[inline]
+    pub fn as_half(&self) -> MatRef<'_, E> {
+        self.inner.as_ref()
+    }
+
+    /// Returns the dense matrix represented by `self`, with both halves filled in.
+    pub fn to_dense(&self) -> Mat<E> {
+        let n = self.dim();
+        Mat::from_fn(n, n, |i, j| self.read(i, j))
+    }
+
+    /// Returns a copy of `self` in packed storage.
+    pub fn to_packed(&self) -> PackedSymMat<E, T> {
+        PackedSymMat::from_sym_mat(self)
+    }
+
+    /// Returns the product of `self` and `rhs`, computed with the global parallelism setting.
+    ///
+    /// # Panics
+    /// Panics if `rhs` doesn't have `self.dim()` rows.
+    #[track_caller]
+    pub fn matmul<ViewE: Conjugate<Canonical = E>>(&self, rhs: MatRef<'_, ViewE>) -> Mat<E> {
+        let (rhs, conj_rhs) = rhs.canonicalize();
+        let mut out = Mat::<E>::zeros(self.dim(), rhs.ncols());
+        self.apply_impl(
+            out.as_mut(),
+            rhs,
+            Conj::No,
+            conj_rhs,
+            get_global_parallelism(),
+        );
+        out
+    }
+
+    /// Returns the Cholesky decomposition of `self`, or an error if it is not positive definite.
+    #[track_caller]
+    #[doc(alias = "llt")]
+    pub fn cholesky(&self) -> Result<Cholesky<E>, CholeskyError> {
+        Cholesky::try_new(self.inner.as_ref(), T::SIDE)
+    }
+
+    /// Returns the Bunch-Kaufman decomposition of `self`.
+    #[track_caller]
+    #[doc(alias = "ldl")]
+    #[doc(alias = "ldlt")]
+    pub fn lblt(&self) -> Lblt<E> {
+        Lblt::new(self.inner.as_ref(), T::SIDE)
+    }
+
+    /// Returns the eigendecomposition of `self`.
+    #[track_caller]
+    #[doc(alias = "hermitian_eigendecomposition")]
+    pub fn selfadjoint_eigendecomposition(&self) -> SelfAdjointEigendecomposition<E> {
+        SelfAdjointEigendecomposition::new(self.inner.as_ref(), T::SIDE)
+    }
+
+    // computes `out = A * rhs`, where `A = S + strict(S)^H` and `S` is the stored half
+    #[track_caller]
+    fn apply_impl(
+        &self,
+        mut out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        conj_lhs: Conj,
+        conj_rhs: Conj,
+        parallelism: Parallelism,
+    ) {
+        use reborrow::*;
+
+        let half = self.inner.as_ref();
+        matmul_with_conj(
+            out.rb_mut(),
+            BlockStructure::Rectangular,
+            half,
+            triangular_structure(T::SIDE),
+            conj_lhs,
+            rhs,
+            BlockStructure::Rectangular,
+            conj_rhs,
+            None,
+            E::faer_one(),
+            parallelism,
+        );
+        matmul_with_conj(
+            out,
+            BlockStructure::Rectangular,
+            half.transpose(),
+            strict_triangular_structure(flip(T::SIDE)),
+            conj_lhs.compose(Conj::Yes),
+            rhs,
+            BlockStructure::Rectangular,
+            conj_rhs,
+            Some(E::faer_one()),
+            E::faer_one(),
+            parallelism,
+        );
+    }
+}
+
+impl<E: ComplexField, T: Triangle> LinOp<E> for SymMat<E, T> {
+    #[inline]
+    fn apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[inline]
+    fn nrows(&self) -> usize {
+        self.dim()
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        self.dim()
+    }
+
+    #[track_caller]
+    fn apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, Conj::No, parallelism);
+    }
+
+    #[track_caller]
+    fn conj_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, Conj::No, parallelism);
+    }
+}
+
+// the transpose of a self-adjoint matrix is its conjugate, and its adjoint is itself
+impl<E: ComplexField, T: Triangle> BiLinOp<E> for SymMat<E, T> {
+    #[inline]
+    fn transpose_apply_req(
+        &self,
+        rhs_ncols: usize,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        _ = (rhs_ncols, parallelism);
+        Ok(StackReq::empty())
+    }
+
+    #[track_caller]
+    fn transpose_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::Yes, Conj::No, parallelism);
+    }
+
+    #[track_caller]
+    fn adjoint_apply(
+        &self,
+        out: MatMut<'_, E>,
+        rhs: MatRef<'_, E>,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        _ = stack;
+        self.apply_impl(out, rhs, Conj::No, Conj::No, parallelism);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex_native::c64, sparse::linalg::solvers::SpSolver};
+    use dyn_stack::GlobalPodBuffer;
+    use equator::assert;
+
+    fn check_tri<T: Triangle>() {
+        let n = 7;
+        let a = Mat::from_fn(n, n, |i, j| {
+            if i == j {
+                c64::new(3.0 + i as f64, 0.5)
+            } else {
+                c64::new((i as f64 - 0.7 * j as f64).sin(), (i * j) as f64 * 0.1)
+            }
+        });
+        let rhs = Mat::from_fn(n, 3, |i, j| c64::new(i as f64, j as f64 - 1.0));
+        let tri = TriMat::<c64, T>::from_full(a.as_ref());
+        let dense = tri.to_dense();
+        for j in 0..n {
+            for i in 0..n {
+                let expected = if is_stored::<T>(i, j) {
+                    a.read(i, j)
+                } else {
+                    c64::new(0.0, 0.0)
+                };
+                assert!(all(
+                    tri.read(i, j) == expected,
+                    dense.read(i, j) == expected
+                ));
+            }
+        }
+
+        let approx_eq = |x: &Mat<c64>, y: &Mat<c64>| (x - y).norm_max() < 1e-10;
+        assert!(approx_eq(&tri.matmul(rhs.as_ref()), &(&dense * &rhs)));
+
+        let par = Parallelism::None;
+        let mut mem = GlobalPodBuffer::new(StackReq::new::<u8>(0));
+        let mut out = Mat::<c64>::zeros(n, 3);
+        tri.transpose_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.transpose() * &rhs)));
+        tri.adjoint_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.adjoint() * &rhs)));
+        tri.conj_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.conjugate() * &rhs)));
+
+        assert!(approx_eq(&(&dense * tri.solve(&rhs)), &rhs));
+        assert!(approx_eq(
+            &(dense.transpose() * tri.solve_transpose(&rhs)),
+            &rhs
+        ));
+        assert!(approx_eq(&(dense.conjugate() * tri.solve_conj(&rhs)), &rhs));
+    }
+
+    fn check_sym<T: Triangle>() {
+        let n = 7;
+        let a = Mat::from_fn(n, n, |i, j| {
+            if i == j {
+                c64::new(2.0 * n as f64 + i as f64, 0.0)
+            } else {
+                c64::new(
+                    (i as f64 - 0.7 * j as f64).sin(),
+                    (i as f64 - j as f64) * 0.1,
+                )
+            }
+        });
+        let rhs = Mat::from_fn(n, 3, |i, j| c64::new(i as f64, j as f64 - 1.0));
+        let sym = SymMat::<c64, T>::from_full(a.as_ref());
+        let dense = sym.to_dense();
+        for j in 0..n {
+            for i in 0..n {
+                let expected = if is_stored::<T>(i, j) {
+                    a.read(i, j)
+                } else {
+                    a.read(j, i).conj()
+                };
+                assert!(all(
+                    sym.read(i, j) == expected,
+                    dense.read(i, j) == expected
+                ));
+            }
+        }
+
+        let approx_eq = |x: &Mat<c64>, y: &Mat<c64>| (x - y).norm_max() < 1e-10;
+        assert!(approx_eq(&sym.matmul(rhs.as_ref()), &(&dense * &rhs)));
+
+        let par = Parallelism::None;
+        let mut mem = GlobalPodBuffer::new(StackReq::new::<u8>(0));
+        let mut out = Mat::<c64>::zeros(n, 3);
+        sym.conj_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.conjugate() * &rhs)));
+        sym.transpose_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.transpose() * &rhs)));
+        sym.adjoint_apply(out.as_mut(), rhs.as_ref(), par, PodStack::new(&mut mem));
+        assert!(approx_eq(&out, &(dense.adjoint() * &rhs)));
+
+        let x = sym.cholesky().unwrap().solve(&rhs);
+        assert!(approx_eq(&(&dense * &x), &rhs));
+        let x = sym.lblt().solve(&rhs);
+        assert!(approx_eq(&(&dense * &x), &rhs));
+
+        // writing through the mirrored half updates the stored half
+        let mut sym = sym;
+        sym.write(0, n - 1, c64::new(1.0, 2.0));
+        assert!(all(
+            sym.read(0, n - 1) == c64::new(1.0, 2.0),
+            sym.read(n - 1, 0) == c64::new(1.0, -2.0),
+        ));
+    }
+
+    #[test]
+    fn test_tri_mat() {
+        check_tri::<Lower>();
+        check_tri::<Upper>();
+    }
+
+    #[test]
+    fn test_sym_mat() {
+        check_sym::<Lower>();
+        check_sym::<Upper>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_tri_mat_write_outside() {
+        let mut tri = TriMat::<f64, Lower>::zeros(3);
+        tri.write(0, 2, 1.0);
+    }
+}
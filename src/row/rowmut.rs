@@ -526,6 +526,28 @@ impl<'a, E: Entity> RowMut<'a, E> {
         unsafe { self.into_const().reverse_cols().const_cast() }
     }
 
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(self, step: usize) -> RowRef<'a, E> {
+        self.into_const().step_by_cols(step)
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols_mut(self, step: usize) -> Self {
+        unsafe { self.into_const().step_by_cols(step).const_cast() }
+    }
+
     /// Returns a view over the subvector starting at column `col_start`, and with number of
     /// columns `ncols`.
     ///
@@ -147,6 +147,34 @@ impl<E: Entity> Row<E> {
         self.as_mut().as_2d_mut()
     }
 
+    /// Returns a value that formats `self` as a matrix according to the given options.
+    ///
+    /// See [`MatRef::fmt_with`] for more details.
+    #[inline]
+    pub fn fmt_with(&self, options: crate::mat::FormatOptions) -> crate::mat::MatFormat<'_, E> {
+        self.as_2d().fmt_with(options)
+    }
+
+    /// Returns a new row vector with the entries of `self` cast to `T`. The imaginary parts
+    /// are discarded when casting complex values to a real type.
+    #[inline]
+    pub fn cast<T: Entity>(&self) -> Row<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.cast_with(crate::mat::ImagPolicy::Discard)
+    }
+
+    /// Returns a new row vector with the entries of `self` cast to `T`, where `policy`
+    /// specifies how the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(&self, policy: crate::mat::ImagPolicy) -> Row<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.as_ref().cast_with(policy)
+    }
+
     /// Returns raw pointers to the element at the given index.
     #[inline(always)]
     pub fn ptr_at(&self, col: usize) -> GroupFor<E, *const E::Unit> {
@@ -768,6 +796,28 @@ impl<E: Entity> Row<E> {
         self.as_mut().reverse_cols_mut()
     }
 
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(&self, step: usize) -> RowRef<'_, E> {
+        self.as_ref().step_by_cols(step)
+    }
+
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols_mut(&mut self, step: usize) -> RowMut<'_, E> {
+        self.as_mut().step_by_cols_mut(step)
+    }
+
     /// Returns an owning [`Row`] of the data
     #[inline]
     pub fn to_owned(&self) -> Row<E::Canonical>
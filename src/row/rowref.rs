@@ -1,5 +1,5 @@
 use super::*;
-use crate::{assert, col::ColRef, debug_assert};
+use crate::{assert, col::ColRef, debug_assert, utils::DivCeil};
 
 /// Immutable view over a row vector, similar to an immutable reference to a strided [prim@slice].
 ///
@@ -107,6 +107,37 @@ impl<'a, E: Entity> RowRef<'a, E> {
         unsafe { crate::mat::from_raw_parts(self.as_ptr(), 1, ncols, isize::MAX, col_stride) }
     }
 
+    /// Returns a value that formats `self` as a matrix according to the given options.
+    ///
+    /// See [`MatRef::fmt_with`] for more details.
+    #[inline]
+    pub fn fmt_with(self, options: crate::mat::FormatOptions) -> crate::mat::MatFormat<'a, E> {
+        self.as_2d().fmt_with(options)
+    }
+
+    /// Returns a new row vector with the entries of `self` cast to `T`. The imaginary parts
+    /// are discarded when casting complex values to a real type.
+    #[inline]
+    pub fn cast<T: Entity>(self) -> Row<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        self.cast_with(crate::mat::ImagPolicy::Discard)
+    }
+
+    /// Returns a new row vector with the entries of `self` cast to `T`, where `policy`
+    /// specifies how the imaginary parts are handled when casting complex values to a real type.
+    #[inline]
+    pub fn cast_with<T: Entity>(self, policy: crate::mat::ImagPolicy) -> Row<T>
+    where
+        E: crate::mat::Cast<T>,
+    {
+        crate::zipped!(self).map(
+            #[inline(always)]
+            |crate::unzipped!(x)| x.read().cast_with(policy),
+        )
+    }
+
     /// Returns raw pointers to the element at the given index.
     #[inline(always)]
     pub fn ptr_at(self, col: usize) -> GroupFor<E, *const E::Unit> {
@@ -347,6 +378,20 @@ impl<'a, E: Entity> RowRef<'a, E> {
         unsafe { Self::__from_raw_parts(ptr, ncols, col_stride) }
     }
 
+    /// Returns a view over every `step`-th column of `self`, starting with the first column.
+    ///
+    /// # Panics
+    /// The function panics if `step == 0`.
+    #[inline]
+    #[track_caller]
+    #[must_use]
+    pub fn step_by_cols(self, step: usize) -> Self {
+        assert!(step > 0);
+        let ncols = self.ncols().msrv_div_ceil(step);
+        let col_stride = self.col_stride().wrapping_mul(step as isize);
+        unsafe { Self::__from_raw_parts(self.as_ptr(), ncols, col_stride) }
+    }
+
     /// Returns a view over the subvector starting at column `col_start`, and with number of
     /// columns `ncols`.
     ///
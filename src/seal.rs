@@ -11,6 +11,9 @@ impl<E: Entity> Seal for crate::col::ColMut<'_, E> {}
 impl<E: Entity> Seal for crate::row::RowRef<'_, E> {}
 impl<E: Entity> Seal for crate::row::RowMut<'_, E> {}
 
+impl Seal for crate::mat::Lower {}
+impl Seal for crate::mat::Upper {}
+
 impl Seal for i32 {}
 impl Seal for i64 {}
 impl Seal for i128 {}
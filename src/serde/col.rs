@@ -0,0 +1,225 @@
+//! Serde implementations for Col and Row
+
+use faer_entity::Entity;
+use serde::{
+    de::Error, ser::SerializeSeq, ser::SerializeStruct, Deserialize, Serialize, Serializer,
+};
+
+use crate::{Col, ColMut, ColRef, Row, RowMut, RowRef};
+
+struct ColSequenceSerializer<'a, E: Entity>(ColRef<'a, E>);
+
+impl<'a, E: Entity> Serialize for ColSequenceSerializer<'a, E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut seq = s.serialize_seq(Some(self.0.nrows()))?;
+        for i in 0..self.0.nrows() {
+            seq.serialize_element(&self.0.read(i))?;
+        }
+        seq.end()
+    }
+}
+
+impl<E: Entity> Serialize for ColRef<'_, E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut structure = s.serialize_struct("Col", 2)?;
+        structure.serialize_field("nrows", &self.nrows())?;
+        structure.serialize_field("data", &ColSequenceSerializer(*self))?;
+        structure.end()
+    }
+}
+
+impl<E: Entity> Serialize for ColMut<'_, E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<E: Entity> Serialize for Col<E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<E: Entity> Serialize for RowRef<'_, E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        let mut structure = s.serialize_struct("Row", 2)?;
+        structure.serialize_field("ncols", &self.ncols())?;
+        structure.serialize_field("data", &ColSequenceSerializer(self.transpose()))?;
+        structure.end()
+    }
+}
+
+impl<E: Entity> Serialize for RowMut<'_, E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<E: Entity> Serialize for Row<E>
+where
+    E: Serialize,
+{
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Col")]
+struct ColRepr<E> {
+    nrows: usize,
+    data: alloc::vec::Vec<E>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Row")]
+struct RowRepr<E> {
+    ncols: usize,
+    data: alloc::vec::Vec<E>,
+}
+
+fn check_len<Err: Error>(len: usize, expected: usize) -> Result<(), Err> {
+    if len != expected {
+        return Err(Err::invalid_length(
+            len,
+            &alloc::format!("{} elements", expected).as_str(),
+        ));
+    }
+    Ok(())
+}
+
+impl<'a, E: Entity> Deserialize<'a> for Col<E>
+where
+    E: Deserialize<'a>,
+{
+    fn deserialize<D>(d: D) -> Result<Self, <D as serde::Deserializer<'a>>::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let repr = ColRepr::<E>::deserialize(d)?;
+        check_len(repr.data.len(), repr.nrows)?;
+        Ok(Col::from_fn(repr.nrows, |i| repr.data[i]))
+    }
+}
+
+impl<'a, E: Entity> Deserialize<'a> for Row<E>
+where
+    E: Deserialize<'a>,
+{
+    fn deserialize<D>(d: D) -> Result<Self, <D as serde::Deserializer<'a>>::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let repr = RowRepr::<E>::deserialize(d)?;
+        check_len(repr.data.len(), repr.ncols)?;
+        Ok(Row::from_fn(repr.ncols, |j| repr.data[j]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn col_row_serialization() {
+        let col = Col::from_fn(3, |i| i as f64);
+        assert_tokens(
+            &col,
+            &[
+                Token::Struct {
+                    name: "Col",
+                    len: 2,
+                },
+                Token::Str("nrows"),
+                Token::U64(3),
+                Token::Str("data"),
+                Token::Seq { len: Some(3) },
+                Token::F64(0.0),
+                Token::F64(1.0),
+                Token::F64(2.0),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        let row = Row::from_fn(2, |j| j as f64 + 0.5);
+        assert_tokens(
+            &row,
+            &[
+                Token::Struct {
+                    name: "Row",
+                    len: 2,
+                },
+                Token::Str("ncols"),
+                Token::U64(2),
+                Token::Str("data"),
+                Token::Seq { len: Some(2) },
+                Token::F64(0.5),
+                Token::F64(1.5),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn col_serialization_errors_length() {
+        assert_de_tokens_error::<Col<f64>>(
+            &[
+                Token::Struct {
+                    name: "Col",
+                    len: 2,
+                },
+                Token::Str("nrows"),
+                Token::U64(3),
+                Token::Str("data"),
+                Token::Seq { len: Some(2) },
+                Token::F64(0.0),
+                Token::F64(1.0),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+            "invalid length 2, expected 3 elements",
+        );
+    }
+}
@@ -1 +1,4 @@
+mod col;
 mod mat;
+pub(crate) mod perm;
+mod sparse;
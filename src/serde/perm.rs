@@ -0,0 +1,116 @@
+//! Serde implementations for Perm
+
+use serde::{de::Error, ser::SerializeStruct, Deserialize, Serialize, Serializer};
+
+use crate::{
+    perm::{Perm, PermRef},
+    Index, SignedIndex,
+};
+
+impl<I: Index + Serialize> Serialize for PermRef<'_, I> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        // the inverse is recomputed on deserialization
+        let mut structure = s.serialize_struct("Perm", 1)?;
+        structure.serialize_field("forward", self.arrays().0)?;
+        structure.end()
+    }
+}
+
+impl<I: Index + Serialize> Serialize for Perm<I> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Perm")]
+struct PermRepr<I> {
+    forward: alloc::vec::Vec<I>,
+}
+
+// returns the inverse of `forward`, or an error if it is not a permutation
+pub(crate) fn inverse_of<I: Index, Err: Error>(forward: &[I]) -> Result<alloc::vec::Vec<I>, Err> {
+    let n = forward.len();
+    if n > I::Signed::MAX.zx() {
+        return Err(Err::custom(
+            "permutation length exceeds the index type range",
+        ));
+    }
+    let none = I::truncate(usize::MAX);
+    let mut inverse = alloc::vec![none; n];
+    for (i, &p) in forward.iter().enumerate() {
+        let p = p.zx();
+        if p >= n || inverse[p] != none {
+            return Err(Err::custom("invalid permutation"));
+        }
+        inverse[p] = I::truncate(i);
+    }
+    Ok(inverse)
+}
+
+impl<'a, I: Index + Deserialize<'a>> Deserialize<'a> for Perm<I> {
+    fn deserialize<D>(d: D) -> Result<Self, <D as serde::Deserializer<'a>>::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let repr = PermRepr::<I>::deserialize(d)?;
+        let inverse = inverse_of(&repr.forward)?;
+        Ok(Perm::new_checked(
+            repr.forward.into_boxed_slice(),
+            inverse.into_boxed_slice(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn perm_serialization() {
+        let perm = Perm::<u32>::new_checked(
+            alloc::vec![2, 0, 1].into_boxed_slice(),
+            alloc::vec![1, 2, 0].into_boxed_slice(),
+        );
+        assert_tokens(
+            &perm,
+            &[
+                Token::Struct {
+                    name: "Perm",
+                    len: 1,
+                },
+                Token::Str("forward"),
+                Token::Seq { len: Some(3) },
+                Token::U32(2),
+                Token::U32(0),
+                Token::U32(1),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        assert_de_tokens_error::<Perm<u32>>(
+            &[
+                Token::Struct {
+                    name: "Perm",
+                    len: 1,
+                },
+                Token::Str("forward"),
+                Token::Seq { len: Some(3) },
+                Token::U32(2),
+                Token::U32(0),
+                Token::U32(2),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+            "invalid permutation",
+        );
+    }
+}
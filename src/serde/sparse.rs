@@ -0,0 +1,408 @@
+//! Serde implementations for SparseColMat and SparseRowMat
+
+use faer_entity::Entity;
+use serde::{
+    de::Error, ser::SerializeSeq, ser::SerializeStruct, Deserialize, Serialize, Serializer,
+};
+
+use crate::{
+    sparse::{
+        SparseColMat, SparseColMatMut, SparseColMatRef, SparseRowMat, SparseRowMatMut,
+        SparseRowMatRef, SymbolicSparseColMat, SymbolicSparseRowMat,
+    },
+    utils::{slice::SliceGroup, vec::VecGroup},
+    Index, SignedIndex,
+};
+
+// the matrices are always serialized in compressed form, with the columns (resp. rows) that are
+// stored contiguously, so that uncompressed matrices don't waste space for their unused entries
+
+struct PtrsSerializer<'a, I: Index, E: Entity>(SparseColMatRef<'a, I, E>);
+struct IndicesSerializer<'a, I: Index, E: Entity>(SparseColMatRef<'a, I, E>);
+struct ValuesSerializer<'a, I: Index, E: Entity>(SparseColMatRef<'a, I, E>);
+
+impl<I: Index + Serialize, E: Entity> Serialize for PtrsSerializer<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mat = self.0;
+        let mut seq = s.serialize_seq(Some(mat.ncols() + 1))?;
+        let mut ptr = 0usize;
+        seq.serialize_element(&I::truncate(ptr))?;
+        for j in 0..mat.ncols() {
+            ptr += mat.col_range(j).len();
+            seq.serialize_element(&I::truncate(ptr))?;
+        }
+        seq.end()
+    }
+}
+
+impl<I: Index + Serialize, E: Entity> Serialize for IndicesSerializer<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mat = self.0;
+        let mut seq = s.serialize_seq(Some(mat.compute_nnz()))?;
+        for j in 0..mat.ncols() {
+            for i in mat.row_indices_of_col_raw(j) {
+                seq.serialize_element(i)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<I: Index, E: Entity + Serialize> Serialize for ValuesSerializer<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mat = self.0;
+        let mut seq = s.serialize_seq(Some(mat.compute_nnz()))?;
+        for j in 0..mat.ncols() {
+            let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+            for k in 0..values.len() {
+                seq.serialize_element(&values.read(k))?;
+            }
+        }
+        seq.end()
+    }
+}
+
+fn serialize_compressed<I: Index + Serialize, E: Entity + Serialize, S: Serializer>(
+    s: S,
+    name: &'static str,
+    fields: [&'static str; 5],
+    nrows: usize,
+    ncols: usize,
+    mat: SparseColMatRef<'_, I, E>,
+) -> Result<S::Ok, S::Error> {
+    let mut structure = s.serialize_struct(name, 5)?;
+    structure.serialize_field(fields[0], &nrows)?;
+    structure.serialize_field(fields[1], &ncols)?;
+    structure.serialize_field(fields[2], &PtrsSerializer(mat))?;
+    structure.serialize_field(fields[3], &IndicesSerializer(mat))?;
+    structure.serialize_field(fields[4], &ValuesSerializer(mat))?;
+    structure.end()
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseColMatRef<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serialize_compressed(
+            s,
+            "SparseColMat",
+            ["nrows", "ncols", "col_ptrs", "row_indices", "values"],
+            self.nrows(),
+            self.ncols(),
+            *self,
+        )
+    }
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseColMatMut<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseColMat<I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseRowMatRef<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        serialize_compressed(
+            s,
+            "SparseRowMat",
+            ["nrows", "ncols", "row_ptrs", "col_indices", "values"],
+            self.nrows(),
+            self.ncols(),
+            self.transpose(),
+        )
+    }
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseRowMatMut<'_, I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+impl<I: Index + Serialize, E: Entity + Serialize> Serialize for SparseRowMat<I, E> {
+    fn serialize<S>(&self, s: S) -> Result<<S as Serializer>::Ok, <S as Serializer>::Error>
+    where
+        S: Serializer,
+    {
+        self.as_ref().serialize(s)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "SparseColMat")]
+struct SparseColMatRepr<I, E> {
+    nrows: usize,
+    ncols: usize,
+    col_ptrs: alloc::vec::Vec<I>,
+    row_indices: alloc::vec::Vec<I>,
+    values: alloc::vec::Vec<E>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "SparseRowMat")]
+struct SparseRowMatRepr<I, E> {
+    nrows: usize,
+    ncols: usize,
+    row_ptrs: alloc::vec::Vec<I>,
+    col_indices: alloc::vec::Vec<I>,
+    values: alloc::vec::Vec<E>,
+}
+
+// checks the invariants of a compressed matrix with `outer` columns (resp. rows) and `inner` rows
+// (resp. columns), so that invalid data is reported as an error instead of a panic
+fn check_compressed<I: Index, Err: Error>(
+    inner: usize,
+    outer: usize,
+    ptrs: &[I],
+    indices: &[I],
+    nvalues: usize,
+) -> Result<(), Err> {
+    let max = I::Signed::MAX.zx();
+    if inner > max || outer > max {
+        return Err(Err::custom("matrix dimensions exceed the index type range"));
+    }
+    if ptrs.len() != outer + 1 {
+        return Err(Err::invalid_length(
+            ptrs.len(),
+            &alloc::format!("{} pointers", outer + 1).as_str(),
+        ));
+    }
+    if ptrs[0].zx() != 0 || ptrs[outer].zx() != indices.len() {
+        return Err(Err::custom(
+            "the pointers must start at zero and end at the number of stored entries",
+        ));
+    }
+    if nvalues != indices.len() {
+        return Err(Err::invalid_length(
+            nvalues,
+            &alloc::format!("{} values", indices.len()).as_str(),
+        ));
+    }
+    for j in 0..outer {
+        let (start, end) = (ptrs[j].zx(), ptrs[j + 1].zx());
+        if start > end {
+            return Err(Err::custom("the pointers must be non decreasing"));
+        }
+        let indices = &indices[start..end];
+        for (k, &i) in indices.iter().enumerate() {
+            if i.zx() >= inner {
+                return Err(Err::custom("index out of bounds"));
+            }
+            if k > 0 && indices[k - 1] > i {
+                return Err(Err::custom("the indices must be sorted"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn into_values<E: Entity>(values: alloc::vec::Vec<E>) -> VecGroup<E> {
+    let mut group = VecGroup::<E>::new();
+    group.reserve_exact(values.len());
+    for value in values {
+        group.push(value.faer_into_units());
+    }
+    group
+}
+
+impl<'a, I: Index + Deserialize<'a>, E: Entity + Deserialize<'a>> Deserialize<'a>
+    for SparseColMat<I, E>
+{
+    fn deserialize<D>(d: D) -> Result<Self, <D as serde::Deserializer<'a>>::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let repr = SparseColMatRepr::<I, E>::deserialize(d)?;
+        check_compressed(
+            repr.nrows,
+            repr.ncols,
+            &repr.col_ptrs,
+            &repr.row_indices,
+            repr.values.len(),
+        )?;
+        let symbolic = SymbolicSparseColMat::new_checked(
+            repr.nrows,
+            repr.ncols,
+            repr.col_ptrs,
+            None,
+            repr.row_indices,
+        );
+        Ok(SparseColMat::new(
+            symbolic,
+            into_values(repr.values).into_inner(),
+        ))
+    }
+}
+
+impl<'a, I: Index + Deserialize<'a>, E: Entity + Deserialize<'a>> Deserialize<'a>
+    for SparseRowMat<I, E>
+{
+    fn deserialize<D>(d: D) -> Result<Self, <D as serde::Deserializer<'a>>::Error>
+    where
+        D: serde::Deserializer<'a>,
+    {
+        let repr = SparseRowMatRepr::<I, E>::deserialize(d)?;
+        check_compressed(
+            repr.ncols,
+            repr.nrows,
+            &repr.row_ptrs,
+            &repr.col_indices,
+            repr.values.len(),
+        )?;
+        let symbolic = SymbolicSparseRowMat::new_checked(
+            repr.nrows,
+            repr.ncols,
+            repr.row_ptrs,
+            None,
+            repr.col_indices,
+        );
+        Ok(SparseRowMat::new(
+            symbolic,
+            into_values(repr.values).into_inner(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+    #[test]
+    fn sparse_serialization() {
+        let mat = SparseColMat::<u32, f64>::try_new_from_triplets(
+            3,
+            2,
+            &[(0, 0, 1.0), (2, 0, 2.0), (1, 1, 3.0)],
+        )
+        .unwrap();
+        assert_tokens(
+            &mat,
+            &[
+                Token::Struct {
+                    name: "SparseColMat",
+                    len: 5,
+                },
+                Token::Str("nrows"),
+                Token::U64(3),
+                Token::Str("ncols"),
+                Token::U64(2),
+                Token::Str("col_ptrs"),
+                Token::Seq { len: Some(3) },
+                Token::U32(0),
+                Token::U32(2),
+                Token::U32(3),
+                Token::SeqEnd,
+                Token::Str("row_indices"),
+                Token::Seq { len: Some(3) },
+                Token::U32(0),
+                Token::U32(2),
+                Token::U32(1),
+                Token::SeqEnd,
+                Token::Str("values"),
+                Token::Seq { len: Some(3) },
+                Token::F64(1.0),
+                Token::F64(2.0),
+                Token::F64(3.0),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+
+        let mat = mat.to_row_major().unwrap();
+        assert_tokens(
+            &mat,
+            &[
+                Token::Struct {
+                    name: "SparseRowMat",
+                    len: 5,
+                },
+                Token::Str("nrows"),
+                Token::U64(3),
+                Token::Str("ncols"),
+                Token::U64(2),
+                Token::Str("row_ptrs"),
+                Token::Seq { len: Some(4) },
+                Token::U32(0),
+                Token::U32(1),
+                Token::U32(2),
+                Token::U32(3),
+                Token::SeqEnd,
+                Token::Str("col_indices"),
+                Token::Seq { len: Some(3) },
+                Token::U32(0),
+                Token::U32(1),
+                Token::U32(0),
+                Token::SeqEnd,
+                Token::Str("values"),
+                Token::Seq { len: Some(3) },
+                Token::F64(1.0),
+                Token::F64(3.0),
+                Token::F64(2.0),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn sparse_serialization_errors_unsorted() {
+        assert_de_tokens_error::<SparseColMat<u32, f64>>(
+            &[
+                Token::Struct {
+                    name: "SparseColMat",
+                    len: 5,
+                },
+                Token::Str("nrows"),
+                Token::U64(3),
+                Token::Str("ncols"),
+                Token::U64(1),
+                Token::Str("col_ptrs"),
+                Token::Seq { len: Some(2) },
+                Token::U32(0),
+                Token::U32(2),
+                Token::SeqEnd,
+                Token::Str("row_indices"),
+                Token::Seq { len: Some(2) },
+                Token::U32(2),
+                Token::U32(0),
+                Token::SeqEnd,
+                Token::Str("values"),
+                Token::Seq { len: Some(2) },
+                Token::F64(1.0),
+                Token::F64(2.0),
+                Token::SeqEnd,
+                Token::StructEnd,
+            ],
+            "the indices must be sorted",
+        );
+    }
+}
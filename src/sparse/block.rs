@@ -0,0 +1,512 @@
+use super::*;
+use crate::assert;
+
+enum Block<'a, I: Index, E: Entity> {
+    ColMajor(SparseColMatRef<'a, I, E>),
+    RowMajor(SparseRowMatRef<'a, I, E>),
+}
+
+impl<I: Index, E: Entity> Copy for Block<'_, I, E> {}
+impl<I: Index, E: Entity> Clone for Block<'_, I, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, I: Index, E: Entity> Block<'a, I, E> {
+    #[inline]
+    fn nrows(&self) -> usize {
+        match self {
+            Block::ColMajor(block) => block.nrows(),
+            Block::RowMajor(block) => block.nrows(),
+        }
+    }
+
+    #[inline]
+    fn ncols(&self) -> usize {
+        match self {
+            Block::ColMajor(block) => block.ncols(),
+            Block::RowMajor(block) => block.ncols(),
+        }
+    }
+
+    #[inline]
+    fn transpose(self) -> Self {
+        match self {
+            Block::ColMajor(block) => Block::RowMajor(block.transpose()),
+            Block::RowMajor(block) => Block::ColMajor(block.transpose()),
+        }
+    }
+}
+
+/// Builder of sparse matrices made of sparse blocks, e.g. for saddle-point systems.
+///
+/// The blocks are arranged in a grid of block rows and block columns. All the blocks in the same
+/// block row must have the same number of rows, and all the blocks in the same block column must
+/// have the same number of columns. Blocks that are never inserted are treated as zero, and the
+/// dimensions of a block row or block column that contains no blocks must be set explicitly.
+///
+/// Blocks may be stored in either column-major or row-major format, so that transposed views
+/// can be inserted without converting them first. The values are copied as-is, and duplicate
+/// entries within a block are preserved.
+///
+/// # Example
+/// ```
+/// use faer::sparse::{SparseBlockBuilder, SparseColMat};
+///
+/// let A = SparseColMat::<usize, f64>::try_new_from_triplets(
+///     2,
+///     2,
+///     &[(0, 0, 4.0), (1, 0, 1.0), (0, 1, 1.0), (1, 1, 3.0)],
+/// )
+/// .unwrap();
+/// let B = SparseColMat::<usize, f64>::try_new_from_triplets(1, 2, &[(0, 0, 1.0), (0, 1, 2.0)])
+///     .unwrap();
+///
+/// // [[A, B^T], [B, 0]]
+/// let mut builder = SparseBlockBuilder::new(2, 2);
+/// builder.insert(0, 0, A.as_ref());
+/// builder.insert_row_major(0, 1, B.as_ref().transpose());
+/// builder.insert(1, 0, B.as_ref());
+///
+/// let K = builder.try_build_col_major().unwrap();
+/// assert_eq!(K.nrows(), 3);
+/// assert_eq!(K.ncols(), 3);
+/// assert_eq!(K.col_ptrs(), &[0, 3, 6, 8]);
+/// assert_eq!(K.row_indices(), &[0, 1, 2, 0, 1, 2, 0, 1]);
+/// assert_eq!(K.values(), &[4.0, 1.0, 1.0, 1.0, 3.0, 2.0, 1.0, 2.0]);
+/// ```
+pub struct SparseBlockBuilder<'a, I: Index, E: Entity> {
+    block_nrows: alloc::vec::Vec<Option<usize>>,
+    block_ncols: alloc::vec::Vec<Option<usize>>,
+    // column-major grid of blocks
+    blocks: alloc::vec::Vec<Option<Block<'a, I, E>>>,
+}
+
+impl<'a, I: Index, E: Entity> SparseBlockBuilder<'a, I, E> {
+    /// Creates a new builder with the given number of block rows and block columns, where all
+    /// the blocks are initially zero.
+    #[inline]
+    pub fn new(n_block_rows: usize, n_block_cols: usize) -> Self {
+        Self {
+            block_nrows: alloc::vec![None; n_block_rows],
+            block_ncols: alloc::vec![None; n_block_cols],
+            blocks: alloc::vec![None; n_block_rows * n_block_cols],
+        }
+    }
+
+    /// Returns the number of block rows.
+    #[inline]
+    pub fn n_block_rows(&self) -> usize {
+        self.block_nrows.len()
+    }
+
+    /// Returns the number of block columns.
+    #[inline]
+    pub fn n_block_cols(&self) -> usize {
+        self.block_ncols.len()
+    }
+
+    /// Sets the number of rows of the block row at index `i`.
+    ///
+    /// # Panics
+    /// Panics if `i` is out of bounds, or if the block row already has a different number of
+    /// rows.
+    #[track_caller]
+    pub fn set_block_nrows(&mut self, i: usize, nrows: usize) {
+        assert!(i < self.n_block_rows());
+        let size = &mut self.block_nrows[i];
+        if let Some(size) = *size {
+            assert!(size == nrows);
+        }
+        *size = Some(nrows);
+    }
+
+    /// Sets the number of columns of the block column at index `j`.
+    ///
+    /// # Panics
+    /// Panics if `j` is out of bounds, or if the block column already has a different number of
+    /// columns.
+    #[track_caller]
+    pub fn set_block_ncols(&mut self, j: usize, ncols: usize) {
+        assert!(j < self.n_block_cols());
+        let size = &mut self.block_ncols[j];
+        if let Some(size) = *size {
+            assert!(size == ncols);
+        }
+        *size = Some(ncols);
+    }
+
+    #[track_caller]
+    fn insert_impl(&mut self, i: usize, j: usize, block: Block<'a, I, E>) {
+        assert!(all(i < self.n_block_rows(), j < self.n_block_cols()));
+        self.set_block_nrows(i, block.nrows());
+        self.set_block_ncols(j, block.ncols());
+        let n_block_rows = self.n_block_rows();
+        self.blocks[i + j * n_block_rows] = Some(block);
+    }
+
+    /// Sets the block at position `(i, j)` to the column-major matrix `block`, replacing the
+    /// previous block if there was one.
+    ///
+    /// # Panics
+    /// Panics if `(i, j)` is out of bounds, or if the dimensions of `block` don't match the
+    /// dimensions of the block row `i` or the block column `j`.
+    #[track_caller]
+    pub fn insert(&mut self, i: usize, j: usize, block: SparseColMatRef<'a, I, E>) {
+        self.insert_impl(i, j, Block::ColMajor(block));
+    }
+
+    /// Sets the block at position `(i, j)` to the row-major matrix `block`, replacing the
+    /// previous block if there was one.
+    ///
+    /// # Panics
+    /// Panics if `(i, j)` is out of bounds, or if the dimensions of `block` don't match the
+    /// dimensions of the block row `i` or the block column `j`.
+    #[track_caller]
+    pub fn insert_row_major(&mut self, i: usize, j: usize, block: SparseRowMatRef<'a, I, E>) {
+        self.insert_impl(i, j, Block::RowMajor(block));
+    }
+
+    /// Removes the block at position `(i, j)`, so that it is treated as zero. The dimensions of
+    /// its block row and block column are kept.
+    ///
+    /// # Panics
+    /// Panics if `(i, j)` is out of bounds.
+    #[track_caller]
+    pub fn remove(&mut self, i: usize, j: usize) {
+        assert!(all(i < self.n_block_rows(), j < self.n_block_cols()));
+        let n_block_rows = self.n_block_rows();
+        self.blocks[i + j * n_block_rows] = None;
+    }
+
+    #[track_caller]
+    fn sizes(sizes: &[Option<usize>]) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        let sizes = try_collect(sizes.iter().map(|size| {
+            assert!(size.is_some());
+            size.unwrap()
+        }))?;
+        Ok(sizes)
+    }
+
+    /// Assembles the blocks into a column-major matrix.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of a block row or a block column that contains no blocks were
+    /// not set.
+    #[track_caller]
+    pub fn try_build_col_major(&self) -> Result<SparseColMat<I, E>, FaerError> {
+        let block_nrows = Self::sizes(&self.block_nrows)?;
+        let block_ncols = Self::sizes(&self.block_ncols)?;
+        assemble(&block_nrows, &block_ncols, &self.blocks)
+    }
+
+    /// Assembles the blocks into a row-major matrix.
+    ///
+    /// # Panics
+    /// Panics if the dimensions of a block row or a block column that contains no blocks were
+    /// not set.
+    #[track_caller]
+    pub fn try_build_row_major(&self) -> Result<SparseRowMat<I, E>, FaerError> {
+        let block_nrows = Self::sizes(&self.block_nrows)?;
+        let block_ncols = Self::sizes(&self.block_ncols)?;
+
+        // assemble the transpose in column-major format
+        let n_block_rows = self.n_block_rows();
+        let n_block_cols = self.n_block_cols();
+        let mut blocks = alloc::vec::Vec::new();
+        blocks
+            .try_reserve_exact(self.blocks.len())
+            .map_err(|_| FaerError::OutOfMemory)?;
+        for i in 0..n_block_rows {
+            for j in 0..n_block_cols {
+                blocks.push(self.blocks[i + j * n_block_rows].map(Block::transpose));
+            }
+        }
+        Ok(assemble(&block_ncols, &block_nrows, &blocks)?.into_transpose())
+    }
+}
+
+fn offsets(sizes: &[usize]) -> Result<alloc::vec::Vec<usize>, FaerError> {
+    let mut offsets = try_zeroed::<usize>(sizes.len() + 1)?;
+    for (k, &size) in sizes.iter().enumerate() {
+        offsets[k + 1] = offsets[k]
+            .checked_add(size)
+            .ok_or(FaerError::IndexOverflow)?;
+    }
+    Ok(offsets)
+}
+
+/// Returns the column pointers and row indices of a row-major matrix when stored in column-major
+/// format, along with the position of each entry in the original storage.
+fn col_major_structure<I: Index>(
+    block: SymbolicSparseRowMatRef<'_, I>,
+) -> Result<
+    (
+        alloc::vec::Vec<usize>,
+        alloc::vec::Vec<I>,
+        alloc::vec::Vec<usize>,
+    ),
+    FaerError,
+> {
+    let m = block.nrows();
+    let n = block.ncols();
+
+    let mut col_ptr = try_zeroed::<usize>(n + 1)?;
+    for i in 0..m {
+        for &j in block.col_indices_of_row_raw(i) {
+            col_ptr[j.zx() + 1] += 1;
+        }
+    }
+    for j in 0..n {
+        col_ptr[j + 1] += col_ptr[j];
+    }
+
+    let nnz = col_ptr[n];
+    let mut row_ind = try_zeroed::<I>(nnz)?;
+    let mut pos = try_zeroed::<usize>(nnz)?;
+    let mut next = try_collect(col_ptr[..n].iter().copied())?;
+    for i in 0..m {
+        for (k, &j) in zip(block.row_range(i), block.col_indices_of_row_raw(i)) {
+            let next = &mut next[j.zx()];
+            row_ind[*next] = I::truncate(i);
+            pos[*next] = k;
+            *next += 1;
+        }
+    }
+
+    Ok((col_ptr, row_ind, pos))
+}
+
+fn assemble<I: Index, E: Entity>(
+    block_nrows: &[usize],
+    block_ncols: &[usize],
+    blocks: &[Option<Block<'_, I, E>>],
+) -> Result<SparseColMat<I, E>, FaerError> {
+    let n_block_rows = block_nrows.len();
+    let n_block_cols = block_ncols.len();
+
+    let row_offsets = offsets(block_nrows)?;
+    let col_offsets = offsets(block_ncols)?;
+    let m = row_offsets[n_block_rows];
+    let n = col_offsets[n_block_cols];
+    if m > I::Signed::MAX.zx() || n > I::Signed::MAX.zx() {
+        return Err(FaerError::IndexOverflow);
+    }
+
+    let mut row_major = alloc::vec::Vec::new();
+    row_major
+        .try_reserve_exact(blocks.len())
+        .map_err(|_| FaerError::OutOfMemory)?;
+    for block in blocks {
+        row_major.push(match block {
+            Some(Block::RowMajor(block)) => Some(col_major_structure(block.symbolic())?),
+            _ => None,
+        });
+    }
+
+    let mut col_ptrs = try_zeroed::<I>(n + 1)?;
+    let mut nnz = 0usize;
+    for bj in 0..n_block_cols {
+        for j in 0..block_ncols[bj] {
+            for bi in 0..n_block_rows {
+                let idx = bi + bj * n_block_rows;
+                nnz += match (&blocks[idx], &row_major[idx]) {
+                    (Some(Block::ColMajor(block)), _) => block.row_indices_of_col_raw(j).len(),
+                    (_, Some((col_ptr, _, _))) => col_ptr[j + 1] - col_ptr[j],
+                    _ => 0,
+                };
+            }
+            if nnz > I::Signed::MAX.zx() {
+                return Err(FaerError::IndexOverflow);
+            }
+            col_ptrs[col_offsets[bj] + j + 1] = I::truncate(nnz);
+        }
+    }
+
+    let mut row_indices = try_zeroed::<I>(nnz)?;
+    let mut values = VecGroup::<E>::new();
+    values
+        .try_reserve_exact(nnz)
+        .map_err(|_| FaerError::OutOfMemory)?;
+    values.resize(nnz, unsafe { core::mem::zeroed() });
+
+    let mut nnz = 0usize;
+    let mut values_mut = values.as_slice_mut();
+    for bj in 0..n_block_cols {
+        for j in 0..block_ncols[bj] {
+            for bi in 0..n_block_rows {
+                let idx = bi + bj * n_block_rows;
+                let row_offset = row_offsets[bi];
+                match (&blocks[idx], &row_major[idx]) {
+                    (Some(Block::ColMajor(block)), _) => {
+                        let src = SliceGroup::<E>::new(block.values_of_col(j));
+                        for (k, &i) in block.row_indices_of_col_raw(j).iter().enumerate() {
+                            row_indices[nnz] = I::truncate(row_offset + i.zx());
+                            values_mut.write(nnz, src.read(k));
+                            nnz += 1;
+                        }
+                    }
+                    (Some(Block::RowMajor(block)), Some((col_ptr, row_ind, pos))) => {
+                        let src = SliceGroup::<E>::new(block.values());
+                        for k in col_ptr[j]..col_ptr[j + 1] {
+                            row_indices[nnz] = I::truncate(row_offset + row_ind[k].zx());
+                            values_mut.write(nnz, src.read(pos[k]));
+                            nnz += 1;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    Ok(SparseColMat::<I, E>::new(
+        SymbolicSparseColMat::<I>::new_unsorted_checked(m, n, col_ptrs, None, row_indices),
+        values.into_inner(),
+    ))
+}
+
+/// Returns the horizontal concatenation `[A_0, A_1, ...]` of the given matrices.
+///
+/// # Panics
+/// Panics if the matrices don't all have the same number of rows.
+#[track_caller]
+pub fn hstack<I: Index, E: Entity>(
+    blocks: &[SparseColMatRef<'_, I, E>],
+) -> Result<SparseColMat<I, E>, FaerError> {
+    let mut builder = SparseBlockBuilder::new(1, blocks.len());
+    if blocks.is_empty() {
+        builder.set_block_nrows(0, 0);
+    }
+    for (j, &block) in blocks.iter().enumerate() {
+        builder.insert(0, j, block);
+    }
+    builder.try_build_col_major()
+}
+
+/// Returns the vertical concatenation `[A_0; A_1; ...]` of the given matrices.
+///
+/// # Panics
+/// Panics if the matrices don't all have the same number of columns.
+#[track_caller]
+pub fn vstack<I: Index, E: Entity>(
+    blocks: &[SparseColMatRef<'_, I, E>],
+) -> Result<SparseColMat<I, E>, FaerError> {
+    let mut builder = SparseBlockBuilder::new(blocks.len(), 1);
+    if blocks.is_empty() {
+        builder.set_block_ncols(0, 0);
+    }
+    for (i, &block) in blocks.iter().enumerate() {
+        builder.insert(i, 0, block);
+    }
+    builder.try_build_col_major()
+}
+
+/// Returns the block diagonal matrix whose diagonal blocks are the given matrices.
+#[track_caller]
+pub fn block_diag<I: Index, E: Entity>(
+    blocks: &[SparseColMatRef<'_, I, E>],
+) -> Result<SparseColMat<I, E>, FaerError> {
+    let mut builder = SparseBlockBuilder::new(blocks.len(), blocks.len());
+    for (k, &block) in blocks.iter().enumerate() {
+        builder.insert(k, k, block);
+    }
+    builder.try_build_col_major()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mat(nrows: usize, ncols: usize, triplets: &[(u32, u32, f64)]) -> SparseColMat<u32, f64> {
+        SparseColMat::try_new_from_triplets(nrows, ncols, triplets).unwrap()
+    }
+
+    #[test]
+    fn test_stack() {
+        let a = mat(2, 2, &[(0, 0, 1.0), (1, 1, 2.0)]);
+        let b = mat(2, 1, &[(1, 0, 3.0)]);
+        let c = mat(1, 2, &[(0, 0, 4.0), (0, 1, 5.0)]);
+
+        let h = hstack(&[a.as_ref(), b.as_ref()]).unwrap();
+        assert!(h.col_ptrs() == &[0, 1, 2, 3]);
+        assert!(h.row_indices() == &[0, 1, 1]);
+        assert!(h.values() == &[1.0, 2.0, 3.0]);
+
+        let v = vstack(&[a.as_ref(), c.as_ref()]).unwrap();
+        assert!(v.col_ptrs() == &[0, 2, 4]);
+        assert!(v.row_indices() == &[0, 2, 1, 2]);
+        assert!(v.values() == &[1.0, 4.0, 2.0, 5.0]);
+
+        let d = block_diag(&[a.as_ref(), b.as_ref(), c.as_ref()]).unwrap();
+        assert!(d.nrows() == 5);
+        assert!(d.ncols() == 5);
+        let dense = d.to_dense();
+        for (i, j, v) in [
+            (0, 0, 1.0),
+            (1, 1, 2.0),
+            (3, 2, 3.0),
+            (4, 3, 4.0),
+            (4, 4, 5.0),
+        ] {
+            assert!(dense.read(i, j) == v);
+        }
+        assert!(d.compute_nnz() == 5);
+
+        let empty = hstack::<u32, f64>(&[]).unwrap();
+        assert!(empty.nrows() == 0);
+        assert!(empty.ncols() == 0);
+    }
+
+    #[test]
+    fn test_block_builder() {
+        let a = mat(2, 2, &[(0, 0, 4.0), (1, 0, 1.0), (0, 1, 1.0), (1, 1, 3.0)]);
+        let b = mat(1, 2, &[(0, 0, 1.0), (0, 1, 2.0)]);
+
+        let mut builder = SparseBlockBuilder::new(3, 3);
+        builder.insert(0, 0, a.as_ref());
+        builder.insert_row_major(0, 1, b.as_ref().transpose());
+        builder.insert(1, 0, b.as_ref());
+        builder.set_block_nrows(2, 1);
+        builder.set_block_ncols(2, 2);
+
+        let expected = [
+            [4.0, 1.0, 1.0, 0.0, 0.0],
+            [1.0, 3.0, 2.0, 0.0, 0.0],
+            [1.0, 2.0, 0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0, 0.0, 0.0],
+        ];
+
+        let col_major = builder.try_build_col_major().unwrap();
+        let row_major = builder.try_build_row_major().unwrap();
+        assert!(col_major.compute_nnz() == 8);
+        assert!(row_major.compute_nnz() == 8);
+        assert!(row_major.row_ptrs() == &[0, 3, 6, 8, 8]);
+
+        let col_major = col_major.to_dense();
+        let row_major = row_major.to_dense();
+        for i in 0..4 {
+            for j in 0..5 {
+                assert!(col_major.read(i, j) == expected[i][j]);
+                assert!(row_major.read(i, j) == expected[i][j]);
+            }
+        }
+
+        builder.remove(0, 1);
+        let K = builder.try_build_col_major().unwrap();
+        assert!(K.col_ptrs() == &[0, 3, 6, 6, 6, 6]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_block_builder_mismatch() {
+        let a = mat(2, 2, &[(0, 0, 1.0)]);
+        let b = mat(3, 1, &[(0, 0, 1.0)]);
+
+        let mut builder = SparseBlockBuilder::new(1, 2);
+        builder.insert(0, 0, a.as_ref());
+        builder.insert(0, 1, b.as_ref());
+    }
+}
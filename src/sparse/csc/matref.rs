@@ -430,6 +430,155 @@ impl<'a, I: Index, E: Entity> SparseColMatRef<'a, I, E> {
     }
 }
 
+impl<I: Index, E: ComplexField> SparseColMatRef<'_, I, E> {
+    /// Returns the maximum norm of `self`, i.e., the maximum absolute value of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_max(&self) -> E::Real {
+        let mut norm = E::Real::faer_zero();
+        for j in 0..self.ncols() {
+            let norm_j = crate::col::from_slice::<E>(self.values_of_col(j)).norm_max();
+            if norm_j > norm {
+                norm = norm_j;
+            }
+        }
+        norm
+    }
+
+    /// Returns the L1 norm of `self`, i.e., the sum of the absolute values of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l1(&self) -> E::Real {
+        let mut norm = E::Real::faer_zero();
+        for j in 0..self.ncols() {
+            norm = norm.faer_add(crate::col::from_slice::<E>(self.values_of_col(j)).norm_l1());
+        }
+        norm
+    }
+
+    /// Returns the L2 norm of `self`, also known as its Frobenius norm.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l2(&self) -> E::Real {
+        self.squared_norm_l2().faer_sqrt()
+    }
+
+    /// Returns the squared L2 norm of `self`.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn squared_norm_l2(&self) -> E::Real {
+        let mut norm = E::Real::faer_zero();
+        for j in 0..self.ncols() {
+            let norm_j = crate::col::from_slice::<E>(self.values_of_col(j)).norm_l2();
+            norm = norm.faer_add(norm_j.faer_mul(norm_j));
+        }
+        norm
+    }
+
+    /// Returns the operator norm of `self` induced by the L1 norm, i.e., the maximum of the L1
+    /// norms of its columns.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_l1(&self) -> E::Real {
+        let mut norm = E::Real::faer_zero();
+        for j in 0..self.ncols() {
+            let norm_j = crate::col::from_slice::<E>(self.values_of_col(j)).norm_l1();
+            if norm_j > norm {
+                norm = norm_j;
+            }
+        }
+        norm
+    }
+
+    /// Returns the operator norm of `self` induced by the maximum norm, i.e., the maximum of the
+    /// L1 norms of its rows.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_linf(&self) -> E::Real {
+        let mut row_norms = alloc::vec![E::Real::faer_zero(); self.nrows()];
+        for j in 0..self.ncols() {
+            for (i, val) in self.row_indices_of_col(j).zip(
+                crate::utils::slice::SliceGroup::<'_, E>::new(self.values_of_col(j))
+                    .into_ref_iter(),
+            ) {
+                row_norms[i] = row_norms[i].faer_add(val.read().faer_abs());
+            }
+        }
+        let mut norm = E::Real::faer_zero();
+        for norm_i in row_norms {
+            if norm_i > norm {
+                norm = norm_i;
+            }
+        }
+        norm
+    }
+
+    /// Returns the sum of the entries of `self`.
+    #[inline]
+    pub fn sum(&self) -> E {
+        let mut sum = E::faer_zero();
+        for j in 0..self.ncols() {
+            sum = sum.faer_add(crate::col::from_slice::<E>(self.values_of_col(j)).sum());
+        }
+        sum
+    }
+
+    /// Returns a row containing the sum of each column of `self`.
+    #[inline]
+    pub fn col_sums(&self) -> Row<E> {
+        Row::from_fn(self.ncols(), |j| {
+            crate::col::from_slice::<E>(self.values_of_col(j)).sum()
+        })
+    }
+
+    /// Returns a column containing the sum of each row of `self`.
+    #[inline]
+    pub fn row_sums(&self) -> Col<E> {
+        let mut sums = Col::<E>::zeros(self.nrows());
+        for j in 0..self.ncols() {
+            for (i, val) in self.row_indices_of_col(j).zip(
+                crate::utils::slice::SliceGroup::<'_, E>::new(self.values_of_col(j))
+                    .into_ref_iter(),
+            ) {
+                sums.write(i, sums.read(i).faer_add(val.read()));
+            }
+        }
+        sums
+    }
+
+    /// Returns the diagonal of `self`, where structurally missing entries are treated as zero.
+    ///
+    /// # Note
+    /// Duplicate entries are summed.
+    #[inline]
+    pub fn diag(&self) -> Col<E> {
+        Col::from_fn(Ord::min(self.nrows(), self.ncols()), |j| {
+            let mut diag = E::faer_zero();
+            for (i, val) in self.row_indices_of_col(j).zip(
+                crate::utils::slice::SliceGroup::<'_, E>::new(self.values_of_col(j))
+                    .into_ref_iter(),
+            ) {
+                if i == j {
+                    diag = diag.faer_add(val.read());
+                }
+            }
+            diag
+        })
+    }
+}
+
 impl<I: Index, E: Entity> core::fmt::Debug for SparseColMatRef<'_, I, E> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mat = *self;
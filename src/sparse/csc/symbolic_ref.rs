@@ -298,6 +298,28 @@ impl<'a, I: Index> SymbolicSparseColMatRef<'a, I> {
         }
     }
 
+    /// Returns the number of symbolic non-zeros in each column of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_col(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        let mut nnz = try_zeroed::<usize>(self.ncols)?;
+        for (j, nnz_j) in nnz.iter_mut().enumerate() {
+            *nnz_j = self.col_range(j).len();
+        }
+        Ok(nnz)
+    }
+
+    /// Returns the number of symbolic non-zeros in each row of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_row(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        let mut nnz = try_zeroed::<usize>(self.nrows)?;
+        for j in 0..self.ncols {
+            for i in self.row_indices_of_col(j) {
+                nnz[i] += 1;
+            }
+        }
+        Ok(nnz)
+    }
+
     /// Returns the column pointers.
     #[inline]
     pub fn col_ptrs(&self) -> &'a [I] {
@@ -515,3 +515,134 @@ impl<I: Index, E: ComplexField> SparseRowMat<I, E> {
         .into_transpose())
     }
 }
+
+impl<I: Index, E: Entity> core::fmt::Debug for SparseRowMat<I, E> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        self.as_ref().fmt(f)
+    }
+}
+
+impl<I: Index, E: ComplexField> SparseRowMat<I, E> {
+    /// Returns the maximum norm of `self`, i.e., the maximum absolute value of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_max(&self) -> E::Real {
+        self.as_ref().norm_max()
+    }
+
+    /// Returns the L1 norm of `self`, i.e., the sum of the absolute values of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l1(&self) -> E::Real {
+        self.as_ref().norm_l1()
+    }
+
+    /// Returns the L2 norm of `self`, also known as its Frobenius norm.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l2(&self) -> E::Real {
+        self.as_ref().norm_l2()
+    }
+
+    /// Returns the squared L2 norm of `self`.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn squared_norm_l2(&self) -> E::Real {
+        self.as_ref().squared_norm_l2()
+    }
+
+    /// Returns the operator norm of `self` induced by the L1 norm, i.e., the maximum of the L1
+    /// norms of its columns.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_l1(&self) -> E::Real {
+        self.as_ref().induced_norm_l1()
+    }
+
+    /// Returns the operator norm of `self` induced by the maximum norm, i.e., the maximum of the
+    /// L1 norms of its rows.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_linf(&self) -> E::Real {
+        self.as_ref().induced_norm_linf()
+    }
+
+    /// Returns the sum of the entries of `self`.
+    #[inline]
+    pub fn sum(&self) -> E {
+        self.as_ref().sum()
+    }
+
+    /// Returns a row containing the sum of each column of `self`.
+    #[inline]
+    pub fn col_sums(&self) -> Row<E> {
+        self.as_ref().col_sums()
+    }
+
+    /// Returns a column containing the sum of each row of `self`.
+    #[inline]
+    pub fn row_sums(&self) -> Col<E> {
+        self.as_ref().row_sums()
+    }
+    /// Returns the diagonal of `self`, where structurally missing entries are treated as zero.
+    ///
+    /// # Note
+    /// Duplicate entries are summed.
+    #[inline]
+    pub fn diag(&self) -> Col<E> {
+        self.as_ref().diag()
+    }
+
+    /// Sets the diagonal of `self` to `diag`, inserting explicit entries at the diagonal
+    /// positions that are not structurally present.
+    ///
+    /// # Note
+    /// If a diagonal entry is duplicated, the first one is set to the new value and the others
+    /// are set to zero.
+    ///
+    /// # Panics
+    /// Panics if `diag.nrows()` is not equal to `min(self.nrows(), self.ncols())`.
+    #[track_caller]
+    pub fn set_diag(&mut self, diag: ColRef<'_, E>) -> Result<(), FaerError> {
+        assert!(diag.nrows() == Ord::min(self.nrows(), self.ncols()));
+        self.try_insert_missing_diag()?;
+        self.as_mut().set_diag(diag);
+        Ok(())
+    }
+
+    /// Adds `alpha` to the diagonal entries of `self`, i.e., replaces `self` with
+    /// `self + alpha * I`, inserting explicit entries at the diagonal positions that are not
+    /// structurally present.
+    ///
+    /// # Note
+    /// If a diagonal entry is duplicated, `alpha` is only added to the first one.
+    #[track_caller]
+    pub fn shift_diag(&mut self, alpha: E) -> Result<(), FaerError> {
+        self.try_insert_missing_diag()?;
+        self.as_mut().shift_diag(alpha);
+        Ok(())
+    }
+
+    fn try_insert_missing_diag(&mut self) -> Result<(), FaerError> {
+        try_insert_missing_diag(
+            Ord::min(self.nrows(), self.ncols()),
+            self.nrows(),
+            &mut self.symbolic.row_ptr,
+            &mut self.symbolic.row_nnz,
+            &mut self.symbolic.col_ind,
+            &mut self.values,
+        )
+    }
+}
@@ -370,6 +370,102 @@ impl<'a, I: Index, E: Entity> SparseRowMatRef<'a, I, E> {
     }
 }
 
+impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
+    /// Returns the maximum norm of `self`, i.e., the maximum absolute value of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_max(&self) -> E::Real {
+        (*self).transpose().norm_max()
+    }
+
+    /// Returns the L1 norm of `self`, i.e., the sum of the absolute values of its entries.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l1(&self) -> E::Real {
+        (*self).transpose().norm_l1()
+    }
+
+    /// Returns the L2 norm of `self`, also known as its Frobenius norm.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn norm_l2(&self) -> E::Real {
+        (*self).transpose().norm_l2()
+    }
+
+    /// Returns the squared L2 norm of `self`.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn squared_norm_l2(&self) -> E::Real {
+        (*self).transpose().squared_norm_l2()
+    }
+
+    /// Returns the operator norm of `self` induced by the L1 norm, i.e., the maximum of the L1
+    /// norms of its columns.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_l1(&self) -> E::Real {
+        (*self).transpose().induced_norm_linf()
+    }
+
+    /// Returns the operator norm of `self` induced by the maximum norm, i.e., the maximum of the
+    /// L1 norms of its rows.
+    ///
+    /// # Note
+    /// Duplicate entries are treated as separate entries.
+    #[inline]
+    pub fn induced_norm_linf(&self) -> E::Real {
+        (*self).transpose().induced_norm_l1()
+    }
+
+    /// Returns the sum of the entries of `self`.
+    #[inline]
+    pub fn sum(&self) -> E {
+        (*self).transpose().sum()
+    }
+
+    /// Returns a row containing the sum of each column of `self`.
+    #[inline]
+    pub fn col_sums(&self) -> Row<E> {
+        let mut sums = Row::<E>::zeros(self.ncols());
+        for i in 0..self.nrows() {
+            for (j, val) in self.col_indices_of_row(i).zip(
+                crate::utils::slice::SliceGroup::<'_, E>::new(self.values_of_row(i))
+                    .into_ref_iter(),
+            ) {
+                sums.write(j, sums.read(j).faer_add(val.read()));
+            }
+        }
+        sums
+    }
+
+    /// Returns a column containing the sum of each row of `self`.
+    #[inline]
+    pub fn row_sums(&self) -> Col<E> {
+        Col::from_fn(self.nrows(), |i| {
+            crate::col::from_slice::<E>(self.values_of_row(i)).sum()
+        })
+    }
+
+    /// Returns the diagonal of `self`, where structurally missing entries are treated as zero.
+    ///
+    /// # Note
+    /// Duplicate entries are summed.
+    #[inline]
+    pub fn diag(&self) -> Col<E> {
+        (*self).transpose().diag()
+    }
+}
+
 impl<I: Index, E: Entity> core::fmt::Debug for SparseRowMatRef<'_, I, E> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let mat = *self;
@@ -222,6 +222,18 @@ impl<I: Index> SymbolicSparseRowMat<I> {
         self.as_ref().compute_nnz()
     }
 
+    /// Returns the number of symbolic non-zeros in each column of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_col(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        self.as_ref().compute_nnz_per_col()
+    }
+
+    /// Returns the number of symbolic non-zeros in each row of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_row(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        self.as_ref().compute_nnz_per_row()
+    }
+
     /// Returns the column pointers.
     #[inline]
     pub fn row_ptrs(&self) -> &[I] {
@@ -264,6 +264,18 @@ impl<'a, I: Index> SymbolicSparseRowMatRef<'a, I> {
         self.transpose().compute_nnz()
     }
 
+    /// Returns the number of symbolic non-zeros in each row of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_row(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        self.transpose().compute_nnz_per_col()
+    }
+
+    /// Returns the number of symbolic non-zeros in each column of the sparse matrix.
+    #[inline]
+    pub fn compute_nnz_per_col(&self) -> Result<alloc::vec::Vec<usize>, FaerError> {
+        self.transpose().compute_nnz_per_row()
+    }
+
     /// Returns the column pointers.
     #[inline]
     pub fn row_ptrs(&self) -> &'a [I] {
@@ -526,21 +526,26 @@
 use super::{
     amd::{self, Control},
     ghost::{self, Array, Idx, MaybeIdx},
-    ghost_permute_hermitian_unsorted, ghost_permute_hermitian_unsorted_symbolic, make_raw_req, mem,
+    ghost_permute_hermitian_unsorted, ghost_permute_hermitian_unsorted_symbolic, make_raw,
+    make_raw_req, mem,
     mem::NONE,
-    nomem, triangular_solve, try_collect, try_zeroed, windows2, FaerError, Index, PermRef, Side,
-    SliceGroup, SliceGroupMut, SparseColMatRef, SupernodalThreshold, SymbolicSparseColMatRef,
-    SymbolicSupernodalParams,
+    nested_dissection, nomem, rcm, triangular_solve, try_collect, try_zeroed, windows2,
+    CholeskyUpdateError, FaerError, Index, PermRef, Side, SliceGroup, SliceGroupMut,
+    SparseColMatRef, SupernodalThreshold, SymbolicSparseColMatRef, SymbolicSupernodalParams,
 };
 pub use crate::linalg::cholesky::{
     bunch_kaufman::compute::BunchKaufmanRegularization,
     ldlt_diagonal::compute::LdltRegularization,
     llt::{compute::LltRegularization, CholeskyError},
+    Inertia,
 };
 use crate::{
     assert,
     linalg::{temp_mat_req, temp_mat_uninit},
-    unzipped, zipped, ComplexField, Conj, Entity, MatMut, MatRef, Parallelism, SignedIndex,
+    sparse::{SparseColMat, SymbolicSparseColMat},
+    unzipped,
+    utils::vec::VecGroup,
+    zipped, Col, ComplexField, Conj, Entity, MatMut, MatRef, Parallelism, SignedIndex,
 };
 use core::{cell::Cell, iter::zip};
 use dyn_stack::{PodStack, SizeOverflow, StackReq};
@@ -1334,6 +1339,24 @@ pub mod simplicial {
             self.values.into_inner()
         }
 
+        /// Returns the inertia of the factorized matrix, i.e., the number of positive, negative
+        /// and zero elements of the diagonal factor.
+        pub fn inertia(self) -> Inertia
+        where
+            E: ComplexField,
+        {
+            let ld = SparseColMatRef::<'_, I, E>::new(self.symbolic().factor(), self.values());
+            let mut inertia = Inertia::default();
+            for j in 0..self.symbolic().nrows() {
+                inertia.push_pivot(
+                    SliceGroup::<'_, E>::new(ld.values_of_col(j))
+                        .read(0)
+                        .faer_real(),
+                );
+            }
+            inertia
+        }
+
         /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
         /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
         ///
@@ -1930,6 +1953,49 @@ pub mod supernodal {
             }
         }
 
+        /// Returns the inertia of the factorized matrix, i.e., the number of its positive,
+        /// negative and zero eigenvalues.
+        pub fn inertia(self) -> Inertia
+        where
+            E: ComplexField,
+        {
+            let mut inertia = Inertia::default();
+            for s in 0..self.symbolic().n_supernodes() {
+                let s = self.supernode(s);
+                let size = s.matrix.ncols();
+                let subdiag = crate::mat::from_column_major_slice::<'_, E>(
+                    self.subdiag
+                        .subslice(s.start()..s.start() + size)
+                        .into_inner(),
+                    size,
+                    1,
+                );
+                crate::linalg::cholesky::bunch_kaufman::accumulate_inertia(
+                    &mut inertia,
+                    s.matrix.submatrix(0, 0, size, size),
+                    subdiag,
+                );
+            }
+            inertia
+        }
+
+        /// Returns the inertia of the factorized matrix, i.e., the number of positive, negative
+        /// and zero elements of the diagonal factor.
+        pub fn inertia(self) -> Inertia
+        where
+            E: ComplexField,
+        {
+            let mut inertia = Inertia::default();
+            for s in 0..self.symbolic().n_supernodes() {
+                // the inverses of the diagonal elements are stored on the diagonal
+                let Ds = self.supernode(s).matrix.diagonal().column_vector();
+                for i in 0..Ds.nrows() {
+                    inertia.push_pivot(Ds.read(i).faer_real());
+                }
+            }
+            inertia
+        }
+
         /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
         /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
         ///
@@ -4748,6 +4814,28 @@ impl<'a, I: Index, E: Entity> IntranodeBunchKaufmanRef<'a, I, E> {
         self.symbolic
     }
 
+    /// Returns the inertia of the factorized matrix, i.e., the number of its positive, negative
+    /// and zero eigenvalues.
+    pub fn inertia(self) -> Inertia
+    where
+        E: ComplexField,
+    {
+        match self.symbolic.raw() {
+            SymbolicCholeskyRaw::Simplicial(symbolic) => {
+                simplicial::SimplicialLdltRef::new(symbolic, self.values.into_inner()).inertia()
+            }
+            SymbolicCholeskyRaw::Supernodal(symbolic) => {
+                supernodal::SupernodalIntranodeBunchKaufmanRef::new(
+                    symbolic,
+                    self.values.into_inner(),
+                    self.subdiag.into_inner(),
+                    self.perm,
+                )
+                .inertia()
+            }
+        }
+    }
+
     /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
     /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
     ///
@@ -4836,6 +4924,59 @@ impl<'a, I: Index, E: Entity> LltRef<'a, I, E> {
         self.symbolic
     }
 
+    /// Returns a copy of the $L$ factor of the factorization, with sorted row indices.
+    ///
+    /// The factor satisfies `A[fwd[i], fwd[j]] == (L * L.adjoint())[i, j]`, where `fwd` is the
+    /// forward array of the fill-reducing permutation [`SymbolicCholesky::perm`].
+    pub fn try_l_factor(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_export_l(self.values, true)
+    }
+
+    /// Returns the entries of the inverse of the factorized matrix $A$ that lie in the sparsity
+    /// pattern of the factor, without forming the full inverse.
+    ///
+    /// The output contains the entries at the positions `(fwd[i], fwd[j])` and `(fwd[j], fwd[i])`
+    /// for every nonzero `(i, j)` of [`Self::try_l_factor`], where `fwd` is the forward array of
+    /// the fill-reducing permutation [`SymbolicCholesky::perm`]. This includes the diagonal and
+    /// the sparsity pattern of $A$. The entries are computed with the selected inversion
+    /// algorithm, based on the Takahashi equations.
+    ///
+    /// The output has sorted row indices, and both its lower and upper parts are stored.
+    pub fn try_selected_inverse(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_selected_inverse_impl(self.values, true)
+    }
+
+    /// Returns the diagonal of the inverse of the factorized matrix, without forming the full
+    /// inverse.
+    ///
+    /// See [`Self::try_selected_inverse`] for more details.
+    pub fn try_inverse_diag(self) -> Result<Col<E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_inverse_diag_impl(self.values, true)
+    }
+
+    /// Returns the logarithm of the determinant of the factorized matrix, which is positive.
+    ///
+    /// The logarithms of the diagonal entries of the factor are summed, which avoids the overflow
+    /// or underflow of their product.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn log_abs_det(self) -> E::Real
+    where
+        E: ComplexField,
+        E::Real: num_traits::Float,
+    {
+        self.symbolic.log_abs_det_impl(self.values, true)
+    }
+
     /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
     /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
     ///
@@ -4902,6 +5043,108 @@ impl<'a, I: Index, E: Entity> LdltRef<'a, I, E> {
         self.symbolic
     }
 
+    /// Returns a copy of the unit lower triangular $L$ factor of the factorization, with sorted
+    /// row indices and an explicit unit diagonal.
+    ///
+    /// The factors satisfy `A[fwd[i], fwd[j]] == (L * D * L.adjoint())[i, j]`, where `D` is the
+    /// diagonal matrix built from [`Self::d_factor`], and `fwd` is the forward array of the
+    /// fill-reducing permutation [`SymbolicCholesky::perm`].
+    pub fn try_l_factor(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_export_l(self.values, false)
+    }
+
+    /// Returns the entries of the inverse of the factorized matrix that lie in the sparsity
+    /// pattern of the factors, without forming the full inverse.
+    ///
+    /// See [`LltRef::try_selected_inverse`] for more details.
+    pub fn try_selected_inverse(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_selected_inverse_impl(self.values, false)
+    }
+
+    /// Returns the diagonal of the inverse of the factorized matrix, without forming the full
+    /// inverse.
+    ///
+    /// See [`LltRef::try_selected_inverse`] for more details.
+    pub fn try_inverse_diag(self) -> Result<Col<E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        self.symbolic.try_inverse_diag_impl(self.values, false)
+    }
+
+    /// Returns the logarithm of the absolute value of the determinant of the factorized matrix.
+    ///
+    /// The logarithms of the diagonal entries of the $D$ factor are summed, which avoids the
+    /// overflow or underflow of their product. The result is $-\infty$ if the matrix is singular.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn log_abs_det(self) -> E::Real
+    where
+        E: ComplexField,
+        E::Real: num_traits::Float,
+    {
+        self.symbolic.log_abs_det_impl(self.values, false)
+    }
+
+    /// Returns the sign of the determinant of the factorized matrix, i.e., `1`, `-1`, or zero if
+    /// the matrix is singular.
+    ///
+    /// Together with [`Self::log_abs_det`], this gives the determinant as
+    /// `det_sign * exp(log_abs_det)`, without overflowing.
+    pub fn det_sign(self) -> E::Real
+    where
+        E: ComplexField,
+    {
+        let zero = E::Real::faer_zero();
+        let mut sign = E::Real::faer_one();
+        self.symbolic.for_each_diag(self.values, false, |d| {
+            if d < zero {
+                sign = sign.faer_neg();
+            } else if !(d > zero) {
+                sign = zero;
+            }
+        });
+        sign
+    }
+
+    /// Returns a copy of the diagonal of the $D$ factor of the factorization.
+    ///
+    /// See [`Self::try_l_factor`] for the relation between the factors and the input matrix.
+    pub fn d_factor(self) -> Col<E>
+    where
+        E: ComplexField,
+    {
+        let n = self.symbolic.nrows();
+        let mut col_to_super = alloc::vec![I::truncate(0); n];
+        self.symbolic.fill_col_to_super(&mut col_to_super);
+        Col::<E>::from_fn(n, |j| {
+            let pos = self.symbolic.factor_col(&col_to_super, j).pos;
+            E::faer_from_real(self.symbolic.read_diag(self.values, pos, false))
+        })
+    }
+
+    /// Returns the inertia of the factorized matrix, i.e., the number of positive, negative and
+    /// zero elements of the diagonal factor.
+    pub fn inertia(self) -> Inertia
+    where
+        E: ComplexField,
+    {
+        match self.symbolic.raw() {
+            SymbolicCholeskyRaw::Simplicial(symbolic) => {
+                simplicial::SimplicialLdltRef::new(symbolic, self.values.into_inner()).inertia()
+            }
+            SymbolicCholeskyRaw::Supernodal(symbolic) => {
+                supernodal::SupernodalLdltRef::new(symbolic, self.values.into_inner()).inertia()
+            }
+        }
+    }
+
     /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
     /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
     ///
@@ -5023,324 +5266,1218 @@ pub(crate) fn ghost_postorder<'n, I: Index>(
     }
 }
 
-/// Tuning parameters for the symbolic Cholesky factorization.
-#[derive(Copy, Clone, Debug, Default)]
-pub struct CholeskySymbolicParams<'a> {
-    /// Parameters for computing the fill-reducing permutation.
-    pub amd_params: Control,
-    /// Threshold for selecting the supernodal factorization.
-    pub supernodal_flop_ratio_threshold: SupernodalThreshold,
-    /// Supernodal factorization parameters.
-    pub supernodal_params: SymbolicSupernodalParams<'a>,
+/// Kind of low-rank modification of a Cholesky factorization.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UpdateKind {
+    /// The factorized matrix $A$ is replaced with $A + WW^H$.
+    Update,
+    /// The factorized matrix $A$ is replaced with $A - WW^H$.
+    Downdate,
 }
 
-/// Computes the symbolic Cholesky factorization of the matrix `A`, or returns an error if the
-/// operation could not be completed.
-pub fn factorize_symbolic_cholesky<I: Index>(
-    A: SymbolicSparseColMatRef<'_, I>,
-    side: Side,
-    params: CholeskySymbolicParams<'_>,
-) -> Result<SymbolicCholesky<I>, FaerError> {
-    let n = A.nrows();
-    let A_nnz = A.compute_nnz();
+/// Structure of a single column of a simplicial or supernodal Cholesky factor.
+///
+/// The diagonal entry is stored at the position `pos`, and is followed by the off-diagonal
+/// entries of the rows in `dense`, then those of the rows in `sparse`.
+struct FactorCol<'a, I: Index> {
+    pos: usize,
+    dense: core::ops::Range<usize>,
+    sparse: &'a [I],
+}
 
-    assert!(A.nrows() == A.ncols());
-    ghost::with_size(n, |N| {
-        let A = ghost::SymbolicSparseColMatRef::new(A, N, N);
+impl<'a, I: Index> FactorCol<'a, I> {
+    /// Returns the rows of the off-diagonal entries, in storage order.
+    #[inline]
+    fn rows(&self) -> impl 'a + Iterator<Item = usize> {
+        self.dense
+            .clone()
+            .chain(self.sparse.iter().map(|&i| i.zx()))
+    }
 
-        let req = || -> Result<StackReq, SizeOverflow> {
-            let n_req = StackReq::try_new::<I>(n)?;
-            let A_req = StackReq::try_and(
-                // new_col_ptr
-                StackReq::try_new::<I>(n + 1)?,
-                // new_row_ind
-                StackReq::try_new::<I>(A_nnz)?,
-            )?;
+    /// Returns the parent of the column in the elimination tree.
+    #[inline]
+    fn parent(&self) -> Option<usize> {
+        if self.dense.start < self.dense.end {
+            Some(self.dense.start)
+        } else {
+            self.sparse.iter().map(|&i| i.zx()).min()
+        }
+    }
+}
 
-            StackReq::try_or(
-                amd::order_maybe_unsorted_req::<I>(n, A_nnz)?,
-                StackReq::try_all_of([
-                    A_req,
-                    // permute_symmetric | etree
-                    n_req,
-                    // col_counts
-                    n_req,
-                    // ghost_prefactorize_symbolic
-                    n_req,
-                    // ghost_factorize_*_symbolic
-                    StackReq::try_or(
-                        supernodal::factorize_supernodal_symbolic_cholesky_req::<I>(n)?,
-                        simplicial::factorize_simplicial_symbolic_req::<I>(n)?,
-                    )?,
-                ])?,
-            )
-        };
+impl<I: Index> SymbolicCholesky<I> {
+    fn fill_col_to_super(&self, col_to_super: &mut [I]) {
+        if let SymbolicCholeskyRaw::Supernodal(this) = &self.raw {
+            for s in 0..this.n_supernodes() {
+                for j in this.supernode_begin[s].zx()..this.supernode_begin[s + 1].zx() {
+                    col_to_super[j] = I::truncate(s);
+                }
+            }
+        }
+    }
 
-        let req = req().map_err(nomem)?;
-        let mut mem = dyn_stack::GlobalPodBuffer::try_new(req).map_err(nomem)?;
-        let mut stack = PodStack::new(&mut mem);
+    fn factor_col(&self, col_to_super: &[I], j: usize) -> FactorCol<'_, I> {
+        match &self.raw {
+            SymbolicCholeskyRaw::Simplicial(this) => {
+                let start = this.col_ptrs()[j].zx();
+                let end = this.col_ptrs()[j + 1].zx();
+                FactorCol {
+                    pos: start,
+                    dense: j + 1..j + 1,
+                    sparse: &this.row_indices()[start + 1..end],
+                }
+            }
+            SymbolicCholeskyRaw::Supernodal(this) => {
+                let s = col_to_super[j].zx();
+                let s_start = this.supernode_begin[s].zx();
+                let s_end = this.supernode_begin[s + 1].zx();
+                let s_pattern = this.supernode(s).pattern();
+                let s_nrows = s_end - s_start + s_pattern.len();
+                let c = j - s_start;
+                FactorCol {
+                    pos: this.col_ptrs_for_values()[s].zx() + c * s_nrows + c,
+                    dense: j + 1..s_end,
+                    sparse: s_pattern,
+                }
+            }
+        }
+    }
 
-        let mut perm_fwd = try_zeroed(n)?;
-        let mut perm_inv = try_zeroed(n)?;
-        let flops = amd::order_maybe_unsorted(
-            &mut perm_fwd,
-            &mut perm_inv,
-            A.into_inner(),
-            params.amd_params,
-            stack.rb_mut(),
-        )?;
-        let flops = flops.n_div + flops.n_mult_subs_ldl;
-        let perm_ = ghost::PermRef::new(PermRef::new_checked(&perm_fwd, &perm_inv), N);
+    // the supernodal LDLT factorization stores the inverses of the diagonal entries
+    #[inline]
+    fn read_diag<E: ComplexField>(&self, L: SliceGroup<'_, E>, pos: usize, llt: bool) -> E::Real {
+        let d = L.read(pos).faer_real();
+        match (&self.raw, llt) {
+            (SymbolicCholeskyRaw::Supernodal(_), false) => d.faer_inv(),
+            _ => d,
+        }
+    }
 
-        let (new_col_ptr, stack) = stack.make_raw::<I>(n + 1);
-        let (new_row_ind, mut stack) = stack.make_raw::<I>(A_nnz);
-        let A = unsafe {
-            ghost_permute_hermitian_unsorted_symbolic(
-                new_col_ptr,
-                new_row_ind,
-                A,
-                perm_,
-                side,
-                Side::Upper,
-                stack.rb_mut(),
-            )
+    #[inline]
+    fn write_diag<E: ComplexField>(
+        &self,
+        L: SliceGroupMut<'_, E>,
+        pos: usize,
+        d: E::Real,
+        llt: bool,
+    ) {
+        let mut L = L;
+        let d = match (&self.raw, llt) {
+            (SymbolicCholeskyRaw::Supernodal(_), false) => d.faer_inv(),
+            _ => d,
         };
+        L.write(pos, E::faer_from_real(d));
+    }
 
-        let (etree, stack) = stack.make_raw::<I::Signed>(n);
-        let (col_counts, mut stack) = stack.make_raw::<I>(n);
-        let etree = Array::from_mut(etree, N);
-        let col_counts = Array::from_mut(col_counts, N);
-        let etree =
-            &*ghost_prefactorize_symbolic_cholesky::<I>(etree, col_counts, A, stack.rb_mut());
-        let L_nnz = I::sum_nonnegative(col_counts.as_ref()).ok_or(FaerError::IndexOverflow)?;
+    // copies the factor stored in `L` to a column-major matrix with sorted row indices. the
+    // diagonal of the factor is replaced with ones for an LDLT factorization
+    fn try_export_l<E: ComplexField>(
+        &self,
+        L: SliceGroup<'_, E>,
+        llt: bool,
+    ) -> Result<SparseColMat<I, E>, FaerError> {
+        let n = self.nrows();
+        let mut col_to_super = try_zeroed::<I>(n)?;
+        self.fill_col_to_super(&mut col_to_super);
 
-        let raw = if (flops / L_nnz.zx() as f64)
-            > params.supernodal_flop_ratio_threshold.0
-                * crate::sparse::linalg::CHOLESKY_SUPERNODAL_RATIO_FACTOR
-        {
-            SymbolicCholeskyRaw::Supernodal(supernodal::ghost_factorize_supernodal_symbolic(
-                A,
-                None,
-                None,
-                supernodal::CholeskyInput::A,
-                etree,
-                col_counts,
-                stack.rb_mut(),
-                params.supernodal_params,
-            )?)
-        } else {
-            SymbolicCholeskyRaw::Simplicial(
-                simplicial::ghost_factorize_simplicial_symbolic_cholesky(
-                    A,
-                    etree,
-                    col_counts,
-                    stack.rb_mut(),
-                )?,
-            )
+        let mut col_ptr = try_zeroed::<I>(n + 1)?;
+        let mut nnz = 0usize;
+        for j in 0..n {
+            nnz += 1 + self.factor_col(&col_to_super, j).rows().count();
+            if nnz > I::Signed::MAX.zx() {
+                return Err(FaerError::IndexOverflow);
+            }
+            col_ptr[j + 1] = I::truncate(nnz);
+        }
+
+        let mut row_ind = try_zeroed::<I>(nnz)?;
+        let mut values = VecGroup::<E>::new();
+        values
+            .try_reserve_exact(nnz)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        values.resize(nnz, E::faer_zero().faer_into_units());
+        let mut values_mut = values.as_slice_mut();
+
+        for j in 0..n {
+            let col = self.factor_col(&col_to_super, j);
+            let start = col_ptr[j].zx();
+            row_ind[start] = I::truncate(j);
+            values_mut.write(start, if llt { L.read(col.pos) } else { E::faer_one() });
+            // the off-diagonal entries of a column are already sorted in both the simplicial and
+            // supernodal storage
+            for (idx, (p, i)) in zip(col.pos + 1.., col.rows()).enumerate() {
+                row_ind[start + 1 + idx] = I::truncate(i);
+                values_mut.write(start + 1 + idx, L.read(p));
+            }
+        }
+
+        Ok(SparseColMat::<I, E>::new(
+            SymbolicSparseColMat::<I>::new_checked(n, n, col_ptr, None, row_ind),
+            values.into_inner(),
+        ))
+    }
+
+    // computes the entries of the inverse of the permuted matrix that lie in the pattern of the
+    // factor, using the takahashi equations. the result has the same structure as the exported
+    // factor, and its values are returned along with it
+    fn try_selected_inverse_lower<E: ComplexField>(
+        &self,
+        L: SliceGroup<'_, E>,
+        llt: bool,
+    ) -> Result<(SparseColMat<I, E>, VecGroup<E>), FaerError> {
+        let n = self.nrows();
+        let mut col_to_super = try_zeroed::<I>(n)?;
+        self.fill_col_to_super(&mut col_to_super);
+        let diag = |j: usize| self.read_diag(L, self.factor_col(&col_to_super, j).pos, llt);
+
+        let L = self.try_export_l(L, llt)?;
+        let col_ptr = L.col_ptrs();
+        let row_ind = L.row_indices();
+        let L_values = SliceGroup::<'_, E>::new(L.values());
+        let nnz = row_ind.len();
+
+        let mut Z = VecGroup::<E>::new();
+        Z.try_reserve_exact(nnz)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        Z.resize(nnz, E::faer_zero().faer_into_units());
+        let mut Z_mut = Z.as_slice_mut();
+
+        // position of the entry `(i, j)` of the factor, where `i >= j`
+        let find = |i: usize, j: usize| -> usize {
+            let start = col_ptr[j].zx();
+            let rows = &row_ind[start..col_ptr[j + 1].zx()];
+            start + rows.binary_search(&I::truncate(i)).unwrap()
         };
 
-        Ok(SymbolicCholesky {
-            raw,
-            perm_fwd,
-            perm_inv,
-            A_nnz,
-        })
-    })
-}
+        for j in (0..n).rev() {
+            let start = col_ptr[j].zx();
+            let end = col_ptr[j + 1].zx();
 
-#[cfg(test)]
-pub(crate) mod tests {
-    use super::{supernodal::SupernodalLdltRef, *};
-    use crate::{
-        assert,
-        sparse::linalg::{
-            cholesky::supernodal::{CholeskyInput, SupernodalIntranodeBunchKaufmanRef},
-            qd::Double,
-        },
-        Mat,
-    };
-    use dyn_stack::GlobalPodBuffer;
-    use num_complex::Complex;
-    use rand::{Rng, SeedableRng};
+            // scale the column of the factor so that it has a unit diagonal, and compute the
+            // corresponding diagonal entry of `D`
+            let (l_jj_inv, d) = if llt {
+                let l_jj = diag(j);
+                (l_jj.faer_inv(), l_jj.faer_mul(l_jj))
+            } else {
+                (E::Real::faer_one(), diag(j))
+            };
+            let l = |p: usize| L_values.read(p).faer_scale_real(l_jj_inv);
+
+            // Z[i, j] = -sum_k Z[i, k] L[k, j], for i, k in the pattern of L[:, j]
+            for p in start + 1..end {
+                let i = row_ind[p].zx();
+                let mut acc = E::faer_zero();
+                for q in start + 1..end {
+                    let k = row_ind[q].zx();
+                    let z_ik = if i >= k {
+                        Z_mut.read(find(i, k))
+                    } else {
+                        Z_mut.read(find(k, i)).faer_conj()
+                    };
+                    acc = acc.faer_add(z_ik.faer_mul(l(q)));
+                }
+                Z_mut.write(p, acc.faer_neg());
+            }
 
-    fn test_counts<I: Index>() {
-        let truncate = I::truncate;
+            // Z[j, j] = 1/d_j - sum_k conj(L[k, j]) Z[k, j]
+            let mut acc = E::faer_from_real(d.faer_inv());
+            for p in start + 1..end {
+                acc = acc.faer_sub(l(p).faer_conj().faer_mul(Z_mut.read(p)));
+            }
+            Z_mut.write(start, E::faer_from_real(acc.faer_real()));
+        }
 
-        let n = 11;
-        let col_ptr = &[0, 3, 6, 10, 13, 16, 21, 24, 29, 31, 37, 43].map(truncate);
-        let row_ind = &[
-            0, 5, 6, // 0
-            1, 2, 7, // 1
-            1, 2, 9, 10, // 2
-            3, 5, 9, // 3
-            4, 7, 10, // 4
-            0, 3, 5, 8, 9, // 5
-            0, 6, 10, // 6
-            1, 4, 7, 9, 10, // 7
-            5, 8, // 8
-            2, 3, 5, 7, 9, 10, // 9
-            2, 4, 6, 7, 9, 10, // 10
-        ]
-        .map(truncate);
+        Ok((L, Z))
+    }
 
-        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind);
-        let zero = truncate(0);
-        let mut etree = vec![zero.to_signed(); n];
-        let mut col_count = vec![zero; n];
-        ghost::with_size(n, |N| {
-            let A = ghost::SymbolicSparseColMatRef::new(A, N, N);
-            let etree = ghost_prefactorize_symbolic_cholesky(
-                Array::from_mut(&mut etree, N),
-                Array::from_mut(&mut col_count, N),
-                A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
-            );
+    fn try_selected_inverse_impl<E: ComplexField>(
+        &self,
+        L: SliceGroup<'_, E>,
+        llt: bool,
+    ) -> Result<SparseColMat<I, E>, FaerError> {
+        let n = self.nrows();
+        let (L, Z) = self.try_selected_inverse_lower(L, llt)?;
+        let Z = Z.as_slice();
+        let col_ptr = L.col_ptrs();
+        let row_ind = L.row_indices();
+        let (fwd, _) = self.perm().arrays();
+
+        // the lower part is mirrored to the upper part, and both are mapped back to the original
+        // ordering
+        let nnz = 2 * row_ind.len() - n;
+        if nnz > I::Signed::MAX.zx() {
+            return Err(FaerError::IndexOverflow);
+        }
+        let mut out_col_ptr = try_zeroed::<I>(n + 1)?;
+        for j in 0..n {
+            for &i in &row_ind[col_ptr[j].zx()..col_ptr[j + 1].zx()] {
+                out_col_ptr[fwd[j].zx() + 1] += I::truncate(1);
+                if i.zx() != j {
+                    out_col_ptr[fwd[i.zx()].zx() + 1] += I::truncate(1);
+                }
+            }
+        }
+        for j in 0..n {
+            out_col_ptr[j + 1] = out_col_ptr[j] + out_col_ptr[j + 1];
+        }
 
-            supernodal::ghost_factorize_supernodal_symbolic(
-                A,
-                None,
-                None,
-                CholeskyInput::A,
-                etree,
-                Array::from_ref(&col_count, N),
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-                Default::default(),
-            )
-            .unwrap();
-        });
-        assert_eq!(
-            etree,
-            [5, 2, 7, 5, 7, 6, 8, 9, 9, 10, NONE].map(I::Signed::truncate)
+        let mut pos = try_collect(out_col_ptr[..n].iter().copied())?;
+        let mut out_row_ind = try_zeroed::<I>(nnz)?;
+        let mut out_values = VecGroup::<E>::new();
+        out_values
+            .try_reserve_exact(nnz)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        out_values.resize(nnz, E::faer_zero().faer_into_units());
+        let mut out = out_values.as_slice_mut();
+
+        for j in 0..n {
+            let fj = fwd[j].zx();
+            for p in col_ptr[j].zx()..col_ptr[j + 1].zx() {
+                let i = row_ind[p].zx();
+                let fi = fwd[i].zx();
+                let z = Z.read(p);
+
+                let q = pos[fj].zx();
+                out_row_ind[q] = I::truncate(fi);
+                out.write(q, z);
+                pos[fj] += I::truncate(1);
+
+                if i != j {
+                    let q = pos[fi].zx();
+                    out_row_ind[q] = I::truncate(fj);
+                    out.write(q, z.faer_conj());
+                    pos[fi] += I::truncate(1);
+                }
+            }
+        }
+
+        let mut out = SparseColMat::<I, E>::new(
+            SymbolicSparseColMat::<I>::new_unsorted_checked(n, n, out_col_ptr, None, out_row_ind),
+            out_values.into_inner(),
         );
-        assert_eq!(col_count, [3, 3, 4, 3, 3, 4, 4, 3, 3, 2, 1].map(truncate));
+        out.sort_indices();
+        Ok(out)
     }
 
-    include!("./data.rs");
+    fn try_inverse_diag_impl<E: ComplexField>(
+        &self,
+        L: SliceGroup<'_, E>,
+        llt: bool,
+    ) -> Result<Col<E>, FaerError> {
+        let (L, Z) = self.try_selected_inverse_lower(L, llt)?;
+        let Z = Z.as_slice();
+        let col_ptr = L.col_ptrs();
+        let (_, inv) = self.perm().arrays();
+        Ok(Col::<E>::from_fn(self.nrows(), |i| {
+            Z.read(col_ptr[inv[i].zx()].zx())
+        }))
+    }
 
-    fn test_amd<I: Index>() {
-        for &(_, (_, col_ptr, row_ind, _)) in ALL {
-            let I = I::truncate;
-            let n = col_ptr.len() - 1;
+    // calls `f` with each diagonal entry of the factor stored in `L`, or of its `D` factor for an
+    // LDLT factorization
+    fn for_each_diag<E: ComplexField>(
+        &self,
+        L: SliceGroup<'_, E>,
+        llt: bool,
+        mut f: impl FnMut(E::Real),
+    ) {
+        let n = self.nrows();
+        let mut col_to_super = alloc::vec![I::truncate(0); n];
+        self.fill_col_to_super(&mut col_to_super);
+        for j in 0..n {
+            f(self.read_diag(L, self.factor_col(&col_to_super, j).pos, llt));
+        }
+    }
 
-            let (amd_perm, amd_perm_inv, _) =
-                ::amd::order(n, col_ptr, row_ind, &Default::default()).unwrap();
-            let col_ptr = &*col_ptr.iter().copied().map(I).collect::<Vec<_>>();
-            let row_ind = &*row_ind.iter().copied().map(I).collect::<Vec<_>>();
-            let amd_perm = &*amd_perm.iter().copied().map(I).collect::<Vec<_>>();
-            let amd_perm_inv = &*amd_perm_inv.iter().copied().map(I).collect::<Vec<_>>();
-            let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind);
+    #[cfg(feature = "std")]
+    fn log_abs_det_impl<E: ComplexField>(&self, L: SliceGroup<'_, E>, llt: bool) -> E::Real
+    where
+        E::Real: num_traits::Float,
+    {
+        let mut log = E::Real::faer_zero();
+        self.for_each_diag(L, llt, |d| {
+            log = log.faer_add(num_traits::Float::ln(d.faer_abs()))
+        });
+        if llt {
+            // the determinant is the square of the product of the diagonal entries of `L`
+            log.faer_add(log)
+        } else {
+            log
+        }
+    }
 
-            let perm = &mut vec![I(0); n];
-            let perm_inv = &mut vec![I(0); n];
+    /// Computes the required workspace size and alignment for modifying an LLT or LDLT
+    /// factorization in place.
+    pub fn update_req<E: Entity>(&self) -> Result<StackReq, SizeOverflow> {
+        let n = self.nrows();
+        let n_req = StackReq::try_new::<I>(n)?;
+        StackReq::try_all_of([make_raw_req::<E>(n)?, n_req, n_req, n_req])
+    }
 
-            crate::sparse::linalg::amd::order_maybe_unsorted(
-                perm,
-                perm_inv,
-                A,
-                Default::default(),
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    crate::sparse::linalg::amd::order_maybe_unsorted_req::<I>(n, row_ind.len())
-                        .unwrap(),
-                )),
-            )
-            .unwrap();
+    // replaces the factorization of `A` with that of `A + alpha * w * w^H`, where `w` is stored
+    // in `x`, and its nonzero entries are in the rows `{f} ∪ rows(L[:, f])`.
+    // `alpha` must be `±1` for an LLT factorization.
+    // the entries of `x` are zeroed on success.
+    fn rank_one_update<E: ComplexField>(
+        &self,
+        col_to_super: &[I],
+        L: SliceGroupMut<'_, E>,
+        x: SliceGroupMut<'_, E>,
+        f: usize,
+        alpha: E::Real,
+        llt: bool,
+    ) -> Result<(), CholeskyUpdateError> {
+        let mut L = L;
+        let mut x = x;
+        let zero = E::Real::faer_zero();
+        let update = alpha > zero;
+
+        let mut alpha = alpha;
+        let mut beta = E::Real::faer_one();
+        let mut current = Some(f);
+        while let Some(j) = current {
+            let col = self.factor_col(col_to_super, j);
+            let d = self.read_diag(L.rb(), col.pos, llt);
+            let wj = x.read(j);
+            x.write(j, E::faer_zero());
+
+            if llt {
+                // see Davis, "Direct Methods for Sparse Linear Systems", section 4.5
+                let a = wj.faer_scale_real(d.faer_inv());
+                let beta2 = if update {
+                    beta.faer_mul(beta).faer_add(a.faer_abs2())
+                } else {
+                    beta.faer_mul(beta).faer_sub(a.faer_abs2())
+                };
+                if !(beta2 > zero) {
+                    return Err(CholeskyUpdateError::NotPositiveDefinite);
+                }
+                let beta2 = beta2.faer_sqrt();
+                let (delta, sigma) = if update {
+                    (beta.faer_div(beta2), E::Real::faer_one())
+                } else {
+                    (beta2.faer_div(beta), E::Real::faer_one().faer_neg())
+                };
+                let gamma = a
+                    .faer_conj()
+                    .faer_scale_real(sigma.faer_div(beta2.faer_mul(beta)));
+                beta = beta2;
+
+                let mut d = d.faer_mul(delta);
+                if update {
+                    d = d.faer_add(gamma.faer_mul(wj).faer_real());
+                }
+                self.write_diag(L.rb_mut(), col.pos, d, llt);
+
+                for (p, i) in zip(col.pos + 1.., col.rows()) {
+                    let w1 = x.read(i);
+                    let w2 = w1.faer_sub(a.faer_mul(L.read(p)));
+                    x.write(i, w2);
+                    L.write(
+                        p,
+                        L.read(p)
+                            .faer_scale_real(delta)
+                            .faer_add(gamma.faer_mul(if update { w1 } else { w2 })),
+                    );
+                }
+            } else {
+                // method C1 from Gill, Golub, Murray and Saunders, "Methods for modifying matrix
+                // factorizations"
+                let d_new = d.faer_add(alpha.faer_mul(wj.faer_abs2()));
+                if d_new == zero {
+                    return Err(CholeskyUpdateError::ZeroPivot);
+                }
+                let gamma = wj.faer_conj().faer_scale_real(alpha.faer_div(d_new));
+                alpha = alpha.faer_mul(d).faer_div(d_new);
+                self.write_diag(L.rb_mut(), col.pos, d_new, llt);
+
+                for (p, i) in zip(col.pos + 1.., col.rows()) {
+                    let xi = x.read(i).faer_sub(wj.faer_mul(L.read(p)));
+                    x.write(i, xi);
+                    L.write(p, L.read(p).faer_add(gamma.faer_mul(xi)));
+                }
+            }
 
-            assert!(perm == amd_perm);
-            assert!(perm_inv == amd_perm_inv);
+            current = col.parent();
         }
+        Ok(())
     }
 
-    fn sparse_to_dense<I: Index, E: ComplexField>(sparse: SparseColMatRef<'_, I, E>) -> Mat<E> {
-        let m = sparse.nrows();
-        let n = sparse.ncols();
-
-        let mut dense = Mat::<E>::zeros(m, n);
-        let slice_group = SliceGroup::<'_, E>::new;
+    pub(crate) fn update_impl<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        W: SparseColMatRef<'_, I, E>,
+        kind: UpdateKind,
+        conj: Conj,
+        llt: bool,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        let n = self.nrows();
+        let mut L = SliceGroupMut::<'_, E>::new(L_values);
+        assert!(all(W.nrows() == n, L.len() == self.len_values()));
+
+        let (mut x, stack) = make_raw::<E>(n, stack);
+        let (marks, stack) = stack.make_raw::<I>(n);
+        let (col_to_super, _) = stack.make_raw::<I>(n);
+        x.rb_mut().fill_zero();
+        marks.fill(I::truncate(0));
+        self.fill_col_to_super(col_to_super);
+        let (_, perm_inv) = self.perm().arrays();
+        let first = |j: usize| W.row_indices_of_col(j).map(|i| perm_inv[i].zx()).min();
+
+        // check the structure of all the columns before modifying the factor
+        for j in 0..W.ncols() {
+            let Some(f) = first(j) else { continue };
+            let col = self.factor_col(col_to_super, f);
+            marks[f] = I::truncate(1);
+            for i in col.rows() {
+                marks[i] = I::truncate(1);
+            }
+            let missing = W
+                .row_indices_of_col(j)
+                .map(|i| perm_inv[i].zx())
+                .find(|&i| marks[i] == I::truncate(0));
+            marks[f] = I::truncate(0);
+            for i in col.rows() {
+                marks[i] = I::truncate(0);
+            }
+            if let Some(i) = missing {
+                return Err(CholeskyUpdateError::StructurallyMissing { row: i, col: f });
+            }
+        }
 
-        for j in 0..n {
-            for (i, val) in zip(
-                sparse.row_indices_of_col(j),
-                slice_group(sparse.values_of_col(j)).into_ref_iter(),
+        let alpha = match kind {
+            UpdateKind::Update => E::Real::faer_one(),
+            UpdateKind::Downdate => E::Real::faer_one().faer_neg(),
+        };
+        for j in 0..W.ncols() {
+            let Some(f) = first(j) else { continue };
+            for (i, w) in zip(
+                W.row_indices_of_col(j),
+                SliceGroup::<'_, E>::new(W.values_of_col(j)).into_ref_iter(),
             ) {
-                dense.write(i, j, val.read());
+                let i = perm_inv[i].zx();
+                let w = if conj == Conj::Yes {
+                    w.read().faer_conj()
+                } else {
+                    w.read()
+                };
+                x.write(i, x.read(i).faer_add(w));
             }
+            self.rank_one_update(col_to_super, L.rb_mut(), x.rb_mut(), f, alpha, llt)?;
         }
-
-        dense
+        Ok(())
     }
 
-    fn reconstruct_from_supernodal_llt<I: Index, E: ComplexField>(
-        symbolic: &supernodal::SymbolicSupernodalCholesky<I>,
-        L_values: GroupFor<E, &[E::Unit]>,
-    ) -> Mat<E> {
-        let L_values = SliceGroup::<'_, E>::new(L_values);
-        let ldlt = SupernodalLdltRef::new(symbolic, L_values.into_inner());
-        let n_supernodes = ldlt.symbolic().n_supernodes();
-        let n = ldlt.symbolic().nrows();
-
-        let mut dense = Mat::<E>::zeros(n, n);
-
-        for s in 0..n_supernodes {
-            let s = ldlt.supernode(s);
-            let size = s.matrix().ncols();
+    pub(crate) fn delete_row_impl<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        llt: bool,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        let n = self.nrows();
+        let mut L = SliceGroupMut::<'_, E>::new(L_values);
+        assert!(all(k < n, L.len() == self.len_values()));
 
-            let (Ls_top, Ls_bot) = s.matrix().split_at_row(size);
-            dense
-                .as_mut()
-                .submatrix_mut(s.start(), s.start(), size, size)
-                .copy_from(Ls_top);
+        let (mut x, stack) = make_raw::<E>(n, stack);
+        let (col_to_super, _) = stack.make_raw::<I>(n);
+        x.rb_mut().fill_zero();
+        self.fill_col_to_super(col_to_super);
+        let k = self.perm().arrays().1[k].zx();
 
-            for col in 0..size {
-                for (i, row) in s.pattern().iter().enumerate() {
-                    dense.write(row.zx(), s.start() + col, Ls_bot.read(i, col));
+        // clear row `k`, which can only be nonzero in the columns that precede it
+        for j in 0..k {
+            let col = self.factor_col(col_to_super, j);
+            for (p, i) in zip(col.pos + 1.., col.rows()) {
+                if i == k {
+                    L.write(p, E::faer_zero());
                 }
             }
         }
 
-        &dense * dense.adjoint()
+        // clear column `k`, and apply its contribution to the trailing columns
+        let col = self.factor_col(col_to_super, k);
+        let d = self.read_diag(L.rb(), col.pos, llt);
+        for (p, i) in zip(col.pos + 1.., col.rows()) {
+            x.write(i, L.read(p));
+            L.write(p, E::faer_zero());
+        }
+        L.write(col.pos, E::faer_one());
+
+        match col.parent() {
+            Some(f) => {
+                let alpha = if llt { E::Real::faer_one() } else { d };
+                self.rank_one_update(col_to_super, L, x, f, alpha, llt)
+            }
+            None => Ok(()),
+        }
     }
 
-    fn reconstruct_from_supernodal_ldlt<I: Index, E: ComplexField>(
-        symbolic: &supernodal::SymbolicSupernodalCholesky<I>,
-        L_values: GroupFor<E, &[E::Unit]>,
-    ) -> Mat<E> {
-        let L_values = SliceGroup::<'_, E>::new(L_values);
-        let ldlt = SupernodalLdltRef::new(symbolic, L_values.into_inner());
-        let n_supernodes = ldlt.symbolic().n_supernodes();
-        let n = ldlt.symbolic().nrows();
+    pub(crate) fn add_row_impl<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        col: SparseColMatRef<'_, I, E>,
+        conj: Conj,
+        llt: bool,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        let n = self.nrows();
+        let mut L = SliceGroupMut::<'_, E>::new(L_values);
+        assert!(all(
+            k < n,
+            col.nrows() == n,
+            col.ncols() == 1,
+            L.len() == self.len_values(),
+        ));
 
-        let mut dense = Mat::<E>::zeros(n, n);
+        let (mut x, stack) = make_raw::<E>(n, stack);
+        let (marks, stack) = stack.make_raw::<I>(n);
+        let (reach, stack) = stack.make_raw::<I>(n);
+        let (col_to_super, _) = stack.make_raw::<I>(n);
+        x.rb_mut().fill_zero();
+        marks.fill(I::truncate(0));
+        self.fill_col_to_super(col_to_super);
+        let (_, perm_inv) = self.perm().arrays();
+        let k = perm_inv[k].zx();
+
+        let col_k = self.factor_col(col_to_super, k);
+        // the rows of column `k` are all greater than `k`, and the columns in the reach of its
+        // upper part are all less than `k`, so they can share the same marks
+        for i in col_k.rows() {
+            marks[i] = I::truncate(1);
+        }
 
-        for s in 0..n_supernodes {
-            let s = ldlt.supernode(s);
-            let size = s.matrix().ncols();
+        // scatter the new column, and compute the nonzero pattern of row `k` of the factor
+        let mut reach_len = 0usize;
+        for (i, a) in zip(
+            col.row_indices_of_col(0),
+            SliceGroup::<'_, E>::new(col.values_of_col(0)).into_ref_iter(),
+        ) {
+            let i = perm_inv[i].zx();
+            let a = if conj == Conj::Yes {
+                a.read().faer_conj()
+            } else {
+                a.read()
+            };
+            x.write(i, x.read(i).faer_add(a));
 
-            let (Ls_top, Ls_bot) = s.matrix().split_at_row(size);
-            dense
-                .as_mut()
-                .submatrix_mut(s.start(), s.start(), size, size)
-                .copy_from(Ls_top);
+            if i > k && marks[i] == I::truncate(0) {
+                return Err(CholeskyUpdateError::StructurallyMissing { row: i, col: k });
+            }
+            let mut j = i;
+            while j < k && marks[j] == I::truncate(0) {
+                marks[j] = I::truncate(1);
+                reach[reach_len] = I::truncate(j);
+                reach_len += 1;
+
+                match self.factor_col(col_to_super, j).parent() {
+                    Some(parent) if parent <= k => j = parent,
+                    _ => return Err(CholeskyUpdateError::StructurallyMissing { row: k, col: j }),
+                }
+            }
+        }
+        let reach = &mut reach[..reach_len];
+        reach.sort_unstable();
+
+        // check the structure of the columns in the reach before modifying the factor
+        for &j in &*reach {
+            let col_j = self.factor_col(col_to_super, j.zx());
+            let mut found = false;
+            for i in col_j.rows() {
+                if i == k {
+                    found = true;
+                } else if i > k && marks[i] == I::truncate(0) {
+                    return Err(CholeskyUpdateError::StructurallyMissing { row: i, col: k });
+                }
+            }
+            if !found {
+                return Err(CholeskyUpdateError::StructurallyMissing {
+                    row: k,
+                    col: j.zx(),
+                });
+            }
+        }
 
-            for col in 0..size {
-                for (i, row) in s.pattern().iter().enumerate() {
-                    dense.write(row.zx(), s.start() + col, Ls_bot.read(i, col));
+        // solve for row `k` of the factor with the leading columns, while accumulating their
+        // contribution to column `k`
+        let mut d = x.read(k).faer_real();
+        x.write(k, E::faer_zero());
+        for &j in &*reach {
+            let j = j.zx();
+            let col_j = self.factor_col(col_to_super, j);
+            let dj = self.read_diag(L.rb(), col_j.pos, llt);
+            let mut y = x.read(j);
+            x.write(j, E::faer_zero());
+            if llt {
+                y = y.faer_scale_real(dj.faer_inv());
+                d = d.faer_sub(y.faer_abs2());
+            } else {
+                d = d.faer_sub(y.faer_abs2().faer_div(dj));
+            }
+
+            for (p, i) in zip(col_j.pos + 1.., col_j.rows()) {
+                if i == k {
+                    let l = if llt {
+                        y
+                    } else {
+                        y.faer_scale_real(dj.faer_inv())
+                    };
+                    L.write(p, l.faer_conj());
+                } else {
+                    x.write(i, x.read(i).faer_sub(L.read(p).faer_mul(y)));
                 }
             }
         }
 
-        let mut D = Mat::<E>::zeros(n, n);
-        zipped!(
-            D.as_mut().diagonal_mut().column_vector_mut().as_2d_mut(),
-            dense.as_ref().diagonal().column_vector().as_2d()
-        )
-        .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_inv()));
-        dense
-            .as_mut()
-            .diagonal_mut()
-            .column_vector_mut()
-            .fill(E::faer_one());
-        &dense * D * dense.adjoint()
-    }
+        let d = if llt {
+            if !(d > E::Real::faer_zero()) {
+                return Err(CholeskyUpdateError::NotPositiveDefinite);
+            }
+            d.faer_sqrt()
+        } else {
+            if d == E::Real::faer_zero() {
+                return Err(CholeskyUpdateError::ZeroPivot);
+            }
+            d
+        };
+
+        // compute column `k`, and remove its contribution from the trailing columns
+        let d_inv = d.faer_inv();
+        self.write_diag(L.rb_mut(), col_k.pos, d, llt);
+        for (p, i) in zip(col_k.pos + 1.., col_k.rows()) {
+            let l = x.read(i).faer_scale_real(d_inv);
+            L.write(p, l);
+            x.write(i, l);
+        }
+
+        match col_k.parent() {
+            Some(f) => {
+                let alpha = if llt {
+                    E::Real::faer_one().faer_neg()
+                } else {
+                    d.faer_neg()
+                };
+                self.rank_one_update(col_to_super, L, x, f, alpha, llt)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Replaces the LLT factorization of $A$ stored in `L_values` with that of $A + WW^H$ or
+    /// $A - WW^H$, depending on `kind`, without refactorizing the matrix.
+    ///
+    /// The row indices of `W` refer to the rows of $A$ before the fill-reducing permutation is
+    /// applied. The nonzero pattern of each column of `W` must be contained in the symbolic
+    /// structure of the factor. This is the case for example if it is contained in the pattern of
+    /// a column of a matrix $B$, and the symbolic structure was computed for $A + BB^H$.
+    ///
+    /// # Note
+    /// If the structure of `W` is not contained in that of the factor, an error is returned and
+    /// the factor is left unchanged. If an error is returned because the downdated matrix is not
+    /// positive definite, the contents of the factor are unspecified.
+    ///
+    /// # Panics
+    /// Panics if `W.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn update_llt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        W: SparseColMatRef<'_, I, E>,
+        kind: UpdateKind,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.update_impl(L_values, W, kind, Conj::No, true, stack)
+    }
+
+    /// Replaces the LDLT factorization of $A$ stored in `L_values` with that of $A + WW^H$ or
+    /// $A - WW^H$, depending on `kind`, without refactorizing the matrix.
+    ///
+    /// See [`Self::update_llt`] for the requirements on `W`.
+    ///
+    /// # Note
+    /// If the structure of `W` is not contained in that of the factor, an error is returned and
+    /// the factor is left unchanged. If an error is returned because a pivot is zero, the
+    /// contents of the factor are unspecified.
+    ///
+    /// # Panics
+    /// Panics if `W.nrows() != self.nrows()`.
+    #[track_caller]
+    pub fn update_ldlt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        W: SparseColMatRef<'_, I, E>,
+        kind: UpdateKind,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.update_impl(L_values, W, kind, Conj::No, false, stack)
+    }
+
+    /// Replaces the LLT factorization of $A$ stored in `L_values` with that of the matrix
+    /// obtained by replacing the `k`-th row and column of $A$ with those of the identity.
+    ///
+    /// The index `k` refers to the rows of $A$ before the fill-reducing permutation is applied.
+    /// The symbolic structure of the factor is unchanged, so that the row can be added back
+    /// with [`Self::add_row_llt`].
+    ///
+    /// # Note
+    /// This takes time proportional to the number of nonzeros of the factor in the columns that
+    /// precede `k`.
+    ///
+    /// # Panics
+    /// Panics if `k >= self.nrows()`.
+    #[track_caller]
+    pub fn delete_row_llt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.delete_row_impl(L_values, k, true, stack)
+    }
+
+    /// Replaces the LDLT factorization of $A$ stored in `L_values` with that of the matrix
+    /// obtained by replacing the `k`-th row and column of $A$ with those of the identity.
+    ///
+    /// See [`Self::delete_row_llt`] for more details.
+    ///
+    /// # Panics
+    /// Panics if `k >= self.nrows()`.
+    #[track_caller]
+    pub fn delete_row_ldlt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.delete_row_impl(L_values, k, false, stack)
+    }
+
+    /// Replaces the LLT factorization of $A$ stored in `L_values`, whose `k`-th row and column
+    /// are those of the identity, with that of the matrix obtained by replacing them with `col`
+    /// and its adjoint.
+    ///
+    /// `col` must be a matrix with a single column containing the whole `k`-th column of the new
+    /// matrix, including its diagonal entry. Its row indices, as well as `k`, refer to the rows of
+    /// $A$ before the fill-reducing permutation is applied. The nonzero pattern of `col` must be
+    /// contained in that of the `k`-th column of the matrix that was used to compute the symbolic
+    /// structure.
+    ///
+    /// # Note
+    /// If the structure of `col` is not contained in that of the factor, an error is returned and
+    /// the factor is left unchanged. If an error is returned because the new matrix is not
+    /// positive definite, the contents of the factor are unspecified.
+    ///
+    /// # Panics
+    /// Panics if `k >= self.nrows()`, or if `col` doesn't have the shape `(self.nrows(), 1)`.
+    #[track_caller]
+    pub fn add_row_llt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        col: SparseColMatRef<'_, I, E>,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.add_row_impl(L_values, k, col, Conj::No, true, stack)
+    }
+
+    /// Replaces the LDLT factorization of $A$ stored in `L_values`, whose `k`-th row and column
+    /// are those of the identity, with that of the matrix obtained by replacing them with `col`
+    /// and its adjoint.
+    ///
+    /// See [`Self::add_row_llt`] for the requirements on `col`.
+    ///
+    /// # Panics
+    /// Panics if `k >= self.nrows()`, or if `col` doesn't have the shape `(self.nrows(), 1)`.
+    #[track_caller]
+    pub fn add_row_ldlt<E: ComplexField>(
+        &self,
+        L_values: GroupFor<E, &mut [E::Unit]>,
+        k: usize,
+        col: SparseColMatRef<'_, I, E>,
+        stack: PodStack<'_>,
+    ) -> Result<(), CholeskyUpdateError> {
+        self.add_row_impl(L_values, k, col, Conj::No, false, stack)
+    }
+}
+
+/// Fill-reducing ordering computed by the symbolic Cholesky factorization.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub enum SymmetricOrdering {
+    /// Approximate minimum degree ordering. See [`amd`].
+    #[default]
+    Amd,
+    /// Reverse Cuthill-McKee ordering, which reduces the bandwidth of the matrix rather than the
+    /// fill-in of the factor. See [`rcm`].
+    ReverseCuthillMcKee,
+    /// Nested dissection ordering, usually preferable to [`SymmetricOrdering::Amd`] for large
+    /// matrices arising from 2D and 3D meshes, computed with the given parameters. See
+    /// [`nested_dissection`].
+    NestedDissection(nested_dissection::Control),
+}
+
+/// Tuning parameters for the symbolic Cholesky factorization.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct CholeskySymbolicParams<'a> {
+    /// Fill-reducing ordering.
+    pub ordering: SymmetricOrdering,
+    /// Parameters for computing the fill-reducing permutation, when using
+    /// [`SymmetricOrdering::Amd`].
+    pub amd_params: Control,
+    /// Threshold for selecting the supernodal factorization.
+    pub supernodal_flop_ratio_threshold: SupernodalThreshold,
+    /// Supernodal factorization parameters.
+    pub supernodal_params: SymbolicSupernodalParams<'a>,
+}
+
+/// Computes the symbolic Cholesky factorization of the matrix `A`, or returns an error if the
+/// operation could not be completed.
+pub fn factorize_symbolic_cholesky<I: Index>(
+    A: SymbolicSparseColMatRef<'_, I>,
+    side: Side,
+    params: CholeskySymbolicParams<'_>,
+) -> Result<SymbolicCholesky<I>, FaerError> {
+    let n = A.nrows();
+    let A_nnz = A.compute_nnz();
+
+    assert!(A.nrows() == A.ncols());
+    ghost::with_size(n, |N| {
+        let A = ghost::SymbolicSparseColMatRef::new(A, N, N);
+
+        let req = || -> Result<StackReq, SizeOverflow> {
+            let n_req = StackReq::try_new::<I>(n)?;
+            let A_req = StackReq::try_and(
+                // new_col_ptr
+                StackReq::try_new::<I>(n + 1)?,
+                // new_row_ind
+                StackReq::try_new::<I>(A_nnz)?,
+            )?;
+
+            let ordering_req = match params.ordering {
+                SymmetricOrdering::Amd => amd::order_maybe_unsorted_req::<I>(n, A_nnz)?,
+                SymmetricOrdering::ReverseCuthillMcKee => rcm::order_req::<I>(n, A_nnz)?,
+                SymmetricOrdering::NestedDissection(_) => {
+                    nested_dissection::order_req::<I>(n, A_nnz)?
+                }
+            };
+
+            StackReq::try_or(
+                ordering_req,
+                StackReq::try_all_of([
+                    A_req,
+                    // permute_symmetric | etree
+                    n_req,
+                    // col_counts
+                    n_req,
+                    // ghost_prefactorize_symbolic
+                    n_req,
+                    // ghost_factorize_*_symbolic
+                    StackReq::try_or(
+                        supernodal::factorize_supernodal_symbolic_cholesky_req::<I>(n)?,
+                        simplicial::factorize_simplicial_symbolic_req::<I>(n)?,
+                    )?,
+                ])?,
+            )
+        };
+
+        let req = req().map_err(nomem)?;
+        let mut mem = dyn_stack::GlobalPodBuffer::try_new(req).map_err(nomem)?;
+        let mut stack = PodStack::new(&mut mem);
+
+        let mut perm_fwd = try_zeroed(n)?;
+        let mut perm_inv = try_zeroed(n)?;
+        let flops = match params.ordering {
+            SymmetricOrdering::Amd => {
+                let flops = amd::order_maybe_unsorted(
+                    &mut perm_fwd,
+                    &mut perm_inv,
+                    A.into_inner(),
+                    params.amd_params,
+                    stack.rb_mut(),
+                )?;
+                Some(flops.n_div + flops.n_mult_subs_ldl)
+            }
+            SymmetricOrdering::ReverseCuthillMcKee => {
+                rcm::order(&mut perm_fwd, &mut perm_inv, A.into_inner(), stack.rb_mut())?;
+                None
+            }
+            SymmetricOrdering::NestedDissection(nested_dissection_params) => {
+                nested_dissection::order(
+                    &mut perm_fwd,
+                    &mut perm_inv,
+                    A.into_inner(),
+                    nested_dissection_params,
+                    stack.rb_mut(),
+                )?;
+                None
+            }
+        };
+        let perm_ = ghost::PermRef::new(PermRef::new_checked(&perm_fwd, &perm_inv), N);
+
+        let (new_col_ptr, stack) = stack.make_raw::<I>(n + 1);
+        let (new_row_ind, mut stack) = stack.make_raw::<I>(A_nnz);
+        let A = unsafe {
+            ghost_permute_hermitian_unsorted_symbolic(
+                new_col_ptr,
+                new_row_ind,
+                A,
+                perm_,
+                side,
+                Side::Upper,
+                stack.rb_mut(),
+            )
+        };
+
+        let (etree, stack) = stack.make_raw::<I::Signed>(n);
+        let (col_counts, mut stack) = stack.make_raw::<I>(n);
+        let etree = Array::from_mut(etree, N);
+        let col_counts = Array::from_mut(col_counts, N);
+        let etree =
+            &*ghost_prefactorize_symbolic_cholesky::<I>(etree, col_counts, A, stack.rb_mut());
+        let L_nnz = I::sum_nonnegative(col_counts.as_ref()).ok_or(FaerError::IndexOverflow)?;
+
+        // the flop count is only provided by amd, otherwise compute it from the column counts
+        let flops = flops.unwrap_or_else(|| {
+            col_counts
+                .as_ref()
+                .iter()
+                .map(|&count| {
+                    let count = (count.zx() - 1) as f64;
+                    count + count * count
+                })
+                .sum()
+        });
+
+        let raw = if (flops / L_nnz.zx() as f64)
+            > params.supernodal_flop_ratio_threshold.0
+                * crate::sparse::linalg::CHOLESKY_SUPERNODAL_RATIO_FACTOR
+        {
+            SymbolicCholeskyRaw::Supernodal(supernodal::ghost_factorize_supernodal_symbolic(
+                A,
+                None,
+                None,
+                supernodal::CholeskyInput::A,
+                etree,
+                col_counts,
+                stack.rb_mut(),
+                params.supernodal_params,
+            )?)
+        } else {
+            SymbolicCholeskyRaw::Simplicial(
+                simplicial::ghost_factorize_simplicial_symbolic_cholesky(
+                    A,
+                    etree,
+                    col_counts,
+                    stack.rb_mut(),
+                )?,
+            )
+        };
+
+        Ok(SymbolicCholesky {
+            raw,
+            perm_fwd,
+            perm_inv,
+            A_nnz,
+        })
+    })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{supernodal::SupernodalLdltRef, *};
+    use crate::{
+        assert,
+        sparse::linalg::{
+            cholesky::supernodal::{CholeskyInput, SupernodalIntranodeBunchKaufmanRef},
+            qd::Double,
+        },
+        sparse::SparseColMat,
+        Mat,
+    };
+    use dyn_stack::GlobalPodBuffer;
+    use num_complex::Complex;
+    use rand::{Rng, SeedableRng};
+
+    fn test_counts<I: Index>() {
+        let truncate = I::truncate;
+
+        let n = 11;
+        let col_ptr = &[0, 3, 6, 10, 13, 16, 21, 24, 29, 31, 37, 43].map(truncate);
+        let row_ind = &[
+            0, 5, 6, // 0
+            1, 2, 7, // 1
+            1, 2, 9, 10, // 2
+            3, 5, 9, // 3
+            4, 7, 10, // 4
+            0, 3, 5, 8, 9, // 5
+            0, 6, 10, // 6
+            1, 4, 7, 9, 10, // 7
+            5, 8, // 8
+            2, 3, 5, 7, 9, 10, // 9
+            2, 4, 6, 7, 9, 10, // 10
+        ]
+        .map(truncate);
+
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind);
+        let zero = truncate(0);
+        let mut etree = vec![zero.to_signed(); n];
+        let mut col_count = vec![zero; n];
+        ghost::with_size(n, |N| {
+            let A = ghost::SymbolicSparseColMatRef::new(A, N, N);
+            let etree = ghost_prefactorize_symbolic_cholesky(
+                Array::from_mut(&mut etree, N),
+                Array::from_mut(&mut col_count, N),
+                A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
+            );
+
+            supernodal::ghost_factorize_supernodal_symbolic(
+                A,
+                None,
+                None,
+                CholeskyInput::A,
+                etree,
+                Array::from_ref(&col_count, N),
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+                Default::default(),
+            )
+            .unwrap();
+        });
+        assert_eq!(
+            etree,
+            [5, 2, 7, 5, 7, 6, 8, 9, 9, 10, NONE].map(I::Signed::truncate)
+        );
+        assert_eq!(col_count, [3, 3, 4, 3, 3, 4, 4, 3, 3, 2, 1].map(truncate));
+    }
+
+    include!("./data.rs");
+
+    fn test_amd<I: Index>() {
+        for &(_, (_, col_ptr, row_ind, _)) in ALL {
+            let I = I::truncate;
+            let n = col_ptr.len() - 1;
+
+            let (amd_perm, amd_perm_inv, _) =
+                ::amd::order(n, col_ptr, row_ind, &Default::default()).unwrap();
+            let col_ptr = &*col_ptr.iter().copied().map(I).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(I).collect::<Vec<_>>();
+            let amd_perm = &*amd_perm.iter().copied().map(I).collect::<Vec<_>>();
+            let amd_perm_inv = &*amd_perm_inv.iter().copied().map(I).collect::<Vec<_>>();
+            let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind);
+
+            let perm = &mut vec![I(0); n];
+            let perm_inv = &mut vec![I(0); n];
+
+            crate::sparse::linalg::amd::order_maybe_unsorted(
+                perm,
+                perm_inv,
+                A,
+                Default::default(),
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    crate::sparse::linalg::amd::order_maybe_unsorted_req::<I>(n, row_ind.len())
+                        .unwrap(),
+                )),
+            )
+            .unwrap();
+
+            assert!(perm == amd_perm);
+            assert!(perm_inv == amd_perm_inv);
+        }
+    }
+
+    fn sparse_to_dense<I: Index, E: ComplexField>(sparse: SparseColMatRef<'_, I, E>) -> Mat<E> {
+        let m = sparse.nrows();
+        let n = sparse.ncols();
+
+        let mut dense = Mat::<E>::zeros(m, n);
+        let slice_group = SliceGroup::<'_, E>::new;
+
+        for j in 0..n {
+            for (i, val) in zip(
+                sparse.row_indices_of_col(j),
+                slice_group(sparse.values_of_col(j)).into_ref_iter(),
+            ) {
+                dense.write(i, j, val.read());
+            }
+        }
+
+        dense
+    }
+
+    fn reconstruct_from_supernodal_llt<I: Index, E: ComplexField>(
+        symbolic: &supernodal::SymbolicSupernodalCholesky<I>,
+        L_values: GroupFor<E, &[E::Unit]>,
+    ) -> Mat<E> {
+        let L_values = SliceGroup::<'_, E>::new(L_values);
+        let ldlt = SupernodalLdltRef::new(symbolic, L_values.into_inner());
+        let n_supernodes = ldlt.symbolic().n_supernodes();
+        let n = ldlt.symbolic().nrows();
+
+        let mut dense = Mat::<E>::zeros(n, n);
+
+        for s in 0..n_supernodes {
+            let s = ldlt.supernode(s);
+            let size = s.matrix().ncols();
+
+            let (Ls_top, Ls_bot) = s.matrix().split_at_row(size);
+            dense
+                .as_mut()
+                .submatrix_mut(s.start(), s.start(), size, size)
+                .copy_from(Ls_top);
+
+            for col in 0..size {
+                for (i, row) in s.pattern().iter().enumerate() {
+                    dense.write(row.zx(), s.start() + col, Ls_bot.read(i, col));
+                }
+            }
+        }
+
+        &dense * dense.adjoint()
+    }
+
+    fn reconstruct_from_supernodal_ldlt<I: Index, E: ComplexField>(
+        symbolic: &supernodal::SymbolicSupernodalCholesky<I>,
+        L_values: GroupFor<E, &[E::Unit]>,
+    ) -> Mat<E> {
+        let L_values = SliceGroup::<'_, E>::new(L_values);
+        let ldlt = SupernodalLdltRef::new(symbolic, L_values.into_inner());
+        let n_supernodes = ldlt.symbolic().n_supernodes();
+        let n = ldlt.symbolic().nrows();
+
+        let mut dense = Mat::<E>::zeros(n, n);
+
+        for s in 0..n_supernodes {
+            let s = ldlt.supernode(s);
+            let size = s.matrix().ncols();
+
+            let (Ls_top, Ls_bot) = s.matrix().split_at_row(size);
+            dense
+                .as_mut()
+                .submatrix_mut(s.start(), s.start(), size, size)
+                .copy_from(Ls_top);
+
+            for col in 0..size {
+                for (i, row) in s.pattern().iter().enumerate() {
+                    dense.write(row.zx(), s.start() + col, Ls_bot.read(i, col));
+                }
+            }
+        }
+
+        let mut D = Mat::<E>::zeros(n, n);
+        zipped!(
+            D.as_mut().diagonal_mut().column_vector_mut().as_2d_mut(),
+            dense.as_ref().diagonal().column_vector().as_2d()
+        )
+        .for_each(|unzipped!(mut dst, src)| dst.write(src.read().faer_inv()));
+        dense
+            .as_mut()
+            .diagonal_mut()
+            .column_vector_mut()
+            .fill(E::faer_one());
+        &dense * D * dense.adjoint()
+    }
 
     fn reconstruct_from_simplicial_llt<'a, I: Index, E: ComplexField>(
         symbolic: &'a simplicial::SymbolicSimplicialCholesky<I>,
@@ -5357,94 +6494,467 @@ pub(crate) mod tests {
                 n,
                 symbolic.col_ptrs(),
                 None,
-                symbolic.row_indices(),
-            ),
-            L_values.into_inner(),
-        );
+                symbolic.row_indices(),
+            ),
+            L_values.into_inner(),
+        );
+
+        for j in 0..n {
+            for (i, val) in zip(
+                L.row_indices_of_col(j),
+                slice_group(L.values_of_col(j)).into_ref_iter(),
+            ) {
+                dense.write(i, j, val.read());
+            }
+        }
+
+        &dense * dense.adjoint()
+    }
+
+    fn reconstruct_from_simplicial_ldlt<'a, I: Index, E: ComplexField>(
+        symbolic: &'a simplicial::SymbolicSimplicialCholesky<I>,
+        L_values: GroupFor<E, &'a [E::Unit]>,
+    ) -> Mat<E> {
+        let slice_group = SliceGroup::<'_, E>::new;
+        let L_values = slice_group(L_values);
+        let n = symbolic.nrows();
+        let mut dense = Mat::<E>::zeros(n, n);
+
+        let L = SparseColMatRef::<'_, I, E>::new(
+            SymbolicSparseColMatRef::new_unsorted_checked(
+                n,
+                n,
+                symbolic.col_ptrs(),
+                None,
+                symbolic.row_indices(),
+            ),
+            L_values.into_inner(),
+        );
+
+        for j in 0..n {
+            for (i, val) in zip(
+                L.row_indices_of_col(j),
+                slice_group(L.values_of_col(j)).into_ref_iter(),
+            ) {
+                dense.write(i, j, val.read());
+            }
+        }
+
+        let mut D = Mat::<E>::zeros(n, n);
+        D.as_mut()
+            .diagonal_mut()
+            .column_vector_mut()
+            .copy_from(dense.as_ref().diagonal().column_vector());
+        dense
+            .as_mut()
+            .diagonal_mut()
+            .column_vector_mut()
+            .fill(E::faer_one());
+
+        &dense * D * dense.adjoint()
+    }
+
+    fn test_supernodal<I: Index>() {
+        type E = Complex<Double<f64>>;
+        let truncate = I::truncate;
+
+        let (_, col_ptr, row_ind, values) = MEDIUM;
+
+        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
+        let mut complexify = |e: E| {
+            let i = E::faer_one().faer_neg().faer_sqrt();
+            if e == E::faer_from_f64(1.0) {
+                e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+            } else {
+                e
+            }
+        };
+
+        let n = col_ptr.len() - 1;
+        let nnz = values.len();
+        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
+        let values_mat =
+            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
+        let values = values_mat.col_as_slice(0);
+
+        let A = SparseColMatRef::<'_, I, E>::new(
+            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+            values,
+        );
+        let zero = truncate(0);
+        let mut etree = vec![zero.to_signed(); n];
+        let mut col_count = vec![zero; n];
+        ghost::with_size(n, |N| {
+            let A = ghost::SparseColMatRef::new(A, N, N);
+            let etree = ghost_prefactorize_symbolic_cholesky(
+                Array::from_mut(&mut etree, N),
+                Array::from_mut(&mut col_count, N),
+                *A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
+            );
+
+            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
+                *A,
+                None,
+                None,
+                CholeskyInput::A,
+                etree,
+                Array::from_ref(&col_count, N),
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+                Default::default(),
+            )
+            .unwrap();
+
+            let mut A_lower_col_ptr = col_ptr.to_vec();
+            let mut A_lower_values = values_mat.clone();
+            let mut A_lower_row_ind = row_ind.to_vec();
+            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
+            let A_lower = crate::sparse::utils::ghost_adjoint(
+                &mut A_lower_col_ptr,
+                &mut A_lower_row_ind,
+                A_lower_values,
+                A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+            );
+            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
+
+            supernodal::factorize_supernodal_numeric_ldlt(
+                values.col_as_slice_mut(0),
+                A_lower.into_inner().into_const(),
+                Default::default(),
+                &symbolic,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    supernodal::factorize_supernodal_numeric_ldlt_req::<I, E>(
+                        &symbolic,
+                        Parallelism::None,
+                    )
+                    .unwrap(),
+                )),
+            );
+            let mut A = sparse_to_dense(A.into_inner());
+            for j in 0..n {
+                for i in j + 1..n {
+                    A.write(i, j, A.read(j, i).faer_conj());
+                }
+            }
+
+            let err =
+                reconstruct_from_supernodal_ldlt::<I, E>(&symbolic, values.col_as_slice(0)) - A;
+            let mut max = <E as ComplexField>::Real::faer_zero();
+            for j in 0..n {
+                for i in 0..n {
+                    let x = err.read(i, j).faer_abs();
+                    max = if max > x { max } else { x }
+                }
+            }
+            assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+        });
+    }
+
+    fn test_supernodal_ldlt<I: Index>() {
+        type E = Complex<Double<f64>>;
+        let truncate = I::truncate;
+
+        let (_, col_ptr, row_ind, values) = MEDIUM;
+
+        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
+        let i = E::faer_one().faer_neg().faer_sqrt();
+        let mut complexify = |e: E| {
+            if e == E::faer_from_f64(1.0) {
+                e.faer_add(i.faer_mul(E::faer_from_f64(2000.0 * gen.gen::<f64>())))
+                    .faer_add(E::faer_from_f64(2000.0 * gen.gen::<f64>()))
+            } else {
+                e.faer_add(E::faer_from_f64(100.0 * gen.gen::<f64>()))
+            }
+        };
+
+        let n = col_ptr.len() - 1;
+        let nnz = values.len();
+        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
+        let values_mat =
+            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
+        let values = values_mat.col_as_slice(0);
+
+        let A = SparseColMatRef::<'_, I, E>::new(
+            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+            values,
+        );
+        let mut A_dense = sparse_to_dense(A);
+        for j in 0..n {
+            for i in j + 1..n {
+                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
+            }
+        }
+
+        let zero = truncate(0);
+        let mut etree = vec![zero.to_signed(); n];
+        let mut col_count = vec![zero; n];
+        ghost::with_size(n, |N| {
+            let A = ghost::SparseColMatRef::new(A, N, N);
+            let etree = ghost_prefactorize_symbolic_cholesky(
+                Array::from_mut(&mut etree, N),
+                Array::from_mut(&mut col_count, N),
+                *A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
+            );
+
+            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
+                *A,
+                None,
+                None,
+                CholeskyInput::A,
+                etree,
+                Array::from_ref(&col_count, N),
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+                Default::default(),
+            )
+            .unwrap();
 
-        for j in 0..n {
-            for (i, val) in zip(
-                L.row_indices_of_col(j),
-                slice_group(L.values_of_col(j)).into_ref_iter(),
-            ) {
-                dense.write(i, j, val.read());
-            }
-        }
+            let mut A_lower_col_ptr = col_ptr.to_vec();
+            let mut A_lower_values = values_mat.clone();
+            let mut A_lower_row_ind = row_ind.to_vec();
+            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
+            let A_lower = crate::sparse::utils::ghost_adjoint(
+                &mut A_lower_col_ptr,
+                &mut A_lower_row_ind,
+                A_lower_values,
+                A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+            );
+            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
 
-        &dense * dense.adjoint()
+            supernodal::factorize_supernodal_numeric_ldlt(
+                values.col_as_slice_mut(0),
+                A_lower.into_inner().into_const(),
+                Default::default(),
+                &symbolic,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    supernodal::factorize_supernodal_numeric_ldlt_req::<I, E>(
+                        &symbolic,
+                        Parallelism::None,
+                    )
+                    .unwrap(),
+                )),
+            );
+            let k = 2;
+
+            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
+                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+            });
+            for conj in [Conj::Yes, Conj::No] {
+                let mut x = rhs.clone();
+                let ldlt = SupernodalLdltRef::new(&symbolic, values.col_as_slice(0));
+                ldlt.solve_in_place_with_conj(
+                    conj,
+                    x.as_mut(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic.solve_in_place_req::<E>(k).unwrap(),
+                    )),
+                );
+
+                let rhs_reconstructed = if conj == Conj::No {
+                    &A_dense * &x
+                } else {
+                    A_dense.conjugate() * &x
+                };
+                let mut max = <E as ComplexField>::Real::faer_zero();
+                for j in 0..k {
+                    for i in 0..n {
+                        let x = rhs_reconstructed
+                            .read(i, j)
+                            .faer_sub(rhs.read(i, j))
+                            .faer_abs();
+                        max = if max > x { max } else { x }
+                    }
+                }
+                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+            }
+        });
     }
 
-    fn reconstruct_from_simplicial_ldlt<'a, I: Index, E: ComplexField>(
-        symbolic: &'a simplicial::SymbolicSimplicialCholesky<I>,
-        L_values: GroupFor<E, &'a [E::Unit]>,
-    ) -> Mat<E> {
-        let slice_group = SliceGroup::<'_, E>::new;
-        let L_values = slice_group(L_values);
-        let n = symbolic.nrows();
-        let mut dense = Mat::<E>::zeros(n, n);
+    fn test_supernodal_intranode_bk_1<I: Index>() {
+        type E = Complex<f64>;
+        let truncate = I::truncate;
 
-        let L = SparseColMatRef::<'_, I, E>::new(
-            SymbolicSparseColMatRef::new_unsorted_checked(
-                n,
-                n,
-                symbolic.col_ptrs(),
-                None,
-                symbolic.row_indices(),
-            ),
-            L_values.into_inner(),
-        );
+        let (_, col_ptr, row_ind, values) = MEDIUM;
+
+        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
+        let i = E::faer_one().faer_neg().faer_sqrt();
+
+        let n = col_ptr.len() - 1;
+        let nnz = values.len();
+        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
 
+        let mut complexify = |e: E| {
+            let i = E::faer_one().faer_neg().faer_sqrt();
+            if e == E::faer_from_f64(1.0) {
+                e.faer_add(i.faer_mul(E::faer_from_f64(1000.0 * gen.gen::<f64>())))
+            } else {
+                e.faer_add(E::faer_from_f64(1000.0 * gen.gen::<f64>()))
+            }
+        };
+        let values_mat =
+            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
+        let values = values_mat.col_as_slice(0);
+
+        let A = SparseColMatRef::<'_, I, E>::new(
+            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+            values,
+        );
+        let mut A_dense = sparse_to_dense(A);
         for j in 0..n {
-            for (i, val) in zip(
-                L.row_indices_of_col(j),
-                slice_group(L.values_of_col(j)).into_ref_iter(),
-            ) {
-                dense.write(i, j, val.read());
+            for i in j + 1..n {
+                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
             }
         }
 
-        let mut D = Mat::<E>::zeros(n, n);
-        D.as_mut()
-            .diagonal_mut()
-            .column_vector_mut()
-            .copy_from(dense.as_ref().diagonal().column_vector());
-        dense
-            .as_mut()
-            .diagonal_mut()
-            .column_vector_mut()
-            .fill(E::faer_one());
+        let zero = truncate(0);
+        let mut etree = vec![zero.to_signed(); n];
+        let mut col_count = vec![zero; n];
+        ghost::with_size(n, |N| {
+            let A = ghost::SparseColMatRef::new(A, N, N);
+            let etree = ghost_prefactorize_symbolic_cholesky(
+                Array::from_mut(&mut etree, N),
+                Array::from_mut(&mut col_count, N),
+                *A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
+            );
 
-        &dense * D * dense.adjoint()
+            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
+                *A,
+                None,
+                None,
+                CholeskyInput::A,
+                etree,
+                Array::from_ref(&col_count, N),
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+                Default::default(),
+            )
+            .unwrap();
+
+            let mut A_lower_col_ptr = col_ptr.to_vec();
+            let mut A_lower_values = values_mat.clone();
+            let mut A_lower_row_ind = row_ind.to_vec();
+            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
+            let A_lower = crate::sparse::utils::ghost_adjoint(
+                &mut A_lower_col_ptr,
+                &mut A_lower_row_ind,
+                A_lower_values,
+                A,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+            );
+            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
+
+            let mut fwd = vec![zero; n];
+            let mut inv = vec![zero; n];
+            let mut subdiag = Mat::<E>::zeros(n, 1);
+
+            supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman(
+                values.col_as_slice_mut(0),
+                subdiag.col_as_slice_mut(0),
+                &mut fwd,
+                &mut inv,
+                A_lower.into_inner().into_const(),
+                Default::default(),
+                &symbolic,
+                Parallelism::None,
+                PodStack::new(&mut GlobalPodBuffer::new(
+                    supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman_req::<I, E>(
+                        &symbolic,
+                        Parallelism::None,
+                    )
+                    .unwrap(),
+                )),
+            );
+            let k = 2;
+
+            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
+                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+            });
+            for conj in [Conj::Yes, Conj::No] {
+                let mut x = rhs.clone();
+                let lblt = SupernodalIntranodeBunchKaufmanRef::new(
+                    &symbolic,
+                    values.col_as_slice(0),
+                    subdiag.col_as_slice(0),
+                    PermRef::new_checked(&fwd, &inv),
+                );
+                crate::perm::permute_rows_in_place(
+                    x.as_mut(),
+                    lblt.perm,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
+                    )),
+                );
+                lblt.solve_in_place_no_numeric_permute_with_conj(
+                    conj,
+                    x.as_mut(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic.solve_in_place_req::<E>(k).unwrap(),
+                    )),
+                );
+                crate::perm::permute_rows_in_place(
+                    x.as_mut(),
+                    lblt.perm.inverse(),
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
+                    )),
+                );
+
+                let rhs_reconstructed = if conj == Conj::No {
+                    &A_dense * &x
+                } else {
+                    A_dense.conjugate() * &x
+                };
+                let mut max = <E as ComplexField>::Real::faer_zero();
+                for j in 0..k {
+                    for i in 0..n {
+                        let x = rhs_reconstructed
+                            .read(i, j)
+                            .faer_sub(rhs.read(i, j))
+                            .faer_abs();
+                        max = if max > x { max } else { x }
+                    }
+                }
+                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-10));
+            }
+        });
     }
 
-    fn test_supernodal<I: Index>() {
-        type E = Complex<Double<f64>>;
+    fn test_supernodal_intranode_bk_2<I: Index>() {
+        type E = Complex<f64>;
         let truncate = I::truncate;
 
-        let (_, col_ptr, row_ind, values) = MEDIUM;
+        let (_, col_ptr, row_ind, values) = MEDIUM_P;
 
         let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-        let mut complexify = |e: E| {
-            let i = E::faer_one().faer_neg().faer_sqrt();
-            if e == E::faer_from_f64(1.0) {
-                e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-            } else {
-                e
-            }
-        };
+        let i = E::faer_one().faer_neg().faer_sqrt();
 
         let n = col_ptr.len() - 1;
         let nnz = values.len();
         let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
         let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
-        let values_mat =
-            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
+        let values_mat = crate::Mat::<E>::from_fn(nnz, 1, |i, _| values[i]);
         let values = values_mat.col_as_slice(0);
 
         let A = SparseColMatRef::<'_, I, E>::new(
             SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
             values,
         );
+        let mut A_dense = sparse_to_dense(A);
+        for j in 0..n {
+            for i in j + 1..n {
+                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
+            }
+        }
+
         let zero = truncate(0);
         let mut etree = vec![zero.to_signed(); n];
         let mut col_count = vec![zero; n];
@@ -5482,54 +6992,96 @@ pub(crate) mod tests {
             );
             let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
 
-            supernodal::factorize_supernodal_numeric_ldlt(
+            let mut fwd = vec![zero; n];
+            let mut inv = vec![zero; n];
+            let mut subdiag = Mat::<E>::zeros(n, 1);
+
+            supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman(
                 values.col_as_slice_mut(0),
+                subdiag.col_as_slice_mut(0),
+                &mut fwd,
+                &mut inv,
                 A_lower.into_inner().into_const(),
                 Default::default(),
                 &symbolic,
                 Parallelism::None,
                 PodStack::new(&mut GlobalPodBuffer::new(
-                    supernodal::factorize_supernodal_numeric_ldlt_req::<I, E>(
+                    supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman_req::<I, E>(
                         &symbolic,
                         Parallelism::None,
                     )
                     .unwrap(),
                 )),
             );
-            let mut A = sparse_to_dense(A.into_inner());
-            for j in 0..n {
-                for i in j + 1..n {
-                    A.write(i, j, A.read(j, i).faer_conj());
-                }
-            }
+            let k = 2;
 
-            let err =
-                reconstruct_from_supernodal_ldlt::<I, E>(&symbolic, values.col_as_slice(0)) - A;
-            let mut max = <E as ComplexField>::Real::faer_zero();
-            for j in 0..n {
-                for i in 0..n {
-                    let x = err.read(i, j).faer_abs();
-                    max = if max > x { max } else { x }
+            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
+                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+            });
+            for conj in [Conj::Yes, Conj::No] {
+                let mut x = rhs.clone();
+                let lblt = SupernodalIntranodeBunchKaufmanRef::new(
+                    &symbolic,
+                    values.col_as_slice(0),
+                    subdiag.col_as_slice(0),
+                    PermRef::new_checked(&fwd, &inv),
+                );
+                crate::perm::permute_rows_in_place(
+                    x.as_mut(),
+                    lblt.perm,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
+                    )),
+                );
+                lblt.solve_in_place_no_numeric_permute_with_conj(
+                    conj,
+                    x.as_mut(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic.solve_in_place_req::<E>(k).unwrap(),
+                    )),
+                );
+                crate::perm::permute_rows_in_place(
+                    x.as_mut(),
+                    lblt.perm.inverse(),
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
+                    )),
+                );
+
+                let rhs_reconstructed = if conj == Conj::No {
+                    &A_dense * &x
+                } else {
+                    A_dense.conjugate() * &x
+                };
+                let mut max = <E as ComplexField>::Real::faer_zero();
+                for j in 0..k {
+                    for i in 0..n {
+                        let x = rhs_reconstructed
+                            .read(i, j)
+                            .faer_sub(rhs.read(i, j))
+                            .faer_abs();
+                        max = if max > x { max } else { x }
+                    }
                 }
+                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-10));
             }
-            assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
         });
     }
 
-    fn test_supernodal_ldlt<I: Index>() {
+    fn test_simplicial<I: Index>() {
         type E = Complex<Double<f64>>;
         let truncate = I::truncate;
 
-        let (_, col_ptr, row_ind, values) = MEDIUM;
+        let (_, col_ptr, row_ind, values) = SMALL;
 
         let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-        let i = E::faer_one().faer_neg().faer_sqrt();
         let mut complexify = |e: E| {
+            let i = E::faer_one().faer_neg().faer_sqrt();
             if e == E::faer_from_f64(1.0) {
-                e.faer_add(i.faer_mul(E::faer_from_f64(2000.0 * gen.gen::<f64>())))
-                    .faer_add(E::faer_from_f64(2000.0 * gen.gen::<f64>()))
+                e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
             } else {
-                e.faer_add(E::faer_from_f64(100.0 * gen.gen::<f64>()))
+                e
             }
         };
 
@@ -5545,13 +7097,6 @@ pub(crate) mod tests {
             SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
             values,
         );
-        let mut A_dense = sparse_to_dense(A);
-        for j in 0..n {
-            for i in j + 1..n {
-                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
-            }
-        }
-
         let zero = truncate(0);
         let mut etree = vec![zero.to_signed(); n];
         let mut col_count = vec![zero; n];
@@ -5564,530 +7109,668 @@ pub(crate) mod tests {
                 PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
             );
 
-            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
+            let symbolic = simplicial::ghost_factorize_simplicial_symbolic_cholesky(
                 *A,
-                None,
-                None,
-                CholeskyInput::A,
                 etree,
                 Array::from_ref(&col_count, N),
                 PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-                Default::default(),
             )
             .unwrap();
 
-            let mut A_lower_col_ptr = col_ptr.to_vec();
-            let mut A_lower_values = values_mat.clone();
-            let mut A_lower_row_ind = row_ind.to_vec();
-            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
-            let A_lower = crate::sparse::utils::ghost_adjoint(
-                &mut A_lower_col_ptr,
-                &mut A_lower_row_ind,
-                A_lower_values,
-                A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-            );
             let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
 
-            supernodal::factorize_supernodal_numeric_ldlt(
+            simplicial::factorize_simplicial_numeric_ldlt::<I, E>(
                 values.col_as_slice_mut(0),
-                A_lower.into_inner().into_const(),
+                A.into_inner(),
                 Default::default(),
                 &symbolic,
-                Parallelism::None,
                 PodStack::new(&mut GlobalPodBuffer::new(
-                    supernodal::factorize_supernodal_numeric_ldlt_req::<I, E>(
-                        &symbolic,
-                        Parallelism::None,
-                    )
-                    .unwrap(),
+                    simplicial::factorize_simplicial_numeric_ldlt_req::<I, E>(n).unwrap(),
                 )),
             );
-            let k = 2;
+            let mut A = sparse_to_dense(A.into_inner());
+            for j in 0..n {
+                for i in j + 1..n {
+                    A.write(i, j, A.read(j, i).faer_conj());
+                }
+            }
 
-            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
-                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-            });
-            for conj in [Conj::Yes, Conj::No] {
-                let mut x = rhs.clone();
-                let ldlt = SupernodalLdltRef::new(&symbolic, values.col_as_slice(0));
-                ldlt.solve_in_place_with_conj(
-                    conj,
-                    x.as_mut(),
+            let err =
+                reconstruct_from_simplicial_ldlt::<I, E>(&symbolic, values.col_as_slice(0)) - &A;
+
+            let mut max = <E as ComplexField>::Real::faer_zero();
+            for j in 0..n {
+                for i in 0..n {
+                    let x = err.read(i, j).faer_abs();
+                    max = if max > x { max } else { x }
+                }
+            }
+            assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+        });
+    }
+
+    fn test_solver_llt<I: Index>() {
+        type E = Complex<Double<f64>>;
+        let truncate = I::truncate;
+
+        for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
+            let mut gen = rand::rngs::StdRng::seed_from_u64(0);
+            let i = E::faer_one().faer_neg().faer_sqrt();
+            let mut complexify = |e: E| {
+                if e == E::faer_from_f64(1.0) {
+                    e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+                } else {
+                    e
+                }
+            };
+
+            let n = col_ptr.len() - 1;
+            let nnz = values.len();
+            let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
+            let values_mat =
+                crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
+            let values = values_mat.col_as_slice(0);
+
+            let A_upper = SparseColMatRef::<'_, I, E>::new(
+                SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+                values,
+            );
+
+            let mut A_lower_col_ptr = col_ptr.to_vec();
+            let mut A_lower_values = values_mat.clone();
+            let mut A_lower_row_ind = row_ind.to_vec();
+            let A_lower = crate::sparse::utils::adjoint(
+                &mut A_lower_col_ptr,
+                &mut A_lower_row_ind,
+                A_lower_values.col_as_slice_mut(0),
+                A_upper,
+                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
+            )
+            .into_const();
+
+            let mut A_dense = sparse_to_dense(A_upper);
+            for j in 0..n {
+                for i in j + 1..n {
+                    A_dense.write(i, j, A_dense.read(j, i).faer_conj());
+                }
+            }
+
+            for (A, side, supernodal_flop_ratio_threshold, parallelism) in [
+                (
+                    A_upper,
+                    Side::Upper,
+                    SupernodalThreshold::FORCE_SIMPLICIAL,
                     Parallelism::None,
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        symbolic.solve_in_place_req::<E>(k).unwrap(),
-                    )),
+                ),
+                (
+                    A_upper,
+                    Side::Upper,
+                    SupernodalThreshold::FORCE_SUPERNODAL,
+                    Parallelism::None,
+                ),
+                (
+                    A_lower,
+                    Side::Lower,
+                    SupernodalThreshold::FORCE_SIMPLICIAL,
+                    Parallelism::None,
+                ),
+                (
+                    A_lower,
+                    Side::Lower,
+                    SupernodalThreshold::FORCE_SUPERNODAL,
+                    Parallelism::None,
+                ),
+            ] {
+                let symbolic = factorize_symbolic_cholesky(
+                    A.symbolic(),
+                    side,
+                    CholeskySymbolicParams {
+                        supernodal_flop_ratio_threshold,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut mem = GlobalPodBuffer::new(
+                    symbolic
+                        .factorize_numeric_ldlt_req::<E>(false, parallelism)
+                        .unwrap(),
                 );
+                let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
 
-                let rhs_reconstructed = if conj == Conj::No {
-                    &A_dense * &x
-                } else {
-                    A_dense.conjugate() * &x
+                symbolic
+                    .factorize_numeric_llt::<E>(
+                        L_values.col_as_slice_mut(0),
+                        A,
+                        side,
+                        Default::default(),
+                        parallelism,
+                        PodStack::new(&mut mem),
+                    )
+                    .unwrap();
+                let L_values = L_values.col_as_slice(0);
+
+                let A_reconstructed = match symbolic.raw() {
+                    SymbolicCholeskyRaw::Simplicial(symbolic) => {
+                        reconstruct_from_simplicial_llt::<I, E>(symbolic, L_values)
+                    }
+                    SymbolicCholeskyRaw::Supernodal(symbolic) => {
+                        reconstruct_from_supernodal_llt::<I, E>(symbolic, L_values)
+                    }
                 };
+
+                let (perm_fwd, _) = symbolic.perm().arrays();
+
                 let mut max = <E as ComplexField>::Real::faer_zero();
-                for j in 0..k {
+                for j in 0..n {
                     for i in 0..n {
-                        let x = rhs_reconstructed
+                        let x = (A_reconstructed
                             .read(i, j)
-                            .faer_sub(rhs.read(i, j))
-                            .faer_abs();
+                            .faer_sub(A_dense.read(perm_fwd[i].zx(), perm_fwd[j].zx())))
+                        .faer_abs();
                         max = if max > x { max } else { x }
                     }
                 }
                 assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+
+                for k in (1..16).chain(128..132) {
+                    let rhs = Mat::<E>::from_fn(n, k, |_, _| {
+                        E::faer_from_f64(gen.gen())
+                            .faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
+                    });
+                    for conj in [Conj::Yes, Conj::No] {
+                        let mut x = rhs.clone();
+                        let llt = LltRef::new(&symbolic, L_values);
+                        llt.solve_in_place_with_conj(
+                            conj,
+                            x.as_mut(),
+                            parallelism,
+                            PodStack::new(&mut GlobalPodBuffer::new(
+                                symbolic.solve_in_place_req::<E>(k).unwrap(),
+                            )),
+                        );
+
+                        let rhs_reconstructed = if conj == Conj::No {
+                            &A_dense * &x
+                        } else {
+                            A_dense.conjugate() * &x
+                        };
+                        let mut max = <E as ComplexField>::Real::faer_zero();
+                        for j in 0..k {
+                            for i in 0..n {
+                                let x = rhs_reconstructed
+                                    .read(i, j)
+                                    .faer_sub(rhs.read(i, j))
+                                    .faer_abs();
+                                max = if max > x { max } else { x }
+                            }
+                        }
+                        assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+                    }
+                }
             }
-        });
+        }
     }
 
-    fn test_supernodal_intranode_bk_1<I: Index>() {
-        type E = Complex<f64>;
+    fn test_solver_orderings<I: Index>() {
+        type E = f64;
         let truncate = I::truncate;
 
-        let (_, col_ptr, row_ind, values) = MEDIUM;
-
-        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-        let i = E::faer_one().faer_neg().faer_sqrt();
-
-        let n = col_ptr.len() - 1;
-        let nnz = values.len();
-        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
-        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
-
-        let mut complexify = |e: E| {
-            let i = E::faer_one().faer_neg().faer_sqrt();
-            if e == E::faer_from_f64(1.0) {
-                e.faer_add(i.faer_mul(E::faer_from_f64(1000.0 * gen.gen::<f64>())))
-            } else {
-                e.faer_add(E::faer_from_f64(1000.0 * gen.gen::<f64>()))
-            }
-        };
-        let values_mat =
-            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
-        let values = values_mat.col_as_slice(0);
+        for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
+            let mut gen = rand::rngs::StdRng::seed_from_u64(0);
 
-        let A = SparseColMatRef::<'_, I, E>::new(
-            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
-            values,
-        );
-        let mut A_dense = sparse_to_dense(A);
-        for j in 0..n {
-            for i in j + 1..n {
-                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
-            }
-        }
+            let n = col_ptr.len() - 1;
+            let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
 
-        let zero = truncate(0);
-        let mut etree = vec![zero.to_signed(); n];
-        let mut col_count = vec![zero; n];
-        ghost::with_size(n, |N| {
-            let A = ghost::SparseColMatRef::new(A, N, N);
-            let etree = ghost_prefactorize_symbolic_cholesky(
-                Array::from_mut(&mut etree, N),
-                Array::from_mut(&mut col_count, N),
-                *A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
+            let A_upper = SparseColMatRef::<'_, I, E>::new(
+                SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+                values,
             );
 
-            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
-                *A,
-                None,
-                None,
-                CholeskyInput::A,
-                etree,
-                Array::from_ref(&col_count, N),
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-                Default::default(),
-            )
-            .unwrap();
-
-            let mut A_lower_col_ptr = col_ptr.to_vec();
-            let mut A_lower_values = values_mat.clone();
-            let mut A_lower_row_ind = row_ind.to_vec();
-            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
-            let A_lower = crate::sparse::utils::ghost_adjoint(
-                &mut A_lower_col_ptr,
-                &mut A_lower_row_ind,
-                A_lower_values,
-                A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-            );
-            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
+            let mut A_dense = sparse_to_dense(A_upper);
+            for j in 0..n {
+                for i in j + 1..n {
+                    A_dense.write(i, j, A_dense.read(j, i));
+                }
+            }
 
-            let mut fwd = vec![zero; n];
-            let mut inv = vec![zero; n];
-            let mut subdiag = Mat::<E>::zeros(n, 1);
+            let nested_dissection =
+                SymmetricOrdering::NestedDissection(nested_dissection::Control {
+                    leaf_size: 8,
+                    ..Default::default()
+                });
+            for (ordering, supernodal_flop_ratio_threshold) in [
+                (
+                    SymmetricOrdering::ReverseCuthillMcKee,
+                    SupernodalThreshold::FORCE_SIMPLICIAL,
+                ),
+                (
+                    SymmetricOrdering::ReverseCuthillMcKee,
+                    SupernodalThreshold::FORCE_SUPERNODAL,
+                ),
+                (nested_dissection, SupernodalThreshold::FORCE_SIMPLICIAL),
+                (nested_dissection, SupernodalThreshold::FORCE_SUPERNODAL),
+            ] {
+                let symbolic = factorize_symbolic_cholesky(
+                    A_upper.symbolic(),
+                    Side::Upper,
+                    CholeskySymbolicParams {
+                        ordering,
+                        supernodal_flop_ratio_threshold,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
 
-            supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman(
-                values.col_as_slice_mut(0),
-                subdiag.col_as_slice_mut(0),
-                &mut fwd,
-                &mut inv,
-                A_lower.into_inner().into_const(),
-                Default::default(),
-                &symbolic,
-                Parallelism::None,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman_req::<I, E>(
-                        &symbolic,
+                let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                symbolic
+                    .factorize_numeric_llt::<E>(
+                        L_values.col_as_slice_mut(0),
+                        A_upper,
+                        Side::Upper,
+                        Default::default(),
                         Parallelism::None,
+                        PodStack::new(&mut GlobalPodBuffer::new(
+                            symbolic
+                                .factorize_numeric_llt_req::<E>(Parallelism::None)
+                                .unwrap(),
+                        )),
                     )
-                    .unwrap(),
-                )),
-            );
-            let k = 2;
+                    .unwrap();
+                let L_values = L_values.col_as_slice(0);
 
-            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
-                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-            });
-            for conj in [Conj::Yes, Conj::No] {
+                let k = 3;
+                let rhs = Mat::<E>::from_fn(n, k, |_, _| gen.gen());
                 let mut x = rhs.clone();
-                let lblt = SupernodalIntranodeBunchKaufmanRef::new(
-                    &symbolic,
-                    values.col_as_slice(0),
-                    subdiag.col_as_slice(0),
-                    PermRef::new_checked(&fwd, &inv),
-                );
-                crate::perm::permute_rows_in_place(
-                    x.as_mut(),
-                    lblt.perm,
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
-                    )),
-                );
-                lblt.solve_in_place_no_numeric_permute_with_conj(
-                    conj,
-                    x.as_mut(),
-                    Parallelism::None,
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        symbolic.solve_in_place_req::<E>(k).unwrap(),
-                    )),
-                );
-                crate::perm::permute_rows_in_place(
-                    x.as_mut(),
-                    lblt.perm.inverse(),
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
-                    )),
-                );
-
-                let rhs_reconstructed = if conj == Conj::No {
-                    &A_dense * &x
-                } else {
-                    A_dense.conjugate() * &x
-                };
-                let mut max = <E as ComplexField>::Real::faer_zero();
-                for j in 0..k {
-                    for i in 0..n {
-                        let x = rhs_reconstructed
-                            .read(i, j)
-                            .faer_sub(rhs.read(i, j))
-                            .faer_abs();
-                        max = if max > x { max } else { x }
-                    }
-                }
-                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-10));
+                LltRef::new(&symbolic, L_values).solve_in_place_with_conj(
+                    Conj::No,
+                    x.as_mut(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic.solve_in_place_req::<E>(k).unwrap(),
+                    )),
+                );
+
+                assert!((&A_dense * &x - &rhs).norm_max() < 1e-10);
             }
-        });
+        }
     }
 
-    fn test_supernodal_intranode_bk_2<I: Index>() {
-        type E = Complex<f64>;
+    fn test_update_downdate<I: Index>() {
+        type E = f64;
         let truncate = I::truncate;
 
-        let (_, col_ptr, row_ind, values) = MEDIUM_P;
+        for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
+            let mut gen = rand::rngs::StdRng::seed_from_u64(0);
 
-        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-        let i = E::faer_one().faer_neg().faer_sqrt();
+            let n = col_ptr.len() - 1;
+            let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
 
-        let n = col_ptr.len() - 1;
-        let nnz = values.len();
-        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
-        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
-        let values_mat = crate::Mat::<E>::from_fn(nnz, 1, |i, _| values[i]);
-        let values = values_mat.col_as_slice(0);
+            let A_upper = SparseColMatRef::<'_, I, E>::new(
+                SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+                values,
+            );
 
-        let A = SparseColMatRef::<'_, I, E>::new(
-            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
-            values,
-        );
-        let mut A_dense = sparse_to_dense(A);
-        for j in 0..n {
-            for i in j + 1..n {
-                A_dense.write(i, j, A_dense.read(j, i).faer_conj());
+            let mut A_dense = sparse_to_dense(A_upper);
+            for j in 0..n {
+                for i in j + 1..n {
+                    A_dense.write(i, j, A_dense.read(j, i));
+                }
             }
-        }
 
-        let zero = truncate(0);
-        let mut etree = vec![zero.to_signed(); n];
-        let mut col_count = vec![zero; n];
-        ghost::with_size(n, |N| {
-            let A = ghost::SparseColMatRef::new(A, N, N);
-            let etree = ghost_prefactorize_symbolic_cholesky(
-                Array::from_mut(&mut etree, N),
-                Array::from_mut(&mut col_count, N),
-                *A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
-            );
+            // each column of `W` is supported on an off-diagonal edge of `A`, which is always
+            // contained in the structure of the factor
+            let mut triplets = Vec::new();
+            for j in 0..n {
+                for i in A_upper.row_indices_of_col(j) {
+                    if i < j && triplets.len() < 8 {
+                        let col = truncate(triplets.len() / 2);
+                        triplets.push((truncate(i), col, gen.gen::<f64>()));
+                        triplets.push((truncate(j), col, gen.gen::<f64>()));
+                    }
+                }
+            }
+            let W = SparseColMat::<I, E>::try_new_from_triplets(n, 4, &triplets).unwrap();
+            let W_dense = sparse_to_dense(W.as_ref());
+            let A_updated = &A_dense + &W_dense * W_dense.transpose();
+
+            let k = n / 2;
+            let mut A_deleted = A_dense.clone();
+            for i in 0..n {
+                A_deleted.write(i, k, 0.0);
+                A_deleted.write(k, i, 0.0);
+            }
+            A_deleted.write(k, k, 1.0);
 
-            let symbolic = supernodal::ghost_factorize_supernodal_symbolic(
-                *A,
-                None,
-                None,
-                CholeskyInput::A,
-                etree,
-                Array::from_ref(&col_count, N),
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-                Default::default(),
+            let col_k = SparseColMat::<I, E>::try_new_from_triplets(
+                n,
+                1,
+                &(0..n)
+                    .filter(|&i| A_dense.read(i, k) != 0.0)
+                    .map(|i| (truncate(i), truncate(0), A_dense.read(i, k)))
+                    .collect::<Vec<_>>(),
             )
             .unwrap();
 
-            let mut A_lower_col_ptr = col_ptr.to_vec();
-            let mut A_lower_values = values_mat.clone();
-            let mut A_lower_row_ind = row_ind.to_vec();
-            let A_lower_values = SliceGroupMut::new(A_lower_values.col_as_slice_mut(0));
-            let A_lower = crate::sparse::utils::ghost_adjoint(
-                &mut A_lower_col_ptr,
-                &mut A_lower_row_ind,
-                A_lower_values,
-                A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-            );
-            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
+            for supernodal_flop_ratio_threshold in [
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+                SupernodalThreshold::FORCE_SUPERNODAL,
+            ] {
+                let symbolic = factorize_symbolic_cholesky(
+                    A_upper.symbolic(),
+                    Side::Upper,
+                    CholeskySymbolicParams {
+                        supernodal_flop_ratio_threshold,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut mem = GlobalPodBuffer::new(
+                    StackReq::try_any_of([
+                        symbolic
+                            .factorize_numeric_ldlt_req::<E>(false, Parallelism::None)
+                            .unwrap(),
+                        symbolic.update_req::<E>().unwrap(),
+                        symbolic.solve_in_place_req::<E>(1).unwrap(),
+                    ])
+                    .unwrap(),
+                );
 
-            let mut fwd = vec![zero; n];
-            let mut inv = vec![zero; n];
-            let mut subdiag = Mat::<E>::zeros(n, 1);
+                for llt in [true, false] {
+                    let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                    if llt {
+                        symbolic
+                            .factorize_numeric_llt::<E>(
+                                L_values.col_as_slice_mut(0),
+                                A_upper,
+                                Side::Upper,
+                                Default::default(),
+                                Parallelism::None,
+                                PodStack::new(&mut mem),
+                            )
+                            .unwrap();
+                    } else {
+                        symbolic.factorize_numeric_ldlt::<E>(
+                            L_values.col_as_slice_mut(0),
+                            A_upper,
+                            Side::Upper,
+                            Default::default(),
+                            Parallelism::None,
+                            PodStack::new(&mut mem),
+                        );
+                    }
 
-            supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman(
-                values.col_as_slice_mut(0),
-                subdiag.col_as_slice_mut(0),
-                &mut fwd,
-                &mut inv,
-                A_lower.into_inner().into_const(),
-                Default::default(),
-                &symbolic,
-                Parallelism::None,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    supernodal::factorize_supernodal_numeric_intranode_bunch_kaufman_req::<I, E>(
-                        &symbolic,
-                        Parallelism::None,
-                    )
-                    .unwrap(),
-                )),
-            );
-            let k = 2;
+                    let rhs = Mat::<E>::from_fn(n, 1, |_, _| gen.gen());
+                    let check = |L_values: &Mat<E>, target: &Mat<E>, mem: &mut GlobalPodBuffer| {
+                        let mut x = rhs.clone();
+                        let L_values = L_values.col_as_slice(0);
+                        let stack = PodStack::new(mem);
+                        if llt {
+                            LltRef::new(&symbolic, L_values).solve_in_place_with_conj(
+                                Conj::No,
+                                x.as_mut(),
+                                Parallelism::None,
+                                stack,
+                            );
+                        } else {
+                            LdltRef::new(&symbolic, L_values).solve_in_place_with_conj(
+                                Conj::No,
+                                x.as_mut(),
+                                Parallelism::None,
+                                stack,
+                            );
+                        }
+                        assert!((target * &x - &rhs).norm_max() < 1e-10);
+                    };
+                    check(&L_values, &A_dense, &mut mem);
+
+                    for (kind, target) in [
+                        (UpdateKind::Update, &A_updated),
+                        (UpdateKind::Downdate, &A_dense),
+                    ] {
+                        let L = L_values.col_as_slice_mut(0);
+                        let stack = PodStack::new(&mut mem);
+                        if llt {
+                            symbolic.update_llt(L, W.as_ref(), kind, stack).unwrap();
+                        } else {
+                            symbolic.update_ldlt(L, W.as_ref(), kind, stack).unwrap();
+                        }
+                        check(&L_values, target, &mut mem);
+                    }
 
-            let rhs = Mat::<E>::from_fn(n, k, |_, _| {
-                E::faer_from_f64(gen.gen()).faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-            });
-            for conj in [Conj::Yes, Conj::No] {
-                let mut x = rhs.clone();
-                let lblt = SupernodalIntranodeBunchKaufmanRef::new(
-                    &symbolic,
-                    values.col_as_slice(0),
-                    subdiag.col_as_slice(0),
-                    PermRef::new_checked(&fwd, &inv),
-                );
-                crate::perm::permute_rows_in_place(
-                    x.as_mut(),
-                    lblt.perm,
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
-                    )),
-                );
-                lblt.solve_in_place_no_numeric_permute_with_conj(
-                    conj,
-                    x.as_mut(),
-                    Parallelism::None,
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        symbolic.solve_in_place_req::<E>(k).unwrap(),
-                    )),
-                );
-                crate::perm::permute_rows_in_place(
-                    x.as_mut(),
-                    lblt.perm.inverse(),
-                    PodStack::new(&mut GlobalPodBuffer::new(
-                        crate::perm::permute_rows_in_place_req::<I, E>(n, k).unwrap(),
-                    )),
-                );
+                    {
+                        let L = L_values.col_as_slice_mut(0);
+                        let stack = PodStack::new(&mut mem);
+                        if llt {
+                            symbolic.delete_row_llt(L, k, stack).unwrap();
+                        } else {
+                            symbolic.delete_row_ldlt(L, k, stack).unwrap();
+                        }
+                        check(&L_values, &A_deleted, &mut mem);
+                    }
 
-                let rhs_reconstructed = if conj == Conj::No {
-                    &A_dense * &x
-                } else {
-                    A_dense.conjugate() * &x
-                };
-                let mut max = <E as ComplexField>::Real::faer_zero();
-                for j in 0..k {
-                    for i in 0..n {
-                        let x = rhs_reconstructed
-                            .read(i, j)
-                            .faer_sub(rhs.read(i, j))
-                            .faer_abs();
-                        max = if max > x { max } else { x }
+                    {
+                        let L = L_values.col_as_slice_mut(0);
+                        let stack = PodStack::new(&mut mem);
+                        if llt {
+                            symbolic.add_row_llt(L, k, col_k.as_ref(), stack).unwrap();
+                        } else {
+                            symbolic.add_row_ldlt(L, k, col_k.as_ref(), stack).unwrap();
+                        }
+                        check(&L_values, &A_dense, &mut mem);
                     }
                 }
-                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-10));
             }
-        });
+        }
     }
 
-    fn test_simplicial<I: Index>() {
-        type E = Complex<Double<f64>>;
+    fn test_export_factors<I: Index>() {
+        type E = f64;
         let truncate = I::truncate;
 
-        let (_, col_ptr, row_ind, values) = SMALL;
+        for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
+            let n = col_ptr.len() - 1;
+            let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
 
-        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-        let mut complexify = |e: E| {
-            let i = E::faer_one().faer_neg().faer_sqrt();
-            if e == E::faer_from_f64(1.0) {
-                e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-            } else {
-                e
+            let A_upper = SparseColMatRef::<'_, I, E>::new(
+                SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+                values,
+            );
+
+            let mut A_dense = sparse_to_dense(A_upper);
+            for j in 0..n {
+                for i in j + 1..n {
+                    A_dense.write(i, j, A_dense.read(j, i));
+                }
             }
-        };
 
-        let n = col_ptr.len() - 1;
-        let nnz = values.len();
-        let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
-        let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
-        let values_mat =
-            crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
-        let values = values_mat.col_as_slice(0);
+            for supernodal_flop_ratio_threshold in [
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+                SupernodalThreshold::FORCE_SUPERNODAL,
+            ] {
+                let symbolic = factorize_symbolic_cholesky(
+                    A_upper.symbolic(),
+                    Side::Upper,
+                    CholeskySymbolicParams {
+                        supernodal_flop_ratio_threshold,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut mem = GlobalPodBuffer::new(
+                    symbolic
+                        .factorize_numeric_ldlt_req::<E>(false, Parallelism::None)
+                        .unwrap(),
+                );
 
-        let A = SparseColMatRef::<'_, I, E>::new(
-            SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
-            values,
-        );
-        let zero = truncate(0);
-        let mut etree = vec![zero.to_signed(); n];
-        let mut col_count = vec![zero; n];
-        ghost::with_size(n, |N| {
-            let A = ghost::SparseColMatRef::new(A, N, N);
-            let etree = ghost_prefactorize_symbolic_cholesky(
-                Array::from_mut(&mut etree, N),
-                Array::from_mut(&mut col_count, N),
-                *A,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(5 * n))),
-            );
+                let (fwd, _) = symbolic.perm().arrays();
+                let PAP = Mat::<E>::from_fn(n, n, |i, j| A_dense.read(fwd[i].zx(), fwd[j].zx()));
+
+                let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let llt = symbolic
+                    .factorize_numeric_llt::<E>(
+                        L_values.col_as_slice_mut(0),
+                        A_upper,
+                        Side::Upper,
+                        Default::default(),
+                        Parallelism::None,
+                        PodStack::new(&mut mem),
+                    )
+                    .unwrap();
+                let L = sparse_to_dense(llt.try_l_factor().unwrap().as_ref());
+                assert!((&L * L.transpose() - &PAP).norm_max() < 1e-10);
+
+                let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let ldlt = symbolic.factorize_numeric_ldlt::<E>(
+                    L_values.col_as_slice_mut(0),
+                    A_upper,
+                    Side::Upper,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut mem),
+                );
+                let L = sparse_to_dense(ldlt.try_l_factor().unwrap().as_ref());
+                let D = ldlt.d_factor();
+                let LD = Mat::<E>::from_fn(n, n, |i, j| L.read(i, j) * D.read(j));
+                for j in 0..n {
+                    assert!(L.read(j, j) == 1.0);
+                }
+                assert!((&LD * L.transpose() - &PAP).norm_max() < 1e-10);
+            }
+        }
+    }
 
-            let symbolic = simplicial::ghost_factorize_simplicial_symbolic_cholesky(
-                *A,
-                etree,
-                Array::from_ref(&col_count, N),
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-            )
-            .unwrap();
+    fn test_selected_inversion<I: Index>() {
+        use crate::linalg::solvers::SolverCore;
+        type E = f64;
+        let truncate = I::truncate;
 
-            let mut values = crate::Mat::<E>::zeros(symbolic.len_values(), 1);
+        for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
+            let n = col_ptr.len() - 1;
+            let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
+            let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
 
-            simplicial::factorize_simplicial_numeric_ldlt::<I, E>(
-                values.col_as_slice_mut(0),
-                A.into_inner(),
-                Default::default(),
-                &symbolic,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    simplicial::factorize_simplicial_numeric_ldlt_req::<I, E>(n).unwrap(),
-                )),
+            let A_upper = SparseColMatRef::<'_, I, E>::new(
+                SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
+                values,
             );
-            let mut A = sparse_to_dense(A.into_inner());
+
+            let mut A_dense = sparse_to_dense(A_upper);
             for j in 0..n {
                 for i in j + 1..n {
-                    A.write(i, j, A.read(j, i).faer_conj());
+                    A_dense.write(i, j, A_dense.read(j, i));
                 }
             }
+            let A_inv = A_dense.partial_piv_lu().inverse();
+            let tol = 1e-10 * A_inv.norm_max();
 
-            let err =
-                reconstruct_from_simplicial_ldlt::<I, E>(&symbolic, values.col_as_slice(0)) - &A;
+            for supernodal_flop_ratio_threshold in [
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+                SupernodalThreshold::FORCE_SUPERNODAL,
+            ] {
+                let symbolic = factorize_symbolic_cholesky(
+                    A_upper.symbolic(),
+                    Side::Upper,
+                    CholeskySymbolicParams {
+                        supernodal_flop_ratio_threshold,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut mem = GlobalPodBuffer::new(
+                    symbolic
+                        .factorize_numeric_ldlt_req::<E>(false, Parallelism::None)
+                        .unwrap(),
+                );
 
-            let mut max = <E as ComplexField>::Real::faer_zero();
-            for j in 0..n {
-                for i in 0..n {
-                    let x = err.read(i, j).faer_abs();
-                    max = if max > x { max } else { x }
+                let mut llt_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let llt = symbolic
+                    .factorize_numeric_llt::<E>(
+                        llt_values.col_as_slice_mut(0),
+                        A_upper,
+                        Side::Upper,
+                        Default::default(),
+                        Parallelism::None,
+                        PodStack::new(&mut mem),
+                    )
+                    .unwrap();
+                let mut ldlt_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let ldlt = symbolic.factorize_numeric_ldlt::<E>(
+                    ldlt_values.col_as_slice_mut(0),
+                    A_upper,
+                    Side::Upper,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut mem),
+                );
+
+                for (Z, diag) in [
+                    (
+                        llt.try_selected_inverse().unwrap(),
+                        llt.try_inverse_diag().unwrap(),
+                    ),
+                    (
+                        ldlt.try_selected_inverse().unwrap(),
+                        ldlt.try_inverse_diag().unwrap(),
+                    ),
+                ] {
+                    for i in 0..n {
+                        assert!((diag.read(i) - A_inv.read(i, i)).abs() < tol);
+                    }
+
+                    let Z = Z.as_ref();
+                    for j in 0..n {
+                        let rows = Z.row_indices_of_col_raw(j);
+                        // the diagonal and the pattern of `A` must be included
+                        assert!(rows.binary_search(&truncate(j)).is_ok());
+                        for i in A_upper.row_indices_of_col(j) {
+                            assert!(rows.binary_search(&truncate(i)).is_ok());
+                        }
+                        for (i, z) in zip(rows, Z.values_of_col(j)) {
+                            assert!((z - A_inv.read(i.zx(), j)).abs() < tol);
+                        }
+                    }
                 }
             }
-            assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
-        });
+        }
     }
 
-    fn test_solver_llt<I: Index>() {
-        type E = Complex<Double<f64>>;
+    #[cfg(feature = "std")]
+    fn test_log_abs_det<I: Index>() {
+        type E = f64;
         let truncate = I::truncate;
 
         for (_, col_ptr, row_ind, values) in [SMALL, MEDIUM] {
-            let mut gen = rand::rngs::StdRng::seed_from_u64(0);
-            let i = E::faer_one().faer_neg().faer_sqrt();
-            let mut complexify = |e: E| {
-                if e == E::faer_from_f64(1.0) {
-                    e.faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-                } else {
-                    e
-                }
-            };
-
             let n = col_ptr.len() - 1;
-            let nnz = values.len();
             let col_ptr = &*col_ptr.iter().copied().map(truncate).collect::<Vec<_>>();
             let row_ind = &*row_ind.iter().copied().map(truncate).collect::<Vec<_>>();
-            let values_mat =
-                crate::Mat::<E>::from_fn(nnz, 1, |i, _| complexify(E::faer_from_f64(values[i])));
-            let values = values_mat.col_as_slice(0);
+            let neg_values = &*values.iter().map(|x| -x).collect::<Vec<_>>();
 
             let A_upper = SparseColMatRef::<'_, I, E>::new(
                 SymbolicSparseColMatRef::new_unsorted_checked(n, n, col_ptr, None, row_ind),
                 values,
             );
-
-            let mut A_lower_col_ptr = col_ptr.to_vec();
-            let mut A_lower_values = values_mat.clone();
-            let mut A_lower_row_ind = row_ind.to_vec();
-            let A_lower = crate::sparse::utils::adjoint(
-                &mut A_lower_col_ptr,
-                &mut A_lower_row_ind,
-                A_lower_values.col_as_slice_mut(0),
-                A_upper,
-                PodStack::new(&mut GlobalPodBuffer::new(StackReq::new::<I>(20 * n))),
-            )
-            .into_const();
+            let A_neg_upper = SparseColMatRef::<'_, I, E>::new(A_upper.symbolic(), neg_values);
 
             let mut A_dense = sparse_to_dense(A_upper);
             for j in 0..n {
                 for i in j + 1..n {
-                    A_dense.write(i, j, A_dense.read(j, i).faer_conj());
+                    A_dense.write(i, j, A_dense.read(j, i));
                 }
             }
+            let log_abs_det = A_dense.determinant().abs().ln();
+            let tol = 1e-10 * log_abs_det.abs().max(1.0);
 
-            for (A, side, supernodal_flop_ratio_threshold, parallelism) in [
-                (
-                    A_upper,
-                    Side::Upper,
-                    SupernodalThreshold::FORCE_SIMPLICIAL,
-                    Parallelism::None,
-                ),
-                (
-                    A_upper,
-                    Side::Upper,
-                    SupernodalThreshold::FORCE_SUPERNODAL,
-                    Parallelism::None,
-                ),
-                (
-                    A_lower,
-                    Side::Lower,
-                    SupernodalThreshold::FORCE_SIMPLICIAL,
-                    Parallelism::None,
-                ),
-                (
-                    A_lower,
-                    Side::Lower,
-                    SupernodalThreshold::FORCE_SUPERNODAL,
-                    Parallelism::None,
-                ),
+            for supernodal_flop_ratio_threshold in [
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+                SupernodalThreshold::FORCE_SUPERNODAL,
             ] {
                 let symbolic = factorize_symbolic_cholesky(
-                    A.symbolic(),
-                    side,
+                    A_upper.symbolic(),
+                    Side::Upper,
                     CholeskySymbolicParams {
                         supernodal_flop_ratio_threshold,
                         ..Default::default()
@@ -6096,81 +7779,46 @@ pub(crate) mod tests {
                 .unwrap();
                 let mut mem = GlobalPodBuffer::new(
                     symbolic
-                        .factorize_numeric_ldlt_req::<E>(false, parallelism)
+                        .factorize_numeric_ldlt_req::<E>(false, Parallelism::None)
                         .unwrap(),
                 );
-                let mut L_values = Mat::<E>::zeros(symbolic.len_values(), 1);
 
-                symbolic
+                let mut llt_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let llt = symbolic
                     .factorize_numeric_llt::<E>(
-                        L_values.col_as_slice_mut(0),
-                        A,
-                        side,
+                        llt_values.col_as_slice_mut(0),
+                        A_upper,
+                        Side::Upper,
                         Default::default(),
-                        parallelism,
+                        Parallelism::None,
                         PodStack::new(&mut mem),
                     )
                     .unwrap();
-                let L_values = L_values.col_as_slice(0);
-
-                let A_reconstructed = match symbolic.raw() {
-                    SymbolicCholeskyRaw::Simplicial(symbolic) => {
-                        reconstruct_from_simplicial_llt::<I, E>(symbolic, L_values)
-                    }
-                    SymbolicCholeskyRaw::Supernodal(symbolic) => {
-                        reconstruct_from_supernodal_llt::<I, E>(symbolic, L_values)
-                    }
-                };
-
-                let (perm_fwd, _) = symbolic.perm().arrays();
-
-                let mut max = <E as ComplexField>::Real::faer_zero();
-                for j in 0..n {
-                    for i in 0..n {
-                        let x = (A_reconstructed
-                            .read(i, j)
-                            .faer_sub(A_dense.read(perm_fwd[i].zx(), perm_fwd[j].zx())))
-                        .faer_abs();
-                        max = if max > x { max } else { x }
-                    }
-                }
-                assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
+                assert!((llt.log_abs_det() - log_abs_det).abs() < tol);
 
-                for k in (1..16).chain(128..132) {
-                    let rhs = Mat::<E>::from_fn(n, k, |_, _| {
-                        E::faer_from_f64(gen.gen())
-                            .faer_add(i.faer_mul(E::faer_from_f64(gen.gen())))
-                    });
-                    for conj in [Conj::Yes, Conj::No] {
-                        let mut x = rhs.clone();
-                        let llt = LltRef::new(&symbolic, L_values);
-                        llt.solve_in_place_with_conj(
-                            conj,
-                            x.as_mut(),
-                            parallelism,
-                            PodStack::new(&mut GlobalPodBuffer::new(
-                                symbolic.solve_in_place_req::<E>(k).unwrap(),
-                            )),
-                        );
+                let mut ldlt_values = Mat::<E>::zeros(symbolic.len_values(), 1);
+                let ldlt = symbolic.factorize_numeric_ldlt::<E>(
+                    ldlt_values.col_as_slice_mut(0),
+                    A_upper,
+                    Side::Upper,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut mem),
+                );
+                assert!((ldlt.log_abs_det() - log_abs_det).abs() < tol);
+                assert!(ldlt.det_sign() == 1.0);
 
-                        let rhs_reconstructed = if conj == Conj::No {
-                            &A_dense * &x
-                        } else {
-                            A_dense.conjugate() * &x
-                        };
-                        let mut max = <E as ComplexField>::Real::faer_zero();
-                        for j in 0..k {
-                            for i in 0..n {
-                                let x = rhs_reconstructed
-                                    .read(i, j)
-                                    .faer_sub(rhs.read(i, j))
-                                    .faer_abs();
-                                max = if max > x { max } else { x }
-                            }
-                        }
-                        assert!(max < <E as ComplexField>::Real::faer_from_f64(1e-25));
-                    }
-                }
+                // the determinant of `-A` has the sign of `(-1)^n`
+                let ldlt = symbolic.factorize_numeric_ldlt::<E>(
+                    ldlt_values.col_as_slice_mut(0),
+                    A_neg_upper,
+                    Side::Upper,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut mem),
+                );
+                assert!((ldlt.log_abs_det() - log_abs_det).abs() < tol);
+                assert!(ldlt.det_sign() == if n % 2 == 0 { 1.0 } else { -1.0 });
             }
         }
     }
@@ -6478,6 +8126,97 @@ pub(crate) mod tests {
         }
     }
 
+    fn test_inertia<I: Index>() {
+        let truncate = I::truncate;
+        let n = 60;
+        let mut gen = rand::rngs::StdRng::seed_from_u64(0);
+
+        // strictly diagonally dominant, so that the signs of the pivots match the signs of the
+        // diagonal for any elimination order
+        let mut triplets = Vec::new();
+        let mut negative = 0;
+        for j in 0..n {
+            let d = 2.0 + gen.gen::<f64>();
+            if j % 3 == 0 {
+                negative += 1;
+                triplets.push((truncate(j), truncate(j), -d));
+            } else {
+                triplets.push((truncate(j), truncate(j), d));
+            }
+            if j + 1 < n {
+                triplets.push((truncate(j + 1), truncate(j), 0.3));
+            }
+            if j + 7 < n {
+                triplets.push((truncate(j + 7), truncate(j), -0.2));
+            }
+        }
+        let A =
+            crate::sparse::SparseColMat::<I, f64>::try_new_from_triplets(n, n, &triplets).unwrap();
+        let A = A.as_ref();
+
+        for supernodal_flop_ratio_threshold in [
+            SupernodalThreshold::FORCE_SIMPLICIAL,
+            SupernodalThreshold::FORCE_SUPERNODAL,
+        ] {
+            let symbolic = factorize_symbolic_cholesky(
+                A.symbolic(),
+                Side::Lower,
+                CholeskySymbolicParams {
+                    supernodal_flop_ratio_threshold,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut L_values = Mat::<f64>::zeros(symbolic.len_values(), 1);
+            let inertia = symbolic
+                .factorize_numeric_ldlt::<f64>(
+                    L_values.col_as_slice_mut(0),
+                    A,
+                    Side::Lower,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic
+                            .factorize_numeric_ldlt_req::<f64>(false, Parallelism::None)
+                            .unwrap(),
+                    )),
+                )
+                .inertia();
+            assert!(inertia.negative == negative);
+            assert!(inertia.positive == n - negative);
+            assert!(inertia.zero == 0);
+
+            let mut L_values = Mat::<f64>::zeros(symbolic.len_values(), 1);
+            let mut subdiag = Mat::<f64>::zeros(n, 1);
+            let mut fwd = vec![I::truncate(0); n];
+            let mut inv = vec![I::truncate(0); n];
+            let inertia = symbolic
+                .factorize_numeric_intranode_bunch_kaufman::<f64>(
+                    L_values.col_as_slice_mut(0),
+                    subdiag.col_as_slice_mut(0),
+                    &mut fwd,
+                    &mut inv,
+                    A,
+                    Side::Lower,
+                    Default::default(),
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic
+                            .factorize_numeric_intranode_bunch_kaufman_req::<f64>(
+                                false,
+                                Parallelism::None,
+                            )
+                            .unwrap(),
+                    )),
+                )
+                .inertia();
+            assert!(inertia.negative == negative);
+            assert!(inertia.positive == n - negative);
+            assert!(inertia.zero == 0);
+        }
+    }
+
     fn test_solver_regularization<I: Index>() {
         type E = f64;
         let I = I::truncate;
@@ -6612,6 +8351,13 @@ pub(crate) mod tests {
     monomorphize_test!(test_simplicial, u32);
     monomorphize_test!(test_solver_llt, u32);
     monomorphize_test!(test_solver_ldlt, u32);
+    monomorphize_test!(test_solver_orderings, u32);
+    monomorphize_test!(test_update_downdate, u32);
+    monomorphize_test!(test_export_factors, u32);
+    monomorphize_test!(test_selected_inversion, u32);
+    #[cfg(feature = "std")]
+    monomorphize_test!(test_log_abs_det, u32);
     monomorphize_test!(test_solver_intranode_bk, u32);
     monomorphize_test!(test_solver_regularization, u32);
+    monomorphize_test!(test_inertia, u32);
 }
@@ -15,14 +15,16 @@ use super::{
     mem::{
         NONE, {self},
     },
-    nomem, try_zeroed, FaerError, Index, LuError, SupernodalThreshold, SymbolicSparseColMatRef,
-    SymbolicSupernodalParams,
+    nested_dissection, nomem, try_zeroed, FaerError, Index, LuError, SupernodalThreshold,
+    SymbolicSparseColMatRef, SymbolicSupernodalParams,
 };
 use crate::{
     assert,
     linalg::{matmul, temp_mat_req, temp_mat_uninit, triangular_solve as solve},
     perm::PermRef,
-    sparse::SparseColMatRef,
+    sparse::{
+        SparseColMat, SparseColMatRef, SparseRowMat, SymbolicSparseColMat, SymbolicSparseRowMat,
+    },
     utils::{constrained::Size, slice::*, vec::*},
     Conj, MatMut, Parallelism, SignedIndex,
 };
@@ -182,6 +184,168 @@ pub mod supernodal {
             self.nsupernodes
         }
 
+        /// Returns a copy of the $L$ factor of the LU factorization, with sorted row indices and
+        /// an explicit unit diagonal.
+        pub fn try_l_factor(&self) -> Result<SparseColMat<I, E>, FaerError>
+        where
+            E: ComplexField,
+        {
+            let supernode_ptr = &*self.supernode_ptr;
+
+            let mut nnz = 0usize;
+            for s in 0..self.nsupernodes {
+                let s_size = (supernode_ptr[s + 1] - supernode_ptr[s]).zx();
+                let s_row_index_count =
+                    (self.l_col_ptr_for_row_ind[s + 1] - self.l_col_ptr_for_row_ind[s]).zx();
+                for c in 0..s_size {
+                    nnz += s_row_index_count - c;
+                }
+            }
+            if nnz > I::Signed::MAX.zx() {
+                return Err(FaerError::IndexOverflow);
+            }
+
+            let mut col_ptr = try_zeroed::<I>(self.ncols + 1)?;
+            let mut row_ind = try_zeroed::<I>(nnz)?;
+            let mut values = VecGroup::<E>::new();
+            resize_scalar::<E>(&mut values, nnz, true, false)?;
+            let mut values_mut = values.as_slice_mut();
+
+            let mut pos = 0usize;
+            for s in 0..self.nsupernodes {
+                let s_begin = supernode_ptr[s].zx();
+                let s_end = supernode_ptr[s + 1].zx();
+                let s_size = s_end - s_begin;
+                let s_row_ind = &self.l_row_ind
+                    [self.l_col_ptr_for_row_ind[s].zx()..self.l_col_ptr_for_row_ind[s + 1].zx()];
+                let s_row_index_count = s_row_ind.len();
+                let s_val = self
+                    .l_val
+                    .as_slice()
+                    .subslice(self.l_col_ptr_for_val[s].zx()..self.l_col_ptr_for_val[s + 1].zx());
+
+                for c in 0..s_size {
+                    row_ind[pos] = I::truncate(s_begin + c);
+                    values_mut.write(pos, E::faer_one());
+                    pos += 1;
+                    // the strictly lower part of the diagonal block, followed by the rows below
+                    // the supernode
+                    for k in c + 1..s_row_index_count {
+                        row_ind[pos] = if k < s_size {
+                            I::truncate(s_begin + k)
+                        } else {
+                            s_row_ind[k]
+                        };
+                        values_mut.write(pos, s_val.read(k + c * s_row_index_count));
+                        pos += 1;
+                    }
+                    col_ptr[s_begin + c + 1] = I::truncate(pos);
+                }
+            }
+
+            let mut L = SparseColMat::<I, E>::new(
+                SymbolicSparseColMat::<I>::new_unsorted_checked(
+                    self.nrows, self.ncols, col_ptr, None, row_ind,
+                ),
+                values.into_inner(),
+            );
+            L.sort_indices();
+            Ok(L)
+        }
+
+        /// Returns a copy of the $U$ factor of the LU factorization, with sorted row indices.
+        pub fn try_u_factor(&self) -> Result<SparseColMat<I, E>, FaerError>
+        where
+            E: ComplexField,
+        {
+            let supernode_ptr = &*self.supernode_ptr;
+
+            let mut nnz = 0usize;
+            for s in 0..self.nsupernodes {
+                let s_size = (supernode_ptr[s + 1] - supernode_ptr[s]).zx();
+                let s_col_index_count =
+                    (self.ut_col_ptr_for_row_ind[s + 1] - self.ut_col_ptr_for_row_ind[s]).zx();
+                for r in 0..s_size {
+                    nnz += s_size - r + s_col_index_count;
+                }
+            }
+            if nnz > I::Signed::MAX.zx() {
+                return Err(FaerError::IndexOverflow);
+            }
+
+            // the rows of `U` are stored contiguously, so it's assembled in row-major format
+            let mut row_ptr = try_zeroed::<I>(self.ncols + 1)?;
+            let mut col_ind = try_zeroed::<I>(nnz)?;
+            let mut values = VecGroup::<E>::new();
+            resize_scalar::<E>(&mut values, nnz, true, false)?;
+            let mut values_mut = values.as_slice_mut();
+
+            let mut pos = 0usize;
+            for s in 0..self.nsupernodes {
+                let s_begin = supernode_ptr[s].zx();
+                let s_end = supernode_ptr[s + 1].zx();
+                let s_size = s_end - s_begin;
+                let s_row_index_count =
+                    (self.l_col_ptr_for_row_ind[s + 1] - self.l_col_ptr_for_row_ind[s]).zx();
+                let s_col_ind = &self.ut_row_ind
+                    [self.ut_col_ptr_for_row_ind[s].zx()..self.ut_col_ptr_for_row_ind[s + 1].zx()];
+                let s_col_index_count = s_col_ind.len();
+                let s_l_val = self
+                    .l_val
+                    .as_slice()
+                    .subslice(self.l_col_ptr_for_val[s].zx()..self.l_col_ptr_for_val[s + 1].zx());
+                let s_ut_val = self
+                    .ut_val
+                    .as_slice()
+                    .subslice(self.ut_col_ptr_for_val[s].zx()..self.ut_col_ptr_for_val[s + 1].zx());
+
+                for r in 0..s_size {
+                    // the upper part of the diagonal block, including the diagonal
+                    for c in r..s_size {
+                        col_ind[pos] = I::truncate(s_begin + c);
+                        values_mut.write(pos, s_l_val.read(r + c * s_row_index_count));
+                        pos += 1;
+                    }
+                    // the columns to the right of the supernode
+                    for (k, &j) in s_col_ind.iter().enumerate() {
+                        col_ind[pos] = j;
+                        values_mut.write(pos, s_ut_val.read(k + r * s_col_index_count));
+                        pos += 1;
+                    }
+                    row_ptr[s_begin + r + 1] = I::truncate(pos);
+                }
+            }
+
+            SparseRowMat::<I, E>::new(
+                SymbolicSparseRowMat::<I>::new_unsorted_checked(
+                    self.ncols, self.ncols, row_ptr, None, col_ind,
+                ),
+                values.into_inner(),
+            )
+            .to_col_major()
+        }
+
+        // calls `f` with each diagonal entry of `U`, which is stored in the diagonal block of
+        // the `L` part of its supernode
+        pub(crate) fn for_each_u_diag(&self, mut f: impl FnMut(E))
+        where
+            E: ComplexField,
+        {
+            let supernode_ptr = &*self.supernode_ptr;
+            for s in 0..self.nsupernodes {
+                let s_size = (supernode_ptr[s + 1] - supernode_ptr[s]).zx();
+                let s_row_index_count =
+                    (self.l_col_ptr_for_row_ind[s + 1] - self.l_col_ptr_for_row_ind[s]).zx();
+                let s_l_val = self
+                    .l_val
+                    .as_slice()
+                    .subslice(self.l_col_ptr_for_val[s].zx()..self.l_col_ptr_for_val[s + 1].zx());
+                for c in 0..s_size {
+                    f(s_l_val.read(c + c * s_row_index_count));
+                }
+            }
+        }
+
         /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
         /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
         ///
@@ -1460,6 +1624,34 @@ pub mod simplicial {
             )
         }
 
+        /// Returns a copy of the $L$ factor of the LU factorization, with sorted row indices and
+        /// an explicit unit diagonal.
+        pub fn try_l_factor(&self) -> Result<SparseColMat<I, E>, FaerError>
+        where
+            E: ComplexField,
+        {
+            self.l_factor_unsorted().to_sorted()
+        }
+
+        /// Returns a copy of the $U$ factor of the LU factorization, with sorted row indices.
+        pub fn try_u_factor(&self) -> Result<SparseColMat<I, E>, FaerError>
+        where
+            E: ComplexField,
+        {
+            self.u_factor_unsorted().to_sorted()
+        }
+
+        // calls `f` with each diagonal entry of `U`, which is the last entry of its column
+        pub(crate) fn for_each_u_diag(&self, mut f: impl FnMut(E))
+        where
+            E: ComplexField,
+        {
+            let u_val = self.u_val.as_slice();
+            for j in 0..self.ncols {
+                f(u_val.read(self.u_col_ptr[j + 1].zx() - 1));
+            }
+        }
+
         /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
         /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
         ///
@@ -1834,10 +2026,27 @@ pub mod simplicial {
     }
 }
 
+/// Fill-reducing column ordering computed by the LU symbolic factorization.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+#[non_exhaustive]
+pub enum ColumnOrdering {
+    /// Column approximate minimum degree ordering. See [`colamd`](super::colamd).
+    #[default]
+    Colamd,
+    /// Nested dissection ordering of the pattern of `A + A.T`, which is usually preferable to
+    /// [`ColumnOrdering::Colamd`] for large matrices with a (nearly) symmetric sparsity pattern
+    /// arising from 2D and 3D meshes, computed with the given parameters. See
+    /// [`nested_dissection`].
+    NestedDissection(nested_dissection::Control),
+}
+
 /// Tuning parameters for the LU symbolic factorization.
 #[derive(Copy, Clone, Debug, Default)]
 pub struct LuSymbolicParams<'a> {
-    /// Parameters for the fill reducing column permutation
+    /// Fill-reducing column ordering.
+    pub ordering: ColumnOrdering,
+    /// Parameters for the fill reducing column permutation, when using
+    /// [`ColumnOrdering::Colamd`].
     pub colamd_params: Control,
     /// Threshold for selecting the supernodal factorization.
     pub supernodal_flop_ratio_threshold: SupernodalThreshold,
@@ -1902,6 +2111,26 @@ impl<I: Index, E: Entity> NumericLu<I, E> {
     }
 }
 
+// returns `true` if the permutation with the forward array `fwd` is odd, i.e., if it has an odd
+// number of cycles of even length
+fn perm_is_odd<I: Index>(fwd: &[I]) -> bool {
+    let mut visited = alloc::vec![false; fwd.len()];
+    let mut odd = false;
+    for start in 0..fwd.len() {
+        let mut i = start;
+        let mut len = 0usize;
+        while !visited[i] {
+            visited[i] = true;
+            i = fwd[i].zx();
+            len += 1;
+        }
+        if len > 0 && len % 2 == 0 {
+            odd = !odd;
+        }
+    }
+    odd
+}
+
 /// Sparse LU factorization wrapper.
 #[derive(Debug)]
 pub struct LuRef<'a, I: Index, E: Entity> {
@@ -1944,6 +2173,90 @@ impl<'a, I: Index, E: Entity> LuRef<'a, I, E> {
         self.symbolic.col_perm()
     }
 
+    /// Returns a copy of the $L$ factor of the LU factorization, with sorted row indices and an
+    /// explicit unit diagonal.
+    ///
+    /// The factors satisfy `A[row_fwd[i], col_fwd[j]] == (L * U)[i, j]`, where `row_fwd` and
+    /// `col_fwd` are the forward arrays of [`Self::row_perm`] and [`Self::col_perm`].
+    pub fn try_l_factor(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        match &self.numeric.raw {
+            NumericLuRaw::Simplicial(numeric) => numeric.try_l_factor(),
+            NumericLuRaw::Supernodal(numeric) => numeric.try_l_factor(),
+            NumericLuRaw::None => unreachable!(),
+        }
+    }
+
+    /// Returns a copy of the $U$ factor of the LU factorization, with sorted row indices.
+    ///
+    /// See [`Self::try_l_factor`] for the relation between the factors and the input matrix.
+    pub fn try_u_factor(self) -> Result<SparseColMat<I, E>, FaerError>
+    where
+        E: ComplexField,
+    {
+        match &self.numeric.raw {
+            NumericLuRaw::Simplicial(numeric) => numeric.try_u_factor(),
+            NumericLuRaw::Supernodal(numeric) => numeric.try_u_factor(),
+            NumericLuRaw::None => unreachable!(),
+        }
+    }
+
+    fn for_each_u_diag(self, f: impl FnMut(E))
+    where
+        E: ComplexField,
+    {
+        match &self.numeric.raw {
+            NumericLuRaw::Simplicial(numeric) => numeric.for_each_u_diag(f),
+            NumericLuRaw::Supernodal(numeric) => numeric.for_each_u_diag(f),
+            NumericLuRaw::None => unreachable!(),
+        }
+    }
+
+    /// Returns the logarithm of the absolute value of the determinant of the factorized matrix.
+    ///
+    /// The logarithms of the pivots are summed, which avoids the overflow or underflow of their
+    /// product. The result is $-\infty$ if the matrix is singular.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn log_abs_det(self) -> E::Real
+    where
+        E: ComplexField,
+        E::Real: num_traits::Float,
+    {
+        let mut log = E::Real::faer_zero();
+        self.for_each_u_diag(|d| log = log.faer_add(num_traits::Float::ln(d.faer_abs())));
+        log
+    }
+
+    /// Returns the sign of the determinant of the factorized matrix, i.e., the determinant
+    /// divided by its absolute value, or zero if the matrix is singular.
+    ///
+    /// Together with [`Self::log_abs_det`], this gives the determinant as
+    /// `det_sign * exp(log_abs_det)`, without overflowing.
+    pub fn det_sign(self) -> E
+    where
+        E: ComplexField,
+    {
+        let odd =
+            perm_is_odd(self.row_perm().arrays().0) != perm_is_odd(self.col_perm().arrays().0);
+        let mut sign = if odd {
+            E::faer_one().faer_neg()
+        } else {
+            E::faer_one()
+        };
+        self.for_each_u_diag(|d| {
+            let abs = d.faer_abs();
+            sign = if abs == E::Real::faer_zero() {
+                E::faer_zero()
+            } else {
+                sign.faer_mul(d.faer_scale_real(abs.faer_inv()))
+            };
+        });
+        sign
+    }
+
     /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
     /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
     ///
@@ -2205,8 +2518,15 @@ pub fn factorize_symbolic_lu<I: Index>(
                 StackReq::try_new::<I>(A_nnz)?,
             )?;
 
+            let ordering_req = match params.ordering {
+                ColumnOrdering::Colamd => {
+                    crate::sparse::linalg::colamd::order_req::<I>(m, n, A_nnz)?
+                }
+                ColumnOrdering::NestedDissection(_) => nested_dissection::order_req::<I>(n, A_nnz)?,
+            };
+
             StackReq::try_or(
-                crate::sparse::linalg::colamd::order_req::<I>(m, n, A_nnz)?,
+                ordering_req,
                 StackReq::try_all_of([
                     n_req,
                     n_req,
@@ -2231,13 +2551,22 @@ pub fn factorize_symbolic_lu<I: Index>(
         let mut col_perm_inv = try_zeroed::<I>(n)?;
         let mut min_row = try_zeroed::<I>(m)?;
 
-        crate::sparse::linalg::colamd::order(
-            &mut col_perm_fwd,
-            &mut col_perm_inv,
-            A.into_inner(),
-            params.colamd_params,
-            stack.rb_mut(),
-        )?;
+        match params.ordering {
+            ColumnOrdering::Colamd => crate::sparse::linalg::colamd::order(
+                &mut col_perm_fwd,
+                &mut col_perm_inv,
+                A.into_inner(),
+                params.colamd_params,
+                stack.rb_mut(),
+            )?,
+            ColumnOrdering::NestedDissection(nested_dissection_params) => nested_dissection::order(
+                &mut col_perm_fwd,
+                &mut col_perm_inv,
+                A.into_inner(),
+                nested_dissection_params,
+                stack.rb_mut(),
+            )?,
+        }
 
         let col_perm = ghost::PermRef::new(PermRef::new_checked(&col_perm_fwd, &col_perm_inv), N);
 
@@ -2358,7 +2687,7 @@ mod tests {
                         factorize_supernodal_numeric_lu, factorize_supernodal_numeric_lu_req,
                         SupernodalLu,
                     },
-                    LuSymbolicParams, NumericLu,
+                    ColumnOrdering, LuSymbolicParams, NumericLu,
                 },
                 qr::col_etree,
                 SupernodalThreshold, SymbolicSparseColMatRef,
@@ -2732,14 +3061,29 @@ mod tests {
 
         let rhs = Mat::<E>::from_fn(m, 6, |_, _| gen());
 
-        for supernodal_flop_ratio_threshold in [
-            SupernodalThreshold::AUTO,
-            SupernodalThreshold::FORCE_SUPERNODAL,
-            SupernodalThreshold::FORCE_SIMPLICIAL,
+        for (ordering, supernodal_flop_ratio_threshold) in [
+            (ColumnOrdering::Colamd, SupernodalThreshold::AUTO),
+            (
+                ColumnOrdering::Colamd,
+                SupernodalThreshold::FORCE_SUPERNODAL,
+            ),
+            (
+                ColumnOrdering::Colamd,
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+            ),
+            (
+                ColumnOrdering::NestedDissection(Default::default()),
+                SupernodalThreshold::FORCE_SUPERNODAL,
+            ),
+            (
+                ColumnOrdering::NestedDissection(Default::default()),
+                SupernodalThreshold::FORCE_SIMPLICIAL,
+            ),
         ] {
             let symbolic = factorize_symbolic_lu(
                 A.symbolic(),
                 LuSymbolicParams {
+                    ordering,
                     supernodal_flop_ratio_threshold,
                     ..Default::default()
                 },
@@ -2824,6 +3168,81 @@ mod tests {
                 let linsolve_diff = A.adjoint() * &x - &rhs;
                 assert!(linsolve_diff.norm_max() <= 1e-10);
             }
+
+            {
+                let L = lu.try_l_factor().unwrap();
+                let U = lu.try_u_factor().unwrap();
+                let (row_fwd, _) = lu.row_perm().arrays();
+                let (col_fwd, _) = lu.col_perm().arrays();
+                let A_dense = sparse_to_dense(A);
+                let PAQ =
+                    Mat::<E>::from_fn(m, n, |i, j| A_dense.read(row_fwd[i].zx(), col_fwd[j].zx()));
+
+                for j in 0..n {
+                    assert!(L.row_indices_of_col_raw(j)[0] == j);
+                    assert!(*U.row_indices_of_col_raw(j).last().unwrap() == j);
+                }
+                let diff = &sparse_to_dense(L.as_ref()) * &sparse_to_dense(U.as_ref()) - &PAQ;
+                assert!(diff.norm_max() <= 1e-10 * PAQ.norm_max());
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_log_abs_det() {
+        type E = crate::complex_native::c64;
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let n = 40;
+        let mut col_ptr = vec![0usize];
+        let mut row_ind = vec![];
+        let mut val = vec![];
+        for j in 0..n {
+            for i in 0..n {
+                if i == j || rng.gen::<f64>() < 0.1 {
+                    row_ind.push(i);
+                    val.push(E::new(rng.gen::<f64>(), rng.gen::<f64>()));
+                }
+            }
+            col_ptr.push(row_ind.len());
+        }
+        let A = SparseColMatRef::<'_, usize, E>::new(
+            SymbolicSparseColMatRef::new_checked(n, n, &col_ptr, None, &row_ind),
+            &val,
+        );
+        let det = sparse_to_dense(A).determinant();
+
+        for supernodal_flop_ratio_threshold in [
+            SupernodalThreshold::FORCE_SUPERNODAL,
+            SupernodalThreshold::FORCE_SIMPLICIAL,
+        ] {
+            let symbolic = factorize_symbolic_lu(
+                A.symbolic(),
+                LuSymbolicParams {
+                    supernodal_flop_ratio_threshold,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut numeric = NumericLu::<usize, E>::new();
+            let lu = symbolic
+                .factorize_numeric_lu(
+                    &mut numeric,
+                    A,
+                    Parallelism::None,
+                    PodStack::new(&mut GlobalPodBuffer::new(
+                        symbolic
+                            .factorize_numeric_lu_req::<E>(Parallelism::None)
+                            .unwrap(),
+                    )),
+                )
+                .unwrap();
+
+            let log_abs_det = lu.log_abs_det();
+            let sign = lu.det_sign();
+            assert!((log_abs_det - det.faer_abs().ln()).abs() < 1e-10);
+            assert!((sign - det * det.faer_abs().recip()).faer_abs() < 1e-10);
         }
     }
 }
@@ -1,5 +1,4 @@
 // PERF: optimize matmul
-// - parallelization
 // - simd(?)
 
 use super::*;
@@ -245,13 +244,246 @@ pub fn sparse_sparse_matmul<
     Ok(SparseColMat::<I, E>::new(symbolic, values.into_inner()))
 }
 
-/// Multiplies a sparse matrix `lhs` by a dense matrix `rhs`, and stores the result in
-/// `acc`. See [`faer::linalg::matmul::matmul`](crate::linalg::matmul::matmul) for more details.
+/// Symbolic structure of the product of two sparse matrices, which can be reused for multiple
+/// numeric products of matrices with the same sparsity patterns.
 ///
-/// # Note
-/// Allows unsorted matrices.
+/// # Example
+/// ```
+/// use faer::{
+///     sparse::{linalg::matmul::SymbolicSparseMatmul, SparseColMat},
+///     Parallelism,
+/// };
+///
+/// let a = SparseColMat::<usize, f64>::try_new_from_triplets(
+///     2,
+///     2,
+///     &[(0, 0, 1.0), (1, 0, 2.0), (1, 1, 3.0)],
+/// )
+/// .unwrap();
+///
+/// let symbolic = SymbolicSparseMatmul::try_new(a.symbolic(), a.symbolic()).unwrap();
+/// let c = symbolic
+///     .try_matmul(a.as_ref(), a.as_ref(), 1.0, Parallelism::None)
+///     .unwrap();
+/// assert!(c.to_dense() == a.to_dense() * a.to_dense());
+///
+/// // the structure can be reused for new values with the same sparsity pattern
+/// let mut b = a.to_owned().unwrap();
+/// b.values_mut()[1] = -1.0;
+/// let c = symbolic
+///     .try_matmul(b.as_ref(), a.as_ref(), 2.0, Parallelism::None)
+///     .unwrap();
+/// assert!(c.to_dense() == faer::scale(2.0) * b.to_dense() * a.to_dense());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SymbolicSparseMatmul<I: Index> {
+    // dimensions and number of non-zeros of the lhs and rhs, used to catch mismatched inputs
+    lhs_shape: (usize, usize, usize),
+    rhs_shape: (usize, usize, usize),
+    symbolic: SymbolicSparseColMat<I>,
+    info: SparseMatmulInfo,
+}
+
+impl<I: Index> SymbolicSparseMatmul<I> {
+    /// Computes the symbolic structure of the product of sparse matrices with the sparsity
+    /// patterns `lhs` and `rhs`.
+    ///
+    /// # Note
+    /// Allows unsorted matrices, and produces a sorted output.
+    #[track_caller]
+    pub fn try_new(
+        lhs: SymbolicSparseColMatRef<'_, I>,
+        rhs: SymbolicSparseColMatRef<'_, I>,
+    ) -> Result<Self, FaerError> {
+        let (symbolic, info) = sparse_sparse_matmul_symbolic(lhs, rhs)?;
+        Ok(Self {
+            lhs_shape: (lhs.nrows(), lhs.ncols(), lhs.compute_nnz()),
+            rhs_shape: (rhs.nrows(), rhs.ncols(), rhs.compute_nnz()),
+            symbolic,
+            info,
+        })
+    }
+
+    /// Returns the number of rows of the product.
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.symbolic.nrows()
+    }
+
+    /// Returns the number of columns of the product.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.symbolic.ncols()
+    }
+
+    /// Returns the sparsity pattern of the product.
+    #[inline]
+    pub fn symbolic(&self) -> SymbolicSparseColMatRef<'_, I> {
+        self.symbolic.as_ref()
+    }
+
+    /// Returns the info about the product, used to split the workload between threads.
+    #[inline]
+    pub fn info(&self) -> &SparseMatmulInfo {
+        &self.info
+    }
+
+    /// Computes the size and alignment of the workspace required to perform the numeric matrix
+    /// multiplication.
+    pub fn matmul_numeric_req<E: ComplexField>(
+        &self,
+        parallelism: Parallelism,
+    ) -> Result<StackReq, SizeOverflow> {
+        sparse_sparse_matmul_numeric_req::<I, E>(self.symbolic(), parallelism)
+    }
+
+    /// Performs a numeric matrix multiplication of a sparse matrix `lhs` by a sparse matrix `rhs`
+    /// multiplied by `k`, and stores the values of the result in `dst_values`, in the order of
+    /// the sparsity pattern [`Self::symbolic`].
+    ///
+    /// # Panics
+    /// - Panics if `lhs` or `rhs` don't have the same dimensions and number of non-zeros as the
+    /// sparsity patterns used to construct `self`.
+    /// - Panics if the length of `dst_values` is not equal to the number of non-zeros of the
+    /// product.
+    ///
+    /// # Note
+    /// `lhs` and `rhs` must have the same sparsity patterns as the ones used to construct `self`.
+    /// Otherwise, the result is unspecified.
+    #[track_caller]
+    pub fn matmul_numeric<
+        E: ComplexField,
+        LhsE: Conjugate<Canonical = E>,
+        RhsE: Conjugate<Canonical = E>,
+    >(
+        &self,
+        dst_values: GroupFor<E, &mut [E::Unit]>,
+        lhs: SparseColMatRef<'_, I, LhsE>,
+        rhs: SparseColMatRef<'_, I, RhsE>,
+        k: E,
+        parallelism: Parallelism,
+        stack: PodStack<'_>,
+    ) {
+        assert!(all(
+            (lhs.nrows(), lhs.ncols(), lhs.compute_nnz()) == self.lhs_shape,
+            (rhs.nrows(), rhs.ncols(), rhs.compute_nnz()) == self.rhs_shape,
+        ));
+        sparse_sparse_matmul_numeric(
+            SparseColMatMut::new(self.symbolic(), dst_values),
+            lhs,
+            rhs,
+            k,
+            &self.info,
+            parallelism,
+            stack,
+        );
+    }
+
+    /// Multiplies a sparse matrix `lhs` by a sparse matrix `rhs`, multiplied by `k`, and returns
+    /// the result.
+    ///
+    /// # Panics
+    /// Panics if `lhs` or `rhs` don't have the same dimensions and number of non-zeros as the
+    /// sparsity patterns used to construct `self`.
+    ///
+    /// # Note
+    /// `lhs` and `rhs` must have the same sparsity patterns as the ones used to construct `self`.
+    /// Otherwise, the result is unspecified.
+    #[track_caller]
+    pub fn try_matmul<
+        E: ComplexField,
+        LhsE: Conjugate<Canonical = E>,
+        RhsE: Conjugate<Canonical = E>,
+    >(
+        &self,
+        lhs: SparseColMatRef<'_, I, LhsE>,
+        rhs: SparseColMatRef<'_, I, RhsE>,
+        k: E,
+        parallelism: Parallelism,
+    ) -> Result<SparseColMat<I, E>, FaerError> {
+        let nnz = self.symbolic.row_indices().len();
+        let mut values = VecGroup::<E>::new();
+        values
+            .try_reserve_exact(nnz)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        values.resize(nnz, E::faer_zero().faer_into_units());
+
+        self.matmul_numeric(
+            values.as_slice_mut().into_inner(),
+            lhs,
+            rhs,
+            k,
+            parallelism,
+            PodStack::new(
+                &mut GlobalPodBuffer::try_new(
+                    self.matmul_numeric_req::<E>(parallelism)
+                        .map_err(|_| FaerError::OutOfMemory)?,
+                )
+                .map_err(|_| FaerError::OutOfMemory)?,
+            ),
+        );
+
+        Ok(SparseColMat::<I, E>::new(
+            self.symbolic.clone(),
+            values.into_inner(),
+        ))
+    }
+}
+
+// minimum number of flops before the sparse-dense products are parallelized
+const SPARSE_DENSE_PAR_THRESHOLD: f64 = 128.0 * 128.0;
+
+#[inline]
+fn sparse_dense_par(nnz: usize, n: usize, parallelism: Parallelism) -> usize {
+    if (nnz as f64) * (n as f64) >= SPARSE_DENSE_PAR_THRESHOLD {
+        crate::utils::thread::parallelism_degree(parallelism)
+    } else {
+        1
+    }
+}
+
+/// Returns the range of columns of the matrix with column pointers `col_ptr` assigned to the
+/// consumer at index `idx`, when split between `chunk_count` consumers with a balanced number of
+/// non-zeros.
+fn par_split_nnz<I: Index>(
+    col_ptr: &[I],
+    idx: usize,
+    chunk_count: usize,
+) -> core::ops::Range<usize> {
+    let n = col_ptr.len() - 1;
+    let nnz_start = col_ptr[0].zx();
+    let total = (col_ptr[n].zx() - nnz_start) as f64;
+
+    let col_start = |idx: usize| {
+        if idx == chunk_count {
+            n
+        } else {
+            let target = total * (idx as f64 / chunk_count as f64);
+            Ord::min(
+                n,
+                col_ptr.partition_point(|&p| ((p.zx() - nnz_start) as f64) < target),
+            )
+        }
+    };
+    col_start(idx)..col_start(idx + 1)
+}
+
 #[track_caller]
-pub fn sparse_dense_matmul<
+fn scale_acc<E: ComplexField>(acc: MatMut<'_, E>, alpha: Option<E>) {
+    let mut acc = acc;
+    match alpha {
+        Some(alpha) => {
+            if alpha != E::faer_one() {
+                zipped!(acc.rb_mut())
+                    .for_each(|unzipped!(mut dst)| dst.write(dst.read().faer_mul(alpha)))
+            }
+        }
+        None => acc.fill_zero(),
+    }
+}
+
+// computes `acc += beta * lhs[:, depth] * rhs[depth, :]`
+fn sparse_dense_matmul_serial<
     I: Index,
     E: ComplexField,
     LhsE: Conjugate<Canonical = E>,
@@ -260,33 +492,13 @@ pub fn sparse_dense_matmul<
     acc: MatMut<'_, E>,
     lhs: SparseColMatRef<'_, I, LhsE>,
     rhs: MatRef<'_, RhsE>,
-    alpha: Option<E>,
     beta: E,
-    parallelism: Parallelism,
+    depth: core::ops::Range<usize>,
 ) {
-    assert!(all(
-        acc.nrows() == lhs.nrows(),
-        acc.ncols() == rhs.ncols(),
-        lhs.ncols() == rhs.nrows(),
-    ));
-
-    let _ = parallelism;
     let m = acc.nrows();
     let n = acc.ncols();
     let k = lhs.ncols();
 
-    let mut acc = acc;
-
-    match alpha {
-        Some(alpha) => {
-            if alpha != E::faer_one() {
-                zipped!(acc.rb_mut())
-                    .for_each(|unzipped!(mut dst)| dst.write(dst.read().faer_mul(alpha)))
-            }
-        }
-        None => acc.fill_zero(),
-    }
-
     Size::with2(m, n, |m, n| {
         Size::with(k, |k| {
             let mut acc = constrained::mat::MatMut::new(acc, m, n);
@@ -294,7 +506,8 @@ pub fn sparse_dense_matmul<
             let rhs = constrained::mat::MatRef::new(rhs, k, n);
 
             for j in n.indices() {
-                for depth in k.indices() {
+                for depth in depth.clone() {
+                    let depth = k.check(depth);
                     let rhs_kj = rhs.read(depth, j).canonicalize().faer_mul(beta);
                     for (i, lhs_ik) in zip(
                         lhs.row_indices_of_col(depth),
@@ -313,13 +526,8 @@ pub fn sparse_dense_matmul<
     });
 }
 
-/// Multiplies a dense matrix `lhs` by a sparse matrix `rhs`, and stores the result in
-/// `acc`. See [`faer::linalg::matmul::matmul`](crate::linalg::matmul::matmul) for more details.
-///
-/// # Note
-/// Allows unsorted matrices.
-#[track_caller]
-pub fn dense_sparse_matmul<
+// computes `acc[:, cols] += beta * lhs * rhs[:, cols]`
+fn dense_sparse_matmul_serial<
     I: Index,
     E: ComplexField,
     LhsE: Conjugate<Canonical = E>,
@@ -328,41 +536,22 @@ pub fn dense_sparse_matmul<
     acc: MatMut<'_, E>,
     lhs: MatRef<'_, LhsE>,
     rhs: SparseColMatRef<'_, I, RhsE>,
-    alpha: Option<E>,
     beta: E,
-    parallelism: Parallelism,
+    cols: core::ops::Range<usize>,
 ) {
-    assert!(all(
-        acc.nrows() == lhs.nrows(),
-        acc.ncols() == rhs.ncols(),
-        lhs.ncols() == rhs.nrows(),
-    ));
-
-    let _ = parallelism;
     let m = acc.nrows();
     let n = acc.ncols();
     let k = lhs.ncols();
 
-    let mut acc = acc;
-
-    match alpha {
-        Some(alpha) => {
-            if alpha != E::faer_one() {
-                zipped!(acc.rb_mut())
-                    .for_each(|unzipped!(mut dst)| dst.write(dst.read().faer_mul(alpha)))
-            }
-        }
-        None => acc.fill_zero(),
-    }
-
     Size::with2(m, n, |m, n| {
         Size::with(k, |k| {
             let mut acc = constrained::mat::MatMut::new(acc, m, n);
             let lhs = constrained::mat::MatRef::new(lhs, m, k);
             let rhs = constrained::sparse::SparseColMatRef::new(rhs, k, n);
 
-            for i in m.indices() {
-                for j in n.indices() {
+            for j in cols.clone() {
+                let j = n.check(j);
+                for i in m.indices() {
                     let mut acc_ij = E::faer_zero();
                     for (depth, rhs_kj) in zip(
                         rhs.row_indices_of_col(j),
@@ -380,6 +569,159 @@ pub fn dense_sparse_matmul<
     });
 }
 
+/// Multiplies a sparse matrix `lhs` by a dense matrix `rhs`, and stores the result in
+/// `acc`. See [`faer::linalg::matmul::matmul`](crate::linalg::matmul::matmul) for more details.
+///
+/// The work is split between threads over the columns of `rhs`, or over the columns of `lhs` if
+/// `rhs` has too few columns, in which case the partial products are summed at the end.
+///
+/// # Note
+/// Allows unsorted matrices.
+#[track_caller]
+pub fn sparse_dense_matmul<
+    I: Index,
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    acc: MatMut<'_, E>,
+    lhs: SparseColMatRef<'_, I, LhsE>,
+    rhs: MatRef<'_, RhsE>,
+    alpha: Option<E>,
+    beta: E,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        acc.nrows() == lhs.nrows(),
+        acc.ncols() == rhs.ncols(),
+        lhs.ncols() == rhs.nrows(),
+    ));
+
+    let m = acc.nrows();
+    let n = acc.ncols();
+    let k = lhs.ncols();
+
+    let mut acc = acc;
+    scale_acc(acc.rb_mut(), alpha);
+
+    let par = sparse_dense_par(lhs.compute_nnz(), n, parallelism);
+    if par == 1 {
+        sparse_dense_matmul_serial(acc, lhs, rhs, beta, 0..k);
+    } else if n >= par {
+        // the columns of the product are independent
+        crate::utils::thread::for_each_raw(
+            par,
+            |tid| {
+                let (col_start, ncols) = crate::utils::thread::par_split_indices(n, tid, par);
+                let acc = unsafe { acc.rb().subcols(col_start, ncols).const_cast() };
+                sparse_dense_matmul_serial(acc, lhs, rhs.subcols(col_start, ncols), beta, 0..k);
+            },
+            parallelism,
+        );
+    } else {
+        // split the inner dimension, with each thread other than the first one accumulating its
+        // partial product in a separate workspace
+        let mut work = crate::Mat::<E>::zeros(m, n * (par - 1));
+        {
+            let acc = acc.rb();
+            let work = work.as_ref();
+            crate::utils::thread::for_each_raw(
+                par,
+                |tid| {
+                    let dst = if tid == 0 {
+                        acc
+                    } else {
+                        work.subcols((tid - 1) * n, n)
+                    };
+                    let dst = unsafe { dst.const_cast() };
+                    sparse_dense_matmul_serial(
+                        dst,
+                        lhs,
+                        rhs,
+                        beta,
+                        par_split_nnz(lhs.col_ptrs(), tid, par),
+                    );
+                },
+                parallelism,
+            );
+        }
+        for tid in 1..par {
+            zipped!(acc.rb_mut(), work.as_ref().subcols((tid - 1) * n, n))
+                .for_each(|unzipped!(mut dst, src)| dst.write(dst.read().faer_add(src.read())));
+        }
+    }
+}
+
+/// Multiplies a dense matrix `lhs` by a sparse matrix `rhs`, and stores the result in
+/// `acc`. See [`faer::linalg::matmul::matmul`](crate::linalg::matmul::matmul) for more details.
+///
+/// The work is split between threads over the columns of `rhs`, or over the rows of `lhs` if
+/// `rhs` has too few columns.
+///
+/// # Note
+/// Allows unsorted matrices.
+#[track_caller]
+pub fn dense_sparse_matmul<
+    I: Index,
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    acc: MatMut<'_, E>,
+    lhs: MatRef<'_, LhsE>,
+    rhs: SparseColMatRef<'_, I, RhsE>,
+    alpha: Option<E>,
+    beta: E,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        acc.nrows() == lhs.nrows(),
+        acc.ncols() == rhs.ncols(),
+        lhs.ncols() == rhs.nrows(),
+    ));
+
+    let m = acc.nrows();
+    let n = acc.ncols();
+
+    let mut acc = acc;
+    scale_acc(acc.rb_mut(), alpha);
+
+    let par = sparse_dense_par(rhs.compute_nnz(), m, parallelism);
+    if par == 1 {
+        dense_sparse_matmul_serial(acc, lhs, rhs, beta, 0..n);
+    } else if n >= par {
+        // the columns of the product are independent
+        let acc = acc.rb();
+        crate::utils::thread::for_each_raw(
+            par,
+            |tid| {
+                let acc = unsafe { acc.const_cast() };
+                dense_sparse_matmul_serial(
+                    acc,
+                    lhs,
+                    rhs,
+                    beta,
+                    par_split_nnz(rhs.col_ptrs(), tid, par),
+                );
+            },
+            parallelism,
+        );
+    } else {
+        // the rows of the product are independent
+        let par = Ord::min(par, m);
+        let acc = acc.rb();
+        crate::utils::thread::for_each_raw(
+            par,
+            |tid| {
+                let (row_start, nrows) = crate::utils::thread::par_split_indices(m, tid, par);
+                let acc = unsafe { acc.subrows(row_start, nrows).const_cast() };
+                dense_sparse_matmul_serial(acc, lhs.subrows(row_start, nrows), rhs, beta, 0..n);
+            },
+            parallelism,
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -439,4 +781,126 @@ mod tests {
 
         assert!(c.to_dense() == crate::scale(2.00) * a.to_dense() * b.to_dense());
     }
+
+    #[test]
+    fn test_symbolic_sparse_matmul_reuse() {
+        let a = SparseColMat::<usize, f64>::try_new_from_triplets(
+            4,
+            4,
+            &[
+                (0, 0, 1.0),
+                (1, 0, 2.0),
+                (1, 1, 3.0),
+                (2, 1, 4.0),
+                (2, 2, 5.0),
+                (3, 2, 6.0),
+                (3, 3, 7.0),
+                (0, 3, 8.0),
+            ],
+        )
+        .unwrap();
+        let mut b = a.to_owned().unwrap();
+        let symbolic = SymbolicSparseMatmul::try_new(a.symbolic(), b.symbolic()).unwrap();
+
+        let mut values = alloc::vec![0.0; symbolic.symbolic().compute_nnz()];
+        let mut mem = GlobalPodBuffer::new(
+            symbolic
+                .matmul_numeric_req::<f64>(Parallelism::None)
+                .unwrap(),
+        );
+        for step in 0..3 {
+            for (idx, v) in b.values_mut().iter_mut().enumerate() {
+                *v = (step * 7 + idx) as f64 - 4.0;
+            }
+
+            let expected =
+                sparse_sparse_matmul(a.as_ref(), b.as_ref(), 2.0, Parallelism::None).unwrap();
+            let c = symbolic
+                .try_matmul(a.as_ref(), b.as_ref(), 2.0, Parallelism::None)
+                .unwrap();
+            assert!(c.symbolic().col_ptrs() == expected.symbolic().col_ptrs());
+            assert!(c.symbolic().row_indices() == expected.symbolic().row_indices());
+            assert!(c.values() == expected.values());
+
+            symbolic.matmul_numeric(
+                values.as_mut_slice(),
+                a.as_ref(),
+                b.as_ref(),
+                2.0,
+                Parallelism::None,
+                PodStack::new(&mut mem),
+            );
+            assert!(values == expected.values());
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_symbolic_sparse_matmul_mismatch() {
+        let a = SparseColMat::<usize, f64>::try_new_from_triplets(2, 2, &[(0, 0, 1.0)]).unwrap();
+        let b =
+            SparseColMat::<usize, f64>::try_new_from_triplets(2, 2, &[(0, 0, 1.0), (1, 1, 1.0)])
+                .unwrap();
+        let symbolic = SymbolicSparseMatmul::try_new(a.symbolic(), a.symbolic()).unwrap();
+        let _ = symbolic.try_matmul(a.as_ref(), b.as_ref(), 1.0, Parallelism::None);
+    }
+
+    #[test]
+    fn test_sparse_dense_matmul_parallel() {
+        use crate::{scale, Mat};
+
+        // integer valued entries, so that the results are exact regardless of the summation
+        // order
+        let sparse = |m: usize, n: usize| {
+            let mut triplets = alloc::vec::Vec::new();
+            for j in 0..n {
+                for i in 0..m {
+                    if (i * 7 + j * 3) % 5 == 0 || n < 4 {
+                        triplets.push((i, j, ((i + 2 * j) % 11) as f64 - 5.0));
+                    }
+                }
+            }
+            SparseColMat::<usize, f64>::try_new_from_triplets(m, n, &triplets).unwrap()
+        };
+        let dense = |m: usize, n: usize| Mat::from_fn(m, n, |i, j| ((i * 3 + j) % 7) as f64 - 3.0);
+
+        let wide = sparse(400, 300);
+        let tall = sparse(400, 2);
+
+        for parallelism in [Parallelism::None, Parallelism::Rayon(4)] {
+            // sparse * dense, split over the columns of the product or the inner dimension
+            for n in [1, 2, 9] {
+                let b = dense(300, n);
+                let c = dense(400, n);
+
+                let mut acc = c.clone();
+                sparse_dense_matmul(
+                    acc.as_mut(),
+                    wide.as_ref(),
+                    b.as_ref(),
+                    Some(2.0),
+                    3.0,
+                    parallelism,
+                );
+                assert!(acc == scale(2.0) * &c + scale(3.0) * (wide.to_dense() * &b));
+            }
+
+            // dense * sparse, split over the columns or the rows of the product
+            for (rhs, m) in [(&wide, 1), (&wide, 9), (&tall, 60)] {
+                let a = dense(m, 400);
+                let c = dense(m, rhs.ncols());
+
+                let mut acc = c.clone();
+                dense_sparse_matmul(
+                    acc.as_mut(),
+                    a.as_ref(),
+                    rhs.as_ref(),
+                    Some(2.0),
+                    3.0,
+                    parallelism,
+                );
+                assert!(acc == scale(2.0) * &c + scale(3.0) * (&a * rhs.to_dense()));
+            }
+        }
+    }
 }
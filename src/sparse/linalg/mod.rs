@@ -169,11 +169,16 @@ pub mod triangular_solve;
 
 pub mod amd;
 pub mod colamd;
+pub mod nested_dissection;
+pub mod rcm;
+pub mod structure;
 
 pub mod cholesky;
 pub mod lu;
 pub mod qr;
 
+pub mod out_of_core;
+
 /// Sparse LU error.
 #[derive(Copy, Clone, Debug)]
 pub enum LuError {
@@ -239,6 +244,37 @@ impl From<crate::linalg::solvers::CholeskyError> for CholeskyError {
     }
 }
 
+/// Error that can occur when modifying a sparse Cholesky factorization in place.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CholeskyUpdateError {
+    /// The modification would require an entry that is not part of the symbolic structure of
+    /// the factor.
+    ///
+    /// The indices refer to the rows and columns of the factor, i.e., after the fill-reducing
+    /// permutation is applied.
+    StructurallyMissing {
+        /// Row of the missing entry.
+        row: usize,
+        /// Column of the missing entry.
+        col: usize,
+    },
+    /// The modified matrix is not positive definite.
+    NotPositiveDefinite,
+    /// The modified matrix has a zero pivot.
+    ZeroPivot,
+}
+
+impl core::fmt::Display for CholeskyUpdateError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for CholeskyUpdateError {}
+
 /// High level sparse solvers.
 pub mod solvers;
 
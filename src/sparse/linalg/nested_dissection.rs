@@ -0,0 +1,456 @@
+//! Nested dissection ordering.
+//!
+//! Nested dissection recursively splits the graph of a symmetric sparsity pattern into two parts
+//! by removing a small set of vertices, the separator, and numbers the separator after both parts.
+//! The factorization of the two parts is then independent, and the fill-in is confined to the
+//! separators. On the matrices arising from 2D and 3D meshes, this produces much sparser factors
+//! than minimum degree orderings.
+//!
+//! The separators are found from the level structure of a breadth first search rooted at a
+//! pseudo-peripheral vertex, and the subgraphs that become smaller than
+//! [`Control::leaf_size`] are ordered with [`amd`](super::amd).
+
+use super::{
+    amd,
+    mem::NONE,
+    rcm::{bfs, pseudo_peripheral_vertex, symmetric_adjacency},
+    FaerError, Index, SignedIndex, SymbolicSparseColMatRef,
+};
+use crate::assert;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+use reborrow::*;
+
+/// Tuning parameters for the nested dissection ordering.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Control {
+    /// Subgraphs with at most this many vertices are not split further, and are ordered with
+    /// the approximate minimum degree algorithm instead.
+    pub leaf_size: usize,
+    /// Parameters for ordering the leaf subgraphs.
+    pub amd_params: amd::Control,
+}
+
+impl Default for Control {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            leaf_size: 64,
+            amd_params: amd::Control::default(),
+        }
+    }
+}
+
+/// Computes the size and alignment of required workspace for computing the nested dissection
+/// ordering of a matrix with dimension `n` and `nnz` stored entries.
+pub fn order_req<I: Index>(n: usize, nnz: usize) -> Result<StackReq, SizeOverflow> {
+    let n_req = StackReq::try_new::<I>(n)?;
+    let n1_req = StackReq::try_new::<I>(n.checked_add(1).ok_or(SizeOverflow)?)?;
+    let nnz2 = nnz.checked_mul(2).ok_or(SizeOverflow)?;
+    let nnz2_req = StackReq::try_new::<I>(nnz2)?;
+    StackReq::try_all_of([
+        // adj_ptr
+        n1_req,
+        // adj_ind
+        nnz2_req,
+        // mark
+        n_req,
+        // queue
+        n_req,
+        // level_ptr
+        n1_req,
+        // ranges
+        StackReq::try_new::<I>(n.checked_mul(2).ok_or(SizeOverflow)?)?,
+        // leaf_col_ptr
+        n1_req,
+        // leaf_row_ind
+        nnz2_req,
+        amd::order_maybe_unsorted_req::<I>(n, nnz2)?,
+    ])
+}
+
+/// Computes the nested dissection ordering of the sparsity pattern of `A + A.T`, and stores it
+/// in `perm` and `perm_inv`, such that `perm[new] = old` and `perm_inv[old] = new`.
+///
+/// The matrix may store only its upper or lower triangular part, and its row indices do not need
+/// to be sorted.
+pub fn order<I: Index>(
+    perm: &mut [I],
+    perm_inv: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+    control: Control,
+    stack: PodStack<'_>,
+) -> Result<(), FaerError> {
+    let n = perm.len();
+    assert!(all(A.nrows() == n, A.ncols() == n, perm_inv.len() == n));
+    if n == 0 {
+        return Ok(());
+    }
+
+    let nnz = A.compute_nnz();
+    if nnz.checked_mul(2).ok_or(FaerError::IndexOverflow)? >= I::Signed::MAX.zx()
+        || n.checked_mul(2).ok_or(FaerError::IndexOverflow)? >= I::Signed::MAX.zx()
+    {
+        return Err(FaerError::IndexOverflow);
+    }
+
+    let I = I::truncate;
+    let zero = I(0);
+    let one = I(1);
+    let two = I(2);
+
+    let (adj_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (adj_ind, stack) = stack.make_raw::<I>(2 * nnz);
+    let (mark, stack) = stack.make_raw::<I>(n);
+    let (queue, stack) = stack.make_raw::<I>(n);
+    let (level_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (ranges, stack) = stack.make_raw::<I>(2 * n);
+    let (leaf_col_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (leaf_row_ind, mut stack) = stack.make_raw::<I>(2 * nnz);
+
+    let adj_nnz = symmetric_adjacency(adj_ptr, adj_ind, mark, A);
+    let adj_ptr = &*adj_ptr;
+    let adj_ind = &adj_ind[..adj_nnz];
+    mark.fill(zero);
+
+    // each pending subgraph occupies the range `begin..end` of `perm`, which is also the range of
+    // its final numbering, and its vertices are labeled with `begin` in `perm_inv`. separator
+    // vertices are labeled with `NONE`
+    for (k, p) in perm.iter_mut().enumerate() {
+        *p = I(k);
+    }
+    perm_inv.fill(zero);
+
+    ranges[0] = zero;
+    ranges[1] = I(n);
+    let mut n_ranges = 1usize;
+
+    while n_ranges > 0 {
+        n_ranges -= 1;
+        let begin = ranges[2 * n_ranges].zx();
+        let end = ranges[2 * n_ranges + 1].zx();
+        let size = end - begin;
+        let label = I(begin);
+
+        let mut is_leaf = size <= Ord::max(control.leaf_size, 2);
+
+        if !is_leaf {
+            let root = {
+                let perm_inv = &*perm_inv;
+                pseudo_peripheral_vertex(
+                    perm[begin].zx(),
+                    adj_ptr,
+                    adj_ind,
+                    |v| perm_inv[v] == label,
+                    mark,
+                    queue,
+                )
+            };
+
+            let mut n_levels = 0usize;
+            level_ptr[0] = zero;
+            let reached = {
+                let perm_inv = &*perm_inv;
+                bfs(
+                    root,
+                    adj_ptr,
+                    adj_ind,
+                    |v| perm_inv[v] == label,
+                    mark,
+                    queue,
+                    |_, last| {
+                        n_levels += 1;
+                        level_ptr[n_levels] = I(last);
+                    },
+                )
+            };
+
+            if reached < size {
+                // the subgraph is disconnected: split off the component of the root
+                for &v in &queue[..reached] {
+                    mark[v.zx()] = one;
+                }
+                let mut pos = reached;
+                for &v in &perm[begin..end] {
+                    if mark[v.zx()] == zero {
+                        queue[pos] = v;
+                        pos += 1;
+                    }
+                }
+                for &v in &queue[..reached] {
+                    mark[v.zx()] = zero;
+                }
+                perm[begin..end].copy_from_slice(&queue[..size]);
+
+                for &v in &perm[begin + reached..end] {
+                    perm_inv[v.zx()] = I(begin + reached);
+                }
+                ranges[2 * n_ranges] = I(begin);
+                ranges[2 * n_ranges + 1] = I(begin + reached);
+                ranges[2 * n_ranges + 2] = I(begin + reached);
+                ranges[2 * n_ranges + 3] = I(end);
+                n_ranges += 2;
+                continue;
+            }
+
+            if n_levels < 3 {
+                // no separator can be extracted from the level structure
+                is_leaf = true;
+            } else {
+                // the separator is the level that splits the vertices in two halves
+                let mut sep = 1usize;
+                while sep < n_levels - 2 && level_ptr[sep + 1].zx() < size / 2 {
+                    sep += 1;
+                }
+                let sep_begin = level_ptr[sep].zx();
+                let sep_end = level_ptr[sep + 1].zx();
+
+                // separator vertices that are not adjacent to the second part can be moved to
+                // the first one
+                for &v in &queue[sep_end..size] {
+                    mark[v.zx()] = one;
+                }
+                for &v in &queue[sep_begin..sep_end] {
+                    let v = v.zx();
+                    if adj_ind[adj_ptr[v].zx()..adj_ptr[v + 1].zx()]
+                        .iter()
+                        .all(|&u| mark[u.zx()] != one)
+                    {
+                        mark[v] = two;
+                    }
+                }
+
+                let mut pos = begin;
+                for &v in &queue[..sep_begin] {
+                    perm[pos] = v;
+                    pos += 1;
+                }
+                for &v in &queue[sep_begin..sep_end] {
+                    if mark[v.zx()] == two {
+                        perm[pos] = v;
+                        pos += 1;
+                    }
+                }
+                let first_end = pos;
+                for &v in &queue[sep_end..size] {
+                    perm[pos] = v;
+                    perm_inv[v.zx()] = I(first_end);
+                    pos += 1;
+                }
+                let second_end = pos;
+                for &v in &queue[sep_begin..sep_end] {
+                    if mark[v.zx()] != two {
+                        perm[pos] = v;
+                        perm_inv[v.zx()] = I(NONE);
+                        pos += 1;
+                    }
+                }
+                debug_assert!(pos == end);
+                for &v in &queue[sep_begin..size] {
+                    mark[v.zx()] = zero;
+                }
+
+                ranges[2 * n_ranges] = I(begin);
+                ranges[2 * n_ranges + 1] = I(first_end);
+                ranges[2 * n_ranges + 2] = I(first_end);
+                ranges[2 * n_ranges + 3] = I(second_end);
+                n_ranges += 2;
+            }
+        }
+
+        if is_leaf && size > 1 {
+            // order the leaf with amd, using `mark` to map the vertices to local indices
+            let verts = &mut perm[begin..end];
+            for (k, &v) in verts.iter().enumerate() {
+                mark[v.zx()] = I(k);
+            }
+
+            let mut pos = 0usize;
+            leaf_col_ptr[0] = zero;
+            for (k, &v) in verts.iter().enumerate() {
+                let v = v.zx();
+                for &u in &adj_ind[adj_ptr[v].zx()..adj_ptr[v + 1].zx()] {
+                    if perm_inv[u.zx()] == label {
+                        leaf_row_ind[pos] = mark[u.zx()];
+                        pos += 1;
+                    }
+                }
+                leaf_col_ptr[k + 1] = I(pos);
+            }
+
+            let leaf = SymbolicSparseColMatRef::new_unsorted_checked(
+                size,
+                size,
+                &leaf_col_ptr[..size + 1],
+                None,
+                &leaf_row_ind[..pos],
+            );
+            amd::order_maybe_unsorted(
+                &mut queue[..size],
+                &mut level_ptr[..size],
+                leaf,
+                control.amd_params,
+                stack.rb_mut(),
+            )?;
+
+            level_ptr[..size].copy_from_slice(verts);
+            for (v, &k) in verts.iter_mut().zip(&queue[..size]) {
+                *v = level_ptr[k.zx()];
+                mark[v.zx()] = zero;
+            }
+        }
+    }
+
+    for (k, &v) in perm.iter().enumerate() {
+        perm_inv[v.zx()] = I(k);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert;
+    use dyn_stack::GlobalPodBuffer;
+
+    fn compute_nd<I: Index>(
+        A: SymbolicSparseColMatRef<'_, I>,
+        control: Control,
+    ) -> (Vec<I>, Vec<I>) {
+        let n = A.nrows();
+        let mut perm = vec![I::truncate(0); n];
+        let mut perm_inv = vec![I::truncate(0); n];
+        order(
+            &mut perm,
+            &mut perm_inv,
+            A,
+            control,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                order_req::<I>(n, A.compute_nnz()).unwrap(),
+            )),
+        )
+        .unwrap();
+
+        let mut seen = vec![false; n];
+        for k in 0..n {
+            assert!(perm_inv[perm[k].zx()].zx() == k);
+            assert!(!seen[perm[k].zx()]);
+            seen[perm[k].zx()] = true;
+        }
+        (perm, perm_inv)
+    }
+
+    /// Number of nonzeros in the cholesky factor of the pattern `A` after permutation, computed
+    /// by symbolic elimination.
+    fn factor_nnz<I: Index>(A: SymbolicSparseColMatRef<'_, I>, perm_inv: &[I]) -> usize {
+        let n = A.nrows();
+        let mut cols = vec![std::collections::BTreeSet::new(); n];
+        for j in 0..n {
+            for i in A.row_indices_of_col(j) {
+                let (pi, pj) = (perm_inv[i].zx(), perm_inv[j].zx());
+                let (lo, hi) = if pi < pj { (pi, pj) } else { (pj, pi) };
+                if lo != hi {
+                    cols[lo].insert(hi);
+                }
+            }
+        }
+        let mut nnz = n;
+        for j in 0..n {
+            let col = core::mem::take(&mut cols[j]);
+            nnz += col.len();
+            if let Some(&parent) = col.iter().next() {
+                cols[parent].extend(col.iter().copied().filter(|&i| i != parent));
+            }
+        }
+        nnz
+    }
+
+    fn grid<I: Index>(k: usize) -> (Vec<I>, Vec<I>) {
+        let I = I::truncate;
+        let n = k * k;
+        let mut col_ptr = vec![I(0)];
+        let mut row_ind = vec![];
+        for j in 0..n {
+            let (x, y) = (j % k, j / k);
+            if y > 0 {
+                row_ind.push(I(j - k));
+            }
+            if x > 0 {
+                row_ind.push(I(j - 1));
+            }
+            row_ind.push(I(j));
+            col_ptr.push(I(row_ind.len()));
+        }
+        (col_ptr, row_ind)
+    }
+
+    fn test_nd_grid<I: Index>() {
+        let k = 40;
+        let n = k * k;
+        let (col_ptr, row_ind) = grid::<I>(k);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        let identity = (0..n).map(I::truncate).collect::<Vec<_>>();
+        let (_, perm_inv) = compute_nd(
+            A,
+            Control {
+                leaf_size: 16,
+                ..Default::default()
+            },
+        );
+
+        // the natural ordering has a banded factor with about `k` nonzeros per column
+        assert!(factor_nnz(A, &perm_inv) < factor_nnz(A, &identity) / 2);
+    }
+
+    fn test_nd_disconnected<I: Index>() {
+        let I = I::truncate;
+
+        // two disjoint grids, with interleaved vertices
+        let k = 10;
+        let n = 2 * k * k;
+        let (col_ptr_, row_ind_) = grid::<I>(k);
+        let mut cols = vec![vec![]; n];
+        for j in 0..k * k {
+            for &i in &row_ind_[col_ptr_[j].zx()..col_ptr_[j + 1].zx()] {
+                cols[2 * j].push(I(2 * i.zx()));
+                cols[2 * j + 1].push(I(2 * i.zx() + 1));
+            }
+        }
+        let mut col_ptr = vec![I(0)];
+        let mut row_ind = vec![];
+        for col in &cols {
+            row_ind.extend_from_slice(col);
+            col_ptr.push(I(row_ind.len()));
+        }
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        let (perm, _) = compute_nd(
+            A,
+            Control {
+                leaf_size: 8,
+                ..Default::default()
+            },
+        );
+
+        // the components are numbered one after the other
+        let first = perm[0].zx() % 2;
+        assert!(perm[..k * k].iter().all(|p| p.zx() % 2 == first));
+    }
+
+    fn test_nd_small<I: Index>() {
+        let I = I::truncate;
+        let col_ptr = [0, 1, 3, 5].map(I);
+        let row_ind = [0, 0, 1, 1, 2].map(I);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(3, 3, &col_ptr, None, &row_ind);
+        compute_nd(A, Control::default());
+
+        let col_ptr = [I(0)];
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(0, 0, &col_ptr, None, &[]);
+        compute_nd(A, Control::default());
+    }
+
+    monomorphize_test!(test_nd_grid);
+    monomorphize_test!(test_nd_disconnected);
+    monomorphize_test!(test_nd_small);
+}
@@ -0,0 +1,945 @@
+//! Out-of-core sparse Cholesky factorization.
+//!
+//! When the Cholesky factor of a matrix doesn't fit in memory, the supernodal LLT factorization
+//! can be computed within a user-specified memory budget. Each supernode of the factor is written
+//! to a [`SupernodeStorage`] backend as soon as it's computed, and read back when it's needed to
+//! update a later supernode, or to solve a linear system. The recently computed supernodes are
+//! kept in an in-memory cache that fills the rest of the budget, which avoids most of the reads
+//! for the updates between nearby supernodes of the elimination tree.
+//!
+//! The memory budget accounts for the numerical values of the factor, and must be at least
+//! [`min_memory_budget`], which is twice the size of the largest supernode. The input matrix,
+//! the symbolic factorization, and a workspace proportional to the largest update between two
+//! supernodes are kept in memory in addition to the budget.
+//!
+//! Only the supernodal LLT factorization is supported, since the simplicial factorization and
+//! the sparse LU factorization access their whole factors at every step.
+
+use super::{
+    cholesky::{supernodal::SymbolicSupernodalCholesky, SymbolicCholesky, SymbolicCholeskyRaw},
+    ghost, ghost_permute_hermitian_unsorted, make_raw, make_raw_req,
+    mem::NONE,
+    FaerError, Index,
+};
+use crate::{
+    assert,
+    linalg::{
+        cholesky::llt::compute::{cholesky_in_place, cholesky_in_place_req, LltRegularization},
+        temp_mat_req, temp_mat_uninit,
+    },
+    sparse::SparseColMatRef,
+    utils::vec::VecGroup,
+    ComplexField, Conj, Entity, Mat, MatMut, Parallelism, Side,
+};
+use alloc::{collections::VecDeque, vec::Vec};
+use core::{iter::zip, marker::PhantomData};
+use dyn_stack::{GlobalPodBuffer, PodStack, SizeOverflow, StackReq};
+use faer_entity::GroupFor;
+use reborrow::*;
+
+/// Storage backend for the supernodes of an out-of-core factorization.
+pub trait SupernodeStorage<E: Entity> {
+    /// Error returned by the backend.
+    type Error;
+
+    /// Stores the values of the supernode `s`.
+    ///
+    /// If the same backend is used for several factorizations, a supernode may be stored again,
+    /// in which case its values have the same length and replace the previous ones.
+    fn store(&mut self, s: usize, values: GroupFor<E, &[E::Unit]>) -> Result<(), Self::Error>;
+
+    /// Loads the values of the supernode `s` into `values`, which has the same length as the
+    /// stored ones.
+    fn load(&mut self, s: usize, values: GroupFor<E, &mut [E::Unit]>) -> Result<(), Self::Error>;
+}
+
+impl<E: Entity, S: ?Sized + SupernodeStorage<E>> SupernodeStorage<E> for &mut S {
+    type Error = S::Error;
+
+    #[inline]
+    fn store(&mut self, s: usize, values: GroupFor<E, &[E::Unit]>) -> Result<(), Self::Error> {
+        (**self).store(s, values)
+    }
+
+    #[inline]
+    fn load(&mut self, s: usize, values: GroupFor<E, &mut [E::Unit]>) -> Result<(), Self::Error> {
+        (**self).load(s, values)
+    }
+}
+
+/// Storage backend that keeps the supernodes in memory.
+///
+/// This is mostly useful for testing, or as a building block for backends that compress the
+/// supernodes.
+#[derive(Debug)]
+pub struct MemoryStorage<E: Entity> {
+    blocks: Vec<VecGroup<E>>,
+}
+
+impl<E: Entity> Default for MemoryStorage<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: Entity> MemoryStorage<E> {
+    /// Creates an empty storage backend.
+    #[inline]
+    pub fn new() -> Self {
+        Self { blocks: Vec::new() }
+    }
+}
+
+impl<E: Entity> SupernodeStorage<E> for MemoryStorage<E> {
+    type Error = core::convert::Infallible;
+
+    fn store(&mut self, s: usize, values: GroupFor<E, &[E::Unit]>) -> Result<(), Self::Error> {
+        if self.blocks.len() <= s {
+            self.blocks.resize_with(s + 1, VecGroup::new);
+        }
+        self.blocks[s] = VecGroup::from_inner(E::faer_map(values, |values| values.to_vec()));
+        Ok(())
+    }
+
+    fn load(&mut self, s: usize, values: GroupFor<E, &mut [E::Unit]>) -> Result<(), Self::Error> {
+        let block = self.blocks[s].as_slice().into_inner();
+        E::faer_map(E::faer_zip(values, block), |(dst, src)| {
+            dst.copy_from_slice(src)
+        });
+        Ok(())
+    }
+}
+
+/// Storage backend that writes the supernodes to a file.
+///
+/// The supernodes are appended to the file the first time they're stored, and overwritten in
+/// place afterwards. The file should be opened for both reading and writing, and is typically a
+/// temporary file on a fast local disk.
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct FileStorage {
+    file: std::fs::File,
+    // byte offset and length of each stored supernode
+    blocks: Vec<Option<(u64, usize)>>,
+    end: u64,
+}
+
+#[cfg(feature = "std")]
+impl FileStorage {
+    /// Creates a storage backend that writes to `file`, starting at its beginning.
+    #[inline]
+    pub fn new(file: std::fs::File) -> Self {
+        Self {
+            file,
+            blocks: Vec::new(),
+            end: 0,
+        }
+    }
+
+    /// Returns the underlying file.
+    #[inline]
+    pub fn into_inner(self) -> std::fs::File {
+        self.file
+    }
+}
+
+#[cfg(feature = "std")]
+impl<E: Entity> SupernodeStorage<E> for FileStorage {
+    type Error = std::io::Error;
+
+    fn store(&mut self, s: usize, values: GroupFor<E, &[E::Unit]>) -> Result<(), Self::Error> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let (len, _) =
+            E::faer_map_with_context(0usize, E::faer_copy(&values), &mut |len, values| {
+                (len + core::mem::size_of_val(values), ())
+            });
+        if self.blocks.len() <= s {
+            self.blocks.resize(s + 1, None);
+        }
+        let offset = match self.blocks[s] {
+            Some((offset, old_len)) if old_len == len => offset,
+            _ => {
+                let offset = self.end;
+                self.end += len as u64;
+                self.blocks[s] = Some((offset, len));
+                offset
+            }
+        };
+
+        let file = &mut self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        E::faer_map_with_context(
+            Ok(()),
+            values,
+            &mut |result: std::io::Result<()>, values| {
+                (
+                    result.and_then(|()| file.write_all(bytemuck::cast_slice(values))),
+                    (),
+                )
+            },
+        )
+        .0
+    }
+
+    fn load(&mut self, s: usize, values: GroupFor<E, &mut [E::Unit]>) -> Result<(), Self::Error> {
+        use std::io::{Read, Seek, SeekFrom};
+
+        let (offset, _) = self.blocks.get(s).copied().flatten().ok_or_else(|| {
+            std::io::Error::new(std::io::ErrorKind::NotFound, "supernode was not stored")
+        })?;
+
+        let file = &mut self.file;
+        file.seek(SeekFrom::Start(offset))?;
+        E::faer_map_with_context(
+            Ok(()),
+            values,
+            &mut |result: std::io::Result<()>, values| {
+                (
+                    result.and_then(|()| file.read_exact(bytemuck::cast_slice_mut(values))),
+                    (),
+                )
+            },
+        )
+        .0
+    }
+}
+
+/// Out-of-core Cholesky factorization error.
+#[derive(Debug)]
+pub enum OutOfCoreError<S> {
+    /// Generic sparse error.
+    Generic(FaerError),
+    /// The symbolic factorization is not supernodal.
+    NotSupernodal,
+    /// The memory budget is smaller than the minimum required by the factorization.
+    BudgetTooSmall {
+        /// Minimum memory budget in bytes, as returned by [`min_memory_budget`].
+        required: usize,
+    },
+    /// Matrix is not positive definite.
+    NotPositiveDefinite,
+    /// Error returned by the storage backend.
+    Storage(S),
+}
+
+impl<S: core::fmt::Debug> core::fmt::Display for OutOfCoreError<S> {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl<S: core::fmt::Debug> std::error::Error for OutOfCoreError<S> {}
+
+impl<S> From<FaerError> for OutOfCoreError<S> {
+    #[inline]
+    fn from(value: FaerError) -> Self {
+        Self::Generic(value)
+    }
+}
+
+fn supernodal<I: Index>(symbolic: &SymbolicCholesky<I>) -> Option<&SymbolicSupernodalCholesky<I>> {
+    match symbolic.raw() {
+        SymbolicCholeskyRaw::Simplicial(_) => None,
+        SymbolicCholeskyRaw::Supernodal(this) => Some(this),
+    }
+}
+
+#[inline]
+fn supernode_ncols<I: Index>(symbolic: &SymbolicSupernodalCholesky<I>, s: usize) -> usize {
+    symbolic.supernode_end()[s].zx() - symbolic.supernode_begin()[s].zx()
+}
+
+#[inline]
+fn block_len<I: Index>(symbolic: &SymbolicSupernodalCholesky<I>, s: usize) -> usize {
+    let col_ptr_val = symbolic.col_ptrs_for_values();
+    (col_ptr_val[s + 1] - col_ptr_val[s]).zx()
+}
+
+/// Returns the minimum memory budget in bytes for computing an out-of-core factorization with
+/// the given symbolic structure, or `None` if it is not supernodal.
+///
+/// This is twice the size of the largest supernode, one of them holding the supernode being
+/// computed, and the other one a previous supernode read from the storage backend.
+pub fn min_memory_budget<I: Index, E: Entity>(symbolic: &SymbolicCholesky<I>) -> Option<usize> {
+    let symbolic = supernodal(symbolic)?;
+    let max_len = (0..symbolic.n_supernodes())
+        .map(|s| block_len(symbolic, s))
+        .max()
+        .unwrap_or(0);
+    Some(max_len.saturating_mul(2 * core::mem::size_of::<E>()))
+}
+
+// workspace for the updates between supernodes, and the dense factorization of each supernode
+fn factorize_req<I: Index, E: Entity>(
+    symbolic: &SymbolicSupernodalCholesky<I>,
+    parallelism: Parallelism,
+) -> Result<StackReq, SizeOverflow> {
+    let mut max_ncols = 0usize;
+    let mut max_pattern_len = 0usize;
+    for s in 0..symbolic.n_supernodes() {
+        max_ncols = Ord::max(max_ncols, supernode_ncols(symbolic, s));
+        max_pattern_len = Ord::max(max_pattern_len, symbolic.supernode(s).pattern().len());
+    }
+    StackReq::try_any_of([
+        temp_mat_req::<E>(max_pattern_len, max_ncols)?,
+        cholesky_in_place_req::<E>(max_ncols, parallelism, Default::default())?,
+    ])
+}
+
+fn factorize_supernodal<I: Index, E: ComplexField, S: SupernodeStorage<E>>(
+    storage: &mut S,
+    memory_budget: usize,
+    A_lower: SparseColMatRef<'_, I, E>,
+    regularization: LltRegularization<E>,
+    symbolic: &SymbolicSupernodalCholesky<I>,
+    parallelism: Parallelism,
+    stack: PodStack<'_>,
+) -> Result<(), OutOfCoreError<S::Error>> {
+    let mut stack = stack;
+    let n_supernodes = symbolic.n_supernodes();
+    let n = symbolic.nrows();
+
+    let unit_size = core::mem::size_of::<E>();
+    let max_len = (0..n_supernodes)
+        .map(|s| block_len(symbolic, s))
+        .max()
+        .unwrap_or(0);
+    let required = max_len.saturating_mul(2 * unit_size);
+    if memory_budget < required {
+        return Err(OutOfCoreError::BudgetTooSmall { required });
+    }
+    // number of values that the cache can hold
+    let cache_capacity = (memory_budget - required) / Ord::max(unit_size, 1);
+
+    let mut col_to_super = alloc::vec![0usize; n];
+    for s in 0..n_supernodes {
+        let start = symbolic.supernode(s).start();
+        col_to_super[start..start + supernode_ncols(symbolic, s)].fill(s);
+    }
+
+    // each computed supernode `d` that still has to update later supernodes is in the linked
+    // list of the supernode containing `pattern(d)[pos[d]]`, its next row that wasn't used for an
+    // update yet. the list of `s` starts at `head[s]`, and continues with `next[d]`
+    let mut head = alloc::vec![NONE; n_supernodes];
+    let mut next = alloc::vec![NONE; n_supernodes];
+    let mut pos = alloc::vec![0usize; n_supernodes];
+
+    let mut cache = (0..n_supernodes)
+        .map(|_| None)
+        .collect::<Vec<Option<VecGroup<E>>>>();
+    let mut cache_order = VecDeque::new();
+    let mut cache_len = 0usize;
+
+    let mut current = VecGroup::<E>::new();
+    let mut loaded = VecGroup::<E>::new();
+    loaded
+        .try_reserve_exact(max_len)
+        .map_err(|_| FaerError::OutOfMemory)?;
+
+    // mapping from global indices to local
+    let mut global_to_local = alloc::vec![NONE; n];
+
+    for s in 0..n_supernodes {
+        let s_start = symbolic.supernode(s).start();
+        let s_pattern = symbolic.supernode(s).pattern();
+        let s_ncols = supernode_ncols(symbolic, s);
+        let s_end = s_start + s_ncols;
+        let s_nrows = s_pattern.len() + s_ncols;
+        let s_len = block_len(symbolic, s);
+
+        for (i, &row) in s_pattern.iter().enumerate() {
+            global_to_local[row.zx()] = i + s_ncols;
+        }
+
+        current.clear();
+        current
+            .try_reserve_exact(s_len)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        current.resize(s_len, E::faer_zero().faer_into_units());
+        let mut Ls = crate::mat::from_column_major_slice_mut::<'_, E>(
+            current.as_slice_mut().into_inner(),
+            s_nrows,
+            s_ncols,
+        );
+
+        for j in s_start..s_end {
+            let j_shifted = j - s_start;
+            for (i, val) in zip(
+                A_lower.row_indices_of_col(j),
+                crate::utils::slice::SliceGroup::<'_, E>::new(A_lower.values_of_col(j))
+                    .into_ref_iter(),
+            ) {
+                if i < j {
+                    continue;
+                }
+                let ix = if i >= s_end {
+                    global_to_local[i]
+                } else {
+                    i - s_start
+                };
+                Ls.write(ix, j_shifted, Ls.read(ix, j_shifted).faer_add(val.read()));
+            }
+        }
+
+        let mut d = core::mem::replace(&mut head[s], NONE);
+        while d != NONE {
+            let d_next = next[d];
+
+            let d_pattern = symbolic.supernode(d).pattern();
+            let d_ncols = supernode_ncols(symbolic, d);
+            let d_nrows = d_pattern.len() + d_ncols;
+
+            let d_pattern_start = pos[d];
+            let d_pattern_mid_len =
+                d_pattern[d_pattern_start..].partition_point(|&i| i.zx() < s_end);
+            let d_pattern_mid = d_pattern_start + d_pattern_mid_len;
+
+            let Ld_values = match &cache[d] {
+                Some(block) => block.as_slice(),
+                None => {
+                    loaded.clear();
+                    loaded.resize(block_len(symbolic, d), E::faer_zero().faer_into_units());
+                    <S as SupernodeStorage<E>>::load(
+                        storage,
+                        d,
+                        loaded.as_slice_mut().into_inner(),
+                    )
+                    .map_err(OutOfCoreError::Storage)?;
+                    loaded.as_slice()
+                }
+            };
+            let Ld = crate::mat::from_column_major_slice::<'_, E>(
+                Ld_values.into_inner(),
+                d_nrows,
+                d_ncols,
+            );
+
+            let (_, Ld_mid_bot) = Ld.split_at_row(d_ncols);
+            let (_, Ld_mid_bot) = Ld_mid_bot.split_at_row(d_pattern_start);
+            let (Ld_mid, Ld_bot) = Ld_mid_bot.split_at_row(d_pattern_mid_len);
+
+            let (tmp, _) =
+                temp_mat_uninit::<E>(Ld_mid_bot.nrows(), d_pattern_mid_len, stack.rb_mut());
+            let (mut tmp_top, mut tmp_bot) = tmp.split_at_row_mut(d_pattern_mid_len);
+
+            use crate::linalg::{matmul, matmul::triangular};
+            triangular::matmul(
+                tmp_top.rb_mut(),
+                triangular::BlockStructure::TriangularLower,
+                Ld_mid,
+                triangular::BlockStructure::Rectangular,
+                Ld_mid.adjoint(),
+                triangular::BlockStructure::Rectangular,
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            matmul::matmul(
+                tmp_bot.rb_mut(),
+                Ld_bot,
+                Ld_mid.adjoint(),
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+
+            let d_pattern_mid_rows = &d_pattern[d_pattern_start..d_pattern_mid];
+            for (j_idx, j) in d_pattern_mid_rows.iter().enumerate() {
+                let j_s = j.zx() - s_start;
+                for (i_idx, i) in d_pattern_mid_rows.iter().enumerate().skip(j_idx) {
+                    let i_s = i.zx() - s_start;
+                    Ls.write(
+                        i_s,
+                        j_s,
+                        Ls.read(i_s, j_s).faer_sub(tmp_top.read(i_idx, j_idx)),
+                    );
+                }
+                for (i_idx, i) in d_pattern[d_pattern_mid..].iter().enumerate() {
+                    let i_s = global_to_local[i.zx()];
+                    Ls.write(
+                        i_s,
+                        j_s,
+                        Ls.read(i_s, j_s).faer_sub(tmp_bot.read(i_idx, j_idx)),
+                    );
+                }
+            }
+
+            // move `d` to the list of the next supernode that it updates, or drop it from the
+            // cache if it has no updates left
+            pos[d] = d_pattern_mid;
+            if d_pattern_mid < d_pattern.len() {
+                let target = col_to_super[d_pattern[d_pattern_mid].zx()];
+                next[d] = head[target];
+                head[target] = d;
+            } else if cache[d].take().is_some() {
+                cache_len -= block_len(symbolic, d);
+            }
+
+            d = d_next;
+        }
+
+        let (mut Ls_top, mut Ls_bot) = Ls.rb_mut().split_at_row_mut(s_ncols);
+        cholesky_in_place(
+            Ls_top.rb_mut(),
+            regularization,
+            parallelism,
+            stack.rb_mut(),
+            Default::default(),
+        )
+        .map_err(|_| OutOfCoreError::NotPositiveDefinite)?;
+        crate::linalg::triangular_solve::solve_lower_triangular_in_place(
+            Ls_top.rb().conjugate(),
+            Ls_bot.rb_mut().transpose_mut(),
+            parallelism,
+        );
+
+        for &row in s_pattern {
+            global_to_local[row.zx()] = NONE;
+        }
+
+        <S as SupernodeStorage<E>>::store(storage, s, current.as_slice().into_inner())
+            .map_err(OutOfCoreError::Storage)?;
+
+        if let Some(&first) = s_pattern.first() {
+            let target = col_to_super[first.zx()];
+            pos[s] = 0;
+            next[s] = head[target];
+            head[target] = s;
+
+            if s_len <= cache_capacity {
+                while cache_len + s_len > cache_capacity {
+                    // the queue may contain supernodes that were already dropped from the cache
+                    let oldest = cache_order.pop_front().unwrap();
+                    if cache[oldest].take().is_some() {
+                        cache_len -= block_len(symbolic, oldest);
+                    }
+                }
+                cache[s] = Some(core::mem::take(&mut current));
+                cache_order.push_back(s);
+                cache_len += s_len;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Computes a numerical LLT factorization of $A$ within a memory budget of `memory_budget` bytes,
+/// storing the supernodes of the factor in `storage`.
+///
+/// The symbolic factorization must be supernodal, which can be ensured with
+/// [`SupernodalThreshold::FORCE_SUPERNODAL`](super::SupernodalThreshold::FORCE_SUPERNODAL). See
+/// the [module level documentation](self) for the contents of the memory budget.
+///
+/// A mutable reference to a storage backend can be passed instead of the backend itself, so that
+/// it can be reused after an error.
+///
+/// # Panics
+/// Panics if `A` is not a square matrix with the same dimension as `symbolic`.
+#[track_caller]
+pub fn factorize_numeric_llt_out_of_core<'a, I: Index, E: ComplexField, S: SupernodeStorage<E>>(
+    symbolic: &'a SymbolicCholesky<I>,
+    storage: S,
+    memory_budget: usize,
+    A: SparseColMatRef<'_, I, E>,
+    side: Side,
+    regularization: LltRegularization<E>,
+    parallelism: Parallelism,
+) -> Result<OutOfCoreLlt<'a, I, E, S>, OutOfCoreError<S::Error>> {
+    let n = symbolic.nrows();
+    assert!(all(A.nrows() == n, A.ncols() == n));
+    let this = supernodal(symbolic).ok_or(OutOfCoreError::NotSupernodal)?;
+    let mut storage = storage;
+
+    let A_nnz = A.compute_nnz();
+    let req = || -> Result<StackReq, SizeOverflow> {
+        StackReq::try_all_of([
+            make_raw_req::<E>(A_nnz)?,
+            StackReq::try_new::<I>(n + 1)?,
+            StackReq::try_new::<I>(A_nnz)?,
+            StackReq::try_any_of([
+                StackReq::try_new::<I>(n)?,
+                factorize_req::<I, E>(this, parallelism)?,
+            ])?,
+        ])
+    };
+    let mut mem =
+        GlobalPodBuffer::try_new(req().map_err(FaerError::from)?).map_err(FaerError::from)?;
+
+    ghost::with_size(n, |N| {
+        let A = ghost::SparseColMatRef::new(A, N, N);
+        let perm = ghost::PermRef::new(symbolic.perm(), N);
+
+        let stack = PodStack::new(&mut mem);
+        let (mut new_values, stack) = make_raw::<E>(A_nnz, stack);
+        let (new_col_ptr, stack) = stack.make_raw::<I>(n + 1);
+        let (new_row_ind, mut stack) = stack.make_raw::<I>(A_nnz);
+
+        let A = unsafe {
+            ghost_permute_hermitian_unsorted(
+                new_values.rb_mut(),
+                new_col_ptr,
+                new_row_ind,
+                A,
+                perm,
+                side,
+                Side::Lower,
+                false,
+                stack.rb_mut(),
+            )
+        };
+
+        factorize_supernodal(
+            &mut storage,
+            memory_budget,
+            A.into_inner().into_const(),
+            regularization,
+            this,
+            parallelism,
+            stack,
+        )
+    })?;
+
+    Ok(OutOfCoreLlt {
+        symbolic,
+        storage,
+        __marker: PhantomData,
+    })
+}
+
+/// Out-of-core supernodal LLT factorization, whose supernodes are kept in a storage backend.
+///
+/// See [`factorize_numeric_llt_out_of_core`].
+#[derive(Debug)]
+pub struct OutOfCoreLlt<'a, I: Index, E: Entity, S> {
+    symbolic: &'a SymbolicCholesky<I>,
+    storage: S,
+    __marker: PhantomData<E>,
+}
+
+impl<'a, I: Index, E: ComplexField, S: SupernodeStorage<E>> OutOfCoreLlt<'a, I, E, S> {
+    /// Returns the symbolic part of the Cholesky factor.
+    #[inline]
+    pub fn symbolic(&self) -> &'a SymbolicCholesky<I> {
+        self.symbolic
+    }
+
+    /// Returns the storage backend containing the supernodes of the factor.
+    #[inline]
+    pub fn storage(&self) -> &S {
+        &self.storage
+    }
+
+    /// Returns the storage backend containing the supernodes of the factor.
+    #[inline]
+    pub fn into_storage(self) -> S {
+        self.storage
+    }
+
+    /// Solves the equation $\text{Op}(A) x = \text{rhs}$ and stores the result in `rhs`, where
+    /// $\text{Op}$ is either the identity or the conjugate, depending on the value of `conj`.
+    ///
+    /// Each supernode is read twice from the storage backend, once for the forward substitution
+    /// and once for the backward substitution, so multiple right-hand sides should be solved
+    /// together.
+    ///
+    /// # Panics
+    /// Panics if `rhs.nrows() != self.symbolic().nrows()`.
+    #[track_caller]
+    pub fn solve_in_place_with_conj(
+        &mut self,
+        conj: Conj,
+        rhs: MatMut<'_, E>,
+        parallelism: Parallelism,
+    ) -> Result<(), S::Error> {
+        let symbolic = supernodal(self.symbolic).unwrap();
+        let n = symbolic.nrows();
+        assert!(rhs.nrows() == n);
+
+        let mut rhs = rhs;
+        let k = rhs.ncols();
+        let (fwd, inv) = self.symbolic.perm().arrays();
+        let mut x = Mat::<E>::from_fn(n, k, |i, j| rhs.read(fwd[i].zx(), j));
+
+        let max_pattern_len = (0..symbolic.n_supernodes())
+            .map(|s| symbolic.supernode(s).pattern().len())
+            .max()
+            .unwrap_or(0);
+        let mut tmp = Mat::<E>::zeros(max_pattern_len, k);
+        let mut block = VecGroup::<E>::new();
+
+        let mut load = |block: &mut VecGroup<E>, s: usize| {
+            block.clear();
+            block.resize(block_len(symbolic, s), E::faer_zero().faer_into_units());
+            <S as SupernodeStorage<E>>::load(
+                &mut self.storage,
+                s,
+                block.as_slice_mut().into_inner(),
+            )
+        };
+
+        for s in 0..symbolic.n_supernodes() {
+            load(&mut block, s)?;
+            let size = supernode_ncols(symbolic, s);
+            let s_start = symbolic.supernode(s).start();
+            let s_pattern = symbolic.supernode(s).pattern();
+            let Ls = crate::mat::from_column_major_slice::<'_, E>(
+                block.as_slice().into_inner(),
+                size + s_pattern.len(),
+                size,
+            );
+            let (Ls_top, Ls_bot) = Ls.split_at_row(size);
+            let mut x_top = x.as_mut().subrows_mut(s_start, size);
+            crate::linalg::triangular_solve::solve_lower_triangular_in_place_with_conj(
+                Ls_top,
+                conj,
+                x_top.rb_mut(),
+                parallelism,
+            );
+
+            let mut tmp = tmp.as_mut().subrows_mut(0, s_pattern.len());
+            crate::linalg::matmul::matmul_with_conj(
+                tmp.rb_mut(),
+                Ls_bot,
+                conj,
+                x_top.rb(),
+                Conj::No,
+                None,
+                E::faer_one(),
+                parallelism,
+            );
+            for j in 0..k {
+                for (idx, i) in s_pattern.iter().enumerate() {
+                    let i = i.zx();
+                    x.write(i, j, x.read(i, j).faer_sub(tmp.read(idx, j)));
+                }
+            }
+        }
+
+        for s in (0..symbolic.n_supernodes()).rev() {
+            load(&mut block, s)?;
+            let size = supernode_ncols(symbolic, s);
+            let s_start = symbolic.supernode(s).start();
+            let s_pattern = symbolic.supernode(s).pattern();
+            let Ls = crate::mat::from_column_major_slice::<'_, E>(
+                block.as_slice().into_inner(),
+                size + s_pattern.len(),
+                size,
+            );
+            let (Ls_top, Ls_bot) = Ls.split_at_row(size);
+
+            let mut tmp = tmp.as_mut().subrows_mut(0, s_pattern.len());
+            for j in 0..k {
+                for (idx, i) in s_pattern.iter().enumerate() {
+                    tmp.write(idx, j, x.read(i.zx(), j));
+                }
+            }
+
+            let mut x_top = x.as_mut().subrows_mut(s_start, size);
+            crate::linalg::matmul::matmul_with_conj(
+                x_top.rb_mut(),
+                Ls_bot.transpose(),
+                conj.compose(Conj::Yes),
+                tmp.rb(),
+                Conj::No,
+                Some(E::faer_one()),
+                E::faer_one().faer_neg(),
+                parallelism,
+            );
+            crate::linalg::triangular_solve::solve_upper_triangular_in_place_with_conj(
+                Ls_top.transpose(),
+                conj.compose(Conj::Yes),
+                x_top.rb_mut(),
+                parallelism,
+            );
+        }
+
+        for j in 0..k {
+            for (i, inv) in inv.iter().enumerate() {
+                rhs.write(i, j, x.read(inv.zx(), j));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        assert,
+        complex_native::c64,
+        sparse::{
+            linalg::{
+                cholesky::{factorize_symbolic_cholesky, CholeskySymbolicParams},
+                SupernodalThreshold,
+            },
+            SparseColMat,
+        },
+    };
+
+    // upper triangular part of a shifted 5-point laplacian on a `k x k` grid
+    fn laplacian(k: usize) -> SparseColMat<usize, c64> {
+        let mut triplets = Vec::new();
+        for y in 0..k {
+            for x in 0..k {
+                let v = y * k + x;
+                triplets.push((v, v, c64::new(4.5, 0.0)));
+                if x > 0 {
+                    triplets.push((v - 1, v, c64::new(-1.0, 0.25)));
+                }
+                if y > 0 {
+                    triplets.push((v - k, v, c64::new(-1.0, -0.5)));
+                }
+            }
+        }
+        SparseColMat::try_new_from_triplets(k * k, k * k, &triplets).unwrap()
+    }
+
+    fn check_solve<S: SupernodeStorage<c64>>(
+        llt: &mut OutOfCoreLlt<'_, usize, c64, S>,
+        A_upper: &SparseColMat<usize, c64>,
+    ) where
+        S::Error: core::fmt::Debug,
+    {
+        let n = A_upper.nrows();
+        let mut A = A_upper.to_dense();
+        for j in 0..n {
+            for i in j + 1..n {
+                A.write(i, j, A.read(j, i).conj());
+            }
+        }
+
+        let rhs = Mat::<c64>::from_fn(n, 2, |i, j| c64::new(i as f64, j as f64 - 1.0));
+        for conj in [Conj::No, Conj::Yes] {
+            let mut x = rhs.clone();
+            llt.solve_in_place_with_conj(conj, x.as_mut(), Parallelism::None)
+                .unwrap();
+            let A = if conj == Conj::Yes {
+                A.as_ref().conjugate().to_owned()
+            } else {
+                A.clone()
+            };
+            assert!((&A * &x - &rhs).norm_max() < 1e-10);
+        }
+    }
+
+    #[test]
+    fn test_out_of_core_llt() {
+        let A_upper = laplacian(12);
+        let symbolic = factorize_symbolic_cholesky(
+            A_upper.symbolic(),
+            Side::Upper,
+            CholeskySymbolicParams {
+                supernodal_flop_ratio_threshold: SupernodalThreshold::FORCE_SUPERNODAL,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let min_budget = min_memory_budget::<usize, c64>(&symbolic).unwrap();
+
+        let mut storage = MemoryStorage::<c64>::new();
+        let err = factorize_numeric_llt_out_of_core(
+            &symbolic,
+            &mut storage,
+            min_budget - 1,
+            A_upper.as_ref(),
+            Side::Upper,
+            Default::default(),
+            Parallelism::None,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            OutOfCoreError::BudgetTooSmall { required } if required == min_budget
+        ));
+
+        // without a cache, then with a cache holding the whole factor
+        for budget in [min_budget, usize::MAX] {
+            let mut llt = factorize_numeric_llt_out_of_core(
+                &symbolic,
+                MemoryStorage::<c64>::new(),
+                budget,
+                A_upper.as_ref(),
+                Side::Upper,
+                Default::default(),
+                Parallelism::None,
+            )
+            .unwrap();
+            check_solve(&mut llt, &A_upper);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_out_of_core_llt_file() {
+        let A_upper = laplacian(10);
+        let symbolic = factorize_symbolic_cholesky(
+            A_upper.symbolic(),
+            Side::Upper,
+            CholeskySymbolicParams {
+                supernodal_flop_ratio_threshold: SupernodalThreshold::FORCE_SUPERNODAL,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let min_budget = min_memory_budget::<usize, c64>(&symbolic).unwrap();
+
+        let path =
+            std::env::temp_dir().join(format!("faer-out-of-core-test-{}.bin", std::process::id()));
+        let file = std::fs::OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&path)
+            .unwrap();
+
+        let mut llt = factorize_numeric_llt_out_of_core(
+            &symbolic,
+            FileStorage::new(file),
+            min_budget,
+            A_upper.as_ref(),
+            Side::Upper,
+            Default::default(),
+            Parallelism::None,
+        )
+        .unwrap();
+        check_solve(&mut llt, &A_upper);
+
+        drop(llt);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_out_of_core_simplicial() {
+        let A_upper = laplacian(3);
+        let symbolic = factorize_symbolic_cholesky(
+            A_upper.symbolic(),
+            Side::Upper,
+            CholeskySymbolicParams {
+                supernodal_flop_ratio_threshold: SupernodalThreshold::FORCE_SIMPLICIAL,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert!(min_memory_budget::<usize, c64>(&symbolic).is_none());
+        let err = factorize_numeric_llt_out_of_core(
+            &symbolic,
+            MemoryStorage::<c64>::new(),
+            usize::MAX,
+            A_upper.as_ref(),
+            Side::Upper,
+            Default::default(),
+            Parallelism::None,
+        )
+        .unwrap_err();
+        assert!(matches!(err, OutOfCoreError::NotSupernodal));
+    }
+}
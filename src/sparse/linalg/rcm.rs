@@ -0,0 +1,405 @@
+//! Reverse Cuthill-McKee ordering.
+//!
+//! The reverse Cuthill-McKee ordering is a bandwidth reducing permutation for symmetric sparsity
+//! patterns. It is usually less effective than [`amd`](super::amd) at reducing the fill-in of the
+//! Cholesky factor, but it clusters the nonzeros of the matrix close to the diagonal, which
+//! benefits banded and profile solvers, as well as the memory access pattern of sparse
+//! matrix-vector products.
+
+use super::{mem::NONE, FaerError, Index, SignedIndex, SymbolicSparseColMatRef};
+use crate::assert;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+
+/// Computes the size and alignment of required workspace for computing the reverse
+/// Cuthill-McKee ordering of a matrix with dimension `n` and `nnz` stored entries.
+pub fn order_req<I: Index>(n: usize, nnz: usize) -> Result<StackReq, SizeOverflow> {
+    let n_req = StackReq::try_new::<I>(n)?;
+    StackReq::try_all_of([
+        // adj_ptr
+        StackReq::try_new::<I>(n.checked_add(1).ok_or(SizeOverflow)?)?,
+        // adj_ind
+        StackReq::try_new::<I>(nnz.checked_mul(2).ok_or(SizeOverflow)?)?,
+        // mark
+        n_req,
+        // queue
+        n_req,
+    ])
+}
+
+/// Stores the sparsity pattern of `A + A.T`, without the diagonal and duplicate entries, in
+/// `adj_ptr` and `adj_ind`, and returns the number of stored entries.
+///
+/// `adj_ptr` must have length `n + 1`, `adj_ind` must have length at least `2 * nnz(A)` and
+/// `mark` must have length `n`.
+pub(super) fn symmetric_adjacency<I: Index>(
+    adj_ptr: &mut [I],
+    adj_ind: &mut [I],
+    mark: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+) -> usize {
+    let n = A.ncols();
+    let I = I::truncate;
+    let zero = I(0);
+    let one = I(1);
+
+    adj_ptr.fill(zero);
+    for j in 0..n {
+        for i in A.row_indices_of_col(j) {
+            if i != j {
+                adj_ptr[i + 1] += one;
+                adj_ptr[j + 1] += one;
+            }
+        }
+    }
+    for j in 0..n {
+        let prev = adj_ptr[j];
+        adj_ptr[j + 1] += prev;
+    }
+    mark.copy_from_slice(&adj_ptr[..n]);
+    for j in 0..n {
+        for i in A.row_indices_of_col(j) {
+            if i != j {
+                adj_ind[mark[i].zx()] = I(j);
+                mark[i] += one;
+                adj_ind[mark[j].zx()] = I(i);
+                mark[j] += one;
+            }
+        }
+    }
+
+    // remove duplicate entries, compacting the adjacency structure in place
+    mark.fill(I(NONE));
+    let mut pos = 0usize;
+    for j in 0..n {
+        let start = adj_ptr[j].zx();
+        let end = adj_ptr[j + 1].zx();
+        adj_ptr[j] = I(pos);
+        for idx in start..end {
+            let i = adj_ind[idx].zx();
+            if mark[i] != I(j) {
+                mark[i] = I(j);
+                adj_ind[pos] = I(i);
+                pos += 1;
+            }
+        }
+    }
+    adj_ptr[n] = I(pos);
+    pos
+}
+
+/// Breadth first search from `root`, restricted to the vertices for which `active` returns
+/// `true`. The visited vertices are stored in `queue`, and `on_level` is called with the range of
+/// each level in `queue`.
+///
+/// Returns the number of visited vertices. `mark` must be filled with zeros, and is restored
+/// before returning.
+pub(super) fn bfs<I: Index>(
+    root: usize,
+    adj_ptr: &[I],
+    adj_ind: &[I],
+    active: impl Fn(usize) -> bool,
+    mark: &mut [I],
+    queue: &mut [I],
+    mut on_level: impl FnMut(usize, usize),
+) -> usize {
+    let zero = I::truncate(0);
+    let one = I::truncate(1);
+
+    queue[0] = I::truncate(root);
+    mark[root] = one;
+
+    let mut head = 0usize;
+    let mut tail = 1usize;
+    while head < tail {
+        let level_end = tail;
+        on_level(head, level_end);
+
+        while head < level_end {
+            let v = queue[head].zx();
+            head += 1;
+            for &u in &adj_ind[adj_ptr[v].zx()..adj_ptr[v + 1].zx()] {
+                let u = u.zx();
+                if mark[u] == zero && active(u) {
+                    mark[u] = one;
+                    queue[tail] = I::truncate(u);
+                    tail += 1;
+                }
+            }
+        }
+    }
+
+    for &v in &queue[..tail] {
+        mark[v.zx()] = zero;
+    }
+    tail
+}
+
+/// Finds a pseudo-peripheral vertex of the connected component of `start`, restricted to the
+/// vertices for which `active` returns `true`, using the George-Liu algorithm.
+pub(super) fn pseudo_peripheral_vertex<I: Index>(
+    start: usize,
+    adj_ptr: &[I],
+    adj_ind: &[I],
+    active: impl Copy + Fn(usize) -> bool,
+    mark: &mut [I],
+    queue: &mut [I],
+) -> usize {
+    let degree = |v: usize| adj_ptr[v + 1].zx() - adj_ptr[v].zx();
+
+    // start from a vertex of minimum degree in the connected component
+    let size = bfs(start, adj_ptr, adj_ind, active, mark, queue, |_, _| {});
+    let mut root = start;
+    for &v in &queue[..size] {
+        let v = v.zx();
+        if (degree(v), v) < (degree(root), root) {
+            root = v;
+        }
+    }
+
+    let mut n_levels = 0usize;
+    let mut last_level = (0usize, 0usize);
+    bfs(
+        root,
+        adj_ptr,
+        adj_ind,
+        active,
+        mark,
+        queue,
+        |first, last| {
+            n_levels += 1;
+            last_level = (first, last);
+        },
+    );
+
+    loop {
+        let mut candidate = queue[last_level.0].zx();
+        for &v in &queue[last_level.0..last_level.1] {
+            let v = v.zx();
+            if (degree(v), v) < (degree(candidate), candidate) {
+                candidate = v;
+            }
+        }
+
+        let mut candidate_levels = 0usize;
+        let mut candidate_last_level = (0usize, 0usize);
+        bfs(
+            candidate,
+            adj_ptr,
+            adj_ind,
+            active,
+            mark,
+            queue,
+            |first, last| {
+                candidate_levels += 1;
+                candidate_last_level = (first, last);
+            },
+        );
+
+        if candidate_levels > n_levels {
+            root = candidate;
+            n_levels = candidate_levels;
+            last_level = candidate_last_level;
+        } else {
+            return root;
+        }
+    }
+}
+
+/// Computes the reverse Cuthill-McKee ordering of the sparsity pattern of `A + A.T`, ignoring
+/// the diagonal, and stores it in `perm` and `perm_inv`, such that `perm[new] = old` and
+/// `perm_inv[old] = new`.
+///
+/// Each connected component is numbered separately, starting from a pseudo-peripheral vertex
+/// found by the George-Liu algorithm. The matrix may store only its upper or lower triangular
+/// part, and its row indices do not need to be sorted.
+pub fn order<I: Index>(
+    perm: &mut [I],
+    perm_inv: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+    stack: PodStack<'_>,
+) -> Result<(), FaerError> {
+    let n = perm.len();
+    assert!(all(A.nrows() == n, A.ncols() == n, perm_inv.len() == n));
+    if n == 0 {
+        return Ok(());
+    }
+
+    let nnz = A.compute_nnz();
+    if nnz.checked_mul(2).ok_or(FaerError::IndexOverflow)? >= I::Signed::MAX.zx() {
+        return Err(FaerError::IndexOverflow);
+    }
+
+    let I = I::truncate;
+    let zero = I(0);
+    let one = I(1);
+
+    let (adj_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (adj_ind, stack) = stack.make_raw::<I>(2 * nnz);
+    let (mark, stack) = stack.make_raw::<I>(n);
+    let (queue, _) = stack.make_raw::<I>(n);
+
+    let adj_nnz = symmetric_adjacency(adj_ptr, adj_ind, mark, A);
+    let adj_ptr = &*adj_ptr;
+    let adj_ind = &adj_ind[..adj_nnz];
+    let degree = |v: usize| adj_ptr[v + 1].zx() - adj_ptr[v].zx();
+
+    // `perm_inv` marks the numbered vertices until the final pass
+    mark.fill(zero);
+    perm_inv.fill(zero);
+
+    let mut next = 0usize;
+    for start in 0..n {
+        if perm_inv[start] != zero {
+            continue;
+        }
+
+        let root = {
+            let perm_inv = &*perm_inv;
+            pseudo_peripheral_vertex(
+                start,
+                adj_ptr,
+                adj_ind,
+                |v| perm_inv[v] == zero,
+                mark,
+                queue,
+            )
+        };
+
+        // cuthill-mckee numbering of the component, with the neighbors of each vertex visited by
+        // increasing degree
+        perm[next] = I(root);
+        perm_inv[root] = one;
+        let mut head = next;
+        let mut tail = next + 1;
+        while head < tail {
+            let v = perm[head].zx();
+            head += 1;
+
+            let level_start = tail;
+            for &u in &adj_ind[adj_ptr[v].zx()..adj_ptr[v + 1].zx()] {
+                if perm_inv[u.zx()] == zero {
+                    perm_inv[u.zx()] = one;
+                    perm[tail] = u;
+                    tail += 1;
+                }
+            }
+            perm[level_start..tail].sort_unstable_by_key(|&u| (degree(u.zx()), u.zx()));
+        }
+        next = tail;
+    }
+    debug_assert!(next == n);
+
+    perm.reverse();
+    for (k, &v) in perm.iter().enumerate() {
+        perm_inv[v.zx()] = I(k);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert;
+    use dyn_stack::GlobalPodBuffer;
+
+    fn bandwidth<I: Index>(A: SymbolicSparseColMatRef<'_, I>, perm_inv: Option<&[I]>) -> usize {
+        let p = |i: usize| perm_inv.map(|p| p[i].zx()).unwrap_or(i);
+        let mut bw = 0;
+        for j in 0..A.ncols() {
+            for i in A.row_indices_of_col(j) {
+                bw = Ord::max(bw, p(i).abs_diff(p(j)));
+            }
+        }
+        bw
+    }
+
+    fn compute_rcm<I: Index>(A: SymbolicSparseColMatRef<'_, I>) -> (Vec<I>, Vec<I>) {
+        let n = A.nrows();
+        let mut perm = vec![I::truncate(0); n];
+        let mut perm_inv = vec![I::truncate(0); n];
+        order(
+            &mut perm,
+            &mut perm_inv,
+            A,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                order_req::<I>(n, A.compute_nnz()).unwrap(),
+            )),
+        )
+        .unwrap();
+        (perm, perm_inv)
+    }
+
+    fn test_rcm_grid<I: Index>() {
+        let I = I::truncate;
+
+        // upper triangular part of the 5-point laplacian on a `k x k` grid, with the vertices
+        // numbered in a scrambled order
+        let k = 12;
+        let n = k * k;
+        let scramble = |v: usize| (v * 37 + 11) % n;
+
+        let mut col_ptr = vec![I(0)];
+        let mut row_ind = vec![];
+        let mut cols = vec![vec![]; n];
+        for y in 0..k {
+            for x in 0..k {
+                let v = scramble(y * k + x);
+                cols[v].push(v);
+                for (dx, dy) in [(1, 0), (0, 1)] {
+                    if x + dx < k && y + dy < k {
+                        let u = scramble((y + dy) * k + (x + dx));
+                        let (i, j) = if u < v { (u, v) } else { (v, u) };
+                        cols[j].push(i);
+                    }
+                }
+            }
+        }
+        for col in &cols {
+            // keep the row indices unsorted, and add a duplicate entry
+            row_ind.extend(col.iter().rev().map(|&i| I(i)));
+            if let Some(&i) = col.get(1) {
+                row_ind.push(I(i));
+            }
+            col_ptr.push(I(row_ind.len()));
+        }
+
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+        let (perm, perm_inv) = compute_rcm(A);
+
+        let mut seen = vec![false; n];
+        for k in 0..n {
+            assert!(perm_inv[perm[k].zx()].zx() == k);
+            assert!(!seen[perm[k].zx()]);
+            seen[perm[k].zx()] = true;
+        }
+
+        assert!(bandwidth(A, None) > 4 * k);
+        assert!(bandwidth(A, Some(&perm_inv)) <= 2 * k);
+    }
+
+    fn test_rcm_components<I: Index>() {
+        let I = I::truncate;
+
+        // two disjoint paths, 0 - 2 - 4 and 1 - 3, plus an isolated vertex 5
+        let n = 6;
+        let col_ptr = [0, 0, 0, 1, 2, 3, 3].map(I);
+        let row_ind = [0, 1, 2].map(I);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+        let (_, perm_inv) = compute_rcm(A);
+
+        assert!(bandwidth(A, Some(&perm_inv)) == 1);
+    }
+
+    fn test_rcm_empty<I: Index>() {
+        let col_ptr = [I::truncate(0)];
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(0, 0, &col_ptr, None, &[]);
+        let (perm, perm_inv) = compute_rcm(A);
+        assert!(perm.is_empty());
+        assert!(perm_inv.is_empty());
+    }
+
+    monomorphize_test!(test_rcm_grid);
+    monomorphize_test!(test_rcm_components);
+    monomorphize_test!(test_rcm_empty);
+}
@@ -205,6 +205,10 @@ pub struct SymbolicCholesky<I: Index> {
 pub struct Cholesky<I: Index, E: Entity> {
     symbolic: SymbolicCholesky<I>,
     values: VecGroup<E>,
+    // `Conj::Yes` if the stored factors are those of the conjugate of the input matrix, which is
+    // the case when it was provided in row-major format
+    conj: Conj,
+    workspace: Workspace,
 }
 
 /// Reference-counted sparse symbolic QR factorization.
@@ -218,6 +222,7 @@ pub struct Qr<I: Index, E: Entity> {
     symbolic: SymbolicQr<I>,
     indices: alloc::vec::Vec<I>,
     values: VecGroup<E>,
+    workspace: Workspace,
 }
 
 /// Reference-counted sparse symbolic LU factorization.
@@ -230,6 +235,52 @@ pub struct SymbolicLu<I: Index> {
 pub struct Lu<I: Index, E: Entity> {
     symbolic: SymbolicLu<I>,
     numeric: super::lu::NumericLu<I, E>,
+    // `true` if the stored factors are those of the transpose of the input matrix, which is the
+    // case when it was provided in row-major format
+    transposed: bool,
+    workspace: Workspace,
+}
+
+/// Workspace memory kept alive across numeric refactorizations.
+///
+/// Cloning a workspace doesn't copy its memory, which is allocated again on first use.
+#[derive(Default)]
+struct Workspace {
+    mem: Option<(StackReq, GlobalPodBuffer)>,
+}
+
+impl Clone for Workspace {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl core::fmt::Debug for Workspace {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Workspace")
+            .field("req", &self.mem.as_ref().map(|(req, _)| *req))
+            .finish()
+    }
+}
+
+impl Workspace {
+    /// Returns a stack satisfying `req`, only allocating if the current memory is too small.
+    fn stack(&mut self, req: Result<StackReq, SizeOverflow>) -> Result<PodStack<'_>, FaerError> {
+        let req = req?;
+        let fits = match &self.mem {
+            Some((cap, _)) => {
+                cap.size_bytes() >= req.size_bytes() && cap.align_bytes() >= req.align_bytes()
+            }
+            None => false,
+        };
+        if !fits {
+            // release the old memory before allocating the new one
+            self.mem = None;
+            self.mem = Some((req, GlobalPodBuffer::try_new(req)?));
+        }
+        Ok(PodStack::new(&mut self.mem.as_mut().unwrap().1))
+    }
 }
 
 impl<I: Index> Clone for SymbolicCholesky<I> {
@@ -271,6 +322,18 @@ impl<I: Index> SymbolicCholesky<I> {
             )?),
         })
     }
+
+    /// Returns the symbolic Cholesky factorization of the input matrix, provided in row-major
+    /// format.
+    ///
+    /// Only the provided side is accessed.
+    #[track_caller]
+    pub fn try_new_row_major(
+        mat: SymbolicSparseRowMatRef<'_, I>,
+        side: Side,
+    ) -> Result<Self, FaerError> {
+        Self::try_new(mat.transpose(), flip_side(side))
+    }
 }
 impl<I: Index> SymbolicQr<I> {
     /// Returns the symbolic QR factorization of the input matrix.
@@ -295,6 +358,21 @@ impl<I: Index> SymbolicLu<I> {
             )?),
         })
     }
+
+    /// Returns the symbolic LU factorization of the input matrix, provided in row-major format.
+    #[track_caller]
+    pub fn try_new_row_major(mat: SymbolicSparseRowMatRef<'_, I>) -> Result<Self, FaerError> {
+        Self::try_new(mat.transpose())
+    }
+}
+
+// the lower half of a row-major matrix is the upper half of its column-major transpose
+#[inline]
+fn flip_side(side: Side) -> Side {
+    match side {
+        Side::Lower => Side::Upper,
+        Side::Upper => Side::Lower,
+    }
 }
 
 impl<I: Index, E: ComplexField> Cholesky<I, E> {
@@ -314,21 +392,282 @@ impl<I: Index, E: ComplexField> Cholesky<I, E> {
             .try_reserve_exact(len_values)
             .map_err(|_| FaerError::OutOfMemory)?;
         values.resize(len_values, E::faer_zero().faer_into_units());
+        let mut this = Self {
+            symbolic,
+            values,
+            conj: Conj::No,
+            workspace: Workspace::default(),
+        };
+        this.try_refactor(mat, side)?;
+        Ok(this)
+    }
+
+    /// Recomputes the Cholesky factorization in place for a new input matrix with the same
+    /// sparsity pattern as the original one used to construct the symbolic factorization.
+    ///
+    /// The storage of the factors, as well as the workspace memory, is reused from the previous
+    /// factorization, which makes this suitable for repeatedly solving systems whose values
+    /// change while their structure stays the same.
+    ///
+    /// Only the provided side is accessed.
+    ///
+    /// # Note
+    /// If an error is returned, the contents of the factorization are unspecified, and it should
+    /// be refactorized before it's used again.
+    ///
+    /// # Example
+    /// ```
+    /// use faer::{
+    ///     mat,
+    ///     sparse::{
+    ///         linalg::solvers::{Cholesky, SpSolver, SymbolicCholesky},
+    ///         SparseColMat,
+    ///     },
+    ///     Side,
+    /// };
+    ///
+    /// let triplets = |shift: f64| {
+    ///     [
+    ///         (0, 0, 4.0 + shift),
+    ///         (0, 1, 1.0),
+    ///         (1, 1, 3.0 + shift),
+    ///         (1, 2, 1.0),
+    ///         (2, 2, 2.0 + shift),
+    ///     ]
+    /// };
+    /// let A = SparseColMat::<usize, f64>::try_new_from_triplets(3, 3, &triplets(0.0)).unwrap();
+    /// let symbolic = SymbolicCholesky::try_new(A.symbolic(), Side::Upper).unwrap();
+    /// let mut llt = Cholesky::try_new_with_symbolic(symbolic, A.as_ref(), Side::Upper).unwrap();
+    ///
+    /// for step in 1..4 {
+    ///     let A = SparseColMat::<usize, f64>::try_new_from_triplets(3, 3, &triplets(step as f64))
+    ///         .unwrap();
+    ///     llt.try_refactor(A.as_ref(), Side::Upper).unwrap();
+    ///
+    ///     let rhs = mat![[1.0], [2.0], [3.0]];
+    ///     let mut x = rhs.clone();
+    ///     llt.solve_in_place(&mut x);
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn try_refactor(
+        &mut self,
+        mat: SparseColMatRef<'_, I, E>,
+        side: Side,
+    ) -> Result<(), CholeskyError> {
         let parallelism = get_global_parallelism();
-        symbolic.inner.factorize_numeric_llt::<E>(
-            values.as_slice_mut().into_inner(),
+        let symbolic = &*self.symbolic.inner;
+        let stack = self
+            .workspace
+            .stack(symbolic.factorize_numeric_llt_req::<E>(parallelism))?;
+        self.conj = Conj::No;
+        symbolic.factorize_numeric_llt::<E>(
+            self.values.as_slice_mut().into_inner(),
             mat,
             side,
             Default::default(),
             parallelism,
+            stack,
+        )?;
+        Ok(())
+    }
+
+    /// Recomputes the Cholesky factorization in place for a new input matrix, provided in
+    /// row-major format, with the same sparsity pattern as the original one used to construct
+    /// the symbolic factorization with [`SymbolicCholesky::try_new_row_major`].
+    ///
+    /// See [`Self::try_refactor`] for more details.
+    #[track_caller]
+    pub fn try_refactor_row_major(
+        &mut self,
+        mat: SparseRowMatRef<'_, I, E>,
+        side: Side,
+    ) -> Result<(), CholeskyError> {
+        self.try_refactor(mat.transpose(), flip_side(side))?;
+        self.conj = Conj::Yes;
+        Ok(())
+    }
+
+    /// Returns the Cholesky factorization of the input matrix, provided in row-major format, with
+    /// the same sparsity pattern as the original one used to construct the symbolic
+    /// factorization with [`SymbolicCholesky::try_new_row_major`].
+    ///
+    /// Only the provided side is accessed.
+    #[track_caller]
+    pub fn try_new_with_symbolic_row_major(
+        symbolic: SymbolicCholesky<I>,
+        mat: SparseRowMatRef<'_, I, E>,
+        side: Side,
+    ) -> Result<Self, CholeskyError> {
+        // the transpose of a hermitian matrix is its conjugate, so we factorize the conjugate of
+        // `mat` and conjugate the solves
+        let mut this = Self::try_new_with_symbolic(symbolic, mat.transpose(), flip_side(side))?;
+        this.conj = Conj::Yes;
+        Ok(this)
+    }
+
+    /// Replaces the factorization of $A$ with that of $A + WW^H$ or $A - WW^H$, depending on
+    /// `kind`, without refactorizing the matrix.
+    ///
+    /// The nonzero pattern of each column of `W` must be contained in the symbolic structure of
+    /// the factor. See [`super::cholesky::SymbolicCholesky::update_llt`] for more details.
+    ///
+    /// # Panics
+    /// Panics if `W.nrows()` is not equal to the dimension of the factorized matrix.
+    #[track_caller]
+    pub fn update(
+        &mut self,
+        W: SparseColMatRef<'_, I, E>,
+        kind: super::cholesky::UpdateKind,
+    ) -> Result<(), super::CholeskyUpdateError> {
+        let symbolic = &*self.symbolic.inner;
+        symbolic.update_impl(
+            self.values.as_slice_mut().into_inner(),
+            W,
+            kind,
+            self.conj,
+            true,
             PodStack::new(&mut GlobalPodBuffer::new(
-                symbolic
-                    .inner
-                    .factorize_numeric_llt_req::<E>(parallelism)
-                    .map_err(|_| FaerError::OutOfMemory)?,
+                symbolic.update_req::<E>().unwrap(),
             )),
-        )?;
-        Ok(Self { symbolic, values })
+        )
+    }
+
+    /// Replaces the factorization of $A$ with that of the matrix obtained by replacing its `k`-th
+    /// row and column with those of the identity.
+    ///
+    /// See [`super::cholesky::SymbolicCholesky::delete_row_llt`] for more details.
+    ///
+    /// # Panics
+    /// Panics if `k` is greater than or equal to the dimension of the factorized matrix.
+    #[track_caller]
+    pub fn delete_row(&mut self, k: usize) -> Result<(), super::CholeskyUpdateError> {
+        let symbolic = &*self.symbolic.inner;
+        symbolic.delete_row_impl::<E>(
+            self.values.as_slice_mut().into_inner(),
+            k,
+            true,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                symbolic.update_req::<E>().unwrap(),
+            )),
+        )
+    }
+
+    /// Replaces the factorization of $A$, whose `k`-th row and column are those of the identity,
+    /// with that of the matrix obtained by replacing them with `col` and its adjoint.
+    ///
+    /// See [`super::cholesky::SymbolicCholesky::add_row_llt`] for the requirements on `col`.
+    ///
+    /// # Panics
+    /// Panics if `k` is greater than or equal to the dimension `n` of the factorized matrix, or
+    /// if `col` doesn't have the shape `(n, 1)`.
+    #[track_caller]
+    pub fn add_row(
+        &mut self,
+        k: usize,
+        col: SparseColMatRef<'_, I, E>,
+    ) -> Result<(), super::CholeskyUpdateError> {
+        let symbolic = &*self.symbolic.inner;
+        symbolic.add_row_impl(
+            self.values.as_slice_mut().into_inner(),
+            k,
+            col,
+            self.conj,
+            true,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                symbolic.update_req::<E>().unwrap(),
+            )),
+        )
+    }
+
+    /// Returns an estimate of the condition number in the 1-norm of the factorized matrix, given
+    /// its 1-norm (see [`SparseColMatRef::induced_norm_l1`]).
+    ///
+    /// # Note
+    /// When only one side of the matrix was provided for the factorization, `norm1` must still
+    /// be the norm of the full hermitian matrix.
+    ///
+    /// The estimate only requires a few sparse triangular solves with the factors, and the matrix
+    /// is never densified. See [`crate::linalg::cond_est::cond1_est`] for more details.
+    #[track_caller]
+    pub fn cond1_est(&self, norm1: E::Real) -> E::Real {
+        crate::linalg::cond_est::cond1_est(self, norm1)
+    }
+
+    /// Returns an estimate of the reciprocal of the condition number in the 1-norm of the
+    /// factorized matrix, given its 1-norm.
+    ///
+    /// Unlike [`Self::cond1_est`], this is zero rather than infinite for numerically singular
+    /// matrices. See [`crate::linalg::cond_est::rcond1_est`] for more details.
+    #[track_caller]
+    pub fn rcond1_est(&self, norm1: E::Real) -> E::Real {
+        crate::linalg::cond_est::rcond1_est(self, norm1)
+    }
+
+    /// Returns the fill-reducing permutation of the factorization.
+    #[inline]
+    pub fn perm(&self) -> PermRef<'_, I> {
+        self.symbolic.inner.perm()
+    }
+
+    /// Returns a copy of the $L$ factor of the factorization, with sorted row indices.
+    ///
+    /// The factor satisfies `A[fwd[i], fwd[j]] == (L * L.adjoint())[i, j]`, where `fwd` is the
+    /// forward array of [`Self::perm`].
+    pub fn try_l_factor(&self) -> Result<SparseColMat<I, E>, FaerError> {
+        let L = self.llt_ref().try_l_factor()?;
+        if self.conj == Conj::Yes {
+            L.as_ref().conjugate().to_owned()
+        } else {
+            Ok(L)
+        }
+    }
+
+    #[inline]
+    fn llt_ref(&self) -> super::cholesky::LltRef<'_, I, E> {
+        super::cholesky::LltRef::<'_, I, E>::new(
+            &self.symbolic.inner,
+            self.values.as_slice().into_inner(),
+        )
+    }
+
+    /// Returns the entries of the inverse of the factorized matrix that lie in the sparsity
+    /// pattern of the factor, without forming the full inverse.
+    ///
+    /// See [`super::cholesky::LltRef::try_selected_inverse`] for more details.
+    pub fn try_selected_inverse(&self) -> Result<SparseColMat<I, E>, FaerError> {
+        let Z = self.llt_ref().try_selected_inverse()?;
+        // the inverse of the conjugate is the conjugate of the inverse
+        if self.conj == Conj::Yes {
+            Z.as_ref().conjugate().to_owned()
+        } else {
+            Ok(Z)
+        }
+    }
+
+    /// Returns the diagonal of the inverse of the factorized matrix, without forming the full
+    /// inverse.
+    ///
+    /// This is useful for example to compute the marginal variances of a Gaussian distribution
+    /// from its precision matrix.
+    pub fn try_inverse_diag(&self) -> Result<Col<E>, FaerError> {
+        // the diagonal of the inverse of a hermitian matrix is real, so it's unaffected by
+        // `self.conj`
+        self.llt_ref().try_inverse_diag()
+    }
+
+    /// Returns the logarithm of the determinant of the factorized matrix, which is positive.
+    ///
+    /// This is useful for example to compute the log-likelihood of a Gaussian distribution from
+    /// its precision matrix. See [`super::cholesky::LltRef::log_abs_det`] for more details.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn log_abs_det(&self) -> E::Real
+    where
+        E::Real: num_traits::Float,
+    {
+        // the determinant of a hermitian matrix is real, so it's unaffected by `self.conj`
+        self.llt_ref().log_abs_det()
     }
 }
 
@@ -352,24 +691,36 @@ impl<I: Index, E: ComplexField> Qr<I, E> {
             .map_err(|_| FaerError::OutOfMemory)?;
         values.resize(len_values, E::faer_zero().faer_into_units());
         indices.resize(len_indices, I::truncate(0));
+        let mut this = Self {
+            symbolic,
+            indices,
+            values,
+            workspace: Workspace::default(),
+        };
+        this.try_refactor(mat)?;
+        Ok(this)
+    }
+
+    /// Recomputes the QR factorization in place for a new input matrix with the same sparsity
+    /// pattern as the original one used to construct the symbolic factorization.
+    ///
+    /// The storage of the factors, as well as the workspace memory, is reused from the previous
+    /// factorization.
+    #[track_caller]
+    pub fn try_refactor(&mut self, mat: SparseColMatRef<'_, I, E>) -> Result<(), FaerError> {
         let parallelism = get_global_parallelism();
-        symbolic.inner.factorize_numeric_qr::<E>(
-            &mut indices,
-            values.as_slice_mut().into_inner(),
+        let symbolic = &*self.symbolic.inner;
+        let stack = self
+            .workspace
+            .stack(symbolic.factorize_numeric_qr_req::<E>(parallelism))?;
+        symbolic.factorize_numeric_qr::<E>(
+            &mut self.indices,
+            self.values.as_slice_mut().into_inner(),
             mat,
             parallelism,
-            PodStack::new(&mut GlobalPodBuffer::new(
-                symbolic
-                    .inner
-                    .factorize_numeric_qr_req::<E>(parallelism)
-                    .map_err(|_| FaerError::OutOfMemory)?,
-            )),
+            stack,
         );
-        Ok(Self {
-            symbolic,
-            indices,
-            values,
-        })
+        Ok(())
     }
 }
 
@@ -381,20 +732,154 @@ impl<I: Index, E: ComplexField> Lu<I, E> {
         symbolic: SymbolicLu<I>,
         mat: SparseColMatRef<'_, I, E>,
     ) -> Result<Self, super::LuError> {
-        let mut numeric = super::lu::NumericLu::new();
+        let mut this = Self {
+            symbolic,
+            numeric: super::lu::NumericLu::new(),
+            transposed: false,
+            workspace: Workspace::default(),
+        };
+        this.try_refactor(mat)?;
+        Ok(this)
+    }
+
+    /// Returns the LU factorization of the input matrix, provided in row-major format, with the
+    /// same sparsity pattern as the original one used to construct the symbolic factorization
+    /// with [`SymbolicLu::try_new_row_major`].
+    ///
+    /// # Note
+    /// The factorization is computed for the column-major transpose of `mat`, so the pivoting is
+    /// performed on the columns of `mat` rather than its rows.
+    #[track_caller]
+    pub fn try_new_with_symbolic_row_major(
+        symbolic: SymbolicLu<I>,
+        mat: SparseRowMatRef<'_, I, E>,
+    ) -> Result<Self, super::LuError> {
+        let mut this = Self::try_new_with_symbolic(symbolic, mat.transpose())?;
+        this.transposed = true;
+        Ok(this)
+    }
+
+    /// Recomputes the LU factorization in place for a new input matrix with the same sparsity
+    /// pattern as the original one used to construct the symbolic factorization.
+    ///
+    /// The storage of the factors, as well as the workspace memory, is reused from the previous
+    /// factorization, which makes this suitable for repeatedly solving systems whose values
+    /// change while their structure stays the same.
+    ///
+    /// # Note
+    /// If an error is returned, the contents of the factorization are unspecified, and it should
+    /// be refactorized before it's used again.
+    #[track_caller]
+    pub fn try_refactor(&mut self, mat: SparseColMatRef<'_, I, E>) -> Result<(), super::LuError> {
         let parallelism = get_global_parallelism();
-        symbolic.inner.factorize_numeric_lu::<E>(
-            &mut numeric,
-            mat,
-            parallelism,
-            PodStack::new(&mut GlobalPodBuffer::new(
-                symbolic
-                    .inner
-                    .factorize_numeric_lu_req::<E>(parallelism)
-                    .map_err(|_| FaerError::OutOfMemory)?,
-            )),
-        )?;
-        Ok(Self { symbolic, numeric })
+        let symbolic = &*self.symbolic.inner;
+        let stack = self
+            .workspace
+            .stack(symbolic.factorize_numeric_lu_req::<E>(parallelism))?;
+        self.transposed = false;
+        symbolic.factorize_numeric_lu::<E>(&mut self.numeric, mat, parallelism, stack)?;
+        Ok(())
+    }
+
+    /// Recomputes the LU factorization in place for a new input matrix, provided in row-major
+    /// format, with the same sparsity pattern as the original one used to construct the symbolic
+    /// factorization with [`SymbolicLu::try_new_row_major`].
+    ///
+    /// See [`Self::try_refactor`] and [`Self::try_new_with_symbolic_row_major`] for more details.
+    #[track_caller]
+    pub fn try_refactor_row_major(
+        &mut self,
+        mat: SparseRowMatRef<'_, I, E>,
+    ) -> Result<(), super::LuError> {
+        self.try_refactor(mat.transpose())?;
+        self.transposed = true;
+        Ok(())
+    }
+
+    /// Returns an estimate of the condition number in the 1-norm of the factorized matrix, given
+    /// its 1-norm (see [`SparseColMatRef::induced_norm_l1`]).
+    ///
+    /// The estimate only requires a few sparse triangular solves with the factors, and the matrix
+    /// is never densified. See [`crate::linalg::cond_est::cond1_est`] for more details.
+    #[track_caller]
+    pub fn cond1_est(&self, norm1: E::Real) -> E::Real {
+        crate::linalg::cond_est::cond1_est(self, norm1)
+    }
+
+    /// Returns an estimate of the reciprocal of the condition number in the 1-norm of the
+    /// factorized matrix, given its 1-norm.
+    ///
+    /// Unlike [`Self::cond1_est`], this is zero rather than infinite for numerically singular
+    /// matrices. See [`crate::linalg::cond_est::rcond1_est`] for more details.
+    #[track_caller]
+    pub fn rcond1_est(&self, norm1: E::Real) -> E::Real {
+        crate::linalg::cond_est::rcond1_est(self, norm1)
+    }
+
+    #[inline]
+    fn lu_ref(&self) -> super::lu::LuRef<'_, I, E> {
+        unsafe { super::lu::LuRef::<'_, I, E>::new_unchecked(&self.symbolic.inner, &self.numeric) }
+    }
+
+    /// Returns the row pivoting permutation of the factorization.
+    ///
+    /// # Note
+    /// If the factorization was computed from a row-major matrix, this is the permutation of the
+    /// rows of its transpose.
+    #[inline]
+    pub fn row_perm(&self) -> PermRef<'_, I> {
+        self.lu_ref().row_perm()
+    }
+
+    /// Returns the fill-reducing column permutation of the factorization.
+    ///
+    /// # Note
+    /// If the factorization was computed from a row-major matrix, this is the permutation of the
+    /// columns of its transpose.
+    #[inline]
+    pub fn col_perm(&self) -> PermRef<'_, I> {
+        self.lu_ref().col_perm()
+    }
+
+    /// Returns a copy of the $L$ factor of the factorization, with sorted row indices and an
+    /// explicit unit diagonal.
+    ///
+    /// The factors satisfy `A[row_fwd[i], col_fwd[j]] == (L * U)[i, j]`, where `row_fwd` and
+    /// `col_fwd` are the forward arrays of [`Self::row_perm`] and [`Self::col_perm`].
+    ///
+    /// # Note
+    /// If the factorization was computed from a row-major matrix, these are the factors of its
+    /// transpose.
+    pub fn try_l_factor(&self) -> Result<SparseColMat<I, E>, FaerError> {
+        self.lu_ref().try_l_factor()
+    }
+
+    /// Returns a copy of the $U$ factor of the factorization, with sorted row indices.
+    ///
+    /// See [`Self::try_l_factor`] for the relation between the factors and the input matrix.
+    pub fn try_u_factor(&self) -> Result<SparseColMat<I, E>, FaerError> {
+        self.lu_ref().try_u_factor()
+    }
+
+    /// Returns the logarithm of the absolute value of the determinant of the factorized matrix.
+    ///
+    /// See [`super::lu::LuRef::log_abs_det`] for more details.
+    #[cfg(feature = "std")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+    pub fn log_abs_det(&self) -> E::Real
+    where
+        E::Real: num_traits::Float,
+    {
+        // the determinant of the transpose is the same as that of the matrix
+        self.lu_ref().log_abs_det()
+    }
+
+    /// Returns the sign of the determinant of the factorized matrix, i.e., the determinant
+    /// divided by its absolute value, or zero if the matrix is singular.
+    ///
+    /// See [`super::lu::LuRef::det_sign`] for more details.
+    pub fn det_sign(&self) -> E {
+        self.lu_ref().det_sign()
     }
 }
 
@@ -417,7 +902,7 @@ impl<I: Index, E: ComplexField> SpSolverCore<E> for Cholesky<I, E> {
             self.values.as_slice().into_inner(),
         )
         .solve_in_place_with_conj(
-            conj,
+            conj.compose(self.conj),
             rhs,
             parallelism,
             PodStack::new(&mut GlobalPodBuffer::new(
@@ -438,7 +923,7 @@ impl<I: Index, E: ComplexField> SpSolverCore<E> for Cholesky<I, E> {
             self.values.as_slice().into_inner(),
         )
         .solve_in_place_with_conj(
-            conj.compose(Conj::Yes),
+            conj.compose(Conj::Yes).compose(self.conj),
             rhs,
             parallelism,
             PodStack::new(&mut GlobalPodBuffer::new(
@@ -502,6 +987,27 @@ impl<I: Index, E: ComplexField> SpSolverLstsqCore<E> for Qr<I, E> {
     }
 }
 
+impl<I: Index, E: ComplexField> Lu<I, E> {
+    #[track_caller]
+    fn solve_impl(&self, rhs: MatMut<'_, E>, conj: Conj, transpose: bool) {
+        let parallelism = get_global_parallelism();
+        let rhs_ncols = rhs.ncols();
+        let lu = self.lu_ref();
+        let stack = PodStack::new(&mut GlobalPodBuffer::new(
+            self.symbolic
+                .inner
+                .solve_in_place_req::<E>(rhs_ncols, parallelism)
+                .unwrap(),
+        ));
+        // a transposed factorization swaps the roles of the two solves
+        if transpose != self.transposed {
+            lu.solve_transpose_in_place_with_conj(conj, rhs, parallelism, stack);
+        } else {
+            lu.solve_in_place_with_conj(conj, rhs, parallelism, stack);
+        }
+    }
+}
+
 impl<I: Index, E: ComplexField> SpSolverCore<E> for Lu<I, E> {
     #[inline]
     fn nrows(&self) -> usize {
@@ -514,38 +1020,12 @@ impl<I: Index, E: ComplexField> SpSolverCore<E> for Lu<I, E> {
 
     #[track_caller]
     fn solve_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
-        let parallelism = get_global_parallelism();
-        let rhs_ncols = rhs.ncols();
-        unsafe { super::lu::LuRef::<'_, I, E>::new_unchecked(&self.symbolic.inner, &self.numeric) }
-            .solve_in_place_with_conj(
-                conj,
-                rhs,
-                parallelism,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    self.symbolic
-                        .inner
-                        .solve_in_place_req::<E>(rhs_ncols, parallelism)
-                        .unwrap(),
-                )),
-            );
+        self.solve_impl(rhs, conj, false);
     }
 
     #[track_caller]
     fn solve_transpose_in_place_with_conj_impl(&self, rhs: MatMut<'_, E>, conj: Conj) {
-        let parallelism = get_global_parallelism();
-        let rhs_ncols = rhs.ncols();
-        unsafe { super::lu::LuRef::<'_, I, E>::new_unchecked(&self.symbolic.inner, &self.numeric) }
-            .solve_transpose_in_place_with_conj(
-                conj,
-                rhs,
-                parallelism,
-                PodStack::new(&mut GlobalPodBuffer::new(
-                    self.symbolic
-                        .inner
-                        .solve_in_place_req::<E>(rhs_ncols, parallelism)
-                        .unwrap(),
-                )),
-            );
+        self.solve_impl(rhs, conj, true);
     }
 }
 
@@ -636,7 +1116,7 @@ impl<I: Index, E: ComplexField> SparseColMatRef<'_, I, E> {
 }
 
 impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
-    /// Assuming `self` is an upper triangular matrix, solves the equation `self * X = rhs`, and
+    /// Assuming `self` is a lower triangular matrix, solves the equation `self * X = rhs`, and
     /// stores the result in `rhs`.
     ///
     /// # Note
@@ -644,7 +1124,7 @@ impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
     /// the diagonal element is assumed to be the last stored element in each row.
     #[track_caller]
     pub fn sp_solve_lower_triangular_in_place(&self, mut rhs: impl ColBatchMut<E>) {
-        crate::sparse::linalg::triangular_solve::solve_upper_triangular_in_place(
+        crate::sparse::linalg::triangular_solve::solve_upper_triangular_transpose_in_place(
             self.transpose(),
             Conj::No,
             rhs.as_2d_mut(),
@@ -659,7 +1139,7 @@ impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
     /// the diagonal element is assumed to be the first stored element in each row.
     #[track_caller]
     pub fn sp_solve_upper_triangular_in_place(&self, mut rhs: impl ColBatchMut<E>) {
-        crate::sparse::linalg::triangular_solve::solve_lower_triangular_in_place(
+        crate::sparse::linalg::triangular_solve::solve_lower_triangular_transpose_in_place(
             self.transpose(),
             Conj::No,
             rhs.as_2d_mut(),
@@ -674,7 +1154,7 @@ impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
     /// the diagonal element is assumed to be the last stored element in each row.
     #[track_caller]
     pub fn sp_solve_unit_lower_triangular_in_place(&self, mut rhs: impl ColBatchMut<E>) {
-        crate::sparse::linalg::triangular_solve::solve_unit_upper_triangular_in_place(
+        crate::sparse::linalg::triangular_solve::solve_unit_upper_triangular_transpose_in_place(
             self.transpose(),
             Conj::No,
             rhs.as_2d_mut(),
@@ -689,7 +1169,7 @@ impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
     /// the diagonal element is assumed to be the first stored element in each row.
     #[track_caller]
     pub fn sp_solve_unit_upper_triangular_in_place(&self, mut rhs: impl ColBatchMut<E>) {
-        crate::sparse::linalg::triangular_solve::solve_unit_lower_triangular_in_place(
+        crate::sparse::linalg::triangular_solve::solve_unit_lower_triangular_transpose_in_place(
             self.transpose(),
             Conj::No,
             rhs.as_2d_mut(),
@@ -698,27 +1178,33 @@ impl<I: Index, E: ComplexField> SparseRowMatRef<'_, I, E> {
     }
 
     /// Returns the Cholesky decomposition of `self`. Only the provided side is accessed.
+    ///
+    /// The factorization is computed directly from the row-major storage, without converting
+    /// `self` to column-major format.
     #[track_caller]
     #[doc(alias = "sp_llt")]
     pub fn sp_cholesky(&self, side: Side) -> Result<Cholesky<I, E>, CholeskyError> {
-        let this = self.to_col_major()?;
-        let this = this.as_ref();
-        Cholesky::try_new_with_symbolic(
-            SymbolicCholesky::try_new(this.symbolic(), side)?,
-            this,
+        Cholesky::try_new_with_symbolic_row_major(
+            SymbolicCholesky::try_new_row_major(self.symbolic(), side)?,
+            *self,
             side,
         )
     }
 
-    /// Returns the LU decomposition of `self` with partial (row) pivoting.
+    /// Returns the LU decomposition of `self` with partial (column) pivoting.
+    ///
+    /// The factorization is computed directly from the row-major storage, without converting
+    /// `self` to column-major format. See [`Lu::try_new_with_symbolic_row_major`].
     #[track_caller]
     pub fn sp_lu(&self) -> Result<Lu<I, E>, LuError> {
-        let this = self.to_col_major()?;
-        let this = this.as_ref();
-        Lu::try_new_with_symbolic(SymbolicLu::try_new(this.symbolic())?, this)
+        Lu::try_new_with_symbolic_row_major(SymbolicLu::try_new_row_major(self.symbolic())?, *self)
     }
 
     /// Returns the QR decomposition of `self`.
+    ///
+    /// # Note
+    /// Unlike the Cholesky and LU decompositions, this requires a conversion of `self` to
+    /// column-major format.
     #[track_caller]
     pub fn sp_qr(&self) -> Result<Qr<I, E>, FaerError> {
         let this = self.to_col_major()?;
@@ -790,7 +1276,7 @@ impl<I: Index, E: ComplexField> SparseColMatMut<'_, I, E> {
 }
 
 impl<I: Index, E: ComplexField> SparseRowMatMut<'_, I, E> {
-    /// Assuming `self` is an upper triangular matrix, solves the equation `self * X = rhs`, and
+    /// Assuming `self` is a lower triangular matrix, solves the equation `self * X = rhs`, and
     /// stores the result in `rhs`.
     ///
     /// # Note
@@ -838,7 +1324,7 @@ impl<I: Index, E: ComplexField> SparseRowMatMut<'_, I, E> {
         self.as_ref().sp_cholesky(side)
     }
 
-    /// Returns the LU decomposition of `self` with partial (row) pivoting.
+    /// Returns the LU decomposition of `self` with partial (column) pivoting.
     #[track_caller]
     pub fn sp_lu(&self) -> Result<Lu<I, E>, LuError> {
         self.as_ref().sp_lu()
@@ -913,7 +1399,7 @@ impl<I: Index, E: ComplexField> SparseColMat<I, E> {
 }
 
 impl<I: Index, E: ComplexField> SparseRowMat<I, E> {
-    /// Assuming `self` is an upper triangular matrix, solves the equation `self * X = rhs`, and
+    /// Assuming `self` is a lower triangular matrix, solves the equation `self * X = rhs`, and
     /// stores the result in `rhs`.
     ///
     /// # Note
@@ -961,7 +1447,7 @@ impl<I: Index, E: ComplexField> SparseRowMat<I, E> {
         self.as_ref().sp_cholesky(side)
     }
 
-    /// Returns the LU decomposition of `self` with partial (row) pivoting.
+    /// Returns the LU decomposition of `self` with partial (column) pivoting.
     #[track_caller]
     pub fn sp_lu(&self) -> Result<Lu<I, E>, LuError> {
         self.as_ref().sp_lu()
@@ -973,3 +1459,288 @@ impl<I: Index, E: ComplexField> SparseRowMat<I, E> {
         self.as_ref().sp_qr()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, Mat};
+
+    fn rhs(n: usize) -> Mat<c64> {
+        Mat::from_fn(n, 2, |i, j| {
+            c64::new(i as f64 + 1.0, j as f64 - 0.5 * i as f64)
+        })
+    }
+
+    // residual of `A * X = B`, relative to the rhs
+    fn residual(A: MatRef<'_, c64>, X: MatRef<'_, c64>, B: MatRef<'_, c64>) -> f64 {
+        let AX = A * X;
+        (&AX - &B.to_owned()).norm_max() / B.norm_max()
+    }
+
+    #[test]
+    fn test_row_major_triangular_solve() {
+        let n = 6;
+        let mut lower = alloc::vec::Vec::new();
+        let mut upper = alloc::vec::Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                if (i + 2 * j) % 3 != 0 && i != j {
+                    continue;
+                }
+                let v = c64::new(
+                    if i == j {
+                        4.0
+                    } else {
+                        1.0 + (i + j) as f64 * 0.1
+                    },
+                    (i as f64 - j as f64) * 0.3,
+                );
+                if i >= j {
+                    lower.push((i, j, v));
+                }
+                if i <= j {
+                    upper.push((i, j, v));
+                }
+            }
+        }
+        let L = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &lower).unwrap();
+        let U = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &upper).unwrap();
+        let B = rhs(n);
+
+        let mut X = B.clone();
+        L.sp_solve_lower_triangular_in_place(X.as_mut());
+        assert!(residual(L.to_dense().as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+
+        let mut X = B.clone();
+        U.sp_solve_upper_triangular_in_place(X.as_mut());
+        assert!(residual(U.to_dense().as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+
+        let unit = |mut A: Mat<c64>| {
+            for i in 0..n {
+                A.write(i, i, c64::new(1.0, 0.0));
+            }
+            A
+        };
+
+        let mut X = B.clone();
+        L.sp_solve_unit_lower_triangular_in_place(X.as_mut());
+        assert!(residual(unit(L.to_dense()).as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+
+        let mut X = B.clone();
+        U.sp_solve_unit_upper_triangular_in_place(X.as_mut());
+        assert!(residual(unit(U.to_dense()).as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+    }
+
+    #[test]
+    fn test_row_major_cholesky() {
+        let n = 8;
+        let mut lower = alloc::vec::Vec::new();
+        let mut full = alloc::vec::Vec::new();
+        for i in 0..n {
+            full.push((i, i, c64::new(6.0, 0.0)));
+            lower.push((i, i, c64::new(6.0, 0.0)));
+            for j in 0..i {
+                if (i * j) % 3 == 1 || i == j + 1 {
+                    let v = c64::new(1.0 + j as f64 * 0.1, 0.5 - i as f64 * 0.1);
+                    lower.push((i, j, v));
+                    full.push((i, j, v));
+                    full.push((j, i, c64::new(v.re, -v.im)));
+                }
+            }
+        }
+        let A_lower = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &lower).unwrap();
+        let A_upper = A_lower.transpose().conjugate().to_row_major().unwrap();
+        let A = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &full).unwrap();
+        let A_dense = A.to_dense();
+        let B = rhs(n);
+
+        for (mat, side) in [
+            (A_lower.as_ref(), Side::Lower),
+            (A_upper.as_ref(), Side::Upper),
+            (A.as_ref(), Side::Lower),
+        ] {
+            let llt = mat.sp_cholesky(side).unwrap();
+
+            let X = llt.solve(&B);
+            assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+            let X = llt.solve_conj(&B);
+            assert!(
+                residual(
+                    A_dense.conjugate().to_owned().as_ref(),
+                    X.as_ref(),
+                    B.as_ref()
+                ) < 1e-12
+            );
+            let X = llt.solve_transpose(&B);
+            assert!(residual(A_dense.transpose(), X.as_ref(), B.as_ref()) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_row_major_lu() {
+        let n = 8;
+        let mut triplets = alloc::vec::Vec::new();
+        for i in 0..n {
+            for j in 0..n {
+                if i == j || (i + 3 * j) % 5 == 0 {
+                    let v = c64::new(
+                        if i == j { 1.0 } else { 2.0 + i as f64 * 0.2 },
+                        j as f64 * 0.1,
+                    );
+                    triplets.push((i, j, v));
+                }
+            }
+        }
+        let A = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &triplets).unwrap();
+        let A_dense = A.to_dense();
+        let B = rhs(n);
+
+        let lu = A.sp_lu().unwrap();
+
+        let X = lu.solve(&B);
+        assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-10);
+        let X = lu.solve_transpose(&B);
+        assert!(residual(A_dense.transpose(), X.as_ref(), B.as_ref()) < 1e-10);
+        let X = lu.solve_conj_transpose(&B);
+        assert!(
+            residual(
+                A_dense.adjoint().to_owned().as_ref(),
+                X.as_ref(),
+                B.as_ref()
+            ) < 1e-10
+        );
+    }
+    #[test]
+    fn test_refactor() {
+        let n = 8;
+        let triplets = |shift: f64| {
+            let mut triplets = alloc::vec::Vec::new();
+            for i in 0..n {
+                triplets.push((i, i, c64::new(12.0 + shift, 0.0)));
+                for j in 0..i {
+                    if (i * j) % 3 == 1 || i == j + 1 {
+                        let v = c64::new(1.0 + j as f64 * 0.1, 0.5 - i as f64 * 0.1 + shift);
+                        triplets.push((i, j, v));
+                        triplets.push((j, i, c64::new(v.re, -v.im)));
+                    }
+                }
+            }
+            triplets
+        };
+        let A = SparseColMat::<usize, c64>::try_new_from_triplets(n, n, &triplets(0.0)).unwrap();
+        let B = rhs(n);
+
+        let mut llt = Cholesky::try_new_with_symbolic(
+            SymbolicCholesky::try_new(A.symbolic(), Side::Lower).unwrap(),
+            A.as_ref(),
+            Side::Lower,
+        )
+        .unwrap();
+        let mut lu =
+            Lu::try_new_with_symbolic(SymbolicLu::try_new(A.symbolic()).unwrap(), A.as_ref())
+                .unwrap();
+        let mut qr =
+            Qr::try_new_with_symbolic(SymbolicQr::try_new(A.symbolic()).unwrap(), A.as_ref())
+                .unwrap();
+
+        for step in 1..4 {
+            let A = SparseColMat::<usize, c64>::try_new_from_triplets(
+                n,
+                n,
+                &triplets(step as f64 * 0.25),
+            )
+            .unwrap();
+            let A_dense = A.to_dense();
+
+            llt.try_refactor(A.as_ref(), Side::Lower).unwrap();
+            let X = llt.solve(&B);
+            assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+
+            lu.try_refactor(A.as_ref()).unwrap();
+            let X = lu.solve(&B);
+            assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-10);
+
+            qr.try_refactor(A.as_ref()).unwrap();
+            let X = qr.solve(&B);
+            assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-10);
+
+            // the row-major matrix is the adjoint of `A`, which is `A` itself
+            let A_row = A.to_row_major().unwrap();
+            llt.try_refactor_row_major(A_row.as_ref(), Side::Lower)
+                .unwrap();
+            let X = llt.solve(&B);
+            assert!(residual(A_dense.as_ref(), X.as_ref(), B.as_ref()) < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_cond_est() {
+        use crate::linalg::solvers::SolverCore;
+
+        let n = 8;
+        let mut triplets = alloc::vec::Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, c64::new(4.0 + i as f64, 0.0)));
+            for j in 0..i {
+                if (i * j) % 3 == 1 || i == j + 1 {
+                    let v = c64::new(1.0 + j as f64 * 0.1, 0.5 - i as f64 * 0.1);
+                    triplets.push((i, j, v));
+                    triplets.push((j, i, c64::new(v.re, -v.im)));
+                }
+            }
+        }
+        let A = SparseColMat::<usize, c64>::try_new_from_triplets(n, n, &triplets).unwrap();
+        let A_dense = A.to_dense();
+        let norm1 = A.induced_norm_l1();
+        let exact =
+            norm1 * crate::linalg::cond_est::norm1(A_dense.partial_piv_lu().inverse().as_ref());
+
+        let llt = A.sp_cholesky(Side::Lower).unwrap();
+        let lu = A.sp_lu().unwrap();
+        for (cond, rcond) in [
+            (llt.cond1_est(norm1), llt.rcond1_est(norm1)),
+            (lu.cond1_est(norm1), lu.rcond1_est(norm1)),
+        ] {
+            assert!(cond <= exact * (1.0 + 1e-8));
+            assert!(cond >= exact / 10.0);
+            assert!((cond * rcond - 1.0).abs() < 1e-8);
+        }
+    }
+
+    #[test]
+    fn test_export_factors() {
+        let n = 8;
+        let mut triplets = alloc::vec::Vec::new();
+        for i in 0..n {
+            triplets.push((i, i, c64::new(6.0, 0.0)));
+            for j in 0..i {
+                if (i * j) % 3 == 1 || i == j + 1 {
+                    let v = c64::new(1.0 + j as f64 * 0.1, 0.5 - i as f64 * 0.1);
+                    triplets.push((i, j, v));
+                    triplets.push((j, i, c64::new(v.re, -v.im)));
+                }
+            }
+        }
+        let A = SparseColMat::<usize, c64>::try_new_from_triplets(n, n, &triplets).unwrap();
+        let A_row = SparseRowMat::<usize, c64>::try_new_from_triplets(n, n, &triplets).unwrap();
+        let A_dense = A.to_dense();
+        let permuted = |row_fwd: &[usize], col_fwd: &[usize]| {
+            Mat::<c64>::from_fn(n, n, |i, j| A_dense.read(row_fwd[i], col_fwd[j]))
+        };
+
+        for llt in [
+            A.sp_cholesky(Side::Lower).unwrap(),
+            A_row.sp_cholesky(Side::Lower).unwrap(),
+        ] {
+            let (fwd, _) = llt.perm().arrays();
+            let L = llt.try_l_factor().unwrap().to_dense();
+            assert!((&L * L.adjoint() - permuted(fwd, fwd)).norm_max() < 1e-12);
+        }
+
+        let lu = A.sp_lu().unwrap();
+        let L = lu.try_l_factor().unwrap().to_dense();
+        let U = lu.try_u_factor().unwrap().to_dense();
+        let PAQ = permuted(lu.row_perm().arrays().0, lu.col_perm().arrays().0);
+        assert!((&L * &U - PAQ).norm_max() < 1e-12);
+    }
+}
@@ -0,0 +1,819 @@
+//! Structural analysis of sparse matrices.
+//!
+//! The functions in this module inspect the sparsity pattern, and optionally the values, of a
+//! matrix, which helps choose an appropriate ordering and factorization:
+//! - a structurally symmetric matrix is better ordered with [`amd`](super::amd) than with
+//!   [`colamd`](super::colamd), and a hermitian matrix can be factorized with the
+//!   [`cholesky`](super::cholesky) module instead of the [`lu`](super::lu) module,
+//! - a matrix with a small bandwidth or profile benefits from banded or skyline solvers,
+//! - a matrix with several connected components, or several diagonal blocks in its block
+//!   triangular form, can be factorized one block at a time.
+
+use super::{make_raw, make_raw_req, mem::NONE, Index, SparseColMatRef, SymbolicSparseColMatRef};
+use crate::{assert, utils::slice::SliceGroup, ComplexField, Conj, Entity};
+use core::iter::zip;
+use dyn_stack::{PodStack, SizeOverflow, StackReq};
+
+// stores the pattern of the transpose of `A` in `t_ptr` and `t_ind`, along with the position of
+// each of its entries in the values of `A` in `t_pos`
+fn transpose_pattern<I: Index>(
+    t_ptr: &mut [I],
+    t_ind: &mut [I],
+    t_pos: &mut [I],
+    cursor: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+) {
+    let I = I::truncate;
+    let m = A.nrows();
+
+    t_ptr.fill(I(0));
+    for j in 0..A.ncols() {
+        for i in A.row_indices_of_col(j) {
+            t_ptr[i + 1] += I(1);
+        }
+    }
+    for i in 0..m {
+        let prev = t_ptr[i];
+        t_ptr[i + 1] += prev;
+    }
+    cursor.copy_from_slice(&t_ptr[..m]);
+    for j in 0..A.ncols() {
+        for (p, i) in zip(A.col_range(j), A.row_indices_of_col(j)) {
+            let q = cursor[i].zx();
+            t_ind[q] = I(j);
+            t_pos[q] = I(p);
+            cursor[i] += I(1);
+        }
+    }
+}
+
+/// Computes the size and alignment of required workspace for checking whether a matrix with
+/// dimension `n` and `nnz` stored entries is structurally symmetric.
+pub fn structural_symmetry_req<I: Index>(n: usize, nnz: usize) -> Result<StackReq, SizeOverflow> {
+    let n_req = StackReq::try_new::<I>(n)?;
+    let nnz_req = StackReq::try_new::<I>(nnz)?;
+    StackReq::try_all_of([
+        // t_ptr
+        StackReq::try_new::<I>(n.checked_add(1).ok_or(SizeOverflow)?)?,
+        // t_ind
+        nnz_req,
+        // t_pos
+        nnz_req,
+        // mark
+        n_req,
+        // mark_t
+        n_req,
+    ])
+}
+
+/// Computes the size and alignment of required workspace for checking whether a matrix with
+/// dimension `n` and `nnz` stored entries is symmetric or hermitian.
+pub fn symmetry_req<I: Index, E: Entity>(n: usize, nnz: usize) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_all_of([
+        structural_symmetry_req::<I>(n, nnz)?,
+        make_raw_req::<E>(n)?,
+        make_raw_req::<E>(n)?,
+    ])
+}
+
+/// Returns `true` if the sparsity pattern of `A` is equal to that of its transpose.
+///
+/// Non-square matrices are never structurally symmetric. The row indices of `A` do not need to
+/// be sorted, and duplicate entries are allowed. Explicitly stored zeros are considered part of
+/// the pattern.
+pub fn is_structurally_symmetric<I: Index>(
+    A: SymbolicSparseColMatRef<'_, I>,
+    stack: PodStack<'_>,
+) -> bool {
+    let n = A.nrows();
+    if A.ncols() != n {
+        return false;
+    }
+    let nnz = A.compute_nnz();
+
+    let (t_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (t_ind, stack) = stack.make_raw::<I>(nnz);
+    let (t_pos, stack) = stack.make_raw::<I>(nnz);
+    let (mark, stack) = stack.make_raw::<I>(n);
+    let (mark_t, _) = stack.make_raw::<I>(n);
+
+    transpose_pattern(t_ptr, t_ind, t_pos, mark, A);
+    mark.fill(I::truncate(NONE));
+    mark_t.fill(I::truncate(NONE));
+
+    for j in 0..n {
+        let tag = I::truncate(j);
+
+        // number of distinct rows of the column `j` that weren't found in the row `j`
+        let mut count = 0usize;
+        for i in A.row_indices_of_col(j) {
+            if mark[i] != tag {
+                mark[i] = tag;
+                count += 1;
+            }
+        }
+        for &k in &t_ind[t_ptr[j].zx()..t_ptr[j + 1].zx()] {
+            let k = k.zx();
+            if mark[k] != tag {
+                return false;
+            }
+            if mark_t[k] != tag {
+                mark_t[k] = tag;
+                count -= 1;
+            }
+        }
+        if count != 0 {
+            return false;
+        }
+    }
+    true
+}
+
+fn is_self_adjoint_impl<I: Index, E: ComplexField>(
+    A: SparseColMatRef<'_, I, E>,
+    conj: Conj,
+    tol: E::Real,
+    stack: PodStack<'_>,
+) -> bool {
+    let n = A.nrows();
+    if A.ncols() != n {
+        return false;
+    }
+    let nnz = A.compute_nnz();
+
+    let (t_ptr, stack) = stack.make_raw::<I>(n + 1);
+    let (t_ind, stack) = stack.make_raw::<I>(nnz);
+    let (t_pos, stack) = stack.make_raw::<I>(nnz);
+    let (cursor, stack) = stack.make_raw::<I>(n);
+    let (mut x, stack) = make_raw::<E>(n, stack);
+    let (mut y, _) = make_raw::<E>(n, stack);
+
+    transpose_pattern(t_ptr, t_ind, t_pos, cursor, A.symbolic());
+    x.fill_zero();
+    y.fill_zero();
+
+    let values = SliceGroup::<'_, E>::new(A.values());
+    for j in 0..n {
+        let col_pos = A.col_range(j);
+        let col_ind = A.row_indices_of_col_raw(j);
+        let row_pos = &t_pos[t_ptr[j].zx()..t_ptr[j + 1].zx()];
+        let row_ind = &t_ind[t_ptr[j].zx()..t_ptr[j + 1].zx()];
+
+        // scatter the column `j` in `x` and the row `j` in `y`, summing the duplicate entries
+        for (p, &i) in zip(col_pos, col_ind) {
+            let i = i.zx();
+            x.write(i, x.read(i).faer_add(values.read(p)));
+        }
+        for (&p, &k) in zip(row_pos, row_ind) {
+            let a = values.read(p.zx());
+            let a = if conj == Conj::Yes { a.faer_conj() } else { a };
+            let k = k.zx();
+            y.write(k, y.read(k).faer_add(a));
+        }
+
+        // compare them on the union of both patterns
+        for &i in col_ind.iter().chain(row_ind) {
+            let i = i.zx();
+            if !(x.read(i).faer_sub(y.read(i)).faer_abs() <= tol) {
+                return false;
+            }
+        }
+        for &i in col_ind.iter().chain(row_ind) {
+            let i = i.zx();
+            x.write(i, E::faer_zero());
+            y.write(i, E::faer_zero());
+        }
+    }
+    true
+}
+
+/// Returns `true` if `A` is symmetric, i.e., if `abs(A[i, j] - A[j, i]) <= tol` for all `i` and
+/// `j`.
+///
+/// Non-square matrices are never symmetric. The row indices of `A` do not need to be sorted, and
+/// duplicate entries are summed.
+pub fn is_symmetric<I: Index, E: ComplexField>(
+    A: SparseColMatRef<'_, I, E>,
+    tol: E::Real,
+    stack: PodStack<'_>,
+) -> bool {
+    is_self_adjoint_impl(A, Conj::No, tol, stack)
+}
+
+/// Returns `true` if `A` is hermitian, i.e., if `abs(A[i, j] - conj(A[j, i])) <= tol` for all `i`
+/// and `j`.
+///
+/// Non-square matrices are never hermitian. The row indices of `A` do not need to be sorted, and
+/// duplicate entries are summed.
+pub fn is_hermitian<I: Index, E: ComplexField>(
+    A: SparseColMatRef<'_, I, E>,
+    tol: E::Real,
+    stack: PodStack<'_>,
+) -> bool {
+    is_self_adjoint_impl(A, Conj::Yes, tol, stack)
+}
+
+/// Returns the lower and upper bandwidths of `A`, i.e., the maximum of `i - j` and `j - i`
+/// respectively over its stored entries `(i, j)`.
+///
+/// A diagonal matrix has bandwidths `(0, 0)`, and a tridiagonal matrix has bandwidths `(1, 1)`.
+pub fn bandwidth<I: Index>(A: SymbolicSparseColMatRef<'_, I>) -> (usize, usize) {
+    let mut lower = 0usize;
+    let mut upper = 0usize;
+    for j in 0..A.ncols() {
+        for i in A.row_indices_of_col(j) {
+            if i > j {
+                lower = Ord::max(lower, i - j);
+            } else {
+                upper = Ord::max(upper, j - i);
+            }
+        }
+    }
+    (lower, upper)
+}
+
+/// Returns the profile of the upper triangular part of `A`, i.e., the sum over its columns `j` of
+/// `j - i`, where `i` is the smallest row index of the stored entries of the column `j`, or `j` if
+/// there are none above the diagonal.
+///
+/// This is the number of off-diagonal entries stored by a skyline (or envelope) solver for a
+/// symmetric matrix.
+pub fn profile<I: Index>(A: SymbolicSparseColMatRef<'_, I>) -> usize {
+    let mut profile = 0usize;
+    for j in 0..A.ncols() {
+        let first = A.row_indices_of_col(j).fold(j, Ord::min);
+        profile += j - first;
+    }
+    profile
+}
+
+/// Computes the size and alignment of required workspace for computing the connected components
+/// of a matrix with dimension `n`.
+pub fn connected_components_req<I: Index>(n: usize) -> Result<StackReq, SizeOverflow> {
+    StackReq::try_new::<I>(n)
+}
+
+/// Computes the connected components of the graph whose adjacency matrix has the sparsity pattern
+/// of `A + A.T`, and returns their number.
+///
+/// The index of the component containing the vertex `i` is stored in `component[i]`, with the
+/// components numbered by increasing smallest vertex. The matrix may store only its upper or
+/// lower triangular part, and its row indices do not need to be sorted.
+///
+/// # Panics
+/// Panics if `A` is not a square matrix with dimension `component.len()`.
+pub fn connected_components<I: Index>(
+    component: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+    stack: PodStack<'_>,
+) -> usize {
+    let n = component.len();
+    assert!(all(A.nrows() == n, A.ncols() == n));
+
+    // union-find forest, where the root of each tree is its smallest vertex
+    let (parent, _) = stack.make_raw::<I>(n);
+    for (i, p) in parent.iter_mut().enumerate() {
+        *p = I::truncate(i);
+    }
+    fn find<I: Index>(parent: &mut [I], mut i: usize) -> usize {
+        // path halving
+        while parent[i].zx() != i {
+            let grandparent = parent[parent[i].zx()];
+            parent[i] = grandparent;
+            i = grandparent.zx();
+        }
+        i
+    }
+
+    for j in 0..n {
+        for i in A.row_indices_of_col(j) {
+            let ri = find(parent, i);
+            let rj = find(parent, j);
+            if ri < rj {
+                parent[rj] = I::truncate(ri);
+            } else if rj < ri {
+                parent[ri] = I::truncate(rj);
+            }
+        }
+    }
+
+    let mut count = 0usize;
+    for i in 0..n {
+        let root = find(parent, i);
+        if root == i {
+            component[i] = I::truncate(count);
+            count += 1;
+        } else {
+            // the root is smaller than `i`, so its component was already numbered
+            component[i] = component[root];
+        }
+    }
+    count
+}
+
+/// Information about the block triangular form computed by [`block_triangular_form`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlockTriangularForm {
+    /// Number of diagonal blocks.
+    pub n_blocks: usize,
+    /// Structural rank of the matrix, i.e., the size of a maximum matching between its rows and
+    /// columns. The matrix is structurally singular if this is less than its dimension.
+    pub structural_rank: usize,
+}
+
+/// Computes the size and alignment of required workspace for computing the block triangular form
+/// of a matrix with dimension `n`.
+pub fn block_triangular_form_req<I: Index>(n: usize) -> Result<StackReq, SizeOverflow> {
+    let n_req = StackReq::try_new::<I>(n)?;
+    StackReq::try_all_of([n_req; 12])
+}
+
+/// Computes row and column permutations that put `A` in block upper triangular form, such that
+/// `A[row_perm[i], col_perm[j]]` is zero whenever the row `i` belongs to a later diagonal block
+/// than the column `j`.
+///
+/// The permutations are computed as in the Dulmage-Mendelsohn decomposition of square matrices:
+/// a maximum matching of the rows and columns is found with Duff's algorithm and placed on the
+/// diagonal, then the diagonal blocks are the strongly connected components of the resulting
+/// graph, found with Tarjan's algorithm. The diagonal blocks are irreducible, so the
+/// decomposition is the finest possible.
+///
+/// The `k`-th diagonal block contains the rows and columns with permuted indices in
+/// `block_ptr[k]..block_ptr[k + 1]`, for `k < n_blocks`. If the matrix is structurally singular,
+/// the unmatched rows and columns are paired arbitrarily, and some diagonal entries of the
+/// permuted matrix are structurally zero.
+///
+/// The row indices of `A` do not need to be sorted.
+///
+/// # Panics
+/// Panics if `A` is not a square matrix with dimension `n = row_perm.len()`, if `col_perm`
+/// doesn't have length `n`, or if `block_ptr` doesn't have length `n + 1`.
+pub fn block_triangular_form<I: Index>(
+    row_perm: &mut [I],
+    col_perm: &mut [I],
+    block_ptr: &mut [I],
+    A: SymbolicSparseColMatRef<'_, I>,
+    stack: PodStack<'_>,
+) -> BlockTriangularForm {
+    let n = row_perm.len();
+    assert!(all(
+        A.nrows() == n,
+        A.ncols() == n,
+        col_perm.len() == n,
+        block_ptr.len() == n + 1,
+    ));
+
+    let I = I::truncate;
+    let none = I(NONE);
+
+    let (row_match, stack) = stack.make_raw::<I>(n);
+    let (col_match, stack) = stack.make_raw::<I>(n);
+    let (visited, stack) = stack.make_raw::<I>(n);
+    let (cheap, stack) = stack.make_raw::<I>(n);
+    let (js, stack) = stack.make_raw::<I>(n);
+    let (is, stack) = stack.make_raw::<I>(n);
+    let (ps, stack) = stack.make_raw::<I>(n);
+
+    // maximum matching, using depth-first searches for augmenting paths with a cheap assignment
+    // lookahead. `row_match[i]` is the column matched to the row `i`, and `col_match[j]` the row
+    // matched to the column `j`
+    row_match.fill(none);
+    col_match.fill(none);
+    visited.fill(none);
+    cheap.fill(I(0));
+
+    let mut structural_rank = 0usize;
+    for k in 0..n {
+        let mut head = 0usize;
+        js[0] = I(k);
+        let mut found = false;
+
+        loop {
+            let j = js[head].zx();
+            let rows = A.row_indices_of_col_raw(j);
+
+            if visited[j] != I(k) {
+                visited[j] = I(k);
+
+                // rows before `cheap[j]` are already matched, and stay matched afterwards
+                let mut p = cheap[j].zx();
+                while p < rows.len() && row_match[rows[p].zx()] != none {
+                    p += 1;
+                }
+                if p < rows.len() {
+                    cheap[j] = I(p + 1);
+                    is[head] = rows[p];
+                    found = true;
+                    break;
+                }
+                cheap[j] = I(p);
+                ps[head] = I(0);
+            }
+
+            // continue the search through the column matched to the next row
+            let mut p = ps[head].zx();
+            let mut pushed = false;
+            while p < rows.len() {
+                let i = rows[p];
+                p += 1;
+                let jm = row_match[i.zx()];
+                if jm == none || visited[jm.zx()] == I(k) {
+                    continue;
+                }
+                ps[head] = I(p);
+                is[head] = i;
+                head += 1;
+                js[head] = jm;
+                pushed = true;
+                break;
+            }
+
+            if !pushed {
+                if head == 0 {
+                    break;
+                }
+                head -= 1;
+            }
+        }
+
+        if found {
+            // augment the matching along the path
+            for h in 0..=head {
+                row_match[is[h].zx()] = js[h];
+                col_match[js[h].zx()] = is[h];
+            }
+            structural_rank += 1;
+        }
+    }
+
+    // pair the unmatched rows and columns arbitrarily
+    let mut i = 0usize;
+    for j in 0..n {
+        if col_match[j] == none {
+            while row_match[i] != none {
+                i += 1;
+            }
+            row_match[i] = I(j);
+            col_match[j] = I(i);
+        }
+    }
+
+    // strongly connected components of the graph with an edge from `i` to `k` for each entry
+    // `(k, row_match[i])` of the matrix. tarjan's algorithm completes each component after all
+    // the ones reachable from it, which is the order of the diagonal blocks of an upper
+    // triangular form
+    let (index, stack) = stack.make_raw::<I>(n);
+    let (low, stack) = stack.make_raw::<I>(n);
+    let (scc_stack, stack) = stack.make_raw::<I>(n);
+    let (call_node, stack) = stack.make_raw::<I>(n);
+    let (call_pos, _) = stack.make_raw::<I>(n);
+
+    // `low[v]` is reset to `NONE` once the component of `v` is complete
+    index.fill(none);
+    let mut counter = 0usize;
+    let mut scc_len = 0usize;
+    let mut n_blocks = 0usize;
+    let mut pos = 0usize;
+    block_ptr[0] = I(0);
+
+    for root in 0..n {
+        if index[root] != none {
+            continue;
+        }
+
+        let mut depth = 0usize;
+        call_node[0] = I(root);
+        call_pos[0] = I(0);
+        index[root] = I(counter);
+        low[root] = I(counter);
+        counter += 1;
+        scc_stack[scc_len] = I(root);
+        scc_len += 1;
+
+        loop {
+            let v = call_node[depth].zx();
+            let rows = A.row_indices_of_col_raw(row_match[v].zx());
+            let p = call_pos[depth].zx();
+
+            if p < rows.len() {
+                call_pos[depth] = I(p + 1);
+                let w = rows[p].zx();
+                if index[w] == none {
+                    index[w] = I(counter);
+                    low[w] = I(counter);
+                    counter += 1;
+                    scc_stack[scc_len] = I(w);
+                    scc_len += 1;
+
+                    depth += 1;
+                    call_node[depth] = I(w);
+                    call_pos[depth] = I(0);
+                } else if low[w] != none && index[w] < low[v] {
+                    low[v] = index[w];
+                }
+                continue;
+            }
+
+            if low[v] == index[v] {
+                loop {
+                    scc_len -= 1;
+                    let w = scc_stack[scc_len].zx();
+                    low[w] = none;
+                    row_perm[pos] = I(w);
+                    col_perm[pos] = row_match[w];
+                    pos += 1;
+                    if w == v {
+                        break;
+                    }
+                }
+                n_blocks += 1;
+                block_ptr[n_blocks] = I(pos);
+            }
+
+            if depth == 0 {
+                break;
+            }
+            depth -= 1;
+            let u = call_node[depth].zx();
+            if low[v] != none && low[v] < low[u] {
+                low[u] = low[v];
+            }
+        }
+    }
+    debug_assert!(pos == n);
+
+    for k in n_blocks + 1..n + 1 {
+        block_ptr[k] = I(n);
+    }
+
+    BlockTriangularForm {
+        n_blocks,
+        structural_rank,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{assert, complex_native::c64, sparse::SparseColMat};
+    use dyn_stack::GlobalPodBuffer;
+
+    fn symbolic<I: Index>(
+        n: usize,
+        entries: &[(usize, usize)],
+    ) -> crate::sparse::SymbolicSparseColMat<I> {
+        let triplets = entries
+            .iter()
+            .map(|&(i, j)| (I::truncate(i), I::truncate(j), 1.0))
+            .collect::<Vec<_>>();
+        SparseColMat::<I, f64>::try_new_from_triplets(n, n, &triplets)
+            .unwrap()
+            .symbolic()
+            .to_owned()
+            .unwrap()
+    }
+
+    fn test_structural_symmetry<I: Index>() {
+        let I = I::truncate;
+
+        // unsorted, with a duplicate entry
+        let n = 4;
+        let col_ptr = [0, 2, 5, 6, 7].map(I);
+        let sym_ind = [1, 0, 3, 0, 0, 2, 1].map(I);
+        let unsym_ind = [1, 0, 3, 0, 0, 2, 2].map(I);
+        let sym = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &sym_ind);
+        let unsym = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &unsym_ind);
+
+        let mut mem = GlobalPodBuffer::new(structural_symmetry_req::<I>(n, 7).unwrap());
+        assert!(is_structurally_symmetric(sym, PodStack::new(&mut mem)));
+        assert!(!is_structurally_symmetric(unsym, PodStack::new(&mut mem)));
+
+        let col_ptr = [0, 1, 2].map(I);
+        let rect =
+            SymbolicSparseColMatRef::new_unsorted_checked(3, 2, &col_ptr, None, &sym_ind[..2]);
+        assert!(!is_structurally_symmetric(rect, PodStack::new(&mut mem)));
+    }
+
+    fn test_symmetry<I: Index>() {
+        let I = I::truncate;
+
+        let n = 5;
+        let mut hermitian = Vec::new();
+        let mut symmetric = Vec::new();
+        for i in 0..n {
+            hermitian.push((I(i), I(i), c64::new(i as f64 + 1.0, 0.0)));
+            symmetric.push((I(i), I(i), c64::new(i as f64 + 1.0, 0.5)));
+            if i + 2 < n {
+                let v = c64::new(1.0, i as f64 + 1.0);
+                // split one of the entries into two duplicates
+                hermitian.push((I(i), I(i + 2), v * 0.5));
+                hermitian.push((I(i), I(i + 2), v * 0.5));
+                hermitian.push((I(i + 2), I(i), v.conj()));
+                symmetric.push((I(i), I(i + 2), v));
+                symmetric.push((I(i + 2), I(i), v));
+            }
+        }
+        let H = SparseColMat::<I, c64>::try_new_from_triplets(n, n, &hermitian).unwrap();
+        let S = SparseColMat::<I, c64>::try_new_from_triplets(n, n, &symmetric).unwrap();
+
+        let mut mem = GlobalPodBuffer::new(symmetry_req::<I, c64>(n, 3 * n).unwrap());
+        assert!(is_hermitian(H.as_ref(), 1e-12, PodStack::new(&mut mem)));
+        assert!(!is_symmetric(H.as_ref(), 1e-12, PodStack::new(&mut mem)));
+        assert!(is_symmetric(S.as_ref(), 1e-12, PodStack::new(&mut mem)));
+        assert!(!is_hermitian(S.as_ref(), 1e-12, PodStack::new(&mut mem)));
+        // the imaginary parts of the entries of `H` are at most 3
+        assert!(is_symmetric(H.as_ref(), 7.0, PodStack::new(&mut mem)));
+    }
+
+    fn test_bandwidth_profile<I: Index>() {
+        let I = I::truncate;
+
+        // tridiagonal, except for the entries (0, 3) and (4, 1)
+        let n = 5;
+        let col_ptr = [0, 2, 6, 9, 13, 15].map(I);
+        let row_ind = [0, 1, 0, 1, 2, 4, 1, 2, 3, 0, 2, 3, 4, 3, 4].map(I);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        assert!(bandwidth(A) == (3, 3));
+        assert!(profile(A) == 6);
+
+        let col_ptr = [0, 1, 2, 3].map(I);
+        let row_ind = [0, 1, 2].map(I);
+        let diag = SymbolicSparseColMatRef::new_unsorted_checked(3, 3, &col_ptr, None, &row_ind);
+        assert!(bandwidth(diag) == (0, 0));
+        assert!(profile(diag) == 0);
+    }
+
+    fn test_connected_components<I: Index>() {
+        let I = I::truncate;
+
+        // lower triangular part of a graph with the components {0, 3, 5}, {1, 4}, {2} and {6}
+        let n = 7;
+        let col_ptr = [0, 1, 2, 3, 5, 5, 5, 6].map(I);
+        let row_ind = [3, 4, 2, 5, 3, 6].map(I);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        let mut component = vec![I(0); n];
+        let count = connected_components(
+            &mut component,
+            A,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                connected_components_req::<I>(n).unwrap(),
+            )),
+        );
+        assert!(count == 4);
+        assert!(component == [0, 1, 2, 0, 1, 0, 3].map(I));
+    }
+
+    fn compute_btf<I: Index>(
+        A: SymbolicSparseColMatRef<'_, I>,
+    ) -> (Vec<I>, Vec<I>, Vec<I>, BlockTriangularForm) {
+        let n = A.nrows();
+        let mut row_perm = vec![I::truncate(0); n];
+        let mut col_perm = vec![I::truncate(0); n];
+        let mut block_ptr = vec![I::truncate(0); n + 1];
+        let btf = block_triangular_form(
+            &mut row_perm,
+            &mut col_perm,
+            &mut block_ptr,
+            A,
+            PodStack::new(&mut GlobalPodBuffer::new(
+                block_triangular_form_req::<I>(n).unwrap(),
+            )),
+        );
+        (row_perm, col_perm, block_ptr, btf)
+    }
+
+    // checks that the permutations are valid, and that the permuted matrix is block upper
+    // triangular
+    fn check_btf<I: Index>(
+        A: SymbolicSparseColMatRef<'_, I>,
+        row_perm: &[I],
+        col_perm: &[I],
+        block_ptr: &[I],
+        n_blocks: usize,
+    ) {
+        let n = A.nrows();
+        let mut row_perm_inv = vec![NONE; n];
+        let mut col_perm_inv = vec![NONE; n];
+        for k in 0..n {
+            assert!(row_perm_inv[row_perm[k].zx()] == NONE);
+            assert!(col_perm_inv[col_perm[k].zx()] == NONE);
+            row_perm_inv[row_perm[k].zx()] = k;
+            col_perm_inv[col_perm[k].zx()] = k;
+        }
+
+        let mut block = vec![0usize; n];
+        assert!(block_ptr[0].zx() == 0);
+        assert!(block_ptr[n_blocks].zx() == n);
+        for b in 0..n_blocks {
+            assert!(block_ptr[b] < block_ptr[b + 1]);
+            for k in block_ptr[b].zx()..block_ptr[b + 1].zx() {
+                block[k] = b;
+            }
+        }
+
+        for j in 0..n {
+            for i in A.row_indices_of_col(j) {
+                assert!(block[row_perm_inv[i]] <= block[col_perm_inv[j]]);
+            }
+        }
+    }
+
+    fn test_block_triangular_form<I: Index>() {
+        let I = I::truncate;
+
+        // irreducible block upper triangular matrix with the diagonal blocks {0, 1}, {2} and
+        // {3, 4, 5}, with its rows and columns scrambled
+        let n = 6;
+        let mut entries = vec![
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (1, 1),
+            (2, 2),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (3, 3),
+            (4, 4),
+            (5, 5),
+            (0, 2),
+            (2, 5),
+            (1, 4),
+        ];
+        let row_scramble = [4, 0, 5, 2, 1, 3];
+        let col_scramble = [1, 3, 0, 5, 4, 2];
+        for e in &mut entries {
+            *e = (row_scramble[e.0], col_scramble[e.1]);
+        }
+
+        let mut cols = vec![vec![]; n];
+        for &(i, j) in &entries {
+            cols[j].push(I(i));
+        }
+        let mut col_ptr = vec![I(0)];
+        let mut row_ind = vec![];
+        for col in &cols {
+            row_ind.extend_from_slice(col);
+            col_ptr.push(I(row_ind.len()));
+        }
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        let (row_perm, col_perm, block_ptr, btf) = compute_btf(A);
+        assert!(
+            btf == BlockTriangularForm {
+                n_blocks: 3,
+                structural_rank: n,
+            }
+        );
+        check_btf(A, &row_perm, &col_perm, &block_ptr, btf.n_blocks);
+        for b in btf.n_blocks + 1..n + 1 {
+            assert!(block_ptr[b].zx() == n);
+        }
+
+        // the diagonal of the permuted matrix is zero-free
+        for k in 0..n {
+            assert!(A
+                .row_indices_of_col(col_perm[k].zx())
+                .any(|i| i == row_perm[k].zx()));
+        }
+    }
+
+    fn test_block_triangular_form_singular<I: Index>() {
+        let I = I::truncate;
+
+        // the columns 1 and 2 only have entries in the row 0
+        let n = 4;
+        let col_ptr = [0, 2, 3, 4, 6].map(I);
+        let row_ind = [0, 1, 0, 0, 3, 2].map(I);
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(n, n, &col_ptr, None, &row_ind);
+
+        let (row_perm, col_perm, block_ptr, btf) = compute_btf(A);
+        assert!(btf.structural_rank == 3);
+        check_btf(A, &row_perm, &col_perm, &block_ptr, btf.n_blocks);
+    }
+
+    fn test_block_triangular_form_empty<I: Index>() {
+        let col_ptr = [I::truncate(0)];
+        let A = SymbolicSparseColMatRef::new_unsorted_checked(0, 0, &col_ptr, None, &[]);
+        let (row_perm, col_perm, block_ptr, btf) = compute_btf(A);
+        assert!(row_perm.is_empty());
+        assert!(col_perm.is_empty());
+        assert!(block_ptr == [I::truncate(0)]);
+        assert!(
+            btf == BlockTriangularForm {
+                n_blocks: 0,
+                structural_rank: 0,
+            }
+        );
+    }
+
+    monomorphize_test!(test_structural_symmetry);
+    monomorphize_test!(test_symmetry);
+    monomorphize_test!(test_bandwidth_profile);
+    monomorphize_test!(test_connected_components);
+    monomorphize_test!(test_block_triangular_form);
+    monomorphize_test!(test_block_triangular_form_singular);
+    monomorphize_test!(test_block_triangular_form_empty);
+}
@@ -1,6 +1,11 @@
 // FIXME: support duplicate entries
 
-use crate::{assert, sparse::SparseColMatRef, utils::slice::*, Conj, Index, MatMut, Parallelism};
+use crate::{
+    assert,
+    sparse::{FaerError, SparseColMatRef, SymbolicSparseColMatRef},
+    utils::slice::*,
+    Conj, Index, MatMut, Parallelism,
+};
 use core::iter::zip;
 use faer_entity::ComplexField;
 
@@ -1665,3 +1670,406 @@ pub fn solve_unit_upper_triangular_transpose_in_place<I: Index, E: ComplexField>
         },
     );
 }
+
+/// Level schedule of a sparse triangular matrix, grouping its columns into sets whose
+/// corresponding unknowns can be solved for in parallel in a transpose triangular solve.
+///
+/// In the equation `Op(A)^T * X = rhs`, where `A` is a column-major triangular matrix, the row `j`
+/// of `X` only depends on the rows `i` of `X` such that `A[i, j]` is a non-zero off-diagonal
+/// entry. The level of `j` is one more than the maximum level of these dependencies, and all the
+/// rows with the same level can be computed independently.
+///
+/// The same schedule can be reused for solves with any number of right-hand sides, and any
+/// matrix with the same sparsity pattern.
+///
+/// # Note
+/// Non-transposed solves with a column-major matrix `A` can be performed by using the
+/// transpose of the row-major copy of `A`, which can be computed once with
+/// [`SparseColMatRef::to_row_major`].
+///
+/// # Example
+/// ```
+/// use faer::{
+///     mat,
+///     sparse::{
+///         linalg::triangular_solve::{
+///             solve_lower_triangular_transpose_in_place_scheduled, LevelSchedule,
+///         },
+///         SparseColMat,
+///     },
+///     Conj, Parallelism,
+/// };
+///
+/// let l = SparseColMat::<usize, f64>::try_new_from_triplets(
+///     3,
+///     3,
+///     &[(0, 0, 2.0), (2, 0, 1.0), (1, 1, 1.0), (2, 2, 4.0)],
+/// )
+/// .unwrap();
+/// let schedule = LevelSchedule::try_new_lower(l.symbolic()).unwrap();
+/// // rows 1 and 2 don't depend on any other rows, and row 0 depends on row 2
+/// assert!(schedule.n_levels() == 2);
+///
+/// let mut x = mat![[4.0], [1.0], [8.0]];
+/// solve_lower_triangular_transpose_in_place_scheduled(
+///     l.as_ref(),
+///     &schedule,
+///     Conj::No,
+///     x.as_mut(),
+///     Parallelism::None,
+/// );
+/// assert!(x == mat![[1.0], [1.0], [2.0]]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct LevelSchedule<I: Index> {
+    level_ptr: alloc::vec::Vec<I>,
+    nodes: alloc::vec::Vec<I>,
+}
+
+impl<I: Index> LevelSchedule<I> {
+    /// Computes the level schedule of the transpose solve with the lower triangular matrix with
+    /// sparsity pattern `l`.
+    #[track_caller]
+    pub fn try_new_lower(l: SymbolicSparseColMatRef<'_, I>) -> Result<Self, FaerError> {
+        assert!(l.nrows() == l.ncols());
+        Self::try_new_impl(l, (0..l.ncols()).rev())
+    }
+
+    /// Computes the level schedule of the transpose solve with the upper triangular matrix with
+    /// sparsity pattern `u`.
+    #[track_caller]
+    pub fn try_new_upper(u: SymbolicSparseColMatRef<'_, I>) -> Result<Self, FaerError> {
+        assert!(u.nrows() == u.ncols());
+        Self::try_new_impl(u, 0..u.ncols())
+    }
+
+    fn try_new_impl(
+        a: SymbolicSparseColMatRef<'_, I>,
+        order: impl Iterator<Item = usize>,
+    ) -> Result<Self, FaerError> {
+        let n = a.ncols();
+
+        // `order` is a topological order of the dependency graph, so the levels of the
+        // dependencies of `j` are already known when it's visited
+        let mut level = alloc::vec::Vec::new();
+        level.try_reserve_exact(n)?;
+        level.resize(n, 0usize);
+        let mut n_levels = 0usize;
+        for j in order {
+            let mut level_j = 0usize;
+            for i in a.row_indices_of_col(j) {
+                if i != j {
+                    level_j = Ord::max(level_j, level[i] + 1);
+                }
+            }
+            level[j] = level_j;
+            n_levels = Ord::max(n_levels, level_j + 1);
+        }
+
+        let mut level_ptr = alloc::vec::Vec::new();
+        let mut nodes = alloc::vec::Vec::new();
+        level_ptr.try_reserve_exact(n_levels + 1)?;
+        nodes.try_reserve_exact(n)?;
+        level_ptr.resize(n_levels + 1, I::truncate(0));
+        nodes.resize(n, I::truncate(0));
+
+        for j in 0..n {
+            level_ptr[level[j] + 1] += I::truncate(1);
+        }
+        for l in 0..n_levels {
+            let next = level_ptr[l] + level_ptr[l + 1];
+            level_ptr[l + 1] = next;
+        }
+        let mut next = alloc::vec::Vec::new();
+        next.try_reserve_exact(n_levels)?;
+        next.extend(level_ptr[..n_levels].iter().map(|p| p.zx()));
+        for j in 0..n {
+            let pos = &mut next[level[j]];
+            nodes[*pos] = I::truncate(j);
+            *pos += 1;
+        }
+
+        Ok(Self { level_ptr, nodes })
+    }
+
+    /// Returns the dimension of the matrix.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of levels.
+    #[inline]
+    pub fn n_levels(&self) -> usize {
+        self.level_ptr.len() - 1
+    }
+
+    /// Returns the columns belonging to the level at index `level`.
+    #[inline]
+    #[track_caller]
+    pub fn level(&self, level: usize) -> &[I] {
+        &self.nodes[self.level_ptr[level].zx()..self.level_ptr[level + 1].zx()]
+    }
+}
+
+#[track_caller]
+fn solve_transpose_scheduled_impl<I: Index, E: ComplexField>(
+    a: SparseColMatRef<'_, I, E>,
+    schedule: &LevelSchedule<I>,
+    conj: Conj,
+    unit: bool,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        a.nrows() == a.ncols(),
+        rhs.nrows() == a.nrows(),
+        schedule.dim() == a.ncols(),
+    ));
+
+    let k = rhs.ncols();
+    let x = rhs.as_ref();
+    let n_threads = crate::utils::thread::parallelism_degree(parallelism);
+
+    for level in 0..schedule.n_levels() {
+        let nodes = schedule.level(level);
+        // small levels are not worth the synchronization cost
+        let par = if nodes.len() * k >= 64 * n_threads {
+            n_threads
+        } else {
+            1
+        };
+
+        crate::utils::thread::for_each_raw(
+            par,
+            |tid| {
+                let (start, len) = crate::utils::thread::par_split_indices(nodes.len(), tid, par);
+                // SAFETY: the rows written by each thread are distinct, and only depend on rows
+                // from previous levels, which are not written to during this level
+                let mut x = unsafe { x.const_cast() };
+                for &j in &nodes[start..][..len] {
+                    let j = j.zx();
+                    for c in 0..k {
+                        let mut acc = E::faer_zero();
+                        let mut diag = E::faer_one();
+                        for (i, aij) in zip(
+                            a.row_indices_of_col(j),
+                            SliceGroup::<'_, E>::new(a.values_of_col(j)).into_ref_iter(),
+                        ) {
+                            let aij = aij.read();
+                            let aij = if conj == Conj::Yes {
+                                aij.faer_conj()
+                            } else {
+                                aij
+                            };
+                            if i == j {
+                                diag = aij;
+                            } else {
+                                acc = acc.faer_add(aij.faer_mul(x.read(i, c)));
+                            }
+                        }
+                        let xj = x.read(j, c).faer_sub(acc);
+                        x.write(
+                            j,
+                            c,
+                            if unit {
+                                xj
+                            } else {
+                                xj.faer_mul(diag.faer_inv())
+                            },
+                        );
+                    }
+                }
+            },
+            parallelism,
+        );
+    }
+}
+
+/// Assuming `l` is a lower triangular matrix, solves the equation `Op(l)^T * X = rhs` using the
+/// level schedule `schedule` computed with [`LevelSchedule::try_new_lower`], and stores the
+/// result in `rhs`, where `Op` is either the conjugate or the identity depending on the value of
+/// `conj`.
+///
+/// The rows belonging to the same level are solved for in parallel.
+///
+/// # Note
+/// The matrix indices need not be sorted.
+#[track_caller]
+pub fn solve_lower_triangular_transpose_in_place_scheduled<I: Index, E: ComplexField>(
+    l: SparseColMatRef<'_, I, E>,
+    schedule: &LevelSchedule<I>,
+    conj: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    solve_transpose_scheduled_impl(l, schedule, conj, false, rhs, parallelism);
+}
+
+/// Assuming `l` is a unit lower triangular matrix, solves the equation `Op(l)^T * X = rhs` using
+/// the level schedule `schedule` computed with [`LevelSchedule::try_new_lower`], and stores the
+/// result in `rhs`, where `Op` is either the conjugate or the identity depending on the value of
+/// `conj`.
+///
+/// The rows belonging to the same level are solved for in parallel.
+///
+/// # Note
+/// The matrix indices need not be sorted, and stored diagonal elements are ignored.
+#[track_caller]
+pub fn solve_unit_lower_triangular_transpose_in_place_scheduled<I: Index, E: ComplexField>(
+    l: SparseColMatRef<'_, I, E>,
+    schedule: &LevelSchedule<I>,
+    conj: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    solve_transpose_scheduled_impl(l, schedule, conj, true, rhs, parallelism);
+}
+
+/// Assuming `u` is an upper triangular matrix, solves the equation `Op(u)^T * X = rhs` using the
+/// level schedule `schedule` computed with [`LevelSchedule::try_new_upper`], and stores the
+/// result in `rhs`, where `Op` is either the conjugate or the identity depending on the value of
+/// `conj`.
+///
+/// The rows belonging to the same level are solved for in parallel.
+///
+/// # Note
+/// The matrix indices need not be sorted.
+#[track_caller]
+pub fn solve_upper_triangular_transpose_in_place_scheduled<I: Index, E: ComplexField>(
+    u: SparseColMatRef<'_, I, E>,
+    schedule: &LevelSchedule<I>,
+    conj: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    solve_transpose_scheduled_impl(u, schedule, conj, false, rhs, parallelism);
+}
+
+/// Assuming `u` is a unit upper triangular matrix, solves the equation `Op(u)^T * X = rhs` using
+/// the level schedule `schedule` computed with [`LevelSchedule::try_new_upper`], and stores the
+/// result in `rhs`, where `Op` is either the conjugate or the identity depending on the value of
+/// `conj`.
+///
+/// The rows belonging to the same level are solved for in parallel.
+///
+/// # Note
+/// The matrix indices need not be sorted, and stored diagonal elements are ignored.
+#[track_caller]
+pub fn solve_unit_upper_triangular_transpose_in_place_scheduled<I: Index, E: ComplexField>(
+    u: SparseColMatRef<'_, I, E>,
+    schedule: &LevelSchedule<I>,
+    conj: Conj,
+    rhs: MatMut<'_, E>,
+    parallelism: Parallelism,
+) {
+    solve_transpose_scheduled_impl(u, schedule, conj, true, rhs, parallelism);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{complex_native::c64, sparse::SparseColMat, Mat};
+
+    #[test]
+    fn test_scheduled_transpose_solve() {
+        let n = 200;
+        let mut lower = alloc::vec::Vec::new();
+        let mut upper = alloc::vec::Vec::new();
+        for j in 0..n {
+            for i in j..n {
+                if i == j || ((i * 13 + j * 7) % 17 == 0 && i - j < 30) {
+                    let v = if i == j {
+                        c64::new(4.0, 1.0)
+                    } else {
+                        c64::new(1.0 / (1.0 + (i - j) as f64), 0.5)
+                    };
+                    lower.push((i, j, v));
+                    upper.push((j, i, v));
+                }
+            }
+        }
+        let l = SparseColMat::<usize, c64>::try_new_from_triplets(n, n, &lower).unwrap();
+        let u = SparseColMat::<usize, c64>::try_new_from_triplets(n, n, &upper).unwrap();
+        let l_schedule = LevelSchedule::try_new_lower(l.symbolic()).unwrap();
+        let u_schedule = LevelSchedule::try_new_upper(u.symbolic()).unwrap();
+        assert!(l_schedule.n_levels() == u_schedule.n_levels());
+        assert!(l_schedule.n_levels() < n / 4);
+        assert!(l_schedule.dim() == n);
+
+        let rhs = Mat::from_fn(n, 32, |i, j| c64::new(i as f64 - j as f64, 1.0));
+        let close = |x: &Mat<c64>, y: &Mat<c64>| (x - y).norm_max() <= 1e-12 * y.norm_max();
+
+        for parallelism in [Parallelism::None, Parallelism::Rayon(4)] {
+            for conj in [Conj::No, Conj::Yes] {
+                let mut expected = rhs.clone();
+                let mut x = rhs.clone();
+                solve_lower_triangular_transpose_in_place(
+                    l.as_ref(),
+                    conj,
+                    expected.as_mut(),
+                    parallelism,
+                );
+                solve_lower_triangular_transpose_in_place_scheduled(
+                    l.as_ref(),
+                    &l_schedule,
+                    conj,
+                    x.as_mut(),
+                    parallelism,
+                );
+                assert!(close(&x, &expected));
+
+                let mut expected = rhs.clone();
+                let mut x = rhs.clone();
+                solve_unit_lower_triangular_transpose_in_place(
+                    l.as_ref(),
+                    conj,
+                    expected.as_mut(),
+                    parallelism,
+                );
+                solve_unit_lower_triangular_transpose_in_place_scheduled(
+                    l.as_ref(),
+                    &l_schedule,
+                    conj,
+                    x.as_mut(),
+                    parallelism,
+                );
+                assert!(close(&x, &expected));
+
+                let mut expected = rhs.clone();
+                let mut x = rhs.clone();
+                solve_upper_triangular_transpose_in_place(
+                    u.as_ref(),
+                    conj,
+                    expected.as_mut(),
+                    parallelism,
+                );
+                solve_upper_triangular_transpose_in_place_scheduled(
+                    u.as_ref(),
+                    &u_schedule,
+                    conj,
+                    x.as_mut(),
+                    parallelism,
+                );
+                assert!(close(&x, &expected));
+
+                let mut expected = rhs.clone();
+                let mut x = rhs.clone();
+                solve_unit_upper_triangular_transpose_in_place(
+                    u.as_ref(),
+                    conj,
+                    expected.as_mut(),
+                    parallelism,
+                );
+                solve_unit_upper_triangular_transpose_in_place_scheduled(
+                    u.as_ref(),
+                    &u_schedule,
+                    conj,
+                    x.as_mut(),
+                    parallelism,
+                );
+                assert!(close(&x, &expected));
+            }
+        }
+    }
+}
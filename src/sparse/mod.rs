@@ -192,6 +192,82 @@ fn try_collect<I: IntoIterator>(iter: I) -> Result<alloc::vec::Vec<I::Item>, Fae
     Ok(v)
 }
 
+/// Inserts explicit zeros at the structurally missing diagonal positions of a compressed sparse
+/// matrix, whose outer dimension (columns or rows) is described by `ptr`, `nnz` and `ind`.
+///
+/// Entries are inserted before the first entry with a greater inner index, so sorted inputs
+/// produce sorted outputs.
+fn try_insert_missing_diag<I: Index, E: ComplexField>(
+    diag_len: usize,
+    outer: usize,
+    ptr: &mut alloc::vec::Vec<I>,
+    nnz: &mut Option<alloc::vec::Vec<I>>,
+    ind: &mut alloc::vec::Vec<I>,
+    values: &mut VecGroup<E>,
+) -> Result<(), FaerError> {
+    let range = |j: usize| {
+        let start = ptr[j].zx();
+        let end = match nnz {
+            Some(nnz) => start + nnz[j].zx(),
+            None => ptr[j + 1].zx(),
+        };
+        start..end
+    };
+    let is_missing = |j: usize| j < diag_len && !ind[range(j)].iter().any(|&i| i.zx() == j);
+
+    let mut total = 0usize;
+    let mut missing = 0usize;
+    for j in 0..outer {
+        total += range(j).len();
+        missing += is_missing(j) as usize;
+    }
+    if missing == 0 {
+        return Ok(());
+    }
+    let total = total + missing;
+    if total > I::Signed::MAX.zx() {
+        return Err(FaerError::IndexOverflow);
+    }
+
+    let mut new_ptr = try_zeroed::<I>(outer + 1)?;
+    let mut new_ind = try_zeroed::<I>(total)?;
+    let mut new_values = VecGroup::<E>::new();
+    new_values
+        .try_reserve_exact(total)
+        .map_err(|_| FaerError::OutOfMemory)?;
+    new_values.resize(total, E::faer_zero().faer_into_units());
+
+    {
+        let old_values = values.as_slice();
+        let mut new_values = new_values.as_slice_mut();
+        let mut pos = 0usize;
+        for j in 0..outer {
+            let mut inserted = !is_missing(j);
+            for k in range(j) {
+                if !inserted && ind[k].zx() > j {
+                    new_ind[pos] = I::truncate(j);
+                    pos += 1;
+                    inserted = true;
+                }
+                new_ind[pos] = ind[k];
+                new_values.write(pos, old_values.read(k));
+                pos += 1;
+            }
+            if !inserted {
+                new_ind[pos] = I::truncate(j);
+                pos += 1;
+            }
+            new_ptr[j + 1] = I::truncate(pos);
+        }
+    }
+
+    *ptr = new_ptr;
+    *nnz = None;
+    *ind = new_ind;
+    *values = new_values;
+    Ok(())
+}
+
 /// The order values should be read in, when constructing/filling from indices and values.
 ///
 /// Allows separately creating the symbolic structure and filling the numerical values.
@@ -212,8 +288,10 @@ pub enum FillMode {
     Add,
 }
 
+mod block;
 mod csc;
 mod csr;
+mod triplets;
 
 /// Sparse linear algebra module.  
 /// Contains low level routines and the implementation of their corresponding high level wrappers.
@@ -222,8 +300,10 @@ pub mod linalg;
 /// Sparse matrix binary and ternary operation implementations.
 pub mod ops;
 
+pub use block::*;
 pub use csc::*;
 pub use csr::*;
+pub use triplets::*;
 
 /// Useful sparse matrix primitives.
 pub mod utils {
@@ -1594,6 +1674,168 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hadamard() {
+        let lhs = SparseColMat::<usize, f64>::try_new_from_triplets(
+            4,
+            3,
+            &[
+                (0, 0, 1.0),
+                (2, 0, 2.0),
+                (1, 1, 3.0),
+                (3, 1, 4.0),
+                (0, 2, 5.0),
+                (3, 2, 6.0),
+            ],
+        )
+        .unwrap();
+        let rhs = SparseColMat::<usize, f64>::try_new_from_triplets(
+            4,
+            3,
+            &[
+                (2, 0, 7.0),
+                (3, 0, 8.0),
+                (1, 1, 9.0),
+                (3, 1, 10.0),
+                (1, 2, 11.0),
+            ],
+        )
+        .unwrap();
+
+        let prod = ops::hadamard(lhs.as_ref(), rhs.as_ref()).unwrap();
+        let pattern = ops::intersection_symbolic(lhs.symbolic(), rhs.symbolic()).unwrap();
+        assert!(prod.col_ptrs() == pattern.col_ptrs());
+        assert!(prod.row_indices() == pattern.row_indices());
+        assert!(prod.col_ptrs() == &[0, 1, 3, 3]);
+        assert!(prod.row_indices() == &[2, 1, 3]);
+        assert!(prod.values() == &[14.0, 27.0, 40.0]);
+
+        // reuse the union pattern, where the entries outside of the intersection are zeroed
+        let mut sum = ops::add(lhs.as_ref(), rhs.as_ref()).unwrap();
+        ops::hadamard_into(sum.as_mut(), lhs.as_ref(), rhs.as_ref());
+        for j in 0..3 {
+            for i in 0..4 {
+                let expected = prod.get(i, j).copied().unwrap_or(0.0);
+                assert!(sum.get(i, j).copied().unwrap_or(0.0) == expected);
+            }
+        }
+        assert!(sum.compute_nnz() == 8);
+
+        let max = ops::binary_op_intersection(lhs.as_ref(), rhs.as_ref(), f64::max).unwrap();
+        assert!(max.values() == &[7.0, 9.0, 10.0]);
+    }
+
+    #[test]
+    fn test_norms_and_sums() {
+        let a = SparseColMat::<usize, f64>::try_new_from_triplets(
+            4,
+            3,
+            &[
+                (0, 0, 1.0),
+                (2, 0, -2.0),
+                (1, 1, 3.0),
+                (3, 1, -4.0),
+                (0, 2, 5.0),
+                (3, 2, 6.0),
+            ],
+        )
+        .unwrap();
+        let dense = a.to_dense();
+        let a_csr = a.to_row_major().unwrap();
+
+        let abs_sum = |f: &dyn Fn(usize, usize) -> f64, m: usize, n: usize| {
+            (0..n)
+                .map(|j| (0..m).map(|i| f(i, j).abs()).sum::<f64>())
+                .fold(0.0f64, f64::max)
+        };
+        let induced_l1 = abs_sum(&|i, j| dense.read(i, j), 4, 3);
+        let induced_linf = abs_sum(&|i, j| dense.read(j, i), 3, 4);
+
+        for (max, l1, l2, ind_l1, ind_linf, sum) in [
+            (
+                a.norm_max(),
+                a.norm_l1(),
+                a.norm_l2(),
+                a.induced_norm_l1(),
+                a.induced_norm_linf(),
+                a.sum(),
+            ),
+            (
+                a_csr.norm_max(),
+                a_csr.norm_l1(),
+                a_csr.norm_l2(),
+                a_csr.induced_norm_l1(),
+                a_csr.induced_norm_linf(),
+                a_csr.sum(),
+            ),
+        ] {
+            assert!(max == dense.norm_max());
+            assert!(l1 == 21.0);
+            assert!((l2 - dense.norm_l2()).abs() < 1e-12);
+            assert!(ind_l1 == induced_l1);
+            assert!(ind_linf == induced_linf);
+            assert!(sum == dense.sum());
+        }
+
+        for col_sums in [a.col_sums(), a_csr.col_sums()] {
+            for j in 0..3 {
+                assert!(col_sums.read(j) == (0..4).map(|i| dense.read(i, j)).sum::<f64>());
+            }
+        }
+        for row_sums in [a.row_sums(), a_csr.row_sums()] {
+            for i in 0..4 {
+                assert!(row_sums.read(i) == (0..3).map(|j| dense.read(i, j)).sum::<f64>());
+            }
+        }
+
+        let nnz_per_col = a.symbolic().compute_nnz_per_col().unwrap();
+        let nnz_per_row = a.symbolic().compute_nnz_per_row().unwrap();
+        assert!(nnz_per_col == [2, 2, 2]);
+        assert!(nnz_per_row == [2, 1, 1, 2]);
+        assert!(a_csr.symbolic().compute_nnz_per_col().unwrap() == nnz_per_col);
+        assert!(a_csr.symbolic().compute_nnz_per_row().unwrap() == nnz_per_row);
+    }
+
+    #[test]
+    fn test_diag() {
+        let a = SparseColMat::<usize, f64>::try_new_from_triplets(
+            3,
+            4,
+            &[
+                (0, 0, 1.0),
+                (2, 0, 2.0),
+                (0, 1, 3.0),
+                (2, 2, 4.0),
+                (2, 2, 5.0),
+                (1, 3, 6.0),
+            ],
+        )
+        .unwrap();
+        let mut a_csr = a.to_row_major().unwrap();
+        let mut a = a;
+
+        assert!(a.diag() == crate::col::from_slice::<f64>(&[1.0, 0.0, 9.0]));
+        assert!(a_csr.diag() == a.diag());
+
+        a.shift_diag(10.0).unwrap();
+        a_csr.shift_diag(10.0).unwrap();
+        assert!(a.diag() == crate::col::from_slice::<f64>(&[11.0, 10.0, 19.0]));
+        assert!(a_csr.diag() == a.diag());
+        assert!(a.compute_nnz() == 6);
+        assert!(a.col_ptrs() == &[0, 2, 4, 5, 6]);
+        assert!(a.row_indices() == &[0, 2, 0, 1, 2, 1]);
+        assert!(a_csr.row_ptrs() == &[0, 2, 4, 6]);
+        assert!(a_csr.col_indices() == &[0, 1, 1, 3, 0, 2]);
+
+        let diag = crate::col::from_slice::<f64>(&[-1.0, -2.0, -3.0]);
+        a.as_mut().set_diag(diag);
+        a_csr.set_diag(diag).unwrap();
+        assert!(a.diag() == diag);
+        assert!(a_csr.diag() == diag);
+        assert!(a.to_dense() == a_csr.to_dense());
+        assert!(a.get(2, 0) == Some(&2.0));
+    }
+
     #[test]
     fn test_add_disjoint() {
         let lhs = SparseColMat::<usize, f64>::try_new_from_triplets(
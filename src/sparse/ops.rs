@@ -352,6 +352,147 @@ pub fn ternary_op_assign_into<I: Index, E: Entity, LhsE: Entity, RhsE: Entity>(
     }
 }
 
+/// Returns the resulting matrix obtained by applying `f` to the elements from `lhs` and `rhs`,
+/// skipping entries that are unavailable in either of `lhs` and `rhs`.
+///
+/// # Panics
+/// Panics if `lhs` and `rhs` don't have matching dimensions.  
+#[track_caller]
+pub fn binary_op_intersection<I: Index, E: Entity, LhsE: Entity, RhsE: Entity>(
+    lhs: SparseColMatRef<'_, I, LhsE>,
+    rhs: SparseColMatRef<'_, I, RhsE>,
+    f: impl FnMut(LhsE, RhsE) -> E,
+) -> Result<SparseColMat<I, E>, FaerError> {
+    assert!(lhs.nrows() == rhs.nrows());
+    assert!(lhs.ncols() == rhs.ncols());
+    let mut f = f;
+    let m = lhs.nrows();
+    let n = lhs.ncols();
+
+    let mut col_ptrs = try_zeroed::<I>(n + 1)?;
+
+    let mut nnz = 0usize;
+    for j in 0..n {
+        let lhs = lhs.row_indices_of_col_raw(j);
+        let rhs = rhs.row_indices_of_col_raw(j);
+
+        let mut lhs_pos = 0usize;
+        let mut rhs_pos = 0usize;
+        while lhs_pos < lhs.len() && rhs_pos < rhs.len() {
+            let lhs = lhs[lhs_pos];
+            let rhs = rhs[rhs_pos];
+
+            lhs_pos += (lhs <= rhs) as usize;
+            rhs_pos += (rhs <= lhs) as usize;
+            nnz += (lhs == rhs) as usize;
+        }
+        col_ptrs[j + 1] = I::truncate(nnz);
+    }
+
+    if nnz > I::Signed::MAX.zx() {
+        return Err(FaerError::IndexOverflow);
+    }
+
+    let mut row_indices = try_zeroed(nnz)?;
+    let mut values = VecGroup::<E>::new();
+    values
+        .try_reserve_exact(nnz)
+        .map_err(|_| FaerError::OutOfMemory)?;
+    values.resize(nnz, unsafe { core::mem::zeroed() });
+
+    let mut nnz = 0usize;
+    for j in 0..n {
+        let mut values = values.as_slice_mut();
+        let lhs_values = SliceGroup::<LhsE>::new(lhs.values_of_col(j));
+        let rhs_values = SliceGroup::<RhsE>::new(rhs.values_of_col(j));
+        let lhs = lhs.row_indices_of_col_raw(j);
+        let rhs = rhs.row_indices_of_col_raw(j);
+
+        let mut lhs_pos = 0usize;
+        let mut rhs_pos = 0usize;
+        while lhs_pos < lhs.len() && rhs_pos < rhs.len() {
+            let lhs = lhs[lhs_pos];
+            let rhs = rhs[rhs_pos];
+
+            if lhs == rhs {
+                row_indices[nnz] = lhs;
+                values.write(nnz, f(lhs_values.read(lhs_pos), rhs_values.read(rhs_pos)));
+                nnz += 1;
+            }
+
+            lhs_pos += (lhs <= rhs) as usize;
+            rhs_pos += (rhs <= lhs) as usize;
+        }
+    }
+
+    Ok(SparseColMat::<I, E>::new(
+        SymbolicSparseColMat::<I>::new_checked(m, n, col_ptrs, None, row_indices),
+        values.into_inner(),
+    ))
+}
+
+/// Returns the resulting matrix obtained by applying `f` to the elements from `lhs` and `rhs`
+/// that are available in both of them, and stores the result in `dst`. The other entries of
+/// `dst` are set to zero.  
+/// The sparsity patter of `dst` is unchanged, and can be precomputed with
+/// [`intersection_symbolic`] or [`union_symbolic`].
+///
+/// # Panics
+/// Panics if `lhs`, `rhs` and `dst` don't have matching dimensions.  
+/// Panics if an index that's available in both `lhs` and `rhs` is unavailable in `dst`.  
+#[track_caller]
+pub fn binary_op_intersection_into<I: Index, E: Entity, LhsE: Entity, RhsE: Entity>(
+    dst: SparseColMatMut<'_, I, E>,
+    lhs: SparseColMatRef<'_, I, LhsE>,
+    rhs: SparseColMatRef<'_, I, RhsE>,
+    f: impl FnMut(LhsE, RhsE) -> E,
+) {
+    assert!(dst.nrows() == lhs.nrows());
+    assert!(dst.ncols() == lhs.ncols());
+    assert!(dst.nrows() == rhs.nrows());
+    assert!(dst.ncols() == rhs.ncols());
+
+    let n = dst.ncols();
+    let mut dst = dst;
+    let mut f = f;
+
+    for j in 0..n {
+        let (dst, dst_val) = dst.rb_mut().parts_mut();
+
+        let mut dst_val = SliceGroupMut::<E>::new(dst_val).subslice(dst.col_range(j));
+        let lhs_val = SliceGroup::<LhsE>::new(lhs.values_of_col(j));
+        let rhs_val = SliceGroup::<RhsE>::new(rhs.values_of_col(j));
+
+        let dst = dst.row_indices_of_col_raw(j);
+        let lhs = lhs.row_indices_of_col_raw(j);
+        let rhs = rhs.row_indices_of_col_raw(j);
+
+        for dst_pos in 0..dst.len() {
+            dst_val.write(dst_pos, unsafe { core::mem::zeroed() });
+        }
+
+        let mut dst_pos = 0usize;
+        let mut lhs_pos = 0usize;
+        let mut rhs_pos = 0usize;
+        while lhs_pos < lhs.len() && rhs_pos < rhs.len() {
+            let lhs = lhs[lhs_pos];
+            let rhs = rhs[rhs_pos];
+
+            if lhs == rhs {
+                while dst_pos < dst.len() && dst[dst_pos] < lhs {
+                    dst_pos += 1;
+                }
+                assert!(dst_pos < dst.len());
+                assert!(dst[dst_pos] == lhs);
+                dst_val.write(dst_pos, f(lhs_val.read(lhs_pos), rhs_val.read(rhs_pos)));
+            }
+
+            lhs_pos += (lhs <= rhs) as usize;
+            rhs_pos += (rhs <= lhs) as usize;
+        }
+    }
+}
+
 /// Returns the sparsity pattern containing the union of those of `lhs` and `rhs`.
 ///
 /// # Panics
@@ -372,6 +513,26 @@ pub fn union_symbolic<I: Index>(
     .0)
 }
 
+/// Returns the sparsity pattern containing the intersection of those of `lhs` and `rhs`.
+///
+/// # Panics
+/// Panics if `lhs` and `rhs` don't have matching dimensions.  
+#[track_caller]
+#[inline]
+pub fn intersection_symbolic<I: Index>(
+    lhs: SymbolicSparseColMatRef<'_, I>,
+    rhs: SymbolicSparseColMatRef<'_, I>,
+) -> Result<SymbolicSparseColMat<I>, FaerError> {
+    Ok(binary_op_intersection(
+        SparseColMatRef::<I, Symbolic>::new(lhs, Symbolic::materialize(lhs.compute_nnz())),
+        SparseColMatRef::<I, Symbolic>::new(rhs, Symbolic::materialize(rhs.compute_nnz())),
+        #[inline(always)]
+        |_, _| Symbolic,
+    )?
+    .into_parts()
+    .0)
+}
+
 /// Returns the sum of `lhs` and `rhs`.
 ///
 /// # Panics
@@ -412,6 +573,27 @@ pub fn sub<
     })
 }
 
+/// Returns the element-wise product of `lhs` and `rhs`, whose sparsity pattern is the
+/// intersection of theirs.
+///
+/// # Panics
+/// Panics if `lhs` and `rhs` don't have matching dimensions.  
+#[track_caller]
+#[inline]
+pub fn hadamard<
+    I: Index,
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    lhs: SparseColMatRef<'_, I, LhsE>,
+    rhs: SparseColMatRef<'_, I, RhsE>,
+) -> Result<SparseColMat<I, E>, FaerError> {
+    binary_op_intersection(lhs, rhs, |lhs, rhs| {
+        lhs.canonicalize().faer_mul(rhs.canonicalize())
+    })
+}
+
 /// Computes the sum of `dst` and `src` and stores the result in `dst` without changing its
 /// symbolic structure.
 ///
@@ -483,3 +665,26 @@ pub fn sub_into<
         lhs.canonicalize().faer_sub(rhs.canonicalize())
     })
 }
+
+/// Computes the element-wise product of `lhs` and `rhs`, storing the result in `dst` without
+/// changing its symbolic structure.
+///
+/// # Panics
+/// Panics if `dst`, `lhs` and `rhs` don't have matching dimensions.  
+/// Panics if an index that's available in both `lhs` and `rhs` is unavailable in `dst`.  
+#[track_caller]
+#[inline]
+pub fn hadamard_into<
+    I: Index,
+    E: ComplexField,
+    LhsE: Conjugate<Canonical = E>,
+    RhsE: Conjugate<Canonical = E>,
+>(
+    dst: SparseColMatMut<'_, I, E>,
+    lhs: SparseColMatRef<'_, I, LhsE>,
+    rhs: SparseColMatRef<'_, I, RhsE>,
+) {
+    binary_op_intersection_into(dst, lhs, rhs, |lhs, rhs| {
+        lhs.canonicalize().faer_mul(rhs.canonicalize())
+    })
+}
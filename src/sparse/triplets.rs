@@ -0,0 +1,423 @@
+use super::*;
+use crate::assert;
+
+/// How entries sharing the same `(row, col)` position are combined when building a matrix from
+/// a [`SparseTripletBuilder`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Duplicate entries are summed.
+    #[default]
+    Sum,
+    /// The most recently pushed entry replaces the previous ones.
+    Overwrite,
+    /// Duplicate entries are rejected with [`TripletError::Duplicate`].
+    Error,
+}
+
+/// How entries that were not pushed in the storage order of the output matrix are handled when
+/// building a matrix from a [`SparseTripletBuilder`].
+///
+/// The storage order is `(col, row)` lexicographic order for column-major output, and `(row,
+/// col)` lexicographic order for row-major output.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OrderPolicy {
+    /// Entries are sorted as needed. Sorting is skipped if they are already in storage order.
+    #[default]
+    Sort,
+    /// Entries are required to be in storage order, and are otherwise rejected with
+    /// [`TripletError::Unsorted`].
+    RequireSorted,
+}
+
+/// Parameters of a [`SparseTripletBuilder`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TripletBuilderParams {
+    /// Handling of duplicate entries.
+    pub duplicates: DuplicatePolicy,
+    /// Handling of entries that are not in storage order.
+    pub order: OrderPolicy,
+}
+
+/// Errors that can occur when building a sparse matrix from triplets.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd)]
+#[non_exhaustive]
+pub enum TripletError {
+    /// Generic error (allocation or index overflow).
+    Generic(FaerError),
+    /// Duplicate entry, rejected by [`DuplicatePolicy::Error`].
+    Duplicate {
+        /// Row of the duplicate entry.
+        row: usize,
+        /// Column of the duplicate entry.
+        col: usize,
+    },
+    /// Entry that is out of storage order, rejected by [`OrderPolicy::RequireSorted`].
+    Unsorted {
+        /// Position of the entry in insertion order.
+        pos: usize,
+    },
+}
+
+impl From<FaerError> for TripletError {
+    #[inline]
+    fn from(value: FaerError) -> Self {
+        Self::Generic(value)
+    }
+}
+
+impl core::fmt::Display for TripletError {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+impl std::error::Error for TripletError {}
+
+/// Incremental builder of sparse matrices from `(row, col, value)` triplets, e.g. for finite
+/// element assembly.
+///
+/// Entries can be pushed in any order, and are compiled to a column-major or row-major matrix
+/// once assembly is done. The builder is left untouched by the compilation, and can be cleared
+/// to reuse its storage for a matrix with a different set of entries.
+///
+/// # Example
+/// ```
+/// use faer::sparse::{DuplicatePolicy, SparseTripletBuilder, TripletBuilderParams};
+///
+/// let mut builder = SparseTripletBuilder::<usize, f64>::new(2, 2, Default::default());
+/// builder.push(1, 0, 1.0);
+/// builder.push(0, 0, 2.0);
+/// builder.push(1, 0, 3.0);
+///
+/// let A = builder.try_build_col_major().unwrap();
+/// assert_eq!(A.row_indices(), &[0, 1]);
+/// assert_eq!(A.values(), &[2.0, 4.0]);
+///
+/// let mut builder = builder.with_params(TripletBuilderParams {
+///     duplicates: DuplicatePolicy::Overwrite,
+///     ..Default::default()
+/// });
+/// let A = builder.try_build_row_major().unwrap();
+/// assert_eq!(A.values(), &[2.0, 3.0]);
+///
+/// builder.clear();
+/// assert!(builder.is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SparseTripletBuilder<I: Index, E: Entity> {
+    nrows: usize,
+    ncols: usize,
+    row_ind: alloc::vec::Vec<I>,
+    col_ind: alloc::vec::Vec<I>,
+    values: VecGroup<E>,
+    params: TripletBuilderParams,
+}
+
+impl<I: Index, E: ComplexField> SparseTripletBuilder<I, E> {
+    /// Creates a new empty builder for a matrix with the given dimensions.
+    ///
+    /// # Panics
+    /// Panics if `nrows` or `ncols` is greater than `I::Signed::MAX`.
+    #[track_caller]
+    pub fn new(nrows: usize, ncols: usize, params: TripletBuilderParams) -> Self {
+        assert!(all(
+            nrows <= I::Signed::MAX.zx(),
+            ncols <= I::Signed::MAX.zx(),
+        ));
+        Self {
+            nrows,
+            ncols,
+            row_ind: alloc::vec::Vec::new(),
+            col_ind: alloc::vec::Vec::new(),
+            values: VecGroup::new(),
+            params,
+        }
+    }
+
+    /// Returns the builder with its parameters replaced by `params`.
+    #[inline]
+    pub fn with_params(self, params: TripletBuilderParams) -> Self {
+        Self { params, ..self }
+    }
+
+    /// Returns the parameters of the builder.
+    #[inline]
+    pub fn params(&self) -> TripletBuilderParams {
+        self.params
+    }
+
+    /// Returns the number of rows of the matrix.
+    #[inline]
+    pub fn nrows(&self) -> usize {
+        self.nrows
+    }
+
+    /// Returns the number of columns of the matrix.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.ncols
+    }
+
+    /// Returns the number of pushed entries, including duplicates.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.row_ind.len()
+    }
+
+    /// Returns `true` if no entries have been pushed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.row_ind.is_empty()
+    }
+
+    /// Removes all the entries, keeping the allocated storage.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.row_ind.clear();
+        self.col_ind.clear();
+        self.values.clear();
+    }
+
+    /// Reserves capacity for at least `additional` more entries.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), FaerError> {
+        self.row_ind.try_reserve(additional)?;
+        self.col_ind.try_reserve(additional)?;
+        self.values
+            .try_reserve_exact(additional)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        Ok(())
+    }
+
+    /// Pushes the entry `value` at position `(row, col)`.
+    ///
+    /// # Panics
+    /// Panics if `row >= self.nrows()`.
+    /// Panics if `col >= self.ncols()`.
+    #[inline]
+    #[track_caller]
+    pub fn push(&mut self, row: usize, col: usize, value: E) {
+        assert!(all(row < self.nrows, col < self.ncols));
+        self.row_ind.push(I::truncate(row));
+        self.col_ind.push(I::truncate(col));
+        self.values.push(value.faer_into_units());
+    }
+
+    /// Pushes all the entries of `triplets`.
+    ///
+    /// # Panics
+    /// Panics if any of the row or column indices is out of bounds.
+    #[track_caller]
+    pub fn extend_from_triplets(&mut self, triplets: &[(I, I, E)]) {
+        for &(row, col, value) in triplets {
+            self.push(row.zx(), col.zx(), value);
+        }
+    }
+
+    /// Compiles the entries into a column-major matrix with sorted row indices.
+    #[track_caller]
+    pub fn try_build_col_major(&self) -> Result<SparseColMat<I, E>, TripletError> {
+        let (col_ptr, row_ind, values) =
+            self.build_impl(self.ncols, &self.col_ind, &self.row_ind)?;
+        Ok(SparseColMat {
+            symbolic: SymbolicSparseColMat {
+                nrows: self.nrows,
+                ncols: self.ncols,
+                col_ptr,
+                col_nnz: None,
+                row_ind,
+            },
+            values,
+        })
+    }
+
+    /// Compiles the entries into a row-major matrix with sorted column indices.
+    #[track_caller]
+    pub fn try_build_row_major(&self) -> Result<SparseRowMat<I, E>, TripletError> {
+        // the row-major matrix is built as the column-major storage of its transpose
+        let (row_ptr, col_ind, values) =
+            self.build_impl(self.nrows, &self.row_ind, &self.col_ind)?;
+        Ok(SparseColMat {
+            symbolic: SymbolicSparseColMat {
+                nrows: self.ncols,
+                ncols: self.nrows,
+                col_ptr: row_ptr,
+                col_nnz: None,
+                row_ind: col_ind,
+            },
+            values,
+        }
+        .into_transpose())
+    }
+
+    fn build_impl(
+        &self,
+        n_major: usize,
+        major: &[I],
+        minor: &[I],
+    ) -> Result<(alloc::vec::Vec<I>, alloc::vec::Vec<I>, VecGroup<E>), TripletError> {
+        let len = self.len();
+        let key = |k: usize| (major[k], minor[k]);
+
+        let unsorted = (1..len).find(|&k| key(k - 1) > key(k));
+        let perm = match (unsorted, self.params.order) {
+            (None, _) => None,
+            (Some(pos), OrderPolicy::RequireSorted) => return Err(TripletError::Unsorted { pos }),
+            (Some(_), OrderPolicy::Sort) => {
+                let mut perm = try_collect(0..len)?;
+                // stable sort, so that the most recent duplicate comes last
+                perm.sort_by_key(|&k| key(k));
+                Some(perm)
+            }
+        };
+        let at = |pos: usize| perm.as_ref().map(|perm| perm[pos]).unwrap_or(pos);
+
+        let mut ptr = try_zeroed::<I>(n_major + 1)?;
+        let mut ind = alloc::vec::Vec::new();
+        let mut values = VecGroup::<E>::new();
+        ind.try_reserve_exact(len)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        values
+            .try_reserve_exact(len)
+            .map_err(|_| FaerError::OutOfMemory)?;
+
+        let src = self.values.as_slice();
+        let mut counts = alloc::vec::Vec::new();
+        counts
+            .try_reserve_exact(n_major)
+            .map_err(|_| FaerError::OutOfMemory)?;
+        counts.resize(n_major, 0usize);
+
+        let mut prev = None;
+        for pos in 0..len {
+            let k = at(pos);
+            let value = src.read(k);
+            if prev == Some(key(k)) {
+                let last = values.len() - 1;
+                match self.params.duplicates {
+                    DuplicatePolicy::Sum => {
+                        let old = values.as_slice().read(last);
+                        values.as_slice_mut().write(last, old.faer_add(value));
+                    }
+                    DuplicatePolicy::Overwrite => values.as_slice_mut().write(last, value),
+                    DuplicatePolicy::Error => {
+                        return Err(TripletError::Duplicate {
+                            row: self.row_ind[k].zx(),
+                            col: self.col_ind[k].zx(),
+                        })
+                    }
+                }
+            } else {
+                ind.push(minor[k]);
+                values.push(value.faer_into_units());
+                counts[major[k].zx()] += 1;
+            }
+            prev = Some(key(k));
+        }
+
+        if ind.len() > I::Signed::MAX.zx() {
+            return Err(TripletError::Generic(FaerError::IndexOverflow));
+        }
+        for j in 0..n_major {
+            ptr[j + 1] = ptr[j] + I::truncate(counts[j]);
+        }
+
+        Ok((ptr, ind, values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn builder(params: TripletBuilderParams) -> SparseTripletBuilder<u32, f64> {
+        let mut builder = SparseTripletBuilder::new(3, 4, params);
+        builder.extend_from_triplets(&[
+            (2, 1, 1.0),
+            (0, 3, 2.0),
+            (1, 1, 3.0),
+            (2, 1, 4.0),
+            (0, 0, 5.0),
+        ]);
+        builder
+    }
+
+    #[test]
+    fn test_duplicate_policies() {
+        let sum = builder(Default::default());
+        let expected = SparseColMat::<u32, f64>::try_new_from_triplets(
+            3,
+            4,
+            &[
+                (2, 1, 1.0),
+                (0, 3, 2.0),
+                (1, 1, 3.0),
+                (2, 1, 4.0),
+                (0, 0, 5.0),
+            ],
+        )
+        .unwrap();
+
+        let A = sum.try_build_col_major().unwrap();
+        assert!(A.col_ptrs() == expected.col_ptrs());
+        assert!(A.row_indices() == expected.row_indices());
+        assert!(A.values() == expected.values());
+        assert!(A.values() == &[5.0, 3.0, 5.0, 2.0]);
+
+        let A = sum.try_build_row_major().unwrap();
+        assert!(A.row_ptrs() == &[0, 2, 3, 4]);
+        assert!(A.col_indices() == &[0, 3, 1, 1]);
+        assert!(A.values() == &[5.0, 2.0, 3.0, 5.0]);
+
+        let overwrite = sum.with_params(TripletBuilderParams {
+            duplicates: DuplicatePolicy::Overwrite,
+            ..Default::default()
+        });
+        let A = overwrite.try_build_col_major().unwrap();
+        assert!(A.values() == &[5.0, 3.0, 4.0, 2.0]);
+        let A = overwrite.try_build_row_major().unwrap();
+        assert!(A.values() == &[5.0, 2.0, 3.0, 4.0]);
+
+        let error = overwrite.with_params(TripletBuilderParams {
+            duplicates: DuplicatePolicy::Error,
+            ..Default::default()
+        });
+        assert!(
+            error.try_build_col_major().unwrap_err() == TripletError::Duplicate { row: 2, col: 1 }
+        );
+        assert!(
+            error.try_build_row_major().unwrap_err() == TripletError::Duplicate { row: 2, col: 1 }
+        );
+    }
+
+    #[test]
+    fn test_order_policies() {
+        let params = TripletBuilderParams {
+            order: OrderPolicy::RequireSorted,
+            ..Default::default()
+        };
+        assert!(
+            builder(params).try_build_col_major().unwrap_err() == TripletError::Unsorted { pos: 2 }
+        );
+
+        let mut sorted = SparseTripletBuilder::<u32, f64>::new(2, 3, params);
+        sorted.push(0, 0, 1.0);
+        sorted.push(1, 0, 2.0);
+        sorted.push(1, 0, 3.0);
+        sorted.push(0, 2, 4.0);
+
+        let A = sorted.try_build_col_major().unwrap();
+        assert!(A.col_ptrs() == &[0, 2, 2, 3]);
+        assert!(A.row_indices() == &[0, 1, 0]);
+        assert!(A.values() == &[1.0, 5.0, 4.0]);
+        // (0, 2) comes before (1, 0) in row-major order
+        assert!(sorted.try_build_row_major().unwrap_err() == TripletError::Unsorted { pos: 3 });
+
+        sorted.clear();
+        let A = sorted.try_build_row_major().unwrap();
+        assert!(A.row_ptrs() == &[0, 0, 0]);
+        assert!(A.compute_nnz() == 0);
+    }
+}
@@ -0,0 +1,205 @@
+use super::meanvar::from_usize;
+use crate::{
+    linalg::fft::{autocorrelation, NativeFft},
+    prelude::*,
+    RealField,
+};
+use equator::assert;
+use reborrow::*;
+
+/// Specifies how the autocovariance is computed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AutocovMethod {
+    /// Each lag is computed as a dot product, with a cost proportional to `n * (max_lag + 1)`.
+    Direct,
+    /// All the lags are computed at once from the Fourier transform of the series, with a cost
+    /// proportional to `n * log(n)`. This is faster for long series and large lag counts.
+    Fft,
+}
+
+fn autocov_impl<E: RealField>(
+    out: ColMut<'_, E>,
+    col: ColRef<'_, E>,
+    method: AutocovMethod,
+    normalize: bool,
+) {
+    let mut out = out;
+    let n = col.nrows();
+    if n == 0 {
+        out.fill(E::faer_nan());
+        return;
+    }
+
+    let n_e = from_usize::<E>(n);
+    let mut mean = E::faer_zero();
+    for i in 0..n {
+        mean = mean.faer_add(col.read(i));
+    }
+    let mean = mean.faer_div(n_e);
+    let centered = Col::<E>::from_fn(n, |i| col.read(i).faer_sub(mean));
+
+    match method {
+        AutocovMethod::Direct => {
+            for k in 0..out.nrows() {
+                let acov = if k < n {
+                    let lhs = centered.as_ref().subrows(0, n - k);
+                    let rhs = centered.as_ref().subrows(k, n - k);
+                    (lhs.transpose() * rhs).faer_div(n_e)
+                } else {
+                    E::faer_zero()
+                };
+                out.write(k, acov);
+            }
+        }
+        AutocovMethod::Fft => {
+            let nlags = Ord::min(out.nrows(), n);
+            let (mut head, mut tail) = out.rb_mut().split_at_mut(nlags);
+            autocorrelation(head.rb_mut(), centered.as_ref(), &NativeFft);
+            for k in 0..nlags {
+                head.write(k, head.read(k).faer_div(n_e));
+            }
+            tail.fill_zero();
+        }
+    }
+
+    if normalize {
+        let acov0 = out.read(0);
+        for k in 0..out.nrows() {
+            out.write(k, out.read(k).faer_div(acov0));
+        }
+    }
+}
+
+/// Computes the autocovariance of the time series `col` for the lags `0..=max_lag`, and stores
+/// the result in `out`.
+///
+/// The `k`-th entry of `out` is `sum((x[t] - mean) * (x[t + k] - mean)) / n`, where the sum is
+/// taken over `0 <= t < n - k`. Lags greater than or equal to `n` have zero autocovariance.
+/// `method` selects between the direct sums and the FFT-based computation, which only differ by
+/// rounding errors.
+///
+/// # Panics
+/// Panics if `out.nrows() != max_lag + 1`.
+#[track_caller]
+pub fn autocov<E: RealField>(
+    out: ColMut<'_, E>,
+    col: ColRef<'_, E>,
+    max_lag: usize,
+    method: AutocovMethod,
+) {
+    assert!(all(out.nrows() == max_lag + 1));
+    autocov_impl(out, col, method, false);
+}
+
+/// Computes the autocorrelation of the time series `col` for the lags `0..=max_lag`, and stores
+/// the result in `out`.
+///
+/// The result is the autocovariance computed by [`autocov`], divided by the variance of the
+/// series, so that the first entry of `out` is one.
+///
+/// # Panics
+/// Panics if `out.nrows() != max_lag + 1`.
+#[track_caller]
+pub fn autocorr<E: RealField>(
+    out: ColMut<'_, E>,
+    col: ColRef<'_, E>,
+    max_lag: usize,
+    method: AutocovMethod,
+) {
+    assert!(all(out.nrows() == max_lag + 1));
+    autocov_impl(out, col, method, true);
+}
+
+/// Computes the autocovariance of each column of `mat` for the lags `0..=max_lag`, and stores
+/// the result in the corresponding column of `out`.
+///
+/// See [`autocov`] for the conventions.
+///
+/// # Panics
+/// Panics if `out.nrows() != max_lag + 1`, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn autocov_mat<E: RealField>(
+    out: MatMut<'_, E>,
+    mat: MatRef<'_, E>,
+    max_lag: usize,
+    method: AutocovMethod,
+) {
+    assert!(all(out.nrows() == max_lag + 1, out.ncols() == mat.ncols()));
+    let mut out = out;
+    for j in 0..mat.ncols() {
+        autocov_impl(out.rb_mut().col_mut(j), mat.col(j), method, false);
+    }
+}
+
+/// Computes the autocorrelation of each column of `mat` for the lags `0..=max_lag`, and stores
+/// the result in the corresponding column of `out`.
+///
+/// See [`autocorr`] for the conventions.
+///
+/// # Panics
+/// Panics if `out.nrows() != max_lag + 1`, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn autocorr_mat<E: RealField>(
+    out: MatMut<'_, E>,
+    mat: MatRef<'_, E>,
+    max_lag: usize,
+    method: AutocovMethod,
+) {
+    assert!(all(out.nrows() == max_lag + 1, out.ncols() == mat.ncols()));
+    let mut out = out;
+    for j in 0..mat.ncols() {
+        autocov_impl(out.rb_mut().col_mut(j), mat.col(j), method, true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_autocorr() {
+        let x = col![1.0, 2.0, 3.0, 4.0f64];
+        // centered: [-1.5, -0.5, 0.5, 1.5]
+        let mut acov = Col::<f64>::zeros(6);
+        autocov(acov.as_mut(), x.as_ref(), 5, AutocovMethod::Direct);
+        let target = [5.0 / 4.0, 1.25 / 4.0, -1.5 / 4.0, -2.25 / 4.0, 0.0, 0.0];
+        for k in 0..6 {
+            assert!((acov[k] - target[k]).abs() < 1e-12);
+        }
+
+        let mut acorr = Col::<f64>::zeros(3);
+        autocorr(acorr.as_mut(), x.as_ref(), 2, AutocovMethod::Direct);
+        assert!(acorr[0] == 1.0);
+        assert!((acorr[1] - 0.25).abs() < 1e-12);
+        assert!((acorr[2] + 0.3).abs() < 1e-12);
+
+        let A = mat![[1.0, 5.0], [2.0, 1.0], [3.0, 4.0], [4.0, 2.0f64]];
+        let mut out = Mat::<f64>::zeros(3, 2);
+        autocorr_mat(out.as_mut(), A.as_ref(), 2, AutocovMethod::Direct);
+        assert!(out.col(0) == acorr);
+        let mut acorr1 = Col::<f64>::zeros(3);
+        autocorr(acorr1.as_mut(), A.col(1), 2, AutocovMethod::Direct);
+        assert!(out.col(1) == acorr1);
+    }
+
+    #[test]
+    fn test_autocorr_fft() {
+        // non power of two length, with more lags than samples
+        let A = Mat::<f64>::from_fn(37, 3, |i, j| ((i * (j + 2)) as f64).sin() + (i % 5) as f64);
+        for max_lag in [0, 5, 36, 40] {
+            let mut direct = Mat::<f64>::zeros(max_lag + 1, 3);
+            let mut fft = Mat::<f64>::zeros(max_lag + 1, 3);
+            autocov_mat(direct.as_mut(), A.as_ref(), max_lag, AutocovMethod::Direct);
+            autocov_mat(fft.as_mut(), A.as_ref(), max_lag, AutocovMethod::Fft);
+            assert!((&direct - &fft).norm_max() < 1e-12);
+
+            autocorr_mat(direct.as_mut(), A.as_ref(), max_lag, AutocovMethod::Direct);
+            autocorr_mat(fft.as_mut(), A.as_ref(), max_lag, AutocovMethod::Fft);
+            assert!((&direct - &fft).norm_max() < 1e-12);
+            for j in 0..3 {
+                assert!(fft.read(0, j) == 1.0);
+            }
+        }
+    }
+}
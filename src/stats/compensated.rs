@@ -0,0 +1,405 @@
+use super::{meanvar::should_split, NanHandling};
+use crate::{
+    linalg::entity::{pulp, SimdGroupFor},
+    prelude::*,
+    utils::{
+        simd::SimdFor,
+        slice::{SliceGroup, SliceGroupMut},
+    },
+    Parallelism, RealField,
+};
+use core::iter::zip;
+use equator::assert;
+use pulp::{Read, Write};
+use reborrow::*;
+
+/// Adds `x` to the running sum `(sum, comp)` using Neumaier's compensated summation, where `comp`
+/// accumulates the rounding errors of the additions.
+#[inline(always)]
+fn neumaier<E: RealField>(sum: E, comp: E, x: E) -> (E, E) {
+    let t = sum.faer_add(x);
+    let err = if sum.faer_abs() >= x.faer_abs() {
+        sum.faer_sub(t).faer_add(x)
+    } else {
+        x.faer_sub(t).faer_add(sum)
+    };
+    (t, comp.faer_add(err))
+}
+
+#[inline(always)]
+fn neumaier_simd<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    sum: SimdGroupFor<E, S>,
+    comp: SimdGroupFor<E, S>,
+    x: SimdGroupFor<E, S>,
+) -> (SimdGroupFor<E, S>, SimdGroupFor<E, S>) {
+    let t = simd.add(sum, x);
+    let sum_is_larger = simd.greater_than_or_equal(simd.abs(sum), simd.abs(x));
+    let err = simd.select(
+        sum_is_larger,
+        simd.add(simd.sub(sum, t), x),
+        simd.add(simd.sub(x, t), sum),
+    );
+    (t, simd.add(comp, err))
+}
+
+/// Applies the accumulated correction to the sum. The correction is meaningless if the sum
+/// overflowed, in which case the sum is returned as-is.
+#[inline(always)]
+fn finalize<E: RealField>(sum: E, comp: E) -> E {
+    if sum.faer_is_finite() {
+        sum.faer_add(comp)
+    } else {
+        sum
+    }
+}
+
+fn col_sum_compensated_col_major<E: RealField>(
+    sum: ColMut<'_, E>,
+    comp: ColMut<'_, E>,
+    count: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    ignore_nan: bool,
+) {
+    struct Impl<'a, E: RealField> {
+        sum: ColMut<'a, E>,
+        comp: ColMut<'a, E>,
+        count: ColMut<'a, E>,
+        mat: MatRef<'a, E>,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                sum,
+                comp,
+                count,
+                mat,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let offset = simd.align_offset_ptr(mat.as_ptr(), mat.nrows());
+            let mut sum = SliceGroupMut::<'_, E>::new(sum.try_as_slice_mut().unwrap());
+            let mut comp = SliceGroupMut::<'_, E>::new(comp.try_as_slice_mut().unwrap());
+            let mut count = SliceGroupMut::<'_, E>::new(count.try_as_slice_mut().unwrap());
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                ignore_nan: bool,
+                mut sum: impl Write<Output = SimdGroupFor<E, S>>,
+                mut comp: impl Write<Output = SimdGroupFor<E, S>>,
+                mut count: impl Write<Output = SimdGroupFor<E, S>>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(zero);
+                let cnt = count.read_or(zero);
+                let (val, cnt) = if ignore_nan {
+                    let is_not_nan = simd.less_than_or_equal(val, val);
+                    (
+                        simd.select(is_not_nan, val, zero),
+                        simd.select(is_not_nan, simd.add(cnt, simd.splat(E::faer_one())), cnt),
+                    )
+                } else {
+                    (val, simd.add(cnt, simd.splat(E::faer_one())))
+                };
+
+                let (s, c) = neumaier_simd(simd, sum.read_or(zero), comp.read_or(zero), val);
+                sum.write(s);
+                comp.write(c);
+                count.write(cnt);
+            }
+
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (head, body, tail) = simd.as_aligned_simd(col, offset);
+                let (sum_head, sum_body, sum_tail) = simd.as_aligned_simd_mut(sum.rb_mut(), offset);
+                let (comp_head, comp_body, comp_tail) =
+                    simd.as_aligned_simd_mut(comp.rb_mut(), offset);
+                let (count_head, count_body, count_tail) =
+                    simd.as_aligned_simd_mut(count.rb_mut(), offset);
+
+                process(simd, ignore_nan, sum_head, comp_head, count_head, head);
+                for (((sum, comp), count), x) in zip(
+                    zip(
+                        zip(sum_body.into_mut_iter(), comp_body.into_mut_iter()),
+                        count_body.into_mut_iter(),
+                    ),
+                    body.into_ref_iter(),
+                ) {
+                    process(simd, ignore_nan, sum, comp, count, x);
+                }
+                process(simd, ignore_nan, sum_tail, comp_tail, count_tail, tail);
+            }
+        }
+    }
+
+    E::Simd::default().dispatch(Impl {
+        sum,
+        comp,
+        count,
+        mat,
+        ignore_nan,
+    });
+}
+
+fn col_sum_compensated_impl<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mean: bool,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let m = mat.nrows();
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    let mut comp = Col::<E>::zeros(m);
+    let mut count = Col::<E>::zeros(m);
+    out.fill_zero();
+
+    if mat.row_stride() == 1 && out.row_stride() == 1 {
+        col_sum_compensated_col_major(out.rb_mut(), comp.as_mut(), count.as_mut(), mat, ignore_nan);
+    } else {
+        for j in 0..mat.ncols() {
+            for i in 0..m {
+                let x = mat.read(i, j);
+                if ignore_nan && x.faer_is_nan() {
+                    continue;
+                }
+                let (s, c) = neumaier(out.read(i), comp.read(i), x);
+                out.write(i, s);
+                comp.write(i, c);
+                count.write(i, count.read(i).faer_add(E::faer_one()));
+            }
+        }
+    }
+
+    for i in 0..m {
+        let sum = finalize(out.read(i), comp.read(i));
+        out.write(
+            i,
+            if mean {
+                sum.faer_mul(count.read(i).faer_inv())
+            } else {
+                sum
+            },
+        );
+    }
+}
+
+fn col_sum_compensated_par<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mean: bool,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        col_sum_compensated_impl(out, mat, mean, nan);
+        return;
+    }
+
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_sum_compensated_par(out_top, mat_top, mean, nan, parallelism),
+        |parallelism| col_sum_compensated_par(out_bot, mat_bot, mean, nan, parallelism),
+        parallelism,
+    );
+}
+
+/// Computes the sum of the columns of `mat` using compensated summation, and stores the result
+/// in `out`.
+///
+/// This is slower than [`col_sum`](super::col_sum), but the result is accurate to a few units in
+/// the last place even when the data is badly scaled or the terms cancel out.
+#[track_caller]
+pub fn col_sum_compensated<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_sum_compensated_par(out, mat, false, nan, parallelism);
+}
+
+/// Computes the sum of the rows of `mat` using compensated summation, and stores the result in
+/// `out`.
+///
+/// See [`col_sum_compensated`] for details.
+#[track_caller]
+pub fn row_sum_compensated<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_sum_compensated_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        false,
+        nan,
+        parallelism,
+    );
+}
+
+/// Computes the mean of the columns of `mat` using compensated summation, and stores the result
+/// in `out`.
+///
+/// See [`col_sum_compensated`] for details.
+#[track_caller]
+pub fn col_mean_compensated<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_sum_compensated_par(out, mat, true, nan, parallelism);
+}
+
+/// Computes the mean of the rows of `mat` using compensated summation, and stores the result in
+/// `out`.
+///
+/// See [`col_sum_compensated`] for details.
+#[track_caller]
+pub fn row_mean_compensated<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_sum_compensated_par(out.transpose_mut(), mat.transpose(), true, nan, parallelism);
+}
+
+/// Computes the dot product of `lhs` and `rhs` using compensated summation of the products.
+#[track_caller]
+pub fn dot_compensated<E: RealField>(lhs: ColRef<'_, E>, rhs: ColRef<'_, E>) -> E {
+    struct Impl<'a, E: RealField> {
+        lhs: ColRef<'a, E>,
+        rhs: ColRef<'a, E>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = E;
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { lhs, rhs } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let offset = simd.align_offset_ptr(lhs.as_ptr(), lhs.nrows());
+            let lhs = SliceGroup::<'_, E>::new(lhs.try_as_slice().unwrap());
+            let rhs = SliceGroup::<'_, E>::new(rhs.try_as_slice().unwrap());
+            let (lhs_head, lhs_body, lhs_tail) = simd.as_aligned_simd(lhs, offset);
+            let (rhs_head, rhs_body, rhs_tail) = simd.as_aligned_simd(rhs, offset);
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                sum: SimdGroupFor<E, S>,
+                comp: SimdGroupFor<E, S>,
+                x: impl Read<Output = SimdGroupFor<E, S>>,
+                y: impl Read<Output = SimdGroupFor<E, S>>,
+            ) -> (SimdGroupFor<E, S>, SimdGroupFor<E, S>) {
+                let zero = simd.splat(E::faer_zero());
+                neumaier_simd(simd, sum, comp, simd.mul(x.read_or(zero), y.read_or(zero)))
+            }
+
+            let mut sum = simd.splat(E::faer_zero());
+            let mut comp = simd.splat(E::faer_zero());
+
+            (sum, comp) = process(simd, sum, comp, lhs_head, rhs_head);
+            for (x, y) in zip(lhs_body.into_ref_iter(), rhs_body.into_ref_iter()) {
+                (sum, comp) = process(simd, sum, comp, x, y);
+            }
+            (sum, comp) = process(simd, sum, comp, lhs_tail, rhs_tail);
+
+            let sum = simd.rotate_left(sum, offset.rotate_left_amount());
+            let comp = simd.rotate_left(comp, offset.rotate_left_amount());
+            finalize(simd.reduce_add(sum), simd.reduce_add(comp))
+        }
+    }
+
+    assert!(all(lhs.nrows() == rhs.nrows()));
+
+    let mut lhs = lhs;
+    let mut rhs = rhs;
+    if lhs.row_stride() < 0 {
+        lhs = lhs.reverse_rows();
+        rhs = rhs.reverse_rows();
+    }
+
+    if lhs.row_stride() == 1 && rhs.row_stride() == 1 {
+        E::Simd::default().dispatch(Impl { lhs, rhs })
+    } else {
+        let mut sum = E::faer_zero();
+        let mut comp = E::faer_zero();
+        for i in 0..lhs.nrows() {
+            (sum, comp) = neumaier(sum, comp, lhs.read(i).faer_mul(rhs.read(i)));
+        }
+        finalize(sum, comp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_compensated() {
+        let m = 19;
+        let n = 1003;
+        // each row is `[1e16, 1, 1, ..., 1, -1e16]`, whose naive sum loses all the ones
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if j == 0 {
+                1e16
+            } else if j == n - 1 {
+                -1e16
+            } else if i == 4 && j == 7 {
+                f64::NAN
+            } else {
+                1.0
+            }
+        });
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+
+        for A in [A.as_ref(), A_row_major] {
+            let mut sum = Col::<f64>::zeros(m);
+            col_sum_compensated(sum.as_mut(), A, NanHandling::Ignore, Parallelism::None);
+            for i in 0..m {
+                let target = if i == 4 {
+                    (n - 3) as f64
+                } else {
+                    (n - 2) as f64
+                };
+                assert!(sum[i] == target);
+            }
+
+            col_sum_compensated(sum.as_mut(), A, NanHandling::Propagate, Parallelism::None);
+            assert!(sum[4].is_nan());
+            assert!(sum[0] == (n - 2) as f64);
+
+            let mut mean = Col::<f64>::zeros(m);
+            col_mean_compensated(mean.as_mut(), A, NanHandling::Ignore, Parallelism::None);
+            assert!((mean[0] - (n - 2) as f64 / n as f64).abs() < 1e-15);
+        }
+
+        let x = Col::<f64>::from_fn(n, |i| A.read(0, i));
+        let y = Col::<f64>::from_fn(n, |_| 1.0);
+        assert!(dot_compensated(x.as_ref(), y.as_ref()) == (n - 2) as f64);
+        assert!(dot_compensated(A.row(0).transpose(), y.as_ref()) == (n - 2) as f64);
+    }
+}
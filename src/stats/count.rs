@@ -0,0 +1,246 @@
+use super::{meanvar::col_non_nan_count, NanHandling};
+use crate::{prelude::*, ComplexField, RealField};
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use equator::assert;
+
+fn col_nan_count_impl<E: ComplexField>(out: &mut [usize], mat: MatRef<'_, E>) {
+    col_non_nan_count(out, mat);
+    for count in out {
+        *count = mat.ncols() - *count;
+    }
+}
+
+/// Counts the NaN values in the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the number of NaN values in the `i`-th row of `mat`. A complex
+/// value is considered NaN if either its real or imaginary part is NaN.
+#[track_caller]
+pub fn col_nan_count<E: ComplexField>(out: &mut [usize], mat: MatRef<'_, E>) {
+    assert!(all(out.len() == mat.nrows()));
+    col_nan_count_impl(out, mat);
+}
+
+/// Counts the NaN values in the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the number of NaN values in the `j`-th column of `mat`. A complex
+/// value is considered NaN if either its real or imaginary part is NaN.
+#[track_caller]
+pub fn row_nan_count<E: ComplexField>(out: &mut [usize], mat: MatRef<'_, E>) {
+    assert!(all(out.len() == mat.ncols()));
+    col_nan_count_impl(out, mat.transpose());
+}
+
+/// Returns a matrix with the same dimensions as `mat`, whose entries are one where the
+/// corresponding entry of `mat` is finite, and zero otherwise.
+pub fn is_finite_mask<E: ComplexField>(mat: MatRef<'_, E>) -> Mat<E::Real> {
+    Mat::from_fn(mat.nrows(), mat.ncols(), |i, j| {
+        if mat.read(i, j).faer_is_finite() {
+            E::Real::faer_one()
+        } else {
+            E::Real::faer_zero()
+        }
+    })
+}
+
+/// Sorts `buf` and calls `f` with the first value and the size of each group of values within
+/// `tolerance` of the smallest value of the group, in increasing order.
+fn for_each_group<E: RealField>(buf: &mut [E], tolerance: E, mut f: impl FnMut(E, usize)) {
+    // nans are filtered out before sorting, so the comparison is never unordered
+    buf.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+    let mut start = 0;
+    while start < buf.len() {
+        let first = buf[start];
+        let mut end = start + 1;
+        while end < buf.len() && buf[end].faer_sub(first) <= tolerance {
+            end += 1;
+        }
+        f(first, end - start);
+        start = end;
+    }
+}
+
+/// Returns the distinct values of `mat` in increasing order, along with the number of times
+/// each of them occurs.
+///
+/// Values are grouped starting from the smallest one, and each group contains the values that
+/// are within `tolerance` of its smallest value, which is the one that is returned. A tolerance
+/// of zero only groups values that compare equal. NaN values are ignored, and can be counted
+/// with [`col_nan_count`] or [`row_nan_count`] instead.
+///
+/// # Panics
+/// Panics if `tolerance` is negative or NaN.
+#[track_caller]
+pub fn unique_counts<E: RealField>(mat: MatRef<'_, E>, tolerance: E) -> (Vec<E>, Vec<usize>) {
+    assert!(tolerance >= E::faer_zero());
+
+    let mut buf = Vec::<E>::with_capacity(mat.nrows() * mat.ncols());
+    for j in 0..mat.ncols() {
+        for i in 0..mat.nrows() {
+            let x = mat.read(i, j);
+            if !x.faer_is_nan() {
+                buf.push(x);
+            }
+        }
+    }
+
+    let mut values = Vec::new();
+    let mut counts = Vec::new();
+    for_each_group(&mut buf, tolerance, |value, count| {
+        values.push(value);
+        counts.push(count);
+    });
+    (values, counts)
+}
+
+fn col_mode_impl<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    tolerance: E,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let mut buf = Vec::<E>::with_capacity(mat.ncols());
+    for i in 0..mat.nrows() {
+        buf.clear();
+        let mut has_nan = false;
+        for j in 0..mat.ncols() {
+            let x = mat.read(i, j);
+            if x.faer_is_nan() {
+                has_nan = true;
+            } else {
+                buf.push(x);
+            }
+        }
+
+        let mut mode = E::faer_nan();
+        if !(has_nan && nan == NanHandling::Propagate) {
+            let mut max_count = 0;
+            for_each_group(&mut buf, tolerance, |value, count| {
+                // groups are visited in increasing order, so ties keep the smallest value
+                if count > max_count {
+                    max_count = count;
+                    mode = value;
+                }
+            });
+        }
+        out.write(i, mode);
+    }
+}
+
+/// Computes the mode (most frequent value) of the columns of `mat` and stores the result in
+/// `out`.
+///
+/// The `i`-th entry of `out` is the mode of the `i`-th row of `mat`. Values are grouped as in
+/// [`unique_counts`], and if several groups occur equally often, the smallest value is chosen.
+/// If no non-NaN values are available, the result is NaN.
+///
+/// # Panics
+/// Panics if `tolerance` is negative or NaN, or if `out.nrows() != mat.nrows()`.
+#[track_caller]
+pub fn col_mode<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    tolerance: E,
+    nan: NanHandling,
+) {
+    assert!(all(out.nrows() == mat.nrows(), tolerance >= E::faer_zero()));
+    col_mode_impl(out, mat, tolerance, nan);
+}
+
+/// Computes the mode (most frequent value) of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the mode of the `j`-th column of `mat`. Values are grouped as in
+/// [`unique_counts`], and if several groups occur equally often, the smallest value is chosen.
+/// If no non-NaN values are available, the result is NaN.
+///
+/// # Panics
+/// Panics if `tolerance` is negative or NaN, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn row_mode<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    tolerance: E,
+    nan: NanHandling,
+) {
+    assert!(all(out.ncols() == mat.ncols(), tolerance >= E::faer_zero()));
+    col_mode_impl(out.transpose_mut(), mat.transpose(), tolerance, nan);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_nan_count() {
+        let nan = f64::NAN;
+        let inf = f64::INFINITY;
+        let A = mat![[1.0, nan, 3.0], [nan, nan, inf], [0.0, 1.0, -inf]];
+
+        let mut col_count = [0usize; 3];
+        col_nan_count(&mut col_count, A.as_ref());
+        assert!(col_count == [1, 2, 0]);
+
+        let mut row_count = [0usize; 3];
+        row_nan_count(&mut row_count, A.as_ref());
+        assert!(row_count == [1, 2, 0]);
+
+        // long enough to go through the vectorized counts, in both layouts
+        let B = Mat::<f64>::from_fn(37, 19, |i, j| if (i * j) % 5 == 1 { nan } else { 1.0 });
+        let target = |i: usize, n: usize| (0..n).filter(|j| (i * j) % 5 == 1).count();
+        let B_row_major = B.transpose().to_owned();
+        for B in [B.as_ref(), B_row_major.transpose()] {
+            let mut col_count = [0usize; 37];
+            col_nan_count(&mut col_count, B);
+            assert!(col_count == core::array::from_fn(|i| target(i, 19)));
+
+            let mut row_count = [0usize; 19];
+            row_nan_count(&mut row_count, B);
+            assert!(row_count == core::array::from_fn(|j| target(j, 37)));
+        }
+
+        let mask = is_finite_mask(A.as_ref());
+        assert!(mask == mat![[1.0, 0.0, 1.0], [0.0, 0.0, 0.0], [1.0, 1.0, 0.0]]);
+
+        let B = mat![[c64::new(1.0, nan), c64::new(1.0, 2.0)]];
+        let mut col_count = [0usize; 1];
+        col_nan_count(&mut col_count, B.as_ref());
+        assert!(col_count == [1]);
+        assert!(is_finite_mask(B.as_ref()) == mat![[0.0, 1.0]]);
+    }
+
+    #[test]
+    fn test_mode_unique_counts() {
+        let nan = f64::NAN;
+        let A = mat![
+            [3.0, 1.0, 3.0, 1.0, 2.0f64],
+            [1.0, 1.0001, 2.0, nan, 0.9999],
+        ];
+
+        let (values, counts) = unique_counts(A.as_ref(), 0.0);
+        assert!(values == [0.9999, 1.0, 1.0001, 2.0, 3.0]);
+        assert!(counts == [1, 3, 1, 2, 2]);
+
+        let (values, counts) = unique_counts(A.as_ref(), 1e-3);
+        assert!(values == [0.9999, 2.0, 3.0]);
+        assert!(counts == [5, 2, 2]);
+
+        let mut out = Col::<f64>::zeros(2);
+        col_mode(out.as_mut(), A.as_ref(), 0.0, NanHandling::Ignore);
+        // ties are broken towards the smallest value
+        assert!(out == col![1.0, 0.9999]);
+
+        col_mode(out.as_mut(), A.as_ref(), 1e-3, NanHandling::Ignore);
+        assert!(out == col![1.0, 0.9999]);
+
+        col_mode(out.as_mut(), A.as_ref(), 1e-3, NanHandling::Propagate);
+        assert!(out[0] == 1.0);
+        assert!(out[1].is_nan());
+
+        let mut out = Row::<f64>::zeros(5);
+        row_mode(out.as_mut(), A.as_ref(), 0.0, NanHandling::Ignore);
+        assert!(out == row![1.0, 1.0, 2.0, 1.0, 0.9999]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::{
+    linalg::matmul::{
+        matmul,
+        triangular::{self, BlockStructure},
+    },
+    prelude::*,
+    ComplexField, Parallelism,
+};
+use equator::assert;
+use reborrow::*;
+
+/// Computes the Gram matrix `Xᴴ X` of the columns of `x`, and stores the result in `out`.
+///
+/// Only the lower triangle is computed with a matrix multiplication, and the upper triangle is
+/// filled in from it.
+///
+/// # Panics
+/// Panics if `out` is not a square matrix of dimension `x.ncols()`.
+#[track_caller]
+pub fn gram<E: ComplexField>(out: MatMut<'_, E>, x: MatRef<'_, E>, parallelism: Parallelism) {
+    assert!(all(out.nrows() == x.ncols(), out.ncols() == x.ncols()));
+    let mut out = out;
+    let n = out.nrows();
+
+    triangular::matmul(
+        out.rb_mut(),
+        BlockStructure::TriangularLower,
+        x.adjoint(),
+        BlockStructure::Rectangular,
+        x,
+        BlockStructure::Rectangular,
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+
+    for j in 0..n {
+        for i in 0..j {
+            out.write(i, j, out.read(j, i).faer_conj());
+        }
+    }
+}
+
+/// Returns the inverse of the norm of each row of `mat`, or zero for rows with zero norm.
+fn inv_row_norms<E: ComplexField>(mat: MatRef<'_, E>) -> Col<E::Real> {
+    Col::from_fn(mat.nrows(), |i| {
+        let norm = mat.row(i).norm_l2();
+        if norm == E::Real::faer_zero() {
+            E::Real::faer_zero()
+        } else {
+            norm.faer_inv()
+        }
+    })
+}
+
+/// Computes the cosine similarity between each row of `x` and each row of `y`, and stores the
+/// result in `out`.
+///
+/// The entry at position `(i, j)` is `⟨x_i, y_j⟩ / (‖x_i‖ ‖y_j‖)`, where `x_i` and `y_j` are
+/// the `i`-th row of `x` and the `j`-th row of `y`, and the inner product is linear in the first
+/// argument. If either row has zero norm, the similarity is zero.
+///
+/// # Panics
+/// Panics if `x.ncols() != y.ncols()`, or if `out` doesn't have dimensions
+/// `(x.nrows(), y.nrows())`.
+#[track_caller]
+pub fn cosine_similarity<E: ComplexField>(
+    out: MatMut<'_, E>,
+    x: MatRef<'_, E>,
+    y: MatRef<'_, E>,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        x.ncols() == y.ncols(),
+        out.nrows() == x.nrows(),
+        out.ncols() == y.nrows(),
+    ));
+
+    let mut out = out;
+    let inv_x = inv_row_norms(x);
+    let inv_y = inv_row_norms(y);
+
+    // the rows are not normalized before the product, instead the result is scaled by the inverse
+    // norms, which avoids copying the inputs
+    matmul(
+        out.rb_mut(),
+        x,
+        y.adjoint(),
+        None,
+        E::faer_one(),
+        parallelism,
+    );
+    for j in 0..out.ncols() {
+        let inv_y = inv_y.read(j);
+        zipped!(out.rb_mut().col_mut(j), inv_x.as_ref()).for_each(
+            #[inline(always)]
+            |unzipped!(mut out, inv_x)| {
+                out.write(out.read().faer_scale_real(inv_x.read().faer_mul(inv_y)))
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_gram() {
+        let x = Mat::<c64>::from_fn(7, 4, |i, j| c64::new((i + j) as f64, i as f64 - j as f64));
+        let mut out = Mat::<c64>::zeros(4, 4);
+        gram(out.as_mut(), x.as_ref(), Parallelism::None);
+        let target = x.adjoint() * &x;
+        assert!((&out - &target).norm_max() < 1e-10);
+    }
+
+    #[test]
+    fn test_cosine_similarity() {
+        let x = mat![[1.0, 0.0], [1.0, 1.0], [0.0, 0.0f64]];
+        let y = mat![[2.0, 0.0], [0.0, -3.0f64]];
+        let mut out = Mat::<f64>::zeros(3, 2);
+        cosine_similarity(out.as_mut(), x.as_ref(), y.as_ref(), Parallelism::None);
+
+        let s = 1.0 / 2.0f64.sqrt();
+        let target = mat![[1.0, 0.0], [s, -s], [0.0, 0.0]];
+        assert!((&out - &target).norm_max() < 1e-12);
+    }
+}
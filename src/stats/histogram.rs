@@ -0,0 +1,104 @@
+use crate::{prelude::*, RealField};
+use alloc::{vec, vec::Vec};
+use equator::assert;
+
+/// Returns the index of the bin containing `x`, or `None` if `x` is NaN or lies outside the
+/// edges.
+#[inline]
+fn bin_index<E: RealField>(edges: &[E], x: E) -> Option<usize> {
+    let n_bins = edges.len() - 1;
+    if x.faer_is_nan() || x < edges[0] || x > edges[n_bins] {
+        return None;
+    }
+    // the last bin is closed on the right
+    if x == edges[n_bins] {
+        return Some(n_bins - 1);
+    }
+    Some(edges.partition_point(|edge| *edge <= x) - 1)
+}
+
+#[track_caller]
+fn check_edges<E: RealField>(edges: &[E]) {
+    assert!(edges.len() >= 2);
+    for k in 0..edges.len() - 1 {
+        assert!(edges[k] < edges[k + 1]);
+    }
+}
+
+/// Computes the histogram of the entries of `mat` over the bins delimited by `edges`.
+///
+/// The `k`-th entry of the result is the number of values in `[edges[k], edges[k + 1])`, except
+/// for the last bin which also includes its right edge. NaN values and values outside the edges
+/// are skipped.
+///
+/// # Panics
+/// Panics if `edges` has fewer than two elements, or is not strictly increasing.
+#[track_caller]
+pub fn histogram<E: RealField>(mat: MatRef<'_, E>, edges: &[E]) -> Vec<usize> {
+    check_edges(edges);
+
+    let mut counts = vec![0usize; edges.len() - 1];
+    for j in 0..mat.ncols() {
+        for i in 0..mat.nrows() {
+            if let Some(bin) = bin_index(edges, mat.read(i, j)) {
+                counts[bin] += 1;
+            }
+        }
+    }
+    counts
+}
+
+/// Computes the two-dimensional histogram of the pairs `(x[i], y[i])` over the bins delimited by
+/// `x_edges` and `y_edges`.
+///
+/// The result has `nx * ny` entries, where `nx = x_edges.len() - 1` and
+/// `ny = y_edges.len() - 1`, and the entry at index `kx * ny + ky` is the number of pairs whose
+/// first component lies in the `kx`-th bin of `x_edges` and whose second component lies in the
+/// `ky`-th bin of `y_edges`, with the same conventions as [`histogram`]. Pairs with a NaN
+/// component or a component outside the edges are skipped.
+///
+/// # Panics
+/// Panics if `x` and `y` don't have the same length, or if either set of edges has fewer than
+/// two elements, or is not strictly increasing.
+#[track_caller]
+pub fn histogram2d<E: RealField>(
+    x: ColRef<'_, E>,
+    y: ColRef<'_, E>,
+    x_edges: &[E],
+    y_edges: &[E],
+) -> Vec<usize> {
+    assert!(all(x.nrows() == y.nrows()));
+    check_edges(x_edges);
+    check_edges(y_edges);
+
+    let ny = y_edges.len() - 1;
+    let mut counts = vec![0usize; (x_edges.len() - 1) * ny];
+    for i in 0..x.nrows() {
+        if let (Some(kx), Some(ky)) = (bin_index(x_edges, x.read(i)), bin_index(y_edges, y.read(i)))
+        {
+            counts[kx * ny + ky] += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_histogram() {
+        let nan = f64::NAN;
+        let A = mat![[0.0, 0.5, 1.0, 3.0], [nan, 2.0, -1.0, 2.5f64]];
+
+        let counts = histogram(A.as_ref(), &[0.0, 1.0, 2.0, 3.0]);
+        assert!(counts == [2, 1, 3]);
+
+        let x = col![0.1, 0.9, 0.5, nan, 0.2f64];
+        let y = col![1.5, 0.5, 1.0, 0.0, 5.0f64];
+        let counts = histogram2d(x.as_ref(), y.as_ref(), &[0.0, 0.5, 1.0], &[0.0, 1.0, 2.0]);
+        // (0.1, 1.5) -> (0, 1), (0.9, 0.5) -> (1, 0), (0.5, 1.0) -> (1, 1)
+        assert!(counts == [0, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,633 @@
+use super::{
+    meanvar::{
+        count_if, finalize_col_major_ignore_nan, flush_count, from_usize, index_chunk_size,
+        should_split,
+    },
+    NanHandling,
+};
+use crate::{
+    linalg::entity::{pulp, SimdGroupFor, SimdIndexFor, SimdMaskFor},
+    prelude::*,
+    utils::{
+        simd::SimdFor,
+        slice::{SliceGroup, SliceGroupMut},
+    },
+    ComplexField, Parallelism, RealField,
+};
+use coe::Coerce;
+use core::iter::zip;
+use equator::assert;
+use pulp::{Read, Write};
+use reborrow::*;
+
+/// Returns `true` if the entry of the mask selects the corresponding entry of the data, which is
+/// the case if it is neither zero nor NaN.
+#[inline(always)]
+fn is_selected<E: RealField>(mask: E) -> bool {
+    mask > E::faer_zero() || mask < E::faer_zero()
+}
+
+/// Returns a mask whose lanes are set where the mask register selects the corresponding entry of
+/// the data. This matches [`is_selected`].
+#[inline(always)]
+fn is_selected_simd<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    mask: SimdGroupFor<E, S>,
+) -> SimdMaskFor<E, S> {
+    // `mask != 0` is computed as `|mask| > 0`, which is false for nan
+    simd.greater_than(simd.abs(mask), simd.splat(E::faer_zero()))
+}
+
+/// Returns `acc` where the mask is zero or NaN, and `new` elsewhere.
+#[inline(always)]
+fn select_masked<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    mask: SimdGroupFor<E, S>,
+    new: SimdGroupFor<E, S>,
+    acc: SimdGroupFor<E, S>,
+) -> SimdGroupFor<E, S> {
+    simd.select(is_selected_simd(simd, mask), new, acc)
+}
+
+/// Accumulates the selected entries of each row of `mat` (or their squared deviation from `mean`
+/// if it is provided) in `sum`, and the number of selected entries in `count`.
+fn col_masked_accumulate_col_major_real<E: RealField>(
+    sum: ColMut<'_, E>,
+    count: &mut [usize],
+    mat: MatRef<'_, E>,
+    mean: Option<ColRef<'_, E>>,
+    mask: MatRef<'_, E>,
+    ignore_nan: bool,
+) {
+    struct Impl<'a, E: RealField> {
+        sum: ColMut<'a, E>,
+        count: &'a mut [usize],
+        mat: MatRef<'a, E>,
+        mean: Option<ColRef<'a, E>>,
+        mask: MatRef<'a, E>,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                sum,
+                count,
+                mat,
+                mean,
+                mask,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            // the registers map to consecutive rows, so that the counts can be kept in index
+            // registers, and the remaining rows are handled by scalar code
+            let mut sum = SliceGroupMut::<'_, E>::new(sum.try_as_slice_mut().unwrap());
+            let (n_simd, m_tail) = {
+                let (body, tail) = simd.as_simd_mut(sum.rb_mut());
+                (body.len(), tail.len())
+            };
+            let (count, count_tail) = count.split_at_mut(mat.nrows() - m_tail);
+            let mut simd_count = alloc::vec![simd.index_splat(E::faer_usize_to_index(0)); n_simd];
+            let zero = simd.splat(E::faer_zero());
+            let mean = mean
+                .map(|mean| simd.as_simd(SliceGroup::<'_, E>::new(mean.try_as_slice().unwrap())));
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                ignore_nan: bool,
+                mut sum: impl Write<Output = SimdGroupFor<E, S>>,
+                count: &mut SimdIndexFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+                mean: Option<SimdGroupFor<E, S>>,
+                mask: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(zero);
+                let mask = mask.read_or(zero);
+                let acc = sum.read_or(zero);
+
+                let new = match mean {
+                    None => simd.add(acc, val),
+                    Some(mean) => {
+                        let diff = simd.sub(val, mean);
+                        simd.mul_add_e(diff, diff, acc)
+                    }
+                };
+                let new_count = count_if(simd, is_selected_simd(simd, mask), *count);
+
+                let (new, new_count) = if ignore_nan {
+                    let is_not_nan = simd.less_than_or_equal(val, val);
+                    (
+                        simd.select(is_not_nan, new, acc),
+                        simd.index_select(is_not_nan, new_count, *count),
+                    )
+                } else {
+                    (new, new_count)
+                };
+
+                sum.write(select_masked(simd, mask, new, acc));
+                *count = new_count;
+            }
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let mask_col = SliceGroup::<'_, E>::new(mask.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd.as_simd(col);
+                let (mask_body, mask_tail) = simd.as_simd(mask_col);
+                let (sum_body, mut sum_tail) = simd.as_simd_mut(sum.rb_mut());
+
+                match mean {
+                    None => {
+                        for ((sum, count), (x, mask)) in zip(
+                            zip(sum_body.into_mut_iter(), simd_count.iter_mut()),
+                            zip(body.into_ref_iter(), mask_body.into_ref_iter()),
+                        ) {
+                            process(simd, ignore_nan, sum, count, x, None, mask);
+                        }
+                    }
+                    Some((mean_body, _)) => {
+                        for (((sum, count), (x, mask)), mean) in zip(
+                            zip(
+                                zip(sum_body.into_mut_iter(), simd_count.iter_mut()),
+                                zip(body.into_ref_iter(), mask_body.into_ref_iter()),
+                            ),
+                            mean_body.into_ref_iter(),
+                        ) {
+                            process(
+                                simd,
+                                ignore_nan,
+                                sum,
+                                count,
+                                x,
+                                Some(mean.read_or(zero)),
+                                mask,
+                            );
+                        }
+                    }
+                }
+
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    let x = tail.read(i);
+                    if !is_selected(mask_tail.read(i)) || (ignore_nan && x.faer_is_nan()) {
+                        continue;
+                    }
+                    let new = match mean {
+                        None => x,
+                        Some((_, mean_tail)) => x.faer_sub(mean_tail.read(i)).faer_abs2(),
+                    };
+                    sum_tail.write(i, sum_tail.read(i).faer_add(new));
+                    *count += 1;
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    E::Simd::default().dispatch(Impl {
+        sum,
+        count,
+        mat,
+        mean,
+        mask,
+        ignore_nan,
+    });
+}
+
+/// Computes the sum of the selected entries of each row of `mat` in `sum`, and their number in
+/// `count`.
+fn col_masked_sum_impl<E: ComplexField>(
+    sum: ColMut<'_, E>,
+    count: &mut [usize],
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+) {
+    let mut sum = sum;
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    sum.fill_zero();
+    count.fill(0);
+
+    if coe::is_same::<E, E::Real>()
+        && mat.row_stride() == 1
+        && mask.row_stride() == 1
+        && sum.row_stride() == 1
+    {
+        col_masked_accumulate_col_major_real::<E::Real>(
+            sum.coerce(),
+            count,
+            mat.coerce(),
+            None,
+            mask,
+            ignore_nan,
+        );
+    } else {
+        for j in 0..mat.ncols() {
+            for i in 0..mat.nrows() {
+                let x = mat.read(i, j);
+                if !is_selected(mask.read(i, j)) || (ignore_nan && x.faer_is_nan()) {
+                    continue;
+                }
+                sum.write(i, sum.read(i).faer_add(x));
+                count[i] += 1;
+            }
+        }
+    }
+}
+
+/// Computes the sum of squared deviations from `mean` of the selected entries of each row of
+/// `mat` in `sum`, and their number in `count`.
+fn col_masked_sqdev_impl<E: ComplexField>(
+    sum: ColMut<'_, E::Real>,
+    count: &mut [usize],
+    mat: MatRef<'_, E>,
+    mean: ColRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+) {
+    let mut sum = sum;
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    sum.fill_zero();
+    count.fill(0);
+
+    if coe::is_same::<E, E::Real>()
+        && mat.row_stride() == 1
+        && mean.row_stride() == 1
+        && mask.row_stride() == 1
+        && sum.row_stride() == 1
+    {
+        col_masked_accumulate_col_major_real::<E::Real>(
+            sum,
+            count,
+            mat.coerce(),
+            Some(mean.coerce()),
+            mask,
+            ignore_nan,
+        );
+    } else {
+        for j in 0..mat.ncols() {
+            for i in 0..mat.nrows() {
+                let x = mat.read(i, j);
+                if !is_selected(mask.read(i, j)) || (ignore_nan && x.faer_is_nan()) {
+                    continue;
+                }
+                let diff = x.faer_sub(mean.read(i));
+                sum.write(i, sum.read(i).faer_add(diff.faer_abs2()));
+                count[i] += 1;
+            }
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum MaskedReduction {
+    Sum,
+    Mean,
+}
+
+fn col_masked_par<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    reduction: MaskedReduction,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        let mut out = out;
+        let mut count = alloc::vec![0usize; m];
+        col_masked_sum_impl(out.rb_mut(), &mut count, mat, mask, nan);
+        if reduction == MaskedReduction::Mean {
+            for (i, &count) in count.iter().enumerate() {
+                out.write(
+                    i,
+                    out.read(i)
+                        .faer_scale_real(from_usize::<E::Real>(count).faer_inv()),
+                );
+            }
+        }
+        return;
+    }
+
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    let (mask_top, mask_bot) = mask.split_at_row(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_masked_par(out_top, mat_top, mask_top, reduction, nan, parallelism),
+        |parallelism| col_masked_par(out_bot, mat_bot, mask_bot, reduction, nan, parallelism),
+        parallelism,
+    );
+}
+
+fn col_varm_masked_par<E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        let mut out = out;
+        let mut count = alloc::vec![0usize; m];
+        col_masked_sqdev_impl(out.rb_mut(), &mut count, mat, col_mean, mask, nan);
+        finalize_col_major_ignore_nan(out, &count, Some(1));
+        return;
+    }
+
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    let (mean_top, mean_bot) = col_mean.split_at(m / 2);
+    let (mask_top, mask_bot) = mask.split_at_row(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_varm_masked_par(out_top, mat_top, mean_top, mask_top, nan, parallelism),
+        |parallelism| col_varm_masked_par(out_bot, mat_bot, mean_bot, mask_bot, nan, parallelism),
+        parallelism,
+    );
+}
+
+/// Computes the sum of the columns of `mat` and stores the result in `out`, only including the
+/// entries for which the corresponding entry of `mask` is nonzero.
+///
+/// NaN entries of `mask` don't select the corresponding entries of `mat`. NaN values among the
+/// selected entries are handled according to `nan`. A mask in the right
+/// format can be built from a sentinel-coded data matrix, with
+/// [`is_finite_mask`](super::is_finite_mask), or from a [`Mask`](crate::mat::Mask) with
+/// [`Mask::to_mat`](crate::mat::Mask::to_mat).
+#[track_caller]
+pub fn col_sum_masked<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_masked_par(out, mat, mask, MaskedReduction::Sum, nan, parallelism);
+}
+
+/// Computes the sum of the rows of `mat` and stores the result in `out`, only including the
+/// entries for which the corresponding entry of `mask` is nonzero.
+///
+/// See [`col_sum_masked`] for the conventions.
+#[track_caller]
+pub fn row_sum_masked<E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_masked_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        mask.transpose(),
+        MaskedReduction::Sum,
+        nan,
+        parallelism,
+    );
+}
+
+/// Computes the mean of the columns of `mat` and stores the result in `out`, only including the
+/// entries for which the corresponding entry of `mask` is nonzero.
+///
+/// See [`col_sum_masked`] for the conventions. If no entries are selected, the result is NaN.
+#[track_caller]
+pub fn col_mean_masked<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_masked_par(out, mat, mask, MaskedReduction::Mean, nan, parallelism);
+}
+
+/// Computes the mean of the rows of `mat` and stores the result in `out`, only including the
+/// entries for which the corresponding entry of `mask` is nonzero.
+///
+/// See [`col_sum_masked`] for the conventions. If no entries are selected, the result is NaN.
+#[track_caller]
+pub fn row_mean_masked<E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_masked_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        mask.transpose(),
+        MaskedReduction::Mean,
+        nan,
+        parallelism,
+    );
+}
+
+/// Computes the variance of the columns of `mat` given their mean, and stores the result in
+/// `out`, only including the entries for which the corresponding entry of `mask` is nonzero.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of selected
+/// entries in each row. See [`col_sum_masked`] for the conventions.
+#[track_caller]
+pub fn col_varm_masked<E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        col_mean.nrows() == mat.nrows(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_varm_masked_par(out, mat, col_mean, mask, nan, parallelism);
+}
+
+/// Computes the variance of the rows of `mat` given their mean, and stores the result in `out`,
+/// only including the entries for which the corresponding entry of `mask` is nonzero.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of selected
+/// entries in each column. See [`col_sum_masked`] for the conventions.
+#[track_caller]
+pub fn row_varm_masked<E: ComplexField>(
+    out: RowMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    row_mean: RowRef<'_, E>,
+    mask: MatRef<'_, E::Real>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        row_mean.ncols() == mat.ncols(),
+        mask.nrows() == mat.nrows(),
+        mask.ncols() == mat.ncols(),
+    ));
+    col_varm_masked_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        row_mean.transpose(),
+        mask.transpose(),
+        nan,
+        parallelism,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_masked() {
+        let sentinel = -999.0;
+        let m = 29;
+        let n = 7;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i + 3 * j) % 5 == 0 {
+                sentinel
+            } else if (i * j) % 11 == 3 {
+                f64::NAN
+            } else {
+                (i as f64 * 0.3 + j as f64).sin()
+            }
+        });
+        let mask = Mat::<f64>::from_fn(m, n, |i, j| if A[(i, j)] == sentinel { 0.0 } else { 1.0 });
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+        let mask_row_major = mask.transpose().to_owned();
+        let mask_row_major = mask_row_major.transpose();
+
+        for nan in [NanHandling::Propagate, NanHandling::Ignore] {
+            for (A, mask) in [(A.as_ref(), mask.as_ref()), (A_row_major, mask_row_major)] {
+                let mut sum = Col::<f64>::zeros(m);
+                let mut mean = Col::<f64>::zeros(m);
+                let mut var = Col::<f64>::zeros(m);
+                col_sum_masked(sum.as_mut(), A, mask, nan, Parallelism::None);
+                col_mean_masked(mean.as_mut(), A, mask, nan, Parallelism::None);
+                col_varm_masked(var.as_mut(), A, mean.as_ref(), mask, nan, Parallelism::None);
+
+                for i in 0..m {
+                    let selected = (0..n)
+                        .map(|j| A.read(i, j))
+                        .filter(|&x| x != sentinel)
+                        .filter(|x| nan == NanHandling::Propagate || !x.is_nan())
+                        .collect::<alloc::vec::Vec<_>>();
+
+                    let count = selected.len() as f64;
+                    let target_sum: f64 = selected.iter().sum();
+                    let target_mean = target_sum / count;
+                    let target_var = selected
+                        .iter()
+                        .map(|x| (x - target_mean) * (x - target_mean))
+                        .sum::<f64>()
+                        / (count - 1.0);
+
+                    if target_sum.is_nan() {
+                        assert!(sum[i].is_nan());
+                        assert!(mean[i].is_nan());
+                        assert!(var[i].is_nan());
+                    } else {
+                        assert!((sum[i] - target_sum).abs() < 1e-12);
+                        assert!((mean[i] - target_mean).abs() < 1e-12);
+                        assert!((var[i] - target_var).abs() < 1e-12);
+                    }
+                }
+            }
+        }
+
+        let mut row_sum = Row::<f64>::zeros(n);
+        row_sum_masked(
+            row_sum.as_mut(),
+            A.as_ref(),
+            mask.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        for j in 0..n {
+            let target: f64 = (0..m)
+                .map(|i| A[(i, j)])
+                .filter(|&x| x != sentinel && !x.is_nan())
+                .sum();
+            assert!((row_sum[j] - target).abs() < 1e-12);
+        }
+    }
+
+    #[test]
+    fn test_masked_nan_mask() {
+        let m = 19;
+        let n = 3;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| (i + 2 * j) as f64);
+        // nan entries in the mask should be unselected, both in the vectorized body and in the
+        // scalar fallback
+        let mask = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i + j) % 3 == 0 {
+                f64::NAN
+            } else if (i + j) % 3 == 1 {
+                0.0
+            } else {
+                -1.0
+            }
+        });
+        let A_row_major = A.transpose().to_owned();
+        let mask_row_major = mask.transpose().to_owned();
+
+        for (A, mask) in [
+            (A.as_ref(), mask.as_ref()),
+            (A_row_major.transpose(), mask_row_major.transpose()),
+        ] {
+            let mut sum = Col::<f64>::zeros(m);
+            col_sum_masked(
+                sum.as_mut(),
+                A,
+                mask,
+                NanHandling::Propagate,
+                Parallelism::None,
+            );
+            for i in 0..m {
+                let target: f64 = (0..n)
+                    .filter(|&j| (i + j) % 3 == 2)
+                    .map(|j| A.read(i, j))
+                    .sum();
+                assert!(sum[i] == target);
+            }
+        }
+    }
+}
@@ -1,11 +1,11 @@
 use crate::{
-    linalg::entity::{pulp, SimdCtx, SimdGroupFor, SimdIndexFor},
+    linalg::entity::{pulp, SimdCtx, SimdGroupFor, SimdIndexFor, SimdMaskFor},
     prelude::*,
     utils::{
         simd::SimdFor,
         slice::{RefGroup, SliceGroup, SliceGroupMut},
     },
-    ComplexField, RealField,
+    ComplexField, Parallelism, RealField,
 };
 use coe::Coerce;
 use core::iter::zip;
@@ -24,7 +24,7 @@ pub enum NanHandling {
 }
 
 #[inline(always)]
-fn from_usize<E: RealField>(n: usize) -> E {
+pub(super) fn from_usize<E: RealField>(n: usize) -> E {
     E::faer_from_f64(n as u32 as f64)
         .faer_add(E::faer_from_f64((n as u64 - (n as u32 as u64)) as f64))
 }
@@ -40,6 +40,19 @@ fn reduce<E: RealField, S: pulp::Simd>(non_nan_count: SimdIndexFor<E, S>) -> usi
     acc
 }
 
+/// Computes the variance from the sum of squared deviations `sum` of `count` samples, using
+/// `count - ddof` as the denominator.
+#[inline(always)]
+pub(super) fn var_from_sum<E: RealField>(sum: E, count: usize, ddof: usize) -> E {
+    if count == 0 {
+        E::faer_nan()
+    } else if count <= ddof {
+        E::faer_zero()
+    } else {
+        sum.faer_scale_real(from_usize::<E>(count - ddof).faer_inv())
+    }
+}
+
 fn col_mean_row_major_ignore_nan_real<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
     struct Impl<'a, E: RealField> {
         out: ColMut<'a, E>,
@@ -161,11 +174,13 @@ fn col_varm_row_major_ignore_nan_real<E: RealField>(
     out: ColMut<'_, E>,
     mat: MatRef<'_, E>,
     col_mean: ColRef<'_, E>,
+    ddof: usize,
 ) {
     struct Impl<'a, E: RealField> {
         out: ColMut<'a, E>,
         mat: MatRef<'a, E>,
         col_mean: ColRef<'a, E>,
+        ddof: usize,
     }
 
     impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
@@ -177,6 +192,7 @@ fn col_varm_row_major_ignore_nan_real<E: RealField>(
                 mut out,
                 mat,
                 col_mean,
+                ddof,
             } = self;
             let simd = SimdFor::<E, S>::new(simd);
 
@@ -275,20 +291,19 @@ fn col_varm_row_major_ignore_nan_real<E: RealField>(
                 sum0 = simd.rotate_left(sum0, offset.rotate_left_amount());
                 let sum = simd.reduce_add(sum0);
 
-                let var = if non_nan_count_total == 0 {
-                    E::faer_nan()
-                } else if non_nan_count_total == 1 {
-                    E::faer_zero()
-                } else {
-                    sum.faer_scale_real(from_usize::<E>(non_nan_count_total - 1).faer_inv())
-                };
+                let var = var_from_sum::<E>(sum, non_nan_count_total, ddof);
 
                 out.write(i, var);
             }
         }
     }
 
-    E::Simd::default().dispatch(Impl { out, mat, col_mean });
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        col_mean,
+        ddof,
+    });
 }
 
 fn col_mean_row_major_ignore_nan_cplx<E: RealField>(
@@ -436,11 +451,13 @@ fn col_varm_row_major_ignore_nan_cplx<E: RealField>(
     out: ColMut<'_, E>,
     mat: MatRef<'_, Complex<E>>,
     col_mean: ColRef<'_, Complex<E>>,
+    ddof: usize,
 ) {
     struct Impl<'a, E: RealField> {
         out: ColMut<'a, E>,
         mat: MatRef<'a, Complex<E>>,
         col_mean: ColRef<'a, Complex<E>>,
+        ddof: usize,
     }
 
     impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
@@ -452,6 +469,7 @@ fn col_varm_row_major_ignore_nan_cplx<E: RealField>(
                 mut out,
                 mat,
                 col_mean,
+                ddof,
             } = self;
             let simd_cplx = SimdFor::<Complex<E>, S>::new(simd);
             let simd = SimdFor::<E, S>::new(simd);
@@ -571,20 +589,19 @@ fn col_varm_row_major_ignore_nan_cplx<E: RealField>(
                 sum0 = simd.rotate_left(sum0, offset.rotate_left_amount());
                 let sum = simd.reduce_add(sum0);
 
-                let var = if non_nan_count_total == 0 {
-                    E::faer_nan()
-                } else if non_nan_count_total == 1 {
-                    E::faer_zero()
-                } else {
-                    sum.faer_scale_real(from_usize::<E>(non_nan_count_total - 1).faer_inv())
-                };
+                let var = var_from_sum::<E>(sum, non_nan_count_total, ddof);
 
                 out.write(i, var);
             }
         }
     }
 
-    E::Simd::default().dispatch(Impl { out, mat, col_mean });
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        col_mean,
+        ddof,
+    });
 }
 
 fn col_mean_row_major_ignore_nan_c32(out: ColMut<'_, c32>, mat: MatRef<'_, c32>) {
@@ -867,6 +884,7 @@ fn col_varm_row_major_ignore_nan_c32(
     out: ColMut<'_, f32>,
     mat: MatRef<'_, c32>,
     col_mean: ColRef<'_, c32>,
+    ddof: usize,
 ) {
     type E = f32;
 
@@ -874,6 +892,7 @@ fn col_varm_row_major_ignore_nan_c32(
         out: ColMut<'a, f32>,
         mat: MatRef<'a, c32>,
         col_mean: ColRef<'a, c32>,
+        ddof: usize,
     }
 
     impl pulp::WithSimd for Impl<'_> {
@@ -885,6 +904,7 @@ fn col_varm_row_major_ignore_nan_c32(
                 mut out,
                 mat,
                 col_mean,
+                ddof,
             } = self;
 
             let m = mat.nrows();
@@ -1008,26 +1028,26 @@ fn col_varm_row_major_ignore_nan_c32(
 
                 non_nan_count_total /= 2;
 
-                let var = if non_nan_count_total == 0 {
-                    E::faer_nan()
-                } else if non_nan_count_total == 1 {
-                    E::faer_zero()
-                } else {
-                    sum.faer_scale_real(from_usize::<E>(non_nan_count_total - 1).faer_inv())
-                };
+                let var = var_from_sum::<E>(sum, non_nan_count_total, ddof);
 
                 out.write(i, var);
             }
         }
     }
 
-    <c32 as ComplexField>::Simd::default().dispatch(Impl { out, mat, col_mean });
+    <c32 as ComplexField>::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        col_mean,
+        ddof,
+    });
 }
 
 fn col_varm_row_major_ignore_nan_c64(
     out: ColMut<'_, f64>,
     mat: MatRef<'_, c64>,
     col_mean: ColRef<'_, c64>,
+    ddof: usize,
 ) {
     type E = f64;
 
@@ -1035,6 +1055,7 @@ fn col_varm_row_major_ignore_nan_c64(
         out: ColMut<'a, f64>,
         mat: MatRef<'a, c64>,
         col_mean: ColRef<'a, c64>,
+        ddof: usize,
     }
 
     impl pulp::WithSimd for Impl<'_> {
@@ -1046,6 +1067,7 @@ fn col_varm_row_major_ignore_nan_c64(
                 mut out,
                 mat,
                 col_mean,
+                ddof,
             } = self;
 
             let m = mat.nrows();
@@ -1169,20 +1191,19 @@ fn col_varm_row_major_ignore_nan_c64(
 
                 non_nan_count_total /= 2;
 
-                let var = if non_nan_count_total == 0 {
-                    E::faer_nan()
-                } else if non_nan_count_total == 1 {
-                    E::faer_zero()
-                } else {
-                    sum.faer_scale_real(from_usize::<E>(non_nan_count_total - 1).faer_inv())
-                };
+                let var = var_from_sum::<E>(sum, non_nan_count_total, ddof);
 
                 out.write(i, var);
             }
         }
     }
 
-    <c64 as ComplexField>::Simd::default().dispatch(Impl { out, mat, col_mean });
+    <c64 as ComplexField>::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        col_mean,
+        ddof,
+    });
 }
 
 fn col_mean_propagate<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
@@ -1340,24 +1361,23 @@ fn col_mean_propagate<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
     }
 }
 
-fn row_mean_propagate<E: ComplexField>(out: RowMut<'_, E>, mat: MatRef<'_, E>) {
-    col_mean_propagate(out.transpose_mut(), mat.transpose());
-}
-
 fn col_varm_propagate<E: ComplexField>(
     out: ColMut<'_, E::Real>,
     mat: MatRef<'_, E>,
     col_mean: ColRef<'_, E>,
+    ddof: usize,
 ) {
     fn col_varm_row_major<E: ComplexField>(
         out: ColMut<'_, E::Real>,
         mat: MatRef<'_, E>,
         col_mean: ColRef<'_, E>,
+        ddof: usize,
     ) {
         struct Impl<'a, E: ComplexField> {
             out: ColMut<'a, E::Real>,
             mat: MatRef<'a, E>,
             col_mean: ColRef<'a, E>,
+            ddof: usize,
         }
 
         impl<E: ComplexField> pulp::WithSimd for Impl<'_, E> {
@@ -1369,6 +1389,7 @@ fn col_varm_propagate<E: ComplexField>(
                     mut out,
                     mat,
                     col_mean,
+                    ddof,
                 } = self;
 
                 let simd_real = SimdFor::<E::Real, S>::new(simd);
@@ -1376,7 +1397,7 @@ fn col_varm_propagate<E: ComplexField>(
 
                 let m = mat.nrows();
                 let n = mat.ncols();
-                let one_n1 = from_usize::<E::Real>(n - 1).faer_inv();
+                let one_n1 = from_usize::<E::Real>(n - ddof).faer_inv();
 
                 let offset = simd.align_offset_ptr(mat.as_ptr(), mat.ncols());
                 for i in 0..m {
@@ -1474,18 +1495,25 @@ fn col_varm_propagate<E: ComplexField>(
             }
         }
 
-        E::Simd::default().dispatch(Impl { out, mat, col_mean });
+        E::Simd::default().dispatch(Impl {
+            out,
+            mat,
+            col_mean,
+            ddof,
+        });
     }
 
     fn col_varm_col_major_real<E: RealField>(
         out: ColMut<'_, E>,
         mat: MatRef<'_, E>,
         col_mean: ColRef<'_, E>,
+        ddof: usize,
     ) {
         struct Impl<'a, E: RealField> {
             out: ColMut<'a, E>,
             mat: MatRef<'a, E>,
             col_mean: ColRef<'a, E>,
+            ddof: usize,
         }
 
         impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
@@ -1493,12 +1521,17 @@ fn col_varm_propagate<E: ComplexField>(
 
             #[inline(always)]
             fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
-                let Self { out, mat, col_mean } = self;
+                let Self {
+                    out,
+                    mat,
+                    col_mean,
+                    ddof,
+                } = self;
 
                 let simd = SimdFor::<E, S>::new(simd);
 
                 let n = mat.ncols();
-                let one_n1 = simd.splat(from_usize::<E::Real>(n - 1).faer_inv());
+                let one_n1 = simd.splat(from_usize::<E::Real>(n - ddof).faer_inv());
 
                 let offset = simd.align_offset_ptr(mat.as_ptr(), mat.nrows());
 
@@ -1552,18 +1585,25 @@ fn col_varm_propagate<E: ComplexField>(
             }
         }
 
-        E::Simd::default().dispatch(Impl { out, mat, col_mean });
+        E::Simd::default().dispatch(Impl {
+            out,
+            mat,
+            col_mean,
+            ddof,
+        });
     }
 
     fn col_varm_col_major_cplx<E: RealField>(
         out: ColMut<'_, E>,
         mat: MatRef<'_, Complex<E>>,
         col_mean: ColRef<'_, Complex<E>>,
+        ddof: usize,
     ) {
         struct Impl<'a, E: RealField> {
             out: ColMut<'a, E>,
             mat: MatRef<'a, Complex<E>>,
             col_mean: ColRef<'a, Complex<E>>,
+            ddof: usize,
         }
 
         impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
@@ -1571,13 +1611,18 @@ fn col_varm_propagate<E: ComplexField>(
 
             #[inline(always)]
             fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
-                let Self { out, mat, col_mean } = self;
+                let Self {
+                    out,
+                    mat,
+                    col_mean,
+                    ddof,
+                } = self;
 
                 let simd_cplx = SimdFor::<Complex<E>, S>::new(simd);
                 let simd = SimdFor::<E, S>::new(simd);
 
                 let n = mat.ncols();
-                let one_n1 = simd.splat(from_usize::<E::Real>(n - 1).faer_inv());
+                let one_n1 = simd.splat(from_usize::<E::Real>(n - ddof).faer_inv());
 
                 let offset = simd_cplx.align_offset_ptr(mat.as_ptr(), mat.nrows());
 
@@ -1641,7 +1686,12 @@ fn col_varm_propagate<E: ComplexField>(
             }
         }
 
-        E::Simd::default().dispatch(Impl { out, mat, col_mean });
+        E::Simd::default().dispatch(Impl {
+            out,
+            mat,
+            col_mean,
+            ddof,
+        });
     }
 
     let mut out = out;
@@ -1652,7 +1702,7 @@ fn col_varm_propagate<E: ComplexField>(
         out.fill(E::Real::faer_nan());
         return;
     }
-    if mat.ncols() == 1 {
+    if mat.ncols() <= ddof {
         out.fill_zero();
         return;
     }
@@ -1667,12 +1717,12 @@ fn col_varm_propagate<E: ComplexField>(
     };
 
     if mat.col_stride() == 1 {
-        col_varm_row_major(out, mat, col_mean)
+        col_varm_row_major(out, mat, col_mean, ddof)
     } else if mat.row_stride() == 1 && out.row_stride() == 1 && col_mean.row_stride() == 1 {
         if coe::is_same::<E, E::Real>() {
-            col_varm_col_major_real::<E::Real>(out, mat.coerce(), col_mean.coerce())
+            col_varm_col_major_real::<E::Real>(out, mat.coerce(), col_mean.coerce(), ddof)
         } else if coe::is_same::<E, Complex<E::Real>>() {
-            col_varm_col_major_cplx::<E::Real>(out, mat.coerce(), col_mean.coerce())
+            col_varm_col_major_cplx::<E::Real>(out, mat.coerce(), col_mean.coerce(), ddof)
         } else if coe::is_same::<E, c32>() {
             let m = mat.nrows();
 
@@ -1693,7 +1743,7 @@ fn col_varm_propagate<E: ComplexField>(
             let col_mean =
                 unsafe { col::from_raw_parts::<f32>(col_mean.as_ptr() as *const f32, 2 * m, 1) };
 
-            col_varm_col_major_real::<f32>(tmp.as_mut(), mat, col_mean);
+            col_varm_col_major_real::<f32>(tmp.as_mut(), mat, col_mean, ddof);
             for i in 0..m {
                 out.write(i, tmp.read(2 * i) + tmp.read(2 * i + 1));
             }
@@ -1717,7 +1767,7 @@ fn col_varm_propagate<E: ComplexField>(
             let col_mean =
                 unsafe { col::from_raw_parts::<f64>(col_mean.as_ptr() as *const f64, 2 * m, 1) };
 
-            col_varm_col_major_real::<f64>(tmp.as_mut(), mat, col_mean);
+            col_varm_col_major_real::<f64>(tmp.as_mut(), mat, col_mean, ddof);
             for i in 0..m {
                 out.write(i, tmp.read(2 * i) + tmp.read(2 * i + 1));
             }
@@ -1726,7 +1776,7 @@ fn col_varm_propagate<E: ComplexField>(
         }
     } else {
         let n = mat.ncols();
-        let one_n1 = from_usize::<E::Real>(n - 1).faer_inv();
+        let one_n1 = from_usize::<E::Real>(n - ddof).faer_inv();
 
         out.fill_zero();
         for j in 0..n {
@@ -1739,12 +1789,560 @@ fn col_varm_propagate<E: ComplexField>(
     }
 }
 
-fn row_varm_propagate<E: ComplexField>(
-    out: RowMut<'_, E::Real>,
+/// Returns the number of columns after which the counts in the index registers are flushed to
+/// `usize`, to avoid overflowing them.
+#[inline(always)]
+pub(super) fn index_chunk_size<E: RealField>() -> usize {
+    (if core::mem::size_of::<E::Index>() < core::mem::size_of::<usize>() {
+        1usize << (core::mem::size_of::<E::Index>() * 8)
+    } else {
+        usize::MAX
+    }) / 4
+}
+
+/// Adds the per-lane counts in `simd_count` to `count`, and resets them to zero.
+///
+/// The lanes of the registers are stored contiguously, so the `k`-th lane overall holds the count
+/// of the `k`-th row of the vectorized part of the column.
+#[inline(always)]
+pub(super) fn flush_count<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    count: &mut [usize],
+    simd_count: &mut [SimdIndexFor<E, S>],
+) {
+    let lanes: &[E::Index] = bytemuck::cast_slice(&*simd_count);
+    for (count, &lane) in zip(count.iter_mut(), lanes) {
+        *count += E::faer_index_to_usize(lane);
+    }
+    simd_count.fill(simd.index_splat(E::faer_usize_to_index(0)));
+}
+
+/// Returns `count + 1` in the lanes where `mask` is set, and `count` elsewhere.
+#[inline(always)]
+pub(super) fn count_if<E: RealField, S: pulp::Simd>(
+    simd: SimdFor<E, S>,
+    mask: SimdMaskFor<E, S>,
+    count: SimdIndexFor<E, S>,
+) -> SimdIndexFor<E, S> {
+    simd.index_select(
+        mask,
+        simd.index_add(count, simd.index_splat(E::faer_usize_to_index(1))),
+        count,
+    )
+}
+
+/// Counts the non-NaN values of each row of `mat`, whose columns are contiguous, and stores the
+/// result in `count`.
+fn col_non_nan_count_col_major_real<E: RealField>(count: &mut [usize], mat: MatRef<'_, E>) {
+    struct Impl<'a, E: RealField> {
+        count: &'a mut [usize],
+        mat: MatRef<'a, E>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { count, mat } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+            let zero = simd.splat(E::faer_zero());
+
+            let (n_simd, m_tail) = {
+                let (body, tail) =
+                    simd.as_simd(SliceGroup::<'_, E>::new(mat.col(0).try_as_slice().unwrap()));
+                (body.len(), tail.len())
+            };
+            let (count, count_tail) = count.split_at_mut(mat.nrows() - m_tail);
+            let mut simd_count = vec![simd.index_splat(E::faer_usize_to_index(0)); n_simd];
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd.as_simd(col);
+
+                for (count, x) in zip(simd_count.iter_mut(), body.into_ref_iter()) {
+                    let x = x.read_or(zero);
+                    *count = count_if(simd, simd.less_than_or_equal(x, x), *count);
+                }
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    *count += !tail.read(i).faer_is_nan() as usize;
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    E::Simd::default().dispatch(Impl { count, mat });
+}
+
+/// Counts the non-NaN values of each row of `mat`, whose rows are contiguous, and stores the
+/// result in `count`.
+fn col_non_nan_count_row_major_real<E: RealField>(count: &mut [usize], mat: MatRef<'_, E>) {
+    struct Impl<'a, E: RealField> {
+        count: &'a mut [usize],
+        mat: MatRef<'a, E>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { count, mat } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+            let zero = simd.splat(E::faer_zero());
+
+            let chunk_size = index_chunk_size::<E>();
+            for (i, count) in count.iter_mut().enumerate() {
+                let row = SliceGroup::<'_, E>::new(mat.row(i).try_as_slice().unwrap());
+                let (body, tail) = simd.as_simd(row);
+
+                let mut total = 0usize;
+                let mut start = 0usize;
+                while start < body.len() {
+                    let len = Ord::min(body.len() - start, chunk_size);
+                    let mut non_nan_count = simd.index_splat(E::faer_usize_to_index(0));
+                    for x in body.subslice(start..start + len).into_ref_iter() {
+                        let x = x.read_or(zero);
+                        non_nan_count =
+                            count_if(simd, simd.less_than_or_equal(x, x), non_nan_count);
+                    }
+                    total += reduce::<E, S>(non_nan_count);
+                    start += len;
+                }
+                for k in 0..tail.len() {
+                    total += !tail.read(k).faer_is_nan() as usize;
+                }
+                *count = total;
+            }
+        }
+    }
+
+    E::Simd::default().dispatch(Impl { count, mat });
+}
+
+/// Counts the non-NaN values of each row of `mat`, and stores the result in `count`.
+///
+/// Real matrices with contiguous rows or columns are counted in index registers, in the same way
+/// as in the ignore-NaN mean and variance kernels.
+pub(super) fn col_non_nan_count<E: ComplexField>(count: &mut [usize], mat: MatRef<'_, E>) {
+    count.fill(0);
+    if mat.nrows() == 0 || mat.ncols() == 0 {
+        return;
+    }
+
+    if coe::is_same::<E, E::Real>() && mat.row_stride() == 1 {
+        col_non_nan_count_col_major_real::<E::Real>(count, mat.coerce());
+    } else if coe::is_same::<E, E::Real>() && mat.col_stride() == 1 {
+        col_non_nan_count_row_major_real::<E::Real>(count, mat.coerce());
+    } else {
+        for j in 0..mat.ncols() {
+            for (i, count) in count.iter_mut().enumerate() {
+                *count += !mat.read(i, j).faer_is_nan() as usize;
+            }
+        }
+    }
+}
+
+/// Divides the sums in `out` by the number of non-NaN values in `count`, or by that number minus
+/// `ddof` if it is provided.
+pub(super) fn finalize_col_major_ignore_nan<E: RealField>(
+    out: ColMut<'_, E>,
+    count: &[usize],
+    ddof: Option<usize>,
+) {
+    let mut out = out;
+    for (i, &count) in count.iter().enumerate() {
+        let sum = out.read(i);
+        let value = match ddof {
+            None => sum.faer_scale_real(from_usize::<E>(count).faer_inv()),
+            Some(ddof) => var_from_sum(sum, count, ddof),
+        };
+        out.write(i, value);
+    }
+}
+
+// the column-major kernels accumulate the sums in registers that map to consecutive rows, without
+// aligning them, so that the per-row counts can be kept in index registers. the rows that don't
+// fill a register are handled by scalar code
+
+fn col_mean_col_major_ignore_nan_real<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        count: &'a mut [usize],
+        mat: MatRef<'a, E>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { out, count, mat } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let mut out = SliceGroupMut::<'_, E>::new(out.try_as_slice_mut().unwrap());
+            let (n_simd, m_tail) = {
+                let (body, tail) = simd.as_simd_mut(out.rb_mut());
+                (body.len(), tail.len())
+            };
+            let (count, count_tail) = count.split_at_mut(mat.nrows() - m_tail);
+            let mut simd_count = vec![simd.index_splat(E::faer_usize_to_index(0)); n_simd];
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                mut out: impl Write<Output = SimdGroupFor<E, S>>,
+                count: &mut SimdIndexFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(zero);
+                let is_not_nan = simd.less_than_or_equal(val, val);
+
+                let acc = out.read_or(zero);
+                out.write(simd.select(is_not_nan, simd.add(acc, val), acc));
+                *count = count_if(simd, is_not_nan, *count);
+            }
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd.as_simd(col);
+                let (out_body, mut out_tail) = simd.as_simd_mut(out.rb_mut());
+
+                for (out, (count, x)) in zip(
+                    out_body.into_mut_iter(),
+                    zip(simd_count.iter_mut(), body.into_ref_iter()),
+                ) {
+                    process(simd, out, count, x);
+                }
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    let x = tail.read(i);
+                    if !x.faer_is_nan() {
+                        out_tail.write(i, out_tail.read(i).faer_add(x));
+                        *count += 1;
+                    }
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    let mut out = out;
+    let mut count = vec![0usize; mat.nrows()];
+    out.fill_zero();
+    E::Simd::default().dispatch(Impl {
+        out: out.rb_mut(),
+        count: &mut count,
+        mat,
+    });
+    finalize_col_major_ignore_nan(out, &count, None);
+}
+
+fn col_mean_col_major_ignore_nan_cplx<E: RealField>(
+    out: ColMut<'_, Complex<E>>,
+    mat: MatRef<'_, Complex<E>>,
+) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, Complex<E>>,
+        count: &'a mut [usize],
+        mat: MatRef<'a, Complex<E>>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self { out, count, mat } = self;
+            let simd_cplx = SimdFor::<Complex<E>, S>::new(simd);
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let mut out = SliceGroupMut::<'_, Complex<E>>::new(out.try_as_slice_mut().unwrap());
+            let (n_simd, m_tail) = {
+                let (body, tail) = simd_cplx.as_simd_mut(out.rb_mut());
+                (body.len(), tail.len())
+            };
+            let (count, count_tail) = count.split_at_mut(mat.nrows() - m_tail);
+            let mut simd_count = vec![simd.index_splat(E::faer_usize_to_index(0)); n_simd];
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                mut out: impl Write<Output = SimdGroupFor<Complex<E>, S>>,
+                count: &mut SimdIndexFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<Complex<E>, S>>,
+            ) {
+                let simd_cplx = SimdFor::<Complex<E>, S>::new(simd.simd);
+                let zero = simd_cplx.splat(Complex::<E>::faer_zero());
+                let val = val.read_or(zero);
+                let re_is_not_nan = simd.less_than_or_equal(val.re, val.re);
+                let im_is_not_nan = simd.less_than_or_equal(val.im, val.im);
+
+                let acc = out.read_or(zero);
+                let new = simd_cplx.add(acc, val);
+                out.write(Complex {
+                    re: simd.select(
+                        re_is_not_nan,
+                        simd.select(im_is_not_nan, new.re, acc.re),
+                        acc.re,
+                    ),
+                    im: simd.select(
+                        re_is_not_nan,
+                        simd.select(im_is_not_nan, new.im, acc.im),
+                        acc.im,
+                    ),
+                });
+                *count =
+                    simd.index_select(re_is_not_nan, count_if(simd, im_is_not_nan, *count), *count);
+            }
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, Complex<E>>::new(mat.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd_cplx.as_simd(col);
+                let (out_body, mut out_tail) = simd_cplx.as_simd_mut(out.rb_mut());
+
+                for (out, (count, x)) in zip(
+                    out_body.into_mut_iter(),
+                    zip(simd_count.iter_mut(), body.into_ref_iter()),
+                ) {
+                    process(simd, out, count, x);
+                }
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    let x = tail.read(i);
+                    if !x.faer_is_nan() {
+                        out_tail.write(i, out_tail.read(i).faer_add(x));
+                        *count += 1;
+                    }
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    let mut out = out;
+    let mut count = vec![0usize; mat.nrows()];
+    out.fill_zero();
+    E::Simd::default().dispatch(Impl {
+        out: out.rb_mut(),
+        count: &mut count,
+        mat,
+    });
+
+    for (i, &count) in count.iter().enumerate() {
+        out.write(
+            i,
+            out.read(i)
+                .faer_scale_real(from_usize::<E>(count).faer_inv()),
+        );
+    }
+}
+
+fn col_varm_col_major_ignore_nan_real<E: RealField>(
+    out: ColMut<'_, E>,
     mat: MatRef<'_, E>,
-    row_mean: RowRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    ddof: usize,
+) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        count: &'a mut [usize],
+        mat: MatRef<'a, E>,
+        col_mean: ColRef<'a, E>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                out,
+                count,
+                mat,
+                col_mean,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let mut out = SliceGroupMut::<'_, E>::new(out.try_as_slice_mut().unwrap());
+            let col_mean = SliceGroup::<'_, E>::new(col_mean.try_as_slice().unwrap());
+            let (mean_body, mean_tail) = simd.as_simd(col_mean);
+            let m_simd = mat.nrows() - mean_tail.len();
+            let (count, count_tail) = count.split_at_mut(m_simd);
+            let mut simd_count = vec![simd.index_splat(E::faer_usize_to_index(0)); mean_body.len()];
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                mut out: impl Write<Output = SimdGroupFor<E, S>>,
+                count: &mut SimdIndexFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+                mean: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(zero);
+                let is_not_nan = simd.less_than_or_equal(val, val);
+
+                let acc = out.read_or(zero);
+                let diff = simd.sub(val, mean.read_or(zero));
+                out.write(simd.select(is_not_nan, simd.mul_add_e(diff, diff, acc), acc));
+                *count = count_if(simd, is_not_nan, *count);
+            }
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd.as_simd(col);
+                let (out_body, mut out_tail) = simd.as_simd_mut(out.rb_mut());
+
+                for ((out, count), (x, mean)) in zip(
+                    zip(out_body.into_mut_iter(), simd_count.iter_mut()),
+                    zip(body.into_ref_iter(), mean_body.into_ref_iter()),
+                ) {
+                    process(simd, out, count, x, mean);
+                }
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    let x = tail.read(i);
+                    if !x.faer_is_nan() {
+                        let diff = x.faer_sub(mean_tail.read(i));
+                        out_tail.write(i, out_tail.read(i).faer_add(diff.faer_abs2()));
+                        *count += 1;
+                    }
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    let mut out = out;
+    let mut count = vec![0usize; mat.nrows()];
+    out.fill_zero();
+    E::Simd::default().dispatch(Impl {
+        out: out.rb_mut(),
+        count: &mut count,
+        mat,
+        col_mean,
+    });
+    finalize_col_major_ignore_nan(out, &count, Some(ddof));
+}
+
+fn col_varm_col_major_ignore_nan_cplx<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, Complex<E>>,
+    col_mean: ColRef<'_, Complex<E>>,
+    ddof: usize,
 ) {
-    col_varm_propagate(out.transpose_mut(), mat.transpose(), row_mean.transpose());
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        count: &'a mut [usize],
+        mat: MatRef<'a, Complex<E>>,
+        col_mean: ColRef<'a, Complex<E>>,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                out,
+                count,
+                mat,
+                col_mean,
+            } = self;
+            let simd_cplx = SimdFor::<Complex<E>, S>::new(simd);
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let mut out = SliceGroupMut::<'_, E>::new(out.try_as_slice_mut().unwrap());
+            let col_mean = SliceGroup::<'_, Complex<E>>::new(col_mean.try_as_slice().unwrap());
+            let (mean_body, mean_tail) = simd_cplx.as_simd(col_mean);
+            let m_simd = mat.nrows() - mean_tail.len();
+            let (count, count_tail) = count.split_at_mut(m_simd);
+            let mut simd_count = vec![simd.index_splat(E::faer_usize_to_index(0)); mean_body.len()];
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                mut out: impl Write<Output = SimdGroupFor<E, S>>,
+                count: &mut SimdIndexFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<Complex<E>, S>>,
+                mean: impl Read<Output = SimdGroupFor<Complex<E>, S>>,
+            ) {
+                let simd_cplx = SimdFor::<Complex<E>, S>::new(simd.simd);
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(simd_cplx.splat(Complex::<E>::faer_zero()));
+                let mean = mean.read_or(simd_cplx.splat(Complex::<E>::faer_zero()));
+                let re_is_not_nan = simd.less_than_or_equal(val.re, val.re);
+                let im_is_not_nan = simd.less_than_or_equal(val.im, val.im);
+
+                let acc = out.read_or(zero);
+                let diff_re = simd.sub(val.re, mean.re);
+                let diff_im = simd.sub(val.im, mean.im);
+                let new = simd.mul_add_e(diff_im, diff_im, simd.mul_add_e(diff_re, diff_re, acc));
+
+                out.write(simd.select(re_is_not_nan, simd.select(im_is_not_nan, new, acc), acc));
+                *count =
+                    simd.index_select(re_is_not_nan, count_if(simd, im_is_not_nan, *count), *count);
+            }
+
+            let chunk_size = index_chunk_size::<E>();
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, Complex<E>>::new(mat.col(j).try_as_slice().unwrap());
+                let (body, tail) = simd_cplx.as_simd(col);
+                let (out_body, mut out_tail) = simd.as_simd_mut(out.rb_mut());
+
+                for ((out, count), (x, mean)) in zip(
+                    zip(out_body.into_mut_iter(), simd_count.iter_mut()),
+                    zip(body.into_ref_iter(), mean_body.into_ref_iter()),
+                ) {
+                    process(simd, out, count, x, mean);
+                }
+                for (i, count) in count_tail.iter_mut().enumerate() {
+                    let x = tail.read(i);
+                    if !x.faer_is_nan() {
+                        let diff = x.faer_sub(mean_tail.read(i));
+                        out_tail.write(i, out_tail.read(i).faer_add(diff.faer_abs2()));
+                        *count += 1;
+                    }
+                }
+
+                if (j + 1) % chunk_size == 0 {
+                    flush_count(simd, count, &mut simd_count);
+                }
+            }
+            flush_count(simd, count, &mut simd_count);
+        }
+    }
+
+    let mut out = out;
+    let mut count = vec![0usize; mat.nrows()];
+    out.fill_zero();
+    E::Simd::default().dispatch(Impl {
+        out: out.rb_mut(),
+        count: &mut count,
+        mat,
+        col_mean,
+    });
+    finalize_col_major_ignore_nan(out, &count, Some(ddof));
 }
 
 fn col_mean_ignore<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
@@ -1781,7 +2379,18 @@ fn col_mean_ignore<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
         } else {
             panic!()
         }
+    } else if mat.row_stride() == 1
+        && out.row_stride() == 1
+        && (coe::is_same::<E, E::Real>() || coe::is_same::<E, Complex<E::Real>>())
+    {
+        if coe::is_same::<E, E::Real>() {
+            col_mean_col_major_ignore_nan_real::<E::Real>(out.coerce(), mat.coerce())
+        } else {
+            col_mean_col_major_ignore_nan_cplx::<E::Real>(out.coerce(), mat.coerce())
+        }
     } else {
+        // native complex types store the real and imaginary parts interleaved, which doesn't
+        // allow masking both parts at once, so they go through the scalar path
         let m = mat.nrows();
         let n = mat.ncols();
         let mut valid_count = vec![0usize; m];
@@ -1866,14 +2475,11 @@ fn col_mean_ignore<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>) {
     }
 }
 
-fn row_mean_ignore<E: ComplexField>(out: RowMut<'_, E>, mat: MatRef<'_, E>) {
-    col_mean_ignore(out.transpose_mut(), mat.transpose())
-}
-
 fn col_varm_ignore<E: ComplexField>(
     out: ColMut<'_, E::Real>,
     mat: MatRef<'_, E>,
     col_mean: ColRef<'_, E>,
+    ddof: usize,
 ) {
     let mut out = out;
     if mat.ncols() == 0 {
@@ -1889,31 +2495,54 @@ fn col_varm_ignore<E: ComplexField>(
 
     if mat.col_stride() == 1 {
         if coe::is_same::<E, c32>() {
-            col_varm_row_major_ignore_nan_c32(out.coerce(), mat.coerce(), col_mean.coerce())
+            col_varm_row_major_ignore_nan_c32(out.coerce(), mat.coerce(), col_mean.coerce(), ddof)
         } else if coe::is_same::<E, c64>() {
-            col_varm_row_major_ignore_nan_c64(out.coerce(), mat.coerce(), col_mean.coerce())
+            col_varm_row_major_ignore_nan_c64(out.coerce(), mat.coerce(), col_mean.coerce(), ddof)
         } else if coe::is_same::<E, E::Real>() {
             col_varm_row_major_ignore_nan_real::<E::Real>(
                 out.coerce(),
                 mat.coerce(),
                 col_mean.coerce(),
+                ddof,
             )
         } else if coe::is_same::<E, Complex<E::Real>>() {
             col_varm_row_major_ignore_nan_cplx::<E::Real>(
                 out.coerce(),
                 mat.coerce(),
                 col_mean.coerce(),
+                ddof,
             )
         } else {
             panic!()
         }
-    } else {
-        let m = mat.nrows();
-        let n = mat.ncols();
-        let mut valid_count = vec![0usize; m];
-
-        out.fill_zero();
-        if mat.row_stride() == 1 && out.row_stride() == 1 && col_mean.row_stride() == 1 {
+    } else if mat.row_stride() == 1
+        && out.row_stride() == 1
+        && col_mean.row_stride() == 1
+        && (coe::is_same::<E, E::Real>() || coe::is_same::<E, Complex<E::Real>>())
+    {
+        if coe::is_same::<E, E::Real>() {
+            col_varm_col_major_ignore_nan_real::<E::Real>(
+                out,
+                mat.coerce(),
+                col_mean.coerce(),
+                ddof,
+            )
+        } else {
+            col_varm_col_major_ignore_nan_cplx::<E::Real>(
+                out,
+                mat.coerce(),
+                col_mean.coerce(),
+                ddof,
+            )
+        }
+    } else {
+        // see `col_mean_ignore`
+        let m = mat.nrows();
+        let n = mat.ncols();
+        let mut valid_count = vec![0usize; m];
+
+        out.fill_zero();
+        if mat.row_stride() == 1 && out.row_stride() == 1 && col_mean.row_stride() == 1 {
             struct Impl<'a, E: ComplexField> {
                 out: ColMut<'a, E::Real>,
                 mat: MatRef<'a, E>,
@@ -1996,88 +2625,497 @@ fn col_varm_ignore<E: ComplexField>(
         }
 
         for i in 0..m {
-            let non_nan_count = valid_count[i];
-            let var = if non_nan_count == 0 {
-                E::Real::faer_nan()
-            } else if non_nan_count == 1 {
-                E::Real::faer_zero()
-            } else {
-                out.read(i)
-                    .faer_scale_real(from_usize::<E::Real>(non_nan_count - 1).faer_inv())
-            };
+            let var = var_from_sum::<E::Real>(out.read(i), valid_count[i], ddof);
             out.write(i, var);
         }
     }
 }
 
-fn row_varm_ignore<E: ComplexField>(
-    out: RowMut<'_, E::Real>,
+fn col_mean_impl<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    match nan {
+        NanHandling::Propagate => col_mean_propagate(out, mat),
+        NanHandling::Ignore => col_mean_ignore(out, mat),
+    }
+}
+
+fn col_varm_impl<E: ComplexField>(
+    out: ColMut<'_, E::Real>,
     mat: MatRef<'_, E>,
-    row_mean: RowRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
 ) {
-    col_varm_ignore(out.transpose_mut(), mat.transpose(), row_mean.transpose())
+    match nan {
+        NanHandling::Propagate => col_varm_propagate(out, mat, col_mean, ddof),
+        NanHandling::Ignore => col_varm_ignore(out, mat, col_mean, ddof),
+    }
+}
+
+/// Minimum number of rows handled by each task when a reduction is split between threads.
+const PAR_MIN_ROWS: usize = 128;
+
+#[inline]
+pub(super) fn should_split(nrows: usize, parallelism: Parallelism) -> bool {
+    crate::utils::thread::parallelism_degree(parallelism) > 1 && nrows >= 2 * PAR_MIN_ROWS
+}
+
+fn col_mean_par<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        col_mean_impl(out, mat, nan);
+        return;
+    }
+
+    // each row is reduced independently, so the rows can be processed in disjoint blocks
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_mean_par(out_top, mat_top, nan, parallelism),
+        |parallelism| col_mean_par(out_bot, mat_bot, nan, parallelism),
+        parallelism,
+    );
+}
+
+fn col_varm_par<E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        col_varm_impl(out, mat, col_mean, ddof, nan);
+        return;
+    }
+
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    let (mean_top, mean_bot) = col_mean.split_at(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_varm_par(out_top, mat_top, mean_top, ddof, nan, parallelism),
+        |parallelism| col_varm_par(out_bot, mat_bot, mean_bot, ddof, nan, parallelism),
+        parallelism,
+    );
 }
 
 /// Computes the mean of the columns of `mat` and stores the result in `out`.
+///
+/// The rows of `mat` are split between threads according to `parallelism`.
 #[track_caller]
-pub fn col_mean<E: ComplexField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+pub fn col_mean<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
     assert!(all(out.nrows() == mat.nrows()));
-
-    match nan {
-        NanHandling::Propagate => col_mean_propagate(out, mat),
-        NanHandling::Ignore => col_mean_ignore(out, mat),
-    }
+    col_mean_par(out, mat, nan, parallelism);
 }
 
 /// Computes the mean of the rows of `mat` and stores the result in `out`.
+///
+/// The columns of `mat` are split between threads according to `parallelism`.
 #[track_caller]
-pub fn row_mean<E: ComplexField>(out: RowMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+pub fn row_mean<E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
     assert!(all(out.ncols() == mat.ncols()));
-
-    match nan {
-        NanHandling::Propagate => row_mean_propagate(out, mat),
-        NanHandling::Ignore => row_mean_ignore(out, mat),
-    }
+    col_mean_par(out.transpose_mut(), mat.transpose(), nan, parallelism);
 }
 
 /// Computes the variance of the columns of `mat` given their mean, and stores the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of included
+/// entries in each row. See [`col_varm_with_ddof`] for other choices of the denominator.
 #[track_caller]
 pub fn col_varm<E: ComplexField>(
     out: ColMut<'_, E::Real>,
     mat: MatRef<'_, E>,
     col_mean: ColRef<'_, E>,
     nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    col_varm_with_ddof(out, mat, col_mean, 1, nan, parallelism)
+}
+
+/// Computes the variance of the rows of `mat` given their mean, and stores the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of included
+/// entries in each column. See [`row_varm_with_ddof`] for other choices of the denominator.
+#[track_caller]
+pub fn row_varm<E: ComplexField>(
+    out: RowMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    row_mean: RowRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    row_varm_with_ddof(out, mat, row_mean, 1, nan, parallelism)
+}
+
+/// Computes the variance of the columns of `mat` given their mean, and stores the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - ddof`, where `n` is the number of included
+/// entries in each row. `ddof == 0` gives the population variance, and `ddof == 1` gives the
+/// unbiased sample variance.
+/// If `n == 0`, the variance is NaN, and if `0 < n <= ddof`, the variance is zero.
+///
+/// The rows of `mat` are split between threads according to `parallelism`.
+#[track_caller]
+pub fn col_varm_with_ddof<E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+    parallelism: Parallelism,
 ) {
     assert!(all(
         out.nrows() == mat.nrows(),
         col_mean.nrows() == mat.nrows()
     ));
-
-    match nan {
-        NanHandling::Propagate => col_varm_propagate(out, mat, col_mean),
-        NanHandling::Ignore => col_varm_ignore(out, mat, col_mean),
-    }
+    col_varm_par(out, mat, col_mean, ddof, nan, parallelism);
 }
 
 /// Computes the variance of the rows of `mat` given their mean, and stores the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - ddof`, where `n` is the number of included
+/// entries in each column. `ddof == 0` gives the population variance, and `ddof == 1` gives the
+/// unbiased sample variance.
+/// If `n == 0`, the variance is NaN, and if `0 < n <= ddof`, the variance is zero.
+///
+/// The columns of `mat` are split between threads according to `parallelism`.
 #[track_caller]
-pub fn row_varm<E: ComplexField>(
+pub fn row_varm_with_ddof<E: ComplexField>(
     out: RowMut<'_, E::Real>,
     mat: MatRef<'_, E>,
     row_mean: RowRef<'_, E>,
+    ddof: usize,
     nan: NanHandling,
+    parallelism: Parallelism,
 ) {
     assert!(all(
         out.ncols() == mat.ncols(),
         row_mean.ncols() == mat.ncols(),
     ));
+    col_varm_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        row_mean.transpose(),
+        ddof,
+        nan,
+        parallelism,
+    );
+}
 
-    match nan {
-        NanHandling::Propagate => row_varm_propagate(out, mat, row_mean),
-        NanHandling::Ignore => row_varm_ignore(out, mat, row_mean),
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Moment {
+    Skewness,
+    Kurtosis,
+}
+
+#[inline(always)]
+fn finalize_moment<E: RealField>(moment: Moment, sum2: E, sum3: E, sum4: E, count: usize) -> E {
+    if count == 0 {
+        return E::faer_nan();
+    }
+    let inv_n = from_usize::<E>(count).faer_inv();
+    let m2 = sum2.faer_mul(inv_n);
+    match moment {
+        Moment::Skewness => sum3.faer_mul(inv_n).faer_div(m2.faer_mul(m2.faer_sqrt())),
+        Moment::Kurtosis => sum4
+            .faer_mul(inv_n)
+            .faer_div(m2.faer_mul(m2))
+            .faer_sub(E::faer_from_f64(3.0)),
     }
 }
 
+fn col_moment_row_major<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    moment: Moment,
+    nan: NanHandling,
+) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        mat: MatRef<'a, E>,
+        col_mean: ColRef<'a, E>,
+        moment: Moment,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                mut out,
+                mat,
+                col_mean,
+                moment,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let m = mat.nrows();
+            let n = mat.ncols();
+            let chunk_size = index_chunk_size::<E>();
+
+            let offset = simd.align_offset_ptr(mat.as_ptr(), mat.ncols());
+            for i in 0..m {
+                let mean = simd.splat(col_mean.read(i));
+                // padding lanes must not contribute to the sums: in the nan-ignoring case they are
+                // masked out, otherwise they are set to the mean so that their deviation is zero
+                let pad = if ignore_nan {
+                    simd.splat(E::faer_nan())
+                } else {
+                    mean
+                };
+                let row = SliceGroup::<'_, E>::new(mat.row(i).try_as_slice().unwrap());
+                let (head, body, tail) = simd.as_aligned_simd(row, offset);
+
+                let mut non_nan_count_total = 0usize;
+
+                #[inline(always)]
+                fn process<E: RealField, S: pulp::Simd>(
+                    simd: SimdFor<E, S>,
+                    acc: [SimdGroupFor<E, S>; 3],
+                    mean: SimdGroupFor<E, S>,
+                    pad: SimdGroupFor<E, S>,
+                    ignore_nan: bool,
+                    non_nan_count: SimdIndexFor<E, S>,
+                    val: impl Read<Output = SimdGroupFor<E, S>>,
+                ) -> ([SimdGroupFor<E, S>; 3], SimdIndexFor<E, S>) {
+                    let [sum2, sum3, sum4] = acc;
+                    let val = val.read_or(pad);
+                    let diff = simd.sub(val, mean);
+                    let diff2 = simd.mul(diff, diff);
+                    let diff3 = simd.mul(diff2, diff);
+                    let diff4 = simd.mul(diff2, diff2);
+
+                    if ignore_nan {
+                        let is_not_nan = simd.less_than_or_equal(val, val);
+                        (
+                            [
+                                simd.select(is_not_nan, simd.add(sum2, diff2), sum2),
+                                simd.select(is_not_nan, simd.add(sum3, diff3), sum3),
+                                simd.select(is_not_nan, simd.add(sum4, diff4), sum4),
+                            ],
+                            simd.index_select(
+                                is_not_nan,
+                                simd.index_add(
+                                    non_nan_count,
+                                    simd.index_splat(E::faer_usize_to_index(1)),
+                                ),
+                                non_nan_count,
+                            ),
+                        )
+                    } else {
+                        (
+                            [
+                                simd.add(sum2, diff2),
+                                simd.add(sum3, diff3),
+                                simd.add(sum4, diff4),
+                            ],
+                            non_nan_count,
+                        )
+                    }
+                }
+
+                let zero = simd.splat(E::faer_zero());
+                let mut acc = [zero, zero, zero];
+                let mut non_nan_count = simd.index_splat(E::faer_usize_to_index(0));
+
+                (acc, non_nan_count) =
+                    process(simd, acc, mean, pad, ignore_nan, non_nan_count, head);
+                non_nan_count_total += reduce::<E, S>(non_nan_count);
+                non_nan_count = simd.index_splat(E::faer_usize_to_index(0));
+
+                let mut start = 0usize;
+                while start < body.len() {
+                    let len = Ord::min(body.len() - start, chunk_size);
+                    for x in body.subslice(start..start + len).into_ref_iter() {
+                        (acc, non_nan_count) =
+                            process(simd, acc, mean, pad, ignore_nan, non_nan_count, x);
+                    }
+                    non_nan_count_total += reduce::<E, S>(non_nan_count);
+                    non_nan_count = simd.index_splat(E::faer_usize_to_index(0));
+
+                    start += len;
+                }
+
+                (acc, non_nan_count) =
+                    process(simd, acc, mean, pad, ignore_nan, non_nan_count, tail);
+                non_nan_count_total += reduce::<E, S>(non_nan_count);
+
+                let [sum2, sum3, sum4] = acc
+                    .map(|sum| simd.reduce_add(simd.rotate_left(sum, offset.rotate_left_amount())));
+
+                let count = if ignore_nan { non_nan_count_total } else { n };
+                out.write(i, finalize_moment(moment, sum2, sum3, sum4, count));
+            }
+        }
+    }
+
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        col_mean,
+        moment,
+        ignore_nan: nan == NanHandling::Ignore,
+    });
+}
+
+fn col_moment<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    moment: Moment,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    if mat.ncols() == 0 {
+        out.fill(E::faer_nan());
+        return;
+    }
+
+    let mat = if mat.col_stride() >= 0 {
+        mat
+    } else {
+        mat.reverse_cols()
+    };
+
+    if mat.col_stride() == 1 {
+        col_moment_row_major(out, mat, col_mean, moment, nan);
+    } else {
+        let m = mat.nrows();
+        let n = mat.ncols();
+        let ignore_nan = nan == NanHandling::Ignore;
+
+        let mut valid_count = vec![0usize; m];
+        let mut sum2 = vec![E::faer_zero(); m];
+        let mut sum3 = vec![E::faer_zero(); m];
+        let mut sum4 = vec![E::faer_zero(); m];
+
+        for j in 0..n {
+            for i in 0..m {
+                let elem = mat.read(i, j);
+                if ignore_nan && elem.faer_is_nan() {
+                    continue;
+                }
+                let diff = elem.faer_sub(col_mean.read(i));
+                let diff2 = diff.faer_mul(diff);
+                valid_count[i] += 1;
+                sum2[i] = sum2[i].faer_add(diff2);
+                sum3[i] = sum3[i].faer_add(diff2.faer_mul(diff));
+                sum4[i] = sum4[i].faer_add(diff2.faer_mul(diff2));
+            }
+        }
+
+        for i in 0..m {
+            out.write(
+                i,
+                finalize_moment(moment, sum2[i], sum3[i], sum4[i], valid_count[i]),
+            );
+        }
+    }
+}
+
+/// Computes the skewness of the columns of `mat` given their mean, and stores the result in
+/// `out`.
+///
+/// The skewness is computed from the biased sample moments, $m_3 / m_2^{3/2}$.
+#[track_caller]
+pub fn col_skewness<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        col_mean.nrows() == mat.nrows()
+    ));
+    col_moment(out, mat, col_mean, Moment::Skewness, nan);
+}
+
+/// Computes the skewness of the rows of `mat` given their mean, and stores the result in `out`.
+///
+/// The skewness is computed from the biased sample moments, $m_3 / m_2^{3/2}$.
+#[track_caller]
+pub fn row_skewness<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    row_mean: RowRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        row_mean.ncols() == mat.ncols(),
+    ));
+    col_moment(
+        out.transpose_mut(),
+        mat.transpose(),
+        row_mean.transpose(),
+        Moment::Skewness,
+        nan,
+    );
+}
+
+/// Computes the excess kurtosis of the columns of `mat` given their mean, and stores the result
+/// in `out`.
+///
+/// The kurtosis is computed from the biased sample moments, $m_4 / m_2^2 - 3$.
+#[track_caller]
+pub fn col_kurtosis<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    col_mean: ColRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        col_mean.nrows() == mat.nrows()
+    ));
+    col_moment(out, mat, col_mean, Moment::Kurtosis, nan);
+}
+
+/// Computes the excess kurtosis of the rows of `mat` given their mean, and stores the result in
+/// `out`.
+///
+/// The kurtosis is computed from the biased sample moments, $m_4 / m_2^2 - 3$.
+#[track_caller]
+pub fn row_kurtosis<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    row_mean: RowRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        row_mean.ncols() == mat.ncols(),
+    ));
+    col_moment(
+        out.transpose_mut(),
+        mat.transpose(),
+        row_mean.transpose(),
+        Moment::Kurtosis,
+        nan,
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2094,13 +3132,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_propagate(row_mean.as_mut(), A.as_ref());
-        super::row_varm_propagate(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_propagate(col_mean.as_mut(), A.as_ref());
-        super::col_varm_propagate(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_propagate(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![(A[(0, 0)] + A[(1, 0)]) / 2.0, (A[(0, 1)] + A[(1, 1)]) / 2.0,]);
         assert!(
@@ -2131,13 +3180,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![(A[(0, 0)] + A[(1, 0)]) / 2.0, (A[(0, 1)] + A[(1, 1)]) / 2.0,]);
         assert!(
@@ -2169,13 +3229,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![A[(1, 0)] / 1.0, (A[(0, 1)] + A[(1, 1)]) / 2.0,]);
         assert!(
@@ -2206,13 +3277,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![(A[(0, 0)] + A[(1, 0)]) / 2.0, (A[(0, 1)] + A[(1, 1)]) / 2.0,]);
         assert!(
@@ -2244,13 +3326,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![A[(1, 0)] / 1.0, (A[(0, 1)] + A[(1, 1)]) / 2.0,]);
         assert!(
@@ -2281,13 +3374,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(
             row_mean
@@ -2335,13 +3439,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![A.read(1, 0) / 1.0, (A.read(0, 1) + A.read(1, 1)) / 2.0,]);
         assert!(
@@ -2374,13 +3489,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(
             row_mean
@@ -2428,13 +3554,24 @@ mod tests {
 
         let mut row_mean = Row::zeros(A.ncols());
         let mut row_var = Row::zeros(A.ncols());
-        super::row_mean_ignore(row_mean.as_mut(), A.as_ref());
-        super::row_varm_ignore(row_var.as_mut(), A.as_ref(), row_mean.as_ref());
+        super::row_mean(
+            row_mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        super::row_varm(
+            row_var.as_mut(),
+            A.as_ref(),
+            row_mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
 
         let mut col_mean = Col::zeros(A.nrows());
         let mut col_var = Col::zeros(A.nrows());
         super::col_mean_ignore(col_mean.as_mut(), A.as_ref());
-        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref());
+        super::col_varm_ignore(col_var.as_mut(), A.as_ref(), col_mean.as_ref(), 1);
 
         assert!(row_mean == row![A.read(1, 0) / 1.0, (A.read(0, 1) + A.read(1, 1)) / 2.0,]);
         assert!(
@@ -2456,4 +3593,247 @@ mod tests {
                 ]
         );
     }
+
+    #[test]
+    fn test_skewness_kurtosis() {
+        let nan = f64::NAN;
+        let m = 3;
+        let n = 37;
+        let f = |i: usize, j: usize| {
+            if i == 1 && j % 5 == 2 {
+                nan
+            } else {
+                ((i + 1) as f64 * (j as f64).sqrt() + (j * j) as f64 / 100.0).sin()
+            }
+        };
+
+        // stored in row-major order, so that the simd path is used
+        let A_t = Mat::<f64>::from_fn(n, m, |j, i| f(i, j));
+        let A_row_major = A_t.transpose();
+        // stored in column-major order
+        let A = Mat::<f64>::from_fn(m, n, f);
+
+        for nan_handling in [NanHandling::Propagate, NanHandling::Ignore] {
+            let mut mean = Col::<f64>::zeros(m);
+            col_mean(mean.as_mut(), A.as_ref(), nan_handling, Parallelism::None);
+
+            let mut skew_simd = Col::<f64>::zeros(m);
+            let mut skew = Col::<f64>::zeros(m);
+            let mut kurt_simd = Col::<f64>::zeros(m);
+            let mut kurt = Col::<f64>::zeros(m);
+            col_skewness(skew_simd.as_mut(), A_row_major, mean.as_ref(), nan_handling);
+            col_skewness(skew.as_mut(), A.as_ref(), mean.as_ref(), nan_handling);
+            col_kurtosis(kurt_simd.as_mut(), A_row_major, mean.as_ref(), nan_handling);
+            col_kurtosis(kurt.as_mut(), A.as_ref(), mean.as_ref(), nan_handling);
+
+            for i in 0..m {
+                let vals = (0..n)
+                    .map(|j| A.read(i, j))
+                    .filter(|x| nan_handling == NanHandling::Propagate || !x.is_nan())
+                    .collect::<Vec<_>>();
+                let count = vals.len() as f64;
+                let mu = mean.read(i);
+                let m2 = vals.iter().map(|x| (x - mu).powi(2)).sum::<f64>() / count;
+                let m3 = vals.iter().map(|x| (x - mu).powi(3)).sum::<f64>() / count;
+                let m4 = vals.iter().map(|x| (x - mu).powi(4)).sum::<f64>() / count;
+                let expected_skew = m3 / m2.powf(1.5);
+                let expected_kurt = m4 / (m2 * m2) - 3.0;
+
+                if expected_skew.is_nan() {
+                    assert!(skew.read(i).is_nan());
+                    assert!(skew_simd.read(i).is_nan());
+                    assert!(kurt.read(i).is_nan());
+                    assert!(kurt_simd.read(i).is_nan());
+                } else {
+                    assert!((skew.read(i) - expected_skew).abs() < 1e-10);
+                    assert!((skew_simd.read(i) - expected_skew).abs() < 1e-10);
+                    assert!((kurt.read(i) - expected_kurt).abs() < 1e-10);
+                    assert!((kurt_simd.read(i) - expected_kurt).abs() < 1e-10);
+                }
+            }
+
+            let mut row_skew = Row::<f64>::zeros(m);
+            row_skewness(
+                row_skew.as_mut(),
+                A_t.as_ref(),
+                mean.as_ref().transpose(),
+                nan_handling,
+            );
+            for i in 0..m {
+                let (a, b) = (row_skew.read(i), skew.read(i));
+                assert!((a.is_nan() && b.is_nan()) || (a - b).abs() < 1e-10);
+            }
+        }
+    }
+
+    #[test]
+    fn test_varm_ddof() {
+        let nan = f64::NAN;
+        let A = mat![
+            [1.0, 2.0, 4.0, 7.0],
+            [nan, 3.0, 5.0, nan],
+            [nan, nan, nan, 2.0f64]
+        ];
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+
+        for A in [A.as_ref(), A_row_major] {
+            let mut mean = Col::<f64>::zeros(3);
+            col_mean(mean.as_mut(), A, NanHandling::Ignore, Parallelism::None);
+
+            let mut var = Col::<f64>::zeros(3);
+            col_varm_with_ddof(
+                var.as_mut(),
+                A,
+                mean.as_ref(),
+                0,
+                NanHandling::Ignore,
+                Parallelism::None,
+            );
+            assert!((var[0] - 5.25).abs() < 1e-12);
+            assert!((var[1] - 1.0).abs() < 1e-12);
+            assert!(var[2] == 0.0);
+
+            col_varm_with_ddof(
+                var.as_mut(),
+                A,
+                mean.as_ref(),
+                2,
+                NanHandling::Ignore,
+                Parallelism::None,
+            );
+            assert!((var[0] - 10.5).abs() < 1e-12);
+            assert!(var[1] == 0.0);
+            assert!(var[2] == 0.0);
+
+            col_varm_with_ddof(
+                var.as_mut(),
+                A,
+                mean.as_ref(),
+                0,
+                NanHandling::Propagate,
+                Parallelism::None,
+            );
+            assert!((var[0] - 5.25).abs() < 1e-12);
+            assert!(var[1].is_nan());
+            assert!(var[2].is_nan());
+
+            let mut var1 = Col::<f64>::zeros(3);
+            col_varm(
+                var1.as_mut(),
+                A,
+                mean.as_ref(),
+                NanHandling::Ignore,
+                Parallelism::None,
+            );
+            col_varm_with_ddof(
+                var.as_mut(),
+                A,
+                mean.as_ref(),
+                1,
+                NanHandling::Ignore,
+                Parallelism::None,
+            );
+            assert!(var == var1);
+        }
+    }
+
+    #[test]
+    fn test_meanvar_ignore_nan_col_major() {
+        let m = 37;
+        let n = 5;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i + 2 * j) % 7 == 0 || i == 11 {
+                f64::NAN
+            } else {
+                (i as f64).sin() + j as f64
+            }
+        });
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+
+        let mut mean = Col::<f64>::zeros(m);
+        let mut mean_target = Col::<f64>::zeros(m);
+        col_mean(
+            mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        col_mean(
+            mean_target.as_mut(),
+            A_row_major,
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+
+        let mut var = Col::<f64>::zeros(m);
+        let mut var_target = Col::<f64>::zeros(m);
+        col_varm(
+            var.as_mut(),
+            A.as_ref(),
+            mean.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        col_varm(
+            var_target.as_mut(),
+            A_row_major,
+            mean_target.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+
+        for i in 0..m {
+            if i == 11 {
+                assert!(mean[i].is_nan());
+                assert!(var[i].is_nan());
+            } else {
+                assert!((mean[i] - mean_target[i]).abs() < 1e-12);
+                assert!((var[i] - var_target[i]).abs() < 1e-12);
+            }
+        }
+    }
+
+    #[test]
+    fn test_meanvar_parallel() {
+        let m = 1031;
+        let n = 17;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i + j) % 13 == 0 {
+                f64::NAN
+            } else {
+                (i as f64 * 0.37).cos() * j as f64
+            }
+        });
+
+        for nan in [NanHandling::Propagate, NanHandling::Ignore] {
+            let mut mean = Col::<f64>::zeros(m);
+            let mut mean_par = Col::<f64>::zeros(m);
+            col_mean(mean.as_mut(), A.as_ref(), nan, Parallelism::None);
+            col_mean(mean_par.as_mut(), A.as_ref(), nan, Parallelism::Rayon(4));
+
+            let mut var = Col::<f64>::zeros(m);
+            let mut var_par = Col::<f64>::zeros(m);
+            col_varm(
+                var.as_mut(),
+                A.as_ref(),
+                mean.as_ref(),
+                nan,
+                Parallelism::None,
+            );
+            col_varm(
+                var_par.as_mut(),
+                A.as_ref(),
+                mean.as_ref(),
+                nan,
+                Parallelism::Rayon(4),
+            );
+
+            for i in 0..m {
+                assert!(mean[i].to_bits() == mean_par[i].to_bits());
+                assert!(var[i].to_bits() == var_par[i].to_bits());
+            }
+        }
+    }
 }
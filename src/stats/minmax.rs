@@ -0,0 +1,437 @@
+use super::NanHandling;
+use crate::{
+    linalg::entity::{pulp, SimdGroupFor},
+    prelude::*,
+    utils::{
+        simd::SimdFor,
+        slice::{SliceGroup, SliceGroupMut},
+    },
+    RealField,
+};
+use core::iter::zip;
+use equator::assert;
+use pulp::{Read, Write};
+use reborrow::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Extremum {
+    Min,
+    Max,
+}
+
+#[inline(always)]
+fn is_better<E: RealField>(extremum: Extremum, val: E, acc: E) -> bool {
+    match extremum {
+        Extremum::Min => val < acc,
+        Extremum::Max => val > acc,
+    }
+}
+
+fn col_extremum_col_major<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    extremum: Extremum,
+    nan: NanHandling,
+) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        mat: MatRef<'a, E>,
+        extremum: Extremum,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                out,
+                mat,
+                extremum,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let m = mat.nrows();
+            let n = mat.ncols();
+            let offset = simd.align_offset_ptr(mat.as_ptr(), m);
+            let mut out = SliceGroupMut::<'_, E>::new(out.try_as_slice_mut().unwrap());
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                extremum: Extremum,
+                ignore_nan: bool,
+                mut out: impl Write<Output = SimdGroupFor<E, S>>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let acc = out.read_or(simd.splat(E::faer_nan()));
+                let val = val.read_or(simd.splat(E::faer_nan()));
+
+                // comparisons involving nan are false, so the accumulator is kept in that case
+                let is_better = match extremum {
+                    Extremum::Min => simd.less_than(val, acc),
+                    Extremum::Max => simd.greater_than(val, acc),
+                };
+                let new = simd.select(is_better, val, acc);
+
+                let new = if ignore_nan {
+                    // replace the accumulator if it doesn't hold a valid value yet
+                    let acc_is_not_nan = simd.less_than_or_equal(acc, acc);
+                    simd.select(acc_is_not_nan, new, val)
+                } else {
+                    // propagate nan values
+                    let val_is_not_nan = simd.less_than_or_equal(val, val);
+                    simd.select(val_is_not_nan, new, val)
+                };
+                out.write(new);
+            }
+
+            for j in 1..n {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (head, body, tail) = simd.as_aligned_simd(col, offset);
+                let (out_head, out_body, out_tail) = simd.as_aligned_simd_mut(out.rb_mut(), offset);
+
+                process(simd, extremum, ignore_nan, out_head, head);
+                for (out, x) in zip(out_body.into_mut_iter(), body.into_ref_iter()) {
+                    process(simd, extremum, ignore_nan, out, x);
+                }
+                process(simd, extremum, ignore_nan, out_tail, tail);
+            }
+        }
+    }
+
+    let mut out = out;
+    out.copy_from(mat.col(0));
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        extremum,
+        ignore_nan: nan == NanHandling::Ignore,
+    });
+}
+
+fn col_extremum<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    extremum: Extremum,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let mut mat = mat;
+
+    if mat.ncols() == 0 {
+        out.fill(E::faer_nan());
+        return;
+    }
+    if mat.row_stride() < 0 {
+        mat = mat.reverse_rows();
+        out = out.reverse_rows_mut();
+    }
+
+    if mat.row_stride() == 1 && out.row_stride() == 1 {
+        col_extremum_col_major(out, mat, extremum, nan);
+    } else {
+        let ignore_nan = nan == NanHandling::Ignore;
+        for i in 0..mat.nrows() {
+            let mut acc = E::faer_nan();
+            for j in 0..mat.ncols() {
+                let val = mat.read(i, j);
+                if val.faer_is_nan() {
+                    if !ignore_nan {
+                        acc = val;
+                        break;
+                    }
+                } else if acc.faer_is_nan() || is_better(extremum, val, acc) {
+                    acc = val;
+                }
+            }
+            out.write(i, acc);
+        }
+    }
+}
+
+fn col_arg_extremum<E: RealField>(
+    out: &mut [Option<usize>],
+    mat: MatRef<'_, E>,
+    extremum: Extremum,
+    nan: NanHandling,
+) {
+    let m = mat.nrows();
+
+    // the extremum of each row is computed with the simd kernel, so that only the position of its
+    // first occurrence needs to be located
+    let mut best = Col::<E>::zeros(m);
+    col_extremum(best.as_mut(), mat, extremum, nan);
+
+    out.fill(None);
+    // set when the index of the row is final
+    let mut done = alloc::vec![false; m];
+    let mut remaining = m;
+    for i in 0..m {
+        // with nan values ignored, a nan extremum means that no values are available
+        if nan == NanHandling::Ignore && best.read(i).faer_is_nan() {
+            done[i] = true;
+            remaining -= 1;
+        }
+    }
+
+    for j in 0..mat.ncols() {
+        if remaining == 0 {
+            break;
+        }
+        for i in 0..m {
+            if done[i] {
+                continue;
+            }
+            let val = mat.read(i, j);
+            let best = best.read(i);
+            if val == best || (val.faer_is_nan() && best.faer_is_nan()) {
+                out[i] = Some(j);
+                done[i] = true;
+                remaining -= 1;
+            }
+        }
+    }
+}
+
+fn arg_extremum<E: RealField>(
+    mat: MatRef<'_, E>,
+    extremum: Extremum,
+    nan: NanHandling,
+) -> Option<(usize, usize, E)> {
+    let m = mat.nrows();
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    let mut best = Col::<E>::zeros(m);
+    col_extremum(best.as_mut(), mat, extremum, nan);
+
+    let mut acc = None::<E>;
+    for i in 0..m {
+        let val = best.read(i);
+        if val.faer_is_nan() {
+            if !ignore_nan {
+                acc = Some(val);
+                break;
+            }
+        } else if acc.map_or(true, |acc| is_better(extremum, val, acc)) {
+            acc = Some(val);
+        }
+    }
+    let acc = acc?;
+
+    for j in 0..mat.ncols() {
+        for i in 0..m {
+            let val = mat.read(i, j);
+            if val == acc || (val.faer_is_nan() && acc.faer_is_nan()) {
+                return Some((i, j, val));
+            }
+        }
+    }
+    None
+}
+
+/// Computes the minimum of the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the minimum of the `i`-th row of `mat`. If no non-NaN values are
+/// available, the result is NaN.
+#[track_caller]
+pub fn col_min<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_extremum(out, mat, Extremum::Min, nan);
+}
+
+/// Computes the maximum of the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the maximum of the `i`-th row of `mat`. If no non-NaN values are
+/// available, the result is NaN.
+#[track_caller]
+pub fn col_max<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_extremum(out, mat, Extremum::Max, nan);
+}
+
+/// Computes the minimum of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the minimum of the `j`-th column of `mat`. If no non-NaN values
+/// are available, the result is NaN.
+#[track_caller]
+pub fn row_min<E: RealField>(out: RowMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_extremum(out.transpose_mut(), mat.transpose(), Extremum::Min, nan);
+}
+
+/// Computes the maximum of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the maximum of the `j`-th column of `mat`. If no non-NaN values
+/// are available, the result is NaN.
+#[track_caller]
+pub fn row_max<E: RealField>(out: RowMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_extremum(out.transpose_mut(), mat.transpose(), Extremum::Max, nan);
+}
+
+/// Computes the column index of the minimum of each row of `mat` and stores the result in `out`.
+///
+/// Ties are resolved in favor of the smallest index. With [`NanHandling::Propagate`], the index
+/// of the first NaN is returned if the row contains one. If no non-NaN values are available, the
+/// result is `None`.
+#[track_caller]
+pub fn col_argmin<E: RealField>(out: &mut [Option<usize>], mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.len() == mat.nrows()));
+    col_arg_extremum(out, mat, Extremum::Min, nan);
+}
+
+/// Computes the column index of the maximum of each row of `mat` and stores the result in `out`.
+///
+/// Ties are resolved in favor of the smallest index. With [`NanHandling::Propagate`], the index
+/// of the first NaN is returned if the row contains one. If no non-NaN values are available, the
+/// result is `None`.
+#[track_caller]
+pub fn col_argmax<E: RealField>(out: &mut [Option<usize>], mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.len() == mat.nrows()));
+    col_arg_extremum(out, mat, Extremum::Max, nan);
+}
+
+/// Computes the row index of the minimum of each column of `mat` and stores the result in `out`.
+///
+/// See [`col_argmin`] for the conventions.
+#[track_caller]
+pub fn row_argmin<E: RealField>(out: &mut [Option<usize>], mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.len() == mat.ncols()));
+    col_arg_extremum(out, mat.transpose(), Extremum::Min, nan);
+}
+
+/// Computes the row index of the maximum of each column of `mat` and stores the result in `out`.
+///
+/// See [`col_argmax`] for the conventions.
+#[track_caller]
+pub fn row_argmax<E: RealField>(out: &mut [Option<usize>], mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.len() == mat.ncols()));
+    col_arg_extremum(out, mat.transpose(), Extremum::Max, nan);
+}
+
+/// Returns the position and value of the minimum of `mat`.
+///
+/// Ties are resolved in favor of the first position in column-major order. With
+/// [`NanHandling::Propagate`], the position of the first NaN is returned if `mat` contains one.
+/// If no non-NaN values are available, the result is `None`.
+pub fn argmin<E: RealField>(mat: MatRef<'_, E>, nan: NanHandling) -> Option<(usize, usize, E)> {
+    arg_extremum(mat, Extremum::Min, nan)
+}
+
+/// Returns the position and value of the maximum of `mat`.
+///
+/// See [`argmin`] for the conventions.
+pub fn argmax<E: RealField>(mat: MatRef<'_, E>, nan: NanHandling) -> Option<(usize, usize, E)> {
+    arg_extremum(mat, Extremum::Max, nan)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_minmax() {
+        let nan = f64::NAN;
+        let m = 19;
+        let n = 6;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i == 3 && j == 0) || (i == 5 && j == 4) || i == 7 {
+                nan
+            } else {
+                ((i * 7 + j * 13) % 11) as f64 - 5.0
+            }
+        });
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+
+        for nan_handling in [NanHandling::Propagate, NanHandling::Ignore] {
+            for A in [A.as_ref(), A_row_major] {
+                let mut min = Col::<f64>::zeros(m);
+                let mut max = Col::<f64>::zeros(m);
+                let mut argmin = alloc::vec![None; m];
+                let mut argmax = alloc::vec![None; m];
+                col_min(min.as_mut(), A, nan_handling);
+                col_max(max.as_mut(), A, nan_handling);
+                col_argmin(&mut argmin, A, nan_handling);
+                col_argmax(&mut argmax, A, nan_handling);
+
+                for i in 0..m {
+                    let row = (0..n).map(|j| A.read(i, j)).collect::<alloc::vec::Vec<_>>();
+                    let first_nan = row.iter().position(|x| x.is_nan());
+                    let valid = row.iter().copied().filter(|x| !x.is_nan());
+
+                    if nan_handling == NanHandling::Propagate && first_nan.is_some() {
+                        assert!(min.read(i).is_nan());
+                        assert!(max.read(i).is_nan());
+                        assert!(argmin[i] == first_nan);
+                        assert!(argmax[i] == first_nan);
+                    } else if first_nan.is_some() && valid.clone().count() == 0 {
+                        assert!(min.read(i).is_nan());
+                        assert!(max.read(i).is_nan());
+                        assert!(argmin[i] == None);
+                        assert!(argmax[i] == None);
+                    } else {
+                        let expected_min = valid.clone().fold(f64::INFINITY, f64::min);
+                        let expected_max = valid.fold(f64::NEG_INFINITY, f64::max);
+                        assert!(min.read(i) == expected_min);
+                        assert!(max.read(i) == expected_max);
+                        assert!(argmin[i] == row.iter().position(|&x| x == expected_min));
+                        assert!(argmax[i] == row.iter().position(|&x| x == expected_max));
+                    }
+                }
+            }
+        }
+
+        for nan_handling in [NanHandling::Propagate, NanHandling::Ignore] {
+            let values = (0..n)
+                .flat_map(|j| (0..m).map(move |i| (i, j)))
+                .map(|(i, j)| (i, j, A.read(i, j)));
+            let first_nan = values.clone().find(|(_, _, x)| x.is_nan());
+            if nan_handling == NanHandling::Propagate {
+                let (i, j, _) = first_nan.unwrap();
+                assert!(argmin(A.as_ref(), nan_handling).map(|(i, j, _)| (i, j)) == Some((i, j)));
+                assert!(argmax(A.as_ref(), nan_handling).map(|(i, j, _)| (i, j)) == Some((i, j)));
+            } else {
+                let valid = values.filter(|(_, _, x)| !x.is_nan());
+                let expected_min =
+                    valid
+                        .clone()
+                        .fold(None, |acc: Option<(usize, usize, f64)>, x| {
+                            if acc.map_or(true, |acc| x.2 < acc.2) {
+                                Some(x)
+                            } else {
+                                acc
+                            }
+                        });
+                let expected_max = valid.fold(None, |acc: Option<(usize, usize, f64)>, x| {
+                    if acc.map_or(true, |acc| x.2 > acc.2) {
+                        Some(x)
+                    } else {
+                        acc
+                    }
+                });
+                assert!(argmin(A.as_ref(), nan_handling) == expected_min);
+                assert!(argmax(A.as_ref(), nan_handling) == expected_max);
+            }
+        }
+        let all_nan = Mat::<f64>::from_fn(2, 3, |_, _| nan);
+        assert!(argmax(all_nan.as_ref(), NanHandling::Ignore) == None);
+        assert!(argmax(Mat::<f64>::zeros(0, 3).as_ref(), NanHandling::Propagate) == None);
+
+        let mut min = Row::<f64>::zeros(n);
+        let mut argmax = alloc::vec![None; n];
+        row_min(min.as_mut(), A.as_ref(), NanHandling::Ignore);
+        row_argmax(&mut argmax, A.as_ref(), NanHandling::Ignore);
+        for j in 0..n {
+            let col = (0..m).map(|i| A.read(i, j)).filter(|x| !x.is_nan());
+            let expected_min = col.clone().fold(f64::INFINITY, f64::min);
+            let expected_max = col.fold(f64::NEG_INFINITY, f64::max);
+            assert!(min.read(j) == expected_min);
+            assert!(A.read(argmax[j].unwrap(), j) == expected_max);
+        }
+    }
+}
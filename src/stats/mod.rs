@@ -1,9 +1,70 @@
-use crate::{Col, ComplexField, Mat, Row};
+use crate::{
+    linalg::{
+        cholesky::llt::CholeskyError,
+        matmul::triangular::{self, BlockStructure},
+        solvers::Cholesky,
+    },
+    Col, ColRef, ComplexField, Mat, MatRef, Row, Side,
+};
+use equator::assert;
 use rand::distributions::Distribution;
 use rand_distr::{Standard, StandardNormal};
 
+mod autocorr;
+mod compensated;
+mod count;
+mod gram;
+mod histogram;
+mod masked;
 mod meanvar;
-pub use meanvar::{col_mean, col_varm, row_mean, row_varm, NanHandling};
+mod minmax;
+mod online;
+mod quantile;
+mod rank;
+mod rolling;
+mod shrinkage;
+#[cfg(feature = "std")]
+mod softmax;
+mod sparse;
+mod sum;
+mod whiten;
+pub use autocorr::{autocorr, autocorr_mat, autocov, autocov_mat, AutocovMethod};
+pub use compensated::{
+    col_mean_compensated, col_sum_compensated, dot_compensated, row_mean_compensated,
+    row_sum_compensated,
+};
+pub use count::{col_mode, col_nan_count, is_finite_mask, row_mode, row_nan_count, unique_counts};
+pub use gram::{cosine_similarity, gram};
+pub use histogram::{histogram, histogram2d};
+pub use masked::{
+    col_mean_masked, col_sum_masked, col_varm_masked, row_mean_masked, row_sum_masked,
+    row_varm_masked,
+};
+pub use meanvar::{
+    col_kurtosis, col_mean, col_skewness, col_varm, col_varm_with_ddof, row_kurtosis, row_mean,
+    row_skewness, row_varm, row_varm_with_ddof, NanHandling,
+};
+pub use minmax::{
+    argmax, argmin, col_argmax, col_argmin, col_max, col_min, row_argmax, row_argmin, row_max,
+    row_min,
+};
+pub use online::{OnlineCovariance, OnlineMeanVar};
+pub use quantile::{
+    col_median, col_quantile, col_trimmed_mean, col_winsorized_mean, row_median, row_quantile,
+    row_trimmed_mean, row_winsorized_mean, QuantileInterpolation,
+};
+pub use rank::{rank_transform, RankTies};
+pub use rolling::{rolling_mean, rolling_var};
+pub use shrinkage::{shrinkage_cov, ShrinkageMethod};
+#[cfg(feature = "std")]
+#[cfg_attr(docsrs, doc(cfg(feature = "std")))]
+pub use softmax::{col_logsumexp, row_logsumexp, softmax_rows_in_place};
+pub use sparse::{
+    col_mean_sparse, col_varm_sparse, col_varm_sparse_with_ddof, row_mean_sparse, row_varm_sparse,
+    row_varm_sparse_with_ddof,
+};
+pub use sum::{col_prod, col_sum, row_prod, row_sum};
+pub use whiten::{Whitener, WhiteningKind};
 
 /// The normal distribution, `N(mean, std_dev**2)`.
 pub struct Normal<E: ComplexField> {
@@ -84,6 +145,21 @@ pub struct UnitaryMat {
     pub dimension: usize,
 }
 
+/// The multivariate normal distribution, `N(mean, covariance)`.
+pub struct MultivariateNormal<E: ComplexField> {
+    mean: Col<E>,
+    cholesky_factor: Mat<E>,
+}
+
+/// The multivariate normal distribution, `N(mean, covariance)`, where each row of the sampled
+/// matrix is an independent sample.
+pub struct MultivariateNormalMat<E: ComplexField> {
+    /// Number of rows of the sampled matrix.
+    pub nrows: usize,
+    /// Multivariate normal distribution parameters for a single sample.
+    pub normal: MultivariateNormal<E>,
+}
+
 impl<E: ComplexField> Normal<E> {
     /// Construct, from dimensions, mean and standard deviation.
     ///
@@ -115,6 +191,123 @@ impl<E: ComplexField> Normal<E> {
     }
 }
 
+impl<E: ComplexField> MultivariateNormal<E> {
+    /// Construct, from the mean and covariance.
+    ///
+    /// Parameters:
+    /// - mean (`μ`, unrestricted)
+    /// - covariance (`Σ`, must be positive definite). Only the lower triangular half is accessed.
+    ///
+    /// # Panics
+    /// Panics if `covariance` is not a square matrix with the same dimension as `mean`.
+    #[track_caller]
+    pub fn new(mean: ColRef<'_, E>, covariance: MatRef<'_, E>) -> Result<Self, CholeskyError> {
+        assert!(all(
+            covariance.nrows() == mean.nrows(),
+            covariance.ncols() == mean.nrows(),
+        ));
+        let cholesky_factor = Cholesky::try_new(covariance, Side::Lower)?.compute_l();
+        Ok(Self {
+            mean: mean.to_owned(),
+            cholesky_factor,
+        })
+    }
+
+    /// Construct, from the mean and a precomputed Cholesky factor `L` of the covariance, such
+    /// that `Σ = L Lᴴ`. Only the lower triangular half of `L` is accessed.
+    ///
+    /// # Panics
+    /// Panics if `cholesky_factor` is not a square matrix with the same dimension as `mean`.
+    #[track_caller]
+    pub fn from_cholesky_factor(mean: ColRef<'_, E>, cholesky_factor: MatRef<'_, E>) -> Self {
+        assert!(all(
+            cholesky_factor.nrows() == mean.nrows(),
+            cholesky_factor.ncols() == mean.nrows(),
+        ));
+        let n = mean.nrows();
+        Self {
+            mean: mean.to_owned(),
+            cholesky_factor: Mat::from_fn(n, n, |i, j| {
+                if i >= j {
+                    cholesky_factor.read(i, j)
+                } else {
+                    E::faer_zero()
+                }
+            }),
+        }
+    }
+
+    /// Returns the dimension of the samples.
+    #[inline]
+    pub fn dim(&self) -> usize {
+        self.mean.nrows()
+    }
+
+    /// Returns the mean of the distribution.
+    #[inline]
+    pub fn mean(&self) -> ColRef<'_, E> {
+        self.mean.as_ref()
+    }
+
+    /// Returns the lower triangular Cholesky factor `L` of the covariance.
+    #[inline]
+    pub fn cholesky_factor(&self) -> MatRef<'_, E> {
+        self.cholesky_factor.as_ref()
+    }
+}
+
+impl<E: ComplexField> Distribution<Col<E>> for MultivariateNormal<E>
+where
+    StandardNormal: Distribution<E>,
+{
+    fn sample<R: rand::prelude::Rng + ?Sized>(&self, rng: &mut R) -> Col<E> {
+        let z = StandardNormalCol { nrows: self.dim() }.sample(rng);
+        let mut x = self.mean.clone();
+        triangular::matmul(
+            x.as_mut().as_2d_mut(),
+            BlockStructure::Rectangular,
+            self.cholesky_factor.as_ref(),
+            BlockStructure::TriangularLower,
+            z.as_ref().as_2d(),
+            BlockStructure::Rectangular,
+            Some(E::faer_one()),
+            E::faer_one(),
+            crate::Parallelism::None,
+        );
+        x
+    }
+}
+
+impl<E: ComplexField> Distribution<Mat<E>> for MultivariateNormalMat<E>
+where
+    StandardNormal: Distribution<E>,
+{
+    fn sample<R: rand::prelude::Rng + ?Sized>(&self, rng: &mut R) -> Mat<E> {
+        let dim = self.normal.dim();
+        let z = StandardNormalMat {
+            nrows: self.nrows,
+            ncols: dim,
+        }
+        .sample(rng);
+
+        // each row is `mean + L z`, so the samples are `1 meanᵀ + Z Lᵀ`
+        let mean = self.normal.mean();
+        let mut x = Mat::from_fn(self.nrows, dim, |_, j| mean.read(j));
+        triangular::matmul(
+            x.as_mut(),
+            BlockStructure::Rectangular,
+            z.as_ref(),
+            BlockStructure::Rectangular,
+            self.normal.cholesky_factor().transpose(),
+            BlockStructure::TriangularUpper,
+            Some(E::faer_one()),
+            E::faer_one(),
+            crate::get_global_parallelism(),
+        );
+        x
+    }
+}
+
 impl<E: ComplexField> Distribution<Mat<E>> for NormalMat<E>
 where
     StandardNormal: Distribution<E>,
@@ -245,3 +438,49 @@ where
         Row::from_fn(self.ncols, |_| Standard.sample(rng))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_multivariate_normal() {
+        let mean = crate::col![1.0, -2.0, 0.5f64];
+        let cov = crate::mat![[4.0, 1.0, 0.5], [1.0, 2.0, 0.3], [0.5, 0.3, 1.0f64]];
+
+        let normal = MultivariateNormal::new(mean.as_ref(), cov.as_ref()).unwrap();
+        let l = normal.cholesky_factor();
+        assert!((l * l.transpose() - &cov).norm_max() < 1e-12);
+
+        let from_factor = MultivariateNormal::from_cholesky_factor(mean.as_ref(), l);
+        assert!(from_factor.cholesky_factor() == l);
+
+        let rng = &mut StdRng::seed_from_u64(0);
+        let n = 20_000;
+        let x = MultivariateNormalMat { nrows: n, normal }.sample(rng);
+
+        let mut sample_mean = Row::<f64>::zeros(3);
+        row_mean(
+            sample_mean.as_mut(),
+            x.as_ref(),
+            NanHandling::Propagate,
+            crate::Parallelism::None,
+        );
+        assert!((sample_mean.transpose() - &mean).norm_max() < 0.1);
+
+        let centered = Mat::<f64>::from_fn(n, 3, |i, j| x.read(i, j) - sample_mean.read(j));
+        let sample_cov = centered.transpose() * &centered;
+        let sample_cov = Mat::<f64>::from_fn(3, 3, |i, j| sample_cov.read(i, j) / (n - 1) as f64);
+        assert!((&sample_cov - &cov).norm_max() < 0.15);
+
+        let normal = MultivariateNormal::new(mean.as_ref(), cov.as_ref()).unwrap();
+        assert!(normal.sample(rng).nrows() == 3);
+
+        let not_pd = crate::mat![[1.0, 2.0], [2.0, 1.0f64]];
+        assert!(
+            MultivariateNormal::new(crate::col![0.0, 0.0f64].as_ref(), not_pd.as_ref()).is_err()
+        );
+    }
+}
@@ -0,0 +1,471 @@
+use super::{
+    meanvar::{from_usize, var_from_sum},
+    row_mean, row_varm_with_ddof, NanHandling,
+};
+use crate::{
+    linalg::matmul::triangular::{self, BlockStructure},
+    prelude::*,
+    ComplexField, Parallelism, RealField,
+};
+use equator::assert;
+use reborrow::*;
+
+/// Streaming accumulator for the mean and variance of the columns of a data matrix.
+///
+/// Each row of the data matrix is a sample, and the statistics are computed for each column
+/// (feature) separately. Samples can be fed in batches with [`OnlineMeanVar::update`], and two
+/// accumulators built from disjoint data can be combined with [`OnlineMeanVar::merge`], which
+/// allows aggregating the statistics in parallel.
+///
+/// The batches are combined using the pairwise update formulas of Chan, Golub and LeVeque, which
+/// are numerically stable.
+///
+/// NaN values are propagated to the statistics of the corresponding column.
+#[derive(Clone, Debug)]
+pub struct OnlineMeanVar<E: ComplexField> {
+    count: usize,
+    mean: Row<E>,
+    // sum of squared deviations from the mean
+    m2: Row<E::Real>,
+}
+
+impl<E: ComplexField> OnlineMeanVar<E> {
+    /// Creates a new empty accumulator for samples with `ncols` features.
+    pub fn new(ncols: usize) -> Self {
+        Self {
+            count: 0,
+            mean: Row::from_fn(ncols, |_| E::faer_nan()),
+            m2: Row::zeros(ncols),
+        }
+    }
+
+    /// Returns the number of features of the samples.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.mean.ncols()
+    }
+
+    /// Returns the number of samples accumulated so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the running mean of each column, or NaN if no samples have been accumulated.
+    #[inline]
+    pub fn mean(&self) -> RowRef<'_, E> {
+        self.mean.as_ref()
+    }
+
+    /// Computes the running variance of each column, using `count - ddof` as the denominator,
+    /// and stores the result in `out`.
+    ///
+    /// If no samples have been accumulated, the variance is NaN, and if `0 < count <= ddof`, the
+    /// variance is zero.
+    #[track_caller]
+    pub fn variance(&self, out: RowMut<'_, E::Real>, ddof: usize) {
+        assert!(out.ncols() == self.ncols());
+        let mut out = out;
+        for j in 0..self.ncols() {
+            out.write(j, var_from_sum(self.m2.read(j), self.count, ddof));
+        }
+    }
+
+    /// Adds the rows of `batch` to the accumulated samples.
+    ///
+    /// # Panics
+    /// Panics if `batch.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn update(&mut self, batch: MatRef<'_, E>) {
+        assert!(batch.ncols() == self.ncols());
+
+        let count = batch.nrows();
+        if count == 0 {
+            return;
+        }
+
+        let n = self.ncols();
+        let mut mean = Row::<E>::zeros(n);
+        let mut m2 = Row::<E::Real>::zeros(n);
+        row_mean(
+            mean.as_mut(),
+            batch,
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        row_varm_with_ddof(
+            m2.as_mut(),
+            batch,
+            mean.as_ref(),
+            0,
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+
+        let count_e = from_usize::<E::Real>(count);
+        for j in 0..n {
+            m2.write(j, m2.read(j).faer_mul(count_e));
+        }
+
+        self.combine(count, mean.as_ref(), m2.as_ref());
+    }
+
+    /// Merges the samples accumulated in `other` into `self`.
+    ///
+    /// # Panics
+    /// Panics if `other.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn merge(&mut self, other: &Self) {
+        assert!(other.ncols() == self.ncols());
+        if other.count == 0 {
+            return;
+        }
+        self.combine(other.count, other.mean.as_ref(), other.m2.as_ref());
+    }
+
+    fn combine(&mut self, count: usize, mean: RowRef<'_, E>, m2: RowRef<'_, E::Real>) {
+        if self.count == 0 {
+            self.count = count;
+            self.mean.copy_from(mean);
+            self.m2.copy_from(m2);
+            return;
+        }
+
+        let total = self.count + count;
+        let total_e = from_usize::<E::Real>(total);
+        let count_e = from_usize::<E::Real>(count);
+        let self_count_e = from_usize::<E::Real>(self.count);
+
+        // weight of the new samples in the combined mean
+        let w = count_e.faer_div(total_e);
+        // weight of the squared difference of the means in the combined sum of squares
+        let w2 = self_count_e.faer_mul(count_e).faer_div(total_e);
+
+        for j in 0..self.ncols() {
+            let delta = mean.read(j).faer_sub(self.mean.read(j));
+            self.mean
+                .write(j, self.mean.read(j).faer_add(delta.faer_scale_real(w)));
+            self.m2.write(
+                j,
+                self.m2
+                    .read(j)
+                    .faer_add(m2.read(j))
+                    .faer_add(delta.faer_abs2().faer_mul(w2)),
+            );
+        }
+        self.count = total;
+    }
+}
+
+/// Streaming accumulator for the mean and covariance matrix of the columns of a data matrix.
+///
+/// Each row of the data matrix is a sample, and each column is a feature. Samples can be fed in
+/// batches with [`OnlineCovariance::update`], which adds the centered batch to the running
+/// co-moment matrix as a rank-`k` update, where `k` is the number of rows of the batch. Two
+/// accumulators built from disjoint data can be combined with [`OnlineCovariance::merge`], which
+/// allows aggregating the statistics in parallel.
+///
+/// NaN values are propagated to the statistics of the corresponding rows and columns.
+#[derive(Clone, Debug)]
+pub struct OnlineCovariance<E: ComplexField> {
+    count: usize,
+    mean: Row<E>,
+    // sum of the outer products of the deviations from the mean, only the lower triangular half
+    // is stored
+    comoment: Mat<E>,
+}
+
+impl<E: ComplexField> OnlineCovariance<E> {
+    /// Creates a new empty accumulator for samples with `ncols` features.
+    pub fn new(ncols: usize) -> Self {
+        Self {
+            count: 0,
+            mean: Row::from_fn(ncols, |_| E::faer_nan()),
+            comoment: Mat::zeros(ncols, ncols),
+        }
+    }
+
+    /// Returns the number of features of the samples.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.mean.ncols()
+    }
+
+    /// Returns the number of samples accumulated so far.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Returns the running mean of each column, or NaN if no samples have been accumulated.
+    #[inline]
+    pub fn mean(&self) -> RowRef<'_, E> {
+        self.mean.as_ref()
+    }
+
+    /// Computes the running covariance matrix of the columns, using `count - ddof` as the
+    /// denominator, and stores the result in `out`.
+    ///
+    /// If no samples have been accumulated, the covariance is NaN, and if `0 < count <= ddof`,
+    /// the covariance is zero.
+    ///
+    /// # Panics
+    /// Panics if `out` is not a square matrix of dimension `self.ncols()`.
+    #[track_caller]
+    pub fn covariance(&self, out: MatMut<'_, E>, ddof: usize) {
+        let n = self.ncols();
+        assert!(all(out.nrows() == n, out.ncols() == n));
+        let mut out = out;
+
+        let scale = var_from_sum(E::Real::faer_one(), self.count, ddof);
+        for j in 0..n {
+            for i in j..n {
+                let value = self.comoment.read(i, j).faer_scale_real(scale);
+                out.write(i, j, value);
+                if i != j {
+                    out.write(j, i, value.faer_conj());
+                }
+            }
+        }
+    }
+
+    /// Adds the rows of `batch` to the accumulated samples.
+    ///
+    /// # Panics
+    /// Panics if `batch.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn update(&mut self, batch: MatRef<'_, E>) {
+        assert!(batch.ncols() == self.ncols());
+
+        let count = batch.nrows();
+        if count == 0 {
+            return;
+        }
+
+        let n = self.ncols();
+        let mut mean = Row::<E>::zeros(n);
+        row_mean(
+            mean.as_mut(),
+            batch,
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        let centered = Mat::<E>::from_fn(count, n, |i, j| batch.read(i, j).faer_sub(mean.read(j)));
+
+        triangular::matmul(
+            self.comoment.as_mut(),
+            BlockStructure::TriangularLower,
+            centered.adjoint(),
+            BlockStructure::Rectangular,
+            centered.as_ref(),
+            BlockStructure::Rectangular,
+            Some(E::faer_one()),
+            E::faer_one(),
+            Parallelism::None,
+        );
+
+        self.combine_mean(count, mean.as_ref());
+    }
+
+    /// Merges the samples accumulated in `other` into `self`.
+    ///
+    /// # Panics
+    /// Panics if `other.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn merge(&mut self, other: &Self) {
+        assert!(other.ncols() == self.ncols());
+        if other.count == 0 {
+            return;
+        }
+
+        let n = self.ncols();
+        for j in 0..n {
+            for i in j..n {
+                self.comoment.write(
+                    i,
+                    j,
+                    self.comoment.read(i, j).faer_add(other.comoment.read(i, j)),
+                );
+            }
+        }
+
+        self.combine_mean(other.count, other.mean.as_ref());
+    }
+
+    // combines the running mean with the mean of `count` new samples, and adds the correction
+    // term for the difference of the means to the co-moment matrix. the co-moment of the new
+    // samples must already have been added
+    fn combine_mean(&mut self, count: usize, mean: RowRef<'_, E>) {
+        if self.count == 0 {
+            self.count = count;
+            self.mean.copy_from(mean);
+            return;
+        }
+
+        let total = self.count + count;
+        let total_e = from_usize::<E::Real>(total);
+        let count_e = from_usize::<E::Real>(count);
+        let self_count_e = from_usize::<E::Real>(self.count);
+
+        let w = count_e.faer_div(total_e);
+        let w2 = self_count_e.faer_mul(count_e).faer_div(total_e);
+
+        let n = self.ncols();
+        let delta = Row::<E>::from_fn(n, |j| mean.read(j).faer_sub(self.mean.read(j)));
+
+        let mut comoment = self.comoment.as_mut();
+        for j in 0..n {
+            // the co-moment is `centered^H centered`, so the conjugated factor is the row index
+            let d_j = delta.read(j).faer_scale_real(w2);
+            for i in j..n {
+                comoment.write(
+                    i,
+                    j,
+                    comoment
+                        .read(i, j)
+                        .faer_add(delta.read(i).faer_conj().faer_mul(d_j)),
+                );
+            }
+        }
+        for j in 0..n {
+            self.mean.write(
+                j,
+                self.mean.read(j).faer_add(delta.read(j).faer_scale_real(w)),
+            );
+        }
+        self.count = total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stats::row_varm;
+    use equator::assert;
+
+    #[test]
+    fn test_online_meanvar() {
+        let m = 53;
+        let n = 4;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            ((i * (j + 1)) as f64).sin() * 10.0 + (j as f64) * 1000.0
+        });
+
+        let mut mean = Row::<f64>::zeros(n);
+        let mut var = Row::<f64>::zeros(n);
+        row_mean(
+            mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        row_varm(
+            var.as_mut(),
+            A.as_ref(),
+            mean.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+
+        let mut acc = OnlineMeanVar::<f64>::new(n);
+        assert!(acc.mean().read(0).is_nan());
+        for batch in [0..1, 1..10, 10..10, 10..31, 31..m] {
+            acc.update(A.as_ref().subrows(batch.start, batch.end - batch.start));
+        }
+        assert!(acc.count() == m);
+
+        let mut acc_var = Row::<f64>::zeros(n);
+        acc.variance(acc_var.as_mut(), 1);
+        for j in 0..n {
+            assert!((acc.mean().read(j) - mean.read(j)).abs() < 1e-9);
+            assert!((acc_var.read(j) - var.read(j)).abs() < 1e-9);
+        }
+
+        let mut left = OnlineMeanVar::<f64>::new(n);
+        let mut right = OnlineMeanVar::<f64>::new(n);
+        left.update(A.as_ref().subrows(0, 20));
+        right.update(A.as_ref().subrows(20, m - 20));
+        left.merge(&right);
+        assert!(left.count() == m);
+
+        left.variance(acc_var.as_mut(), 1);
+        for j in 0..n {
+            assert!((left.mean().read(j) - mean.read(j)).abs() < 1e-9);
+            assert!((acc_var.read(j) - var.read(j)).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_online_covariance() {
+        let m = 47;
+        let n = 3;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            ((i * (j + 2)) as f64).cos() * 3.0 + (i as f64) * (j as f64) * 0.1 + 100.0
+        });
+
+        let mut mean = Row::<f64>::zeros(n);
+        row_mean(
+            mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        let centered = Mat::<f64>::from_fn(m, n, |i, j| A.read(i, j) - mean.read(j));
+        let cov = centered.transpose() * &centered * crate::scale(1.0 / (m - 1) as f64);
+
+        let mut acc = OnlineCovariance::<f64>::new(n);
+        for batch in [0..5, 5..5, 5..6, 6..30, 30..m] {
+            acc.update(A.as_ref().subrows(batch.start, batch.end - batch.start));
+        }
+        assert!(acc.count() == m);
+
+        let mut acc_cov = Mat::<f64>::zeros(n, n);
+        acc.covariance(acc_cov.as_mut(), 1);
+        assert!((&acc_cov - &cov).norm_max() < 1e-9);
+        assert!((acc.mean() - &mean).norm_max() < 1e-9);
+
+        let mut left = OnlineCovariance::<f64>::new(n);
+        let mut right = OnlineCovariance::<f64>::new(n);
+        left.update(A.as_ref().subrows(0, 13));
+        right.update(A.as_ref().subrows(13, m - 13));
+        left.merge(&right);
+        assert!(left.count() == m);
+
+        left.covariance(acc_cov.as_mut(), 1);
+        assert!((&acc_cov - &cov).norm_max() < 1e-9);
+    }
+
+    #[test]
+    fn test_online_covariance_complex() {
+        let m = 31;
+        let n = 3;
+        let A = Mat::<c64>::from_fn(m, n, |i, j| {
+            c64::new(
+                ((i * (j + 1)) as f64).sin() + 2.0,
+                ((i + 3 * j) as f64).cos() * (j as f64 + 1.0),
+            )
+        });
+
+        let mut mean = Row::<c64>::zeros(n);
+        row_mean(
+            mean.as_mut(),
+            A.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        let centered = Mat::<c64>::from_fn(m, n, |i, j| A.read(i, j) - mean.read(j));
+        let cov =
+            centered.adjoint() * &centered * crate::scale(c64::new(1.0 / (m - 1) as f64, 0.0));
+
+        let mut left = OnlineCovariance::<c64>::new(n);
+        let mut right = OnlineCovariance::<c64>::new(n);
+        left.update(A.as_ref().subrows(0, 10));
+        right.update(A.as_ref().subrows(10, m - 10));
+        left.merge(&right);
+        assert!(left.count() == m);
+
+        let mut acc_cov = Mat::<c64>::zeros(n, n);
+        left.covariance(acc_cov.as_mut(), 1);
+        assert!((&acc_cov - &cov).norm_max() < 1e-9);
+    }
+}
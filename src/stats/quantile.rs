@@ -0,0 +1,440 @@
+use super::{meanvar::from_usize, NanHandling};
+use crate::{prelude::*, RealField};
+use core::cmp::Ordering;
+use equator::assert;
+
+/// Specifies how a quantile is computed when it lies between two data points `lo < hi`, with
+/// fractional position `frac` between them.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum QuantileInterpolation {
+    /// `lo + frac * (hi - lo)`.
+    Linear,
+    /// `lo`.
+    Lower,
+    /// `hi`.
+    Higher,
+    /// `(lo + hi) / 2`.
+    Midpoint,
+}
+
+#[inline]
+fn cmp_non_nan<E: RealField>(a: &E, b: &E) -> Ordering {
+    // nans are filtered out before selection, so this is never reached with unordered values
+    a.partial_cmp(b).unwrap_or(Ordering::Equal)
+}
+
+/// Computes the `q`-th quantile of `buf`, which is reordered in the process.
+fn quantile_of_slice<E: RealField>(
+    buf: &mut [E],
+    q: f64,
+    interpolation: QuantileInterpolation,
+) -> E {
+    let n = buf.len();
+    if n == 0 {
+        return E::faer_nan();
+    }
+
+    let pos = q * (n - 1) as f64;
+    let lo_idx = Ord::min(pos as usize, n - 1);
+    let frac = pos - lo_idx as f64;
+
+    let (_, lo, right) = buf.select_nth_unstable_by(lo_idx, cmp_non_nan);
+    let lo = *lo;
+    if frac == 0.0 || right.is_empty() {
+        return lo;
+    }
+
+    // the smallest element to the right of the selected one is the next order statistic
+    let mut hi = right[0];
+    for &x in &right[1..] {
+        if x < hi {
+            hi = x;
+        }
+    }
+
+    match interpolation {
+        QuantileInterpolation::Linear => {
+            lo.faer_add(E::faer_from_f64(frac).faer_mul(hi.faer_sub(lo)))
+        }
+        QuantileInterpolation::Lower => lo,
+        QuantileInterpolation::Higher => hi,
+        QuantileInterpolation::Midpoint => lo
+            .faer_add(hi)
+            .faer_scale_power_of_two(E::faer_from_f64(0.5)),
+    }
+}
+
+fn col_quantile_impl<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    q: f64,
+    interpolation: QuantileInterpolation,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let m = mat.nrows();
+    let n = mat.ncols();
+
+    let mut buf = alloc::vec::Vec::<E>::with_capacity(n);
+    for i in 0..m {
+        buf.clear();
+        let mut has_nan = false;
+        for j in 0..n {
+            let x = mat.read(i, j);
+            if x.faer_is_nan() {
+                has_nan = true;
+            } else {
+                buf.push(x);
+            }
+        }
+
+        let value = if has_nan && nan == NanHandling::Propagate {
+            E::faer_nan()
+        } else {
+            quantile_of_slice(&mut buf, q, interpolation)
+        };
+        out.write(i, value);
+    }
+}
+
+/// Computes the `q`-th quantile of the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the quantile of the `i`-th row of `mat`, using the same
+/// conventions as NumPy's `quantile`. If no non-NaN values are available, the result is NaN.
+///
+/// # Panics
+/// Panics if `q` is not in `[0, 1]`, or if `out.nrows() != mat.nrows()`.
+#[track_caller]
+pub fn col_quantile<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    q: f64,
+    interpolation: QuantileInterpolation,
+    nan: NanHandling,
+) {
+    assert!(all(out.nrows() == mat.nrows(), q >= 0.0, q <= 1.0));
+    col_quantile_impl(out, mat, q, interpolation, nan);
+}
+
+/// Computes the `q`-th quantile of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the quantile of the `j`-th column of `mat`, using the same
+/// conventions as NumPy's `quantile`. If no non-NaN values are available, the result is NaN.
+///
+/// # Panics
+/// Panics if `q` is not in `[0, 1]`, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn row_quantile<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    q: f64,
+    interpolation: QuantileInterpolation,
+    nan: NanHandling,
+) {
+    assert!(all(out.ncols() == mat.ncols(), q >= 0.0, q <= 1.0));
+    col_quantile_impl(out.transpose_mut(), mat.transpose(), q, interpolation, nan);
+}
+
+/// Computes the median of the columns of `mat` and stores the result in `out`.
+///
+/// # Panics
+/// Panics if `out.nrows() != mat.nrows()`.
+#[track_caller]
+pub fn col_median<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_quantile_impl(out, mat, 0.5, QuantileInterpolation::Linear, nan);
+}
+
+/// Computes the median of the rows of `mat` and stores the result in `out`.
+///
+/// # Panics
+/// Panics if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn row_median<E: RealField>(out: RowMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_quantile_impl(
+        out.transpose_mut(),
+        mat.transpose(),
+        0.5,
+        QuantileInterpolation::Linear,
+        nan,
+    );
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Robust {
+    Trimmed,
+    Winsorized,
+}
+
+/// Computes the trimmed or winsorized mean of `buf`, which is reordered in the process.
+fn robust_mean_of_slice<E: RealField>(buf: &mut [E], proportion: f64, kind: Robust) -> E {
+    let n = buf.len();
+    if n == 0 {
+        return E::faer_nan();
+    }
+
+    let k = (proportion * n as f64) as usize;
+    if k > 0 {
+        // move the `k` smallest values to the front, and the `k` largest values to the back
+        buf.select_nth_unstable_by(k, cmp_non_nan);
+        if n - k - 1 > k {
+            buf[k + 1..].select_nth_unstable_by(n - 2 * k - 2, cmp_non_nan);
+        }
+    }
+
+    let mut sum = E::faer_zero();
+    for &x in &buf[k..n - k] {
+        sum = sum.faer_add(x);
+    }
+
+    match kind {
+        Robust::Trimmed => sum.faer_div(from_usize::<E>(n - 2 * k)),
+        Robust::Winsorized => {
+            let k_e = from_usize::<E>(k);
+            sum.faer_add(k_e.faer_mul(buf[k]))
+                .faer_add(k_e.faer_mul(buf[n - k - 1]))
+                .faer_div(from_usize::<E>(n))
+        }
+    }
+}
+
+fn col_robust_mean_impl<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    proportion: f64,
+    kind: Robust,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let m = mat.nrows();
+    let n = mat.ncols();
+
+    let mut buf = alloc::vec::Vec::<E>::with_capacity(n);
+    for i in 0..m {
+        buf.clear();
+        let mut has_nan = false;
+        for j in 0..n {
+            let x = mat.read(i, j);
+            if x.faer_is_nan() {
+                has_nan = true;
+            } else {
+                buf.push(x);
+            }
+        }
+
+        let value = if has_nan && nan == NanHandling::Propagate {
+            E::faer_nan()
+        } else {
+            robust_mean_of_slice(&mut buf, proportion, kind)
+        };
+        out.write(i, value);
+    }
+}
+
+/// Computes the trimmed mean of the columns of `mat` and stores the result in `out`.
+///
+/// For each row of `mat` with `n` included entries, the `floor(proportion * n)` smallest and
+/// largest values are dropped before averaging the remaining ones. If no non-NaN values are
+/// available, the result is NaN.
+///
+/// # Panics
+/// Panics if `proportion` is not in `[0, 0.5)`, or if `out.nrows() != mat.nrows()`.
+#[track_caller]
+pub fn col_trimmed_mean<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    proportion: f64,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        proportion >= 0.0,
+        proportion < 0.5,
+    ));
+    col_robust_mean_impl(out, mat, proportion, Robust::Trimmed, nan);
+}
+
+/// Computes the trimmed mean of the rows of `mat` and stores the result in `out`.
+///
+/// See [`col_trimmed_mean`] for the conventions.
+///
+/// # Panics
+/// Panics if `proportion` is not in `[0, 0.5)`, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn row_trimmed_mean<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    proportion: f64,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        proportion >= 0.0,
+        proportion < 0.5,
+    ));
+    col_robust_mean_impl(
+        out.transpose_mut(),
+        mat.transpose(),
+        proportion,
+        Robust::Trimmed,
+        nan,
+    );
+}
+
+/// Computes the winsorized mean of the columns of `mat` and stores the result in `out`.
+///
+/// For each row of `mat` with `n` included entries, the `floor(proportion * n)` smallest and
+/// largest values are clamped to the closest remaining value before averaging. If no non-NaN
+/// values are available, the result is NaN.
+///
+/// # Panics
+/// Panics if `proportion` is not in `[0, 0.5)`, or if `out.nrows() != mat.nrows()`.
+#[track_caller]
+pub fn col_winsorized_mean<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    proportion: f64,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        proportion >= 0.0,
+        proportion < 0.5,
+    ));
+    col_robust_mean_impl(out, mat, proportion, Robust::Winsorized, nan);
+}
+
+/// Computes the winsorized mean of the rows of `mat` and stores the result in `out`.
+///
+/// See [`col_winsorized_mean`] for the conventions.
+///
+/// # Panics
+/// Panics if `proportion` is not in `[0, 0.5)`, or if `out.ncols() != mat.ncols()`.
+#[track_caller]
+pub fn row_winsorized_mean<E: RealField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    proportion: f64,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        proportion >= 0.0,
+        proportion < 0.5,
+    ));
+    col_robust_mean_impl(
+        out.transpose_mut(),
+        mat.transpose(),
+        proportion,
+        Robust::Winsorized,
+        nan,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_median() {
+        let nan = f64::NAN;
+        let A = mat![
+            [3.0, 1.0, 2.0, 5.0],
+            [4.0, nan, 1.0, 2.0],
+            [1.0, 1.0, 1.0, 1.0],
+        ];
+
+        let mut col_median = Col::<f64>::zeros(3);
+        super::col_median(col_median.as_mut(), A.as_ref(), NanHandling::Ignore);
+        assert!(col_median == col![2.5, 2.0, 1.0]);
+
+        super::col_median(col_median.as_mut(), A.as_ref(), NanHandling::Propagate);
+        assert!(col_median[0] == 2.5);
+        assert!(col_median[1].is_nan());
+        assert!(col_median[2] == 1.0);
+
+        let mut row_median = Row::<f64>::zeros(4);
+        super::row_median(row_median.as_mut(), A.as_ref(), NanHandling::Ignore);
+        assert!(row_median == row![3.0, 1.0, 1.0, 2.0]);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let A = mat![[1.0, 2.0, 3.0, 4.0], [4.0, 3.0, 2.0, 1.0f64]];
+        let mut out = Col::<f64>::zeros(2);
+
+        // position = 0.4 * 3 = 1.2, between 2.0 and 3.0
+        let q = 0.4;
+        for (interpolation, expected) in [
+            (QuantileInterpolation::Linear, 2.2),
+            (QuantileInterpolation::Lower, 2.0),
+            (QuantileInterpolation::Higher, 3.0),
+            (QuantileInterpolation::Midpoint, 2.5),
+        ] {
+            col_quantile(
+                out.as_mut(),
+                A.as_ref(),
+                q,
+                interpolation,
+                NanHandling::Ignore,
+            );
+            assert!((out[0] - expected).abs() < 1e-12);
+            assert!((out[1] - expected).abs() < 1e-12);
+        }
+
+        col_quantile(
+            out.as_mut(),
+            A.as_ref(),
+            1.0,
+            QuantileInterpolation::Linear,
+            NanHandling::Ignore,
+        );
+        assert!(out == col![4.0, 4.0]);
+
+        let mut out = Row::<f64>::zeros(4);
+        row_quantile(
+            out.as_mut(),
+            A.as_ref(),
+            0.0,
+            QuantileInterpolation::Linear,
+            NanHandling::Ignore,
+        );
+        assert!(out == row![1.0, 2.0, 2.0, 1.0]);
+    }
+
+    #[test]
+    fn test_trimmed_winsorized_mean() {
+        let nan = f64::NAN;
+        let A = mat![[10.0, 1.0, 2.0, 3.0, -50.0], [4.0, 3.0, nan, 2.0, 1.0f64],];
+
+        let mut out = Col::<f64>::zeros(2);
+        col_trimmed_mean(out.as_mut(), A.as_ref(), 0.2, NanHandling::Ignore);
+        // first row: drops -50 and 10, second row: 4 entries, nothing is dropped
+        assert!((out[0] - 2.0).abs() < 1e-12);
+        assert!((out[1] - 2.5).abs() < 1e-12);
+
+        col_trimmed_mean(out.as_mut(), A.as_ref(), 0.25, NanHandling::Ignore);
+        assert!((out[1] - 2.5).abs() < 1e-12);
+
+        col_winsorized_mean(out.as_mut(), A.as_ref(), 0.2, NanHandling::Propagate);
+        // first row: [1, 1, 2, 3, 3]
+        assert!((out[0] - 2.0).abs() < 1e-12);
+        assert!(out[1].is_nan());
+
+        let A = mat![[1.0, 2.0, 3.0, 4.0, 100.0, 6.0, 7.0, 8.0, 9.0, 10.0f64]];
+        col_winsorized_mean(
+            out.as_mut().subrows_mut(0, 1),
+            A.as_ref(),
+            0.1,
+            NanHandling::Ignore,
+        );
+        // [2, 2, 3, 4, 6, 7, 8, 9, 10, 10]
+        assert!((out[0] - 6.1).abs() < 1e-12);
+
+        let mut out = Row::<f64>::zeros(10);
+        row_trimmed_mean(out.as_mut(), A.as_ref(), 0.0, NanHandling::Ignore);
+        assert!(out == A.row(0));
+    }
+}
@@ -0,0 +1,122 @@
+use super::meanvar::from_usize;
+use crate::{prelude::*, RealField};
+use core::cmp::Ordering;
+use equator::assert;
+
+/// Specifies how ranks are assigned to groups of equal values.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RankTies {
+    /// Each value in the group receives the average of the ranks the group spans.
+    Average,
+    /// Each value in the group receives the smallest rank the group spans.
+    Min,
+    /// Each value in the group receives the largest rank the group spans.
+    Max,
+    /// Each value in the group receives the same rank, and the next distinct value receives the
+    /// next integer rank.
+    Dense,
+}
+
+/// Replaces each column of `mat` by the ranks of its values, and stores the result in `out`.
+///
+/// Ranks start at `1`, and ties are resolved according to `tie_policy`, using the same
+/// conventions as SciPy's `rankdata`. NaN values are not ranked: their positions in `out` are
+/// set to NaN, and the remaining values of the column are ranked among themselves.
+///
+/// # Panics
+/// Panics if `out` and `mat` don't have the same dimensions.
+#[track_caller]
+pub fn rank_transform<E: RealField>(out: MatMut<'_, E>, mat: MatRef<'_, E>, tie_policy: RankTies) {
+    assert!(all(out.nrows() == mat.nrows(), out.ncols() == mat.ncols()));
+
+    let mut out = out;
+    let m = mat.nrows();
+    let n = mat.ncols();
+
+    let mut perm = alloc::vec::Vec::<usize>::with_capacity(m);
+    for j in 0..n {
+        let col = mat.col(j);
+
+        perm.clear();
+        for i in 0..m {
+            if col.read(i).faer_is_nan() {
+                out.write(i, j, E::faer_nan());
+            } else {
+                perm.push(i);
+            }
+        }
+        // stable sort, so that equal values are grouped in index order
+        perm.sort_by(|&a, &b| {
+            col.read(a)
+                .partial_cmp(&col.read(b))
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let mut start = 0;
+        let mut dense = 0;
+        while start < perm.len() {
+            let value = col.read(perm[start]);
+            let mut end = start + 1;
+            while end < perm.len() && col.read(perm[end]) == value {
+                end += 1;
+            }
+            dense += 1;
+
+            let rank = match tie_policy {
+                RankTies::Average => {
+                    from_usize::<E>(start + end + 1).faer_scale_power_of_two(E::faer_from_f64(0.5))
+                }
+                RankTies::Min => from_usize::<E>(start + 1),
+                RankTies::Max => from_usize::<E>(end),
+                RankTies::Dense => from_usize::<E>(dense),
+            };
+            for &i in &perm[start..end] {
+                out.write(i, j, rank);
+            }
+            start = end;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_transform() {
+        let nan = f64::NAN;
+        let A = mat![
+            [3.0, 1.0f64],
+            [1.0, nan],
+            [3.0, 2.0],
+            [2.0, 2.0],
+            [3.0, 0.0],
+        ];
+        let mut out = Mat::<f64>::zeros(5, 2);
+
+        rank_transform(out.as_mut(), A.as_ref(), RankTies::Average);
+        assert!(out.col(0) == col![4.0, 1.0, 4.0, 2.0, 4.0]);
+        assert!(out.read(1, 1).is_nan());
+        assert!(all(
+            out.read(0, 1) == 2.0,
+            out.read(2, 1) == 3.5,
+            out.read(3, 1) == 3.5,
+            out.read(4, 1) == 1.0,
+        ));
+
+        rank_transform(out.as_mut(), A.as_ref(), RankTies::Min);
+        assert!(out.col(0) == col![3.0, 1.0, 3.0, 2.0, 3.0]);
+
+        rank_transform(out.as_mut(), A.as_ref(), RankTies::Max);
+        assert!(out.col(0) == col![5.0, 1.0, 5.0, 2.0, 5.0]);
+
+        rank_transform(out.as_mut(), A.as_ref(), RankTies::Dense);
+        assert!(out.col(0) == col![3.0, 1.0, 3.0, 2.0, 3.0]);
+        assert!(all(
+            out.read(0, 1) == 2.0,
+            out.read(2, 1) == 3.0,
+            out.read(3, 1) == 3.0,
+            out.read(4, 1) == 1.0,
+        ));
+    }
+}
@@ -0,0 +1,264 @@
+use super::{
+    meanvar::{from_usize, var_from_sum},
+    NanHandling,
+};
+use crate::{prelude::*, ComplexField, RealField};
+use equator::assert;
+
+/// Running mean and sum of squared deviations over a sliding window.
+struct Window<E: ComplexField> {
+    count: usize,
+    nan_count: usize,
+    mean: E,
+    m2: E::Real,
+}
+
+impl<E: ComplexField> Window<E> {
+    #[inline]
+    fn new() -> Self {
+        Self {
+            count: 0,
+            nan_count: 0,
+            mean: E::faer_zero(),
+            m2: E::Real::faer_zero(),
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, x: E) {
+        if x.faer_is_nan() {
+            self.nan_count += 1;
+            return;
+        }
+        self.count += 1;
+        let delta = x.faer_sub(self.mean);
+        self.mean = self
+            .mean
+            .faer_add(delta.faer_scale_real(from_usize::<E::Real>(self.count).faer_inv()));
+        self.m2 = self.m2.faer_add(
+            delta
+                .faer_conj()
+                .faer_mul(x.faer_sub(self.mean))
+                .faer_real(),
+        );
+    }
+
+    #[inline]
+    fn pop(&mut self, x: E) {
+        if x.faer_is_nan() {
+            self.nan_count -= 1;
+            return;
+        }
+        self.count -= 1;
+        if self.count == 0 {
+            // reset the state to avoid accumulating rounding errors
+            self.mean = E::faer_zero();
+            self.m2 = E::Real::faer_zero();
+            return;
+        }
+        let delta = x.faer_sub(self.mean);
+        self.mean = self
+            .mean
+            .faer_sub(delta.faer_scale_real(from_usize::<E::Real>(self.count).faer_inv()));
+        let m2 = self.m2.faer_sub(
+            delta
+                .faer_conj()
+                .faer_mul(x.faer_sub(self.mean))
+                .faer_real(),
+        );
+        // the sum of squares can become slightly negative due to rounding errors
+        self.m2 = if m2 < E::Real::faer_zero() {
+            E::Real::faer_zero()
+        } else {
+            m2
+        };
+    }
+
+    #[inline]
+    fn is_valid(&self, min_periods: usize, nan: NanHandling) -> bool {
+        // an empty window has no mean, even when `min_periods == 0`
+        self.count > 0
+            && self.count >= min_periods
+            && (nan == NanHandling::Ignore || self.nan_count == 0)
+    }
+}
+
+fn rolling_impl<E: ComplexField>(
+    mat: MatRef<'_, E>,
+    window: usize,
+    min_periods: usize,
+    nan: NanHandling,
+    mut write: impl FnMut(usize, usize, Option<&Window<E>>),
+) {
+    let m = mat.nrows();
+    let n = mat.ncols();
+
+    for j in 0..n {
+        let mut state = Window::<E>::new();
+        for i in 0..m {
+            state.push(mat.read(i, j));
+            if i >= window {
+                state.pop(mat.read(i - window, j));
+            }
+            write(
+                i,
+                j,
+                if state.is_valid(min_periods, nan) {
+                    Some(&state)
+                } else {
+                    None
+                },
+            );
+        }
+    }
+}
+
+/// Computes the rolling mean of each column of `mat` over a trailing window of `window` rows,
+/// and stores the result in `out`.
+///
+/// The `(i, j)`-th entry of `out` is the mean of the entries of the `j`-th column of `mat` with
+/// row indices in `i + 1 - window..=i` (truncated at zero). If fewer than `min_periods` non-NaN
+/// values are in the window, if the window contains no non-NaN values, or if the window contains
+/// a NaN and `nan` is [`NanHandling::Propagate`], the result is NaN.
+///
+/// The statistics are updated incrementally, so the cost does not depend on `window`.
+///
+/// # Panics
+/// Panics if `out` and `mat` don't have the same dimensions, or if `window == 0` or `min_periods
+/// > window`.
+#[track_caller]
+pub fn rolling_mean<E: ComplexField>(
+    out: MatMut<'_, E>,
+    mat: MatRef<'_, E>,
+    window: usize,
+    min_periods: usize,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        out.ncols() == mat.ncols(),
+        window > 0,
+        min_periods <= window,
+    ));
+
+    let mut out = out;
+    rolling_impl(mat, window, min_periods, nan, |i, j, state| {
+        out.write(
+            i,
+            j,
+            match state {
+                Some(state) => state.mean,
+                None => E::faer_nan(),
+            },
+        )
+    });
+}
+
+/// Computes the rolling variance of each column of `mat` over a trailing window of `window` rows,
+/// and stores the result in `out`.
+///
+/// The window and NaN conventions are the same as for [`rolling_mean`]. The sum of squared
+/// deviations is divided by `n - 1`, where `n` is the number of non-NaN values in the window.
+///
+/// # Panics
+/// Panics if `out` and `mat` don't have the same dimensions, or if `window == 0` or `min_periods
+/// > window`.
+#[track_caller]
+pub fn rolling_var<E: ComplexField>(
+    out: MatMut<'_, E::Real>,
+    mat: MatRef<'_, E>,
+    window: usize,
+    min_periods: usize,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        out.ncols() == mat.ncols(),
+        window > 0,
+        min_periods <= window,
+    ));
+
+    let mut out = out;
+    rolling_impl(mat, window, min_periods, nan, |i, j, state| {
+        out.write(
+            i,
+            j,
+            match state {
+                Some(state) => var_from_sum(state.m2, state.count, 1),
+                None => E::Real::faer_nan(),
+            },
+        )
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_rolling() {
+        let nan = f64::NAN;
+        let m = 40;
+        let A = Mat::<f64>::from_fn(m, 2, |i, j| {
+            if j == 1 && (i == 7 || i == 8 || i == 20) {
+                nan
+            } else {
+                (i as f64 * 0.7 + j as f64).sin() * 100.0
+            }
+        });
+
+        let window = 5;
+        let min_periods = 3;
+        for nan_handling in [NanHandling::Propagate, NanHandling::Ignore] {
+            let mut mean = Mat::<f64>::zeros(m, 2);
+            let mut var = Mat::<f64>::zeros(m, 2);
+            rolling_mean(mean.as_mut(), A.as_ref(), window, min_periods, nan_handling);
+            rolling_var(var.as_mut(), A.as_ref(), window, min_periods, nan_handling);
+
+            for j in 0..2 {
+                for i in 0..m {
+                    let start = (i + 1).saturating_sub(window);
+                    let all = (start..=i).map(|k| A.read(k, j)).collect::<Vec<_>>();
+                    let vals = all
+                        .iter()
+                        .copied()
+                        .filter(|x| !x.is_nan())
+                        .collect::<Vec<_>>();
+                    let has_nan = vals.len() != all.len();
+
+                    if vals.len() < min_periods
+                        || (has_nan && nan_handling == NanHandling::Propagate)
+                    {
+                        assert!(mean.read(i, j).is_nan());
+                        assert!(var.read(i, j).is_nan());
+                    } else {
+                        let n = vals.len() as f64;
+                        let mu = vals.iter().sum::<f64>() / n;
+                        let v = vals.iter().map(|x| (x - mu).powi(2)).sum::<f64>() / (n - 1.0);
+                        assert!((mean.read(i, j) - mu).abs() < 1e-9);
+                        assert!((var.read(i, j) - v).abs() < 1e-9);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rolling_all_nan_window() {
+        let nan = f64::NAN;
+        let A = Mat::<f64>::from_fn(6, 1, |i, _| if i < 3 { nan } else { i as f64 });
+
+        let mut mean = Mat::<f64>::zeros(6, 1);
+        let mut var = Mat::<f64>::zeros(6, 1);
+        rolling_mean(mean.as_mut(), A.as_ref(), 2, 0, NanHandling::Ignore);
+        rolling_var(var.as_mut(), A.as_ref(), 2, 0, NanHandling::Ignore);
+
+        for i in 0..3 {
+            assert!(mean.read(i, 0).is_nan());
+            assert!(var.read(i, 0).is_nan());
+        }
+        assert!(mean.read(3, 0) == 3.0);
+        assert!(mean.read(5, 0) == 4.5);
+    }
+}
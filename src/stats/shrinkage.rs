@@ -0,0 +1,176 @@
+use super::{gram, meanvar::from_usize, row_mean, NanHandling};
+use crate::{prelude::*, Parallelism, RealField};
+use equator::assert;
+
+/// Specifies how the shrinkage intensity of [`shrinkage_cov`] is estimated.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ShrinkageMethod {
+    /// The Ledoit–Wolf estimator, which minimizes the asymptotic expected squared Frobenius
+    /// error of the shrunk covariance.
+    LedoitWolf,
+    /// The oracle approximating shrinkage estimator of Chen et al., which assumes Gaussian data
+    /// and usually converges faster than [`ShrinkageMethod::LedoitWolf`] for small sample sizes.
+    OracleApproximating,
+}
+
+/// Computes a shrunk estimate of the covariance matrix of the columns of `data`, stores the
+/// result in `out`, and returns the estimated shrinkage intensity.
+///
+/// Each row of `data` is a sample, and each column is a feature. The result is
+/// `(1 - s) S + s μ I`, where `S` is the maximum likelihood estimate of the covariance (with
+/// denominator `data.nrows()`), `μ` is the average of its diagonal and `s` is the shrinkage
+/// intensity, in `[0, 1]`, estimated according to `method`, using the same conventions as
+/// scikit-learn. Unlike `S`, the result is positive definite whenever `s > 0`, even when the
+/// number of samples is smaller than the number of features.
+///
+/// # Panics
+/// Panics if `data` has no rows, or if `out` is not a square matrix of dimension
+/// `data.ncols()`.
+#[track_caller]
+pub fn shrinkage_cov<E: RealField>(
+    out: MatMut<'_, E>,
+    data: MatRef<'_, E>,
+    method: ShrinkageMethod,
+    parallelism: Parallelism,
+) -> E {
+    let n = data.nrows();
+    let p = data.ncols();
+    assert!(all(n > 0, out.nrows() == p, out.ncols() == p));
+    let mut out = out;
+    if p == 0 {
+        return E::faer_zero();
+    }
+
+    let mut mean = Row::<E>::zeros(p);
+    row_mean(mean.as_mut(), data, NanHandling::Propagate, parallelism);
+    let centered = Mat::<E>::from_fn(n, p, |i, j| data.read(i, j).faer_sub(mean.read(j)));
+
+    gram(out.as_mut(), centered.as_ref(), parallelism);
+    let one_n = from_usize::<E>(n).faer_inv();
+    zipped!(out.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(one_n)));
+
+    let n_e = from_usize::<E>(n);
+    let p_e = from_usize::<E>(p);
+
+    let mut trace = E::faer_zero();
+    for j in 0..p {
+        trace = trace.faer_add(out.read(j, j));
+    }
+    let mu = trace.faer_div(p_e);
+
+    // squared frobenius norm of the empirical covariance
+    let norm2 = out.norm_l2().faer_abs2();
+
+    let shrinkage = match method {
+        ShrinkageMethod::LedoitWolf => {
+            // sum of the fourth powers of the norms of the centered samples
+            let mut beta = E::faer_zero();
+            for i in 0..n {
+                beta = beta.faer_add(centered.row(i).norm_l2().faer_abs2().faer_abs2());
+            }
+            let beta = beta
+                .faer_div(n_e)
+                .faer_sub(norm2)
+                .faer_div(p_e.faer_mul(n_e));
+
+            // squared frobenius distance of the empirical covariance to `μ I`, scaled by `1/p`
+            let delta = norm2
+                .faer_sub(
+                    mu.faer_mul(trace)
+                        .faer_scale_power_of_two(E::faer_from_f64(2.0)),
+                )
+                .faer_add(p_e.faer_mul(mu.faer_abs2()))
+                .faer_div(p_e);
+
+            let beta = if beta < delta { beta } else { delta };
+            if beta == E::faer_zero() {
+                E::faer_zero()
+            } else {
+                beta.faer_div(delta)
+            }
+        }
+        ShrinkageMethod::OracleApproximating => {
+            let alpha = norm2.faer_div(p_e.faer_abs2());
+            let mu2 = mu.faer_abs2();
+            let num = alpha.faer_add(mu2);
+            let den = n_e
+                .faer_add(E::faer_one())
+                .faer_mul(alpha.faer_sub(mu2.faer_div(p_e)));
+            if den == E::faer_zero() {
+                E::faer_one()
+            } else {
+                let s = num.faer_div(den);
+                if s < E::faer_one() {
+                    s
+                } else {
+                    E::faer_one()
+                }
+            }
+        }
+    };
+
+    let one_s = E::faer_one().faer_sub(shrinkage);
+    let s_mu = shrinkage.faer_mul(mu);
+    zipped!(out.as_mut()).for_each(|unzipped!(mut x)| x.write(x.read().faer_mul(one_s)));
+    for j in 0..p {
+        out.write(j, j, out.read(j, j).faer_add(s_mu));
+    }
+
+    shrinkage
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shrinkage_cov() {
+        let n = 20;
+        let p = 3;
+        let data = Mat::<f64>::from_fn(n, p, |i, j| {
+            let t = i as f64;
+            match j {
+                0 => t.sin(),
+                1 => t.sin() + 0.5 * (3.0 * t).cos(),
+                _ => 2.0 * t.sin() - (0.7 * t).cos(),
+            }
+        });
+
+        let mut mean = Row::<f64>::zeros(p);
+        row_mean(
+            mean.as_mut(),
+            data.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        let centered = Mat::<f64>::from_fn(n, p, |i, j| data.read(i, j) - mean.read(j));
+        let emp = centered.transpose() * &centered * crate::scale(1.0 / n as f64);
+        let mu = (emp.read(0, 0) + emp.read(1, 1) + emp.read(2, 2)) / 3.0;
+
+        let mut out = Mat::<f64>::zeros(p, p);
+        for (method, expected) in [
+            (ShrinkageMethod::LedoitWolf, 0.0863570247538476),
+            (ShrinkageMethod::OracleApproximating, 0.1694441165425719),
+        ] {
+            let s = shrinkage_cov(out.as_mut(), data.as_ref(), method, Parallelism::None);
+            assert!((s - expected).abs() < 1e-10);
+
+            let target = Mat::<f64>::from_fn(p, p, |i, j| {
+                (1.0 - s) * emp.read(i, j) + if i == j { s * mu } else { 0.0 }
+            });
+            assert!((&out - &target).norm_max() < 1e-10);
+        }
+
+        // fewer samples than features: the empirical covariance is singular, the shrunk one
+        // isn't
+        let data = data.as_ref().submatrix(0, 0, 2, 3);
+        let s = shrinkage_cov(
+            out.as_mut(),
+            data,
+            ShrinkageMethod::OracleApproximating,
+            Parallelism::None,
+        );
+        assert!(s > 0.0);
+        assert!(out.cholesky(crate::Side::Lower).is_ok());
+    }
+}
@@ -0,0 +1,185 @@
+use super::{col_max, col_sum, NanHandling};
+use crate::{prelude::*, unzipped, zipped, Parallelism, RealField};
+use equator::assert;
+use num_traits::Float;
+use reborrow::*;
+
+/// Returns `exp(x - max)`, where `max` is the maximum of the row containing `x`.
+///
+/// If `max` is infinite, `x - max` would be `inf - inf = NaN` for the entries equal to `max`, so
+/// these entries are mapped to one and the other ones to zero instead, which is the limit of the
+/// shifted exponential.
+#[inline(always)]
+fn exp_shifted<E: RealField + Float>(x: E, max: E) -> E {
+    if max.faer_is_finite() {
+        (x - max).exp()
+    } else if x == max {
+        E::faer_one()
+    } else {
+        E::faer_zero()
+    }
+}
+
+fn col_logsumexp_impl<E: RealField + Float>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let m = mat.nrows();
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    if mat.ncols() == 0 {
+        out.fill(E::neg_infinity());
+        return;
+    }
+
+    // subtracting the maximum before exponentiating avoids overflow
+    let mut max = Col::<E>::zeros(m);
+    col_max(max.as_mut(), mat, nan);
+
+    let mut sum = Col::<E>::zeros(m);
+    for j in 0..mat.ncols() {
+        zipped!(sum.as_mut(), max.as_ref(), mat.col(j)).for_each(
+            #[inline(always)]
+            |unzipped!(mut sum, max, x)| {
+                let x = x.read();
+                if !(ignore_nan && x.faer_is_nan()) {
+                    sum.write(sum.read() + exp_shifted(x, max.read()));
+                }
+            },
+        );
+    }
+
+    for i in 0..m {
+        let max = max.read(i);
+        out.write(
+            i,
+            if max.faer_is_finite() {
+                max + sum.read(i).ln()
+            } else if max.faer_is_nan() && ignore_nan {
+                // no non-NaN values
+                E::neg_infinity()
+            } else {
+                max
+            },
+        );
+    }
+}
+
+/// Computes the log-sum-exp of the columns of `mat`, and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is `log(sum(exp(mat[i, j])))`, computed without overflow by
+/// subtracting the maximum of the row before exponentiating. If no non-NaN values are available,
+/// the result is `-inf`.
+#[track_caller]
+pub fn col_logsumexp<E: RealField + Float>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_logsumexp_impl(out, mat, nan);
+}
+
+/// Computes the log-sum-exp of the rows of `mat`, and stores the result in `out`.
+///
+/// See [`col_logsumexp`] for the conventions.
+#[track_caller]
+pub fn row_logsumexp<E: RealField + Float>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_logsumexp_impl(out.transpose_mut(), mat.transpose(), nan);
+}
+
+/// Replaces each row of `mat` by its softmax, `exp(x) / sum(exp(x))`.
+///
+/// The maximum of each row is subtracted before exponentiating, so that large inputs don't
+/// overflow. If the maximum of a row is infinite, the probability mass is split evenly between the
+/// entries equal to it.
+pub fn softmax_rows_in_place<E: RealField + Float>(mat: MatMut<'_, E>) {
+    let mut mat = mat;
+    let m = mat.nrows();
+
+    let mut max = Col::<E>::zeros(m);
+    col_max(max.as_mut(), mat.rb(), NanHandling::Propagate);
+
+    for j in 0..mat.ncols() {
+        zipped!(mat.rb_mut().col_mut(j), max.as_ref()).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, max)| x.write(exp_shifted(x.read(), max.read())),
+        );
+    }
+
+    let mut sum = Col::<E>::zeros(m);
+    col_sum(
+        sum.as_mut(),
+        mat.rb(),
+        NanHandling::Propagate,
+        Parallelism::None,
+    );
+
+    zipped!(sum.as_mut()).for_each(
+        #[inline(always)]
+        |unzipped!(mut x)| x.write(x.read().recip()),
+    );
+    for j in 0..mat.ncols() {
+        zipped!(mat.rb_mut().col_mut(j), sum.as_ref()).for_each(
+            #[inline(always)]
+            |unzipped!(mut x, inv)| x.write(x.read() * inv.read()),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_softmax_logsumexp() {
+        let nan = f64::NAN;
+        let A = mat![[1.0, 2.0, 3.0], [1000.0, 1000.0, nan], [-1e3, 0.0, 1e3f64]];
+
+        let mut lse = Col::<f64>::zeros(3);
+        col_logsumexp(lse.as_mut(), A.as_ref(), NanHandling::Ignore);
+        let target0 = (1.0f64.exp() + 2.0f64.exp() + 3.0f64.exp()).ln();
+        assert!((lse[0] - target0).abs() < 1e-12);
+        assert!((lse[1] - (1000.0 + 2.0f64.ln())).abs() < 1e-10);
+        assert!((lse[2] - 1e3).abs() < 1e-10);
+
+        col_logsumexp(lse.as_mut(), A.as_ref(), NanHandling::Propagate);
+        assert!(lse[1].is_nan());
+
+        let mut B = mat![[1.0, 2.0, 3.0], [1000.0, 1000.0, 1000.0f64]];
+        softmax_rows_in_place(B.as_mut());
+        let s = 1.0f64.exp() + 2.0f64.exp() + 3.0f64.exp();
+        for j in 0..3 {
+            assert!((B[(0, j)] - ((j + 1) as f64).exp() / s).abs() < 1e-12);
+            assert!((B[(1, j)] - 1.0 / 3.0).abs() < 1e-12);
+        }
+
+        let mut lse = Row::<f64>::zeros(2);
+        row_logsumexp(
+            lse.as_mut(),
+            mat![[0.0, 0.0], [0.0, 0.0f64]].as_ref(),
+            NanHandling::Propagate,
+        );
+        assert!((lse[0] - 2.0f64.ln()).abs() < 1e-12);
+
+        // infinite maxima
+        let inf = f64::INFINITY;
+        let mut C = mat![[inf, 1.0, inf], [-inf, -inf, -inf], [-inf, 0.0, -inf]];
+        let mut lse = Col::<f64>::zeros(3);
+        col_logsumexp(lse.as_mut(), C.as_ref(), NanHandling::Propagate);
+        assert!(lse == col![inf, -inf, 0.0]);
+
+        softmax_rows_in_place(C.as_mut());
+        assert!(C.row(0) == row![0.5, 0.0, 0.5]);
+        assert!(C.row(1) == row![1.0 / 3.0, 1.0 / 3.0, 1.0 / 3.0]);
+        assert!(C.row(2) == row![0.0, 1.0, 0.0]);
+    }
+}
@@ -0,0 +1,317 @@
+use super::{
+    meanvar::{from_usize, var_from_sum},
+    NanHandling,
+};
+use crate::{prelude::*, sparse::SparseColMatRef, utils::slice::SliceGroup, ComplexField, Index};
+use equator::assert;
+use reborrow::*;
+
+// sums the stored entries of each row of `mat` in `out`, and returns the number of excluded NaN
+// entries of each row
+fn col_sum_sparse_impl<I: Index, E: ComplexField>(
+    mut out: ColMut<'_, E>,
+    mat: SparseColMatRef<'_, I, E>,
+    nan: NanHandling,
+) -> alloc::vec::Vec<usize> {
+    let mut nan_count = alloc::vec![0usize; mat.nrows()];
+    out.fill_zero();
+    for j in 0..mat.ncols() {
+        let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+        for (k, i) in mat.row_indices_of_col(j).enumerate() {
+            let x = values.read(k);
+            if nan == NanHandling::Ignore && x.faer_is_nan() {
+                nan_count[i] += 1;
+            } else {
+                out.write(i, out.read(i).faer_add(x));
+            }
+        }
+    }
+    nan_count
+}
+
+// sums the squared deviations from `mean` of the stored entries of each row of `mat`, then adds
+// the contribution of the structural zeros and divides by the denominator
+fn col_varm_sparse_impl<I: Index, E: ComplexField>(
+    mut out: ColMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    mean: ColRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+) {
+    let m = mat.nrows();
+    let n = mat.ncols();
+    let mut stored_count = alloc::vec![0usize; m];
+    let mut nan_count = alloc::vec![0usize; m];
+
+    out.fill_zero();
+    for j in 0..n {
+        let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+        for (k, i) in mat.row_indices_of_col(j).enumerate() {
+            let x = values.read(k);
+            stored_count[i] += 1;
+            if nan == NanHandling::Ignore && x.faer_is_nan() {
+                nan_count[i] += 1;
+            } else {
+                let dev = x.faer_sub(mean.read(i)).faer_abs2();
+                out.write(i, out.read(i).faer_add(dev));
+            }
+        }
+    }
+
+    for i in 0..m {
+        let zeros = from_usize::<E::Real>(n - stored_count[i]);
+        let sum = out
+            .read(i)
+            .faer_add(zeros.faer_mul(mean.read(i).faer_abs2()));
+        out.write(i, var_from_sum(sum, n - nan_count[i], ddof));
+    }
+}
+
+fn col_mean_sparse_impl<I: Index, E: ComplexField>(
+    mut out: ColMut<'_, E>,
+    mat: SparseColMatRef<'_, I, E>,
+    nan: NanHandling,
+) {
+    let n = mat.ncols();
+    let nan_count = col_sum_sparse_impl(out.rb_mut(), mat, nan);
+    for (i, nan_count) in nan_count.into_iter().enumerate() {
+        let count = from_usize::<E::Real>(n - nan_count);
+        out.write(i, out.read(i).faer_scale_real(count.faer_inv()));
+    }
+}
+
+fn row_mean_sparse_impl<I: Index, E: ComplexField>(
+    mut out: RowMut<'_, E>,
+    mat: SparseColMatRef<'_, I, E>,
+    nan: NanHandling,
+) {
+    let m = mat.nrows();
+    for j in 0..mat.ncols() {
+        let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+        let mut sum = E::faer_zero();
+        let mut count = m;
+        for x in values.into_ref_iter() {
+            let x = x.read();
+            if nan == NanHandling::Ignore && x.faer_is_nan() {
+                count -= 1;
+            } else {
+                sum = sum.faer_add(x);
+            }
+        }
+        out.write(
+            j,
+            sum.faer_scale_real(from_usize::<E::Real>(count).faer_inv()),
+        );
+    }
+}
+
+fn row_varm_sparse_impl<I: Index, E: ComplexField>(
+    mut out: RowMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    mean: RowRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+) {
+    let m = mat.nrows();
+    for j in 0..mat.ncols() {
+        let values = SliceGroup::<'_, E>::new(mat.values_of_col(j));
+        let mean = mean.read(j);
+        let mut sum = E::Real::faer_zero();
+        let mut count = m;
+        for x in values.into_ref_iter() {
+            let x = x.read();
+            if nan == NanHandling::Ignore && x.faer_is_nan() {
+                count -= 1;
+            } else {
+                sum = sum.faer_add(x.faer_sub(mean).faer_abs2());
+            }
+        }
+        let zeros = from_usize::<E::Real>(m - values.len());
+        let sum = sum.faer_add(zeros.faer_mul(mean.faer_abs2()));
+        out.write(j, var_from_sum(sum, count, ddof));
+    }
+}
+
+/// Computes the mean of the columns of the sparse matrix `mat` and stores the result in `out`.
+///
+/// The structural zeros of `mat` are included in the mean without densifying the matrix, and
+/// the NaN values among its stored entries are handled according to `nan`. The stored entries
+/// of `mat` must not contain duplicates. If no entries are included, the result is NaN.
+#[track_caller]
+pub fn col_mean_sparse<I: Index, E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: SparseColMatRef<'_, I, E>,
+    nan: NanHandling,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_mean_sparse_impl(out, mat, nan);
+}
+
+/// Computes the mean of the rows of the sparse matrix `mat` and stores the result in `out`.
+///
+/// See [`col_mean_sparse`] for the conventions.
+#[track_caller]
+pub fn row_mean_sparse<I: Index, E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: SparseColMatRef<'_, I, E>,
+    nan: NanHandling,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    row_mean_sparse_impl(out, mat, nan);
+}
+
+/// Computes the variance of the columns of the sparse matrix `mat` given their mean, and stores
+/// the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of included
+/// entries in each row. See [`col_varm_sparse_with_ddof`] for other choices of the denominator,
+/// and [`col_mean_sparse`] for the conventions.
+#[track_caller]
+pub fn col_varm_sparse<I: Index, E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    col_mean: ColRef<'_, E>,
+    nan: NanHandling,
+) {
+    col_varm_sparse_with_ddof(out, mat, col_mean, 1, nan)
+}
+
+/// Computes the variance of the rows of the sparse matrix `mat` given their mean, and stores the
+/// result in `out`.
+///
+/// The sum of squared deviations is divided by `n - 1`, where `n` is the number of included
+/// entries in each column. See [`row_varm_sparse_with_ddof`] for other choices of the
+/// denominator, and [`col_mean_sparse`] for the conventions.
+#[track_caller]
+pub fn row_varm_sparse<I: Index, E: ComplexField>(
+    out: RowMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    row_mean: RowRef<'_, E>,
+    nan: NanHandling,
+) {
+    row_varm_sparse_with_ddof(out, mat, row_mean, 1, nan)
+}
+
+/// Computes the variance of the columns of the sparse matrix `mat` given their mean, and stores
+/// the result in `out`.
+///
+/// The sum of squared deviations is divided by `n - ddof`, where `n` is the number of included
+/// entries in each row. If `n == 0`, the variance is NaN, and if `0 < n <= ddof`, the variance is
+/// zero. See [`col_mean_sparse`] for the conventions.
+#[track_caller]
+pub fn col_varm_sparse_with_ddof<I: Index, E: ComplexField>(
+    out: ColMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    col_mean: ColRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.nrows() == mat.nrows(),
+        col_mean.nrows() == mat.nrows(),
+    ));
+    col_varm_sparse_impl(out, mat, col_mean, ddof, nan);
+}
+
+/// Computes the variance of the rows of the sparse matrix `mat` given their mean, and stores the
+/// result in `out`.
+///
+/// The sum of squared deviations is divided by `n - ddof`, where `n` is the number of included
+/// entries in each column. If `n == 0`, the variance is NaN, and if `0 < n <= ddof`, the variance
+/// is zero. See [`col_mean_sparse`] for the conventions.
+#[track_caller]
+pub fn row_varm_sparse_with_ddof<I: Index, E: ComplexField>(
+    out: RowMut<'_, E::Real>,
+    mat: SparseColMatRef<'_, I, E>,
+    row_mean: RowRef<'_, E>,
+    ddof: usize,
+    nan: NanHandling,
+) {
+    assert!(all(
+        out.ncols() == mat.ncols(),
+        row_mean.ncols() == mat.ncols(),
+    ));
+    row_varm_sparse_impl(out, mat, row_mean, ddof, nan);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        complex_native::c64,
+        sparse::SparseColMat,
+        stats::{col_mean, col_varm_with_ddof, row_mean, row_varm_with_ddof},
+        Parallelism,
+    };
+    use equator::assert;
+
+    #[test]
+    fn test_sparse_meanvar() {
+        let m = 13;
+        let n = 9;
+        let mut triplets = alloc::vec::Vec::new();
+        for j in 0..n {
+            for i in 0..m {
+                if (i * 7 + j * 3) % 4 == 0 {
+                    let x = if (i + j) % 11 == 5 {
+                        c64::new(f64::NAN, 0.0)
+                    } else {
+                        c64::new((i as f64 * 0.3 + j as f64).sin(), (i as f64).cos())
+                    };
+                    triplets.push((i as u32, j as u32, x));
+                }
+            }
+        }
+        // a row and a column without stored entries
+        triplets.retain(|&(i, j, _)| i != 4 && j != 6);
+        let A = SparseColMat::<u32, c64>::try_new_from_triplets(m, n, &triplets).unwrap();
+        let dense = A.to_dense();
+
+        let approx_eq = |x: f64, y: f64| (x.is_nan() && y.is_nan()) || (x - y).abs() < 1e-12;
+        let approx_eq_c = |x: c64, y: c64| approx_eq(x.re, y.re) && approx_eq(x.im, y.im);
+
+        for nan in [NanHandling::Propagate, NanHandling::Ignore] {
+            for ddof in [0, 1] {
+                let mut mean = Col::<c64>::zeros(m);
+                let mut var = Col::<f64>::zeros(m);
+                let mut target_mean = Col::<c64>::zeros(m);
+                let mut target_var = Col::<f64>::zeros(m);
+                col_mean_sparse(mean.as_mut(), A.as_ref(), nan);
+                col_varm_sparse_with_ddof(var.as_mut(), A.as_ref(), mean.as_ref(), ddof, nan);
+                col_mean(target_mean.as_mut(), dense.as_ref(), nan, Parallelism::None);
+                col_varm_with_ddof(
+                    target_var.as_mut(),
+                    dense.as_ref(),
+                    target_mean.as_ref(),
+                    ddof,
+                    nan,
+                    Parallelism::None,
+                );
+                for i in 0..m {
+                    assert!(approx_eq_c(mean.read(i), target_mean.read(i)));
+                    assert!(approx_eq(var.read(i), target_var.read(i)));
+                }
+
+                let mut mean = Row::<c64>::zeros(n);
+                let mut var = Row::<f64>::zeros(n);
+                let mut target_mean = Row::<c64>::zeros(n);
+                let mut target_var = Row::<f64>::zeros(n);
+                row_mean_sparse(mean.as_mut(), A.as_ref(), nan);
+                row_varm_sparse_with_ddof(var.as_mut(), A.as_ref(), mean.as_ref(), ddof, nan);
+                row_mean(target_mean.as_mut(), dense.as_ref(), nan, Parallelism::None);
+                row_varm_with_ddof(
+                    target_var.as_mut(),
+                    dense.as_ref(),
+                    target_mean.as_ref(),
+                    ddof,
+                    nan,
+                    Parallelism::None,
+                );
+                for j in 0..n {
+                    assert!(approx_eq_c(mean.read(j), target_mean.read(j)));
+                    assert!(approx_eq(var.read(j), target_var.read(j)));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,422 @@
+use super::{meanvar::should_split, NanHandling};
+use crate::{
+    linalg::entity::{pulp, SimdGroupFor},
+    prelude::*,
+    utils::{
+        simd::SimdFor,
+        slice::{RefGroup, SliceGroup, SliceGroupMut},
+    },
+    ComplexField, Parallelism, RealField,
+};
+use coe::Coerce;
+use core::iter::zip;
+use equator::assert;
+use pulp::{Read, Write};
+use reborrow::*;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Reduction {
+    Sum,
+    Prod,
+}
+
+impl Reduction {
+    #[inline(always)]
+    fn identity<E: ComplexField>(self) -> E {
+        match self {
+            Reduction::Sum => E::faer_zero(),
+            Reduction::Prod => E::faer_one(),
+        }
+    }
+
+    #[inline(always)]
+    fn apply<E: ComplexField>(self, acc: E, val: E) -> E {
+        match self {
+            Reduction::Sum => acc.faer_add(val),
+            Reduction::Prod => acc.faer_mul(val),
+        }
+    }
+}
+
+fn col_sum_row_major_real<E: RealField>(out: ColMut<'_, E>, mat: MatRef<'_, E>, nan: NanHandling) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        mat: MatRef<'a, E>,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                mut out,
+                mat,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                ignore_nan: bool,
+                acc: SimdGroupFor<E, S>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+            ) -> SimdGroupFor<E, S> {
+                let zero = simd.splat(E::faer_zero());
+                let val = val.read_or(zero);
+                if ignore_nan {
+                    let is_not_nan = simd.less_than_or_equal(val, val);
+                    simd.add(acc, simd.select(is_not_nan, val, zero))
+                } else {
+                    simd.add(acc, val)
+                }
+            }
+
+            let offset = simd.align_offset_ptr(mat.as_ptr(), mat.ncols());
+            for i in 0..mat.nrows() {
+                let row = SliceGroup::<'_, E>::new(mat.row(i).try_as_slice().unwrap());
+                let (head, body, tail) = simd.as_aligned_simd(row, offset);
+
+                let mut sum0 = simd.splat(E::faer_zero());
+                let mut sum1 = simd.splat(E::faer_zero());
+                let mut sum2 = simd.splat(E::faer_zero());
+                let mut sum3 = simd.splat(E::faer_zero());
+
+                sum0 = process(simd, ignore_nan, sum0, head);
+
+                let (body4, body1) = body.as_arrays::<4>();
+                for [x0, x1, x2, x3] in body4.into_ref_iter().map(RefGroup::unzip) {
+                    sum0 = process(simd, ignore_nan, sum0, x0);
+                    sum1 = process(simd, ignore_nan, sum1, x1);
+                    sum2 = process(simd, ignore_nan, sum2, x2);
+                    sum3 = process(simd, ignore_nan, sum3, x3);
+                }
+                for x0 in body1.into_ref_iter() {
+                    sum0 = process(simd, ignore_nan, sum0, x0);
+                }
+
+                sum0 = process(simd, ignore_nan, sum0, tail);
+
+                sum0 = simd.add(sum0, sum1);
+                sum2 = simd.add(sum2, sum3);
+                sum0 = simd.add(sum0, sum2);
+
+                sum0 = simd.rotate_left(sum0, offset.rotate_left_amount());
+                out.write(i, simd.reduce_add(sum0));
+            }
+        }
+    }
+
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        ignore_nan: nan == NanHandling::Ignore,
+    });
+}
+
+fn col_reduce_col_major_real<E: RealField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    op: Reduction,
+    nan: NanHandling,
+) {
+    struct Impl<'a, E: RealField> {
+        out: ColMut<'a, E>,
+        mat: MatRef<'a, E>,
+        op: Reduction,
+        ignore_nan: bool,
+    }
+
+    impl<E: RealField> pulp::WithSimd for Impl<'_, E> {
+        type Output = ();
+
+        #[inline(always)]
+        fn with_simd<S: pulp::Simd>(self, simd: S) -> Self::Output {
+            let Self {
+                out,
+                mat,
+                op,
+                ignore_nan,
+            } = self;
+            let simd = SimdFor::<E, S>::new(simd);
+
+            let offset = simd.align_offset_ptr(mat.as_ptr(), mat.nrows());
+            let mut out = SliceGroupMut::<'_, E>::new(out.try_as_slice_mut().unwrap());
+
+            #[inline(always)]
+            fn process<E: RealField, S: pulp::Simd>(
+                simd: SimdFor<E, S>,
+                op: Reduction,
+                ignore_nan: bool,
+                mut out: impl Write<Output = SimdGroupFor<E, S>>,
+                val: impl Read<Output = SimdGroupFor<E, S>>,
+            ) {
+                let identity = simd.splat(op.identity::<E>());
+                let acc = out.read_or(identity);
+                let val = val.read_or(identity);
+                let val = if ignore_nan {
+                    let is_not_nan = simd.less_than_or_equal(val, val);
+                    simd.select(is_not_nan, val, identity)
+                } else {
+                    val
+                };
+                out.write(match op {
+                    Reduction::Sum => simd.add(acc, val),
+                    Reduction::Prod => simd.mul(acc, val),
+                });
+            }
+
+            for j in 0..mat.ncols() {
+                let col = SliceGroup::<'_, E>::new(mat.col(j).try_as_slice().unwrap());
+                let (head, body, tail) = simd.as_aligned_simd(col, offset);
+                let (out_head, out_body, out_tail) = simd.as_aligned_simd_mut(out.rb_mut(), offset);
+
+                process(simd, op, ignore_nan, out_head, head);
+                for (out, x) in zip(out_body.into_mut_iter(), body.into_ref_iter()) {
+                    process(simd, op, ignore_nan, out, x);
+                }
+                process(simd, op, ignore_nan, out_tail, tail);
+            }
+        }
+    }
+
+    let mut out = out;
+    out.fill(op.identity());
+    E::Simd::default().dispatch(Impl {
+        out,
+        mat,
+        op,
+        ignore_nan: nan == NanHandling::Ignore,
+    });
+}
+
+fn col_reduce_fallback<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    op: Reduction,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let ignore_nan = nan == NanHandling::Ignore;
+
+    out.fill(op.identity());
+    for j in 0..mat.ncols() {
+        for i in 0..mat.nrows() {
+            let val = mat.read(i, j);
+            if ignore_nan && val.faer_is_nan() {
+                continue;
+            }
+            out.write(i, op.apply(out.read(i), val));
+        }
+    }
+}
+
+fn col_reduce<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    op: Reduction,
+    nan: NanHandling,
+) {
+    let mut out = out;
+    let mut mat = mat;
+
+    if mat.col_stride() < 0 {
+        mat = mat.reverse_cols();
+    }
+    if mat.row_stride() < 0 {
+        mat = mat.reverse_rows();
+        out = out.reverse_rows_mut();
+    }
+
+    if coe::is_same::<E, E::Real>() {
+        if mat.col_stride() == 1 && op == Reduction::Sum {
+            col_sum_row_major_real::<E::Real>(out.coerce(), mat.coerce(), nan)
+        } else if mat.row_stride() == 1 && out.row_stride() == 1 {
+            col_reduce_col_major_real::<E::Real>(out.coerce(), mat.coerce(), op, nan)
+        } else {
+            // there is no horizontal product reduction, so row-major products are computed
+            // one entry at a time
+            col_reduce_fallback(out, mat, op, nan)
+        }
+    } else {
+        col_reduce_fallback(out, mat, op, nan)
+    }
+}
+
+fn col_reduce_par<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    op: Reduction,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    let m = mat.nrows();
+    if !should_split(m, parallelism) {
+        col_reduce(out, mat, op, nan);
+        return;
+    }
+
+    let (out_top, out_bot) = out.split_at_mut(m / 2);
+    let (mat_top, mat_bot) = mat.split_at_row(m / 2);
+    crate::utils::thread::join_raw(
+        |parallelism| col_reduce_par(out_top, mat_top, op, nan, parallelism),
+        |parallelism| col_reduce_par(out_bot, mat_bot, op, nan, parallelism),
+        parallelism,
+    );
+}
+
+/// Computes the sum of the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the sum of the `i`-th row of `mat`. With [`NanHandling::Ignore`],
+/// NaN values are skipped, so that the sum of a row with no non-NaN values is zero.
+/// The rows of `mat` are split between threads according to `parallelism`.
+#[track_caller]
+pub fn col_sum<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_reduce_par(out, mat, Reduction::Sum, nan, parallelism);
+}
+
+/// Computes the sum of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the sum of the `j`-th column of `mat`. With
+/// [`NanHandling::Ignore`], NaN values are skipped, so that the sum of a column with no non-NaN
+/// values is zero.
+/// The columns of `mat` are split between threads according to `parallelism`.
+#[track_caller]
+pub fn row_sum<E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_reduce_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        Reduction::Sum,
+        nan,
+        parallelism,
+    );
+}
+
+/// Computes the product of the columns of `mat` and stores the result in `out`.
+///
+/// The `i`-th entry of `out` is the product of the `i`-th row of `mat`. With
+/// [`NanHandling::Ignore`], NaN values are skipped, so that the product of a row with no non-NaN
+/// values is one.
+/// The rows of `mat` are split between threads according to `parallelism`.
+#[track_caller]
+pub fn col_prod<E: ComplexField>(
+    out: ColMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.nrows() == mat.nrows()));
+    col_reduce_par(out, mat, Reduction::Prod, nan, parallelism);
+}
+
+/// Computes the product of the rows of `mat` and stores the result in `out`.
+///
+/// The `j`-th entry of `out` is the product of the `j`-th column of `mat`. With
+/// [`NanHandling::Ignore`], NaN values are skipped, so that the product of a column with no
+/// non-NaN values is one.
+/// The columns of `mat` are split between threads according to `parallelism`.
+#[track_caller]
+pub fn row_prod<E: ComplexField>(
+    out: RowMut<'_, E>,
+    mat: MatRef<'_, E>,
+    nan: NanHandling,
+    parallelism: Parallelism,
+) {
+    assert!(all(out.ncols() == mat.ncols()));
+    col_reduce_par(
+        out.transpose_mut(),
+        mat.transpose(),
+        Reduction::Prod,
+        nan,
+        parallelism,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_sum_prod() {
+        let nan = f64::NAN;
+        let m = 23;
+        let n = 41;
+        let A = Mat::<f64>::from_fn(m, n, |i, j| {
+            if (i * 3 + j) % 17 == 0 {
+                nan
+            } else {
+                1.0 + ((i + 2 * j) % 5) as f64 / 64.0
+            }
+        });
+        let A_row_major = A.transpose().to_owned();
+        let A_row_major = A_row_major.transpose();
+
+        for nan_handling in [NanHandling::Propagate, NanHandling::Ignore] {
+            for A in [A.as_ref(), A_row_major] {
+                let mut sum = Col::<f64>::zeros(m);
+                let mut prod = Col::<f64>::zeros(m);
+                col_sum(sum.as_mut(), A, nan_handling, Parallelism::None);
+                col_prod(prod.as_mut(), A, nan_handling, Parallelism::None);
+
+                for i in 0..m {
+                    let row = (0..n).map(|j| A.read(i, j));
+                    if nan_handling == NanHandling::Propagate && row.clone().any(|x| x.is_nan()) {
+                        assert!(sum[i].is_nan());
+                        assert!(prod[i].is_nan());
+                    } else {
+                        let row = row.filter(|x| !x.is_nan());
+                        let target_sum: f64 = row.clone().sum();
+                        let target_prod: f64 = row.product();
+                        assert!((sum[i] - target_sum).abs() < 1e-12);
+                        assert!((prod[i] - target_prod).abs() < 1e-12 * target_prod);
+                    }
+                }
+            }
+        }
+
+        let mut sum = Row::<f64>::zeros(n);
+        row_sum(
+            sum.as_mut(),
+            A.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        for j in 0..n {
+            let target: f64 = (0..m).map(|i| A.read(i, j)).filter(|x| !x.is_nan()).sum();
+            assert!((sum[j] - target).abs() < 1e-12);
+        }
+
+        let B = mat![[c64::new(1.0, 1.0), c64::new(nan, 0.0), c64::new(0.0, 2.0)]];
+        let mut prod = Col::<c64>::zeros(1);
+        col_prod(
+            prod.as_mut(),
+            B.as_ref(),
+            NanHandling::Ignore,
+            Parallelism::None,
+        );
+        assert!(prod[0] == c64::new(-2.0, 2.0));
+        let mut sum = Row::<c64>::zeros(3);
+        row_sum(
+            sum.as_mut(),
+            B.as_ref(),
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+        assert!(sum[1].re.is_nan());
+    }
+}
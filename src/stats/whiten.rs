@@ -0,0 +1,184 @@
+use super::{meanvar::from_usize, row_mean, NanHandling};
+use crate::{prelude::*, ComplexField, Parallelism, RealField, Side};
+use equator::assert;
+
+/// Specifies the rotation applied by a [`Whitener`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum WhiteningKind {
+    /// The data is projected onto the principal axes of the covariance, then rescaled.
+    Pca,
+    /// The data is rescaled along the principal axes of the covariance, then rotated back to the
+    /// original axes, which keeps the whitened data as close as possible to the input.
+    Zca,
+}
+
+/// Whitening transform, mapping a data matrix to one with identity covariance.
+///
+/// Each row of the data matrix is a sample, and each column is a feature. The transform is
+/// computed from the eigendecomposition `C = V Λ Vᴴ` of the sample covariance of the data used
+/// for fitting, with each eigenvalue regularized by `epsilon` to avoid dividing by zero for
+/// rank-deficient data.
+#[derive(Clone, Debug)]
+pub struct Whitener<E: ComplexField> {
+    mean: Row<E>,
+    whitening: Mat<E>,
+    dewhitening: Mat<E>,
+}
+
+impl<E: ComplexField> Whitener<E> {
+    /// Fits the whitening transform to `data`.
+    ///
+    /// # Panics
+    /// Panics if `data` has fewer than two rows, or if `epsilon` is negative.
+    #[track_caller]
+    pub fn fit(data: MatRef<'_, E>, kind: WhiteningKind, epsilon: E::Real) -> Self {
+        assert!(all(data.nrows() >= 2, epsilon >= E::Real::faer_zero()));
+
+        let n = data.nrows();
+        let p = data.ncols();
+
+        let mut mean = Row::<E>::zeros(p);
+        row_mean(
+            mean.as_mut(),
+            data,
+            NanHandling::Propagate,
+            Parallelism::None,
+        );
+
+        let centered = Mat::<E>::from_fn(n, p, |i, j| data.read(i, j).faer_sub(mean.read(j)));
+        let mut cov = centered.adjoint() * &centered;
+        let one_n1 = from_usize::<E::Real>(n - 1).faer_inv();
+        zipped!(cov.as_mut())
+            .for_each(|unzipped!(mut x)| x.write(x.read().faer_scale_real(one_n1)));
+
+        let evd = cov.selfadjoint_eigendecomposition(Side::Lower);
+        let u = evd.u();
+        let s = evd.s().column_vector();
+
+        let mut scale = Col::<E::Real>::zeros(p);
+        let mut inv_scale = Col::<E::Real>::zeros(p);
+        for k in 0..p {
+            let mut eigval = s.read(k).faer_real();
+            // roundoff can make the eigenvalues of a semidefinite matrix slightly negative
+            if eigval < E::Real::faer_zero() {
+                eigval = E::Real::faer_zero();
+            }
+            let sqrt = eigval.faer_add(epsilon).faer_sqrt();
+            scale.write(k, sqrt.faer_inv());
+            inv_scale.write(k, sqrt);
+        }
+
+        // the transforms act on row vectors, so the whitened data is `(x - mean) * whitening`
+        let u_scale = Mat::<E>::from_fn(p, p, |i, k| u.read(i, k).faer_scale_real(scale.read(k)));
+        let inv_scale_ut = Mat::<E>::from_fn(p, p, |k, j| {
+            u.read(j, k).faer_conj().faer_scale_real(inv_scale.read(k))
+        });
+
+        let (whitening, dewhitening) = match kind {
+            WhiteningKind::Pca => (u_scale, inv_scale_ut),
+            WhiteningKind::Zca => (&u_scale * u.adjoint(), u * &inv_scale_ut),
+        };
+
+        Self {
+            mean,
+            whitening,
+            dewhitening,
+        }
+    }
+
+    /// Returns the number of features.
+    #[inline]
+    pub fn ncols(&self) -> usize {
+        self.mean.ncols()
+    }
+
+    /// Returns the mean of the data used for fitting.
+    #[inline]
+    pub fn mean(&self) -> RowRef<'_, E> {
+        self.mean.as_ref()
+    }
+
+    /// Returns the matrix `W` such that the whitened data is `(data - mean) * W`.
+    #[inline]
+    pub fn whitening_matrix(&self) -> MatRef<'_, E> {
+        self.whitening.as_ref()
+    }
+
+    /// Returns the matrix `W⁻¹` such that the original data is `whitened * W⁻¹ + mean`.
+    #[inline]
+    pub fn dewhitening_matrix(&self) -> MatRef<'_, E> {
+        self.dewhitening.as_ref()
+    }
+
+    /// Applies the whitening transform to the rows of `data`.
+    ///
+    /// # Panics
+    /// Panics if `data.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn transform(&self, data: MatRef<'_, E>) -> Mat<E> {
+        assert!(all(data.ncols() == self.ncols()));
+        let centered = Mat::<E>::from_fn(data.nrows(), data.ncols(), |i, j| {
+            data.read(i, j).faer_sub(self.mean.read(j))
+        });
+        &centered * &self.whitening
+    }
+
+    /// Applies the inverse of the whitening transform to the rows of `data`.
+    ///
+    /// # Panics
+    /// Panics if `data.ncols() != self.ncols()`.
+    #[track_caller]
+    pub fn inverse_transform(&self, data: MatRef<'_, E>) -> Mat<E> {
+        assert!(all(data.ncols() == self.ncols()));
+        let mut out = data * &self.dewhitening;
+        for j in 0..out.ncols() {
+            let mean = self.mean.read(j);
+            for i in 0..out.nrows() {
+                out.write(i, j, out.read(i, j).faer_add(mean));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use equator::assert;
+
+    #[test]
+    fn test_whitener() {
+        let n = 40;
+        let p = 3;
+        let data = Mat::<f64>::from_fn(n, p, |i, j| {
+            let t = i as f64;
+            (t * 0.7).sin() + (j as f64 + 1.0) * (t * 0.3 + j as f64).cos() + 0.1 * t * j as f64
+        });
+
+        for kind in [WhiteningKind::Pca, WhiteningKind::Zca] {
+            let whitener = Whitener::fit(data.as_ref(), kind, 0.0);
+            let white = whitener.transform(data.as_ref());
+
+            let mut mean = Row::<f64>::zeros(p);
+            row_mean(
+                mean.as_mut(),
+                white.as_ref(),
+                NanHandling::Propagate,
+                Parallelism::None,
+            );
+            assert!(mean.norm_max() < 1e-10);
+
+            let cov = white.transpose() * &white;
+            let cov = Mat::<f64>::from_fn(p, p, |i, j| cov.read(i, j) / (n - 1) as f64);
+            assert!((&cov - Mat::<f64>::identity(p, p)).norm_max() < 1e-10);
+
+            let back = whitener.inverse_transform(white.as_ref());
+            assert!((&back - &data).norm_max() < 1e-10);
+
+            if kind == WhiteningKind::Zca {
+                let w = whitener.whitening_matrix();
+                assert!((w - w.transpose()).norm_max() < 1e-10);
+            }
+        }
+    }
+}